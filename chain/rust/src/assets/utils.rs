@@ -16,6 +16,7 @@ use std::{
 use std::collections::BTreeMap;
 
 use super::AssetName;
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 
 pub type Coin = u64;
 
@@ -296,6 +297,18 @@ where
     }
 }
 
+impl<T> CBORFieldNames for AssetBundle<T> {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::entries(
+            ("policy_id", FieldNames::None),
+            (
+                "",
+                FieldNames::entries(("asset_name", FieldNames::None), ("", FieldNames::None)),
+            ),
+        )
+    }
+}
+
 pub type Mint = AssetBundle<NonZeroInt64>;
 
 pub type MultiAsset = AssetBundle<PositiveCoin>;
@@ -347,6 +360,8 @@ pub struct Value {
     pub encodings: Option<ValueEncoding>,
 }
 
+cml_core::impl_cbor_field_names!(Value [coin, multiasset: MultiAsset::cbor_field_names()]);
+
 impl Value {
     pub fn new(coin: PositiveCoin, multiasset: MultiAsset) -> Self {
         Self {
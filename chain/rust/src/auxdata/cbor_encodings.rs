@@ -2,6 +2,7 @@
 // https://github.com/dcSpark/cddl-codegen
 
 use cml_core::serialization::LenEncoding;
+use cml_core::UnknownFields;
 
 #[derive(Clone, Debug, Default)]
 pub struct ConwayFormatAuxDataEncoding {
@@ -17,6 +18,7 @@ pub struct ConwayFormatAuxDataEncoding {
     pub plutus_v2_scripts_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v3_scripts_encoding: LenEncoding,
     pub plutus_v3_scripts_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -5,7 +5,7 @@ use cbor_event::{de::Deserializer, se::Serializer};
 use cml_core::{
     error::{DeserializeError, DeserializeFailure},
     serialization::{fit_sz, Deserialize, LenEncoding, Serialize, StringEncoding},
-    Int,
+    CborValue, Int,
 };
 use derivative::Derivative;
 
@@ -252,6 +252,8 @@ impl Serialize for MetadatumMap {
 
 impl Deserialize for MetadatumMap {
     fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        // duplicate keys are valid on chain
+        cml_core::canonical::allow_duplicate_keys();
         let mut entries = Vec::new();
         let entries_len = raw.map_sz()?;
         let entries_encoding = entries_len.into();
@@ -574,6 +576,21 @@ pub fn decode_arbitrary_bytes_from_metadatum(metadata: &TransactionMetadatum) ->
     Some(bytes)
 }
 
+impl From<&TransactionMetadatum> for CborValue {
+    fn from(metadatum: &TransactionMetadatum) -> Self {
+        CborValue::from_cbor_type(metadatum)
+    }
+}
+
+/// Fails if the CBOR is not valid metadata e.g. contains tags, floats or strings over 64 bytes
+impl std::convert::TryFrom<&CborValue> for TransactionMetadatum {
+    type Error = DeserializeError;
+
+    fn try_from(value: &CborValue) -> Result<Self, Self::Error> {
+        value.to_cbor_type()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,6 +617,19 @@ mod tests {
         assert_eq!(input_bytes, output_bytes);
     }
 
+    #[test]
+    fn metadatum_cbor_value() {
+        use std::convert::TryFrom;
+        // indefinite list with a non-minimal int
+        let bytes = hex::decode("9f1800426869ff").unwrap();
+        let metadatum = TransactionMetadatum::from_cbor_bytes(&bytes).unwrap();
+        let value = CborValue::from(&metadatum);
+        assert_eq!(value.to_cbor_bytes(), bytes);
+        let back = TransactionMetadatum::try_from(&value).unwrap();
+        assert_eq!(back.to_cbor_bytes(), bytes);
+        assert!(TransactionMetadatum::try_from(&CborValue::new_float(1.5)).is_err());
+    }
+
     #[test]
     fn metadatum_default_json() {
         let json_str = "{\"map\":[{\"k\":{\"list\":[{\"map\":[{\"k\":{\"int\":5},\"v\":{\"int\":-7}},{\"k\":{\"string\":\"hello\"},\"v\":{\"string\":\"world\"}}]},{\"bytes\":\"ff00ff00\"}]},\"v\":{\"int\":5}}]}";
@@ -9,6 +9,8 @@ use cbor_event::de::Deserializer;
 use cbor_event::se::Serializer;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::{CborValue, Int};
 use std::io::{BufRead, Seek, SeekFrom, Write};
 
 impl Serialize for AuxiliaryData {
@@ -77,6 +79,26 @@ impl Serialize for ConwayFormatAuxData {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ConwayFormatAuxData {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_tag_sz(
             259u64,
@@ -110,7 +132,7 @@ impl Serialize for ConwayFormatAuxData {
                     } + match &self.plutus_v3_scripts {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -139,7 +161,9 @@ impl Serialize for ConwayFormatAuxData {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.metadata {
@@ -280,6 +304,8 @@ impl Serialize for ConwayFormatAuxData {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -305,6 +331,7 @@ impl Deserialize for ConwayFormatAuxData {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut metadata_key_encoding = None;
             let mut metadata = None;
             let mut native_scripts_encoding = LenEncoding::default();
@@ -326,150 +353,150 @@ impl Deserialize for ConwayFormatAuxData {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if metadata.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let tmp_metadata = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Metadata::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("metadata"))?;
-                            metadata = Some(tmp_metadata);
-                            metadata_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if native_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_native_scripts, tmp_native_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if metadata.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let tmp_metadata = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    let mut native_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let native_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (native_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        native_scripts_arr.push(NativeScript::deserialize(raw)?);
-                                    }
-                                    Ok((native_scripts_arr, native_scripts_encoding))
+                                    Metadata::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("native_scripts"))?;
-                            native_scripts = Some(tmp_native_scripts);
-                            native_scripts_encoding = tmp_native_scripts_encoding;
-                            native_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if plutus_v1_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_scripts_arr
-                                            .push(PlutusV1Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_scripts_arr, plutus_v1_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1_scripts"))?;
-                            plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
-                            plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
-                            plutus_v1_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if plutus_v2_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v2_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_scripts_arr
-                                            .push(PlutusV2Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_scripts_arr, plutus_v2_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v2_scripts"))?;
-                            plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
-                            plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
-                            plutus_v2_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (4, key_enc) => {
-                            if plutus_v3_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_plutus_v3_scripts, tmp_plutus_v3_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v3_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v3_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v3_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v3_scripts_arr
-                                            .push(PlutusV3Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v3_scripts_arr, plutus_v3_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v3_scripts"))?;
-                            plutus_v3_scripts = Some(tmp_plutus_v3_scripts);
-                            plutus_v3_scripts_encoding = tmp_plutus_v3_scripts_encoding;
-                            plutus_v3_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                .map_err(|e| e.annotate("metadata"))?;
+                                metadata = Some(tmp_metadata);
+                                metadata_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if native_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_native_scripts, tmp_native_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, NativeScript::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("native_scripts"))?;
+                                native_scripts = Some(tmp_native_scripts);
+                                native_scripts_encoding = tmp_native_scripts_encoding;
+                                native_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV1Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1_scripts"))?;
+                                plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
+                                plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
+                                plutus_v1_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV2Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2_scripts"))?;
+                                plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
+                                plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
+                                plutus_v2_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v3_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v3_scripts, tmp_plutus_v3_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV3Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v3_scripts"))?;
+                                plutus_v3_scripts = Some(tmp_plutus_v3_scripts);
+                                plutus_v3_scripts_encoding = tmp_plutus_v3_scripts_encoding;
+                                plutus_v3_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -506,6 +533,7 @@ impl Deserialize for ConwayFormatAuxData {
                     plutus_v2_scripts_encoding,
                     plutus_v3_scripts_key_encoding,
                     plutus_v3_scripts_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -6,6 +6,37 @@ use crate::{
 };
 
 use super::{AuxiliaryData, ConwayFormatAuxData, ShelleyMaFormatAuxData};
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
+
+cml_core::impl_unknown_fields!(ConwayFormatAuxData);
+
+impl CBORFieldNames for Metadata {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::entries(("label", FieldNames::None), ("", FieldNames::None))
+    }
+}
+
+impl CBORFieldNames for AuxiliaryData {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            Metadata::cbor_field_names(),
+            ShelleyMaFormatAuxData::cbor_field_names(),
+            ConwayFormatAuxData::cbor_field_names(),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(ShelleyMaFormatAuxData [
+    transaction_metadata: Metadata::cbor_field_names(),
+    auxiliary_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+]);
+cml_core::impl_cbor_field_names!(ConwayFormatAuxData tag 259 {
+    0 => metadata: Metadata::cbor_field_names(),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => plutus_v1_scripts,
+    3 => plutus_v2_scripts,
+    4 => plutus_v3_scripts,
+});
 
 impl AuxiliaryData {
     pub fn new() -> Self {
@@ -3,6 +3,7 @@
 
 pub mod cbor_encodings;
 pub mod serialization;
+pub mod utils;
 
 use super::TransactionIndex;
 use crate::auxdata::AuxiliaryData;
@@ -0,0 +1,31 @@
+use super::{Block, Header, HeaderBody, OperationalCert, ProtocolVersion};
+use crate::auxdata::AuxiliaryData;
+use crate::crypto::VRFCert;
+use crate::transaction::{TransactionBody, TransactionWitnessSet};
+use cml_core::diagnostic::FieldNames;
+
+cml_core::impl_cbor_field_names!(Block [
+    header: Header::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(TransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(TransactionWitnessSet::cbor_field_names()),
+    auxiliary_data_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", AuxiliaryData::cbor_field_names()),
+    ),
+    invalid_transactions,
+]);
+cml_core::impl_cbor_field_names!(Header [header_body: HeaderBody::cbor_field_names(), body_signature]);
+cml_core::impl_cbor_field_names!(HeaderBody [
+    block_number,
+    slot,
+    prev_hash,
+    issuer_vkey,
+    vrf_vkey,
+    vrf_result: VRFCert::cbor_field_names(),
+    block_body_size,
+    block_body_hash,
+    operational_cert: OperationalCert::cbor_field_names(),
+    protocol_version: ProtocolVersion::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(OperationalCert [hot_vkey, sequence_number, kes_period, sigma]);
+cml_core::impl_cbor_field_names!(ProtocolVersion [major, minor]);
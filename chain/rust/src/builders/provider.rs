@@ -0,0 +1,499 @@
+//! Chain access for the `TransactionBuilder` so that `TransactionBuilder::complete()` can do
+//! coin selection, collateral, ExUnits and change without every dApp writing the same glue.
+//!
+//! Implement `Provider` for your backend (node, indexer, wallet, etc). `InMemoryProvider` is a
+//! deterministic implementation for tests.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use crate::address::Address;
+use crate::fees::LinearFee;
+use crate::plutus::{CostModels, ExUnitPrices, ExUnits};
+use crate::transaction::{Transaction, TransactionInput, TransactionOutput};
+use crate::Coin;
+use cml_crypto::TransactionHash;
+
+use super::redeemer_builder::RedeemerWitnessKey;
+use super::tx_builder::{TransactionBuilderConfigBuilder, TransactionUnspentOutput};
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Provider: {0}")]
+pub struct ProviderError(pub String);
+
+impl ProviderError {
+    pub fn new(msg: impl Into<String>) -> Self {
+        Self(msg.into())
+    }
+}
+
+/// The protocol parameters the `TransactionBuilder` needs, as returned by a `Provider`
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct ProtocolParameters {
+    /// a i.e. lovelace per byte of the tx
+    pub min_fee_coefficient: Coin,
+    /// b i.e. lovelace per tx
+    pub min_fee_constant: Coin,
+    pub pool_deposit: Coin,
+    pub key_deposit: Coin,
+    pub max_value_size: u32,
+    pub max_tx_size: u32,
+    pub coins_per_utxo_byte: Coin,
+    pub ex_unit_prices: ExUnitPrices,
+    pub cost_models: CostModels,
+    pub collateral_percentage: u32,
+    pub max_collateral_inputs: u32,
+}
+
+impl ProtocolParameters {
+    /// Config builder with all protocol parameters set. Non-protocol options
+    /// (e.g. prefer_pure_change) can still be set on it before building.
+    pub fn to_config_builder(&self) -> TransactionBuilderConfigBuilder {
+        TransactionBuilderConfigBuilder::new()
+            .fee_algo(LinearFee::new(
+                self.min_fee_coefficient,
+                self.min_fee_constant,
+            ))
+            .pool_deposit(self.pool_deposit)
+            .key_deposit(self.key_deposit)
+            .max_value_size(self.max_value_size)
+            .max_tx_size(self.max_tx_size)
+            .coins_per_utxo_byte(self.coins_per_utxo_byte)
+            .ex_unit_prices(self.ex_unit_prices.clone())
+            .cost_models(self.cost_models.clone())
+            .collateral_percentage(self.collateral_percentage)
+            .max_collateral_inputs(self.max_collateral_inputs)
+    }
+}
+
+/// Source of chain data for building transactions.
+/// None of these need to be Send so that JS promises can be used on WASM.
+pub trait Provider {
+    /// All UTxOs currently locked at `address`
+    fn utxos_at_address(
+        &self,
+        address: &Address,
+    ) -> impl Future<Output = Result<Vec<TransactionUnspentOutput>, ProviderError>>;
+
+    /// Current protocol parameters
+    fn protocol_params(&self) -> impl Future<Output = Result<ProtocolParameters, ProviderError>>;
+
+    /// ExUnits needed by each redeemer of `tx` (which will have dummy ExUnits).
+    /// `additional_utxos` are the UTxOs spent/referenced by `tx` in case they aren't on-chain yet.
+    fn evaluate_tx(
+        &self,
+        tx: &Transaction,
+        additional_utxos: &[TransactionUnspentOutput],
+    ) -> impl Future<Output = Result<Vec<(RedeemerWitnessKey, ExUnits)>, ProviderError>>;
+
+    fn submit_tx(
+        &self,
+        tx: &Transaction,
+    ) -> impl Future<Output = Result<TransactionHash, ProviderError>>;
+}
+
+/// Deterministic `Provider` backed by an in-memory UTxO set, for tests.
+/// UTxOs are always returned sorted by input, every redeemer is evaluated to the same ExUnits
+/// and submitting only checks that the inputs exist (no signatures, scripts or balance) before
+/// spending them and adding the outputs to the UTxO set.
+#[derive(Clone, Debug)]
+pub struct InMemoryProvider {
+    params: ProtocolParameters,
+    ex_units: ExUnits,
+    utxos: RefCell<BTreeMap<TransactionInput, TransactionOutput>>,
+    submitted: RefCell<Vec<Transaction>>,
+}
+
+impl InMemoryProvider {
+    pub fn new(params: ProtocolParameters, ex_units: ExUnits) -> Self {
+        Self {
+            params,
+            ex_units,
+            utxos: RefCell::new(BTreeMap::new()),
+            submitted: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn add_utxo(&self, utxo: TransactionUnspentOutput) {
+        self.utxos.borrow_mut().insert(utxo.input, utxo.output);
+    }
+
+    pub fn utxos(&self) -> Vec<TransactionUnspentOutput> {
+        self.utxos
+            .borrow()
+            .iter()
+            .map(|(input, output)| TransactionUnspentOutput::new(input.clone(), output.clone()))
+            .collect()
+    }
+
+    /// All transactions successfully submitted so far, in order
+    pub fn submitted(&self) -> Vec<Transaction> {
+        self.submitted.borrow().clone()
+    }
+}
+
+impl Provider for InMemoryProvider {
+    async fn utxos_at_address(
+        &self,
+        address: &Address,
+    ) -> Result<Vec<TransactionUnspentOutput>, ProviderError> {
+        Ok(self
+            .utxos()
+            .into_iter()
+            .filter(|utxo| utxo.output.address() == address)
+            .collect())
+    }
+
+    async fn protocol_params(&self) -> Result<ProtocolParameters, ProviderError> {
+        Ok(self.params.clone())
+    }
+
+    async fn evaluate_tx(
+        &self,
+        tx: &Transaction,
+        additional_utxos: &[TransactionUnspentOutput],
+    ) -> Result<Vec<(RedeemerWitnessKey, ExUnits)>, ProviderError> {
+        let utxos = self.utxos.borrow();
+        let known = |input: &TransactionInput| {
+            utxos.contains_key(input) || additional_utxos.iter().any(|utxo| utxo.input == *input)
+        };
+        if let Some(input) = tx.body.inputs.iter().find(|input| !known(input)) {
+            return Err(ProviderError::new(format!("Unknown input {input:?}")));
+        }
+        Ok(tx
+            .witness_set
+            .redeemers
+            .iter()
+            .flatten()
+            .map(|redeemer| {
+                (
+                    RedeemerWitnessKey::new(redeemer.tag, redeemer.index),
+                    self.ex_units.clone(),
+                )
+            })
+            .collect())
+    }
+
+    async fn submit_tx(&self, tx: &Transaction) -> Result<TransactionHash, ProviderError> {
+        let mut utxos = self.utxos.borrow_mut();
+        if let Some(input) = tx
+            .body
+            .inputs
+            .iter()
+            .find(|input| !utxos.contains_key(input))
+        {
+            return Err(ProviderError::new(format!(
+                "Input {input:?} is unknown or already spent"
+            )));
+        }
+        let hash = tx.body.hash();
+        for input in tx.body.inputs.iter() {
+            utxos.remove(input);
+        }
+        for (index, output) in tx.body.outputs.iter().enumerate() {
+            utxos.insert(TransactionInput::new(hash, index as u64), output.clone());
+        }
+        self.submitted.borrow_mut().push(tx.clone());
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    use cml_crypto::Ed25519KeyHash;
+
+    use super::*;
+    use crate::address::EnterpriseAddress;
+    use crate::builders::input_builder::SingleInputBuilder;
+    use crate::builders::output_builder::TransactionOutputBuilder;
+    use crate::builders::redeemer_builder::RedeemerWitnessKey;
+    use crate::builders::tx_builder::{
+        ChangeSelectionAlgo, CoinSelectionStrategyCIP2, SignedTxBuilder, TransactionBuilder,
+        TxBuilderError,
+    };
+    use crate::builders::witness_builder::{PartialPlutusWitness, PlutusScriptWitness};
+    use crate::certs::StakeCredential;
+    use crate::genesis::network_info::plutus_alonzo_cost_models;
+    use crate::plutus::{PlutusData, PlutusScript, PlutusV1Script, RedeemerTag};
+    use crate::{SubCoin, Value};
+
+    // Waker::noop() needs a newer rustc than the async fns in traits used here
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(clone(std::ptr::null())) }
+    }
+
+    // InMemoryProvider never actually awaits anything so one poll is enough
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(&noop_waker()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future not ready"),
+        }
+    }
+
+    fn params() -> ProtocolParameters {
+        ProtocolParameters {
+            min_fee_coefficient: 44,
+            min_fee_constant: 155381,
+            pool_deposit: 500000000,
+            key_deposit: 2000000,
+            max_value_size: 5000,
+            max_tx_size: 16384,
+            coins_per_utxo_byte: 4310,
+            ex_unit_prices: ExUnitPrices::new(
+                SubCoin::new(577, 10000),
+                SubCoin::new(721, 10000000),
+            ),
+            cost_models: plutus_alonzo_cost_models(),
+            collateral_percentage: 150,
+            max_collateral_inputs: 3,
+        }
+    }
+
+    fn key_address(x: u8) -> Address {
+        EnterpriseAddress::new(
+            0,
+            StakeCredential::new_pub_key(Ed25519KeyHash::from([x; Ed25519KeyHash::BYTE_COUNT])),
+        )
+        .to_address()
+    }
+
+    fn utxo(hash_byte: u8, address: &Address, coin: Coin) -> TransactionUnspentOutput {
+        TransactionUnspentOutput::new(
+            TransactionInput::new(TransactionHash::from([hash_byte; 32]), 0),
+            TransactionOutput::new(address.clone(), Value::from(coin), None, None),
+        )
+    }
+
+    fn provider_with(utxos: Vec<TransactionUnspentOutput>) -> InMemoryProvider {
+        let provider = InMemoryProvider::new(params(), ExUnits::new(1000000, 500000000));
+        for utxo in utxos {
+            provider.add_utxo(utxo);
+        }
+        provider
+    }
+
+    #[test]
+    fn complete_simple_payment() {
+        let change_address = key_address(1);
+        let provider = provider_with(vec![
+            utxo(1, &change_address, 5000000),
+            utxo(2, &change_address, 20000000),
+            utxo(3, &key_address(2), 100000000),
+        ]);
+        let mut builder = block_on(TransactionBuilder::from_provider(&provider)).unwrap();
+        builder
+            .add_output(
+                TransactionOutputBuilder::new()
+                    .with_address(key_address(3))
+                    .next()
+                    .unwrap()
+                    .with_value(10000000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        let signed = block_on(builder.complete(
+            &provider,
+            &change_address,
+            CoinSelectionStrategyCIP2::LargestFirst,
+        ))
+        .unwrap();
+        let body = signed.body();
+        // only the largest input at the change address is needed
+        assert_eq!(body.inputs.len(), 1);
+        assert_eq!(
+            body.inputs[0].transaction_id,
+            TransactionHash::from([2; 32])
+        );
+        assert_eq!(body.outputs.len(), 2);
+        assert_eq!(body.outputs[1].address(), &change_address);
+        assert_eq!(
+            body.outputs[0].amount().coin + body.outputs[1].amount().coin + body.fee,
+            20000000
+        );
+    }
+
+    #[test]
+    fn complete_insufficient_funds() {
+        let change_address = key_address(1);
+        let provider = provider_with(vec![utxo(1, &change_address, 5000000)]);
+        let mut builder = block_on(TransactionBuilder::from_provider(&provider)).unwrap();
+        builder
+            .add_output(
+                TransactionOutputBuilder::new()
+                    .with_address(key_address(3))
+                    .next()
+                    .unwrap()
+                    .with_value(10000000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(block_on(builder.complete(
+            &provider,
+            &change_address,
+            CoinSelectionStrategyCIP2::LargestFirst,
+        ))
+        .is_err());
+    }
+
+    /// 10 ADA locked by an always-succeeding script along with 3 and 8 ADA at the change address
+    fn script_spend_provider() -> (InMemoryProvider, TransactionUnspentOutput, PlutusScript) {
+        let script = PlutusScript::PlutusV1(PlutusV1Script::new(
+            hex::decode("4d01000033222220051200120011").unwrap(),
+        ));
+        let script_address =
+            EnterpriseAddress::new(0, StakeCredential::new_script(script.hash())).to_address();
+        let script_utxo = utxo(9, &script_address, 10000000);
+        let provider = provider_with(vec![
+            script_utxo.clone(),
+            utxo(1, &key_address(1), 3000000),
+            utxo(2, &key_address(1), 8000000),
+        ]);
+        (provider, script_utxo, script)
+    }
+
+    /// Spends the script UTxO paying {coin} to another address
+    #[allow(clippy::result_large_err)]
+    fn complete_script_spend(
+        provider: &InMemoryProvider,
+        script_utxo: TransactionUnspentOutput,
+        script: PlutusScript,
+        coin: Coin,
+    ) -> Result<SignedTxBuilder, TxBuilderError> {
+        let mut builder = block_on(TransactionBuilder::from_provider(provider)).unwrap();
+        builder
+            .add_input(
+                SingleInputBuilder::new(script_utxo.input, script_utxo.output)
+                    .plutus_script(
+                        PartialPlutusWitness::new(
+                            PlutusScriptWitness::from(script),
+                            PlutusData::new_list(vec![]),
+                        ),
+                        vec![],
+                        PlutusData::new_list(vec![]),
+                    )
+                    .unwrap(),
+            )
+            .unwrap();
+        builder
+            .add_output(
+                TransactionOutputBuilder::new()
+                    .with_address(key_address(3))
+                    .next()
+                    .unwrap()
+                    .with_value(coin)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        block_on(builder.complete(
+            provider,
+            &key_address(1),
+            CoinSelectionStrategyCIP2::LargestFirst,
+        ))
+    }
+
+    #[test]
+    fn complete_plutus_script_input() {
+        let (provider, script_utxo, script) = script_spend_provider();
+        let signed = complete_script_spend(&provider, script_utxo, script, 9900000).unwrap();
+        // signatures aren't checked by the in-memory provider
+        let tx = signed.build_unchecked();
+        let redeemers = tx.witness_set.redeemers.clone().unwrap();
+        assert_eq!(redeemers.len(), 1);
+        assert_eq!(
+            RedeemerWitnessKey::new(redeemers[0].tag, redeemers[0].index),
+            // inputs are sorted so the added one comes before the script input
+            RedeemerWitnessKey::new(RedeemerTag::Spend, 1)
+        );
+        assert_eq!(redeemers[0].ex_units, ExUnits::new(1000000, 500000000));
+        let collateral = tx.body.collateral_inputs.clone().unwrap();
+        assert!(!collateral.is_empty());
+        // the script input alone can't cover the fee so the largest other one is added
+        let total_in = 10000000 + 8000000;
+        assert_eq!(tx.body.inputs.len(), 2);
+        assert_eq!(
+            tx.body
+                .outputs
+                .iter()
+                .map(|output| output.amount().coin)
+                .sum::<Coin>()
+                + tx.body.fee,
+            total_in
+        );
+
+        let hash = block_on(provider.submit_tx(&tx)).unwrap();
+        assert_eq!(hash, tx.body.hash());
+        assert_eq!(provider.submitted().len(), 1);
+        // inputs spent and replaced by the outputs, leaving only the unused 3 ADA one
+        let remaining = provider.utxos();
+        assert_eq!(remaining.len(), tx.body.outputs.len() + 1);
+        assert_eq!(
+            remaining
+                .iter()
+                .filter(|utxo| utxo.input.transaction_id == hash)
+                .count(),
+            tx.body.outputs.len()
+        );
+        // and so can't be spent again
+        assert!(block_on(provider.submit_tx(&tx)).is_err());
+    }
+
+    #[test]
+    fn complete_collateral_tight_margin() {
+        let (provider, script_utxo, script) = script_spend_provider();
+        // 10 + 8 ADA covers this with the script fee, but not once the fee for the
+        // collateral input and collateral return are added so the 3 ADA input is needed too
+        let signed = complete_script_spend(&provider, script_utxo, script, 17735000).unwrap();
+        let tx = signed.build_unchecked();
+        assert_eq!(tx.body.inputs.len(), 3);
+        assert!(tx.body.collateral_inputs.is_some());
+        assert!(tx.body.collateral_return.is_some());
+        assert_eq!(
+            tx.body
+                .outputs
+                .iter()
+                .map(|output| output.amount().coin)
+                .sum::<Coin>()
+                + tx.body.fee,
+            10000000 + 8000000 + 3000000
+        );
+        let required_collateral = (tx.body.fee * 150).div_ceil(100);
+        assert!(tx.body.total_collateral.unwrap() >= required_collateral);
+    }
+
+    #[test]
+    fn evaluate_unknown_input() {
+        let change_address = key_address(1);
+        let provider = provider_with(vec![utxo(1, &change_address, 5000000)]);
+        let mut builder = block_on(TransactionBuilder::from_provider(&provider)).unwrap();
+        let spent = utxo(7, &change_address, 5000000);
+        builder
+            .add_input(
+                SingleInputBuilder::new(spent.input, spent.output)
+                    .payment_key()
+                    .unwrap(),
+            )
+            .unwrap();
+        let tx = builder
+            .build_for_evaluation(ChangeSelectionAlgo::Default, &change_address)
+            .unwrap()
+            .draft_tx()
+            .unwrap();
+        assert!(block_on(provider.evaluate_tx(&tx, &[])).is_err());
+    }
+}
@@ -20,6 +20,14 @@ impl RedeemerWitnessKey {
     pub fn new(tag: RedeemerTag, index: u64) -> Self {
         Self { tag, index }
     }
+
+    pub fn tag(&self) -> RedeemerTag {
+        self.tag
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
 }
 
 impl From<&Redeemer> for RedeemerWitnessKey {
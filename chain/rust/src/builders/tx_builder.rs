@@ -1,7 +1,8 @@
 use super::certificate_builder::*;
-use super::input_builder::InputBuilderResult;
+use super::input_builder::{InputBuilderError, InputBuilderResult, SingleInputBuilder};
 use super::mint_builder::MintBuilderResult;
 use super::output_builder::{OutputBuilderError, SingleOutputBuilderResult};
+use super::provider::{Provider, ProviderError};
 use super::redeemer_builder::RedeemerBuilderError;
 use super::redeemer_builder::RedeemerSetBuilder;
 use super::redeemer_builder::RedeemerWitnessKey;
@@ -172,6 +173,10 @@ pub enum TxBuilderError {
     RedeemerBuildFailed(#[from] RedeemerBuilderError),
     #[error("Output build failed: {0}")]
     OutputBuildFailed(#[from] OutputBuilderError),
+    #[error("Input build failed: {0}")]
+    InputBuildFailed(#[from] InputBuilderError),
+    #[error("{0}")]
+    ProviderFailed(#[from] ProviderError),
     #[error("Arithmetic: {0}")]
     Arithmetic(#[from] ArithmeticError),
     #[error("Asset arithmetic: {0}")]
@@ -190,6 +195,8 @@ pub enum TxBuilderError {
     CollateralMustBePayment,
     #[error("Max collateral input count {0} exceeded")]
     MaxCollateralInputExceeded(u32),
+    #[error("Collateral of {0} required but only {1} available")]
+    InsufficientCollateral(Coin, Coin),
     #[error("Max value size of {0} exceeded. Found: {1}")]
     MaxValueSizeExceeded(u32, usize),
     #[error("Max transaction size of {0} exceeded. Found: {1}")]
@@ -251,7 +258,7 @@ pub struct TransactionBuilderConfig {
     coins_per_utxo_byte: Coin,    // protocol parameter
     ex_unit_prices: ExUnitPrices, // protocol parameter
     cost_models: CostModels,      // protocol parameter
-    _collateral_percentage: u32,  // protocol parameter
+    collateral_percentage: u32,   // protocol parameter
     max_collateral_inputs: u32,   // protocol parameter
     prefer_pure_change: bool,
 }
@@ -364,7 +371,7 @@ impl TransactionBuilderConfigBuilder {
             } else {
                 CostModels::new()
             },
-            _collateral_percentage: self.collateral_percentage.ok_or(
+            collateral_percentage: self.collateral_percentage.ok_or(
                 TxBuilderError::UninitializedField(TxBuilderConfigField::CollateralPercentage),
             )?,
             max_collateral_inputs: self.max_collateral_inputs.ok_or(
@@ -1371,6 +1378,219 @@ impl TransactionBuilder {
             min_fee_with_exunits(&self_copy)
         }
     }
+
+    /// Creates a builder using the current protocol parameters from the provider
+    pub async fn from_provider<P: Provider>(provider: &P) -> Result<Self, TxBuilderError> {
+        let params = provider.protocol_params().await?;
+        Ok(Self::new(params.to_config_builder().build()?))
+    }
+
+    /// Finishes the transaction using the provider's view of the chain:
+    /// 1) selects inputs from the key-locked UTxOs at {change_address} using {strategy}
+    /// 2) if there are any scripts, evaluates their ExUnits (adding more inputs for the script fees if needed)
+    /// 3) also for scripts, adds collateral from the ADA-only UTxOs at {change_address} unless some was already added.
+    ///    2) and 3) are repeated until no more inputs are needed for the fee they add
+    /// 4) adds change to {change_address} and moves to the signing step like build()
+    ///
+    /// Everything else (outputs, certs, script inputs, etc) must be added before calling this.
+    pub async fn complete<P: Provider>(
+        &mut self,
+        provider: &P,
+        change_address: &Address,
+        strategy: CoinSelectionStrategyCIP2,
+    ) -> Result<SignedTxBuilder, TxBuilderError> {
+        let used_inputs = self
+            .inputs
+            .iter()
+            .map(|utxo| utxo.input.clone())
+            .collect::<BTreeSet<_>>();
+        // anything locked by scripts would need witness info we don't have
+        let spendable = provider
+            .utxos_at_address(change_address)
+            .await?
+            .into_iter()
+            .filter(|utxo| !used_inputs.contains(&utxo.input))
+            .filter_map(|utxo| {
+                SingleInputBuilder::new(utxo.input, utxo.output)
+                    .payment_key()
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        for input in spendable.iter() {
+            self.add_utxo(input.clone());
+        }
+        self.select_utxos(strategy)?;
+
+        if !self
+            .witness_builders
+            .redeemer_set_builder
+            .build(true)?
+            .is_empty()
+        {
+            let add_collateral = self.collateral.is_none();
+            loop {
+                let draft_tx = self
+                    .build_for_evaluation(ChangeSelectionAlgo::Default, change_address)?
+                    .draft_tx()?;
+                let known_utxos = self
+                    .inputs
+                    .iter()
+                    .chain(self.collateral.iter().flatten())
+                    .chain(self.reference_inputs.iter().flatten())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let evaluated = provider.evaluate_tx(&draft_tx, &known_utxos).await?;
+                let draft_redeemers = draft_tx
+                    .witness_set
+                    .redeemers
+                    .iter()
+                    .flatten()
+                    .map(|redeemer| RedeemerWitnessKey::new(redeemer.tag, redeemer.index))
+                    .collect::<BTreeSet<_>>();
+                for (redeemer, ex_units) in evaluated {
+                    if !draft_redeemers.contains(&redeemer) {
+                        return Err(ProviderError::new(format!(
+                            "Evaluated redeemer {redeemer:?} not found in the transaction"
+                        ))
+                        .into());
+                    }
+                    self.set_exunits(redeemer, ex_units);
+                }
+                // input selection could only account for the fee without the scripts
+                let mut added_inputs = self.add_inputs_for_script_fee(&spendable)?;
+                if add_collateral {
+                    // the required collateral depends on the fee so it's redone after any change
+                    self.collateral = None;
+                    self.collateral_return = None;
+                    self.add_collateral_for_scripts(&spendable, change_address)?;
+                    // collateral inputs and the collateral return increase the fee too
+                    added_inputs |= self.add_inputs_for_script_fee(&spendable)?;
+                }
+                // more inputs could change the script execution so they're re-evaluated
+                if !added_inputs {
+                    break;
+                }
+            }
+        }
+
+        self.build(ChangeSelectionAlgo::Default, change_address)
+    }
+
+    /// Adds the largest of {candidates} as inputs until the fee including ExUnits is covered.
+    /// Returns whether any were added.
+    #[allow(clippy::result_large_err)]
+    fn add_inputs_for_script_fee(
+        &mut self,
+        candidates: &[InputBuilderResult],
+    ) -> Result<bool, TxBuilderError> {
+        let used_inputs = self
+            .inputs
+            .iter()
+            .map(|utxo| utxo.input.clone())
+            .collect::<BTreeSet<_>>();
+        let mut remaining = candidates
+            .iter()
+            .filter(|candidate| !used_inputs.contains(&candidate.input))
+            .collect::<Vec<_>>();
+        remaining.sort_by_key(|candidate| std::cmp::Reverse(candidate.utxo_info.amount().coin));
+        let mut remaining = remaining.into_iter();
+        let mut added = false;
+        while self.get_total_input()?.coin
+            < self
+                .get_total_output()?
+                .coin
+                .checked_add(self.min_fee(true)?)
+                .ok_or(ArithmeticError::IntegerOverflow)?
+        {
+            let input = remaining
+                .next()
+                .ok_or(TxBuilderError::UTxOBalanceInsufficient)?;
+            self.add_input(input.clone())?;
+            added = true;
+        }
+        Ok(added)
+    }
+
+    /// Adds the largest ADA-only {candidates} as collateral until collateral_percentage of the fee
+    /// is covered, returning the rest to {change_address} when there's enough for an output.
+    #[allow(clippy::result_large_err)]
+    fn add_collateral_for_scripts(
+        &mut self,
+        candidates: &[InputBuilderResult],
+        change_address: &Address,
+    ) -> Result<(), TxBuilderError> {
+        let required_collateral = |builder: &TransactionBuilder| -> Result<Coin, TxBuilderError> {
+            // the change isn't added until build() so the fee it will end up with is included upfront:
+            // either the fee for a change output or everything left over when that's too small to add
+            let change = builder
+                .get_total_input()?
+                .checked_sub(&builder.get_total_output()?)?;
+            let change_output = TransactionOutput::new(change_address.clone(), change, None, None);
+            let min_fee = builder.min_fee(true)?;
+            let change_coin = change_output.amount().coin;
+            let min_ada = min_ada_required(&change_output, builder.config.coins_per_utxo_byte)?;
+            let fee_with_change =
+                if change_output.amount().has_multiassets() || change_coin >= min_ada {
+                    let fee_with_change = min_fee
+                        .checked_add(builder.fee_for_output(&SingleOutputBuilderResult::new(
+                            change_output.clone(),
+                        ))?)
+                        .ok_or(ArithmeticError::IntegerOverflow)?;
+                    (change_output.amount().has_multiassets()
+                        || change_coin >= min_ada.saturating_add(fee_with_change))
+                    .then_some(fee_with_change)
+                } else {
+                    None
+                };
+            let fee = fee_with_change.unwrap_or_else(|| change_coin.max(min_fee));
+            Ok(fee
+                .checked_mul(builder.config.collateral_percentage as u64)
+                .ok_or(ArithmeticError::IntegerOverflow)?
+                .div_ceil(100))
+        };
+        let mut ada_only = candidates
+            .iter()
+            .filter(|candidate| !candidate.utxo_info.amount().has_multiassets())
+            .collect::<Vec<_>>();
+        ada_only.sort_by_key(|candidate| std::cmp::Reverse(candidate.utxo_info.amount().coin));
+        let mut total = Coin::zero();
+        for candidate in ada_only {
+            if total >= required_collateral(self)? {
+                break;
+            }
+            self.add_collateral(candidate.clone())?;
+            total = total
+                .checked_add(candidate.utxo_info.amount().coin)
+                .ok_or(ArithmeticError::IntegerOverflow)?;
+        }
+        let required = required_collateral(self)?;
+        if total < required {
+            return Err(TxBuilderError::InsufficientCollateral(required, total));
+        }
+        let mut collateral_return = TransactionOutput::new(
+            change_address.clone(),
+            Value::from(total - required),
+            None,
+            None,
+        );
+        if total - required
+            >= min_ada_required(&collateral_return, self.config.coins_per_utxo_byte)?
+        {
+            self.set_collateral_return(collateral_return.clone());
+            // the return output itself increases the fee
+            let required = required_collateral(self)?;
+            collateral_return.set_amount(Value::from(total.saturating_sub(required)));
+            if total >= required
+                && total - required
+                    >= min_ada_required(&collateral_return, self.config.coins_per_utxo_byte)?
+            {
+                self.set_collateral_return(collateral_return);
+            } else {
+                self.collateral_return = None;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
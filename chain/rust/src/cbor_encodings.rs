@@ -2,6 +2,7 @@
 // https://github.com/dcSpark/cddl-codegen
 
 use cml_core::serialization::LenEncoding;
+use cml_core::UnknownFields;
 
 #[derive(Clone, Debug, Default)]
 pub struct DRepVotingThresholdsEncoding {
@@ -66,6 +67,7 @@ pub struct ProtocolParamUpdateEncoding {
     pub d_rep_deposit_key_encoding: Option<cbor_event::Sz>,
     pub d_rep_inactivity_period_encoding: Option<cbor_event::Sz>,
     pub d_rep_inactivity_period_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
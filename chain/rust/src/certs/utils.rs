@@ -1,6 +1,16 @@
 use std::{borrow::Cow, str::FromStr};
 
-use super::{Ipv4, Ipv6, StakeCredential};
+use super::{
+    AuthCommitteeHotCert, Certificate, Credential, DRep, Ipv4, Ipv6, MultiHostName, PoolMetadata,
+    PoolParams, PoolRegistration, PoolRetirement, RegCert, RegDrepCert, Relay,
+    ResignCommitteeColdCert, SingleHostAddr, SingleHostName, StakeCredential, StakeDelegation,
+    StakeDeregistration, StakeRegDelegCert, StakeRegistration, StakeVoteDelegCert,
+    StakeVoteRegDelegCert, UnregCert, UnregDrepCert, UpdateDrepCert, VoteDelegCert,
+    VoteRegDelegCert,
+};
+use crate::governance::Anchor;
+use crate::UnitInterval;
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_core::DeserializeError;
 use cml_crypto::RawBytesEncoding;
 
@@ -14,6 +24,144 @@ impl StakeCredential {
     }
 }
 
+impl CBORFieldNames for Credential {
+    fn cbor_field_names() -> FieldNames {
+        let credential =
+            || FieldNames::Array(vec![("", FieldNames::None), ("hash", FieldNames::None)]);
+        FieldNames::Variants(vec![(0, credential()), (1, credential())])
+    }
+}
+
+impl CBORFieldNames for DRep {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (
+                0,
+                FieldNames::Array(vec![("", FieldNames::None), ("pool", FieldNames::None)]),
+            ),
+            (
+                1,
+                FieldNames::Array(vec![
+                    ("", FieldNames::None),
+                    ("script_hash", FieldNames::None),
+                ]),
+            ),
+            (2, FieldNames::Array(vec![("", FieldNames::None)])),
+            (3, FieldNames::Array(vec![("", FieldNames::None)])),
+        ])
+    }
+}
+
+impl CBORFieldNames for Certificate {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, StakeRegistration::cbor_field_names()),
+            (1, StakeDeregistration::cbor_field_names()),
+            (2, StakeDelegation::cbor_field_names()),
+            (3, PoolRegistration::cbor_field_names()),
+            (4, PoolRetirement::cbor_field_names()),
+            (7, RegCert::cbor_field_names()),
+            (8, UnregCert::cbor_field_names()),
+            (9, VoteDelegCert::cbor_field_names()),
+            (10, StakeVoteDelegCert::cbor_field_names()),
+            (11, StakeRegDelegCert::cbor_field_names()),
+            (12, VoteRegDelegCert::cbor_field_names()),
+            (13, StakeVoteRegDelegCert::cbor_field_names()),
+            (14, AuthCommitteeHotCert::cbor_field_names()),
+            (15, ResignCommitteeColdCert::cbor_field_names()),
+            (16, RegDrepCert::cbor_field_names()),
+            (17, UnregDrepCert::cbor_field_names()),
+            (18, UpdateDrepCert::cbor_field_names()),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(StakeRegistration [0, stake_credential: Credential::cbor_field_names()]);
+cml_core::impl_cbor_field_names!(StakeDeregistration [1, stake_credential: Credential::cbor_field_names()]);
+cml_core::impl_cbor_field_names!(StakeDelegation [2, stake_credential: Credential::cbor_field_names(), pool]);
+cml_core::impl_cbor_field_names!(PoolRegistration [3, pool_params: PoolParams::cbor_field_names().embedded()]);
+cml_core::impl_cbor_field_names!(PoolRetirement [4, pool, epoch]);
+cml_core::impl_cbor_field_names!(RegCert [7, stake_credential: Credential::cbor_field_names(), coin]);
+cml_core::impl_cbor_field_names!(UnregCert [8, stake_credential: Credential::cbor_field_names(), coin]);
+cml_core::impl_cbor_field_names!(VoteDelegCert [
+    9,
+    stake_credential: Credential::cbor_field_names(),
+    d_rep: DRep::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(StakeVoteDelegCert [
+    10,
+    stake_credential: Credential::cbor_field_names(),
+    pool,
+    d_rep: DRep::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(StakeRegDelegCert [
+    11,
+    stake_credential: Credential::cbor_field_names(),
+    pool,
+    coin,
+]);
+cml_core::impl_cbor_field_names!(VoteRegDelegCert [
+    12,
+    stake_credential: Credential::cbor_field_names(),
+    d_rep: DRep::cbor_field_names(),
+    coin,
+]);
+cml_core::impl_cbor_field_names!(StakeVoteRegDelegCert [
+    13,
+    stake_credential: Credential::cbor_field_names(),
+    pool,
+    d_rep: DRep::cbor_field_names(),
+    coin,
+]);
+cml_core::impl_cbor_field_names!(AuthCommitteeHotCert [
+    14,
+    committee_cold_credential: Credential::cbor_field_names(),
+    committee_hot_credential: Credential::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(ResignCommitteeColdCert [
+    15,
+    committee_cold_credential: Credential::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(RegDrepCert [
+    16,
+    drep_credential: Credential::cbor_field_names(),
+    coin,
+    anchor: Anchor::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(UnregDrepCert [17, drep_credential: Credential::cbor_field_names(), coin]);
+cml_core::impl_cbor_field_names!(UpdateDrepCert [
+    18,
+    drep_credential: Credential::cbor_field_names(),
+    anchor: Anchor::cbor_field_names(),
+]);
+
+cml_core::impl_cbor_field_names!(PoolParams [
+    operator,
+    vrf_keyhash,
+    pledge,
+    cost,
+    margin: UnitInterval::cbor_field_names(),
+    reward_account,
+    pool_owners,
+    relays: FieldNames::elements(Relay::cbor_field_names()),
+    pool_metadata: PoolMetadata::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(PoolMetadata [url, pool_metadata_hash]);
+
+impl CBORFieldNames for Relay {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, SingleHostAddr::cbor_field_names()),
+            (1, SingleHostName::cbor_field_names()),
+            (2, MultiHostName::cbor_field_names()),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(SingleHostAddr [0, port, ipv4, ipv6]);
+cml_core::impl_cbor_field_names!(SingleHostName [1, port, dns_name]);
+cml_core::impl_cbor_field_names!(MultiHostName [2, dns_name]);
+
 #[derive(Debug, thiserror::Error)]
 pub enum IPStringParsingError {
     #[error("Invalid IPv4 Address String, expected period-separated bytes e.g. 0.0.0.0")]
@@ -1,7 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
-use super::{BootstrapWitness, Vkeywitness};
+use super::{BootstrapWitness, Nonce, VRFCert, Vkeywitness};
 use crate::byron::AddressContent;
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 
 use cml_crypto::{
     chain_crypto::{self, derive::combine_pk_and_chaincode},
@@ -20,6 +21,22 @@ impl BootstrapWitness {
     }
 }
 
+cml_core::impl_cbor_field_names!(Vkeywitness [vkey, ed25519_signature]);
+cml_core::impl_cbor_field_names!(BootstrapWitness [public_key, signature, chain_code, attributes]);
+cml_core::impl_cbor_field_names!(VRFCert [output, proof]);
+
+impl CBORFieldNames for Nonce {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, FieldNames::Array(vec![("", FieldNames::None)])),
+            (
+                1,
+                FieldNames::Array(vec![("", FieldNames::None), ("hash", FieldNames::None)]),
+            ),
+        ])
+    }
+}
+
 impl TryInto<chain_crypto::PublicKey<chain_crypto::ed25519_derive::Ed25519Bip32>>
     for BootstrapWitness
 {
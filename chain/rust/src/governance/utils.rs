@@ -0,0 +1,95 @@
+use super::{
+    Anchor, Committee, Constitution, GovAction, GovActionId, HardForkInitiationAction,
+    NewCommittee, NewConstitution, NoConfidence, ParameterChangeAction, ProposalProcedure,
+    TreasuryWithdrawalsAction, Voter, VotingProcedure,
+};
+use crate::block::ProtocolVersion;
+use crate::certs::Credential;
+use crate::utils::withdrawals_field_names;
+use crate::{ProtocolParamUpdate, UnitInterval};
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
+
+cml_core::impl_cbor_field_names!(Anchor [anchor_url, anchor_doc_hash]);
+cml_core::impl_cbor_field_names!(Committee [
+    credentials: FieldNames::entries(
+        ("committee_cold_credential", Credential::cbor_field_names()),
+        ("epoch", FieldNames::None),
+    ),
+    unit_interval: UnitInterval::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(Constitution [anchor: Anchor::cbor_field_names(), script_hash]);
+cml_core::impl_cbor_field_names!(GovActionId [transaction_id, gov_action_index]);
+
+impl CBORFieldNames for GovAction {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, ParameterChangeAction::cbor_field_names()),
+            (1, HardForkInitiationAction::cbor_field_names()),
+            (2, TreasuryWithdrawalsAction::cbor_field_names()),
+            (3, NoConfidence::cbor_field_names()),
+            (4, NewCommittee::cbor_field_names()),
+            (5, NewConstitution::cbor_field_names()),
+            (6, FieldNames::Array(vec![("", FieldNames::None)])),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(ParameterChangeAction [
+    0,
+    gov_action_id: GovActionId::cbor_field_names(),
+    protocol_param_update: ProtocolParamUpdate::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(HardForkInitiationAction [
+    1,
+    action_id: GovActionId::cbor_field_names(),
+    version: ProtocolVersion::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(TreasuryWithdrawalsAction [2, withdrawal: withdrawals_field_names()]);
+cml_core::impl_cbor_field_names!(NoConfidence [3, action_id: GovActionId::cbor_field_names()]);
+cml_core::impl_cbor_field_names!(NewCommittee [
+    4,
+    action_id: GovActionId::cbor_field_names(),
+    cold_credentials: FieldNames::elements(Credential::cbor_field_names()),
+    committee: Committee::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(NewConstitution [
+    5,
+    action_id: GovActionId::cbor_field_names(),
+    constitution: Constitution::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(ProposalProcedure [
+    deposit,
+    reward_account,
+    gov_action: GovAction::cbor_field_names(),
+    anchor: Anchor::cbor_field_names(),
+]);
+
+impl CBORFieldNames for Voter {
+    fn cbor_field_names() -> FieldNames {
+        let voter =
+            |name| FieldNames::Array(vec![("", FieldNames::None), (name, FieldNames::None)]);
+        FieldNames::Variants(vec![
+            (0, voter("ed25519_key_hash")),
+            (1, voter("script_hash")),
+            (2, voter("ed25519_key_hash")),
+            (3, voter("script_hash")),
+            (4, voter("ed25519_key_hash")),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(VotingProcedure [vote, anchor: Anchor::cbor_field_names()]);
+
+/// Names for `VotingProcedures`, which (as a map type) can't implement `CBORFieldNames` itself
+pub fn voting_procedures_field_names() -> FieldNames {
+    FieldNames::entries(
+        ("voter", Voter::cbor_field_names()),
+        (
+            "",
+            FieldNames::entries(
+                ("gov_action_id", GovActionId::cbor_field_names()),
+                ("voting_procedure", VotingProcedure::cbor_field_names()),
+            ),
+        ),
+    )
+}
@@ -6,9 +6,13 @@ use crate::{
     utils::BigInteger,
 };
 
-use cml_core::{DeserializeError, Int};
+use cml_core::{
+    serialization::{Deserialize, Serialize},
+    DeserializeError, Int,
+};
 
-use std::collections::BTreeMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
 use std::convert::TryFrom;
 
 #[wasm_bindgen]
@@ -18,6 +22,8 @@ use std::convert::TryFrom;
 // https://github.com/input-output-hk/cardano-node/blob/master/cardano-api/src/Cardano/Api/MetaData.hs
 // but has 2 additional schemas for more or less conversionse
 // Note: Byte/Strings (including keys) in any schema must be at most 64 bytes in length
+// Every schema also has a strict mode (the *_strict functions) which errors instead of doing
+// any conversion that would not survive the round-trip back e.g. duplicate map keys.
 pub enum MetadataJsonSchema {
     // Does zero implicit conversions.
     // Round-trip conversions are 100% consistent
@@ -59,6 +65,10 @@ pub enum MetadataJsonError {
     JsonParse(#[from] JsonParseError),
     #[error("JSON printing: {0}")]
     JsonPrinting(#[from] serde_json::Error),
+    #[error("JSON reading: {0}")]
+    JsonReading(serde_json::Error),
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
     #[error("null not allowed in metadatums")]
     NullFound,
     #[error("bools not allowed in metadatums")]
@@ -74,16 +84,24 @@ pub enum MetadataJsonError {
         0
     )]
     DetailedNonObject(JSONValue),
+    #[error("DetailedSchema tagged objects must have exactly one key")]
+    DetailedMultipleTags,
     #[error("Invalid hex string: {0}")]
     InvalidHex(#[from] hex::FromHexError),
     #[error("Bytes not allowed in BasicConversions schema")]
     BytesInNoConversions,
     #[error("Metadatum ints must fit in 8 bytes: {0}")]
     IntTooBig(BigInteger),
+    #[error("Only integer numbers within +/- 64 bits are supported here: {0}")]
+    UnsupportedNumber(String),
     #[error("key type {0:?} not allowed in JSON under specified schema")]
     InvalidKeyType(TransactionMetadatum),
     #[error("Metadatum structure error (e.g. too big for bounds): {0}")]
     InvalidStructure(#[from] DeserializeError),
+    #[error("Duplicate map key {0:?} would be lost in conversion")]
+    DuplicateKey(TransactionMetadatum),
+    #[error("Conversion would not round-trip: {0}")]
+    NotRoundTrippable(String),
 }
 
 fn supports_tagged_values(schema: MetadataJsonSchema) -> bool {
@@ -105,6 +123,76 @@ fn bytes_to_hex_string(bytes: &[u8]) -> String {
     format!("0x{}", hex::encode(bytes))
 }
 
+fn encode_string(
+    s: String,
+    schema: MetadataJsonSchema,
+    strict: bool,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
+    if schema == MetadataJsonSchema::BasicConversions {
+        if let Some(bytes) = hex_string_to_bytes(&s) {
+            if strict && bytes_to_hex_string(&bytes) != s {
+                return Err(MetadataJsonError::NotRoundTrippable(format!(
+                    "hex string {s} would be read back as {}",
+                    bytes_to_hex_string(&bytes)
+                )));
+            }
+            return TransactionMetadatum::new_bytes(bytes).map_err(Into::into);
+        }
+    }
+    TransactionMetadatum::new_text(s).map_err(Into::into)
+}
+
+// keys of JSON objects in the NoConversions/BasicConversions schemas
+fn encode_object_key(
+    raw_key: String,
+    schema: MetadataJsonSchema,
+    strict: bool,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
+    if schema == MetadataJsonSchema::BasicConversions {
+        if let Ok(x) = raw_key.parse::<i128>() {
+            if strict && x.to_string() != raw_key {
+                return Err(MetadataJsonError::NotRoundTrippable(format!(
+                    "key \"{raw_key}\" would be read back as \"{x}\""
+                )));
+            }
+            return Int::try_from(x)
+                .map(TransactionMetadatum::new_int)
+                .map_err(|_e| MetadataJsonError::IntTooBig(BigInteger::from(x)));
+        }
+        encode_string(raw_key, schema, strict)
+    } else {
+        TransactionMetadatum::new_text(raw_key).map_err(Into::into)
+    }
+}
+
+// the hex (no 0x prefix) of DetailedSchema's {"bytes": ..} objects
+fn encode_detailed_bytes(
+    hex_str: String,
+    strict: bool,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
+    let bytes = hex::decode(&hex_str)?;
+    if strict && hex::encode(&bytes) != hex_str {
+        return Err(MetadataJsonError::NotRoundTrippable(format!(
+            "hex string {hex_str} would be read back as {}",
+            hex::encode(&bytes)
+        )));
+    }
+    TransactionMetadatum::new_bytes(bytes).map_err(Into::into)
+}
+
+fn set_entry(
+    map: &mut MetadatumMap,
+    key: TransactionMetadatum,
+    value: TransactionMetadatum,
+    strict: bool,
+) -> Result<(), MetadataJsonError> {
+    if strict && map.get(&key).is_some() {
+        return Err(MetadataJsonError::DuplicateKey(key));
+    }
+    map.set(key, value);
+    Ok(())
+}
+
 /// Converts JSON to Metadata according to MetadataJsonSchema
 pub fn encode_json_str_to_metadatum(
     json: &str,
@@ -114,30 +202,47 @@ pub fn encode_json_str_to_metadatum(
     encode_json_value_to_metadatum(value, schema)
 }
 
+/// Converts JSON to Metadata according to MetadataJsonSchema.
+/// Errors on any conversion that would not give back the same JSON when decoded again
+/// e.g. duplicate DetailedSchema map keys or BasicConversions keys like "007".
+/// Note: duplicate keys within a JSON object are already merged when parsing the JSON
+/// string so those can only be detected by encode_json_reader_to_metadatum_cbor_strict.
+pub fn encode_json_str_to_metadatum_strict(
+    json: &str,
+    schema: MetadataJsonSchema,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
+    let value = JSONValue::from_string(json)?;
+    encode_json_value_to_metadatum_strict(value, schema)
+}
+
 pub fn encode_json_value_to_metadatum(
     value: JSONValue,
     schema: MetadataJsonSchema,
 ) -> Result<TransactionMetadatum, MetadataJsonError> {
-    fn encode_string(
-        s: String,
-        schema: MetadataJsonSchema,
-    ) -> Result<TransactionMetadatum, DeserializeError> {
-        if schema == MetadataJsonSchema::BasicConversions {
-            match hex_string_to_bytes(&s) {
-                Some(bytes) => TransactionMetadatum::new_bytes(bytes),
-                None => TransactionMetadatum::new_text(s),
-            }
-        } else {
-            TransactionMetadatum::new_text(s)
-        }
-    }
+    encode_json_value_to_metadatum_impl(value, schema, false)
+}
+
+/// See encode_json_str_to_metadatum_strict
+pub fn encode_json_value_to_metadatum_strict(
+    value: JSONValue,
+    schema: MetadataJsonSchema,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
+    encode_json_value_to_metadatum_impl(value, schema, true)
+}
+
+fn encode_json_value_to_metadatum_impl(
+    value: JSONValue,
+    schema: MetadataJsonSchema,
+    strict: bool,
+) -> Result<TransactionMetadatum, MetadataJsonError> {
     fn encode_array(
         json_arr: Vec<JSONValue>,
         schema: MetadataJsonSchema,
+        strict: bool,
     ) -> Result<TransactionMetadatum, MetadataJsonError> {
         json_arr
             .into_iter()
-            .map(|value| encode_json_value_to_metadatum(value, schema))
+            .map(|value| encode_json_value_to_metadatum_impl(value, schema, strict))
             .collect::<Result<Vec<_>, MetadataJsonError>>()
             .map(TransactionMetadatum::new_list)
     }
@@ -148,23 +253,14 @@ pub fn encode_json_value_to_metadatum(
             JSONValue::Number(x) => Ok(TransactionMetadatum::new_int(
                 x.as_int().ok_or(MetadataJsonError::IntTooBig(x.clone()))?,
             )),
-            JSONValue::String(s) => encode_string(s, schema).map_err(Into::into),
-            JSONValue::Array(json_arr) => encode_array(json_arr, schema),
+            JSONValue::String(s) => encode_string(s, schema, strict),
+            JSONValue::Array(json_arr) => encode_array(json_arr, schema, strict),
             JSONValue::Object(json_obj) => {
                 let mut map = MetadatumMap::new();
                 for (raw_key, value) in json_obj {
-                    let key =
-                        if schema == MetadataJsonSchema::BasicConversions {
-                            match raw_key.parse::<i128>() {
-                                Ok(x) => TransactionMetadatum::new_int(Int::try_from(x).map_err(
-                                    |_e| MetadataJsonError::IntTooBig(BigInteger::from(x)),
-                                )?),
-                                Err(_) => encode_string(raw_key, schema)?,
-                            }
-                        } else {
-                            TransactionMetadatum::new_text(raw_key)?
-                        };
-                    map.set(key, encode_json_value_to_metadatum(value, schema)?);
+                    let key = encode_object_key(raw_key, schema, strict)?;
+                    let value = encode_json_value_to_metadatum_impl(value, schema, strict)?;
+                    set_entry(&mut map, key, value, strict)?;
                 }
                 Ok(TransactionMetadatum::new_map(map))
             }
@@ -181,19 +277,15 @@ pub fn encode_json_value_to_metadatum(
                         _ => Err(MetadataJsonError::DetailedKeyMismatch(k, v)),
                     },
                     "string" => match v {
-                        JSONValue::String(string) => {
-                            encode_string(string, schema).map_err(Into::into)
-                        }
+                        JSONValue::String(string) => encode_string(string, schema, strict),
                         _ => Err(MetadataJsonError::DetailedKeyMismatch(k, v)),
                     },
                     "bytes" => match v {
-                        JSONValue::String(string) => hex::decode(string)
-                            .map_err(Into::into)
-                            .and_then(|b| TransactionMetadatum::new_bytes(b).map_err(Into::into)),
+                        JSONValue::String(string) => encode_detailed_bytes(string, strict),
                         _ => Err(MetadataJsonError::DetailedKeyMismatch(k, v)),
                     },
                     "list" => match v {
-                        JSONValue::Array(array) => encode_array(array, schema),
+                        JSONValue::Array(array) => encode_array(array, schema, strict),
                         _ => Err(MetadataJsonError::DetailedKeyMismatch(k, v)),
                     },
                     "map" => {
@@ -214,8 +306,14 @@ pub fn encode_json_value_to_metadatum(
                             let value = entry_obj
                                 .get("v")
                                 .ok_or(MetadataJsonError::InvalidMapEntry)?;
-                            let key = encode_json_value_to_metadatum(raw_key.clone(), schema)?;
-                            map.set(key, encode_json_value_to_metadatum(value.clone(), schema)?);
+                            let key = encode_json_value_to_metadatum_impl(
+                                raw_key.clone(),
+                                schema,
+                                strict,
+                            )?;
+                            let value =
+                                encode_json_value_to_metadatum_impl(value.clone(), schema, strict)?;
+                            set_entry(&mut map, key, value, strict)?;
                         }
                         Ok(TransactionMetadatum::new_map(map))
                     }
@@ -236,16 +334,54 @@ pub fn decode_metadatum_to_json_str(
     value.to_string().map_err(Into::into)
 }
 
+/// Converts Metadata to JSON according to MetadataJsonSchema.
+/// Errors on any conversion that would not give back the same metadatum when encoded again
+/// e.g. duplicate map keys, or under BasicConversions text that would be read back as bytes.
+pub fn decode_metadatum_to_json_str_strict(
+    metadatum: &TransactionMetadatum,
+    schema: MetadataJsonSchema,
+) -> Result<String, MetadataJsonError> {
+    let value = decode_metadatum_to_json_value_strict(metadatum, schema)?;
+    value.to_string().map_err(Into::into)
+}
+
 pub fn decode_metadatum_to_json_value(
     metadatum: &TransactionMetadatum,
     schema: MetadataJsonSchema,
+) -> Result<JSONValue, MetadataJsonError> {
+    decode_metadatum_to_json_value_impl(metadatum, schema, false)
+}
+
+/// See decode_metadatum_to_json_str_strict
+pub fn decode_metadatum_to_json_value_strict(
+    metadatum: &TransactionMetadatum,
+    schema: MetadataJsonSchema,
+) -> Result<JSONValue, MetadataJsonError> {
+    decode_metadatum_to_json_value_impl(metadatum, schema, true)
+}
+
+fn decode_metadatum_to_json_value_impl(
+    metadatum: &TransactionMetadatum,
+    schema: MetadataJsonSchema,
+    strict: bool,
 ) -> Result<JSONValue, MetadataJsonError> {
     fn decode_key(
         key: &TransactionMetadatum,
         schema: MetadataJsonSchema,
+        strict: bool,
     ) -> Result<String, MetadataJsonError> {
         match key {
-            TransactionMetadatum::Text { text, .. } => Ok(text.clone()),
+            TransactionMetadatum::Text { text, .. } => {
+                if strict
+                    && schema == MetadataJsonSchema::BasicConversions
+                    && (text.parse::<i128>().is_ok() || hex_string_to_bytes(text).is_some())
+                {
+                    return Err(MetadataJsonError::NotRoundTrippable(format!(
+                        "text key \"{text}\" would be read back as a non-text key"
+                    )));
+                }
+                Ok(text.clone())
+            }
             TransactionMetadatum::Bytes { bytes, .. }
                 if schema != MetadataJsonSchema::NoConversions =>
             {
@@ -268,45 +404,59 @@ pub fn decode_metadatum_to_json_value(
             _ => Err(MetadataJsonError::InvalidKeyType(key.clone())),
         }
     }
+    fn check_duplicates(map: &MetadatumMap) -> Result<(), MetadataJsonError> {
+        let mut seen = HashSet::new();
+        for (key, _value) in map.entries.iter() {
+            if !seen.insert(key) {
+                return Err(MetadataJsonError::DuplicateKey(key.clone()));
+            }
+        }
+        Ok(())
+    }
     let (type_key, value) = match metadatum {
-        TransactionMetadatum::Map(map) => match schema {
-            MetadataJsonSchema::NoConversions | MetadataJsonSchema::BasicConversions => {
-                // treats maps directly as JSON maps
-                let mut json_map = BTreeMap::new();
-                for (key, value) in map.entries.iter() {
-                    json_map.insert(
-                        decode_key(key, schema)?,
-                        decode_metadatum_to_json_value(value, schema)?,
-                    );
-                }
-                ("map", JSONValue::from(json_map))
+        TransactionMetadatum::Map(map) => {
+            if strict {
+                check_duplicates(map)?;
             }
+            match schema {
+                MetadataJsonSchema::NoConversions | MetadataJsonSchema::BasicConversions => {
+                    // treats maps directly as JSON maps
+                    let mut json_map = BTreeMap::new();
+                    for (key, value) in map.entries.iter() {
+                        json_map.insert(
+                            decode_key(key, schema, strict)?,
+                            decode_metadatum_to_json_value_impl(value, schema, strict)?,
+                        );
+                    }
+                    ("map", JSONValue::from(json_map))
+                }
 
-            MetadataJsonSchema::DetailedSchema => (
-                "map",
-                JSONValue::from(
-                    map.entries
-                        .iter()
-                        .map(|(key, value)| {
-                            // must encode maps as JSON lists of objects with k/v keys
-                            // also in these schemas we support more key types than strings
-                            let k = decode_metadatum_to_json_value(key, schema)?;
-                            let v = decode_metadatum_to_json_value(value, schema)?;
-                            let mut kv_obj = BTreeMap::new();
-                            kv_obj.insert(String::from("k"), k);
-                            kv_obj.insert(String::from("v"), v);
-                            Ok(JSONValue::from(kv_obj))
-                        })
-                        .collect::<Result<Vec<_>, MetadataJsonError>>()?,
+                MetadataJsonSchema::DetailedSchema => (
+                    "map",
+                    JSONValue::from(
+                        map.entries
+                            .iter()
+                            .map(|(key, value)| {
+                                // must encode maps as JSON lists of objects with k/v keys
+                                // also in these schemas we support more key types than strings
+                                let k = decode_metadatum_to_json_value_impl(key, schema, strict)?;
+                                let v = decode_metadatum_to_json_value_impl(value, schema, strict)?;
+                                let mut kv_obj = BTreeMap::new();
+                                kv_obj.insert(String::from("k"), k);
+                                kv_obj.insert(String::from("v"), v);
+                                Ok(JSONValue::from(kv_obj))
+                            })
+                            .collect::<Result<Vec<_>, MetadataJsonError>>()?,
+                    ),
                 ),
-            ),
-        },
+            }
+        }
         TransactionMetadatum::List { elements, .. } => (
             "list",
             JSONValue::from(
                 elements
                     .iter()
-                    .map(|e| decode_metadatum_to_json_value(e, schema))
+                    .map(|e| decode_metadatum_to_json_value_impl(e, schema, strict))
                     .collect::<Result<Vec<_>, MetadataJsonError>>()?,
             ),
         ),
@@ -323,7 +473,17 @@ pub fn decode_metadatum_to_json_value(
                 MetadataJsonSchema::DetailedSchema => Ok(JSONValue::from(hex::encode(bytes))),
             }?,
         ),
-        TransactionMetadatum::Text { text, .. } => ("string", JSONValue::from(text.clone())),
+        TransactionMetadatum::Text { text, .. } => {
+            if strict
+                && schema == MetadataJsonSchema::BasicConversions
+                && hex_string_to_bytes(text).is_some()
+            {
+                return Err(MetadataJsonError::NotRoundTrippable(format!(
+                    "text \"{text}\" would be read back as bytes"
+                )));
+            }
+            ("string", JSONValue::from(text.clone()))
+        }
     };
     // potentially wrap value in a keyed map to represent more types
     if supports_tagged_values(schema) {
@@ -335,6 +495,404 @@ pub fn decode_metadatum_to_json_value(
     }
 }
 
+/// Converts JSON read from `reader` to the CBOR bytes of a metadatum according to
+/// MetadataJsonSchema and writes them to `writer`.
+/// The bytes are the same as encode_json_str_to_metadatum()'s metadatum's to_cbor_bytes(),
+/// but only the CBOR output is held in memory, not the JSON text nor any JSON/metadatum tree.
+/// Numbers must fit in an i64 or u64 as they are read as JSON numbers.
+pub fn encode_json_reader_to_metadatum_cbor<R: std::io::Read, W: std::io::Write>(
+    reader: R,
+    writer: W,
+    schema: MetadataJsonSchema,
+) -> Result<(), MetadataJsonError> {
+    encode_json_reader_to_metadatum_cbor_impl(reader, writer, schema, false)
+}
+
+/// Strict version of encode_json_reader_to_metadatum_cbor (see encode_json_str_to_metadatum_strict)
+/// which also errors on duplicate keys within JSON objects.
+pub fn encode_json_reader_to_metadatum_cbor_strict<R: std::io::Read, W: std::io::Write>(
+    reader: R,
+    writer: W,
+    schema: MetadataJsonSchema,
+) -> Result<(), MetadataJsonError> {
+    encode_json_reader_to_metadatum_cbor_impl(reader, writer, schema, true)
+}
+
+fn encode_json_reader_to_metadatum_cbor_impl<R: std::io::Read, W: std::io::Write>(
+    reader: R,
+    mut writer: W,
+    schema: MetadataJsonSchema,
+    strict: bool,
+) -> Result<(), MetadataJsonError> {
+    use serde::de::DeserializeSeed;
+    let error = RefCell::new(None);
+    let seed = MetadatumCborSeed {
+        schema,
+        strict,
+        error: &error,
+    };
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let cbor = seed
+        .deserialize(&mut deserializer)
+        .and_then(|cbor| deserializer.end().map(|()| cbor))
+        // serde errors lose their type so we keep ours on the side
+        .map_err(|e| error.take().unwrap_or(MetadataJsonError::JsonReading(e)))?;
+    writer.write_all(&cbor)?;
+    Ok(())
+}
+
+/// Reads a single metadatum from JSON directly into its CBOR bytes
+#[derive(Clone, Copy)]
+struct MetadatumCborSeed<'a> {
+    schema: MetadataJsonSchema,
+    strict: bool,
+    error: &'a RefCell<Option<MetadataJsonError>>,
+}
+
+impl<'a> MetadatumCborSeed<'a> {
+    fn fail<E: serde::de::Error>(&self, error: MetadataJsonError) -> E {
+        let e = E::custom(&error);
+        *self.error.borrow_mut() = Some(error);
+        e
+    }
+
+    fn leaf<E: serde::de::Error>(
+        &self,
+        metadatum: Result<TransactionMetadatum, MetadataJsonError>,
+    ) -> Result<Vec<u8>, E> {
+        metadatum
+            .map(|md| md.to_cbor_bytes())
+            .map_err(|e| self.fail(e))
+    }
+
+    fn list<'de, A: serde::de::SeqAccess<'de>>(&self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut len = 0;
+        let mut elements = Vec::new();
+        while let Some(element) = seq.next_element_seed(*self)? {
+            elements.extend(element);
+            len += 1;
+        }
+        let mut serializer = cbor_event::se::Serializer::new_vec();
+        serializer
+            .write_array(cbor_event::Len::Len(len))
+            .expect("writing to a Vec can't fail");
+        let mut bytes = serializer.finalize();
+        bytes.extend(elements);
+        Ok(bytes)
+    }
+
+    // NoConversions/BasicConversions JSON objects
+    fn object<'de, A: serde::de::MapAccess<'de>>(&self, mut map: A) -> Result<Vec<u8>, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(raw_key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(*self)?;
+            entries.push((raw_key, value));
+        }
+        // the JSON string parser orders object keys and keeps the last of any duplicates
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut cbor_map = CborMapEntries::default();
+        let mut entries = entries.into_iter().peekable();
+        while let Some((raw_key, value)) = entries.next() {
+            let duplicate = entries.peek().is_some_and(|(next, _)| *next == raw_key);
+            let key =
+                encode_object_key(raw_key, self.schema, self.strict).map_err(|e| self.fail(e))?;
+            if duplicate {
+                if self.strict {
+                    return Err(self.fail(MetadataJsonError::DuplicateKey(key)));
+                }
+                continue;
+            }
+            cbor_map
+                .set(key.to_cbor_bytes(), value, self.strict)
+                .map_err(|e| self.fail(e))?;
+        }
+        Ok(cbor_map.finish())
+    }
+
+    // DetailedSchema tagged objects e.g. {"int": 5}
+    fn tagged<'de, A: serde::de::MapAccess<'de>>(&self, mut map: A) -> Result<Vec<u8>, A::Error> {
+        let tag = match map.next_key::<String>()? {
+            Some(tag) => tag,
+            None => {
+                return Err(
+                    self.fail(MetadataJsonError::DetailedNonObject(JSONValue::Object(
+                        BTreeMap::new(),
+                    ))),
+                )
+            }
+        };
+        let mismatch = |value: serde_json::Value| match to_json_value(value) {
+            Ok(value) => self.fail(MetadataJsonError::DetailedKeyMismatch(tag.clone(), value)),
+            Err(e) => self.fail(e),
+        };
+        let cbor = match tag.as_str() {
+            "int" => match map.next_value::<serde_json::Value>()? {
+                serde_json::Value::Number(n) => match n
+                    .as_u64()
+                    .map(Int::from)
+                    .or_else(|| n.as_i64().map(Int::from))
+                {
+                    Some(int) => Ok(TransactionMetadatum::new_int(int).to_cbor_bytes()),
+                    None => Err(self.fail(MetadataJsonError::UnsupportedNumber(n.to_string()))),
+                },
+                other => Err(mismatch(other)),
+            },
+            "string" => match map.next_value::<serde_json::Value>()? {
+                serde_json::Value::String(s) => {
+                    self.leaf(encode_string(s, self.schema, self.strict))
+                }
+                other => Err(mismatch(other)),
+            },
+            "bytes" => match map.next_value::<serde_json::Value>()? {
+                serde_json::Value::String(s) => self.leaf(encode_detailed_bytes(s, self.strict)),
+                other => Err(mismatch(other)),
+            },
+            "list" => map.next_value_seed(DetailedListSeed(*self)),
+            "map" => map.next_value_seed(DetailedMapSeed(*self)),
+            _invalid_key => Err(self.fail(MetadataJsonError::InvalidTag(tag.clone()))),
+        }?;
+        if map.next_key::<serde::de::IgnoredAny>()?.is_some() {
+            return Err(self.fail(MetadataJsonError::DetailedMultipleTags));
+        }
+        Ok(cbor)
+    }
+}
+
+impl<'de, 'a> serde::de::DeserializeSeed<'de> for MetadatumCborSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> serde::de::Visitor<'de> for MetadatumCborSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a metadatum")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, _v: bool) -> Result<Vec<u8>, E> {
+        Err(self.fail(MetadataJsonError::BoolFound))
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Vec<u8>, E> {
+        Err(self.fail(MetadataJsonError::NullFound))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Vec<u8>, E> {
+        if supports_tagged_values(self.schema) {
+            return Err(self.fail(MetadataJsonError::DetailedNonObject(JSONValue::from(v))));
+        }
+        self.leaf(Ok(TransactionMetadatum::new_int(Int::from(v))))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Vec<u8>, E> {
+        if supports_tagged_values(self.schema) {
+            return Err(
+                self.fail(MetadataJsonError::DetailedNonObject(JSONValue::Number(
+                    BigInteger::from(v),
+                ))),
+            );
+        }
+        self.leaf(Ok(TransactionMetadatum::new_int(Int::from(v))))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Vec<u8>, E> {
+        Err(self.fail(MetadataJsonError::UnsupportedNumber(v.to_string())))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        if supports_tagged_values(self.schema) {
+            return Err(
+                self.fail(MetadataJsonError::DetailedNonObject(JSONValue::from(
+                    v.to_owned(),
+                ))),
+            );
+        }
+        self.leaf(encode_string(v.to_owned(), self.schema, self.strict))
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, seq: A) -> Result<Vec<u8>, A::Error> {
+        if supports_tagged_values(self.schema) {
+            use serde::Deserialize;
+            let value =
+                serde_json::Value::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))?;
+            return Err(match to_json_value(value) {
+                Ok(value) => self.fail(MetadataJsonError::DetailedNonObject(value)),
+                Err(e) => self.fail(e),
+            });
+        }
+        self.list(seq)
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<Vec<u8>, A::Error> {
+        if supports_tagged_values(self.schema) {
+            self.tagged(map)
+        } else {
+            self.object(map)
+        }
+    }
+}
+
+/// The value of a DetailedSchema {"list": [..]} object
+struct DetailedListSeed<'a>(MetadatumCborSeed<'a>);
+
+impl<'de, 'a> serde::de::DeserializeSeed<'de> for DetailedListSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'a> serde::de::Visitor<'de> for DetailedListSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an array of tagged metadatums")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, seq: A) -> Result<Vec<u8>, A::Error> {
+        self.0.list(seq)
+    }
+}
+
+/// The value of a DetailedSchema {"map": [{"k": .., "v": ..}, ..]} object
+struct DetailedMapSeed<'a>(MetadatumCborSeed<'a>);
+
+impl<'de, 'a> serde::de::DeserializeSeed<'de> for DetailedMapSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'a> serde::de::Visitor<'de> for DetailedMapSeed<'a> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an array of {\"k\": key, \"v\": value} objects")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut cbor_map = CborMapEntries::default();
+        while let Some((key, value)) = seq.next_element_seed(DetailedMapEntrySeed(self.0))? {
+            cbor_map
+                .set(key, value, self.0.strict)
+                .map_err(|e| self.0.fail(e))?;
+        }
+        Ok(cbor_map.finish())
+    }
+}
+
+struct DetailedMapEntrySeed<'a>(MetadatumCborSeed<'a>);
+
+impl<'de, 'a> serde::de::DeserializeSeed<'de> for DetailedMapEntrySeed<'a> {
+    type Value = (Vec<u8>, Vec<u8>);
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> serde::de::Visitor<'de> for DetailedMapEntrySeed<'a> {
+    type Value = (Vec<u8>, Vec<u8>);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a {\"k\": key, \"v\": value} object")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut key, mut value) = (None, None);
+        while let Some(field) = map.next_key::<String>()? {
+            match field.as_str() {
+                "k" => key = Some(map.next_value_seed(self.0)?),
+                "v" => value = Some(map.next_value_seed(self.0)?),
+                _ => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                }
+            }
+        }
+        match (key, value) {
+            (Some(key), Some(value)) => Ok((key, value)),
+            _ => Err(self.0.fail(MetadataJsonError::InvalidMapEntry)),
+        }
+    }
+}
+
+/// CBOR map entries with the same semantics as MetadatumMap::set()
+#[derive(Default)]
+struct CborMapEntries {
+    keys: HashSet<Vec<u8>>,
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl CborMapEntries {
+    fn set(&mut self, key: Vec<u8>, value: Vec<u8>, strict: bool) -> Result<(), MetadataJsonError> {
+        if !self.keys.insert(key.clone()) {
+            if strict {
+                return Err(MetadataJsonError::DuplicateKey(
+                    TransactionMetadatum::from_cbor_bytes(&key)?,
+                ));
+            }
+            self.entries.retain(|(k, _)| *k != key);
+        }
+        self.entries.push((key, value));
+        Ok(())
+    }
+
+    fn finish(self) -> Vec<u8> {
+        let mut serializer = cbor_event::se::Serializer::new_vec();
+        serializer
+            .write_map(cbor_event::Len::Len(self.entries.len() as u64))
+            .expect("writing to a Vec can't fail");
+        let mut bytes = serializer.finalize();
+        for (key, value) in self.entries {
+            bytes.extend(key);
+            bytes.extend(value);
+        }
+        bytes
+    }
+}
+
+// serde_json's Value -> JSONValue conversion panics on non-integer numbers
+fn to_json_value(value: serde_json::Value) -> Result<JSONValue, MetadataJsonError> {
+    use std::str::FromStr;
+    Ok(match value {
+        serde_json::Value::Number(n) => JSONValue::Number(
+            BigInteger::from_str(&n.to_string())
+                .map_err(|_| MetadataJsonError::UnsupportedNumber(n.to_string()))?,
+        ),
+        serde_json::Value::Array(array) => JSONValue::Array(
+            array
+                .into_iter()
+                .map(to_json_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        serde_json::Value::Object(obj) => JSONValue::Object(
+            obj.into_iter()
+                .map(|(k, v)| to_json_value(v).map(|v| (k, v)))
+                .collect::<Result<_, _>>()?,
+        ),
+        other => JSONValue::from(other),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -514,4 +1072,135 @@ mod tests {
         let output_json: serde_json::Value = serde_json::from_str(&output_str).unwrap();
         assert_eq!(input_json, output_json);
     }
+
+    #[test]
+    fn json_encoding_strict() {
+        // "007" would come back as "7"
+        let basic = "{\"007\": \"0xDEADBEEF\"}";
+        encode_json_str_to_metadatum(basic, MetadataJsonSchema::BasicConversions).unwrap();
+        assert!(matches!(
+            encode_json_str_to_metadatum_strict(basic, MetadataJsonSchema::BasicConversions),
+            Err(MetadataJsonError::NotRoundTrippable(_))
+        ));
+        let detailed = "{\"map\":[
+            {\"k\":{\"int\":1},\"v\":{\"int\":2}},
+            {\"k\":{\"int\":1},\"v\":{\"int\":3}}
+        ]}";
+        let metadata =
+            encode_json_str_to_metadatum(detailed, MetadataJsonSchema::DetailedSchema).unwrap();
+        assert_eq!(metadata.as_map().unwrap().len(), 1);
+        assert!(matches!(
+            encode_json_str_to_metadatum_strict(detailed, MetadataJsonSchema::DetailedSchema),
+            Err(MetadataJsonError::DuplicateKey(_))
+        ));
+        assert!(matches!(
+            encode_json_str_to_metadatum_strict(
+                "{\"bytes\":\"DEADBEEF\"}",
+                MetadataJsonSchema::DetailedSchema
+            ),
+            Err(MetadataJsonError::NotRoundTrippable(_))
+        ));
+    }
+
+    #[test]
+    fn json_decoding_strict() {
+        let mut map = MetadatumMap::new();
+        map.entries.push((
+            TransactionMetadatum::new_int(Int::from(1u64)),
+            TransactionMetadatum::new_int(Int::from(2u64)),
+        ));
+        map.entries.push((
+            TransactionMetadatum::new_int(Int::from(1u64)),
+            TransactionMetadatum::new_int(Int::from(3u64)),
+        ));
+        let duplicates = TransactionMetadatum::new_map(map);
+        decode_metadatum_to_json_str(&duplicates, MetadataJsonSchema::DetailedSchema).unwrap();
+        assert!(matches!(
+            decode_metadatum_to_json_str_strict(&duplicates, MetadataJsonSchema::DetailedSchema),
+            Err(MetadataJsonError::DuplicateKey(_))
+        ));
+        let hex_text = TransactionMetadatum::new_text("0xabcd".to_owned()).unwrap();
+        decode_metadatum_to_json_str_strict(&hex_text, MetadataJsonSchema::NoConversions).unwrap();
+        assert!(matches!(
+            decode_metadatum_to_json_str_strict(&hex_text, MetadataJsonSchema::BasicConversions),
+            Err(MetadataJsonError::NotRoundTrippable(_))
+        ));
+        let bytes_key = encode_json_str_to_metadatum(
+            "{\"map\":[{\"k\":{\"bytes\":\"ff\"},\"v\":{\"int\":0}}]}",
+            MetadataJsonSchema::DetailedSchema,
+        )
+        .unwrap();
+        assert!(matches!(
+            decode_metadatum_to_json_str_strict(&bytes_key, MetadataJsonSchema::NoConversions),
+            Err(MetadataJsonError::InvalidKeyType(_))
+        ));
+    }
+
+    #[test]
+    fn json_encoding_streaming() {
+        let inputs = [
+            (
+                "{\"tags\": [0, 264, -1024, 32], \"comment\": \"happy birthday\", \"a\": {}}",
+                MetadataJsonSchema::NoConversions,
+            ),
+            (
+                "{\"0x8badf00d\": \"0xdeadbeef\",\"9\": 5,\"obj\": {\"a\":[{\"5\": 2},{}]}}",
+                MetadataJsonSchema::BasicConversions,
+            ),
+            (
+                "{\"map\":[
+                    {\"v\":{\"bytes\":\"deadbeef\"},\"k\":{\"bytes\":\"8badf00d\"}},
+                    {\"k\":{\"list\":[{\"int\":-5},{\"string\":\"x\"}]},\"v\":{\"map\":[]}}
+                ]}",
+                MetadataJsonSchema::DetailedSchema,
+            ),
+        ];
+        for (input, schema) in inputs {
+            let expected = encode_json_str_to_metadatum(input, schema)
+                .unwrap()
+                .to_cbor_bytes();
+            let mut streamed = Vec::new();
+            encode_json_reader_to_metadatum_cbor(input.as_bytes(), &mut streamed, schema).unwrap();
+            assert_eq!(expected, streamed);
+        }
+        // JSON object duplicates are only visible when streaming
+        let duplicates = "{\"a\": 1, \"b\": 2, \"a\": 3}";
+        let mut streamed = Vec::new();
+        encode_json_reader_to_metadatum_cbor(
+            duplicates.as_bytes(),
+            &mut streamed,
+            MetadataJsonSchema::NoConversions,
+        )
+        .unwrap();
+        assert_eq!(
+            encode_json_str_to_metadatum(duplicates, MetadataJsonSchema::NoConversions)
+                .unwrap()
+                .to_cbor_bytes(),
+            streamed
+        );
+        assert!(matches!(
+            encode_json_reader_to_metadatum_cbor_strict(
+                duplicates.as_bytes(),
+                Vec::new(),
+                MetadataJsonSchema::NoConversions
+            ),
+            Err(MetadataJsonError::DuplicateKey(_))
+        ));
+        assert!(matches!(
+            encode_json_reader_to_metadatum_cbor(
+                "[1, true]".as_bytes(),
+                Vec::new(),
+                MetadataJsonSchema::NoConversions
+            ),
+            Err(MetadataJsonError::BoolFound)
+        ));
+        assert!(matches!(
+            encode_json_reader_to_metadatum_cbor(
+                "{\"list\":[],\"int\":5}".as_bytes(),
+                Vec::new(),
+                MetadataJsonSchema::DetailedSchema
+            ),
+            Err(MetadataJsonError::DetailedMultipleTags)
+        ));
+    }
 }
@@ -1,3 +1,4 @@
 pub(crate) mod json_serialize;
 pub mod metadatums;
 pub mod plutus_datums;
+pub mod views;
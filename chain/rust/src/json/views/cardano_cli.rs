@@ -0,0 +1,965 @@
+//! The JSON printed by `cardano-cli conway transaction view --output-json`
+//!
+//! This view is meant for humans so decoding it is best-effort. The following are not
+//! part of it and are lost when going through it:
+//! * the network id and script data hash of the body
+//! * the witness set's scripts, datums and redeemers
+//! * metadata that doesn't fit cardano-cli's no-schema metadata JSON
+//!   (see MetadataJsonSchema::BasicConversions)
+//! * pool margins that don't have an exact decimal representation (e.g. 1/3)
+//!
+//! Governance actions and votes are not yet pretty-printed by cardano-cli so they use the
+//! same layout as the Ogmios view.
+
+use std::str::FromStr;
+
+use cml_core::serialization::{Deserialize, ToBytes};
+use cml_crypto::{Ed25519KeyHash, Ed25519Signature, PublicKey, RawBytesEncoding};
+use serde_json::json;
+
+use crate::{
+    address::{Address, RewardAccount},
+    assets::{AssetName, Mint, MultiAsset},
+    auxdata::{AuxiliaryData, ConwayFormatAuxData, Metadata},
+    byron::AddrAttributes,
+    certs::{Certificate, Credential, DRep, PoolMetadata, PoolParams, Url},
+    crypto::{hash::hash_auxiliary_data, BootstrapWitness, Vkeywitness},
+    json::{
+        metadatums::{
+            decode_metadatum_to_json_value, encode_json_value_to_metadatum, MetadataJsonSchema,
+        },
+        plutus_datums::{
+            decode_plutus_datum_to_json_value, encode_json_value_to_plutus_datum,
+            CardanoNodePlutusDatumSchema,
+        },
+    },
+    text_envelope::{FromTextEnvelope, TextEnvelope, ToTextEnvelope},
+    transaction::{
+        DatumOption, Transaction, TransactionBody, TransactionInput, TransactionOutput,
+        TransactionWitnessSet,
+    },
+    Script, UnitInterval, Value,
+};
+
+use super::{
+    address_from_string, address_to_string, as_array, as_object, as_str, as_u64, bytes_from_hex,
+    field, from_hex, invalid, ogmios, opt_field, pool_id_from_bech32, pool_id_to_bech32,
+    ChainJsonError, JsonObject, JsonValue,
+};
+
+fn lovelace_to_json(coin: u64) -> JsonValue {
+    format!("{coin} Lovelace").into()
+}
+
+fn lovelace_from_json(json: &JsonValue, name: &str) -> Result<u64, ChainJsonError> {
+    as_str(json, name)?
+        .strip_suffix(" Lovelace")
+        .and_then(|coin| coin.parse().ok())
+        .ok_or_else(|| invalid(name, "expected an amount like \"1000000 Lovelace\""))
+}
+
+fn asset_name_to_key(name: &AssetName) -> String {
+    let bytes = name.get();
+    if bytes.is_empty() {
+        "default asset".to_owned()
+    } else if bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        format!(
+            "asset {} ({})",
+            hex::encode(bytes),
+            String::from_utf8_lossy(bytes)
+        )
+    } else {
+        format!("asset {}", hex::encode(bytes))
+    }
+}
+
+fn asset_name_from_key(key: &str) -> Result<AssetName, ChainJsonError> {
+    if key == "default asset" {
+        return AssetName::new(Vec::new()).map_err(Into::into);
+    }
+    let name_hex = key
+        .strip_prefix("asset ")
+        .and_then(|rest| rest.split(' ').next())
+        .ok_or_else(|| invalid(key, "expected \"asset <hex>\" or \"default asset\""))?;
+    let bytes = hex::decode(name_hex).map_err(|e| invalid(key, e))?;
+    AssetName::new(bytes).map_err(Into::into)
+}
+
+fn policy_from_key(key: &str) -> Result<cml_crypto::ScriptHash, ChainJsonError> {
+    let policy_hex = key
+        .strip_prefix("policy ")
+        .ok_or_else(|| invalid(key, "expected \"policy <hex>\""))?;
+    cml_crypto::ScriptHash::from_hex(policy_hex).map_err(|e| invalid(key, e))
+}
+
+pub(crate) fn value_to_json(value: &Value) -> JsonValue {
+    let mut obj = JsonObject::new();
+    obj.insert("lovelace".to_owned(), value.coin.into());
+    for (policy_id, assets) in value.multiasset.iter() {
+        let assets = assets
+            .iter()
+            .map(|(name, amount)| (asset_name_to_key(name), JsonValue::from(*amount)))
+            .collect::<JsonObject>();
+        obj.insert(format!("policy {}", policy_id.to_hex()), assets.into());
+    }
+    obj.into()
+}
+
+pub(crate) fn value_from_json(json: &JsonValue) -> Result<Value, ChainJsonError> {
+    let mut coin = None;
+    let mut multiasset = MultiAsset::new();
+    for (key, entry) in as_object(json, "amount")? {
+        if key == "lovelace" {
+            coin = Some(as_u64(entry, key)?);
+        } else {
+            let policy_id = policy_from_key(key)?;
+            for (name, amount) in as_object(entry, key)? {
+                multiasset.set(policy_id, asset_name_from_key(name)?, as_u64(amount, name)?);
+            }
+        }
+    }
+    let coin = coin.ok_or_else(|| ChainJsonError::MissingField("lovelace".to_owned()))?;
+    Ok(Value::new(coin, multiasset))
+}
+
+fn mint_to_json(mint: &Mint) -> JsonValue {
+    mint.iter()
+        .map(|(policy_id, assets)| {
+            let assets = assets
+                .iter()
+                .map(|(name, amount)| (asset_name_to_key(name), JsonValue::from(*amount)))
+                .collect::<JsonObject>();
+            (format!("policy {}", policy_id.to_hex()), assets.into())
+        })
+        .collect::<JsonObject>()
+        .into()
+}
+
+fn mint_from_json(json: &JsonValue) -> Result<Mint, ChainJsonError> {
+    let mut mint = Mint::new();
+    for (key, assets) in as_object(json, "mint")? {
+        let policy_id = policy_from_key(key)?;
+        for (name, amount) in as_object(assets, key)? {
+            let amount = amount
+                .as_i64()
+                .ok_or_else(|| invalid(name, "expected an integer"))?;
+            mint.set(policy_id, asset_name_from_key(name)?, amount);
+        }
+    }
+    Ok(mint)
+}
+
+fn input_to_json(input: &TransactionInput) -> JsonValue {
+    format!("{}#{}", input.transaction_id.to_hex(), input.index).into()
+}
+
+fn input_from_json(json: &JsonValue, name: &str) -> Result<TransactionInput, ChainJsonError> {
+    let text = as_str(json, name)?;
+    let (id, index) = text
+        .split_once('#')
+        .ok_or_else(|| invalid(name, "expected an input like \"<tx id>#<index>\""))?;
+    Ok(TransactionInput::new(
+        cml_crypto::TransactionHash::from_hex(id).map_err(|e| invalid(name, e))?,
+        index.parse().map_err(|e| invalid(name, e))?,
+    ))
+}
+
+fn inputs_to_json(inputs: &[TransactionInput]) -> JsonValue {
+    inputs.iter().map(input_to_json).collect()
+}
+
+fn inputs_from_json(json: &JsonValue, name: &str) -> Result<Vec<TransactionInput>, ChainJsonError> {
+    as_array(json, name)?
+        .iter()
+        .map(|input| input_from_json(input, name))
+        .collect()
+}
+
+/// The ledger's credential JSON e.g. {"keyHash": "<hex>"}
+fn credential_to_json(credential: &Credential) -> JsonValue {
+    match credential {
+        Credential::PubKey { hash, .. } => json!({ "keyHash": hash.to_hex() }),
+        Credential::Script { hash, .. } => json!({ "scriptHash": hash.to_hex() }),
+    }
+}
+
+fn credential_from_json(json: &JsonValue, name: &str) -> Result<Credential, ChainJsonError> {
+    let obj = as_object(json, name)?;
+    if let Some(hash) = opt_field(obj, "keyHash") {
+        from_hex(hash, "keyHash").map(Credential::new_pub_key)
+    } else {
+        from_hex(field(obj, "scriptHash")?, "scriptHash").map(Credential::new_script)
+    }
+}
+
+fn network_name(network: u8) -> &'static str {
+    match network {
+        1 => "Mainnet",
+        _ => "Testnet",
+    }
+}
+
+fn script_to_json(script: &Script) -> Result<JsonValue, ChainJsonError> {
+    let language = match script {
+        Script::Native { .. } => "SimpleScriptLanguage",
+        Script::PlutusV1 { .. } => "PlutusScriptLanguage PlutusScriptV1",
+        Script::PlutusV2 { .. } => "PlutusScriptLanguage PlutusScriptV2",
+        Script::PlutusV3 { .. } => "PlutusScriptLanguage PlutusScriptV3",
+    };
+    Ok(json!({
+        "scriptLanguage": language,
+        "script": serde_json::to_value(script.to_text_envelope())?,
+    }))
+}
+
+fn script_from_json(json: &JsonValue) -> Result<Script, ChainJsonError> {
+    let obj = as_object(json, "script")?;
+    let envelope: TextEnvelope = serde_json::from_value(field(obj, "script")?.clone())?;
+    Script::from_text_envelope(&envelope).map_err(|e| invalid("script", e))
+}
+
+pub(crate) fn output_to_json(output: &TransactionOutput) -> Result<JsonValue, ChainJsonError> {
+    let address = output.address();
+    let mut obj = JsonObject::new();
+    obj.insert("address".to_owned(), address_to_string(address)?.into());
+    let era = match address {
+        Address::Byron(_) => "Byron",
+        _ => "Shelley",
+    };
+    obj.insert("address era".to_owned(), era.into());
+    if let Ok(network) = address.network_id() {
+        obj.insert("network".to_owned(), network_name(network).into());
+    }
+    match address.payment_cred() {
+        Some(Credential::PubKey { hash, .. }) => {
+            obj.insert(
+                "payment credential key hash".to_owned(),
+                hash.to_hex().into(),
+            );
+        }
+        Some(Credential::Script { hash, .. }) => {
+            obj.insert(
+                "payment credential script hash".to_owned(),
+                hash.to_hex().into(),
+            );
+        }
+        None => (),
+    }
+    let stake_reference = match address {
+        Address::Base(base) => match &base.stake {
+            Credential::PubKey { hash, .. } => {
+                json!({ "stake credential key hash": hash.to_hex() })
+            }
+            Credential::Script { hash, .. } => {
+                json!({ "stake credential script hash": hash.to_hex() })
+            }
+        },
+        Address::Ptr(ptr) => json!({
+            "slot": ptr.stake.slot(),
+            "transaction index": ptr.stake.tx_index(),
+            "certificate index": ptr.stake.cert_index(),
+        }),
+        _ => "none".into(),
+    };
+    obj.insert("stake reference".to_owned(), stake_reference);
+    obj.insert("amount".to_owned(), value_to_json(output.amount()));
+    let datum = match output.datum() {
+        Some(DatumOption::Hash { datum_hash, .. }) => datum_hash.to_hex().into(),
+        Some(DatumOption::Datum { datum, .. }) => {
+            decode_plutus_datum_to_json_value(&datum, CardanoNodePlutusDatumSchema::DetailedSchema)?
+                .into()
+        }
+        None => JsonValue::Null,
+    };
+    obj.insert("datum".to_owned(), datum);
+    let reference_script = output.script_ref().map(script_to_json).transpose()?;
+    obj.insert(
+        "reference script".to_owned(),
+        reference_script.unwrap_or(JsonValue::Null),
+    );
+    Ok(obj.into())
+}
+
+/// Only "address", "amount", "datum" and "reference script" are read, the rest is derived
+/// from the address.
+pub(crate) fn output_from_json(json: &JsonValue) -> Result<TransactionOutput, ChainJsonError> {
+    let obj = as_object(json, "output")?;
+    let datum = opt_field(obj, "datum")
+        .map(|datum| match datum {
+            JsonValue::String(_) => from_hex(datum, "datum").map(DatumOption::new_hash),
+            _ => encode_json_value_to_plutus_datum(
+                datum.clone().into(),
+                CardanoNodePlutusDatumSchema::DetailedSchema,
+            )
+            .map(DatumOption::new_datum)
+            .map_err(Into::into),
+        })
+        .transpose()?;
+    Ok(TransactionOutput::new(
+        address_from_string(field(obj, "address")?, "address")?,
+        value_from_json(field(obj, "amount")?)?,
+        datum,
+        opt_field(obj, "reference script")
+            .map(script_from_json)
+            .transpose()?,
+    ))
+}
+
+fn drep_to_json(drep: &DRep) -> JsonValue {
+    match drep {
+        DRep::Key { pool, .. } => json!({ "keyHash": pool.to_hex() }),
+        DRep::Script { script_hash, .. } => json!({ "scriptHash": script_hash.to_hex() }),
+        DRep::AlwaysAbstain { .. } => "alwaysAbstain".into(),
+        DRep::AlwaysNoConfidence { .. } => "alwaysNoConfidence".into(),
+    }
+}
+
+fn drep_from_json(json: &JsonValue) -> Result<DRep, ChainJsonError> {
+    match json.as_str() {
+        Some("alwaysAbstain") => Ok(DRep::new_always_abstain()),
+        Some("alwaysNoConfidence") => Ok(DRep::new_always_no_confidence()),
+        _ => Ok(match credential_from_json(json, "drep")? {
+            Credential::PubKey { hash, .. } => DRep::new_key(hash),
+            Credential::Script { hash, .. } => DRep::new_script(hash),
+        }),
+    }
+}
+
+/// cardano-cli prints pool margins as decimals
+fn margin_to_json(margin: &UnitInterval) -> JsonValue {
+    (margin.start as f64 / margin.end as f64).into()
+}
+
+fn margin_from_json(json: &JsonValue) -> Result<UnitInterval, ChainJsonError> {
+    let margin = json
+        .as_f64()
+        .filter(|margin| (0.0..=1.0).contains(margin))
+        .ok_or_else(|| invalid("margin", "expected a number between 0 and 1"))?;
+    // the shortest round-trip representation is what was printed by margin_to_json
+    let text = margin.to_string();
+    let decimals = text.split_once('.').map(|(_, d)| d.len()).unwrap_or(0) as u32;
+    let end = 10u64.pow(decimals);
+    let start = u64::from_str(&text.replace('.', "")).map_err(|e| invalid("margin", e))?;
+    Ok(UnitInterval::new(start, end))
+}
+
+fn pool_params_to_json(params: &PoolParams) -> Result<JsonValue, ChainJsonError> {
+    let metadata = params.pool_metadata.as_ref().map(|metadata| {
+        json!({
+            "url": metadata.url.get(),
+            "hash": metadata.pool_metadata_hash.to_hex(),
+        })
+    });
+    Ok(json!({
+        "pool id": pool_id_to_bech32(&params.operator),
+        "vrf key hash": params.vrf_keyhash.to_hex(),
+        "pledge": lovelace_to_json(params.pledge),
+        "cost": lovelace_to_json(params.cost),
+        "margin": margin_to_json(&params.margin),
+        "reward account": params.reward_account.clone().to_address().to_bech32(None)?,
+        "owners": params.pool_owners.iter().map(|owner| owner.to_hex()).collect::<Vec<_>>(),
+        "relays": params.relays.iter().map(ogmios::relay_to_json).collect::<Vec<_>>(),
+        "metadata": metadata,
+    }))
+}
+
+fn pool_params_from_json(json: &JsonValue) -> Result<PoolParams, ChainJsonError> {
+    let obj = as_object(json, "Pool registration")?;
+    let pool_metadata = opt_field(obj, "metadata")
+        .map(|metadata| {
+            let metadata = as_object(metadata, "metadata")?;
+            Ok::<_, ChainJsonError>(PoolMetadata::new(
+                Url::new(as_str(field(metadata, "url")?, "url")?.to_owned())?,
+                from_hex(field(metadata, "hash")?, "hash")?,
+            ))
+        })
+        .transpose()?;
+    let reward_account = RewardAccount::from_address(&address_from_string(
+        field(obj, "reward account")?,
+        "reward account",
+    )?)
+    .ok_or_else(|| invalid("reward account", "expected a reward account"))?;
+    Ok(PoolParams::new(
+        pool_id_from_bech32(field(obj, "pool id")?, "pool id")?,
+        from_hex(field(obj, "vrf key hash")?, "vrf key hash")?,
+        lovelace_from_json(field(obj, "pledge")?, "pledge")?,
+        lovelace_from_json(field(obj, "cost")?, "cost")?,
+        margin_from_json(field(obj, "margin")?)?,
+        reward_account,
+        as_array(field(obj, "owners")?, "owners")?
+            .iter()
+            .map(|owner| from_hex(owner, "owners"))
+            .collect::<Result<_, _>>()?,
+        as_array(field(obj, "relays")?, "relays")?
+            .iter()
+            .map(ogmios::relay_from_json)
+            .collect::<Result<_, _>>()?,
+        pool_metadata,
+    ))
+}
+
+pub(crate) fn certificate_to_json(cert: &Certificate) -> Result<JsonValue, ChainJsonError> {
+    let (name, contents) = match cert {
+        Certificate::StakeRegistration(reg) => (
+            "Stake address registration",
+            json!({ "stake credential": credential_to_json(&reg.stake_credential) }),
+        ),
+        Certificate::RegCert(reg) => (
+            "Stake address registration",
+            json!({
+                "stake credential": credential_to_json(&reg.stake_credential),
+                "deposit": lovelace_to_json(reg.coin),
+            }),
+        ),
+        Certificate::StakeDeregistration(dereg) => (
+            "Stake address deregistration",
+            json!({ "stake credential": credential_to_json(&dereg.stake_credential) }),
+        ),
+        Certificate::UnregCert(dereg) => (
+            "Stake address deregistration",
+            json!({
+                "stake credential": credential_to_json(&dereg.stake_credential),
+                "refund": lovelace_to_json(dereg.coin),
+            }),
+        ),
+        Certificate::StakeDelegation(deleg) => (
+            "Stake address delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "stake pool": pool_id_to_bech32(&deleg.pool),
+            }),
+        ),
+        Certificate::VoteDelegCert(deleg) => (
+            "Stake address delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "drep": drep_to_json(&deleg.d_rep),
+            }),
+        ),
+        Certificate::StakeVoteDelegCert(deleg) => (
+            "Stake address delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "stake pool": pool_id_to_bech32(&deleg.pool),
+                "drep": drep_to_json(&deleg.d_rep),
+            }),
+        ),
+        Certificate::StakeRegDelegCert(deleg) => (
+            "Stake address registration and delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "stake pool": pool_id_to_bech32(&deleg.pool),
+                "deposit": lovelace_to_json(deleg.coin),
+            }),
+        ),
+        Certificate::VoteRegDelegCert(deleg) => (
+            "Stake address registration and delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "drep": drep_to_json(&deleg.d_rep),
+                "deposit": lovelace_to_json(deleg.coin),
+            }),
+        ),
+        Certificate::StakeVoteRegDelegCert(deleg) => (
+            "Stake address registration and delegation",
+            json!({
+                "stake credential": credential_to_json(&deleg.stake_credential),
+                "stake pool": pool_id_to_bech32(&deleg.pool),
+                "drep": drep_to_json(&deleg.d_rep),
+                "deposit": lovelace_to_json(deleg.coin),
+            }),
+        ),
+        Certificate::PoolRegistration(reg) => {
+            ("Pool registration", pool_params_to_json(&reg.pool_params)?)
+        }
+        Certificate::PoolRetirement(retirement) => (
+            "Pool retirement",
+            json!({
+                "pool id": pool_id_to_bech32(&retirement.pool),
+                "epoch": retirement.epoch,
+            }),
+        ),
+        Certificate::AuthCommitteeHotCert(auth) => (
+            "Constitutional committee member hot key registration",
+            json!({
+                "cold credential": credential_to_json(&auth.committee_cold_credential),
+                "hot credential": credential_to_json(&auth.committee_hot_credential),
+            }),
+        ),
+        Certificate::ResignCommitteeColdCert(resign) => (
+            "Constitutional committee cold key resignation",
+            json!({ "cold credential": credential_to_json(&resign.committee_cold_credential) }),
+        ),
+        Certificate::RegDrepCert(reg) => (
+            "Drep registration certificate",
+            json!({
+                "drep credential": credential_to_json(&reg.drep_credential),
+                "deposit": lovelace_to_json(reg.coin),
+                "anchor": reg.anchor.as_ref().map(ogmios::anchor_to_json),
+            }),
+        ),
+        Certificate::UnregDrepCert(unreg) => (
+            "Drep unregistration certificate",
+            json!({
+                "drep credential": credential_to_json(&unreg.drep_credential),
+                "refund": lovelace_to_json(unreg.coin),
+            }),
+        ),
+        Certificate::UpdateDrepCert(update) => (
+            "Drep certificate update",
+            json!({
+                "drep credential": credential_to_json(&update.drep_credential),
+                "anchor": update.anchor.as_ref().map(ogmios::anchor_to_json),
+            }),
+        ),
+    };
+    let mut obj = JsonObject::new();
+    obj.insert(name.to_owned(), contents);
+    Ok(obj.into())
+}
+
+pub(crate) fn certificate_from_json(json: &JsonValue) -> Result<Certificate, ChainJsonError> {
+    let obj = as_object(json, "certificate")?;
+    let (name, contents) = match obj.iter().next() {
+        Some(entry) if obj.len() == 1 => entry,
+        _ => {
+            return Err(invalid(
+                "certificate",
+                "expected an object with a single key naming the certificate",
+            ))
+        }
+    };
+    let contents = as_object(contents, name)?;
+    let coin = |key: &str| {
+        opt_field(contents, key)
+            .map(|coin| lovelace_from_json(coin, key))
+            .transpose()
+    };
+    let required_coin =
+        |key: &str| coin(key)?.ok_or_else(|| ChainJsonError::MissingField(key.to_owned()));
+    let credential = |key: &str| credential_from_json(field(contents, key)?, key);
+    let pool = opt_field(contents, "stake pool")
+        .map(|pool| pool_id_from_bech32(pool, "stake pool"))
+        .transpose()?;
+    let drep = opt_field(contents, "drep")
+        .map(drep_from_json)
+        .transpose()?;
+    let anchor = opt_field(contents, "anchor")
+        .map(ogmios::anchor_from_json)
+        .transpose()?;
+    match name.as_str() {
+        "Stake address registration" => {
+            let stake_credential = credential("stake credential")?;
+            Ok(match coin("deposit")? {
+                Some(deposit) => Certificate::new_reg_cert(stake_credential, deposit),
+                None => Certificate::new_stake_registration(stake_credential),
+            })
+        }
+        "Stake address deregistration" => {
+            let stake_credential = credential("stake credential")?;
+            Ok(match coin("refund")? {
+                Some(refund) => Certificate::new_unreg_cert(stake_credential, refund),
+                None => Certificate::new_stake_deregistration(stake_credential),
+            })
+        }
+        "Stake address delegation" => {
+            let stake_credential = credential("stake credential")?;
+            match (pool, drep) {
+                (Some(pool), None) => Ok(Certificate::new_stake_delegation(stake_credential, pool)),
+                (None, Some(drep)) => Ok(Certificate::new_vote_deleg_cert(stake_credential, drep)),
+                (Some(pool), Some(drep)) => Ok(Certificate::new_stake_vote_deleg_cert(
+                    stake_credential,
+                    pool,
+                    drep,
+                )),
+                (None, None) => Err(ChainJsonError::MissingField("stake pool".to_owned())),
+            }
+        }
+        "Stake address registration and delegation" => {
+            let stake_credential = credential("stake credential")?;
+            let deposit = required_coin("deposit")?;
+            match (pool, drep) {
+                (Some(pool), None) => Ok(Certificate::new_stake_reg_deleg_cert(
+                    stake_credential,
+                    pool,
+                    deposit,
+                )),
+                (None, Some(drep)) => Ok(Certificate::new_vote_reg_deleg_cert(
+                    stake_credential,
+                    drep,
+                    deposit,
+                )),
+                (Some(pool), Some(drep)) => Ok(Certificate::new_stake_vote_reg_deleg_cert(
+                    stake_credential,
+                    pool,
+                    drep,
+                    deposit,
+                )),
+                (None, None) => Err(ChainJsonError::MissingField("stake pool".to_owned())),
+            }
+        }
+        "Pool registration" => Ok(Certificate::new_pool_registration(pool_params_from_json(
+            &contents.clone().into(),
+        )?)),
+        "Pool retirement" => Ok(Certificate::new_pool_retirement(
+            pool_id_from_bech32(field(contents, "pool id")?, "pool id")?,
+            as_u64(field(contents, "epoch")?, "epoch")?,
+        )),
+        "Constitutional committee member hot key registration" => {
+            Ok(Certificate::new_auth_committee_hot_cert(
+                credential("cold credential")?,
+                credential("hot credential")?,
+            ))
+        }
+        "Constitutional committee cold key resignation" => Ok(
+            Certificate::new_resign_committee_cold_cert(credential("cold credential")?),
+        ),
+        "Drep registration certificate" => Ok(Certificate::new_reg_drep_cert(
+            credential("drep credential")?,
+            required_coin("deposit")?,
+            anchor,
+        )),
+        "Drep unregistration certificate" => Ok(Certificate::new_unreg_drep_cert(
+            credential("drep credential")?,
+            required_coin("refund")?,
+        )),
+        "Drep certificate update" => Ok(Certificate::new_update_drep_cert(
+            credential("drep credential")?,
+            anchor,
+        )),
+        other => Err(ChainJsonError::Unsupported(format!("certificate {other}"))),
+    }
+}
+
+fn optional<T, F>(value: Option<&T>, f: F) -> Result<JsonValue, ChainJsonError>
+where
+    F: FnOnce(&T) -> Result<JsonValue, ChainJsonError>,
+{
+    value
+        .map(f)
+        .transpose()
+        .map(|json| json.unwrap_or(JsonValue::Null))
+}
+
+fn auxiliary_scripts(auxiliary_data: &AuxiliaryData) -> Vec<Script> {
+    let mut scripts = Vec::new();
+    if let Some(native_scripts) = auxiliary_data.native_scripts() {
+        scripts.extend(native_scripts.iter().cloned().map(Script::new_native));
+    }
+    if let Some(v1) = auxiliary_data.plutus_v1_scripts() {
+        scripts.extend(v1.iter().cloned().map(Script::new_plutus_v1));
+    }
+    if let Some(v2) = auxiliary_data.plutus_v2_scripts() {
+        scripts.extend(v2.iter().cloned().map(Script::new_plutus_v2));
+    }
+    if let AuxiliaryData::Conway(conway) = auxiliary_data {
+        if let Some(v3) = &conway.plutus_v3_scripts {
+            scripts.extend(v3.iter().cloned().map(Script::new_plutus_v3));
+        }
+    }
+    scripts
+}
+
+fn metadata_to_json(metadata: &Metadata) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    for (label, metadatum) in metadata.entries.iter() {
+        let json = decode_metadatum_to_json_value(metadatum, MetadataJsonSchema::BasicConversions)?;
+        obj.insert(label.to_string(), json.into());
+    }
+    Ok(obj.into())
+}
+
+fn metadata_from_json(json: &JsonValue) -> Result<Metadata, ChainJsonError> {
+    let mut metadata = Metadata::new();
+    for (label, metadatum) in as_object(json, "metadata")? {
+        let label_num = label
+            .parse::<u64>()
+            .map_err(|e| invalid("metadata", format!("{label}: {e}")))?;
+        metadata.entries.push((
+            label_num,
+            encode_json_value_to_metadatum(
+                metadatum.clone().into(),
+                MetadataJsonSchema::BasicConversions,
+            )?,
+        ));
+    }
+    // JSON objects don't keep the order so use the canonical one
+    metadata.entries.sort_by_key(|(label, _)| *label);
+    Ok(metadata)
+}
+
+fn vkey_witness_to_json(witness: &Vkeywitness) -> JsonValue {
+    json!({
+        "key": format!("VKey (VerKeyEd25519DSIGN \"{}\")", witness.vkey.to_raw_hex()),
+        "signature": format!(
+            "SignedDSIGN (SigEd25519DSIGN \"{}\")",
+            witness.ed25519_signature.to_raw_hex()
+        ),
+    })
+}
+
+fn bootstrap_witness_to_json(witness: &BootstrapWitness) -> JsonValue {
+    json!({
+        "key": format!("VKey (VerKeyEd25519DSIGN \"{}\")", witness.public_key.to_raw_hex()),
+        "signature": format!(
+            "SignedDSIGN (SigEd25519DSIGN \"{}\")",
+            witness.signature.to_raw_hex()
+        ),
+        "chain code": hex::encode(&witness.chain_code),
+        "attributes": hex::encode(witness.attributes.to_bytes()),
+    })
+}
+
+/// Pulls the hex out of e.g. `VKey (VerKeyEd25519DSIGN "<hex>")`
+fn quoted_hex(json: &JsonValue, name: &str) -> Result<JsonValue, ChainJsonError> {
+    let text = as_str(json, name)?;
+    let start = text.find('"').map(|i| i + 1);
+    let end = text.rfind('"');
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => Ok(text[start..end].into()),
+        _ => Err(invalid(name, "expected a quoted hex string")),
+    }
+}
+
+pub(crate) fn transaction_to_json(tx: &Transaction) -> Result<JsonValue, ChainJsonError> {
+    let body = &tx.body;
+    let auxiliary_scripts = tx
+        .auxiliary_data
+        .as_ref()
+        .map(auxiliary_scripts)
+        .filter(|scripts| !scripts.is_empty())
+        .map(|scripts| {
+            scripts
+                .iter()
+                .map(|script| Ok(serde_json::to_value(script.to_text_envelope())?))
+                .collect::<Result<Vec<_>, ChainJsonError>>()
+        })
+        .transpose()?;
+    let metadata = optional(
+        tx.auxiliary_data.as_ref().and_then(AuxiliaryData::metadata),
+        metadata_to_json,
+    )?;
+    let certificates = optional(body.certs.as_ref(), |certs| {
+        certs.iter().map(certificate_to_json).collect()
+    })?;
+    let withdrawals = optional(body.withdrawals.as_ref(), |withdrawals| {
+        withdrawals
+            .iter()
+            .map(|(account, coin)| {
+                Ok(json!({
+                    "address": account.clone().to_address().to_bech32(None)?,
+                    "amount": lovelace_to_json(*coin),
+                    "credential": credential_to_json(&account.payment),
+                }))
+            })
+            .collect()
+    })?;
+    let proposals = optional(body.proposal_procedures.as_ref(), |proposals| {
+        proposals.iter().map(ogmios::proposal_to_json).collect()
+    })?;
+    let mut witnesses = Vec::new();
+    for witness in tx.witness_set.vkeywitnesses.iter().flatten() {
+        witnesses.push(vkey_witness_to_json(witness));
+    }
+    for witness in tx.witness_set.bootstrap_witnesses.iter().flatten() {
+        witnesses.push(bootstrap_witness_to_json(witness));
+    }
+    Ok(json!({
+        "auxiliary scripts": auxiliary_scripts,
+        "certificates": certificates,
+        "collateral inputs": inputs_to_json(body.collateral_inputs.as_deref().unwrap_or_default()),
+        "current treasury value": body.current_treasury_value.map(lovelace_to_json),
+        "era": "Conway",
+        "fee": lovelace_to_json(body.fee),
+        "governance actions": proposals,
+        "inputs": inputs_to_json(&body.inputs),
+        "metadata": metadata,
+        "mint": body.mint.as_ref().map(mint_to_json),
+        "outputs": body.outputs.iter().map(output_to_json).collect::<Result<Vec<_>, _>>()?,
+        "reference inputs": inputs_to_json(body.reference_inputs.as_deref().unwrap_or_default()),
+        "required signers (payment key hashes needed for scripts)": body
+            .required_signers
+            .as_ref()
+            .map(|signers| signers.iter().map(|signer| signer.to_hex()).collect::<Vec<_>>()),
+        "return collateral": optional(body.collateral_return.as_ref(), output_to_json)?,
+        "total collateral": body.total_collateral.map(lovelace_to_json),
+        "treasury donation": body.donation.map(lovelace_to_json),
+        "update proposal": null,
+        "validity range": {
+            "lower bound": body.validity_interval_start,
+            "upper bound": body.ttl,
+        },
+        "voters": body.voting_procedures.as_ref().map(ogmios::votes_to_json),
+        "withdrawals": withdrawals,
+        "witnesses": witnesses,
+    }))
+}
+
+pub(crate) fn transaction_from_json(json: &JsonValue) -> Result<Transaction, ChainJsonError> {
+    let obj = as_object(json, "transaction")?;
+    let lovelace = |key: &str| {
+        opt_field(obj, key)
+            .map(|coin| lovelace_from_json(coin, key))
+            .transpose()
+    };
+    let non_empty_inputs = |key: &str| {
+        opt_field(obj, key)
+            .map(|inputs| inputs_from_json(inputs, key))
+            .transpose()
+            .map(|inputs| inputs.filter(|inputs| !inputs.is_empty()))
+    };
+    let outputs = as_array(field(obj, "outputs")?, "outputs")?
+        .iter()
+        .map(output_from_json)
+        .collect::<Result<_, _>>()?;
+    let mut body = TransactionBody::new(
+        inputs_from_json(field(obj, "inputs")?, "inputs")?,
+        outputs,
+        lovelace_from_json(field(obj, "fee")?, "fee")?,
+    );
+    body.collateral_inputs = non_empty_inputs("collateral inputs")?;
+    body.reference_inputs = non_empty_inputs("reference inputs")?;
+    body.current_treasury_value = lovelace("current treasury value")?;
+    body.donation = lovelace("treasury donation")?;
+    body.total_collateral = lovelace("total collateral")?;
+    body.collateral_return = opt_field(obj, "return collateral")
+        .map(output_from_json)
+        .transpose()?;
+    body.certs = opt_field(obj, "certificates")
+        .map(|certs| {
+            as_array(certs, "certificates")?
+                .iter()
+                .map(certificate_from_json)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    body.proposal_procedures = opt_field(obj, "governance actions")
+        .map(|proposals| {
+            as_array(proposals, "governance actions")?
+                .iter()
+                .map(ogmios::proposal_from_json)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    body.voting_procedures = opt_field(obj, "voters")
+        .map(ogmios::votes_from_json)
+        .transpose()?;
+    body.mint = opt_field(obj, "mint").map(mint_from_json).transpose()?;
+    body.required_signers = opt_field(
+        obj,
+        "required signers (payment key hashes needed for scripts)",
+    )
+    .map(|signers| {
+        as_array(signers, "required signers")?
+            .iter()
+            .map(|signer| from_hex::<Ed25519KeyHash>(signer, "required signers"))
+            .collect::<Result<_, _>>()
+    })
+    .transpose()?;
+    body.withdrawals = opt_field(obj, "withdrawals")
+        .map(|withdrawals| {
+            as_array(withdrawals, "withdrawals")?
+                .iter()
+                .map(|withdrawal| {
+                    let withdrawal = as_object(withdrawal, "withdrawals")?;
+                    let account = RewardAccount::from_address(&address_from_string(
+                        field(withdrawal, "address")?,
+                        "address",
+                    )?)
+                    .ok_or_else(|| invalid("address", "expected a reward account"))?;
+                    Ok((
+                        account,
+                        lovelace_from_json(field(withdrawal, "amount")?, "amount")?,
+                    ))
+                })
+                .collect::<Result<_, ChainJsonError>>()
+        })
+        .transpose()?;
+    if let Some(validity_range) = opt_field(obj, "validity range") {
+        let validity_range = as_object(validity_range, "validity range")?;
+        body.validity_interval_start = opt_field(validity_range, "lower bound")
+            .map(|slot| as_u64(slot, "lower bound"))
+            .transpose()?;
+        body.ttl = opt_field(validity_range, "upper bound")
+            .map(|slot| as_u64(slot, "upper bound"))
+            .transpose()?;
+    }
+
+    let mut witness_set = TransactionWitnessSet::new();
+    for witness in as_array(field(obj, "witnesses")?, "witnesses")? {
+        let witness = as_object(witness, "witnesses")?;
+        let vkey: PublicKey = from_hex(&quoted_hex(field(witness, "key")?, "key")?, "key")?;
+        let signature: Ed25519Signature = from_hex(
+            &quoted_hex(field(witness, "signature")?, "signature")?,
+            "signature",
+        )?;
+        match opt_field(witness, "chain code") {
+            Some(chain_code) => {
+                let attributes = AddrAttributes::from_cbor_bytes(&bytes_from_hex(
+                    field(witness, "attributes")?,
+                    "attributes",
+                )?)?;
+                witness_set
+                    .bootstrap_witnesses
+                    .get_or_insert_with(Vec::new)
+                    .push(BootstrapWitness::new(
+                        vkey,
+                        signature,
+                        bytes_from_hex(chain_code, "chain code")?,
+                        attributes,
+                    )?);
+            }
+            None => witness_set
+                .vkeywitnesses
+                .get_or_insert_with(Vec::new)
+                .push(Vkeywitness::new(vkey, signature)),
+        }
+    }
+
+    let metadata = opt_field(obj, "metadata")
+        .map(metadata_from_json)
+        .transpose()?;
+    let auxiliary_scripts = opt_field(obj, "auxiliary scripts")
+        .map(|scripts| {
+            as_array(scripts, "auxiliary scripts")?
+                .iter()
+                .map(|script| {
+                    let envelope: TextEnvelope = serde_json::from_value(script.clone())?;
+                    Script::from_text_envelope(&envelope)
+                        .map_err(|e| invalid("auxiliary scripts", e))
+                })
+                .collect::<Result<Vec<_>, ChainJsonError>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let auxiliary_data = if metadata.is_some() || !auxiliary_scripts.is_empty() {
+        let mut auxiliary_data = ConwayFormatAuxData::new();
+        auxiliary_data.metadata = metadata;
+        for script in auxiliary_scripts {
+            match script {
+                Script::Native { script, .. } => auxiliary_data
+                    .native_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV1 { script, .. } => auxiliary_data
+                    .plutus_v1_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV2 { script, .. } => auxiliary_data
+                    .plutus_v2_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV3 { script, .. } => auxiliary_data
+                    .plutus_v3_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+            }
+        }
+        Some(AuxiliaryData::new_conway(auxiliary_data))
+    } else {
+        None
+    };
+    body.auxiliary_data_hash = auxiliary_data.as_ref().map(hash_auxiliary_data);
+
+    Ok(Transaction::new(body, witness_set, true, auxiliary_data))
+}
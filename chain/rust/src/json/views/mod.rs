@@ -0,0 +1,630 @@
+//! JSON views of chain types in the formats used by other Cardano tools, as opposed to
+//! the serde JSON which follows CML's own struct layout.
+//!
+//! Both views are lossy in places (e.g. CBOR encoding details, which witnesses came from
+//! auxiliary data) so going through them won't always give back identical bytes.
+//! Where this matters each schema's module documents what is lost. Maps that are JSON
+//! objects in these views (assets, metadata labels, etc) also come back sorted by their key.
+
+pub mod cardano_cli;
+pub mod ogmios;
+
+use crate::{
+    address::{Address, AddressError},
+    byron::ByronAddress,
+    certs::Certificate,
+    json::{metadatums::MetadataJsonError, plutus_datums::PlutusJsonError},
+    transaction::{Transaction, TransactionOutput},
+    Value,
+};
+use cml_core::{error::DeserializeError, serialization::Deserialize};
+use cml_crypto::{Ed25519KeyHash, RawBytesEncoding};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub(crate) type JsonValue = serde_json::Value;
+pub(crate) type JsonObject = serde_json::Map<String, JsonValue>;
+
+/// Which tool's JSON format to follow
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChainJsonSchema {
+    /// `cardano-cli transaction view --output-json` (Conway era).
+    /// Meant for humans: amounts like the fee are rendered as "<n> Lovelace" and scripts,
+    /// datums and redeemers in the witness set are not shown at all.
+    CardanoCliView,
+    /// Ogmios v6 e.g. as returned by its chain synchronization and state query protocols
+    OgmiosV6,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChainJsonError {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Missing field \"{0}\"")]
+    MissingField(String),
+    #[error("Invalid field \"{field}\": {reason}")]
+    InvalidField { field: String, reason: String },
+    // the larger errors are boxed to keep Result<_, ChainJsonError> small
+    #[error("Address: {0}")]
+    Address(Box<AddressError>),
+    #[error("Plutus datum: {0}")]
+    PlutusJson(#[from] PlutusJsonError),
+    #[error("Metadata: {0}")]
+    MetadataJson(Box<MetadataJsonError>),
+    #[error("Deserialization: {0}")]
+    Deserialization(Box<DeserializeError>),
+    #[error("Not representable in this schema: {0}")]
+    Unsupported(String),
+}
+
+impl From<AddressError> for ChainJsonError {
+    fn from(e: AddressError) -> Self {
+        Self::Address(Box::new(e))
+    }
+}
+
+impl From<MetadataJsonError> for ChainJsonError {
+    fn from(e: MetadataJsonError) -> Self {
+        Self::MetadataJson(Box::new(e))
+    }
+}
+
+impl From<DeserializeError> for ChainJsonError {
+    fn from(e: DeserializeError) -> Self {
+        Self::Deserialization(Box::new(e))
+    }
+}
+
+pub(crate) fn invalid(field: &str, reason: impl std::fmt::Display) -> ChainJsonError {
+    ChainJsonError::InvalidField {
+        field: field.to_owned(),
+        reason: reason.to_string(),
+    }
+}
+
+pub(crate) fn as_object<'a>(
+    json: &'a JsonValue,
+    field: &str,
+) -> Result<&'a JsonObject, ChainJsonError> {
+    json.as_object()
+        .ok_or_else(|| invalid(field, "expected an object"))
+}
+
+pub(crate) fn as_array<'a>(
+    json: &'a JsonValue,
+    field: &str,
+) -> Result<&'a Vec<JsonValue>, ChainJsonError> {
+    json.as_array()
+        .ok_or_else(|| invalid(field, "expected an array"))
+}
+
+pub(crate) fn as_str<'a>(json: &'a JsonValue, field: &str) -> Result<&'a str, ChainJsonError> {
+    json.as_str()
+        .ok_or_else(|| invalid(field, "expected a string"))
+}
+
+pub(crate) fn as_u64(json: &JsonValue, field: &str) -> Result<u64, ChainJsonError> {
+    json.as_u64()
+        .ok_or_else(|| invalid(field, "expected an unsigned integer"))
+}
+
+pub(crate) fn as_i64(json: &JsonValue, field: &str) -> Result<i64, ChainJsonError> {
+    json.as_i64()
+        .ok_or_else(|| invalid(field, "expected an integer"))
+}
+
+/// Errors if `field` is missing or null
+pub(crate) fn field<'a>(obj: &'a JsonObject, field: &str) -> Result<&'a JsonValue, ChainJsonError> {
+    opt_field(obj, field).ok_or_else(|| ChainJsonError::MissingField(field.to_owned()))
+}
+
+/// None if `field` is missing or null
+pub(crate) fn opt_field<'a>(obj: &'a JsonObject, field: &str) -> Option<&'a JsonValue> {
+    obj.get(field).filter(|json| !json.is_null())
+}
+
+pub(crate) fn bytes_from_hex(json: &JsonValue, field: &str) -> Result<Vec<u8>, ChainJsonError> {
+    hex::decode(as_str(json, field)?).map_err(|e| invalid(field, e))
+}
+
+pub(crate) fn from_hex<T: RawBytesEncoding>(
+    json: &JsonValue,
+    field: &str,
+) -> Result<T, ChainJsonError> {
+    T::from_raw_bytes(&bytes_from_hex(json, field)?).map_err(|e| invalid(field, e))
+}
+
+pub(crate) fn from_cbor_hex<T: Deserialize>(
+    json: &JsonValue,
+    field: &str,
+) -> Result<T, ChainJsonError> {
+    T::from_cbor_bytes(&bytes_from_hex(json, field)?).map_err(|e| invalid(field, e))
+}
+
+/// bech32 for Shelley addresses, base58 for Byron ones
+pub(crate) fn address_to_string(address: &Address) -> Result<String, ChainJsonError> {
+    match address {
+        Address::Byron(byron) => Ok(byron.to_base58()),
+        _ => address.to_bech32(None).map_err(Into::into),
+    }
+}
+
+pub(crate) fn address_from_string(
+    json: &JsonValue,
+    field: &str,
+) -> Result<Address, ChainJsonError> {
+    let text = as_str(json, field)?;
+    Address::from_bech32(text)
+        .or_else(|_| ByronAddress::from_base58(text).map(ByronAddress::to_address))
+        .map_err(|_| invalid(field, "expected a bech32 or base58 (Byron) address"))
+}
+
+pub(crate) fn pool_id_to_bech32(pool: &Ed25519KeyHash) -> String {
+    pool.to_bech32("pool").unwrap()
+}
+
+pub(crate) fn pool_id_from_bech32(
+    json: &JsonValue,
+    field: &str,
+) -> Result<Ed25519KeyHash, ChainJsonError> {
+    let text = as_str(json, field)?;
+    Ed25519KeyHash::from_bech32(text)
+        .or_else(|_| Ed25519KeyHash::from_hex(text))
+        .map_err(|e| invalid(field, e))
+}
+
+fn to_str(json: JsonValue) -> String {
+    // serde_json::Value's Display never fails
+    json.to_string()
+}
+
+/// Converts JSON to a Value according to ChainJsonSchema
+pub fn encode_json_value_to_value(
+    json: &JsonValue,
+    schema: ChainJsonSchema,
+) -> Result<Value, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::value_from_json(json),
+        ChainJsonSchema::OgmiosV6 => ogmios::value_from_json(json, "value"),
+    }
+}
+
+pub fn encode_json_str_to_value(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<Value, ChainJsonError> {
+    encode_json_value_to_value(&serde_json::from_str(json)?, schema)
+}
+
+/// Converts a Value to JSON according to ChainJsonSchema
+pub fn decode_value_to_json_value(
+    value: &Value,
+    schema: ChainJsonSchema,
+) -> Result<JsonValue, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => Ok(cardano_cli::value_to_json(value)),
+        ChainJsonSchema::OgmiosV6 => Ok(ogmios::value_to_json(value)),
+    }
+}
+
+pub fn decode_value_to_json_str(
+    value: &Value,
+    schema: ChainJsonSchema,
+) -> Result<String, ChainJsonError> {
+    decode_value_to_json_value(value, schema).map(to_str)
+}
+
+/// Converts JSON to a TransactionOutput according to ChainJsonSchema
+pub fn encode_json_value_to_transaction_output(
+    json: &JsonValue,
+    schema: ChainJsonSchema,
+) -> Result<TransactionOutput, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::output_from_json(json),
+        ChainJsonSchema::OgmiosV6 => ogmios::output_from_json(json),
+    }
+}
+
+pub fn encode_json_str_to_transaction_output(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<TransactionOutput, ChainJsonError> {
+    encode_json_value_to_transaction_output(&serde_json::from_str(json)?, schema)
+}
+
+/// Converts a TransactionOutput to JSON according to ChainJsonSchema
+pub fn decode_transaction_output_to_json_value(
+    output: &TransactionOutput,
+    schema: ChainJsonSchema,
+) -> Result<JsonValue, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::output_to_json(output),
+        ChainJsonSchema::OgmiosV6 => ogmios::output_to_json(output),
+    }
+}
+
+pub fn decode_transaction_output_to_json_str(
+    output: &TransactionOutput,
+    schema: ChainJsonSchema,
+) -> Result<String, ChainJsonError> {
+    decode_transaction_output_to_json_value(output, schema).map(to_str)
+}
+
+/// Converts JSON to a Certificate according to ChainJsonSchema
+pub fn encode_json_value_to_certificate(
+    json: &JsonValue,
+    schema: ChainJsonSchema,
+) -> Result<Certificate, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::certificate_from_json(json),
+        ChainJsonSchema::OgmiosV6 => ogmios::certificate_from_json(json),
+    }
+}
+
+pub fn encode_json_str_to_certificate(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<Certificate, ChainJsonError> {
+    encode_json_value_to_certificate(&serde_json::from_str(json)?, schema)
+}
+
+/// Converts a Certificate to JSON according to ChainJsonSchema
+pub fn decode_certificate_to_json_value(
+    cert: &Certificate,
+    schema: ChainJsonSchema,
+) -> Result<JsonValue, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::certificate_to_json(cert),
+        ChainJsonSchema::OgmiosV6 => ogmios::certificate_to_json(cert),
+    }
+}
+
+pub fn decode_certificate_to_json_str(
+    cert: &Certificate,
+    schema: ChainJsonSchema,
+) -> Result<String, ChainJsonError> {
+    decode_certificate_to_json_value(cert, schema).map(to_str)
+}
+
+/// Converts JSON to a Transaction according to ChainJsonSchema
+pub fn encode_json_value_to_transaction(
+    json: &JsonValue,
+    schema: ChainJsonSchema,
+) -> Result<Transaction, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::transaction_from_json(json),
+        ChainJsonSchema::OgmiosV6 => ogmios::transaction_from_json(json),
+    }
+}
+
+pub fn encode_json_str_to_transaction(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<Transaction, ChainJsonError> {
+    encode_json_value_to_transaction(&serde_json::from_str(json)?, schema)
+}
+
+/// Converts a Transaction to JSON according to ChainJsonSchema
+pub fn decode_transaction_to_json_value(
+    tx: &Transaction,
+    schema: ChainJsonSchema,
+) -> Result<JsonValue, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => cardano_cli::transaction_to_json(tx),
+        ChainJsonSchema::OgmiosV6 => ogmios::transaction_to_json(tx),
+    }
+}
+
+pub fn decode_transaction_to_json_str(
+    tx: &Transaction,
+    schema: ChainJsonSchema,
+) -> Result<String, ChainJsonError> {
+    decode_transaction_to_json_value(tx, schema).map(to_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address::{BaseAddress, RewardAddress},
+        assets::{AssetName, Mint, MultiAsset},
+        auxdata::{AuxiliaryData, ConwayFormatAuxData, Metadata, TransactionMetadatum},
+        certs::{Credential, DRep, DnsName, PoolMetadata, PoolParams, Relay, Url},
+        crypto::Vkeywitness,
+        governance::{
+            Anchor, GovAction, GovActionId, ProposalProcedure, Vote, Voter, VotingProcedure,
+            VotingProcedures,
+        },
+        plutus::{ExUnits, PlutusData, PlutusV2Script, Redeemer, RedeemerTag},
+        transaction::{
+            DatumOption, NativeScript, TransactionBody, TransactionInput, TransactionWitnessSet,
+        },
+        NetworkId, ProtocolParamUpdate, Script, UnitInterval,
+    };
+    use cml_core::{ordered_hash_map::OrderedHashMap, serialization::Serialize, Int};
+    use cml_crypto::{PrivateKey, RawBytesEncoding};
+
+    const SCHEMAS: [ChainJsonSchema; 2] =
+        [ChainJsonSchema::CardanoCliView, ChainJsonSchema::OgmiosV6];
+
+    fn hash<T: RawBytesEncoding>(byte: u8, len: usize) -> T {
+        T::from_raw_bytes(&vec![byte; len]).unwrap()
+    }
+
+    fn key_cred(byte: u8) -> Credential {
+        Credential::new_pub_key(hash(byte, 28))
+    }
+
+    fn script_cred(byte: u8) -> Credential {
+        Credential::new_script(hash(byte, 28))
+    }
+
+    fn value() -> Value {
+        let mut multiasset = MultiAsset::new();
+        multiasset.set(hash(1, 28), AssetName::new(b"Token".to_vec()).unwrap(), 5);
+        multiasset.set(hash(2, 28), AssetName::new(vec![0, 255]).unwrap(), 2);
+        multiasset.set(hash(3, 28), AssetName::new(vec![]).unwrap(), 1);
+        Value::new(2_000_000, multiasset)
+    }
+
+    fn anchor() -> Anchor {
+        Anchor::new(
+            Url::new("https://example.com".to_owned()).unwrap(),
+            hash(9, 32),
+        )
+    }
+
+    fn certificates() -> Vec<Certificate> {
+        let pool_params = PoolParams::new(
+            hash(3, 28),
+            hash(4, 32),
+            100_000_000,
+            340_000_000,
+            UnitInterval::new(5, 100),
+            RewardAddress::new(0, key_cred(5)),
+            vec![hash(5, 28)],
+            vec![
+                Relay::new_single_host_addr(Some(3001), Some("1.2.3.4".parse().unwrap()), None),
+                Relay::new_single_host_name(
+                    Some(3001),
+                    DnsName::new("relay.example.com".to_owned()).unwrap(),
+                ),
+                Relay::new_multi_host_name(DnsName::new("example.com".to_owned()).unwrap()),
+            ],
+            Some(PoolMetadata::new(
+                Url::new("https://example.com/pool.json".to_owned()).unwrap(),
+                hash(6, 32),
+            )),
+        );
+        vec![
+            Certificate::new_stake_registration(key_cred(1)),
+            Certificate::new_stake_deregistration(script_cred(2)),
+            Certificate::new_stake_delegation(key_cred(1), hash(3, 28)),
+            Certificate::new_pool_registration(pool_params),
+            Certificate::new_pool_retirement(hash(3, 28), 500),
+            Certificate::new_reg_cert(key_cred(1), 2_000_000),
+            Certificate::new_unreg_cert(key_cred(1), 2_000_000),
+            Certificate::new_vote_deleg_cert(key_cred(1), DRep::new_always_abstain()),
+            Certificate::new_stake_vote_deleg_cert(
+                key_cred(1),
+                hash(3, 28),
+                DRep::new_key(hash(7, 28)),
+            ),
+            Certificate::new_stake_reg_deleg_cert(key_cred(1), hash(3, 28), 2_000_000),
+            Certificate::new_vote_reg_deleg_cert(
+                key_cred(1),
+                DRep::new_script(hash(8, 28)),
+                2_000_000,
+            ),
+            Certificate::new_stake_vote_reg_deleg_cert(
+                script_cred(1),
+                hash(3, 28),
+                DRep::new_always_no_confidence(),
+                2_000_000,
+            ),
+            Certificate::new_auth_committee_hot_cert(key_cred(1), script_cred(2)),
+            Certificate::new_resign_committee_cold_cert(key_cred(1)),
+            Certificate::new_reg_drep_cert(key_cred(7), 500_000_000, Some(anchor())),
+            Certificate::new_unreg_drep_cert(key_cred(7), 500_000_000),
+            Certificate::new_update_drep_cert(script_cred(8), None),
+        ]
+    }
+
+    fn transaction() -> Transaction {
+        let address = BaseAddress::new(0, key_cred(1), key_cred(2)).to_address();
+        let datum = PlutusData::new_list(vec![PlutusData::new_bytes(vec![1, 2, 3])]);
+        let outputs = vec![
+            TransactionOutput::new(
+                address.clone(),
+                value(),
+                Some(DatumOption::new_datum(datum.clone())),
+                Some(Script::new_native(NativeScript::new_script_all(vec![
+                    NativeScript::new_script_pubkey(hash(1, 28)),
+                    NativeScript::new_script_invalid_hereafter(1000),
+                ]))),
+            ),
+            TransactionOutput::new(
+                address.clone(),
+                Value::from(1_000_000),
+                Some(DatumOption::new_hash(hash(3, 32))),
+                None,
+            ),
+        ];
+        let mut body = TransactionBody::new(
+            vec![TransactionInput::new(hash(0, 32), 1)],
+            outputs,
+            200_000,
+        );
+        body.ttl = Some(5000);
+        body.validity_interval_start = Some(1000);
+        body.certs = Some(certificates());
+        let mut withdrawals = OrderedHashMap::new();
+        withdrawals.insert(RewardAddress::new(0, key_cred(2)), 1_000);
+        body.withdrawals = Some(withdrawals);
+        let mut mint = Mint::new();
+        mint.set(hash(1, 28), AssetName::new(b"Token".to_vec()).unwrap(), -5);
+        body.mint = Some(mint);
+        body.collateral_inputs = Some(vec![TransactionInput::new(hash(0, 32), 2)]);
+        body.required_signers = Some(vec![hash(1, 28)]);
+        body.collateral_return = Some(TransactionOutput::new(
+            address,
+            Value::from(3_000_000),
+            None,
+            None,
+        ));
+        body.total_collateral = Some(1_000_000);
+        body.reference_inputs = Some(vec![TransactionInput::new(hash(0, 32), 3)]);
+        let mut update = ProtocolParamUpdate::new();
+        update.minfee_a = Some(44);
+        update.max_tx_ex_units = Some(ExUnits::new(14_000_000, 10_000_000_000));
+        update.expansion_rate = Some(UnitInterval::new(3, 1000));
+        body.proposal_procedures = Some(vec![
+            ProposalProcedure::new(
+                100_000_000_000,
+                RewardAddress::new(0, key_cred(2)),
+                GovAction::new_parameter_change_action(
+                    Some(GovActionId::new(hash(4, 32), 0)),
+                    update,
+                ),
+                anchor(),
+            ),
+            ProposalProcedure::new(
+                100_000_000_000,
+                RewardAddress::new(0, key_cred(2)),
+                GovAction::new_info_action(),
+                anchor(),
+            ),
+        ]);
+        let mut votes = OrderedHashMap::new();
+        votes.insert(
+            GovActionId::new(hash(4, 32), 1),
+            VotingProcedure::new(Vote::Yes, Some(anchor())),
+        );
+        let mut voting_procedures = VotingProcedures::new();
+        voting_procedures.insert(Voter::new_d_rep_key_hash(hash(7, 28)), votes);
+        body.voting_procedures = Some(voting_procedures);
+        body.donation = Some(1_000);
+
+        let mut metadata = Metadata::new();
+        metadata.set(1, TransactionMetadatum::new_int(Int::from(5u64)));
+        metadata.set(
+            674,
+            TransactionMetadatum::new_text("hello".to_owned()).unwrap(),
+        );
+        let mut auxiliary_data = ConwayFormatAuxData::new();
+        auxiliary_data.metadata = Some(metadata);
+        let auxiliary_data = AuxiliaryData::new_conway(auxiliary_data);
+        body.auxiliary_data_hash = Some(crate::crypto::hash::hash_auxiliary_data(&auxiliary_data));
+
+        let key = PrivateKey::generate_ed25519();
+        let mut witness_set = TransactionWitnessSet::new();
+        witness_set.vkeywitnesses = Some(vec![Vkeywitness::new(
+            key.to_public(),
+            key.sign(body.hash().to_raw_bytes()),
+        )]);
+        witness_set.plutus_v2_scripts = Some(vec![PlutusV2Script::new(vec![0x4e, 0x4d, 0x01])]);
+        witness_set.plutus_datums = Some(vec![datum.clone()]);
+        witness_set.redeemers = Some(vec![Redeemer::new(
+            RedeemerTag::Spend,
+            0,
+            datum,
+            ExUnits::new(100, 200),
+        )]);
+        body.network_id = Some(NetworkId::testnet());
+        body.script_data_hash = Some(hash(5, 32));
+        Transaction::new(body, witness_set, true, Some(auxiliary_data))
+    }
+
+    #[test]
+    fn value_round_trip() {
+        for schema in SCHEMAS {
+            let json = decode_value_to_json_str(&value(), schema).unwrap();
+            let read = encode_json_str_to_value(&json, schema).unwrap();
+            assert_eq!(read, value(), "{schema:?}");
+        }
+        let ogmios = decode_value_to_json_value(&value(), ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(ogmios["ada"]["lovelace"], 2_000_000);
+        assert_eq!(ogmios["01".repeat(28)]["546f6b656e"], 5);
+        assert_eq!(ogmios["03".repeat(28)][""], 1);
+        let cli = decode_value_to_json_value(&value(), ChainJsonSchema::CardanoCliView).unwrap();
+        let policy = &cli[format!("policy {}", "01".repeat(28))];
+        assert_eq!(policy["asset 546f6b656e (Token)"], 5);
+        assert_eq!(cli[format!("policy {}", "02".repeat(28))]["asset 00ff"], 2);
+        assert_eq!(
+            cli[format!("policy {}", "03".repeat(28))]["default asset"],
+            1
+        );
+    }
+
+    #[test]
+    fn output_round_trip() {
+        for output in transaction().body.outputs {
+            for schema in SCHEMAS {
+                let json = decode_transaction_output_to_json_str(&output, schema).unwrap();
+                let read = encode_json_str_to_transaction_output(&json, schema).unwrap();
+                assert_eq!(read.to_cbor_bytes(), output.to_cbor_bytes(), "{json}");
+            }
+        }
+        let output = &transaction().body.outputs[0];
+        let cli = decode_transaction_output_to_json_value(output, ChainJsonSchema::CardanoCliView)
+            .unwrap();
+        assert!(cli["address"].as_str().unwrap().starts_with("addr_test1"));
+        assert_eq!(cli["datum"]["list"][0]["bytes"], "010203");
+        assert_eq!(cli["reference script"]["script"]["type"], "SimpleScript");
+        let ogmios =
+            decode_transaction_output_to_json_value(output, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(ogmios["script"]["language"], "native");
+        assert_eq!(ogmios["script"]["json"]["from"][1]["clause"], "before");
+    }
+
+    #[test]
+    fn certificate_round_trip() {
+        for cert in certificates() {
+            for schema in SCHEMAS {
+                let json = decode_certificate_to_json_str(&cert, schema).unwrap();
+                let read = encode_json_str_to_certificate(&json, schema).unwrap();
+                assert_eq!(read.to_cbor_bytes(), cert.to_cbor_bytes(), "{json}");
+            }
+        }
+    }
+
+    #[test]
+    fn ogmios_transaction() {
+        let tx = transaction();
+        let mut json = decode_transaction_to_json_value(&tx, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(json["id"], tx.body.hash().to_hex());
+        assert_eq!(json["fee"]["ada"]["lovelace"], 200_000);
+        assert_eq!(json["validityInterval"]["invalidAfter"], 5000);
+        assert_eq!(json["metadata"]["labels"]["674"]["json"], "hello");
+        let read = encode_json_value_to_transaction(&json, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(read.to_cbor_bytes(), tx.to_cbor_bytes());
+        // everything can be rebuilt without the CBOR too
+        json.as_object_mut().unwrap().remove("cbor");
+        let read = encode_json_value_to_transaction(&json, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(read.to_cbor_bytes(), tx.to_cbor_bytes());
+    }
+
+    #[test]
+    fn cardano_cli_transaction() {
+        let tx = transaction();
+        let json = decode_transaction_to_json_value(&tx, ChainJsonSchema::CardanoCliView).unwrap();
+        assert_eq!(json["fee"], "200000 Lovelace");
+        assert_eq!(json["inputs"][0], format!("{}#1", "00".repeat(32)));
+        assert_eq!(json["validity range"]["lower bound"], 1000);
+        assert_eq!(json["metadata"]["674"], "hello");
+        let key = json["witnesses"][0]["key"].as_str().unwrap();
+        assert!(key.starts_with("VKey (VerKeyEd25519DSIGN \""));
+        let read =
+            encode_json_value_to_transaction(&json, ChainJsonSchema::CardanoCliView).unwrap();
+        // the view doesn't contain these
+        let mut expected = tx.clone();
+        expected.body.network_id = None;
+        expected.body.script_data_hash = None;
+        expected.witness_set.plutus_v2_scripts = None;
+        expected.witness_set.plutus_datums = None;
+        expected.witness_set.redeemers = None;
+        assert_eq!(read.to_cbor_bytes(), expected.to_cbor_bytes());
+        assert_eq!(read.body.hash(), expected.body.hash());
+    }
+}
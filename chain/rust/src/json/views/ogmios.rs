@@ -0,0 +1,1942 @@
+//! Ogmios v6 JSON, following the schema at https://ogmios.dev/api/v6/
+//!
+//! Transactions are encoded with their original "cbor" included, and when it is present
+//! decoding uses it as is. Without it the transaction is rebuilt from the other fields,
+//! which loses the following (besides any CBOR encoding details):
+//! * which scripts were in the auxiliary data instead of the witness set (all go to the witness set)
+//! * the auxiliary data format (Conway format is always used)
+//!
+//! Combined registration/delegation certificates are encoded as a single "stakeDelegation"
+//! object with a "deposit" field so each certificate maps to exactly one JSON object.
+
+use std::convert::TryFrom;
+
+use cml_core::{
+    ordered_hash_map::OrderedHashMap,
+    serialization::{Deserialize, Serialize, ToBytes},
+};
+use cml_crypto::{Ed25519KeyHash, Ed25519Signature, PublicKey, RawBytesEncoding, ScriptHash};
+use serde_json::json;
+
+use crate::{
+    address::RewardAccount,
+    assets::{AssetName, Mint, MultiAsset},
+    auxdata::{AuxiliaryData, ConwayFormatAuxData, Metadata},
+    block::ProtocolVersion,
+    byron::AddrAttributes,
+    certs::{
+        Certificate, Credential, DRep, DnsName, Ipv4, Ipv6, PoolMetadata, PoolParams, Relay, Url,
+    },
+    crypto::{hash::hash_auxiliary_data, hash::hash_plutus_data, BootstrapWitness, Vkeywitness},
+    governance::{
+        Anchor, Committee, Constitution, GovAction, GovActionId, ProposalProcedure, Vote, Voter,
+        VotingProcedure, VotingProcedures,
+    },
+    json::metadatums::{
+        decode_metadatum_to_json_value, encode_json_value_to_metadatum, MetadataJsonSchema,
+    },
+    plutus::{
+        CostModels, ExUnitPrices, ExUnits, PlutusData, PlutusV1Script, PlutusV2Script,
+        PlutusV3Script, Redeemer, RedeemerTag,
+    },
+    transaction::{
+        DatumOption, NativeScript, Transaction, TransactionBody, TransactionInput,
+        TransactionOutput, TransactionWitnessSet,
+    },
+    DRepVotingThresholds, NetworkId, PoolVotingThresholds, ProtocolParamUpdate, Rational, Script,
+    UnitInterval, Value,
+};
+
+use super::{
+    address_from_string, address_to_string, as_array, as_i64, as_object, as_str, as_u64,
+    bytes_from_hex, field, from_cbor_hex, from_hex, invalid, opt_field, pool_id_from_bech32,
+    pool_id_to_bech32, ChainJsonError, JsonObject, JsonValue,
+};
+
+fn ada_to_json(coin: u64) -> JsonValue {
+    json!({ "ada": { "lovelace": coin } })
+}
+
+fn ada_from_json(json: &JsonValue, name: &str) -> Result<u64, ChainJsonError> {
+    let ada = field(as_object(json, name)?, "ada")?;
+    as_u64(field(as_object(ada, name)?, "lovelace")?, name)
+}
+
+fn ratio_to_json(numerator: u64, denominator: u64) -> JsonValue {
+    JsonValue::String(format!("{numerator}/{denominator}"))
+}
+
+fn ratio_from_json(json: &JsonValue, name: &str) -> Result<(u64, u64), ChainJsonError> {
+    let text = as_str(json, name)?;
+    let (numerator, denominator) = text
+        .split_once('/')
+        .ok_or_else(|| invalid(name, "expected a ratio like \"1/2\""))?;
+    let parse = |s: &str| s.parse::<u64>().map_err(|e| invalid(name, e));
+    Ok((parse(numerator)?, parse(denominator)?))
+}
+
+fn unit_interval_from_json(json: &JsonValue, name: &str) -> Result<UnitInterval, ChainJsonError> {
+    ratio_from_json(json, name).map(|(start, end)| UnitInterval::new(start, end))
+}
+
+fn unit_interval_to_json(interval: &UnitInterval) -> JsonValue {
+    ratio_to_json(interval.start, interval.end)
+}
+
+fn rational_from_json(json: &JsonValue, name: &str) -> Result<Rational, ChainJsonError> {
+    ratio_from_json(json, name)
+        .map(|(numerator, denominator)| Rational::new(numerator, denominator))
+}
+
+fn rational_to_json(rational: &Rational) -> JsonValue {
+    ratio_to_json(rational.numerator, rational.denominator)
+}
+
+pub(crate) fn value_to_json(value: &Value) -> JsonValue {
+    let mut obj = JsonObject::new();
+    obj.insert("ada".to_owned(), json!({ "lovelace": value.coin }));
+    for (policy_id, assets) in value.multiasset.iter() {
+        let assets = assets
+            .iter()
+            .map(|(name, amount)| (hex::encode(name.get()), JsonValue::from(*amount)))
+            .collect::<JsonObject>();
+        obj.insert(policy_id.to_hex(), JsonValue::Object(assets));
+    }
+    JsonValue::Object(obj)
+}
+
+pub(crate) fn value_from_json(json: &JsonValue, name: &str) -> Result<Value, ChainJsonError> {
+    let mut coin = None;
+    let mut multiasset = MultiAsset::new();
+    for (key, entry) in as_object(json, name)? {
+        if key == "ada" {
+            coin = Some(as_u64(
+                field(as_object(entry, key)?, "lovelace")?,
+                "lovelace",
+            )?);
+        } else {
+            let policy_id = from_hex(&JsonValue::String(key.clone()), key)?;
+            for (asset_name, amount) in as_object(entry, key)? {
+                multiasset.set(
+                    policy_id,
+                    asset_name_from_hex(asset_name)?,
+                    as_u64(amount, asset_name)?,
+                );
+            }
+        }
+    }
+    let coin = coin.ok_or_else(|| ChainJsonError::MissingField("ada".to_owned()))?;
+    Ok(Value::new(coin, multiasset))
+}
+
+fn asset_name_from_hex(text: &str) -> Result<AssetName, ChainJsonError> {
+    let bytes = hex::decode(text).map_err(|e| invalid(text, e))?;
+    AssetName::new(bytes).map_err(Into::into)
+}
+
+fn mint_to_json(mint: &Mint) -> JsonValue {
+    mint.iter()
+        .map(|(policy_id, assets)| {
+            let assets = assets
+                .iter()
+                .map(|(name, amount)| (hex::encode(name.get()), JsonValue::from(*amount)))
+                .collect::<JsonObject>();
+            (policy_id.to_hex(), JsonValue::Object(assets))
+        })
+        .collect::<JsonObject>()
+        .into()
+}
+
+fn mint_from_json(json: &JsonValue) -> Result<Mint, ChainJsonError> {
+    let mut mint = Mint::new();
+    for (policy, assets) in as_object(json, "mint")? {
+        let policy_id = from_hex(&JsonValue::String(policy.clone()), policy)?;
+        for (asset_name, amount) in as_object(assets, policy)? {
+            mint.set(
+                policy_id,
+                asset_name_from_hex(asset_name)?,
+                as_i64(amount, asset_name)?,
+            );
+        }
+    }
+    Ok(mint)
+}
+
+fn input_to_json(input: &TransactionInput) -> JsonValue {
+    json!({
+        "transaction": { "id": input.transaction_id.to_hex() },
+        "index": input.index,
+    })
+}
+
+fn input_from_json(json: &JsonValue) -> Result<TransactionInput, ChainJsonError> {
+    let obj = as_object(json, "input")?;
+    let transaction = as_object(field(obj, "transaction")?, "transaction")?;
+    Ok(TransactionInput::new(
+        from_hex(field(transaction, "id")?, "id")?,
+        as_u64(field(obj, "index")?, "index")?,
+    ))
+}
+
+fn inputs_from_json(json: &JsonValue, name: &str) -> Result<Vec<TransactionInput>, ChainJsonError> {
+    as_array(json, name)?.iter().map(input_from_json).collect()
+}
+
+fn native_script_to_json(script: &NativeScript) -> JsonValue {
+    let scripts_to_json = |scripts: &[NativeScript]| {
+        scripts
+            .iter()
+            .map(native_script_to_json)
+            .collect::<Vec<_>>()
+    };
+    match script {
+        NativeScript::ScriptPubkey(pubkey) => {
+            json!({ "clause": "signature", "from": pubkey.ed25519_key_hash.to_hex() })
+        }
+        NativeScript::ScriptAll(all) => {
+            json!({ "clause": "all", "from": scripts_to_json(&all.native_scripts) })
+        }
+        NativeScript::ScriptAny(any) => {
+            json!({ "clause": "any", "from": scripts_to_json(&any.native_scripts) })
+        }
+        NativeScript::ScriptNOfK(n_of_k) => json!({
+            "clause": "some",
+            "atLeast": n_of_k.n,
+            "from": scripts_to_json(&n_of_k.native_scripts),
+        }),
+        // both are named after when the script becomes valid, not when it is invalid
+        NativeScript::ScriptInvalidBefore(before) => {
+            json!({ "clause": "after", "slot": before.before })
+        }
+        NativeScript::ScriptInvalidHereafter(after) => {
+            json!({ "clause": "before", "slot": after.after })
+        }
+    }
+}
+
+fn native_script_from_json(json: &JsonValue) -> Result<NativeScript, ChainJsonError> {
+    let obj = as_object(json, "json")?;
+    let scripts_from_json = || -> Result<Vec<NativeScript>, ChainJsonError> {
+        as_array(field(obj, "from")?, "from")?
+            .iter()
+            .map(native_script_from_json)
+            .collect()
+    };
+    match as_str(field(obj, "clause")?, "clause")? {
+        "signature" => Ok(NativeScript::new_script_pubkey(from_hex(
+            field(obj, "from")?,
+            "from",
+        )?)),
+        "all" => Ok(NativeScript::new_script_all(scripts_from_json()?)),
+        "any" => Ok(NativeScript::new_script_any(scripts_from_json()?)),
+        "some" => Ok(NativeScript::new_script_n_of_k(
+            as_u64(field(obj, "atLeast")?, "atLeast")?,
+            scripts_from_json()?,
+        )),
+        "after" => Ok(NativeScript::new_script_invalid_before(as_u64(
+            field(obj, "slot")?,
+            "slot",
+        )?)),
+        "before" => Ok(NativeScript::new_script_invalid_hereafter(as_u64(
+            field(obj, "slot")?,
+            "slot",
+        )?)),
+        other => Err(invalid("clause", format!("unknown clause {other}"))),
+    }
+}
+
+fn script_to_json(script: &Script) -> JsonValue {
+    match script {
+        Script::Native { script, .. } => json!({
+            "language": "native",
+            "json": native_script_to_json(script),
+            "cbor": hex::encode(script.to_cbor_bytes()),
+        }),
+        Script::PlutusV1 { script, .. } => {
+            json!({ "language": "plutus:v1", "cbor": hex::encode(script.get()) })
+        }
+        Script::PlutusV2 { script, .. } => {
+            json!({ "language": "plutus:v2", "cbor": hex::encode(script.get()) })
+        }
+        Script::PlutusV3 { script, .. } => {
+            json!({ "language": "plutus:v3", "cbor": hex::encode(script.get()) })
+        }
+    }
+}
+
+fn script_from_json(json: &JsonValue) -> Result<Script, ChainJsonError> {
+    let obj = as_object(json, "script")?;
+    match as_str(field(obj, "language")?, "language")? {
+        "native" => match opt_field(obj, "cbor") {
+            Some(cbor) => from_cbor_hex(cbor, "cbor").map(Script::new_native),
+            None => native_script_from_json(field(obj, "json")?).map(Script::new_native),
+        },
+        "plutus:v1" => Ok(Script::new_plutus_v1(PlutusV1Script::new(bytes_from_hex(
+            field(obj, "cbor")?,
+            "cbor",
+        )?))),
+        "plutus:v2" => Ok(Script::new_plutus_v2(PlutusV2Script::new(bytes_from_hex(
+            field(obj, "cbor")?,
+            "cbor",
+        )?))),
+        "plutus:v3" => Ok(Script::new_plutus_v3(PlutusV3Script::new(bytes_from_hex(
+            field(obj, "cbor")?,
+            "cbor",
+        )?))),
+        other => Err(invalid(
+            "language",
+            format!("unknown script language {other}"),
+        )),
+    }
+}
+
+pub(crate) fn output_to_json(output: &TransactionOutput) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    obj.insert(
+        "address".to_owned(),
+        address_to_string(output.address())?.into(),
+    );
+    obj.insert("value".to_owned(), value_to_json(output.amount()));
+    match output.datum() {
+        Some(DatumOption::Hash { datum_hash, .. }) => {
+            obj.insert("datumHash".to_owned(), datum_hash.to_hex().into());
+        }
+        Some(DatumOption::Datum { datum, .. }) => {
+            obj.insert(
+                "datum".to_owned(),
+                hex::encode(datum.to_cbor_bytes()).into(),
+            );
+        }
+        None => (),
+    }
+    if let Some(script) = output.script_ref() {
+        obj.insert("script".to_owned(), script_to_json(script));
+    }
+    Ok(obj.into())
+}
+
+pub(crate) fn output_from_json(json: &JsonValue) -> Result<TransactionOutput, ChainJsonError> {
+    let obj = as_object(json, "output")?;
+    let datum = match (opt_field(obj, "datumHash"), opt_field(obj, "datum")) {
+        (_, Some(datum)) => Some(DatumOption::new_datum(from_cbor_hex(datum, "datum")?)),
+        (Some(hash), None) => Some(DatumOption::new_hash(from_hex(hash, "datumHash")?)),
+        (None, None) => None,
+    };
+    Ok(TransactionOutput::new(
+        address_from_string(field(obj, "address")?, "address")?,
+        value_from_json(field(obj, "value")?, "value")?,
+        datum,
+        opt_field(obj, "script").map(script_from_json).transpose()?,
+    ))
+}
+
+fn credential_hex(credential: &Credential) -> (String, &'static str) {
+    match credential {
+        Credential::PubKey { hash, .. } => (hash.to_hex(), "verificationKey"),
+        Credential::Script { hash, .. } => (hash.to_hex(), "script"),
+    }
+}
+
+/// Inserts `id_field` and "from" into `obj` for the credential
+fn insert_credential(obj: &mut JsonObject, id_field: &str, credential: &Credential) {
+    let (id, from) = credential_hex(credential);
+    obj.insert(id_field.to_owned(), id.into());
+    obj.insert("from".to_owned(), from.into());
+}
+
+fn credential_to_json(credential: &Credential) -> JsonValue {
+    let mut obj = JsonObject::new();
+    insert_credential(&mut obj, "id", credential);
+    obj.into()
+}
+
+fn credential_from_obj(obj: &JsonObject, id_field: &str) -> Result<Credential, ChainJsonError> {
+    let id = field(obj, id_field)?;
+    match as_str(field(obj, "from")?, "from")? {
+        "verificationKey" => from_hex(id, id_field).map(Credential::new_pub_key),
+        "script" => from_hex(id, id_field).map(Credential::new_script),
+        other => Err(invalid("from", format!("unknown credential type {other}"))),
+    }
+}
+
+fn credential_from_json(json: &JsonValue, name: &str) -> Result<Credential, ChainJsonError> {
+    credential_from_obj(as_object(json, name)?, "id")
+}
+
+fn drep_to_json(drep: &DRep) -> JsonValue {
+    match drep {
+        DRep::Key { pool, .. } => {
+            json!({ "type": "registered", "id": pool.to_hex(), "from": "verificationKey" })
+        }
+        DRep::Script { script_hash, .. } => {
+            json!({ "type": "registered", "id": script_hash.to_hex(), "from": "script" })
+        }
+        DRep::AlwaysAbstain { .. } => json!({ "type": "abstain" }),
+        DRep::AlwaysNoConfidence { .. } => json!({ "type": "noConfidence" }),
+    }
+}
+
+fn drep_from_json(json: &JsonValue) -> Result<DRep, ChainJsonError> {
+    let obj = as_object(json, "delegateRepresentative")?;
+    match as_str(field(obj, "type")?, "type")? {
+        "registered" => match credential_from_obj(obj, "id")? {
+            Credential::PubKey { hash, .. } => Ok(DRep::new_key(hash)),
+            Credential::Script { hash, .. } => Ok(DRep::new_script(hash)),
+        },
+        "abstain" => Ok(DRep::new_always_abstain()),
+        "noConfidence" => Ok(DRep::new_always_no_confidence()),
+        other => Err(invalid(
+            "type",
+            format!("unknown delegate representative {other}"),
+        )),
+    }
+}
+
+fn drep_credential_to_json(credential: &Credential) -> JsonValue {
+    let mut obj = JsonObject::new();
+    obj.insert("type".to_owned(), "registered".into());
+    insert_credential(&mut obj, "id", credential);
+    obj.into()
+}
+
+pub(super) fn anchor_to_json(anchor: &Anchor) -> JsonValue {
+    json!({ "url": anchor.anchor_url.get(), "hash": anchor.anchor_doc_hash.to_hex() })
+}
+
+pub(super) fn anchor_from_json(json: &JsonValue) -> Result<Anchor, ChainJsonError> {
+    let obj = as_object(json, "anchor")?;
+    Ok(Anchor::new(
+        Url::new(as_str(field(obj, "url")?, "url")?.to_owned())?,
+        from_hex(field(obj, "hash")?, "hash")?,
+    ))
+}
+
+pub(super) fn relay_to_json(relay: &Relay) -> JsonValue {
+    let mut obj = JsonObject::new();
+    let port = match relay {
+        Relay::SingleHostAddr(addr) => {
+            obj.insert("type".to_owned(), "ipAddress".into());
+            if let Some(ipv4) = &addr.ipv4 {
+                obj.insert("ipv4".to_owned(), ipv4.to_string().into());
+            }
+            if let Some(ipv6) = &addr.ipv6 {
+                obj.insert("ipv6".to_owned(), ipv6.to_string().into());
+            }
+            addr.port
+        }
+        Relay::SingleHostName(host) => {
+            obj.insert("type".to_owned(), "hostname".into());
+            obj.insert("hostname".to_owned(), host.dns_name.inner.clone().into());
+            host.port
+        }
+        Relay::MultiHostName(host) => {
+            obj.insert("type".to_owned(), "hostname".into());
+            obj.insert("hostname".to_owned(), host.dns_name.inner.clone().into());
+            None
+        }
+    };
+    if let Some(port) = port {
+        obj.insert("port".to_owned(), port.into());
+    }
+    obj.into()
+}
+
+/// Ogmios doesn't distinguish single/multi host name relays so port-less ones are read as
+/// multi host name relays (i.e. DNS SRV records) as those never have a port.
+pub(super) fn relay_from_json(json: &JsonValue) -> Result<Relay, ChainJsonError> {
+    let obj = as_object(json, "relay")?;
+    let port = opt_field(obj, "port")
+        .map(|port| {
+            as_u64(port, "port")
+                .and_then(|port| u16::try_from(port).map_err(|e| invalid("port", e)))
+        })
+        .transpose()?;
+    match as_str(field(obj, "type")?, "type")? {
+        "ipAddress" => {
+            let ipv4 = opt_field(obj, "ipv4")
+                .map(|ip| {
+                    as_str(ip, "ipv4")?
+                        .parse::<Ipv4>()
+                        .map_err(|e| invalid("ipv4", e))
+                })
+                .transpose()?;
+            let ipv6 = opt_field(obj, "ipv6")
+                .map(|ip| {
+                    as_str(ip, "ipv6")?
+                        .parse::<Ipv6>()
+                        .map_err(|e| invalid("ipv6", e))
+                })
+                .transpose()?;
+            Ok(Relay::new_single_host_addr(port, ipv4, ipv6))
+        }
+        "hostname" => {
+            let dns_name = DnsName::new(as_str(field(obj, "hostname")?, "hostname")?.to_owned())?;
+            match port {
+                Some(port) => Ok(Relay::new_single_host_name(Some(port), dns_name)),
+                None => Ok(Relay::new_multi_host_name(dns_name)),
+            }
+        }
+        other => Err(invalid("type", format!("unknown relay type {other}"))),
+    }
+}
+
+fn reward_account_to_json(account: &RewardAccount) -> Result<JsonValue, ChainJsonError> {
+    Ok(account.clone().to_address().to_bech32(None)?.into())
+}
+
+fn reward_account_from_json(json: &JsonValue, name: &str) -> Result<RewardAccount, ChainJsonError> {
+    RewardAccount::from_address(&address_from_string(json, name)?)
+        .ok_or_else(|| invalid(name, "expected a reward account"))
+}
+
+fn pool_params_to_json(params: &PoolParams) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    obj.insert("id".to_owned(), pool_id_to_bech32(&params.operator).into());
+    obj.insert(
+        "vrfVerificationKeyHash".to_owned(),
+        params.vrf_keyhash.to_hex().into(),
+    );
+    obj.insert("pledge".to_owned(), ada_to_json(params.pledge));
+    obj.insert("cost".to_owned(), ada_to_json(params.cost));
+    obj.insert("margin".to_owned(), unit_interval_to_json(&params.margin));
+    obj.insert(
+        "rewardAccount".to_owned(),
+        reward_account_to_json(&params.reward_account)?,
+    );
+    obj.insert(
+        "owners".to_owned(),
+        params
+            .pool_owners
+            .iter()
+            .map(|owner| JsonValue::from(owner.to_hex()))
+            .collect(),
+    );
+    obj.insert(
+        "relays".to_owned(),
+        params.relays.iter().map(relay_to_json).collect(),
+    );
+    if let Some(metadata) = &params.pool_metadata {
+        obj.insert(
+            "metadata".to_owned(),
+            json!({
+                "url": metadata.url.get(),
+                "hash": metadata.pool_metadata_hash.to_hex(),
+            }),
+        );
+    }
+    Ok(obj.into())
+}
+
+fn pool_params_from_json(json: &JsonValue) -> Result<PoolParams, ChainJsonError> {
+    let obj = as_object(json, "stakePool")?;
+    let pool_metadata = opt_field(obj, "metadata")
+        .map(|metadata| {
+            let metadata = as_object(metadata, "metadata")?;
+            Ok::<_, ChainJsonError>(PoolMetadata::new(
+                Url::new(as_str(field(metadata, "url")?, "url")?.to_owned())?,
+                from_hex(field(metadata, "hash")?, "hash")?,
+            ))
+        })
+        .transpose()?;
+    let pool_owners = as_array(field(obj, "owners")?, "owners")?
+        .iter()
+        .map(|owner| from_hex(owner, "owners"))
+        .collect::<Result<_, _>>()?;
+    let relays = as_array(field(obj, "relays")?, "relays")?
+        .iter()
+        .map(relay_from_json)
+        .collect::<Result<_, _>>()?;
+    Ok(PoolParams::new(
+        pool_id_from_bech32(field(obj, "id")?, "id")?,
+        from_hex(
+            field(obj, "vrfVerificationKeyHash")?,
+            "vrfVerificationKeyHash",
+        )?,
+        ada_from_json(field(obj, "pledge")?, "pledge")?,
+        ada_from_json(field(obj, "cost")?, "cost")?,
+        unit_interval_from_json(field(obj, "margin")?, "margin")?,
+        reward_account_from_json(field(obj, "rewardAccount")?, "rewardAccount")?,
+        pool_owners,
+        relays,
+        pool_metadata,
+    ))
+}
+
+pub(crate) fn certificate_to_json(cert: &Certificate) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    let mut set = |key: &str, value: JsonValue| {
+        obj.insert(key.to_owned(), value);
+    };
+    // stake credential certificates all share the same layout
+    let stake_cert = |ty: &str,
+                      credential: &Credential,
+                      pool: Option<&Ed25519KeyHash>,
+                      drep: Option<&DRep>,
+                      deposit: Option<u64>| {
+        let mut obj = JsonObject::new();
+        obj.insert("type".to_owned(), ty.into());
+        insert_credential(&mut obj, "credential", credential);
+        if let Some(pool) = pool {
+            obj.insert(
+                "stakePool".to_owned(),
+                json!({ "id": pool_id_to_bech32(pool) }),
+            );
+        }
+        if let Some(drep) = drep {
+            obj.insert("delegateRepresentative".to_owned(), drep_to_json(drep));
+        }
+        if let Some(deposit) = deposit {
+            obj.insert("deposit".to_owned(), ada_to_json(deposit));
+        }
+        JsonValue::from(obj)
+    };
+    match cert {
+        Certificate::StakeRegistration(reg) => {
+            return Ok(stake_cert(
+                "stakeCredentialRegistration",
+                &reg.stake_credential,
+                None,
+                None,
+                None,
+            ))
+        }
+        Certificate::RegCert(reg) => {
+            return Ok(stake_cert(
+                "stakeCredentialRegistration",
+                &reg.stake_credential,
+                None,
+                None,
+                Some(reg.coin),
+            ))
+        }
+        Certificate::StakeDeregistration(dereg) => {
+            return Ok(stake_cert(
+                "stakeCredentialDeregistration",
+                &dereg.stake_credential,
+                None,
+                None,
+                None,
+            ))
+        }
+        Certificate::UnregCert(dereg) => {
+            return Ok(stake_cert(
+                "stakeCredentialDeregistration",
+                &dereg.stake_credential,
+                None,
+                None,
+                Some(dereg.coin),
+            ))
+        }
+        Certificate::StakeDelegation(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                Some(&deleg.pool),
+                None,
+                None,
+            ))
+        }
+        Certificate::VoteDelegCert(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                None,
+                Some(&deleg.d_rep),
+                None,
+            ))
+        }
+        Certificate::StakeVoteDelegCert(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                Some(&deleg.pool),
+                Some(&deleg.d_rep),
+                None,
+            ))
+        }
+        Certificate::StakeRegDelegCert(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                Some(&deleg.pool),
+                None,
+                Some(deleg.coin),
+            ))
+        }
+        Certificate::VoteRegDelegCert(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                None,
+                Some(&deleg.d_rep),
+                Some(deleg.coin),
+            ))
+        }
+        Certificate::StakeVoteRegDelegCert(deleg) => {
+            return Ok(stake_cert(
+                "stakeDelegation",
+                &deleg.stake_credential,
+                Some(&deleg.pool),
+                Some(&deleg.d_rep),
+                Some(deleg.coin),
+            ))
+        }
+        Certificate::PoolRegistration(reg) => {
+            set("type", "stakePoolRegistration".into());
+            set("stakePool", pool_params_to_json(&reg.pool_params)?);
+        }
+        Certificate::PoolRetirement(retirement) => {
+            set("type", "stakePoolRetirement".into());
+            set(
+                "stakePool",
+                json!({
+                    "id": pool_id_to_bech32(&retirement.pool),
+                    "retirementEpoch": retirement.epoch,
+                }),
+            );
+        }
+        Certificate::AuthCommitteeHotCert(auth) => {
+            let mut delegate = JsonObject::new();
+            delegate.insert("status".to_owned(), "active".into());
+            insert_credential(&mut delegate, "id", &auth.committee_hot_credential);
+            set("type", "constitutionalCommitteeDelegation".into());
+            set(
+                "member",
+                credential_to_json(&auth.committee_cold_credential),
+            );
+            set("delegate", delegate.into());
+        }
+        Certificate::ResignCommitteeColdCert(resign) => {
+            set("type", "constitutionalCommitteeRetirement".into());
+            set(
+                "member",
+                credential_to_json(&resign.committee_cold_credential),
+            );
+        }
+        Certificate::RegDrepCert(reg) => {
+            set("type", "delegateRepresentativeRegistration".into());
+            set(
+                "delegateRepresentative",
+                drep_credential_to_json(&reg.drep_credential),
+            );
+            set("deposit", ada_to_json(reg.coin));
+            if let Some(anchor) = &reg.anchor {
+                set("anchor", anchor_to_json(anchor));
+            }
+        }
+        Certificate::UpdateDrepCert(update) => {
+            set("type", "delegateRepresentativeUpdate".into());
+            set(
+                "delegateRepresentative",
+                drep_credential_to_json(&update.drep_credential),
+            );
+            if let Some(anchor) = &update.anchor {
+                set("anchor", anchor_to_json(anchor));
+            }
+        }
+        Certificate::UnregDrepCert(unreg) => {
+            set("type", "delegateRepresentativeRetirement".into());
+            set(
+                "delegateRepresentative",
+                drep_credential_to_json(&unreg.drep_credential),
+            );
+            set("deposit", ada_to_json(unreg.coin));
+        }
+    }
+    Ok(obj.into())
+}
+
+pub(crate) fn certificate_from_json(json: &JsonValue) -> Result<Certificate, ChainJsonError> {
+    let obj = as_object(json, "certificate")?;
+    let deposit = opt_field(obj, "deposit")
+        .map(|deposit| ada_from_json(deposit, "deposit"))
+        .transpose()?;
+    let anchor = opt_field(obj, "anchor").map(anchor_from_json).transpose()?;
+    let drep_credential = || {
+        let drep = as_object(
+            field(obj, "delegateRepresentative")?,
+            "delegateRepresentative",
+        )?;
+        credential_from_obj(drep, "id")
+    };
+    let required_deposit =
+        || deposit.ok_or_else(|| ChainJsonError::MissingField("deposit".to_owned()));
+    match as_str(field(obj, "type")?, "type")? {
+        "stakeCredentialRegistration" => {
+            let credential = credential_from_obj(obj, "credential")?;
+            Ok(match deposit {
+                Some(coin) => Certificate::new_reg_cert(credential, coin),
+                None => Certificate::new_stake_registration(credential),
+            })
+        }
+        "stakeCredentialDeregistration" => {
+            let credential = credential_from_obj(obj, "credential")?;
+            Ok(match deposit {
+                Some(coin) => Certificate::new_unreg_cert(credential, coin),
+                None => Certificate::new_stake_deregistration(credential),
+            })
+        }
+        "stakeDelegation" => {
+            let credential = credential_from_obj(obj, "credential")?;
+            let pool = opt_field(obj, "stakePool")
+                .map(|pool| pool_id_from_bech32(field(as_object(pool, "stakePool")?, "id")?, "id"))
+                .transpose()?;
+            let drep = opt_field(obj, "delegateRepresentative")
+                .map(drep_from_json)
+                .transpose()?;
+            match (pool, drep, deposit) {
+                (Some(pool), None, None) => Ok(Certificate::new_stake_delegation(credential, pool)),
+                (None, Some(drep), None) => Ok(Certificate::new_vote_deleg_cert(credential, drep)),
+                (Some(pool), Some(drep), None) => Ok(Certificate::new_stake_vote_deleg_cert(
+                    credential, pool, drep,
+                )),
+                (Some(pool), None, Some(coin)) => Ok(Certificate::new_stake_reg_deleg_cert(
+                    credential, pool, coin,
+                )),
+                (None, Some(drep), Some(coin)) => {
+                    Ok(Certificate::new_vote_reg_deleg_cert(credential, drep, coin))
+                }
+                (Some(pool), Some(drep), Some(coin)) => Ok(
+                    Certificate::new_stake_vote_reg_deleg_cert(credential, pool, drep, coin),
+                ),
+                (None, None, _) => Err(ChainJsonError::MissingField("stakePool".to_owned())),
+            }
+        }
+        "stakePoolRegistration" => Ok(Certificate::new_pool_registration(pool_params_from_json(
+            field(obj, "stakePool")?,
+        )?)),
+        "stakePoolRetirement" => {
+            let pool = as_object(field(obj, "stakePool")?, "stakePool")?;
+            Ok(Certificate::new_pool_retirement(
+                pool_id_from_bech32(field(pool, "id")?, "id")?,
+                as_u64(field(pool, "retirementEpoch")?, "retirementEpoch")?,
+            ))
+        }
+        "constitutionalCommitteeDelegation" => Ok(Certificate::new_auth_committee_hot_cert(
+            credential_from_json(field(obj, "member")?, "member")?,
+            credential_from_json(field(obj, "delegate")?, "delegate")?,
+        )),
+        "constitutionalCommitteeRetirement" => Ok(Certificate::new_resign_committee_cold_cert(
+            credential_from_json(field(obj, "member")?, "member")?,
+        )),
+        "delegateRepresentativeRegistration" => Ok(Certificate::new_reg_drep_cert(
+            drep_credential()?,
+            required_deposit()?,
+            anchor,
+        )),
+        "delegateRepresentativeUpdate" => Ok(Certificate::new_update_drep_cert(
+            drep_credential()?,
+            anchor,
+        )),
+        "delegateRepresentativeRetirement" => Ok(Certificate::new_unreg_drep_cert(
+            drep_credential()?,
+            required_deposit()?,
+        )),
+        other => Err(ChainJsonError::Unsupported(format!(
+            "certificate type {other}"
+        ))),
+    }
+}
+
+fn gov_action_id_to_json(id: &GovActionId) -> JsonValue {
+    json!({
+        "transaction": { "id": id.transaction_id.to_hex() },
+        "index": id.gov_action_index,
+    })
+}
+
+fn gov_action_id_from_json(json: &JsonValue) -> Result<GovActionId, ChainJsonError> {
+    let input = input_from_json(json)?;
+    Ok(GovActionId::new(input.transaction_id, input.index))
+}
+
+fn insert_ancestor(obj: &mut JsonObject, ancestor: &Option<GovActionId>) {
+    if let Some(ancestor) = ancestor {
+        obj.insert("ancestor".to_owned(), gov_action_id_to_json(ancestor));
+    }
+}
+
+fn ancestor_from_json(obj: &JsonObject) -> Result<Option<GovActionId>, ChainJsonError> {
+    opt_field(obj, "ancestor")
+        .map(gov_action_id_from_json)
+        .transpose()
+}
+
+fn withdrawals_to_json(
+    withdrawals: &OrderedHashMap<RewardAccount, u64>,
+) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    for (account, coin) in withdrawals.iter() {
+        obj.insert(
+            account.clone().to_address().to_bech32(None)?,
+            ada_to_json(*coin),
+        );
+    }
+    Ok(obj.into())
+}
+
+fn withdrawals_from_json(
+    json: &JsonValue,
+) -> Result<OrderedHashMap<RewardAccount, u64>, ChainJsonError> {
+    let mut withdrawals = OrderedHashMap::new();
+    for (account, coin) in as_object(json, "withdrawals")? {
+        withdrawals.insert(
+            reward_account_from_json(&JsonValue::String(account.clone()), account)?,
+            ada_from_json(coin, account)?,
+        );
+    }
+    Ok(withdrawals)
+}
+
+fn ex_units_to_json(ex_units: &ExUnits) -> JsonValue {
+    json!({ "memory": ex_units.mem, "cpu": ex_units.steps })
+}
+
+fn ex_units_from_json(json: &JsonValue, name: &str) -> Result<ExUnits, ChainJsonError> {
+    let obj = as_object(json, name)?;
+    Ok(ExUnits::new(
+        as_u64(field(obj, "memory")?, "memory")?,
+        as_u64(field(obj, "cpu")?, "cpu")?,
+    ))
+}
+
+fn bytes_size_to_json(bytes: u64) -> JsonValue {
+    json!({ "bytes": bytes })
+}
+
+fn bytes_size_from_json(json: &JsonValue, name: &str) -> Result<u64, ChainJsonError> {
+    as_u64(field(as_object(json, name)?, "bytes")?, name)
+}
+
+fn pool_voting_thresholds_to_json(thresholds: &PoolVotingThresholds) -> JsonValue {
+    json!({
+        "noConfidence": unit_interval_to_json(&thresholds.motion_no_confidence),
+        "constitutionalCommittee": {
+            "default": unit_interval_to_json(&thresholds.committee_normal),
+            "stateOfNoConfidence": unit_interval_to_json(&thresholds.committee_no_confidence),
+        },
+        "hardForkInitiation": unit_interval_to_json(&thresholds.hard_fork_initiation),
+    })
+}
+
+fn pool_voting_thresholds_from_json(
+    json: &JsonValue,
+) -> Result<PoolVotingThresholds, ChainJsonError> {
+    let name = "stakePoolVotingThresholds";
+    let obj = as_object(json, name)?;
+    let committee = as_object(field(obj, "constitutionalCommittee")?, name)?;
+    Ok(PoolVotingThresholds::new(
+        unit_interval_from_json(field(obj, "noConfidence")?, name)?,
+        unit_interval_from_json(field(committee, "default")?, name)?,
+        unit_interval_from_json(field(committee, "stateOfNoConfidence")?, name)?,
+        unit_interval_from_json(field(obj, "hardForkInitiation")?, name)?,
+    ))
+}
+
+fn drep_voting_thresholds_to_json(thresholds: &DRepVotingThresholds) -> JsonValue {
+    json!({
+        "noConfidence": unit_interval_to_json(&thresholds.motion_no_confidence),
+        "constitutionalCommittee": {
+            "default": unit_interval_to_json(&thresholds.committee_normal),
+            "stateOfNoConfidence": unit_interval_to_json(&thresholds.committee_no_confidence),
+        },
+        "constitution": unit_interval_to_json(&thresholds.update_constitution),
+        "hardForkInitiation": unit_interval_to_json(&thresholds.hard_fork_initiation),
+        "protocolParametersUpdate": {
+            "network": unit_interval_to_json(&thresholds.pp_network_group),
+            "economic": unit_interval_to_json(&thresholds.pp_economic_group),
+            "technical": unit_interval_to_json(&thresholds.pp_technical_group),
+            "governance": unit_interval_to_json(&thresholds.pp_governance_group),
+        },
+        "treasuryWithdrawals": unit_interval_to_json(&thresholds.treasury_withdrawal),
+    })
+}
+
+fn drep_voting_thresholds_from_json(
+    json: &JsonValue,
+) -> Result<DRepVotingThresholds, ChainJsonError> {
+    let name = "delegateRepresentativeVotingThresholds";
+    let obj = as_object(json, name)?;
+    let committee = as_object(field(obj, "constitutionalCommittee")?, name)?;
+    let params = as_object(field(obj, "protocolParametersUpdate")?, name)?;
+    Ok(DRepVotingThresholds::new(
+        unit_interval_from_json(field(obj, "noConfidence")?, name)?,
+        unit_interval_from_json(field(committee, "default")?, name)?,
+        unit_interval_from_json(field(committee, "stateOfNoConfidence")?, name)?,
+        unit_interval_from_json(field(obj, "constitution")?, name)?,
+        unit_interval_from_json(field(obj, "hardForkInitiation")?, name)?,
+        unit_interval_from_json(field(params, "network")?, name)?,
+        unit_interval_from_json(field(params, "economic")?, name)?,
+        unit_interval_from_json(field(params, "technical")?, name)?,
+        unit_interval_from_json(field(params, "governance")?, name)?,
+        unit_interval_from_json(field(obj, "treasuryWithdrawals")?, name)?,
+    ))
+}
+
+fn cost_models_to_json(cost_models: &CostModels) -> JsonValue {
+    let mut obj = JsonObject::new();
+    for (language, costs) in [
+        ("plutus:v1", &cost_models.plutus_v1),
+        ("plutus:v2", &cost_models.plutus_v2),
+        ("plutus:v3", &cost_models.plutus_v3),
+    ] {
+        if let Some(costs) = costs {
+            let costs = costs
+                .iter()
+                .map(|cost| i64::try_from(i128::from(cost)).map(JsonValue::from))
+                .collect::<Result<Vec<_>, _>>()
+                // cost model parameters are far below i64 in practice, fall back to strings
+                .unwrap_or_else(|_| costs.iter().map(|c| c.to_string().into()).collect());
+            obj.insert(language.to_owned(), costs.into());
+        }
+    }
+    obj.into()
+}
+
+fn cost_models_from_json(json: &JsonValue) -> Result<CostModels, ChainJsonError> {
+    let mut cost_models = CostModels::new();
+    for (language, costs) in as_object(json, "plutusCostModels")? {
+        let costs = as_array(costs, language)?
+            .iter()
+            .map(|cost| Ok(as_i64(cost, language)?.into()))
+            .collect::<Result<Vec<_>, ChainJsonError>>()?;
+        match language.as_str() {
+            "plutus:v1" => cost_models.plutus_v1 = Some(costs),
+            "plutus:v2" => cost_models.plutus_v2 = Some(costs),
+            "plutus:v3" => cost_models.plutus_v3 = Some(costs),
+            other => {
+                return Err(invalid(
+                    "plutusCostModels",
+                    format!("unknown language {other}"),
+                ))
+            }
+        }
+    }
+    Ok(cost_models)
+}
+
+fn protocol_param_update_to_json(update: &ProtocolParamUpdate) -> JsonValue {
+    let mut obj = JsonObject::new();
+    let mut set = |key: &str, value: Option<JsonValue>| {
+        if let Some(value) = value {
+            obj.insert(key.to_owned(), value);
+        }
+    };
+    set("minFeeCoefficient", update.minfee_a.map(Into::into));
+    set("minFeeConstant", update.minfee_b.map(ada_to_json));
+    set(
+        "maxBlockBodySize",
+        update.max_block_body_size.map(bytes_size_to_json),
+    );
+    set(
+        "maxTransactionSize",
+        update.max_transaction_size.map(bytes_size_to_json),
+    );
+    set(
+        "maxBlockHeaderSize",
+        update.max_block_header_size.map(bytes_size_to_json),
+    );
+    set(
+        "stakeCredentialDeposit",
+        update.key_deposit.map(ada_to_json),
+    );
+    set("stakePoolDeposit", update.pool_deposit.map(ada_to_json));
+    set(
+        "stakePoolRetirementEpochBound",
+        update.maximum_epoch.map(Into::into),
+    );
+    set("desiredNumberOfStakePools", update.n_opt.map(Into::into));
+    set(
+        "stakePoolPledgeInfluence",
+        update.pool_pledge_influence.as_ref().map(rational_to_json),
+    );
+    set(
+        "monetaryExpansion",
+        update.expansion_rate.as_ref().map(unit_interval_to_json),
+    );
+    set(
+        "treasuryExpansion",
+        update
+            .treasury_growth_rate
+            .as_ref()
+            .map(unit_interval_to_json),
+    );
+    set("minStakePoolCost", update.min_pool_cost.map(ada_to_json));
+    set(
+        "minUtxoDepositCoefficient",
+        update.ada_per_utxo_byte.map(Into::into),
+    );
+    set(
+        "plutusCostModels",
+        update
+            .cost_models_for_script_languages
+            .as_ref()
+            .map(cost_models_to_json),
+    );
+    set(
+        "scriptExecutionPrices",
+        update.execution_costs.as_ref().map(|prices| {
+            json!({
+                "memory": rational_to_json(&prices.mem_price),
+                "cpu": rational_to_json(&prices.step_price),
+            })
+        }),
+    );
+    set(
+        "maxExecutionUnitsPerTransaction",
+        update.max_tx_ex_units.as_ref().map(ex_units_to_json),
+    );
+    set(
+        "maxExecutionUnitsPerBlock",
+        update.max_block_ex_units.as_ref().map(ex_units_to_json),
+    );
+    set(
+        "maxValueSize",
+        update.max_value_size.map(bytes_size_to_json),
+    );
+    set(
+        "collateralPercentage",
+        update.collateral_percentage.map(Into::into),
+    );
+    set(
+        "maxCollateralInputs",
+        update.max_collateral_inputs.map(Into::into),
+    );
+    set(
+        "stakePoolVotingThresholds",
+        update
+            .pool_voting_thresholds
+            .as_ref()
+            .map(pool_voting_thresholds_to_json),
+    );
+    set(
+        "delegateRepresentativeVotingThresholds",
+        update
+            .d_rep_voting_thresholds
+            .as_ref()
+            .map(drep_voting_thresholds_to_json),
+    );
+    set(
+        "constitutionalCommitteeMinSize",
+        update.min_committee_size.map(Into::into),
+    );
+    set(
+        "constitutionalCommitteeMaxTermLength",
+        update.committee_term_limit.map(Into::into),
+    );
+    set(
+        "governanceActionLifetime",
+        update.governance_action_validity_period.map(Into::into),
+    );
+    set(
+        "governanceActionDeposit",
+        update.governance_action_deposit.map(ada_to_json),
+    );
+    set(
+        "delegateRepresentativeDeposit",
+        update.d_rep_deposit.map(ada_to_json),
+    );
+    set(
+        "delegateRepresentativeMaxIdleTime",
+        update.d_rep_inactivity_period.map(Into::into),
+    );
+    obj.into()
+}
+
+fn protocol_param_update_from_json(
+    json: &JsonValue,
+) -> Result<ProtocolParamUpdate, ChainJsonError> {
+    let obj = as_object(json, "parameters")?;
+    let number = |key: &str| opt_field(obj, key).map(|n| as_u64(n, key)).transpose();
+    let ada = |key: &str| {
+        opt_field(obj, key)
+            .map(|n| ada_from_json(n, key))
+            .transpose()
+    };
+    let bytes = |key: &str| {
+        opt_field(obj, key)
+            .map(|n| bytes_size_from_json(n, key))
+            .transpose()
+    };
+    let interval = |key: &str| {
+        opt_field(obj, key)
+            .map(|n| unit_interval_from_json(n, key))
+            .transpose()
+    };
+    let ex_units = |key: &str| {
+        opt_field(obj, key)
+            .map(|n| ex_units_from_json(n, key))
+            .transpose()
+    };
+    let mut update = ProtocolParamUpdate::new();
+    update.minfee_a = number("minFeeCoefficient")?;
+    update.minfee_b = ada("minFeeConstant")?;
+    update.max_block_body_size = bytes("maxBlockBodySize")?;
+    update.max_transaction_size = bytes("maxTransactionSize")?;
+    update.max_block_header_size = bytes("maxBlockHeaderSize")?;
+    update.key_deposit = ada("stakeCredentialDeposit")?;
+    update.pool_deposit = ada("stakePoolDeposit")?;
+    update.maximum_epoch = number("stakePoolRetirementEpochBound")?;
+    update.n_opt = number("desiredNumberOfStakePools")?;
+    update.pool_pledge_influence = opt_field(obj, "stakePoolPledgeInfluence")
+        .map(|n| rational_from_json(n, "stakePoolPledgeInfluence"))
+        .transpose()?;
+    update.expansion_rate = interval("monetaryExpansion")?;
+    update.treasury_growth_rate = interval("treasuryExpansion")?;
+    update.min_pool_cost = ada("minStakePoolCost")?;
+    update.ada_per_utxo_byte = number("minUtxoDepositCoefficient")?;
+    update.cost_models_for_script_languages = opt_field(obj, "plutusCostModels")
+        .map(cost_models_from_json)
+        .transpose()?;
+    update.execution_costs = opt_field(obj, "scriptExecutionPrices")
+        .map(|prices| {
+            let prices = as_object(prices, "scriptExecutionPrices")?;
+            Ok::<_, ChainJsonError>(ExUnitPrices::new(
+                rational_from_json(field(prices, "memory")?, "memory")?,
+                rational_from_json(field(prices, "cpu")?, "cpu")?,
+            ))
+        })
+        .transpose()?;
+    update.max_tx_ex_units = ex_units("maxExecutionUnitsPerTransaction")?;
+    update.max_block_ex_units = ex_units("maxExecutionUnitsPerBlock")?;
+    update.max_value_size = bytes("maxValueSize")?;
+    update.collateral_percentage = number("collateralPercentage")?;
+    update.max_collateral_inputs = number("maxCollateralInputs")?;
+    update.pool_voting_thresholds = opt_field(obj, "stakePoolVotingThresholds")
+        .map(pool_voting_thresholds_from_json)
+        .transpose()?;
+    update.d_rep_voting_thresholds = opt_field(obj, "delegateRepresentativeVotingThresholds")
+        .map(drep_voting_thresholds_from_json)
+        .transpose()?;
+    update.min_committee_size = number("constitutionalCommitteeMinSize")?;
+    update.committee_term_limit = number("constitutionalCommitteeMaxTermLength")?;
+    update.governance_action_validity_period = number("governanceActionLifetime")?;
+    update.governance_action_deposit = ada("governanceActionDeposit")?;
+    update.d_rep_deposit = ada("delegateRepresentativeDeposit")?;
+    update.d_rep_inactivity_period = number("delegateRepresentativeMaxIdleTime")?;
+    Ok(update)
+}
+
+fn gov_action_to_json(action: &GovAction) -> Result<JsonValue, ChainJsonError> {
+    let mut obj = JsonObject::new();
+    match action {
+        GovAction::ParameterChangeAction(change) => {
+            obj.insert("type".to_owned(), "protocolParametersUpdate".into());
+            insert_ancestor(&mut obj, &change.gov_action_id);
+            obj.insert(
+                "parameters".to_owned(),
+                protocol_param_update_to_json(&change.protocol_param_update),
+            );
+        }
+        GovAction::HardForkInitiationAction(hard_fork) => {
+            obj.insert("type".to_owned(), "hardForkInitiation".into());
+            insert_ancestor(&mut obj, &hard_fork.action_id);
+            obj.insert(
+                "version".to_owned(),
+                json!({ "major": hard_fork.version.major, "minor": hard_fork.version.minor }),
+            );
+        }
+        GovAction::TreasuryWithdrawalsAction(withdrawals) => {
+            obj.insert("type".to_owned(), "treasuryWithdrawals".into());
+            obj.insert(
+                "withdrawals".to_owned(),
+                withdrawals_to_json(&withdrawals.withdrawal)?,
+            );
+        }
+        GovAction::NoConfidence(no_confidence) => {
+            obj.insert("type".to_owned(), "noConfidence".into());
+            insert_ancestor(&mut obj, &no_confidence.action_id);
+        }
+        GovAction::NewCommittee(committee) => {
+            obj.insert("type".to_owned(), "constitutionalCommittee".into());
+            insert_ancestor(&mut obj, &committee.action_id);
+            let added = committee
+                .committee
+                .credentials
+                .iter()
+                .map(|(credential, epoch)| {
+                    let mut member = JsonObject::new();
+                    insert_credential(&mut member, "id", credential);
+                    member.insert("mandate".to_owned(), json!({ "epoch": epoch }));
+                    JsonValue::from(member)
+                })
+                .collect::<Vec<_>>();
+            let removed = committee
+                .cold_credentials
+                .iter()
+                .map(credential_to_json)
+                .collect::<Vec<_>>();
+            obj.insert(
+                "members".to_owned(),
+                json!({ "added": added, "removed": removed }),
+            );
+            obj.insert(
+                "quorum".to_owned(),
+                unit_interval_to_json(&committee.committee.unit_interval),
+            );
+        }
+        GovAction::NewConstitution(constitution) => {
+            obj.insert("type".to_owned(), "constitution".into());
+            insert_ancestor(&mut obj, &constitution.action_id);
+            let mut inner = JsonObject::new();
+            inner.insert(
+                "metadata".to_owned(),
+                anchor_to_json(&constitution.constitution.anchor),
+            );
+            if let Some(script_hash) = &constitution.constitution.script_hash {
+                inner.insert(
+                    "guardrails".to_owned(),
+                    json!({ "hash": script_hash.to_hex() }),
+                );
+            }
+            obj.insert("constitution".to_owned(), inner.into());
+        }
+        GovAction::InfoAction { .. } => {
+            obj.insert("type".to_owned(), "information".into());
+        }
+    }
+    Ok(obj.into())
+}
+
+fn gov_action_from_json(json: &JsonValue) -> Result<GovAction, ChainJsonError> {
+    let obj = as_object(json, "action")?;
+    match as_str(field(obj, "type")?, "type")? {
+        "protocolParametersUpdate" => Ok(GovAction::new_parameter_change_action(
+            ancestor_from_json(obj)?,
+            protocol_param_update_from_json(field(obj, "parameters")?)?,
+        )),
+        "hardForkInitiation" => {
+            let version = as_object(field(obj, "version")?, "version")?;
+            Ok(GovAction::new_hard_fork_initiation_action(
+                ancestor_from_json(obj)?,
+                ProtocolVersion::new(
+                    as_u64(field(version, "major")?, "major")?,
+                    as_u64(field(version, "minor")?, "minor")?,
+                ),
+            ))
+        }
+        "treasuryWithdrawals" => Ok(GovAction::new_treasury_withdrawals_action(
+            withdrawals_from_json(field(obj, "withdrawals")?)?,
+        )),
+        "noConfidence" => Ok(GovAction::new_no_confidence(ancestor_from_json(obj)?)),
+        "constitutionalCommittee" => {
+            let members = as_object(field(obj, "members")?, "members")?;
+            let mut credentials = OrderedHashMap::new();
+            for member in as_array(field(members, "added")?, "added")? {
+                let member_obj = as_object(member, "added")?;
+                let mandate = as_object(field(member_obj, "mandate")?, "mandate")?;
+                credentials.insert(
+                    credential_from_obj(member_obj, "id")?,
+                    as_u64(field(mandate, "epoch")?, "epoch")?,
+                );
+            }
+            let cold_credentials = as_array(field(members, "removed")?, "removed")?
+                .iter()
+                .map(|member| credential_from_json(member, "removed"))
+                .collect::<Result<_, _>>()?;
+            Ok(GovAction::new_new_committee(
+                ancestor_from_json(obj)?,
+                cold_credentials,
+                Committee::new(
+                    credentials,
+                    unit_interval_from_json(field(obj, "quorum")?, "quorum")?,
+                ),
+            ))
+        }
+        "constitution" => {
+            let constitution = as_object(field(obj, "constitution")?, "constitution")?;
+            let script_hash = opt_field(constitution, "guardrails")
+                .map(|guardrails| {
+                    from_hex::<ScriptHash>(
+                        field(as_object(guardrails, "guardrails")?, "hash")?,
+                        "hash",
+                    )
+                })
+                .transpose()?;
+            Ok(GovAction::new_new_constitution(
+                ancestor_from_json(obj)?,
+                Constitution::new(
+                    anchor_from_json(field(constitution, "metadata")?)?,
+                    script_hash,
+                ),
+            ))
+        }
+        "information" => Ok(GovAction::new_info_action()),
+        other => Err(invalid(
+            "type",
+            format!("unknown governance action {other}"),
+        )),
+    }
+}
+
+pub(super) fn proposal_to_json(proposal: &ProposalProcedure) -> Result<JsonValue, ChainJsonError> {
+    Ok(json!({
+        "deposit": ada_to_json(proposal.deposit),
+        "returnAccount": reward_account_to_json(&proposal.reward_account)?,
+        "metadata": anchor_to_json(&proposal.anchor),
+        "action": gov_action_to_json(&proposal.gov_action)?,
+    }))
+}
+
+pub(super) fn proposal_from_json(json: &JsonValue) -> Result<ProposalProcedure, ChainJsonError> {
+    let obj = as_object(json, "proposals")?;
+    Ok(ProposalProcedure::new(
+        ada_from_json(field(obj, "deposit")?, "deposit")?,
+        reward_account_from_json(field(obj, "returnAccount")?, "returnAccount")?,
+        gov_action_from_json(field(obj, "action")?)?,
+        anchor_from_json(field(obj, "metadata")?)?,
+    ))
+}
+
+fn voter_to_json(voter: &Voter) -> JsonValue {
+    let (role, id, from) = match voter {
+        Voter::ConstitutionalCommitteeHotKeyHash {
+            ed25519_key_hash, ..
+        } => (
+            "constitutionalCommittee",
+            ed25519_key_hash.to_hex(),
+            "verificationKey",
+        ),
+        Voter::ConstitutionalCommitteeHotScriptHash { script_hash, .. } => {
+            ("constitutionalCommittee", script_hash.to_hex(), "script")
+        }
+        Voter::DRepKeyHash {
+            ed25519_key_hash, ..
+        } => (
+            "delegateRepresentative",
+            ed25519_key_hash.to_hex(),
+            "verificationKey",
+        ),
+        Voter::DRepScriptHash { script_hash, .. } => {
+            ("delegateRepresentative", script_hash.to_hex(), "script")
+        }
+        Voter::StakingPoolKeyHash {
+            ed25519_key_hash, ..
+        } => {
+            return json!({ "role": "stakePoolOperator", "id": pool_id_to_bech32(ed25519_key_hash) })
+        }
+    };
+    json!({ "role": role, "id": id, "from": from })
+}
+
+fn voter_from_json(json: &JsonValue) -> Result<Voter, ChainJsonError> {
+    let obj = as_object(json, "issuer")?;
+    match as_str(field(obj, "role")?, "role")? {
+        "stakePoolOperator" => Ok(Voter::new_staking_pool_key_hash(pool_id_from_bech32(
+            field(obj, "id")?,
+            "id",
+        )?)),
+        "constitutionalCommittee" => Ok(match credential_from_obj(obj, "id")? {
+            Credential::PubKey { hash, .. } => {
+                Voter::new_constitutional_committee_hot_key_hash(hash)
+            }
+            Credential::Script { hash, .. } => {
+                Voter::new_constitutional_committee_hot_script_hash(hash)
+            }
+        }),
+        "delegateRepresentative" => Ok(match credential_from_obj(obj, "id")? {
+            Credential::PubKey { hash, .. } => Voter::new_d_rep_key_hash(hash),
+            Credential::Script { hash, .. } => Voter::new_d_rep_script_hash(hash),
+        }),
+        other => Err(invalid("role", format!("unknown voter role {other}"))),
+    }
+}
+
+pub(super) fn votes_to_json(procedures: &VotingProcedures) -> JsonValue {
+    procedures
+        .iter()
+        .flat_map(|(voter, votes)| {
+            votes.iter().map(move |(action_id, procedure)| {
+                let mut obj = JsonObject::new();
+                obj.insert("issuer".to_owned(), voter_to_json(voter));
+                obj.insert("proposal".to_owned(), gov_action_id_to_json(action_id));
+                let vote = match procedure.vote {
+                    Vote::No => "no",
+                    Vote::Yes => "yes",
+                    Vote::Abstain => "abstain",
+                };
+                obj.insert("vote".to_owned(), vote.into());
+                if let Some(anchor) = &procedure.anchor {
+                    obj.insert("anchor".to_owned(), anchor_to_json(anchor));
+                }
+                JsonValue::from(obj)
+            })
+        })
+        .collect()
+}
+
+pub(super) fn votes_from_json(json: &JsonValue) -> Result<VotingProcedures, ChainJsonError> {
+    let mut procedures = VotingProcedures::new();
+    for vote_json in as_array(json, "votes")? {
+        let obj = as_object(vote_json, "votes")?;
+        let vote = match as_str(field(obj, "vote")?, "vote")? {
+            "no" => Vote::No,
+            "yes" => Vote::Yes,
+            "abstain" => Vote::Abstain,
+            other => return Err(invalid("vote", format!("unknown vote {other}"))),
+        };
+        let anchor = opt_field(obj, "anchor").map(anchor_from_json).transpose()?;
+        procedures
+            .entry(voter_from_json(field(obj, "issuer")?)?)
+            .or_default()
+            .insert(
+                gov_action_id_from_json(field(obj, "proposal")?)?,
+                VotingProcedure::new(vote, anchor),
+            );
+    }
+    Ok(procedures)
+}
+
+fn redeemer_to_json(redeemer: &Redeemer) -> JsonValue {
+    let purpose = match redeemer.tag {
+        RedeemerTag::Spend => "spend",
+        RedeemerTag::Mint => "mint",
+        RedeemerTag::Cert => "publish",
+        RedeemerTag::Reward => "withdraw",
+    };
+    json!({
+        "validator": { "purpose": purpose, "index": redeemer.index },
+        "redeemer": hex::encode(redeemer.data.to_cbor_bytes()),
+        "executionUnits": ex_units_to_json(&redeemer.ex_units),
+    })
+}
+
+fn redeemer_from_json(json: &JsonValue) -> Result<Redeemer, ChainJsonError> {
+    let obj = as_object(json, "redeemers")?;
+    let validator = as_object(field(obj, "validator")?, "validator")?;
+    let tag = match as_str(field(validator, "purpose")?, "purpose")? {
+        "spend" => RedeemerTag::Spend,
+        "mint" => RedeemerTag::Mint,
+        "publish" => RedeemerTag::Cert,
+        "withdraw" => RedeemerTag::Reward,
+        other => {
+            return Err(ChainJsonError::Unsupported(format!(
+                "redeemer purpose {other}"
+            )))
+        }
+    };
+    Ok(Redeemer::new(
+        tag,
+        as_u64(field(validator, "index")?, "index")?,
+        from_cbor_hex(field(obj, "redeemer")?, "redeemer")?,
+        ex_units_from_json(field(obj, "executionUnits")?, "executionUnits")?,
+    ))
+}
+
+fn metadata_to_json(metadata: &Metadata) -> Result<JsonValue, ChainJsonError> {
+    let mut labels = JsonObject::new();
+    for (label, metadatum) in metadata.entries.iter() {
+        let mut entry = JsonObject::new();
+        // not all metadata can be represented by plain JSON but the CBOR is always there
+        if let Ok(json) =
+            decode_metadatum_to_json_value(metadatum, MetadataJsonSchema::NoConversions)
+        {
+            entry.insert("json".to_owned(), json.into());
+        }
+        entry.insert(
+            "cbor".to_owned(),
+            hex::encode(metadatum.to_cbor_bytes()).into(),
+        );
+        labels.insert(label.to_string(), entry.into());
+    }
+    Ok(json!({ "labels": labels }))
+}
+
+fn metadata_from_json(json: &JsonValue) -> Result<Metadata, ChainJsonError> {
+    let labels = as_object(field(as_object(json, "metadata")?, "labels")?, "labels")?;
+    let mut metadata = Metadata::new();
+    for (label, entry) in labels {
+        let label_num = label
+            .parse::<u64>()
+            .map_err(|e| invalid("labels", format!("{label}: {e}")))?;
+        let entry = as_object(entry, label)?;
+        let metadatum = match opt_field(entry, "cbor") {
+            Some(cbor) => from_cbor_hex(cbor, "cbor")?,
+            None => encode_json_value_to_metadatum(
+                field(entry, "json")?.clone().into(),
+                MetadataJsonSchema::NoConversions,
+            )?,
+        };
+        metadata.entries.push((label_num, metadatum));
+    }
+    // JSON objects don't keep the order so use the canonical one
+    metadata.entries.sort_by_key(|(label, _)| *label);
+    Ok(metadata)
+}
+
+fn auxiliary_scripts(auxiliary_data: &AuxiliaryData) -> Vec<Script> {
+    let mut scripts = Vec::new();
+    if let Some(native_scripts) = auxiliary_data.native_scripts() {
+        scripts.extend(native_scripts.iter().cloned().map(Script::new_native));
+    }
+    if let Some(v1) = auxiliary_data.plutus_v1_scripts() {
+        scripts.extend(v1.iter().cloned().map(Script::new_plutus_v1));
+    }
+    if let Some(v2) = auxiliary_data.plutus_v2_scripts() {
+        scripts.extend(v2.iter().cloned().map(Script::new_plutus_v2));
+    }
+    if let AuxiliaryData::Conway(conway) = auxiliary_data {
+        if let Some(v3) = &conway.plutus_v3_scripts {
+            scripts.extend(v3.iter().cloned().map(Script::new_plutus_v3));
+        }
+    }
+    scripts
+}
+
+fn witness_scripts(witness_set: &TransactionWitnessSet) -> Vec<Script> {
+    let mut scripts = Vec::new();
+    if let Some(native_scripts) = &witness_set.native_scripts {
+        scripts.extend(native_scripts.iter().cloned().map(Script::new_native));
+    }
+    if let Some(v1) = &witness_set.plutus_v1_scripts {
+        scripts.extend(v1.iter().cloned().map(Script::new_plutus_v1));
+    }
+    if let Some(v2) = &witness_set.plutus_v2_scripts {
+        scripts.extend(v2.iter().cloned().map(Script::new_plutus_v2));
+    }
+    if let Some(v3) = &witness_set.plutus_v3_scripts {
+        scripts.extend(v3.iter().cloned().map(Script::new_plutus_v3));
+    }
+    scripts
+}
+
+pub(crate) fn transaction_to_json(tx: &Transaction) -> Result<JsonValue, ChainJsonError> {
+    let body = &tx.body;
+    let witness_set = &tx.witness_set;
+    let mut obj = JsonObject::new();
+    let mut set = |key: &str, value: JsonValue| {
+        obj.insert(key.to_owned(), value);
+    };
+    set("id", body.hash().to_hex().into());
+    set(
+        "spends",
+        if tx.is_valid { "inputs" } else { "collaterals" }.into(),
+    );
+    set("inputs", body.inputs.iter().map(input_to_json).collect());
+    if let Some(references) = &body.reference_inputs {
+        set("references", references.iter().map(input_to_json).collect());
+    }
+    if let Some(collaterals) = &body.collateral_inputs {
+        set(
+            "collaterals",
+            collaterals.iter().map(input_to_json).collect(),
+        );
+    }
+    if let Some(collateral_return) = &body.collateral_return {
+        set("collateralReturn", output_to_json(collateral_return)?);
+    }
+    if let Some(total_collateral) = body.total_collateral {
+        set("totalCollateral", ada_to_json(total_collateral));
+    }
+    set(
+        "outputs",
+        body.outputs
+            .iter()
+            .map(output_to_json)
+            .collect::<Result<_, _>>()?,
+    );
+    if let Some(certs) = &body.certs {
+        set(
+            "certificates",
+            certs
+                .iter()
+                .map(certificate_to_json)
+                .collect::<Result<_, _>>()?,
+        );
+    }
+    if let Some(withdrawals) = &body.withdrawals {
+        set("withdrawals", withdrawals_to_json(withdrawals)?);
+    }
+    if let Some(mint) = &body.mint {
+        set("mint", mint_to_json(mint));
+    }
+    set("fee", ada_to_json(body.fee));
+    let mut validity_interval = JsonObject::new();
+    if let Some(start) = body.validity_interval_start {
+        validity_interval.insert("invalidBefore".to_owned(), start.into());
+    }
+    if let Some(ttl) = body.ttl {
+        validity_interval.insert("invalidAfter".to_owned(), ttl.into());
+    }
+    set("validityInterval", validity_interval.into());
+    if let Some(network_id) = &body.network_id {
+        let network = match network_id.network {
+            1 => "mainnet",
+            _ => "testnet",
+        };
+        set("network", network.into());
+    }
+    if let Some(script_data_hash) = &body.script_data_hash {
+        set("scriptIntegrityHash", script_data_hash.to_hex().into());
+    }
+    if let Some(required_signers) = &body.required_signers {
+        set(
+            "requiredExtraSignatories",
+            required_signers
+                .iter()
+                .map(|signer| JsonValue::from(signer.to_hex()))
+                .collect(),
+        );
+    }
+    if let Some(proposals) = &body.proposal_procedures {
+        set(
+            "proposals",
+            proposals
+                .iter()
+                .map(proposal_to_json)
+                .collect::<Result<_, _>>()?,
+        );
+    }
+    if let Some(votes) = &body.voting_procedures {
+        set("votes", votes_to_json(votes));
+    }
+    if body.current_treasury_value.is_some() || body.donation.is_some() {
+        let mut treasury = JsonObject::new();
+        if let Some(value) = body.current_treasury_value {
+            treasury.insert("value".to_owned(), ada_to_json(value));
+        }
+        if let Some(donation) = body.donation {
+            treasury.insert("donation".to_owned(), ada_to_json(donation));
+        }
+        set("treasury", treasury.into());
+    }
+    if let Some(auxiliary_data) = &tx.auxiliary_data {
+        let mut metadata = match auxiliary_data.metadata() {
+            Some(metadata) => metadata_to_json(metadata)?,
+            None => json!({ "labels": {} }),
+        };
+        let hash = body
+            .auxiliary_data_hash
+            .unwrap_or_else(|| hash_auxiliary_data(auxiliary_data));
+        metadata["hash"] = hash.to_hex().into();
+        set("metadata", metadata);
+    }
+    let mut signatories = Vec::new();
+    for witness in witness_set.vkeywitnesses.iter().flatten() {
+        signatories.push(json!({
+            "key": witness.vkey.to_raw_hex(),
+            "signature": witness.ed25519_signature.to_raw_hex(),
+        }));
+    }
+    for witness in witness_set.bootstrap_witnesses.iter().flatten() {
+        signatories.push(json!({
+            "key": witness.public_key.to_raw_hex(),
+            "signature": witness.signature.to_raw_hex(),
+            "chainCode": hex::encode(&witness.chain_code),
+            "addressAttributes": hex::encode(witness.attributes.to_bytes()),
+        }));
+    }
+    set("signatories", signatories.into());
+    let mut scripts = witness_scripts(witness_set);
+    if let Some(auxiliary_data) = &tx.auxiliary_data {
+        scripts.extend(auxiliary_scripts(auxiliary_data));
+    }
+    if !scripts.is_empty() {
+        set(
+            "scripts",
+            scripts
+                .iter()
+                .map(|script| (script.hash().to_hex(), script_to_json(script)))
+                .collect::<JsonObject>()
+                .into(),
+        );
+    }
+    if let Some(datums) = &witness_set.plutus_datums {
+        set(
+            "datums",
+            datums
+                .iter()
+                .map(|datum| {
+                    (
+                        hash_plutus_data(datum).to_hex(),
+                        hex::encode(datum.to_cbor_bytes()).into(),
+                    )
+                })
+                .collect::<JsonObject>()
+                .into(),
+        );
+    }
+    if let Some(redeemers) = &witness_set.redeemers {
+        set(
+            "redeemers",
+            redeemers.iter().map(redeemer_to_json).collect(),
+        );
+    }
+    set("cbor", hex::encode(tx.to_cbor_bytes()).into());
+    Ok(obj.into())
+}
+
+pub(crate) fn transaction_from_json(json: &JsonValue) -> Result<Transaction, ChainJsonError> {
+    let obj = as_object(json, "transaction")?;
+    if let Some(cbor) = opt_field(obj, "cbor") {
+        return from_cbor_hex(cbor, "cbor");
+    }
+
+    let outputs = as_array(field(obj, "outputs")?, "outputs")?
+        .iter()
+        .map(output_from_json)
+        .collect::<Result<_, _>>()?;
+    let mut body = TransactionBody::new(
+        inputs_from_json(field(obj, "inputs")?, "inputs")?,
+        outputs,
+        ada_from_json(field(obj, "fee")?, "fee")?,
+    );
+    if let Some(validity_interval) = opt_field(obj, "validityInterval") {
+        let validity_interval = as_object(validity_interval, "validityInterval")?;
+        body.validity_interval_start = opt_field(validity_interval, "invalidBefore")
+            .map(|slot| as_u64(slot, "invalidBefore"))
+            .transpose()?;
+        body.ttl = opt_field(validity_interval, "invalidAfter")
+            .map(|slot| as_u64(slot, "invalidAfter"))
+            .transpose()?;
+    }
+    body.reference_inputs = opt_field(obj, "references")
+        .map(|inputs| inputs_from_json(inputs, "references"))
+        .transpose()?;
+    body.collateral_inputs = opt_field(obj, "collaterals")
+        .map(|inputs| inputs_from_json(inputs, "collaterals"))
+        .transpose()?;
+    body.collateral_return = opt_field(obj, "collateralReturn")
+        .map(output_from_json)
+        .transpose()?;
+    body.total_collateral = opt_field(obj, "totalCollateral")
+        .map(|coin| ada_from_json(coin, "totalCollateral"))
+        .transpose()?;
+    body.certs = opt_field(obj, "certificates")
+        .map(|certs| {
+            as_array(certs, "certificates")?
+                .iter()
+                .map(certificate_from_json)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    body.withdrawals = opt_field(obj, "withdrawals")
+        .map(withdrawals_from_json)
+        .transpose()?;
+    body.mint = opt_field(obj, "mint").map(mint_from_json).transpose()?;
+    body.network_id = opt_field(obj, "network")
+        .map(|network| match as_str(network, "network")? {
+            "mainnet" => Ok(NetworkId::mainnet()),
+            "testnet" => Ok(NetworkId::testnet()),
+            other => Err(invalid("network", format!("unknown network {other}"))),
+        })
+        .transpose()?;
+    body.script_data_hash = opt_field(obj, "scriptIntegrityHash")
+        .map(|hash| from_hex(hash, "scriptIntegrityHash"))
+        .transpose()?;
+    body.required_signers = opt_field(obj, "requiredExtraSignatories")
+        .map(|signers| {
+            as_array(signers, "requiredExtraSignatories")?
+                .iter()
+                .map(|signer| from_hex(signer, "requiredExtraSignatories"))
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    body.proposal_procedures = opt_field(obj, "proposals")
+        .map(|proposals| {
+            as_array(proposals, "proposals")?
+                .iter()
+                .map(proposal_from_json)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    body.voting_procedures = opt_field(obj, "votes").map(votes_from_json).transpose()?;
+    if let Some(treasury) = opt_field(obj, "treasury") {
+        let treasury = as_object(treasury, "treasury")?;
+        body.current_treasury_value = opt_field(treasury, "value")
+            .map(|coin| ada_from_json(coin, "value"))
+            .transpose()?;
+        body.donation = opt_field(treasury, "donation")
+            .map(|coin| ada_from_json(coin, "donation"))
+            .transpose()?;
+    }
+
+    let mut witness_set = TransactionWitnessSet::new();
+    for signatory in as_array(field(obj, "signatories")?, "signatories")? {
+        let signatory = as_object(signatory, "signatories")?;
+        let vkey: PublicKey = from_hex(field(signatory, "key")?, "key")?;
+        let signature: Ed25519Signature = from_hex(field(signatory, "signature")?, "signature")?;
+        match opt_field(signatory, "chainCode") {
+            Some(chain_code) => {
+                let attributes = match opt_field(signatory, "addressAttributes") {
+                    Some(attributes) => AddrAttributes::from_cbor_bytes(&bytes_from_hex(
+                        attributes,
+                        "addressAttributes",
+                    )?)?,
+                    None => AddrAttributes::default(),
+                };
+                witness_set
+                    .bootstrap_witnesses
+                    .get_or_insert_with(Vec::new)
+                    .push(BootstrapWitness::new(
+                        vkey,
+                        signature,
+                        bytes_from_hex(chain_code, "chainCode")?,
+                        attributes,
+                    )?);
+            }
+            None => witness_set
+                .vkeywitnesses
+                .get_or_insert_with(Vec::new)
+                .push(Vkeywitness::new(vkey, signature)),
+        }
+    }
+    if let Some(scripts) = opt_field(obj, "scripts") {
+        for script in as_object(scripts, "scripts")?.values() {
+            match script_from_json(script)? {
+                Script::Native { script, .. } => witness_set
+                    .native_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV1 { script, .. } => witness_set
+                    .plutus_v1_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV2 { script, .. } => witness_set
+                    .plutus_v2_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+                Script::PlutusV3 { script, .. } => witness_set
+                    .plutus_v3_scripts
+                    .get_or_insert_with(Vec::new)
+                    .push(script),
+            }
+        }
+    }
+    witness_set.plutus_datums = opt_field(obj, "datums")
+        .map(|datums| {
+            as_object(datums, "datums")?
+                .values()
+                .map(|datum| from_cbor_hex::<PlutusData>(datum, "datums"))
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+    witness_set.redeemers = opt_field(obj, "redeemers")
+        .map(|redeemers| {
+            as_array(redeemers, "redeemers")?
+                .iter()
+                .map(redeemer_from_json)
+                .collect::<Result<_, _>>()
+        })
+        .transpose()?;
+
+    let auxiliary_data = opt_field(obj, "metadata")
+        .map(|metadata| {
+            let mut auxiliary_data = ConwayFormatAuxData::new();
+            auxiliary_data.metadata = Some(metadata_from_json(metadata)?);
+            Ok::<_, ChainJsonError>(AuxiliaryData::new_conway(auxiliary_data))
+        })
+        .transpose()?;
+    body.auxiliary_data_hash = auxiliary_data.as_ref().map(hash_auxiliary_data);
+
+    let is_valid = match opt_field(obj, "spends") {
+        Some(spends) => as_str(spends, "spends")? == "inputs",
+        None => true,
+    };
+    Ok(Transaction::new(
+        body,
+        witness_set,
+        is_valid,
+        auxiliary_data,
+    ))
+}
@@ -26,6 +26,7 @@ pub mod json;
 pub mod min_ada;
 pub mod plutus;
 pub mod serialization;
+pub mod text_envelope;
 pub mod transaction;
 pub mod utils;
 
@@ -38,7 +39,7 @@ pub use cml_core::{
     error::{DeserializeError, DeserializeFailure},
     ordered_hash_map::OrderedHashMap,
     serialization::{Deserialize, LenEncoding, Serialize, StringEncoding},
-    CertificateIndex, Epoch, Int, Slot, TransactionIndex,
+    CertificateIndex, Epoch, Int, Slot, TransactionIndex, UnknownFields,
 };
 
 pub mod cbor_encodings;
@@ -2,6 +2,7 @@
 // https://github.com/dcSpark/cddl-codegen
 
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 
 #[derive(Clone, Debug, Default)]
 pub struct CostModelsEncoding {
@@ -13,6 +14,7 @@ pub struct CostModelsEncoding {
     pub plutus_v2_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v3_encoding: LenEncoding,
     pub plutus_v3_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
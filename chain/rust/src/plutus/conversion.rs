@@ -0,0 +1,503 @@
+//! Typed conversions between Rust types and PlutusData.
+//!
+//! The representations follow how Plutus/Aiken types are represented on-chain:
+//! * structs/enum variants are constructors (`ConstrPlutusData`) with their fields in order
+//! * `bool` is `False = 0` / `True = 1` and `Option` is `Some = 0` / `None = 1` constructors
+//! * `Vec<T>` is a list, `BTreeMap<K, V>` a map, integers are integers
+//! * `[u8; N]`, `String` (UTF-8) and hashes are bytes. Use `#[plutus(bytes)]` for `Vec<u8>` fields
+//!
+//! These can be derived for your own types using the `cml-derive` crate.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use cml_crypto::{DatumHash, Ed25519KeyHash, RawBytesEncoding, ScriptHash, TransactionHash};
+
+use super::{ConstrPlutusData, PlutusData, PlutusMap};
+use crate::utils::BigInteger;
+
+pub trait ToPlutusData {
+    fn to_plutus_data(&self) -> PlutusData;
+}
+
+pub trait FromPlutusData: Sized {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlutusDataPathSegment {
+    /// Named field of a struct/variant or the k/v of a map entry
+    Field(String),
+    /// Index of a list element, a tuple field or a map entry
+    Index(usize),
+    /// Enum variant the data was matched as
+    Variant(String),
+}
+
+impl std::fmt::Display for PlutusDataPathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Field(name) => write!(f, ".{name}"),
+            Self::Index(index) => write!(f, "[{index}]"),
+            Self::Variant(name) => write!(f, "::{name}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum PlutusDataErrorKind {
+    #[error("expected {expected}, found {found}")]
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("unknown constructor {found}, expected one of {expected:?}")]
+    UnknownConstructor { expected: Vec<u64>, found: u64 },
+    #[error("expected {expected} fields, found {found}")]
+    WrongFieldCount { expected: usize, found: usize },
+    #[error("integer {value} out of range for {target}")]
+    IntegerOutOfRange { value: String, target: &'static str },
+    #[error("expected {expected} bytes, found {found}")]
+    WrongByteLength { expected: usize, found: usize },
+    #[error("invalid UTF-8")]
+    InvalidUtf8,
+    #[error("{0}")]
+    Custom(String),
+}
+
+/// Error converting from PlutusData along with where in the data it happened
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlutusDataError {
+    /// From the outermost type inwards
+    pub path: Vec<PlutusDataPathSegment>,
+    pub kind: PlutusDataErrorKind,
+}
+
+impl PlutusDataError {
+    pub fn new(kind: PlutusDataErrorKind) -> Self {
+        Self {
+            path: Vec::new(),
+            kind,
+        }
+    }
+
+    pub fn at_field(mut self, name: &str) -> Self {
+        self.path
+            .insert(0, PlutusDataPathSegment::Field(name.to_owned()));
+        self
+    }
+
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.insert(0, PlutusDataPathSegment::Index(index));
+        self
+    }
+
+    pub fn in_variant(mut self, name: &str) -> Self {
+        self.path
+            .insert(0, PlutusDataPathSegment::Variant(name.to_owned()));
+        self
+    }
+
+    /// e.g. `$::Mint.outputs[2].amount`
+    pub fn path_string(&self) -> String {
+        let mut path = String::from("$");
+        for segment in self.path.iter() {
+            path.push_str(&segment.to_string());
+        }
+        path
+    }
+}
+
+impl std::fmt::Display for PlutusDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path_string(), self.kind)
+    }
+}
+
+impl std::error::Error for PlutusDataError {}
+
+impl From<PlutusDataErrorKind> for PlutusDataError {
+    fn from(kind: PlutusDataErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+fn kind_name(data: &PlutusData) -> &'static str {
+    match data {
+        PlutusData::ConstrPlutusData(_) => "constructor",
+        PlutusData::Map(_) => "map",
+        PlutusData::List { .. } => "list",
+        PlutusData::Integer(_) => "integer",
+        PlutusData::Bytes { .. } => "bytes",
+    }
+}
+
+fn unexpected(expected: &'static str, found: &PlutusData) -> PlutusDataError {
+    PlutusDataErrorKind::UnexpectedType {
+        expected,
+        found: kind_name(found),
+    }
+    .into()
+}
+
+pub fn constr_to_plutus_data(alternative: u64, fields: Vec<PlutusData>) -> PlutusData {
+    PlutusData::new_constr_plutus_data(ConstrPlutusData::new(alternative, fields))
+}
+
+/// The constructor index and fields of a constructor
+pub fn constr_from_plutus_data(data: &PlutusData) -> Result<(u64, &[PlutusData]), PlutusDataError> {
+    match data {
+        PlutusData::ConstrPlutusData(constr) => Ok((constr.alternative, &constr.fields)),
+        _ => Err(unexpected("constructor", data)),
+    }
+}
+
+/// The fields of a constructor that must have the given index and number of fields
+pub fn constr_fields_from_plutus_data(
+    data: &PlutusData,
+    alternative: u64,
+    field_count: usize,
+) -> Result<&[PlutusData], PlutusDataError> {
+    let (found, fields) = constr_from_plutus_data(data)?;
+    if found != alternative {
+        return Err(PlutusDataErrorKind::UnknownConstructor {
+            expected: vec![alternative],
+            found,
+        }
+        .into());
+    }
+    check_field_count(fields, field_count)?;
+    Ok(fields)
+}
+
+pub fn check_field_count(fields: &[PlutusData], expected: usize) -> Result<(), PlutusDataError> {
+    if fields.len() != expected {
+        return Err(PlutusDataErrorKind::WrongFieldCount {
+            expected,
+            found: fields.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+pub fn bytes_to_plutus_data(bytes: &[u8]) -> PlutusData {
+    PlutusData::new_bytes(bytes.to_vec())
+}
+
+pub fn bytes_from_plutus_data(data: &PlutusData) -> Result<Vec<u8>, PlutusDataError> {
+    match data {
+        PlutusData::Bytes { bytes, .. } => Ok(bytes.clone()),
+        _ => Err(unexpected("bytes", data)),
+    }
+}
+
+fn integer_from_plutus_data(data: &PlutusData) -> Result<&BigInteger, PlutusDataError> {
+    match data {
+        PlutusData::Integer(integer) => Ok(integer),
+        _ => Err(unexpected("integer", data)),
+    }
+}
+
+impl ToPlutusData for PlutusData {
+    fn to_plutus_data(&self) -> PlutusData {
+        self.clone()
+    }
+}
+
+impl FromPlutusData for PlutusData {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        Ok(data.clone())
+    }
+}
+
+impl ToPlutusData for BigInteger {
+    fn to_plutus_data(&self) -> PlutusData {
+        PlutusData::new_integer(self.clone())
+    }
+}
+
+impl FromPlutusData for BigInteger {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        integer_from_plutus_data(data).cloned()
+    }
+}
+
+macro_rules! impl_plutus_data_int {
+    ($($int:ty),*) => {
+        $(
+            impl ToPlutusData for $int {
+                fn to_plutus_data(&self) -> PlutusData {
+                    PlutusData::new_integer(BigInteger::from(*self))
+                }
+            }
+
+            impl FromPlutusData for $int {
+                fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+                    let integer = integer_from_plutus_data(data)?;
+                    integer
+                        .as_i128()
+                        .and_then(|i| <$int>::try_from(i).ok())
+                        .ok_or_else(|| {
+                            PlutusDataErrorKind::IntegerOutOfRange {
+                                value: integer.to_string(),
+                                target: stringify!($int),
+                            }
+                            .into()
+                        })
+                }
+            }
+        )*
+    };
+}
+
+impl_plutus_data_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl ToPlutusData for u128 {
+    fn to_plutus_data(&self) -> PlutusData {
+        PlutusData::new_integer(BigInteger::from(*self))
+    }
+}
+
+impl FromPlutusData for u128 {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        let integer = integer_from_plutus_data(data)?;
+        integer.as_u128().ok_or_else(|| {
+            PlutusDataErrorKind::IntegerOutOfRange {
+                value: integer.to_string(),
+                target: "u128",
+            }
+            .into()
+        })
+    }
+}
+
+impl ToPlutusData for bool {
+    fn to_plutus_data(&self) -> PlutusData {
+        constr_to_plutus_data(u64::from(*self), vec![])
+    }
+}
+
+impl FromPlutusData for bool {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        match constr_from_plutus_data(data)? {
+            (0, []) => Ok(false),
+            (1, []) => Ok(true),
+            (0 | 1, fields) => Err(PlutusDataErrorKind::WrongFieldCount {
+                expected: 0,
+                found: fields.len(),
+            }
+            .into()),
+            (found, _) => Err(PlutusDataErrorKind::UnknownConstructor {
+                expected: vec![0, 1],
+                found,
+            }
+            .into()),
+        }
+    }
+}
+
+impl ToPlutusData for () {
+    fn to_plutus_data(&self) -> PlutusData {
+        constr_to_plutus_data(0, vec![])
+    }
+}
+
+impl FromPlutusData for () {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        constr_fields_from_plutus_data(data, 0, 0).map(|_| ())
+    }
+}
+
+impl ToPlutusData for String {
+    fn to_plutus_data(&self) -> PlutusData {
+        bytes_to_plutus_data(self.as_bytes())
+    }
+}
+
+impl FromPlutusData for String {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        String::from_utf8(bytes_from_plutus_data(data)?)
+            .map_err(|_| PlutusDataErrorKind::InvalidUtf8.into())
+    }
+}
+
+impl<const N: usize> ToPlutusData for [u8; N] {
+    fn to_plutus_data(&self) -> PlutusData {
+        bytes_to_plutus_data(self)
+    }
+}
+
+impl<const N: usize> FromPlutusData for [u8; N] {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        let bytes = bytes_from_plutus_data(data)?;
+        <[u8; N]>::try_from(bytes.as_slice()).map_err(|_| {
+            PlutusDataErrorKind::WrongByteLength {
+                expected: N,
+                found: bytes.len(),
+            }
+            .into()
+        })
+    }
+}
+
+macro_rules! impl_plutus_data_hash {
+    ($($hash:ty),*) => {
+        $(
+            impl ToPlutusData for $hash {
+                fn to_plutus_data(&self) -> PlutusData {
+                    bytes_to_plutus_data(self.to_raw_bytes())
+                }
+            }
+
+            impl FromPlutusData for $hash {
+                fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+                    let bytes = bytes_from_plutus_data(data)?;
+                    <$hash>::from_raw_bytes(&bytes).map_err(|_| {
+                        PlutusDataErrorKind::WrongByteLength {
+                            expected: <$hash>::BYTE_COUNT,
+                            found: bytes.len(),
+                        }
+                        .into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_plutus_data_hash!(Ed25519KeyHash, ScriptHash, DatumHash, TransactionHash);
+
+impl<T: ToPlutusData> ToPlutusData for Box<T> {
+    fn to_plutus_data(&self) -> PlutusData {
+        self.as_ref().to_plutus_data()
+    }
+}
+
+impl<T: FromPlutusData> FromPlutusData for Box<T> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        T::from_plutus_data(data).map(Box::new)
+    }
+}
+
+impl<T: ToPlutusData> ToPlutusData for Option<T> {
+    fn to_plutus_data(&self) -> PlutusData {
+        match self {
+            Some(x) => constr_to_plutus_data(0, vec![x.to_plutus_data()]),
+            None => constr_to_plutus_data(1, vec![]),
+        }
+    }
+}
+
+impl<T: FromPlutusData> FromPlutusData for Option<T> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        match constr_from_plutus_data(data)? {
+            (0, fields) => {
+                check_field_count(fields, 1).map_err(|e| e.in_variant("Some"))?;
+                T::from_plutus_data(&fields[0])
+                    .map(Some)
+                    .map_err(|e| e.at_index(0).in_variant("Some"))
+            }
+            (1, fields) => check_field_count(fields, 0)
+                .map(|()| None)
+                .map_err(|e| e.in_variant("None")),
+            (found, _) => Err(PlutusDataErrorKind::UnknownConstructor {
+                expected: vec![0, 1],
+                found,
+            }
+            .into()),
+        }
+    }
+}
+
+impl<T: ToPlutusData> ToPlutusData for Vec<T> {
+    fn to_plutus_data(&self) -> PlutusData {
+        PlutusData::new_list(self.iter().map(ToPlutusData::to_plutus_data).collect())
+    }
+}
+
+impl<T: FromPlutusData> FromPlutusData for Vec<T> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        match data {
+            PlutusData::List { list, .. } => list
+                .iter()
+                .enumerate()
+                .map(|(i, element)| T::from_plutus_data(element).map_err(|e| e.at_index(i)))
+                .collect(),
+            _ => Err(unexpected("list", data)),
+        }
+    }
+}
+
+impl<K: ToPlutusData, V: ToPlutusData> ToPlutusData for BTreeMap<K, V> {
+    fn to_plutus_data(&self) -> PlutusData {
+        let mut map = PlutusMap::new();
+        for (key, value) in self.iter() {
+            map.set(key.to_plutus_data(), value.to_plutus_data());
+        }
+        PlutusData::new_map(map)
+    }
+}
+
+impl<K: FromPlutusData + Ord, V: FromPlutusData> FromPlutusData for BTreeMap<K, V> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, PlutusDataError> {
+        match data {
+            PlutusData::Map(map) => map
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(i, (key, value))| {
+                    let key = K::from_plutus_data(key).map_err(|e| e.at_field("k").at_index(i))?;
+                    let value =
+                        V::from_plutus_data(value).map_err(|e| e.at_field("v").at_index(i))?;
+                    Ok((key, value))
+                })
+                .collect(),
+            _ => Err(unexpected("map", data)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives_round_trip() {
+        fn round_trip<T: ToPlutusData + FromPlutusData + PartialEq + std::fmt::Debug>(x: T) {
+            assert_eq!(T::from_plutus_data(&x.to_plutus_data()).unwrap(), x);
+        }
+        round_trip(-5i64);
+        round_trip(u128::MAX);
+        round_trip(i128::MIN);
+        round_trip(true);
+        round_trip(Some(vec![1u8, 2, 3]));
+        round_trip(None::<u64>);
+        round_trip([7u8; 4]);
+        round_trip(String::from("aiken"));
+        round_trip(ScriptHash::from([1; ScriptHash::BYTE_COUNT]));
+        let mut map = BTreeMap::new();
+        map.insert(1u32, vec![Some(true)]);
+        round_trip(map);
+    }
+
+    #[test]
+    fn error_paths() {
+        let data = vec![Some(1u64), Some(300)].to_plutus_data();
+        let err = Vec::<Option<u8>>::from_plutus_data(&data).unwrap_err();
+        assert_eq!(err.path_string(), "$[1]::Some[0]");
+        assert_eq!(
+            err.kind,
+            PlutusDataErrorKind::IntegerOutOfRange {
+                value: "300".to_owned(),
+                target: "u8"
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "$[1]::Some[0]: integer 300 out of range for u8"
+        );
+        let err = <[u8; 2]>::from_plutus_data(&5u64.to_plutus_data()).unwrap_err();
+        assert_eq!(err.to_string(), "$: expected bytes, found integer");
+    }
+}
@@ -2,7 +2,9 @@
 // https://github.com/dcSpark/cddl-codegen
 
 pub mod cbor_encodings;
+pub mod conversion;
 pub mod serialization;
+pub mod uplc;
 pub mod utils;
 
 use self::cbor_encodings::PlutusV3ScriptEncoding;
@@ -7,6 +7,8 @@ use cbor_event::de::Deserializer;
 use cbor_event::se::Serializer;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::CborValue;
 use std::io::{BufRead, Seek, SeekFrom, Write};
 
 // PlutusData::Bytes uses this specific encoding:
@@ -17,6 +19,26 @@ impl Serialize for CostModels {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl CostModels {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -33,7 +55,7 @@ impl Serialize for CostModels {
                     } + match &self.plutus_v3 {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -56,7 +78,9 @@ impl Serialize for CostModels {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.plutus_v1 {
@@ -151,6 +175,8 @@ impl Serialize for CostModels {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -166,6 +192,7 @@ impl Deserialize for CostModels {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut plutus_v1_encoding = LenEncoding::default();
             let mut plutus_v1_key_encoding = None;
             let mut plutus_v1 = None;
@@ -182,104 +209,102 @@ impl Deserialize for CostModels {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if plutus_v1.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_plutus_v1, tmp_plutus_v1_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_arr.push(Int::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_arr, plutus_v1_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1"))?;
-                            plutus_v1 = Some(tmp_plutus_v1);
-                            plutus_v1_encoding = tmp_plutus_v1_encoding;
-                            plutus_v1_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if plutus_v2.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_plutus_v2, tmp_plutus_v2_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v2_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_arr.push(Int::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_arr, plutus_v2_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v2"))?;
-                            plutus_v2 = Some(tmp_plutus_v2);
-                            plutus_v2_encoding = tmp_plutus_v2_encoding;
-                            plutus_v2_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if plutus_v3.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_plutus_v3, tmp_plutus_v3_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v3_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v3_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v3_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v3_arr.push(Int::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v3_arr, plutus_v3_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v3"))?;
-                            plutus_v3 = Some(tmp_plutus_v3);
-                            plutus_v3_encoding = tmp_plutus_v3_encoding;
-                            plutus_v3_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1, tmp_plutus_v1_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Int::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1"))?;
+                                plutus_v1 = Some(tmp_plutus_v1);
+                                plutus_v1_encoding = tmp_plutus_v1_encoding;
+                                plutus_v1_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2, tmp_plutus_v2_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Int::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2"))?;
+                                plutus_v2 = Some(tmp_plutus_v2);
+                                plutus_v2_encoding = tmp_plutus_v2_encoding;
+                                plutus_v2_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v3.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v3, tmp_plutus_v3_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Int::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v3"))?;
+                                plutus_v3 = Some(tmp_plutus_v3);
+                                plutus_v3_encoding = tmp_plutus_v3_encoding;
+                                plutus_v3_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -310,6 +335,7 @@ impl Deserialize for CostModels {
                     plutus_v2_encoding,
                     plutus_v3_key_encoding,
                     plutus_v3_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
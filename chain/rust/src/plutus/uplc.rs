@@ -0,0 +1,1167 @@
+//! Untyped Plutus Core programs in the `flat` format used on-chain.
+//!
+//! The inner bytes of `PlutusV1Script`/`PlutusV2Script`/`PlutusV3Script` are a CBOR bytestring
+//! wrapping the flat-encoded program. This allows inspecting such scripts and applying
+//! arguments to them (e.g. validator parameters) without an external toolchain.
+//!
+//! Decoding keeps enough encoding details (CBOR wrapping, non-canonical flat) for an unmodified
+//! program to re-encode to the exact same bytes, so script hashes are preserved.
+
+use super::PlutusData;
+use crate::utils::BigInteger;
+use cml_core::serialization::{Deserialize, Serialize, StringEncoding};
+use cml_core::DeserializeError;
+use num::{BigInt, BigUint, One, Signed, ToPrimitive, Zero};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum UplcError {
+    #[error("Unexpected end of flat input")]
+    UnexpectedEnd,
+    #[error("Invalid filler at bit {0}")]
+    InvalidFiller(usize),
+    #[error("Invalid term tag {0}")]
+    InvalidTermTag(u8),
+    #[error("Invalid constant type {0:?}")]
+    InvalidConstantType(Vec<u8>),
+    #[error("Natural number too big at bit {0}")]
+    NaturalTooBig(usize),
+    #[error("Invalid UTF-8 in string constant: {0}")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+    #[error("Invalid data constant: {0}")]
+    InvalidData(DeserializeError),
+    #[error("Invalid CBOR script wrapping: {0}")]
+    InvalidWrapping(#[from] cbor_event::Error),
+    #[error("Trailing bytes after program: {0}")]
+    TrailingBytes(usize),
+}
+
+/// The type of a constant
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Integer,
+    ByteString,
+    String,
+    Unit,
+    Bool,
+    List(Box<Type>),
+    Pair(Box<Type>, Box<Type>),
+    Data,
+}
+
+impl Type {
+    fn flat_tags(&self, tags: &mut Vec<u8>) {
+        match self {
+            Self::Integer => tags.push(0),
+            Self::ByteString => tags.push(1),
+            Self::String => tags.push(2),
+            Self::Unit => tags.push(3),
+            Self::Bool => tags.push(4),
+            Self::List(elem) => {
+                tags.extend_from_slice(&[7, 5]);
+                elem.flat_tags(tags);
+            }
+            Self::Pair(fst, snd) => {
+                tags.extend_from_slice(&[7, 7, 6]);
+                fst.flat_tags(tags);
+                snd.flat_tags(tags);
+            }
+            Self::Data => tags.push(8),
+        }
+    }
+
+    /// Parses a type from the front of `tags`, returning the unconsumed tags
+    fn from_flat_tags(tags: &[u8]) -> Option<(Self, &[u8])> {
+        match tags {
+            [0, rest @ ..] => Some((Self::Integer, rest)),
+            [1, rest @ ..] => Some((Self::ByteString, rest)),
+            [2, rest @ ..] => Some((Self::String, rest)),
+            [3, rest @ ..] => Some((Self::Unit, rest)),
+            [4, rest @ ..] => Some((Self::Bool, rest)),
+            [7, 5, rest @ ..] => {
+                let (elem, rest) = Self::from_flat_tags(rest)?;
+                Some((Self::List(Box::new(elem)), rest))
+            }
+            [7, 7, 6, rest @ ..] => {
+                let (fst, rest) = Self::from_flat_tags(rest)?;
+                let (snd, rest) = Self::from_flat_tags(rest)?;
+                Some((Self::Pair(Box::new(fst), Box::new(snd)), rest))
+            }
+            [8, rest @ ..] => Some((Self::Data, rest)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constant {
+    Integer(BigInteger),
+    ByteString(Vec<u8>),
+    String(String),
+    Unit,
+    Bool(bool),
+    /// Lists carry their element type as they could be empty
+    List(Type, Vec<Constant>),
+    Pair(Box<Constant>, Box<Constant>),
+    Data(PlutusData),
+}
+
+impl Constant {
+    pub fn type_of(&self) -> Type {
+        match self {
+            Self::Integer(_) => Type::Integer,
+            Self::ByteString(_) => Type::ByteString,
+            Self::String(_) => Type::String,
+            Self::Unit => Type::Unit,
+            Self::Bool(_) => Type::Bool,
+            Self::List(elem, _) => Type::List(Box::new(elem.clone())),
+            Self::Pair(fst, snd) => Type::Pair(Box::new(fst.type_of()), Box::new(snd.type_of())),
+            Self::Data(_) => Type::Data,
+        }
+    }
+}
+
+/// A builtin function, identified by its flat tag.
+/// Unknown tags are kept as-is so newer scripts still round-trip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Builtin(pub u8);
+
+const BUILTIN_NAMES: [&str; 87] = [
+    "addInteger",
+    "subtractInteger",
+    "multiplyInteger",
+    "divideInteger",
+    "quotientInteger",
+    "remainderInteger",
+    "modInteger",
+    "equalsInteger",
+    "lessThanInteger",
+    "lessThanEqualsInteger",
+    "appendByteString",
+    "consByteString",
+    "sliceByteString",
+    "lengthOfByteString",
+    "indexByteString",
+    "equalsByteString",
+    "lessThanByteString",
+    "lessThanEqualsByteString",
+    "sha2_256",
+    "sha3_256",
+    "blake2b_256",
+    "verifyEd25519Signature",
+    "appendString",
+    "equalsString",
+    "encodeUtf8",
+    "decodeUtf8",
+    "ifThenElse",
+    "chooseUnit",
+    "trace",
+    "fstPair",
+    "sndPair",
+    "chooseList",
+    "mkCons",
+    "headList",
+    "tailList",
+    "nullList",
+    "chooseData",
+    "constrData",
+    "mapData",
+    "listData",
+    "iData",
+    "bData",
+    "unConstrData",
+    "unMapData",
+    "unListData",
+    "unIData",
+    "unBData",
+    "equalsData",
+    "mkPairData",
+    "mkNilData",
+    "mkNilPairData",
+    "serialiseData",
+    "verifyEcdsaSecp256k1Signature",
+    "verifySchnorrSecp256k1Signature",
+    "bls12_381_G1_add",
+    "bls12_381_G1_neg",
+    "bls12_381_G1_scalarMul",
+    "bls12_381_G1_equal",
+    "bls12_381_G1_hashToGroup",
+    "bls12_381_G1_compress",
+    "bls12_381_G1_uncompress",
+    "bls12_381_G2_add",
+    "bls12_381_G2_neg",
+    "bls12_381_G2_scalarMul",
+    "bls12_381_G2_equal",
+    "bls12_381_G2_hashToGroup",
+    "bls12_381_G2_compress",
+    "bls12_381_G2_uncompress",
+    "bls12_381_millerLoop",
+    "bls12_381_mulMlResult",
+    "bls12_381_finalVerify",
+    "keccak_256",
+    "blake2b_224",
+    "integerToByteString",
+    "byteStringToInteger",
+    "andByteString",
+    "orByteString",
+    "xorByteString",
+    "complementByteString",
+    "readBit",
+    "writeBits",
+    "replicateByte",
+    "shiftByteString",
+    "rotateByteString",
+    "countSetBits",
+    "findFirstSetBit",
+    "ripemd_160",
+];
+
+impl Builtin {
+    /// Name as used in the textual UPLC syntax, if known
+    pub fn name(&self) -> Option<&'static str> {
+        BUILTIN_NAMES.get(self.0 as usize).copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        BUILTIN_NAMES
+            .iter()
+            .position(|builtin| *builtin == name)
+            .map(|tag| Self(tag as u8))
+    }
+}
+
+/// UPLC term with de Bruijn indexed variables (1 refers to the innermost binder)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Term {
+    Var(u64),
+    Delay(Box<Term>),
+    Lambda(Box<Term>),
+    Apply(Box<Term>, Box<Term>),
+    Constant(Constant),
+    Force(Box<Term>),
+    Error,
+    Builtin(Builtin),
+    Constr {
+        tag: u64,
+        fields: Vec<Term>,
+    },
+    Case {
+        scrutinee: Box<Term>,
+        branches: Vec<Term>,
+    },
+}
+
+impl Term {
+    pub fn apply(self, argument: Term) -> Self {
+        Self::Apply(Box::new(self), Box::new(argument))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProgramEncoding {
+    /// CBOR bytestring headers around the flat bytes, outermost first
+    pub wrapping: Vec<StringEncoding>,
+    /// Original flat bytes if they weren't canonically encoded,
+    /// along with what they decoded to so they're only reused while unmodified
+    non_canonical_flat: Option<(u64, u64, u64, Term, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug, derivative::Derivative)]
+#[derivative(PartialEq, Eq)]
+pub struct Program {
+    /// (major, minor, patch) version of the Plutus Core language
+    pub version: (u64, u64, u64),
+    pub term: Term,
+    #[derivative(PartialEq = "ignore")]
+    pub encodings: Option<ProgramEncoding>,
+}
+
+impl Program {
+    pub fn new(version: (u64, u64, u64), term: Term) -> Self {
+        Self {
+            version,
+            term,
+            encodings: None,
+        }
+    }
+
+    pub fn from_flat(bytes: &[u8]) -> Result<Self, UplcError> {
+        let mut decoder = FlatDecoder::new(bytes);
+        let version = (decoder.word()?, decoder.word()?, decoder.word()?);
+        let term = decoder.term()?;
+        decoder.filler()?;
+        if decoder.pos / 8 != bytes.len() {
+            return Err(UplcError::TrailingBytes(bytes.len() - decoder.pos / 8));
+        }
+        let encodings = ProgramEncoding {
+            wrapping: Vec::new(),
+            non_canonical_flat: (!decoder.canonical).then(|| {
+                (
+                    version.0,
+                    version.1,
+                    version.2,
+                    term.clone(),
+                    bytes.to_vec(),
+                )
+            }),
+        };
+        Ok(Self {
+            version,
+            term,
+            encodings: Some(encodings),
+        })
+    }
+
+    pub fn to_flat(&self) -> Vec<u8> {
+        if let Some((major, minor, patch, term, bytes)) = self
+            .encodings
+            .as_ref()
+            .and_then(|encs| encs.non_canonical_flat.as_ref())
+        {
+            if (*major, *minor, *patch) == self.version && *term == self.term {
+                return bytes.clone();
+            }
+        }
+        let mut encoder = FlatEncoder::default();
+        encoder.word(self.version.0);
+        encoder.word(self.version.1);
+        encoder.word(self.version.2);
+        encoder.term(&self.term);
+        encoder.filler();
+        encoder.bytes
+    }
+
+    /// Decodes the inner bytes of a Plutus script i.e. CBOR bytestring wrapping the flat program.
+    /// Some tools wrap the program twice, which is also accepted.
+    pub fn from_script_bytes(bytes: &[u8]) -> Result<Self, UplcError> {
+        let (mut flat, encoding) = unwrap_cbor_bytes(bytes)?;
+        let mut wrapping = vec![encoding];
+        // a flat program starts with its major version so can't be mistaken for a CBOR bytestring
+        if flat.first().is_some_and(|b| b >> 5 == 2) {
+            let (inner, encoding) = unwrap_cbor_bytes(&flat)?;
+            flat = inner;
+            wrapping.push(encoding);
+        }
+        let mut program = Self::from_flat(&flat)?;
+        if let Some(encs) = program.encodings.as_mut() {
+            encs.wrapping = wrapping;
+        }
+        Ok(program)
+    }
+
+    /// Encodes as the inner bytes of a Plutus script.
+    /// Uses the CBOR wrapping the program was decoded with, or a single bytestring by default.
+    pub fn to_script_bytes(&self) -> Vec<u8> {
+        let wrapping = match self.encodings.as_ref() {
+            Some(encs) if !encs.wrapping.is_empty() => encs.wrapping.clone(),
+            _ => vec![StringEncoding::Canonical],
+        };
+        wrapping
+            .iter()
+            .rev()
+            .fold(self.to_flat(), |bytes, encoding| {
+                // chunk lengths of an indefinite encoding no longer apply if the program changed
+                let encoding = match encoding {
+                    StringEncoding::Indefinite(chunks)
+                        if chunks.iter().map(|(len, _)| len).sum::<u64>() != bytes.len() as u64 =>
+                    {
+                        StringEncoding::Canonical
+                    }
+                    _ => encoding.clone(),
+                };
+                let mut serializer = cbor_event::se::Serializer::new_vec();
+                serializer
+                    .write_bytes_sz(&bytes, encoding.to_str_len_sz(bytes.len() as u64, false))
+                    .unwrap();
+                serializer.finalize()
+            })
+    }
+
+    /// Textual UPLC syntax e.g. `(program 1.0.0 (lam i0 [(builtin iData) i0]))`
+    pub fn to_pretty_string(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn stats(&self) -> ProgramStats {
+        let mut stats = ProgramStats {
+            version: self.version,
+            flat_size: self.to_flat().len(),
+            script_size: self.to_script_bytes().len(),
+            ..ProgramStats::default()
+        };
+        stats.count(&self.term, 1);
+        stats
+    }
+
+    /// Applies `arguments` in order as `Data` constants e.g. for parameterized validators
+    pub fn apply_data(self, arguments: &[PlutusData]) -> Self {
+        let term = arguments.iter().fold(self.term, |term, argument| {
+            term.apply(Term::Constant(Constant::Data(argument.clone())))
+        });
+        Self::new(self.version, term)
+    }
+}
+
+fn unwrap_cbor_bytes(bytes: &[u8]) -> Result<(Vec<u8>, StringEncoding), UplcError> {
+    let mut raw = cbor_event::de::Deserializer::from(std::io::Cursor::new(bytes));
+    let (inner, encoding) = raw.bytes_sz()?;
+    let read = raw.as_mut_ref().position() as usize;
+    if read != bytes.len() {
+        return Err(UplcError::TrailingBytes(bytes.len() - read));
+    }
+    Ok((inner, encoding.into()))
+}
+
+/// Size and shape of a program, e.g. for reviewing scripts before signing
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramStats {
+    pub version: (u64, u64, u64),
+    /// size of the flat-encoded program in bytes
+    pub flat_size: usize,
+    /// size of the script bytes i.e. including the CBOR wrapping
+    pub script_size: usize,
+    pub terms: usize,
+    pub max_depth: usize,
+    pub lambdas: usize,
+    pub applications: usize,
+    pub constants: usize,
+    /// number of references to each builtin
+    pub builtins: BTreeMap<Builtin, usize>,
+}
+
+impl ProgramStats {
+    fn count(&mut self, term: &Term, depth: usize) {
+        self.terms += 1;
+        self.max_depth = self.max_depth.max(depth);
+        match term {
+            Term::Var(_) | Term::Error => (),
+            Term::Delay(body) | Term::Force(body) => self.count(body, depth + 1),
+            Term::Lambda(body) => {
+                self.lambdas += 1;
+                self.count(body, depth + 1);
+            }
+            Term::Apply(function, argument) => {
+                self.applications += 1;
+                self.count(function, depth + 1);
+                self.count(argument, depth + 1);
+            }
+            Term::Constant(_) => self.constants += 1,
+            Term::Builtin(builtin) => *self.builtins.entry(*builtin).or_default() += 1,
+            Term::Constr { fields, .. } => {
+                for field in fields {
+                    self.count(field, depth + 1);
+                }
+            }
+            Term::Case {
+                scrutinee,
+                branches,
+            } => {
+                self.count(scrutinee, depth + 1);
+                for branch in branches {
+                    self.count(branch, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => write!(f, "integer"),
+            Self::ByteString => write!(f, "bytestring"),
+            Self::String => write!(f, "string"),
+            Self::Unit => write!(f, "unit"),
+            Self::Bool => write!(f, "bool"),
+            Self::List(elem) => write!(f, "(list {elem})"),
+            Self::Pair(fst, snd) => write!(f, "(pair {fst} {snd})"),
+            Self::Data => write!(f, "data"),
+        }
+    }
+}
+
+/// The constant's value as in `(con <type> <value>)`
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::ByteString(bytes) => write!(f, "#{}", hex::encode(bytes)),
+            Self::String(string) => write!(f, "{string:?}"),
+            Self::Unit => write!(f, "()"),
+            Self::Bool(true) => write!(f, "True"),
+            Self::Bool(false) => write!(f, "False"),
+            Self::List(_, elements) => {
+                write!(f, "[")?;
+                for (i, elem) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{elem}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Pair(fst, snd) => write!(f, "({fst}, {snd})"),
+            Self::Data(data) => write!(f, "({})", data_to_string(data)),
+        }
+    }
+}
+
+fn data_to_string(data: &PlutusData) -> String {
+    let join = |items: Vec<String>| items.join(", ");
+    match data {
+        PlutusData::ConstrPlutusData(constr) => format!(
+            "Constr {} [{}]",
+            constr.alternative,
+            join(constr.fields.iter().map(data_to_string).collect())
+        ),
+        PlutusData::Map(map) => format!(
+            "Map [{}]",
+            join(
+                map.entries
+                    .iter()
+                    .map(|(k, v)| format!("({}, {})", data_to_string(k), data_to_string(v)))
+                    .collect()
+            )
+        ),
+        PlutusData::List { list, .. } => {
+            format!("List [{}]", join(list.iter().map(data_to_string).collect()))
+        }
+        PlutusData::Integer(value) => format!("I {value}"),
+        PlutusData::Bytes { bytes, .. } => format!("B #{}", hex::encode(bytes)),
+    }
+}
+
+/// Pretty printed with lines broken to fit within this width where possible
+const PRETTY_WIDTH: usize = 100;
+
+/// Layout of a term: atoms or bracketed lists of documents
+enum Doc {
+    Atom(String),
+    List {
+        open: char,
+        close: char,
+        items: Vec<Doc>,
+        /// length when printed on a single line
+        width: usize,
+    },
+}
+
+impl Doc {
+    fn list(open: char, close: char, items: Vec<Doc>) -> Self {
+        let width = 2 + items.len().saturating_sub(1) + items.iter().map(Doc::width).sum::<usize>();
+        Self::List {
+            open,
+            close,
+            items,
+            width,
+        }
+    }
+
+    fn atom(atom: impl Into<String>) -> Self {
+        Self::Atom(atom.into())
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Self::Atom(atom) => atom.len(),
+            Self::List { width, .. } => *width,
+        }
+    }
+
+    fn from_term(term: &Term, binders: u64) -> Self {
+        match term {
+            Term::Var(index) if *index >= 1 && *index <= binders => {
+                Self::atom(format!("i{}", binders - index))
+            }
+            // not bound by any enclosing lambda
+            Term::Var(index) => Self::atom(format!("free{index}")),
+            Term::Delay(body) => Self::list(
+                '(',
+                ')',
+                vec![Self::atom("delay"), Self::from_term(body, binders)],
+            ),
+            Term::Lambda(body) => Self::list(
+                '(',
+                ')',
+                vec![
+                    Self::atom("lam"),
+                    Self::atom(format!("i{binders}")),
+                    Self::from_term(body, binders + 1),
+                ],
+            ),
+            Term::Apply(..) => {
+                // [f x y] rather than [[f x] y]
+                let mut arguments = Vec::new();
+                let mut function = term;
+                while let Term::Apply(inner, argument) = function {
+                    arguments.push(Self::from_term(argument, binders));
+                    function = inner;
+                }
+                arguments.push(Self::from_term(function, binders));
+                arguments.reverse();
+                Self::list('[', ']', arguments)
+            }
+            Term::Constant(constant) => {
+                Self::atom(format!("(con {} {constant})", constant.type_of()))
+            }
+            Term::Force(body) => Self::list(
+                '(',
+                ')',
+                vec![Self::atom("force"), Self::from_term(body, binders)],
+            ),
+            Term::Error => Self::atom("(error)"),
+            Term::Builtin(builtin) => match builtin.name() {
+                Some(name) => Self::atom(format!("(builtin {name})")),
+                None => Self::atom(format!("(builtin unknown{})", builtin.0)),
+            },
+            Term::Constr { tag, fields } => {
+                let mut items = vec![Self::atom("constr"), Self::atom(tag.to_string())];
+                items.extend(fields.iter().map(|field| Self::from_term(field, binders)));
+                Self::list('(', ')', items)
+            }
+            Term::Case {
+                scrutinee,
+                branches,
+            } => {
+                let mut items = vec![Self::atom("case"), Self::from_term(scrutinee, binders)];
+                items.extend(
+                    branches
+                        .iter()
+                        .map(|branch| Self::from_term(branch, binders)),
+                );
+                Self::list('(', ')', items)
+            }
+        }
+    }
+
+    /// Writes on one line if it fits, otherwise the leading atoms go on the first line
+    /// and every other item on its own line, indented.
+    fn render(&self, out: &mut String, indent: usize) {
+        match self {
+            Self::Atom(atom) => out.push_str(atom),
+            Self::List {
+                open,
+                close,
+                items,
+                width,
+            } => {
+                out.push(*open);
+                let one_line = indent + width <= PRETTY_WIDTH;
+                let head = if one_line {
+                    items.len()
+                } else {
+                    items
+                        .iter()
+                        .take_while(|item| matches!(item, Self::Atom(_)))
+                        .count()
+                        .max(1)
+                };
+                for (i, item) in items.iter().enumerate() {
+                    if i >= head {
+                        out.push('\n');
+                        out.extend(std::iter::repeat_n(' ', indent + 2));
+                        item.render(out, indent + 2);
+                    } else {
+                        if i > 0 {
+                            out.push(' ');
+                        }
+                        item.render(out, indent + 1);
+                    }
+                }
+                out.push(*close);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor, patch) = self.version;
+        let doc = Doc::list(
+            '(',
+            ')',
+            vec![
+                Doc::atom("program"),
+                Doc::atom(format!("{major}.{minor}.{patch}")),
+                Doc::from_term(&self.term, 0),
+            ],
+        );
+        let mut out = String::new();
+        doc.render(&mut out, 0);
+        f.write_str(&out)
+    }
+}
+
+/// Applies Plutus data arguments to the inner bytes of a Plutus script,
+/// returning the inner bytes of the applied script.
+pub fn apply_params_to_script(
+    script_bytes: &[u8],
+    params: &[PlutusData],
+) -> Result<Vec<u8>, UplcError> {
+    Program::from_script_bytes(script_bytes)
+        .map(|program| program.apply_data(params).to_script_bytes())
+}
+
+struct FlatDecoder<'a> {
+    bytes: &'a [u8],
+    /// position in bits
+    pos: usize,
+    /// false once anything was encoded differently than `FlatEncoder` would
+    canonical: bool,
+}
+
+impl<'a> FlatDecoder<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            canonical: true,
+        }
+    }
+
+    fn bit(&mut self) -> Result<bool, UplcError> {
+        let byte = self
+            .bytes
+            .get(self.pos / 8)
+            .ok_or(UplcError::UnexpectedEnd)?;
+        let bit = (byte >> (7 - self.pos % 8)) & 1 == 1;
+        self.pos += 1;
+        Ok(bit)
+    }
+
+    /// Reads `n` <= 8 bits most significant first
+    fn bits(&mut self, n: usize) -> Result<u8, UplcError> {
+        (0..n).try_fold(0u8, |acc, _| Ok((acc << 1) | self.bit()? as u8))
+    }
+
+    /// Zero or more 0 bits followed by a 1 that ends on a byte boundary
+    fn filler(&mut self) -> Result<(), UplcError> {
+        let start = self.pos;
+        while !self.bit()? {}
+        if !self.pos.is_multiple_of(8) {
+            return Err(UplcError::InvalidFiller(start));
+        }
+        if self.pos - start > 8 {
+            self.canonical = false;
+        }
+        Ok(())
+    }
+
+    fn natural(&mut self) -> Result<BigUint, UplcError> {
+        let mut value = BigUint::zero();
+        let mut shift = 0;
+        loop {
+            let more = self.bit()?;
+            let group = self.bits(7)?;
+            if !more && group == 0 && shift > 0 {
+                // redundant leading zero group
+                self.canonical = false;
+            }
+            value |= BigUint::from(group) << shift;
+            shift += 7;
+            if !more {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn word(&mut self) -> Result<u64, UplcError> {
+        let start = self.pos;
+        self.natural()?
+            .to_u64()
+            .ok_or(UplcError::NaturalTooBig(start))
+    }
+
+    fn integer(&mut self) -> Result<BigInt, UplcError> {
+        // zigzag encoding: 0, -1, 1, -2, 2 ...
+        let natural = BigInt::from(self.natural()?);
+        Ok(if (&natural % 2u8).is_zero() {
+            natural / 2u8
+        } else {
+            -(natural + 1u8) / 2u8
+        })
+    }
+
+    fn bytestring(&mut self) -> Result<Vec<u8>, UplcError> {
+        self.filler()?;
+        let mut bytes = Vec::new();
+        let mut previous_len = 255;
+        loop {
+            let len = self.bits(8)? as usize;
+            if len == 0 {
+                return Ok(bytes);
+            }
+            // only the last chunk may be shorter than 255 bytes
+            if previous_len != 255 {
+                self.canonical = false;
+            }
+            previous_len = len;
+            let start = self.pos / 8;
+            let chunk = self
+                .bytes
+                .get(start..start + len)
+                .ok_or(UplcError::UnexpectedEnd)?;
+            bytes.extend_from_slice(chunk);
+            self.pos += len * 8;
+        }
+    }
+
+    fn list<T>(
+        &mut self,
+        mut element: impl FnMut(&mut Self) -> Result<T, UplcError>,
+    ) -> Result<Vec<T>, UplcError> {
+        let mut elements = Vec::new();
+        while self.bit()? {
+            elements.push(element(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn term(&mut self) -> Result<Term, UplcError> {
+        let term = match self.bits(4)? {
+            0 => Term::Var(self.word()?),
+            1 => Term::Delay(Box::new(self.term()?)),
+            2 => Term::Lambda(Box::new(self.term()?)),
+            3 => {
+                let function = self.term()?;
+                let argument = self.term()?;
+                function.apply(argument)
+            }
+            4 => {
+                let tags = self.list(|decoder| decoder.bits(4))?;
+                let typ = match Type::from_flat_tags(&tags) {
+                    Some((typ, [])) => typ,
+                    _ => return Err(UplcError::InvalidConstantType(tags)),
+                };
+                Term::Constant(self.constant(&typ)?)
+            }
+            5 => Term::Force(Box::new(self.term()?)),
+            6 => Term::Error,
+            7 => Term::Builtin(Builtin(self.bits(7)?)),
+            8 => {
+                let tag = self.word()?;
+                let fields = self.list(Self::term)?;
+                Term::Constr { tag, fields }
+            }
+            9 => {
+                let scrutinee = Box::new(self.term()?);
+                let branches = self.list(Self::term)?;
+                Term::Case {
+                    scrutinee,
+                    branches,
+                }
+            }
+            tag => return Err(UplcError::InvalidTermTag(tag)),
+        };
+        Ok(term)
+    }
+
+    fn constant(&mut self, typ: &Type) -> Result<Constant, UplcError> {
+        let constant = match typ {
+            Type::Integer => Constant::Integer(BigInteger::from(self.integer()?)),
+            Type::ByteString => Constant::ByteString(self.bytestring()?),
+            Type::String => Constant::String(String::from_utf8(self.bytestring()?)?),
+            Type::Unit => Constant::Unit,
+            Type::Bool => Constant::Bool(self.bit()?),
+            Type::List(elem) => Constant::List(
+                (**elem).clone(),
+                self.list(|decoder| decoder.constant(elem))?,
+            ),
+            Type::Pair(fst, snd) => {
+                let fst = self.constant(fst)?;
+                let snd = self.constant(snd)?;
+                Constant::Pair(Box::new(fst), Box::new(snd))
+            }
+            Type::Data => {
+                let bytes = self.bytestring()?;
+                let data = PlutusData::from_cbor_bytes(&bytes).map_err(UplcError::InvalidData)?;
+                if data.to_cbor_bytes() != bytes {
+                    self.canonical = false;
+                }
+                Constant::Data(data)
+            }
+        };
+        Ok(constant)
+    }
+}
+
+#[derive(Default)]
+struct FlatEncoder {
+    bytes: Vec<u8>,
+    /// bits used in the last byte of `bytes` (0 = the last byte is complete)
+    used: usize,
+}
+
+impl FlatEncoder {
+    fn bit(&mut self, bit: bool) {
+        if self.used == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 1 << (7 - self.used);
+        }
+        self.used = (self.used + 1) % 8;
+    }
+
+    /// Writes the lowest `n` <= 8 bits of `value` most significant first
+    fn bits(&mut self, n: usize, value: u8) {
+        for i in (0..n).rev() {
+            self.bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn filler(&mut self) {
+        while self.used != 7 {
+            self.bit(false);
+        }
+        self.bit(true);
+    }
+
+    fn natural(&mut self, value: &BigUint) {
+        let mut value = value.clone();
+        let mask = BigUint::from(0x7fu8);
+        loop {
+            let group = (&value & &mask).to_u8().unwrap();
+            value >>= 7;
+            let more = !value.is_zero();
+            self.bit(more);
+            self.bits(7, group);
+            if !more {
+                break;
+            }
+        }
+    }
+
+    fn word(&mut self, value: u64) {
+        self.natural(&BigUint::from(value));
+    }
+
+    fn integer(&mut self, value: &BigInt) {
+        let zigzag = if value.is_negative() {
+            -(value * 2u8) - BigInt::one()
+        } else {
+            value * 2u8
+        };
+        self.natural(&zigzag.to_biguint().unwrap());
+    }
+
+    fn bytestring(&mut self, bytes: &[u8]) {
+        self.filler();
+        for chunk in bytes.chunks(255) {
+            self.bytes.push(chunk.len() as u8);
+            self.bytes.extend_from_slice(chunk);
+        }
+        self.bytes.push(0);
+    }
+
+    fn list<T>(&mut self, elements: &[T], mut element: impl FnMut(&mut Self, &T)) {
+        for elem in elements {
+            self.bit(true);
+            element(self, elem);
+        }
+        self.bit(false);
+    }
+
+    fn term(&mut self, term: &Term) {
+        match term {
+            Term::Var(index) => {
+                self.bits(4, 0);
+                self.word(*index);
+            }
+            Term::Delay(body) => {
+                self.bits(4, 1);
+                self.term(body);
+            }
+            Term::Lambda(body) => {
+                self.bits(4, 2);
+                self.term(body);
+            }
+            Term::Apply(function, argument) => {
+                self.bits(4, 3);
+                self.term(function);
+                self.term(argument);
+            }
+            Term::Constant(constant) => {
+                self.bits(4, 4);
+                let mut tags = Vec::new();
+                constant.type_of().flat_tags(&mut tags);
+                self.list(&tags, |encoder, tag| encoder.bits(4, *tag));
+                self.constant(constant);
+            }
+            Term::Force(body) => {
+                self.bits(4, 5);
+                self.term(body);
+            }
+            Term::Error => self.bits(4, 6),
+            Term::Builtin(builtin) => {
+                self.bits(4, 7);
+                self.bits(7, builtin.0);
+            }
+            Term::Constr { tag, fields } => {
+                self.bits(4, 8);
+                self.word(*tag);
+                self.list(fields, Self::term);
+            }
+            Term::Case {
+                scrutinee,
+                branches,
+            } => {
+                self.bits(4, 9);
+                self.term(scrutinee);
+                self.list(branches, Self::term);
+            }
+        }
+    }
+
+    fn constant(&mut self, constant: &Constant) {
+        match constant {
+            Constant::Integer(value) => self.integer(value.as_bigint()),
+            Constant::ByteString(bytes) => self.bytestring(bytes),
+            Constant::String(string) => self.bytestring(string.as_bytes()),
+            Constant::Unit => (),
+            Constant::Bool(value) => self.bit(*value),
+            Constant::List(_, elements) => self.list(elements, Self::constant),
+            Constant::Pair(fst, snd) => {
+                self.constant(fst);
+                self.constant(snd);
+            }
+            Constant::Data(data) => self.bytestring(&data.to_cbor_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plutus::PlutusV2Script;
+    use std::str::FromStr;
+
+    #[test]
+    fn always_succeeds_round_trip() {
+        // always succeeds as produced by the plutus compiler, double CBOR-wrapped
+        let script = hex::decode("4e4d01000033222220051200120011").unwrap();
+        let program = Program::from_script_bytes(&script).unwrap();
+        assert_eq!(program.version, (1, 0, 0));
+        assert_eq!(
+            program.to_flat(),
+            hex::decode("01000033222220051200120011").unwrap()
+        );
+        assert_eq!(Program::from_flat(&program.to_flat()).unwrap(), program);
+    }
+
+    #[test]
+    fn byte_exact_round_trip() {
+        // outer header with a 1-byte length for a short bytestring, indefinite inner bytestring
+        // and a version natural with a redundant zero group
+        let flat = hex::decode("818000000033222220051200120011").unwrap();
+        let mut inner = vec![0x5f, 0x43];
+        inner.extend_from_slice(&flat[..3]);
+        inner.push(0x4c);
+        inner.extend_from_slice(&flat[3..]);
+        inner.push(0xff);
+        let mut script = vec![0x58, inner.len() as u8];
+        script.extend_from_slice(&inner);
+        let program = Program::from_script_bytes(&script).unwrap();
+        assert_eq!(program.version, (1, 0, 0));
+        assert_eq!(program.to_flat(), flat);
+        assert_eq!(program.to_script_bytes(), script);
+        // once modified the program is encoded canonically but keeps the double wrapping
+        let applied = program
+            .clone()
+            .apply_data(&[PlutusData::new_integer(BigInteger::from(1))]);
+        let mut modified = program;
+        modified.term = applied.term;
+        let reencoded = Program::from_script_bytes(&modified.to_script_bytes()).unwrap();
+        assert_eq!(reencoded, modified);
+        assert_eq!(reencoded.encodings.unwrap().wrapping.len(), 2);
+    }
+
+    #[test]
+    fn pretty_print_and_stats() {
+        let script = hex::decode("4e4d01000033222220051200120011").unwrap();
+        let program = PlutusV2Script::new(script).to_program().unwrap();
+        assert_eq!(
+            program.to_pretty_string(),
+            "(program 1.0.0 [(lam i0 (lam i1 (lam i2 (lam i3 (lam i4 i0))))) (delay (lam i0 i0)) (lam i0 i0)])"
+        );
+        let stats = program.stats();
+        assert_eq!(stats.flat_size, 13);
+        assert_eq!(stats.script_size, 15);
+        assert_eq!(stats.lambdas, 7);
+        assert_eq!(stats.applications, 2);
+        assert_eq!(stats.max_depth, 8);
+
+        let program = Program::new(
+            (1, 1, 0),
+            Term::Lambda(Box::new(
+                Term::Builtin(Builtin::from_name("iData").unwrap())
+                    .apply(Term::Var(1))
+                    .apply(Term::Constant(Constant::Data(PlutusData::new_list(vec![
+                        PlutusData::new_bytes(vec![0xca, 0xfe]),
+                    ])))),
+            )),
+        );
+        assert_eq!(
+            program.to_pretty_string(),
+            "(program 1.1.0 (lam i0 [(builtin iData) i0 (con data (List [B #cafe]))]))"
+        );
+        assert_eq!(program.stats().builtins.get(&Builtin(40)), Some(&1));
+    }
+
+    #[test]
+    fn apply_params() {
+        let program = Program::new(
+            (1, 1, 0),
+            Term::Lambda(Box::new(Term::Lambda(Box::new(Term::Var(2))))),
+        );
+        let params = vec![
+            PlutusData::new_integer(BigInteger::from(-300)),
+            PlutusData::new_bytes(vec![0xab; 300]),
+            PlutusData::new_list(vec![]),
+        ];
+        let applied = Program::from_script_bytes(
+            &apply_params_to_script(&program.to_script_bytes(), &params).unwrap(),
+        )
+        .unwrap();
+        let expected = params.iter().fold(program.term.clone(), |term, param| {
+            term.apply(Term::Constant(Constant::Data(param.clone())))
+        });
+        assert_eq!(applied.term, expected);
+    }
+
+    #[test]
+    fn constants_round_trip() {
+        let constants = vec![
+            Constant::Integer(BigInteger::from_str("-123456789012345678901234567890").unwrap()),
+            Constant::Integer(BigInteger::from(0)),
+            Constant::String("hello".to_owned()),
+            Constant::Unit,
+            Constant::Bool(true),
+            Constant::List(Type::Integer, vec![]),
+            Constant::List(
+                Type::Pair(Box::new(Type::Data), Box::new(Type::ByteString)),
+                vec![Constant::Pair(
+                    Box::new(Constant::Data(PlutusData::new_integer(BigInteger::from(1)))),
+                    Box::new(Constant::ByteString(vec![1, 2, 3])),
+                )],
+            ),
+        ];
+        let term = constants
+            .into_iter()
+            .fold(Term::Builtin(Builtin(0)), |term, constant| {
+                term.apply(Term::Constant(constant))
+            });
+        let program = Program::new(
+            (1, 1, 0),
+            Term::Case {
+                scrutinee: Box::new(Term::Constr {
+                    tag: 1000,
+                    fields: vec![term, Term::Error],
+                }),
+                branches: vec![Term::Force(Box::new(Term::Delay(Box::new(Term::Var(1)))))],
+            },
+        );
+        assert_eq!(Program::from_flat(&program.to_flat()).unwrap(), program);
+    }
+}
@@ -1,18 +1,22 @@
-use super::{CostModels, Language, Redeemer};
+use super::uplc::{Program, UplcError};
+use super::{CostModels, ExUnitPrices, Language, Redeemer};
 use super::{ExUnits, PlutusData, PlutusV1Script, PlutusV2Script, PlutusV3Script};
 use crate::crypto::hash::{hash_script, ScriptHashNamespace};
 use crate::json::plutus_datums::{
     decode_plutus_datum_to_json_value, encode_json_value_to_plutus_datum,
     CardanoNodePlutusDatumSchema,
 };
+use crate::Rational;
 use cbor_event::de::Deserializer;
 use cbor_event::se::Serializer;
 use cml_core::serialization::*;
-use cml_core::{error::*, Int};
+use cml_core::{error::*, CborValue, Int};
 use cml_crypto::ScriptHash;
 use std::collections::BTreeMap;
 use std::io::{BufRead, Seek, Write};
 
+cml_core::impl_unknown_fields!(CostModels);
+
 impl serde::Serialize for PlutusData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -62,6 +66,21 @@ impl schemars::JsonSchema for PlutusData {
     }
 }
 
+impl From<&PlutusData> for CborValue {
+    fn from(datum: &PlutusData) -> Self {
+        CborValue::from_cbor_type(datum)
+    }
+}
+
+/// Fails if the CBOR is not a valid datum e.g. contains text or a float
+impl std::convert::TryFrom<&CborValue> for PlutusData {
+    type Error = DeserializeError;
+
+    fn try_from(value: &CborValue) -> Result<Self, Self::Error> {
+        value.to_cbor_type()
+    }
+}
+
 #[derive(
     Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema, derivative::Derivative,
 )]
@@ -422,6 +441,15 @@ impl PlutusScript {
             Self::PlutusV3(_) => Language::PlutusV3,
         }
     }
+
+    /// Decodes the UPLC program of this script
+    pub fn to_program(&self) -> Result<Program, UplcError> {
+        match self {
+            Self::PlutusV1(script) => script.to_program(),
+            Self::PlutusV2(script) => script.to_program(),
+            Self::PlutusV3(script) => script.to_program(),
+        }
+    }
 }
 
 impl From<PlutusV1Script> for PlutusScript {
@@ -446,20 +474,43 @@ impl PlutusV1Script {
     pub fn hash(&self) -> ScriptHash {
         hash_script(ScriptHashNamespace::PlutusV1, self.get())
     }
+
+    /// Decodes the UPLC program of this script
+    pub fn to_program(&self) -> Result<Program, UplcError> {
+        Program::from_script_bytes(self.get())
+    }
 }
 
 impl PlutusV2Script {
     pub fn hash(&self) -> ScriptHash {
         hash_script(ScriptHashNamespace::PlutusV2, self.get())
     }
+
+    /// Decodes the UPLC program of this script
+    pub fn to_program(&self) -> Result<Program, UplcError> {
+        Program::from_script_bytes(self.get())
+    }
 }
 
 impl PlutusV3Script {
     pub fn hash(&self) -> ScriptHash {
         hash_script(ScriptHashNamespace::PlutusV3, self.get())
     }
+
+    /// Decodes the UPLC program of this script
+    pub fn to_program(&self) -> Result<Program, UplcError> {
+        Program::from_script_bytes(self.get())
+    }
 }
 
+cml_core::impl_cbor_field_names!(ExUnits [mem, steps]);
+cml_core::impl_cbor_field_names!(ExUnitPrices [
+    mem_price: Rational::cbor_field_names(),
+    step_price: Rational::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(Redeemer [tag, index, data, ex_units: ExUnits::cbor_field_names()]);
+cml_core::impl_cbor_field_names!(CostModels { 0 => plutus_v1, 1 => plutus_v2, 2 => plutus_v3 });
+
 impl ExUnits {
     pub fn checked_add(&self, other: &ExUnits) -> Result<ExUnits, ArithmeticError> {
         let mem = self
@@ -583,6 +634,8 @@ impl Serialize for PlutusMap {
 impl Deserialize for PlutusMap {
     fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
         (|| -> Result<_, DeserializeError> {
+            // duplicate keys are valid on chain
+            cml_core::canonical::allow_duplicate_keys();
             let mut entries = Vec::new();
             let map_len = raw.map_sz()?;
             let encoding = map_len.into();
@@ -606,8 +659,22 @@ impl Deserialize for PlutusMap {
 
 #[cfg(test)]
 mod tests {
-    use crate::plutus::CostModels;
-    use cml_core::Int;
+    use crate::plutus::{CostModels, PlutusData};
+    use cml_core::{serialization::*, CborValue, Int};
+
+    #[test]
+    fn plutus_data_cbor_value() {
+        use std::convert::TryFrom;
+        // 121([h'01', -1]) with an indefinite list
+        let bytes = hex::decode("d8799f410120ff").unwrap();
+        let datum = PlutusData::from_cbor_bytes(&bytes).unwrap();
+        let value = CborValue::from(&datum);
+        assert_eq!(value.tag(), Some(121));
+        assert_eq!(value.to_cbor_bytes(), bytes);
+        let back = PlutusData::try_from(&value).unwrap();
+        assert_eq!(back.to_cbor_bytes(), bytes);
+        assert!(PlutusData::try_from(&CborValue::from("text")).is_err());
+    }
 
     #[test]
     pub fn test_cost_model() {
@@ -6,6 +6,7 @@ pub use cml_core::{
         fit_sz, CBORReadLen, Deserialize, DeserializeEmbeddedGroup, Serialize,
         SerializeEmbeddedGroup,
     },
+    CborValue,
 };
 
 // This file was code-generated using an experimental CDDL to rust tool:
@@ -181,6 +182,26 @@ impl Serialize for ProtocolParamUpdate {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ProtocolParamUpdate {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -275,7 +296,7 @@ impl Serialize for ProtocolParamUpdate {
                     } + match &self.d_rep_inactivity_period {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -381,7 +402,9 @@ impl Serialize for ProtocolParamUpdate {
                     22, 23, 24, 25, 26, 27, 28,
                 ]
             });
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.minfee_a {
@@ -1050,6 +1073,8 @@ impl Serialize for ProtocolParamUpdate {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -1065,6 +1090,7 @@ impl Deserialize for ProtocolParamUpdate {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut minfee_a_encoding = None;
             let mut minfee_a_key_encoding = None;
             let mut minfee_a = None;
@@ -1144,388 +1170,826 @@ impl Deserialize for ProtocolParamUpdate {
             let mut d_rep_inactivity_period_key_encoding = None;
             let mut d_rep_inactivity_period = None;
             let mut read = 0;
-            while match len { cbor_event::LenSz::Len(n, _) => read < n, cbor_event::LenSz::Indefinite => true, } {
+            while match len {
+                cbor_event::LenSz::Len(n, _) => read < n,
+                cbor_event::LenSz::Indefinite => true,
+            } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) =>  {
-                            if minfee_a.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_minfee_a, tmp_minfee_a_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("minfee_a"))?;
-                            minfee_a = Some(tmp_minfee_a);
-                            minfee_a_encoding = tmp_minfee_a_encoding;
-                            minfee_a_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        },
-                        (1, key_enc) =>  {
-                            if minfee_b.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_minfee_b, tmp_minfee_b_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("minfee_b"))?;
-                            minfee_b = Some(tmp_minfee_b);
-                            minfee_b_encoding = tmp_minfee_b_encoding;
-                            minfee_b_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        },
-                        (2, key_enc) =>  {
-                            if max_block_body_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("max_block_body_size"))?;
-                            max_block_body_size = Some(tmp_max_block_body_size);
-                            max_block_body_size_encoding = tmp_max_block_body_size_encoding;
-                            max_block_body_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        },
-                        (3, key_enc) =>  {
-                            if max_transaction_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("max_transaction_size"))?;
-                            max_transaction_size = Some(tmp_max_transaction_size);
-                            max_transaction_size_encoding = tmp_max_transaction_size_encoding;
-                            max_transaction_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        },
-                        (4, key_enc) =>  {
-                            if max_block_header_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_max_block_header_size, tmp_max_block_header_size_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("max_block_header_size"))?;
-                            max_block_header_size = Some(tmp_max_block_header_size);
-                            max_block_header_size_encoding = tmp_max_block_header_size_encoding;
-                            max_block_header_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        },
-                        (5, key_enc) =>  {
-                            if key_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_key_deposit, tmp_key_deposit_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("key_deposit"))?;
-                            key_deposit = Some(tmp_key_deposit);
-                            key_deposit_encoding = tmp_key_deposit_encoding;
-                            key_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        },
-                        (6, key_enc) =>  {
-                            if pool_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let (tmp_pool_deposit, tmp_pool_deposit_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("pool_deposit"))?;
-                            pool_deposit = Some(tmp_pool_deposit);
-                            pool_deposit_encoding = tmp_pool_deposit_encoding;
-                            pool_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        },
-                        (7, key_enc) =>  {
-                            if maximum_epoch.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("maximum_epoch"))?;
-                            maximum_epoch = Some(tmp_maximum_epoch);
-                            maximum_epoch_encoding = tmp_maximum_epoch_encoding;
-                            maximum_epoch_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        },
-                        (8, key_enc) =>  {
-                            if n_opt.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_n_opt, tmp_n_opt_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("n_opt"))?;
-                            n_opt = Some(tmp_n_opt);
-                            n_opt_encoding = tmp_n_opt_encoding;
-                            n_opt_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        },
-                        (9, key_enc) =>  {
-                            if pool_pledge_influence.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let tmp_pool_pledge_influence = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Rational::deserialize(raw)
-                            })().map_err(|e| e.annotate("pool_pledge_influence"))?;
-                            pool_pledge_influence = Some(tmp_pool_pledge_influence);
-                            pool_pledge_influence_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        },
-                        (10, key_enc) =>  {
-                            if expansion_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(10)).into());
-                            }
-                            let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })().map_err(|e| e.annotate("expansion_rate"))?;
-                            expansion_rate = Some(tmp_expansion_rate);
-                            expansion_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(10);
-                        },
-                        (11, key_enc) =>  {
-                            if treasury_growth_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(11)).into());
-                            }
-                            let tmp_treasury_growth_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })().map_err(|e| e.annotate("treasury_growth_rate"))?;
-                            treasury_growth_rate = Some(tmp_treasury_growth_rate);
-                            treasury_growth_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(11);
-                        },
-                        (16, key_enc) =>  {
-                            if min_pool_cost.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(16)).into());
-                            }
-                            let (tmp_min_pool_cost, tmp_min_pool_cost_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("min_pool_cost"))?;
-                            min_pool_cost = Some(tmp_min_pool_cost);
-                            min_pool_cost_encoding = tmp_min_pool_cost_encoding;
-                            min_pool_cost_key_encoding = Some(key_enc);
-                            orig_deser_order.push(12);
-                        },
-                        (17, key_enc) =>  {
-                            if ada_per_utxo_byte.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(17)).into());
-                            }
-                            let (tmp_ada_per_utxo_byte, tmp_ada_per_utxo_byte_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("ada_per_utxo_byte"))?;
-                            ada_per_utxo_byte = Some(tmp_ada_per_utxo_byte);
-                            ada_per_utxo_byte_encoding = tmp_ada_per_utxo_byte_encoding;
-                            ada_per_utxo_byte_key_encoding = Some(key_enc);
-                            orig_deser_order.push(13);
-                        },
-                        (18, key_enc) =>  {
-                            if cost_models_for_script_languages.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(18)).into());
-                            }
-                            let tmp_cost_models_for_script_languages = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                CostModels::deserialize(raw)
-                            })().map_err(|e| e.annotate("cost_models_for_script_languages"))?;
-                            cost_models_for_script_languages = Some(tmp_cost_models_for_script_languages);
-                            cost_models_for_script_languages_key_encoding = Some(key_enc);
-                            orig_deser_order.push(14);
-                        },
-                        (19, key_enc) =>  {
-                            if execution_costs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(19)).into());
-                            }
-                            let tmp_execution_costs = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnitPrices::deserialize(raw)
-                            })().map_err(|e| e.annotate("execution_costs"))?;
-                            execution_costs = Some(tmp_execution_costs);
-                            execution_costs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(15);
-                        },
-                        (20, key_enc) =>  {
-                            if max_tx_ex_units.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(20)).into());
-                            }
-                            let tmp_max_tx_ex_units = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnits::deserialize(raw)
-                            })().map_err(|e| e.annotate("max_tx_ex_units"))?;
-                            max_tx_ex_units = Some(tmp_max_tx_ex_units);
-                            max_tx_ex_units_key_encoding = Some(key_enc);
-                            orig_deser_order.push(16);
-                        },
-                        (21, key_enc) =>  {
-                            if max_block_ex_units.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(21)).into());
-                            }
-                            let tmp_max_block_ex_units = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnits::deserialize(raw)
-                            })().map_err(|e| e.annotate("max_block_ex_units"))?;
-                            max_block_ex_units = Some(tmp_max_block_ex_units);
-                            max_block_ex_units_key_encoding = Some(key_enc);
-                            orig_deser_order.push(17);
-                        },
-                        (22, key_enc) =>  {
-                            if max_value_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(22)).into());
-                            }
-                            let (tmp_max_value_size, tmp_max_value_size_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("max_value_size"))?;
-                            max_value_size = Some(tmp_max_value_size);
-                            max_value_size_encoding = tmp_max_value_size_encoding;
-                            max_value_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(18);
-                        },
-                        (23, key_enc) =>  {
-                            if collateral_percentage.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(23)).into());
-                            }
-                            let (tmp_collateral_percentage, tmp_collateral_percentage_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("collateral_percentage"))?;
-                            collateral_percentage = Some(tmp_collateral_percentage);
-                            collateral_percentage_encoding = tmp_collateral_percentage_encoding;
-                            collateral_percentage_key_encoding = Some(key_enc);
-                            orig_deser_order.push(19);
-                        },
-                        (24, key_enc) =>  {
-                            if max_collateral_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(24)).into());
-                            }
-                            let (tmp_max_collateral_inputs, tmp_max_collateral_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("max_collateral_inputs"))?;
-                            max_collateral_inputs = Some(tmp_max_collateral_inputs);
-                            max_collateral_inputs_encoding = tmp_max_collateral_inputs_encoding;
-                            max_collateral_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(20);
-                        },
-                        (25, key_enc) =>  {
-                            if pool_voting_thresholds.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(25)).into());
-                            }
-                            let tmp_pool_voting_thresholds = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                PoolVotingThresholds::deserialize(raw)
-                            })().map_err(|e| e.annotate("pool_voting_thresholds"))?;
-                            pool_voting_thresholds = Some(tmp_pool_voting_thresholds);
-                            pool_voting_thresholds_key_encoding = Some(key_enc);
-                            orig_deser_order.push(21);
-                        },
-                        (26, key_enc) =>  {
-                            if d_rep_voting_thresholds.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(26)).into());
-                            }
-                            let tmp_d_rep_voting_thresholds = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                DRepVotingThresholds::deserialize(raw)
-                            })().map_err(|e| e.annotate("d_rep_voting_thresholds"))?;
-                            d_rep_voting_thresholds = Some(tmp_d_rep_voting_thresholds);
-                            d_rep_voting_thresholds_key_encoding = Some(key_enc);
-                            orig_deser_order.push(22);
-                        },
-                        (27, key_enc) =>  {
-                            if min_committee_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(27)).into());
-                            }
-                            let (tmp_min_committee_size, tmp_min_committee_size_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("min_committee_size"))?;
-                            min_committee_size = Some(tmp_min_committee_size);
-                            min_committee_size_encoding = tmp_min_committee_size_encoding;
-                            min_committee_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(23);
-                        },
-                        (28, key_enc) =>  {
-                            if committee_term_limit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(28)).into());
-                            }
-                            let (tmp_committee_term_limit, tmp_committee_term_limit_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("committee_term_limit"))?;
-                            committee_term_limit = Some(tmp_committee_term_limit);
-                            committee_term_limit_encoding = tmp_committee_term_limit_encoding;
-                            committee_term_limit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(24);
-                        },
-                        (29, key_enc) =>  {
-                            if governance_action_validity_period.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(29)).into());
-                            }
-                            let (tmp_governance_action_validity_period, tmp_governance_action_validity_period_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("governance_action_validity_period"))?;
-                            governance_action_validity_period = Some(tmp_governance_action_validity_period);
-                            governance_action_validity_period_encoding = tmp_governance_action_validity_period_encoding;
-                            governance_action_validity_period_key_encoding = Some(key_enc);
-                            orig_deser_order.push(25);
-                        },
-                        (30, key_enc) =>  {
-                            if governance_action_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(30)).into());
-                            }
-                            let (tmp_governance_action_deposit, tmp_governance_action_deposit_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("governance_action_deposit"))?;
-                            governance_action_deposit = Some(tmp_governance_action_deposit);
-                            governance_action_deposit_encoding = tmp_governance_action_deposit_encoding;
-                            governance_action_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(26);
-                        },
-                        (31, key_enc) =>  {
-                            if d_rep_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(31)).into());
-                            }
-                            let (tmp_d_rep_deposit, tmp_d_rep_deposit_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("d_rep_deposit"))?;
-                            d_rep_deposit = Some(tmp_d_rep_deposit);
-                            d_rep_deposit_encoding = tmp_d_rep_deposit_encoding;
-                            d_rep_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(27);
-                        },
-                        (32, key_enc) =>  {
-                            if d_rep_inactivity_period.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(32)).into());
-                            }
-                            let (tmp_d_rep_inactivity_period, tmp_d_rep_inactivity_period_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("d_rep_inactivity_period"))?;
-                            d_rep_inactivity_period = Some(tmp_d_rep_inactivity_period);
-                            d_rep_inactivity_period_encoding = tmp_d_rep_inactivity_period_encoding;
-                            d_rep_inactivity_period_key_encoding = Some(key_enc);
-                            orig_deser_order.push(28);
-                        },
-                        (unknown_key, _enc) => return Err(DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()),
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_a.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_minfee_a, tmp_minfee_a_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_a"))?;
+                                minfee_a = Some(tmp_minfee_a);
+                                minfee_a_encoding = tmp_minfee_a_encoding;
+                                minfee_a_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_b.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_minfee_b, tmp_minfee_b_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_b"))?;
+                                minfee_b = Some(tmp_minfee_b);
+                                minfee_b_encoding = tmp_minfee_b_encoding;
+                                minfee_b_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_block_body_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_body_size"))?;
+                                max_block_body_size = Some(tmp_max_block_body_size);
+                                max_block_body_size_encoding = tmp_max_block_body_size_encoding;
+                                max_block_body_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_transaction_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("max_transaction_size"))?;
+                                max_transaction_size = Some(tmp_max_transaction_size);
+                                max_transaction_size_encoding = tmp_max_transaction_size_encoding;
+                                max_transaction_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => {
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                4,
+                                key_enc,
+                                |raw, read_len| {
+                                    if max_block_header_size.is_some() {
+                                        return Err(
+                                            DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                        );
+                                    }
+                                    let (
+                                        tmp_max_block_header_size,
+                                        tmp_max_block_header_size_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_header_size"))?;
+                                    max_block_header_size = Some(tmp_max_block_header_size);
+                                    max_block_header_size_encoding =
+                                        tmp_max_block_header_size_encoding;
+                                    max_block_header_size_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(4);
+                                    Ok(())
+                                },
+                            )?
+                        }
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if key_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (tmp_key_deposit, tmp_key_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("key_deposit"))?;
+                                key_deposit = Some(tmp_key_deposit);
+                                key_deposit_encoding = tmp_key_deposit_encoding;
+                                key_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
+                                }
+                                let (tmp_pool_deposit, tmp_pool_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("pool_deposit"))?;
+                                pool_deposit = Some(tmp_pool_deposit);
+                                pool_deposit_encoding = tmp_pool_deposit_encoding;
+                                pool_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if maximum_epoch.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("maximum_epoch"))?;
+                                maximum_epoch = Some(tmp_maximum_epoch);
+                                maximum_epoch_encoding = tmp_maximum_epoch_encoding;
+                                maximum_epoch_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if n_opt.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
+                                }
+                                let (tmp_n_opt, tmp_n_opt_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("n_opt"))?;
+                                n_opt = Some(tmp_n_opt);
+                                n_opt_encoding = tmp_n_opt_encoding;
+                                n_opt_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_pledge_influence.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
+                                }
+                                let tmp_pool_pledge_influence =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        Rational::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("pool_pledge_influence"))?;
+                                pool_pledge_influence = Some(tmp_pool_pledge_influence);
+                                pool_pledge_influence_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (10, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            10,
+                            key_enc,
+                            |raw, read_len| {
+                                if expansion_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(10)).into()
+                                    );
+                                }
+                                let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    UnitInterval::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("expansion_rate"))?;
+                                expansion_rate = Some(tmp_expansion_rate);
+                                expansion_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(10);
+                                Ok(())
+                            },
+                        )?,
+                        (11, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            11,
+                            key_enc,
+                            |raw, read_len| {
+                                if treasury_growth_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(11)).into()
+                                    );
+                                }
+                                let tmp_treasury_growth_rate =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        UnitInterval::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("treasury_growth_rate"))?;
+                                treasury_growth_rate = Some(tmp_treasury_growth_rate);
+                                treasury_growth_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(11);
+                                Ok(())
+                            },
+                        )?,
+                        (16, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            16,
+                            key_enc,
+                            |raw, read_len| {
+                                if min_pool_cost.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(16)).into()
+                                    );
+                                }
+                                let (tmp_min_pool_cost, tmp_min_pool_cost_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("min_pool_cost"))?;
+                                min_pool_cost = Some(tmp_min_pool_cost);
+                                min_pool_cost_encoding = tmp_min_pool_cost_encoding;
+                                min_pool_cost_key_encoding = Some(key_enc);
+                                orig_deser_order.push(12);
+                                Ok(())
+                            },
+                        )?,
+                        (17, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            17,
+                            key_enc,
+                            |raw, read_len| {
+                                if ada_per_utxo_byte.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(17)).into()
+                                    );
+                                }
+                                let (tmp_ada_per_utxo_byte, tmp_ada_per_utxo_byte_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("ada_per_utxo_byte"))?;
+                                ada_per_utxo_byte = Some(tmp_ada_per_utxo_byte);
+                                ada_per_utxo_byte_encoding = tmp_ada_per_utxo_byte_encoding;
+                                ada_per_utxo_byte_key_encoding = Some(key_enc);
+                                orig_deser_order.push(13);
+                                Ok(())
+                            },
+                        )?,
+                        (18, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            18,
+                            key_enc,
+                            |raw, read_len| {
+                                if cost_models_for_script_languages.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(18)).into()
+                                    );
+                                }
+                                let tmp_cost_models_for_script_languages =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        CostModels::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("cost_models_for_script_languages"))?;
+                                cost_models_for_script_languages =
+                                    Some(tmp_cost_models_for_script_languages);
+                                cost_models_for_script_languages_key_encoding = Some(key_enc);
+                                orig_deser_order.push(14);
+                                Ok(())
+                            },
+                        )?,
+                        (19, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            19,
+                            key_enc,
+                            |raw, read_len| {
+                                if execution_costs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(19)).into()
+                                    );
+                                }
+                                let tmp_execution_costs = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    ExUnitPrices::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("execution_costs"))?;
+                                execution_costs = Some(tmp_execution_costs);
+                                execution_costs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(15);
+                                Ok(())
+                            },
+                        )?,
+                        (20, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            20,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_tx_ex_units.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(20)).into()
+                                    );
+                                }
+                                let tmp_max_tx_ex_units = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    ExUnits::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("max_tx_ex_units"))?;
+                                max_tx_ex_units = Some(tmp_max_tx_ex_units);
+                                max_tx_ex_units_key_encoding = Some(key_enc);
+                                orig_deser_order.push(16);
+                                Ok(())
+                            },
+                        )?,
+                        (21, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            21,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_block_ex_units.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(21)).into()
+                                    );
+                                }
+                                let tmp_max_block_ex_units = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    ExUnits::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("max_block_ex_units"))?;
+                                max_block_ex_units = Some(tmp_max_block_ex_units);
+                                max_block_ex_units_key_encoding = Some(key_enc);
+                                orig_deser_order.push(17);
+                                Ok(())
+                            },
+                        )?,
+                        (22, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            22,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_value_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(22)).into()
+                                    );
+                                }
+                                let (tmp_max_value_size, tmp_max_value_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("max_value_size"))?;
+                                max_value_size = Some(tmp_max_value_size);
+                                max_value_size_encoding = tmp_max_value_size_encoding;
+                                max_value_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(18);
+                                Ok(())
+                            },
+                        )?,
+                        (23, key_enc) => {
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                23,
+                                key_enc,
+                                |raw, read_len| {
+                                    if collateral_percentage.is_some() {
+                                        return Err(DeserializeFailure::DuplicateKey(Key::Uint(
+                                            23,
+                                        ))
+                                        .into());
+                                    }
+                                    let (
+                                        tmp_collateral_percentage,
+                                        tmp_collateral_percentage_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("collateral_percentage"))?;
+                                    collateral_percentage = Some(tmp_collateral_percentage);
+                                    collateral_percentage_encoding =
+                                        tmp_collateral_percentage_encoding;
+                                    collateral_percentage_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(19);
+                                    Ok(())
+                                },
+                            )?
+                        }
+                        (24, key_enc) => {
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                24,
+                                key_enc,
+                                |raw, read_len| {
+                                    if max_collateral_inputs.is_some() {
+                                        return Err(DeserializeFailure::DuplicateKey(Key::Uint(
+                                            24,
+                                        ))
+                                        .into());
+                                    }
+                                    let (
+                                        tmp_max_collateral_inputs,
+                                        tmp_max_collateral_inputs_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("max_collateral_inputs"))?;
+                                    max_collateral_inputs = Some(tmp_max_collateral_inputs);
+                                    max_collateral_inputs_encoding =
+                                        tmp_max_collateral_inputs_encoding;
+                                    max_collateral_inputs_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(20);
+                                    Ok(())
+                                },
+                            )?
+                        }
+                        (25, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            25,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_voting_thresholds.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(25)).into()
+                                    );
+                                }
+                                let tmp_pool_voting_thresholds =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        PoolVotingThresholds::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("pool_voting_thresholds"))?;
+                                pool_voting_thresholds = Some(tmp_pool_voting_thresholds);
+                                pool_voting_thresholds_key_encoding = Some(key_enc);
+                                orig_deser_order.push(21);
+                                Ok(())
+                            },
+                        )?,
+                        (26, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            26,
+                            key_enc,
+                            |raw, read_len| {
+                                if d_rep_voting_thresholds.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(26)).into()
+                                    );
+                                }
+                                let tmp_d_rep_voting_thresholds =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        DRepVotingThresholds::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("d_rep_voting_thresholds"))?;
+                                d_rep_voting_thresholds = Some(tmp_d_rep_voting_thresholds);
+                                d_rep_voting_thresholds_key_encoding = Some(key_enc);
+                                orig_deser_order.push(22);
+                                Ok(())
+                            },
+                        )?,
+                        (27, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            27,
+                            key_enc,
+                            |raw, read_len| {
+                                if min_committee_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(27)).into()
+                                    );
+                                }
+                                let (tmp_min_committee_size, tmp_min_committee_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("min_committee_size"))?;
+                                min_committee_size = Some(tmp_min_committee_size);
+                                min_committee_size_encoding = tmp_min_committee_size_encoding;
+                                min_committee_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(23);
+                                Ok(())
+                            },
+                        )?,
+                        (28, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            28,
+                            key_enc,
+                            |raw, read_len| {
+                                if committee_term_limit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(28)).into()
+                                    );
+                                }
+                                let (tmp_committee_term_limit, tmp_committee_term_limit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("committee_term_limit"))?;
+                                committee_term_limit = Some(tmp_committee_term_limit);
+                                committee_term_limit_encoding = tmp_committee_term_limit_encoding;
+                                committee_term_limit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(24);
+                                Ok(())
+                            },
+                        )?,
+                        (29, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            29,
+                            key_enc,
+                            |raw, read_len| {
+                                if governance_action_validity_period.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(29)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_governance_action_validity_period,
+                                    tmp_governance_action_validity_period_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                        .map_err(Into::<DeserializeError>::into)
+                                })()
+                                .map_err(|e| e.annotate("governance_action_validity_period"))?;
+                                governance_action_validity_period =
+                                    Some(tmp_governance_action_validity_period);
+                                governance_action_validity_period_encoding =
+                                    tmp_governance_action_validity_period_encoding;
+                                governance_action_validity_period_key_encoding = Some(key_enc);
+                                orig_deser_order.push(25);
+                                Ok(())
+                            },
+                        )?,
+                        (30, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            30,
+                            key_enc,
+                            |raw, read_len| {
+                                if governance_action_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(30)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_governance_action_deposit,
+                                    tmp_governance_action_deposit_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                        .map_err(Into::<DeserializeError>::into)
+                                })()
+                                .map_err(|e| e.annotate("governance_action_deposit"))?;
+                                governance_action_deposit = Some(tmp_governance_action_deposit);
+                                governance_action_deposit_encoding =
+                                    tmp_governance_action_deposit_encoding;
+                                governance_action_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(26);
+                                Ok(())
+                            },
+                        )?,
+                        (31, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            31,
+                            key_enc,
+                            |raw, read_len| {
+                                if d_rep_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(31)).into()
+                                    );
+                                }
+                                let (tmp_d_rep_deposit, tmp_d_rep_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("d_rep_deposit"))?;
+                                d_rep_deposit = Some(tmp_d_rep_deposit);
+                                d_rep_deposit_encoding = tmp_d_rep_deposit_encoding;
+                                d_rep_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(27);
+                                Ok(())
+                            },
+                        )?,
+                        (32, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            32,
+                            key_enc,
+                            |raw, read_len| {
+                                if d_rep_inactivity_period.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(32)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_d_rep_inactivity_period,
+                                    tmp_d_rep_inactivity_period_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                        .map_err(Into::<DeserializeError>::into)
+                                })()
+                                .map_err(|e| e.annotate("d_rep_inactivity_period"))?;
+                                d_rep_inactivity_period = Some(tmp_d_rep_inactivity_period);
+                                d_rep_inactivity_period_encoding =
+                                    tmp_d_rep_inactivity_period_encoding;
+                                d_rep_inactivity_period_key_encoding = Some(key_enc);
+                                orig_deser_order.push(28);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
-                    cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),
+                    cbor_event::Type::Text => {
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
+                    }
                     cbor_event::Type::Special => match len {
-                        cbor_event::LenSz::Len(_, _) => return Err(DeserializeFailure::BreakInDefiniteLen.into()),
+                        cbor_event::LenSz::Len(_, _) => {
+                            return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                        }
                         cbor_event::LenSz::Indefinite => match raw.special()? {
                             cbor_event::Special::Break => break,
                             _ => return Err(DeserializeFailure::EndingBreakMissing.into()),
                         },
                     },
-                    other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),
+                    other_type => {
+                        return Err(DeserializeFailure::UnexpectedKeyType(other_type).into())
+                    }
                 }
                 read += 1;
             }
@@ -1612,9 +2076,11 @@ impl Deserialize for ProtocolParamUpdate {
                     d_rep_deposit_encoding,
                     d_rep_inactivity_period_key_encoding,
                     d_rep_inactivity_period_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
-        })().map_err(|e| e.annotate("ProtocolParamUpdate"))
+        })()
+        .map_err(|e| e.annotate("ProtocolParamUpdate"))
     }
 }
 
@@ -0,0 +1,476 @@
+//! cardano-cli TextEnvelope types for chain types. See cml_core::text_envelope
+//! Keys are handled in cml_crypto::text_envelope
+
+use std::io::Cursor;
+
+use cbor_event::{de::Deserializer, se::Serializer};
+pub use cml_core::text_envelope::{
+    FromTextEnvelope, TextEnvelope, TextEnvelopeError, TextEnvelopeType, ToTextEnvelope,
+};
+use cml_core::{
+    error::{DeserializeError, DeserializeFailure},
+    serialization::{Deserialize, Serialize},
+};
+
+use crate::{
+    certs::Certificate,
+    crypto::{BootstrapWitness, Vkeywitness},
+    plutus::{PlutusScript, PlutusV1Script, PlutusV2Script, PlutusV3Script},
+    transaction::{NativeScript, Transaction, TransactionWitnessSet},
+    Script,
+};
+
+/// Eras as named in cardano-cli TextEnvelope types e.g. "Tx ConwayEra"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TextEnvelopeEra {
+    Shelley,
+    Allegra,
+    Mary,
+    Alonzo,
+    Babbage,
+    Conway,
+}
+
+impl TextEnvelopeEra {
+    const ALL: [TextEnvelopeEra; 6] = [
+        Self::Shelley,
+        Self::Allegra,
+        Self::Mary,
+        Self::Alonzo,
+        Self::Babbage,
+        Self::Conway,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Shelley => "ShelleyEra",
+            Self::Allegra => "AllegraEra",
+            Self::Mary => "MaryEra",
+            Self::Alonzo => "AlonzoEra",
+            Self::Babbage => "BabbageEra",
+            Self::Conway => "ConwayEra",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|era| era.name() == name).copied()
+    }
+
+    /// Type used for transactions of this era
+    pub fn tx_type_name(&self) -> String {
+        format!("Tx {}", self.name())
+    }
+
+    /// Parses a transaction type. Older cardano-cli versions use "Tx <era>"
+    /// while newer ones use "Witnessed Tx <era>" / "Unwitnessed Tx <era>"
+    pub fn from_tx_type_name(type_name: &str) -> Option<Self> {
+        let era = type_name
+            .strip_prefix("Tx ")
+            .or_else(|| type_name.strip_prefix("Witnessed Tx "))
+            .or_else(|| type_name.strip_prefix("Unwitnessed Tx "))?;
+        Self::from_name(era)
+    }
+
+    /// Type used for key witness files of this era
+    pub fn tx_witness_type_name(&self) -> String {
+        format!("TxWitness {}", self.name())
+    }
+
+    pub fn from_tx_witness_type_name(type_name: &str) -> Option<Self> {
+        type_name
+            .strip_prefix("TxWitness ")
+            .and_then(Self::from_name)
+    }
+}
+
+const TX_DESCRIPTION: &str = "Ledger Cddl Format";
+const KEY_WITNESS_DESCRIPTION: &str = "Key Witness ShelleyEra";
+
+impl Transaction {
+    /// Envelope as a transaction of a specific era.
+    /// The default ToTextEnvelope impl uses the Conway era.
+    pub fn to_text_envelope_for_era(&self, era: TextEnvelopeEra) -> TextEnvelope {
+        TextEnvelope::new(
+            era.tx_type_name(),
+            TX_DESCRIPTION.to_owned(),
+            &self.to_cbor_bytes(),
+        )
+    }
+}
+
+impl TextEnvelopeType for Transaction {
+    fn text_envelope_type(&self) -> String {
+        TextEnvelopeEra::Conway.tx_type_name()
+    }
+
+    fn text_envelope_description(&self) -> String {
+        TX_DESCRIPTION.to_owned()
+    }
+
+    /// Pre-Alonzo transactions do not have the same format. Use cml_multi_era for those.
+    fn accepted_text_envelope_types() -> Vec<String> {
+        TextEnvelopeEra::ALL
+            .iter()
+            .filter(|era| **era >= TextEnvelopeEra::Alonzo)
+            .flat_map(|era| {
+                let tx = era.tx_type_name();
+                [format!("Witnessed {tx}"), format!("Unwitnessed {tx}"), tx]
+            })
+            .collect()
+    }
+}
+
+/// cardano-cli key witness files are `[0, vkeywitness] / [1, bootstrap_witness]`
+fn key_witness_text_envelope<T: Serialize>(tag: u64, witness: &T) -> TextEnvelope {
+    let mut serializer = Serializer::new_vec();
+    serializer.write_array(cbor_event::Len::Len(2)).unwrap();
+    serializer.write_unsigned_integer(tag).unwrap();
+    witness.serialize(&mut serializer, false).unwrap();
+    TextEnvelope::new(
+        TextEnvelopeEra::Conway.tx_witness_type_name(),
+        KEY_WITNESS_DESCRIPTION.to_owned(),
+        &serializer.finalize(),
+    )
+}
+
+enum KeyWitness {
+    Vkey(Vkeywitness),
+    Bootstrap(BootstrapWitness),
+}
+
+impl KeyWitness {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(
+            TextEnvelopeEra::ALL
+                .iter()
+                .map(TextEnvelopeEra::tx_witness_type_name)
+                .collect(),
+        )?;
+        let mut raw = Deserializer::from(Cursor::new(envelope.cbor_bytes()?));
+        (|| -> Result<_, DeserializeError> {
+            raw.tuple(2, "KeyWitness")?;
+            match raw.unsigned_integer()? {
+                0 => Vkeywitness::deserialize(&mut raw).map(Self::Vkey),
+                1 => BootstrapWitness::deserialize(&mut raw).map(Self::Bootstrap),
+                tag => Err(DeserializeFailure::FixedValueMismatch {
+                    found: cml_core::error::Key::Uint(tag),
+                    expected: cml_core::error::Key::Uint(0),
+                }
+                .into()),
+            }
+        })()
+        .map_err(|e| e.annotate("KeyWitness").into())
+    }
+}
+
+impl ToTextEnvelope for Vkeywitness {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        key_witness_text_envelope(0, self)
+    }
+}
+
+impl FromTextEnvelope for Vkeywitness {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        match KeyWitness::from_text_envelope(envelope)? {
+            KeyWitness::Vkey(vkeywitness) => Ok(vkeywitness),
+            KeyWitness::Bootstrap(_) => Err(DeserializeError::new(
+                "Vkeywitness",
+                DeserializeFailure::FixedValueMismatch {
+                    found: cml_core::error::Key::Uint(1),
+                    expected: cml_core::error::Key::Uint(0),
+                },
+            )
+            .into()),
+        }
+    }
+}
+
+impl ToTextEnvelope for BootstrapWitness {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        key_witness_text_envelope(1, self)
+    }
+}
+
+impl FromTextEnvelope for BootstrapWitness {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        match KeyWitness::from_text_envelope(envelope)? {
+            KeyWitness::Bootstrap(bootstrap) => Ok(bootstrap),
+            KeyWitness::Vkey(_) => Err(DeserializeError::new(
+                "BootstrapWitness",
+                DeserializeFailure::FixedValueMismatch {
+                    found: cml_core::error::Key::Uint(0),
+                    expected: cml_core::error::Key::Uint(1),
+                },
+            )
+            .into()),
+        }
+    }
+}
+
+impl TransactionWitnessSet {
+    /// cardano-cli has no witness set format, instead using one key witness file per witness.
+    /// This returns one envelope per vkey/bootstrap witness. Other witnesses are not included.
+    pub fn to_key_witness_text_envelopes(&self) -> Vec<TextEnvelope> {
+        let vkeys = self
+            .vkeywitnesses
+            .iter()
+            .flatten()
+            .map(ToTextEnvelope::to_text_envelope);
+        let bootstraps = self
+            .bootstrap_witnesses
+            .iter()
+            .flatten()
+            .map(ToTextEnvelope::to_text_envelope);
+        vkeys.chain(bootstraps).collect()
+    }
+
+    /// Combines cardano-cli key witness files into a witness set
+    pub fn from_key_witness_text_envelopes(
+        envelopes: &[TextEnvelope],
+    ) -> Result<Self, TextEnvelopeError> {
+        let mut witness_set = Self::new();
+        for envelope in envelopes {
+            match KeyWitness::from_text_envelope(envelope)? {
+                KeyWitness::Vkey(vkeywitness) => witness_set
+                    .vkeywitnesses
+                    .get_or_insert_with(Vec::new)
+                    .push(vkeywitness),
+                KeyWitness::Bootstrap(bootstrap) => witness_set
+                    .bootstrap_witnesses
+                    .get_or_insert_with(Vec::new)
+                    .push(bootstrap),
+            }
+        }
+        Ok(witness_set)
+    }
+}
+
+impl FromTextEnvelope for TransactionWitnessSet {
+    /// Reads a single cardano-cli key witness file into a witness set
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        Self::from_key_witness_text_envelopes(std::slice::from_ref(envelope))
+    }
+}
+
+impl TextEnvelopeType for NativeScript {
+    fn text_envelope_type(&self) -> String {
+        "SimpleScript".to_owned()
+    }
+
+    fn accepted_text_envelope_types() -> Vec<String> {
+        vec![
+            "SimpleScript".to_owned(),
+            "SimpleScriptV1".to_owned(),
+            "SimpleScriptV2".to_owned(),
+        ]
+    }
+}
+
+macro_rules! impl_plutus_script_text_envelope {
+    ($name:ident, $type_name:literal) => {
+        impl TextEnvelopeType for $name {
+            fn text_envelope_type(&self) -> String {
+                $type_name.to_owned()
+            }
+
+            fn accepted_text_envelope_types() -> Vec<String> {
+                vec![$type_name.to_owned()]
+            }
+        }
+    };
+}
+
+impl_plutus_script_text_envelope!(PlutusV1Script, "PlutusScriptV1");
+impl_plutus_script_text_envelope!(PlutusV2Script, "PlutusScriptV2");
+impl_plutus_script_text_envelope!(PlutusV3Script, "PlutusScriptV3");
+
+impl ToTextEnvelope for PlutusScript {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        match self {
+            Self::PlutusV1(script) => script.to_text_envelope(),
+            Self::PlutusV2(script) => script.to_text_envelope(),
+            Self::PlutusV3(script) => script.to_text_envelope(),
+        }
+    }
+}
+
+impl FromTextEnvelope for PlutusScript {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        match envelope.type_name.as_str() {
+            t if PlutusV1Script::accepts_text_envelope_type(t) => {
+                PlutusV1Script::from_text_envelope(envelope).map(Self::PlutusV1)
+            }
+            t if PlutusV2Script::accepts_text_envelope_type(t) => {
+                PlutusV2Script::from_text_envelope(envelope).map(Self::PlutusV2)
+            }
+            _ => PlutusV3Script::from_text_envelope(envelope).map(Self::PlutusV3),
+        }
+    }
+}
+
+impl ToTextEnvelope for Script {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        match self {
+            Self::Native { script, .. } => script.to_text_envelope(),
+            Self::PlutusV1 { script, .. } => script.to_text_envelope(),
+            Self::PlutusV2 { script, .. } => script.to_text_envelope(),
+            Self::PlutusV3 { script, .. } => script.to_text_envelope(),
+        }
+    }
+}
+
+impl FromTextEnvelope for Script {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        if NativeScript::accepts_text_envelope_type(&envelope.type_name) {
+            NativeScript::from_text_envelope(envelope).map(Script::new_native)
+        } else {
+            PlutusScript::from_text_envelope(envelope).map(|script| match script {
+                PlutusScript::PlutusV1(script) => Script::new_plutus_v1(script),
+                PlutusScript::PlutusV2(script) => Script::new_plutus_v2(script),
+                PlutusScript::PlutusV3(script) => Script::new_plutus_v3(script),
+            })
+        }
+    }
+}
+
+impl TextEnvelopeType for Certificate {
+    fn text_envelope_type(&self) -> String {
+        "CertificateConway".to_owned()
+    }
+
+    fn text_envelope_description(&self) -> String {
+        match self {
+            Self::StakeRegistration(_) | Self::RegCert(_) => {
+                "Stake Address Registration Certificate"
+            }
+            Self::StakeDeregistration(_) | Self::UnregCert(_) => {
+                "Stake Address Deregistration Certificate"
+            }
+            Self::StakeDelegation(_) => "Stake Address Delegation Certificate",
+            Self::PoolRegistration(_) => "Stake Pool Registration Certificate",
+            Self::PoolRetirement(_) => "Stake Pool Retirement Certificate",
+            Self::VoteDelegCert(_) => "Vote Delegation Certificate",
+            Self::StakeVoteDelegCert(_) => "Stake and Vote Delegation Certificate",
+            Self::StakeRegDelegCert(_) => "Stake Address Registration and Delegation Certificate",
+            Self::VoteRegDelegCert(_) => {
+                "Stake Address Registration and Vote Delegation Certificate"
+            }
+            Self::StakeVoteRegDelegCert(_) => {
+                "Stake Address Registration and Stake and Vote Delegation Certificate"
+            }
+            Self::AuthCommitteeHotCert(_) => {
+                "Constitutional Committee Hot Key Registration Certificate"
+            }
+            Self::ResignCommitteeColdCert(_) => {
+                "Constitutional Committee Cold Key Resignation Certificate"
+            }
+            Self::RegDrepCert(_) => "DRep Key Registration Certificate",
+            Self::UnregDrepCert(_) => "DRep Key Retirement Certificate",
+            Self::UpdateDrepCert(_) => "DRep Update Certificate",
+        }
+        .to_owned()
+    }
+
+    /// Also accepts pre-Conway certificates. Note that MIR/genesis delegation certificates
+    /// will not be able to be parsed (use cml_multi_era for those).
+    fn accepted_text_envelope_types() -> Vec<String> {
+        vec![
+            "CertificateConway".to_owned(),
+            "CertificateShelley".to_owned(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certs::StakeCredential;
+    use cml_crypto::{Ed25519KeyHash, PrivateKey};
+
+    #[test]
+    fn tx_era_type_names() {
+        assert_eq!(
+            TextEnvelopeEra::from_tx_type_name("Tx BabbageEra"),
+            Some(TextEnvelopeEra::Babbage)
+        );
+        assert_eq!(
+            TextEnvelopeEra::from_tx_type_name("Unwitnessed Tx ConwayEra"),
+            Some(TextEnvelopeEra::Conway)
+        );
+        assert!(Transaction::accepts_text_envelope_type(
+            "Witnessed Tx AlonzoEra"
+        ));
+        assert!(!Transaction::accepts_text_envelope_type("Tx MaryEra"));
+        assert!(!Transaction::accepts_text_envelope_type(
+            "TxWitness ConwayEra"
+        ));
+    }
+
+    #[test]
+    fn transaction_round_trip() {
+        let json = r#"{
+            "type": "Tx BabbageEra",
+            "description": "Ledger Cddl Format",
+            "cborHex": "84a300818258200f3abbc8fc19c2e61bab6059bf8a466e6e754833a08a62a6c56fe0e78f19d9d5000181825839009493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e32c728d3861e164cab28cb8f006448139c8f1740ffb8e7aa9e5232dc1a001e8480021a0002a009a0f5f6"
+        }"#;
+        let tx = Transaction::from_text_envelope_json(json).unwrap();
+        let envelope = tx.to_text_envelope_for_era(TextEnvelopeEra::Babbage);
+        assert_eq!(envelope, TextEnvelope::from_json(json).unwrap());
+        assert_eq!(tx.to_text_envelope().type_name, "Tx ConwayEra");
+    }
+
+    #[test]
+    fn key_witness_files() {
+        let key = PrivateKey::generate_ed25519();
+        let vkeywitness = Vkeywitness::new(key.to_public(), key.sign(&[0; 32]));
+        let envelope = vkeywitness.to_text_envelope();
+        assert_eq!(envelope.type_name, "TxWitness ConwayEra");
+        assert!(envelope.cbor_hex.starts_with("8200825820"));
+        let witness_set = TransactionWitnessSet::from_text_envelope(&envelope).unwrap();
+        assert_eq!(witness_set.vkeywitnesses.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            witness_set.to_key_witness_text_envelopes(),
+            vec![envelope.clone()]
+        );
+        assert!(BootstrapWitness::from_text_envelope(&envelope).is_err());
+    }
+
+    #[test]
+    fn scripts_and_certs() {
+        let native = NativeScript::new_script_pubkey(Ed25519KeyHash::from([0; 28]));
+        let native_envelope = native.to_text_envelope();
+        assert_eq!(native_envelope.type_name, "SimpleScript");
+        assert_eq!(
+            Script::from_text_envelope(&native_envelope).unwrap().hash(),
+            native.hash()
+        );
+
+        let plutus = PlutusV2Script::new(vec![0x4e, 0x4d, 0x01, 0x00, 0x00]);
+        let plutus_envelope = plutus.to_text_envelope();
+        assert_eq!(plutus_envelope.type_name, "PlutusScriptV2");
+        assert_eq!(plutus_envelope.cbor_hex, "454e4d010000");
+        assert!(PlutusV1Script::from_text_envelope(&plutus_envelope).is_err());
+        match PlutusScript::from_text_envelope(&plutus_envelope).unwrap() {
+            PlutusScript::PlutusV2(script) => assert_eq!(script.hash(), plutus.hash()),
+            _ => panic!("expected PlutusV2"),
+        }
+
+        let cert = Certificate::new_stake_registration(StakeCredential::new_pub_key(
+            Ed25519KeyHash::from([1; 28]),
+        ));
+        let cert_envelope = cert.to_text_envelope();
+        assert_eq!(cert_envelope.type_name, "CertificateConway");
+        assert_eq!(
+            cert_envelope.description,
+            "Stake Address Registration Certificate"
+        );
+        let mut shelley_envelope = cert_envelope.clone();
+        shelley_envelope.type_name = "CertificateShelley".to_owned();
+        assert_eq!(
+            Certificate::from_text_envelope(&shelley_envelope)
+                .unwrap()
+                .to_cbor_bytes(),
+            cert.to_cbor_bytes()
+        );
+    }
+}
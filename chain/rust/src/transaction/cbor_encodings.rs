@@ -3,6 +3,7 @@
 
 use crate::{address::RewardAccount, assets::AssetName, Voter};
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use cml_crypto::ScriptHash;
 use std::collections::BTreeMap;
 
@@ -22,6 +23,7 @@ pub struct ConwayFormatTxOutEncoding {
     pub script_reference_tag_encoding: Option<cbor_event::Sz>,
     pub script_reference_bytes_encoding: StringEncoding,
     pub script_reference_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -115,6 +117,7 @@ pub struct TransactionBodyEncoding {
     pub current_treasury_value_key_encoding: Option<cbor_event::Sz>,
     pub donation_encoding: Option<cbor_event::Sz>,
     pub donation_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -149,4 +152,5 @@ pub struct TransactionWitnessSetEncoding {
     pub plutus_v2_scripts_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v3_scripts_encoding: LenEncoding,
     pub plutus_v3_scripts_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
@@ -11,6 +11,8 @@ use cbor_event::de::Deserializer;
 use cbor_event::se::Serializer;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::{CborValue, Int};
 use cml_crypto::RawBytesEncoding;
 use cml_crypto::ScriptHash;
 use std::io::{BufRead, Seek, SeekFrom, Write};
@@ -113,6 +115,26 @@ impl Serialize for ConwayFormatTxOut {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ConwayFormatTxOut {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -126,7 +148,7 @@ impl Serialize for ConwayFormatTxOut {
                     } + match &self.script_reference {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -146,7 +168,9 @@ impl Serialize for ConwayFormatTxOut {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -235,6 +259,8 @@ impl Serialize for ConwayFormatTxOut {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -251,6 +277,7 @@ impl Deserialize for ConwayFormatTxOut {
         read_len.read_elems(2)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut address_key_encoding = None;
             let mut address = None;
             let mut amount_key_encoding = None;
@@ -268,86 +295,142 @@ impl Deserialize for ConwayFormatTxOut {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if address.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let tmp_address = Address::deserialize(raw)
-                                .map_err(|e: DeserializeError| e.annotate("address"))?;
-                            address = Some(tmp_address);
-                            address_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if amount.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let tmp_amount = Value::deserialize(raw)
-                                .map_err(|e: DeserializeError| e.annotate("amount"))?;
-                            amount = Some(tmp_amount);
-                            amount_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if datum_option.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let tmp_datum_option = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                DatumOption::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("datum_option"))?;
-                            datum_option = Some(tmp_datum_option);
-                            datum_option_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if script_reference.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (
-                                tmp_script_reference,
-                                tmp_script_reference_tag_encoding,
-                                tmp_script_reference_bytes_encoding,
-                            ) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                match raw.tag_sz()? {
-                                    (24, tag_enc) => {
-                                        let (
-                                            script_reference_bytes,
-                                            script_reference_bytes_encoding,
-                                        ) = raw.bytes_sz()?;
-                                        let inner_de = &mut Deserializer::from(
-                                            std::io::Cursor::new(script_reference_bytes),
-                                        );
-                                        Ok((
-                                            Script::deserialize(inner_de)?,
-                                            Some(tag_enc),
-                                            StringEncoding::from(script_reference_bytes_encoding),
-                                        ))
-                                    }
-                                    (tag, _enc) => Err(DeserializeFailure::TagMismatch {
-                                        found: tag,
-                                        expected: 24,
-                                    }
-                                    .into()),
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if address.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
                                 }
-                            })()
-                            .map_err(|e| e.annotate("script_reference"))?;
-                            script_reference = Some(tmp_script_reference);
-                            script_reference_tag_encoding = tmp_script_reference_tag_encoding;
-                            script_reference_bytes_encoding = tmp_script_reference_bytes_encoding;
-                            script_reference_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                let tmp_address = Address::deserialize(raw)
+                                    .map_err(|e: DeserializeError| e.annotate("address"))?;
+                                address = Some(tmp_address);
+                                address_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if amount.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let tmp_amount = Value::deserialize(raw)
+                                    .map_err(|e: DeserializeError| e.annotate("amount"))?;
+                                amount = Some(tmp_amount);
+                                amount_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if datum_option.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let tmp_datum_option = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    DatumOption::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("datum_option"))?;
+                                datum_option = Some(tmp_datum_option);
+                                datum_option_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if script_reference.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_script_reference,
+                                    tmp_script_reference_tag_encoding,
+                                    tmp_script_reference_bytes_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    match raw.tag_sz()? {
+                                        (24, tag_enc) => {
+                                            let (
+                                                script_reference_bytes,
+                                                script_reference_bytes_encoding,
+                                            ) = raw.bytes_sz()?;
+                                            let inner_de = &mut Deserializer::from(
+                                                std::io::Cursor::new(script_reference_bytes),
+                                            );
+                                            Ok((
+                                                Script::deserialize(inner_de)?,
+                                                Some(tag_enc),
+                                                StringEncoding::from(
+                                                    script_reference_bytes_encoding,
+                                                ),
+                                            ))
+                                        }
+                                        (tag, _enc) => Err(DeserializeFailure::TagMismatch {
+                                            found: tag,
+                                            expected: 24,
+                                        }
+                                        .into()),
+                                    }
+                                })()
+                                .map_err(|e| e.annotate("script_reference"))?;
+                                script_reference = Some(tmp_script_reference);
+                                script_reference_tag_encoding = tmp_script_reference_tag_encoding;
+                                script_reference_bytes_encoding =
+                                    tmp_script_reference_bytes_encoding;
+                                script_reference_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -387,6 +470,7 @@ impl Deserialize for ConwayFormatTxOut {
                     script_reference_key_encoding,
                     script_reference_tag_encoding,
                     script_reference_bytes_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -1565,6 +1649,26 @@ impl Serialize for TransactionBody {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl TransactionBody {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -1623,7 +1727,7 @@ impl Serialize for TransactionBody {
                     } + match &self.donation {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -1692,7 +1796,9 @@ impl Serialize for TransactionBody {
                     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
                 ]
             });
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -2399,6 +2505,8 @@ impl Serialize for TransactionBody {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -2415,6 +2523,7 @@ impl Deserialize for TransactionBody {
         read_len.read_elems(3)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut inputs_encoding = LenEncoding::default();
             let mut inputs_key_encoding = None;
             let mut inputs = None;
@@ -2479,440 +2588,854 @@ impl Deserialize for TransactionBody {
             let mut donation_key_encoding = None;
             let mut donation = None;
             let mut read = 0;
-            while match len { cbor_event::LenSz::Len(n, _) => read < n, cbor_event::LenSz::Indefinite => true, } {
+            while match len {
+                cbor_event::LenSz::Len(n, _) => read < n,
+                cbor_event::LenSz::Indefinite => true,
+            } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) =>  {
-                            if inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_inputs, tmp_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    inputs_arr.push(TransactionInput::deserialize(raw)?);
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
                                 }
-                                Ok((inputs_arr, inputs_encoding))
-                            })().map_err(|e| e.annotate("inputs"))?;
-                            inputs = Some(tmp_inputs);
-                            inputs_encoding = tmp_inputs_encoding;
-                            inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        },
-                        (1, key_enc) =>  {
-                            if outputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_outputs, tmp_outputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut outputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let outputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (outputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    outputs_arr.push(TransactionOutput::deserialize(raw)?);
+                                let (tmp_inputs, tmp_inputs_encoding) =
+                                    deserialize_array(raw, TransactionInput::deserialize)
+                                        .map_err(|e| e.annotate("inputs"))?;
+                                inputs = Some(tmp_inputs);
+                                inputs_encoding = tmp_inputs_encoding;
+                                inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if outputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
                                 }
-                                Ok((outputs_arr, outputs_encoding))
-                            })().map_err(|e| e.annotate("outputs"))?;
-                            outputs = Some(tmp_outputs);
-                            outputs_encoding = tmp_outputs_encoding;
-                            outputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        },
-                        (2, key_enc) =>  {
-                            if fee.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_fee, tmp_fee_encoding) = raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into).map_err(|e: DeserializeError| e.annotate("fee"))?;
-                            fee = Some(tmp_fee);
-                            fee_encoding = tmp_fee_encoding;
-                            fee_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        },
-                        (3, key_enc) =>  {
-                            if ttl.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_ttl, tmp_ttl_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("ttl"))?;
-                            ttl = Some(tmp_ttl);
-                            ttl_encoding = tmp_ttl_encoding;
-                            ttl_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        },
-                        (4, key_enc) =>  {
-                            if certs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_certs, tmp_certs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut certs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let certs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (certs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    certs_arr.push(Certificate::deserialize(raw)?);
+                                let (tmp_outputs, tmp_outputs_encoding) =
+                                    deserialize_array(raw, TransactionOutput::deserialize)
+                                        .map_err(|e| e.annotate("outputs"))?;
+                                outputs = Some(tmp_outputs);
+                                outputs_encoding = tmp_outputs_encoding;
+                                outputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, _read_len| {
+                                if fee.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
                                 }
-                                Ok((certs_arr, certs_encoding))
-                            })().map_err(|e| e.annotate("certs"))?;
-                            certs = Some(tmp_certs);
-                            certs_encoding = tmp_certs_encoding;
-                            certs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        },
-                        (5, key_enc) =>  {
-                            if withdrawals.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_withdrawals, tmp_withdrawals_encoding, tmp_withdrawals_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut withdrawals_table = OrderedHashMap::new();
-                                let withdrawals_len = raw.map_sz()?;
-                                let withdrawals_encoding = withdrawals_len.into();
-                                let mut withdrawals_value_encodings = BTreeMap::new();
-                                while match withdrawals_len { cbor_event::LenSz::Len(n, _) => (withdrawals_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let withdrawals_key = RewardAccount::deserialize(raw)?;
-                                    let (withdrawals_value, withdrawals_value_encoding) = raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc)))?;
-                                    if withdrawals_table.insert(withdrawals_key.clone(), withdrawals_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
-                                    }
-                                    withdrawals_value_encodings.insert(withdrawals_key, withdrawals_value_encoding);
+                                let (tmp_fee, tmp_fee_encoding) = raw
+                                    .unsigned_integer_sz()
+                                    .map(|(x, enc)| (x, Some(enc)))
+                                    .map_err(Into::<DeserializeError>::into)
+                                    .map_err(|e: DeserializeError| e.annotate("fee"))?;
+                                fee = Some(tmp_fee);
+                                fee_encoding = tmp_fee_encoding;
+                                fee_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if ttl.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
                                 }
-                                Ok((withdrawals_table, withdrawals_encoding, withdrawals_value_encodings))
-                            })().map_err(|e| e.annotate("withdrawals"))?;
-                            withdrawals = Some(tmp_withdrawals);
-                            withdrawals_encoding = tmp_withdrawals_encoding;
-                            withdrawals_value_encodings = tmp_withdrawals_value_encodings;
-                            withdrawals_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        },
-                        (7, key_enc) =>  {
-                            if auxiliary_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| AuxiliaryDataHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))
-                            })().map_err(|e| e.annotate("auxiliary_data_hash"))?;
-                            auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
-                            auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
-                            auxiliary_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        },
-                        (8, key_enc) =>  {
-                            if validity_interval_start.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_validity_interval_start, tmp_validity_interval_start_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("validity_interval_start"))?;
-                            validity_interval_start = Some(tmp_validity_interval_start);
-                            validity_interval_start_encoding = tmp_validity_interval_start_encoding;
-                            validity_interval_start_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        },
-                        (9, key_enc) =>  {
-                            if mint.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let (tmp_mint, tmp_mint_encoding, tmp_mint_key_encodings, tmp_mint_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut mint_table = OrderedHashMap::new();
-                                let mint_len = raw.map_sz()?;
-                                let mint_encoding = mint_len.into();
-                                let mut mint_key_encodings = BTreeMap::new();
-                                let mut mint_value_encodings = BTreeMap::new();
-                                while match mint_len { cbor_event::LenSz::Len(n, _) => (mint_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let (mint_key, mint_key_encoding) = raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| ScriptHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))?;
-                                    let mut mint_value_table = OrderedHashMap::new();
-                                    let mint_value_len = raw.map_sz()?;
-                                    let mint_value_encoding = mint_value_len.into();
-                                    let mut mint_value_value_encodings = BTreeMap::new();
-                                    while match mint_value_len { cbor_event::LenSz::Len(n, _) => (mint_value_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
+                                let (tmp_ttl, tmp_ttl_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("ttl"))?;
+                                ttl = Some(tmp_ttl);
+                                ttl_encoding = tmp_ttl_encoding;
+                                ttl_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if certs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_certs, tmp_certs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Certificate::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("certs"))?;
+                                certs = Some(tmp_certs);
+                                certs_encoding = tmp_certs_encoding;
+                                certs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if withdrawals.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_withdrawals,
+                                    tmp_withdrawals_encoding,
+                                    tmp_withdrawals_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut withdrawals_table = OrderedHashMap::new();
+                                    let withdrawals_len = raw.map_sz()?;
+                                    let withdrawals_encoding = withdrawals_len.into();
+                                    let mut withdrawals_value_encodings = BTreeMap::new();
+                                    while match withdrawals_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (withdrawals_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
                                         if raw.cbor_type()? == cbor_event::Type::Special {
                                             assert_eq!(raw.special()?, cbor_event::Special::Break);
                                             break;
                                         }
-                                        let mint_value_key = AssetName::deserialize(raw)?;
-                                        let (mint_value_value, mint_value_value_encoding) = match raw.cbor_type()? {
-                                            cbor_event::Type::UnsignedInteger => {
-                                                let (x, enc) = raw.unsigned_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                            _ => {
-                                                let (x, enc) = raw.negative_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                        };
-                                        if mint_value_table.insert(mint_value_key.clone(), mint_value_value).is_some() {
-                                            return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        let withdrawals_key = RewardAccount::deserialize(raw)?;
+                                        let (withdrawals_value, withdrawals_value_encoding) = raw
+                                            .unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))?;
+                                        if withdrawals_table
+                                            .insert(withdrawals_key.clone(), withdrawals_value)
+                                            .is_some()
+                                        {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
                                         }
-                                        mint_value_value_encodings.insert(mint_value_key, mint_value_value_encoding);
+                                        withdrawals_value_encodings
+                                            .insert(withdrawals_key, withdrawals_value_encoding);
                                     }
-                                    let (mint_value, mint_value_encoding, mint_value_value_encodings) = (mint_value_table, mint_value_encoding, mint_value_value_encodings);
-                                    if mint_table.insert(mint_key, mint_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
-                                    }
-                                    mint_key_encodings.insert(mint_key, mint_key_encoding);
-                                    mint_value_encodings.insert(mint_key, (mint_value_encoding, mint_value_value_encodings));
+                                    Ok((
+                                        withdrawals_table,
+                                        withdrawals_encoding,
+                                        withdrawals_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("withdrawals"))?;
+                                withdrawals = Some(tmp_withdrawals);
+                                withdrawals_encoding = tmp_withdrawals_encoding;
+                                withdrawals_value_encodings = tmp_withdrawals_value_encodings;
+                                withdrawals_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if auxiliary_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
                                 }
-                                Ok((mint_table, mint_encoding, mint_key_encodings, mint_value_encodings))
-                            })().map_err(|e| e.annotate("mint"))?;
-                            mint = Some(tmp_mint);
-                            mint_encoding = tmp_mint_encoding;
-                            mint_key_encodings = tmp_mint_key_encodings;
-                            mint_value_encodings = tmp_mint_value_encodings;
-                            mint_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        },
-                        (11, key_enc) =>  {
-                            if script_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(11)).into());
-                            }
-                            let (tmp_script_data_hash, tmp_script_data_hash_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| ScriptDataHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))
-                            })().map_err(|e| e.annotate("script_data_hash"))?;
-                            script_data_hash = Some(tmp_script_data_hash);
-                            script_data_hash_encoding = tmp_script_data_hash_encoding;
-                            script_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        },
-                        (13, key_enc) =>  {
-                            if collateral_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(13)).into());
-                            }
-                            let (tmp_collateral_inputs, tmp_collateral_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut collateral_inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let collateral_inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (collateral_inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    collateral_inputs_arr.push(TransactionInput::deserialize(raw)?);
+                                let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                AuxiliaryDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("auxiliary_data_hash"))?;
+                                auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
+                                auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
+                                auxiliary_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if validity_interval_start.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
                                 }
-                                Ok((collateral_inputs_arr, collateral_inputs_encoding))
-                            })().map_err(|e| e.annotate("collateral_inputs"))?;
-                            collateral_inputs = Some(tmp_collateral_inputs);
-                            collateral_inputs_encoding = tmp_collateral_inputs_encoding;
-                            collateral_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(10);
-                        },
-                        (14, key_enc) =>  {
-                            if required_signers.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(14)).into());
-                            }
-                            let (tmp_required_signers, tmp_required_signers_encoding, tmp_required_signers_elem_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut required_signers_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let required_signers_encoding = len.into();
-                                let mut required_signers_elem_encodings = Vec::new();
-                                while match len { cbor_event::LenSz::Len(n, _) => (required_signers_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let (required_signers_elem, required_signers_elem_encoding) = raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| Ed25519KeyHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))?;
-                                    required_signers_arr.push(required_signers_elem);
-                                    required_signers_elem_encodings.push(required_signers_elem_encoding);
+                                let (
+                                    tmp_validity_interval_start,
+                                    tmp_validity_interval_start_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                        .map_err(Into::<DeserializeError>::into)
+                                })()
+                                .map_err(|e| e.annotate("validity_interval_start"))?;
+                                validity_interval_start = Some(tmp_validity_interval_start);
+                                validity_interval_start_encoding =
+                                    tmp_validity_interval_start_encoding;
+                                validity_interval_start_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if mint.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
                                 }
-                                Ok((required_signers_arr, required_signers_encoding, required_signers_elem_encodings))
-                            })().map_err(|e| e.annotate("required_signers"))?;
-                            required_signers = Some(tmp_required_signers);
-                            required_signers_encoding = tmp_required_signers_encoding;
-                            required_signers_elem_encodings = tmp_required_signers_elem_encodings;
-                            required_signers_key_encoding = Some(key_enc);
-                            orig_deser_order.push(11);
-                        },
-                        (15, key_enc) =>  {
-                            if network_id.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(15)).into());
-                            }
-                            let tmp_network_id = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                NetworkId::deserialize(raw)
-                            })().map_err(|e| e.annotate("network_id"))?;
-                            network_id = Some(tmp_network_id);
-                            network_id_key_encoding = Some(key_enc);
-                            orig_deser_order.push(12);
-                        },
-                        (16, key_enc) =>  {
-                            if collateral_return.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(16)).into());
-                            }
-                            let tmp_collateral_return = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                TransactionOutput::deserialize(raw)
-                            })().map_err(|e| e.annotate("collateral_return"))?;
-                            collateral_return = Some(tmp_collateral_return);
-                            collateral_return_key_encoding = Some(key_enc);
-                            orig_deser_order.push(13);
-                        },
-                        (17, key_enc) =>  {
-                            if total_collateral.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(17)).into());
-                            }
-                            let (tmp_total_collateral, tmp_total_collateral_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("total_collateral"))?;
-                            total_collateral = Some(tmp_total_collateral);
-                            total_collateral_encoding = tmp_total_collateral_encoding;
-                            total_collateral_key_encoding = Some(key_enc);
-                            orig_deser_order.push(14);
-                        },
-                        (18, key_enc) =>  {
-                            if reference_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(18)).into());
-                            }
-                            let (tmp_reference_inputs, tmp_reference_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut reference_inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let reference_inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (reference_inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
+                                let (
+                                    tmp_mint,
+                                    tmp_mint_encoding,
+                                    tmp_mint_key_encodings,
+                                    tmp_mint_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut mint_table = OrderedHashMap::new();
+                                    let mint_len = raw.map_sz()?;
+                                    let mint_encoding = mint_len.into();
+                                    let mut mint_key_encodings = BTreeMap::new();
+                                    let mut mint_value_encodings = BTreeMap::new();
+                                    while match mint_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (mint_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
+                                        if raw.cbor_type()? == cbor_event::Type::Special {
+                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
+                                            break;
+                                        }
+                                        let (mint_key, mint_key_encoding) = raw
+                                            .bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                ScriptHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })?;
+                                        let mut mint_value_table = OrderedHashMap::new();
+                                        let mint_value_len = raw.map_sz()?;
+                                        let mint_value_encoding = mint_value_len.into();
+                                        let mut mint_value_value_encodings = BTreeMap::new();
+                                        while match mint_value_len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (mint_value_table.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let mint_value_key = AssetName::deserialize(raw)?;
+                                            let (mint_value_value, mint_value_value_encoding) =
+                                                match raw.cbor_type()? {
+                                                    cbor_event::Type::UnsignedInteger => {
+                                                        let (x, enc) = raw.unsigned_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                    _ => {
+                                                        let (x, enc) = raw.negative_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                };
+                                            if mint_value_table
+                                                .insert(mint_value_key.clone(), mint_value_value)
+                                                .is_some()
+                                            {
+                                                return Err(DeserializeFailure::DuplicateKey(
+                                                    Key::Str(String::from(
+                                                        "some complicated/unsupported type",
+                                                    )),
+                                                )
+                                                .into());
+                                            }
+                                            mint_value_value_encodings
+                                                .insert(mint_value_key, mint_value_value_encoding);
+                                        }
+                                        let (
+                                            mint_value,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        ) = (
+                                            mint_value_table,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        );
+                                        if mint_table.insert(mint_key, mint_value).is_some() {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
+                                        }
+                                        mint_key_encodings.insert(mint_key, mint_key_encoding);
+                                        mint_value_encodings.insert(
+                                            mint_key,
+                                            (mint_value_encoding, mint_value_value_encodings),
+                                        );
                                     }
-                                    reference_inputs_arr.push(TransactionInput::deserialize(raw)?);
+                                    Ok((
+                                        mint_table,
+                                        mint_encoding,
+                                        mint_key_encodings,
+                                        mint_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("mint"))?;
+                                mint = Some(tmp_mint);
+                                mint_encoding = tmp_mint_encoding;
+                                mint_key_encodings = tmp_mint_key_encodings;
+                                mint_value_encodings = tmp_mint_value_encodings;
+                                mint_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (11, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            11,
+                            key_enc,
+                            |raw, read_len| {
+                                if script_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(11)).into()
+                                    );
                                 }
-                                Ok((reference_inputs_arr, reference_inputs_encoding))
-                            })().map_err(|e| e.annotate("reference_inputs"))?;
-                            reference_inputs = Some(tmp_reference_inputs);
-                            reference_inputs_encoding = tmp_reference_inputs_encoding;
-                            reference_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(15);
-                        },
-                        (19, key_enc) =>  {
-                            if voting_procedures.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(19)).into());
-                            }
-                            let (tmp_voting_procedures, tmp_voting_procedures_encoding, tmp_voting_procedures_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut voting_procedures_table = OrderedHashMap::new();
-                                let voting_procedures_len = raw.map_sz()?;
-                                let voting_procedures_encoding = voting_procedures_len.into();
-                                let mut voting_procedures_value_encodings = BTreeMap::new();
-                                while match voting_procedures_len { cbor_event::LenSz::Len(n, _) => (voting_procedures_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let voting_procedures_key = Voter::deserialize(raw)?;
-                                    let mut voting_procedures_value_table = OrderedHashMap::new();
-                                    let voting_procedures_value_len = raw.map_sz()?;
-                                    let voting_procedures_value_encoding = voting_procedures_value_len.into();
-                                    while match voting_procedures_value_len { cbor_event::LenSz::Len(n, _) => (voting_procedures_value_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
+                                let (tmp_script_data_hash, tmp_script_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                ScriptDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("script_data_hash"))?;
+                                script_data_hash = Some(tmp_script_data_hash);
+                                script_data_hash_encoding = tmp_script_data_hash_encoding;
+                                script_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (13, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            13,
+                            key_enc,
+                            |raw, read_len| {
+                                if collateral_inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(13)).into()
+                                    );
+                                }
+                                let (tmp_collateral_inputs, tmp_collateral_inputs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, TransactionInput::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("collateral_inputs"))?;
+                                collateral_inputs = Some(tmp_collateral_inputs);
+                                collateral_inputs_encoding = tmp_collateral_inputs_encoding;
+                                collateral_inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(10);
+                                Ok(())
+                            },
+                        )?,
+                        (14, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            14,
+                            key_enc,
+                            |raw, read_len| {
+                                if required_signers.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(14)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_required_signers,
+                                    tmp_required_signers_encoding,
+                                    tmp_required_signers_elem_encodings,
+                                ) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        let mut required_signers_arr = Vec::new();
+                                        let len = raw.array_sz()?;
+                                        let required_signers_encoding = len.into();
+                                        let mut required_signers_elem_encodings = Vec::new();
+                                        while match len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (required_signers_arr.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let (
+                                                required_signers_elem,
+                                                required_signers_elem_encoding,
+                                            ) = raw
+                                                .bytes_sz()
+                                                .map_err(Into::<DeserializeError>::into)
+                                                .and_then(|(bytes, enc)| {
+                                                    Ed25519KeyHash::from_raw_bytes(&bytes)
+                                                        .map(|bytes| {
+                                                            (bytes, StringEncoding::from(enc))
+                                                        })
+                                                        .map_err(|e| {
+                                                            DeserializeFailure::InvalidStructure(
+                                                                Box::new(e),
+                                                            )
+                                                            .into()
+                                                        })
+                                                })?;
+                                            required_signers_arr.push(required_signers_elem);
+                                            required_signers_elem_encodings
+                                                .push(required_signers_elem_encoding);
+                                        }
+                                        Ok((
+                                            required_signers_arr,
+                                            required_signers_encoding,
+                                            required_signers_elem_encodings,
+                                        ))
+                                    })()
+                                    .map_err(|e| e.annotate("required_signers"))?;
+                                required_signers = Some(tmp_required_signers);
+                                required_signers_encoding = tmp_required_signers_encoding;
+                                required_signers_elem_encodings =
+                                    tmp_required_signers_elem_encodings;
+                                required_signers_key_encoding = Some(key_enc);
+                                orig_deser_order.push(11);
+                                Ok(())
+                            },
+                        )?,
+                        (15, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            15,
+                            key_enc,
+                            |raw, read_len| {
+                                if network_id.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(15)).into()
+                                    );
+                                }
+                                let tmp_network_id = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    NetworkId::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("network_id"))?;
+                                network_id = Some(tmp_network_id);
+                                network_id_key_encoding = Some(key_enc);
+                                orig_deser_order.push(12);
+                                Ok(())
+                            },
+                        )?,
+                        (16, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            16,
+                            key_enc,
+                            |raw, read_len| {
+                                if collateral_return.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(16)).into()
+                                    );
+                                }
+                                let tmp_collateral_return = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    TransactionOutput::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("collateral_return"))?;
+                                collateral_return = Some(tmp_collateral_return);
+                                collateral_return_key_encoding = Some(key_enc);
+                                orig_deser_order.push(13);
+                                Ok(())
+                            },
+                        )?,
+                        (17, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            17,
+                            key_enc,
+                            |raw, read_len| {
+                                if total_collateral.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(17)).into()
+                                    );
+                                }
+                                let (tmp_total_collateral, tmp_total_collateral_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("total_collateral"))?;
+                                total_collateral = Some(tmp_total_collateral);
+                                total_collateral_encoding = tmp_total_collateral_encoding;
+                                total_collateral_key_encoding = Some(key_enc);
+                                orig_deser_order.push(14);
+                                Ok(())
+                            },
+                        )?,
+                        (18, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            18,
+                            key_enc,
+                            |raw, read_len| {
+                                if reference_inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(18)).into()
+                                    );
+                                }
+                                let (tmp_reference_inputs, tmp_reference_inputs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, TransactionInput::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("reference_inputs"))?;
+                                reference_inputs = Some(tmp_reference_inputs);
+                                reference_inputs_encoding = tmp_reference_inputs_encoding;
+                                reference_inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(15);
+                                Ok(())
+                            },
+                        )?,
+                        (19, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            19,
+                            key_enc,
+                            |raw, read_len| {
+                                if voting_procedures.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(19)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_voting_procedures,
+                                    tmp_voting_procedures_encoding,
+                                    tmp_voting_procedures_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut voting_procedures_table = OrderedHashMap::new();
+                                    let voting_procedures_len = raw.map_sz()?;
+                                    let voting_procedures_encoding = voting_procedures_len.into();
+                                    let mut voting_procedures_value_encodings = BTreeMap::new();
+                                    while match voting_procedures_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (voting_procedures_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
                                         if raw.cbor_type()? == cbor_event::Type::Special {
                                             assert_eq!(raw.special()?, cbor_event::Special::Break);
                                             break;
                                         }
-                                        let voting_procedures_value_key = GovActionId::deserialize(raw)?;
-                                        let voting_procedures_value_value = VotingProcedure::deserialize(raw)?;
-                                        if voting_procedures_value_table.insert(voting_procedures_value_key.clone(), voting_procedures_value_value).is_some() {
-                                            return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        let voting_procedures_key = Voter::deserialize(raw)?;
+                                        let mut voting_procedures_value_table =
+                                            OrderedHashMap::new();
+                                        let voting_procedures_value_len = raw.map_sz()?;
+                                        let voting_procedures_value_encoding =
+                                            voting_procedures_value_len.into();
+                                        while match voting_procedures_value_len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (voting_procedures_value_table.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let voting_procedures_value_key =
+                                                GovActionId::deserialize(raw)?;
+                                            let voting_procedures_value_value =
+                                                VotingProcedure::deserialize(raw)?;
+                                            if voting_procedures_value_table
+                                                .insert(
+                                                    voting_procedures_value_key.clone(),
+                                                    voting_procedures_value_value,
+                                                )
+                                                .is_some()
+                                            {
+                                                return Err(DeserializeFailure::DuplicateKey(
+                                                    Key::Str(String::from(
+                                                        "some complicated/unsupported type",
+                                                    )),
+                                                )
+                                                .into());
+                                            }
                                         }
+                                        let (
+                                            voting_procedures_value,
+                                            voting_procedures_value_encoding,
+                                        ) = (
+                                            voting_procedures_value_table,
+                                            voting_procedures_value_encoding,
+                                        );
+                                        if voting_procedures_table
+                                            .insert(
+                                                voting_procedures_key.clone(),
+                                                voting_procedures_value,
+                                            )
+                                            .is_some()
+                                        {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
+                                        }
+                                        voting_procedures_value_encodings.insert(
+                                            voting_procedures_key,
+                                            voting_procedures_value_encoding,
+                                        );
                                     }
-                                    let (voting_procedures_value, voting_procedures_value_encoding) = (voting_procedures_value_table, voting_procedures_value_encoding);
-                                    if voting_procedures_table.insert(voting_procedures_key.clone(), voting_procedures_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
-                                    }
-                                    voting_procedures_value_encodings.insert(voting_procedures_key, voting_procedures_value_encoding);
+                                    Ok((
+                                        voting_procedures_table,
+                                        voting_procedures_encoding,
+                                        voting_procedures_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("voting_procedures"))?;
+                                voting_procedures = Some(tmp_voting_procedures);
+                                voting_procedures_encoding = tmp_voting_procedures_encoding;
+                                voting_procedures_value_encodings =
+                                    tmp_voting_procedures_value_encodings;
+                                voting_procedures_key_encoding = Some(key_enc);
+                                orig_deser_order.push(16);
+                                Ok(())
+                            },
+                        )?,
+                        (20, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            20,
+                            key_enc,
+                            |raw, read_len| {
+                                if proposal_procedures.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(20)).into()
+                                    );
                                 }
-                                Ok((voting_procedures_table, voting_procedures_encoding, voting_procedures_value_encodings))
-                            })().map_err(|e| e.annotate("voting_procedures"))?;
-                            voting_procedures = Some(tmp_voting_procedures);
-                            voting_procedures_encoding = tmp_voting_procedures_encoding;
-                            voting_procedures_value_encodings = tmp_voting_procedures_value_encodings;
-                            voting_procedures_key_encoding = Some(key_enc);
-                            orig_deser_order.push(16);
-                        },
-                        (20, key_enc) =>  {
-                            if proposal_procedures.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(20)).into());
-                            }
-                            let (tmp_proposal_procedures, tmp_proposal_procedures_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut proposal_procedures_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let proposal_procedures_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (proposal_procedures_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    proposal_procedures_arr.push(ProposalProcedure::deserialize(raw)?);
+                                let (tmp_proposal_procedures, tmp_proposal_procedures_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, ProposalProcedure::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("proposal_procedures"))?;
+                                proposal_procedures = Some(tmp_proposal_procedures);
+                                proposal_procedures_encoding = tmp_proposal_procedures_encoding;
+                                proposal_procedures_key_encoding = Some(key_enc);
+                                orig_deser_order.push(17);
+                                Ok(())
+                            },
+                        )?,
+                        (21, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            21,
+                            key_enc,
+                            |raw, read_len| {
+                                if current_treasury_value.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(21)).into()
+                                    );
                                 }
-                                Ok((proposal_procedures_arr, proposal_procedures_encoding))
-                            })().map_err(|e| e.annotate("proposal_procedures"))?;
-                            proposal_procedures = Some(tmp_proposal_procedures);
-                            proposal_procedures_encoding = tmp_proposal_procedures_encoding;
-                            proposal_procedures_key_encoding = Some(key_enc);
-                            orig_deser_order.push(17);
-                        },
-                        (21, key_enc) =>  {
-                            if current_treasury_value.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(21)).into());
-                            }
-                            let (tmp_current_treasury_value, tmp_current_treasury_value_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("current_treasury_value"))?;
-                            current_treasury_value = Some(tmp_current_treasury_value);
-                            current_treasury_value_encoding = tmp_current_treasury_value_encoding;
-                            current_treasury_value_key_encoding = Some(key_enc);
-                            orig_deser_order.push(18);
-                        },
-                        (22, key_enc) =>  {
-                            if donation.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(22)).into());
-                            }
-                            let (tmp_donation, tmp_donation_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc))).map_err(Into::<DeserializeError>::into)
-                            })().map_err(|e| e.annotate("donation"))?;
-                            donation = Some(tmp_donation);
-                            donation_encoding = tmp_donation_encoding;
-                            donation_key_encoding = Some(key_enc);
-                            orig_deser_order.push(19);
-                        },
-                        (unknown_key, _enc) => return Err(DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()),
+                                let (
+                                    tmp_current_treasury_value,
+                                    tmp_current_treasury_value_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                        .map_err(Into::<DeserializeError>::into)
+                                })()
+                                .map_err(|e| e.annotate("current_treasury_value"))?;
+                                current_treasury_value = Some(tmp_current_treasury_value);
+                                current_treasury_value_encoding =
+                                    tmp_current_treasury_value_encoding;
+                                current_treasury_value_key_encoding = Some(key_enc);
+                                orig_deser_order.push(18);
+                                Ok(())
+                            },
+                        )?,
+                        (22, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            22,
+                            key_enc,
+                            |raw, read_len| {
+                                if donation.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(22)).into()
+                                    );
+                                }
+                                let (tmp_donation, tmp_donation_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                            .map_err(Into::<DeserializeError>::into)
+                                    })()
+                                    .map_err(|e| e.annotate("donation"))?;
+                                donation = Some(tmp_donation);
+                                donation_encoding = tmp_donation_encoding;
+                                donation_key_encoding = Some(key_enc);
+                                orig_deser_order.push(19);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
-                    cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),
+                    cbor_event::Type::Text => {
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
+                    }
                     cbor_event::Type::Special => match len {
-                        cbor_event::LenSz::Len(_, _) => return Err(DeserializeFailure::BreakInDefiniteLen.into()),
+                        cbor_event::LenSz::Len(_, _) => {
+                            return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                        }
                         cbor_event::LenSz::Indefinite => match raw.special()? {
                             cbor_event::Special::Break => break,
                             _ => return Err(DeserializeFailure::EndingBreakMissing.into()),
                         },
                     },
-                    other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),
+                    other_type => {
+                        return Err(DeserializeFailure::UnexpectedKeyType(other_type).into())
+                    }
                 }
                 read += 1;
             }
@@ -2997,9 +3520,11 @@ impl Deserialize for TransactionBody {
                     current_treasury_value_encoding,
                     donation_key_encoding,
                     donation_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
-        })().map_err(|e| e.annotate("TransactionBody"))
+        })()
+        .map_err(|e| e.annotate("TransactionBody"))
     }
 }
 
@@ -3131,6 +3656,26 @@ impl Serialize for TransactionWitnessSet {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl TransactionWitnessSet {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -3162,7 +3707,7 @@ impl Serialize for TransactionWitnessSet {
                     } + match &self.plutus_v3_scripts {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -3200,7 +3745,9 @@ impl Serialize for TransactionWitnessSet {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6, 7]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.vkeywitnesses {
@@ -3445,6 +3992,8 @@ impl Serialize for TransactionWitnessSet {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -3460,6 +4009,7 @@ impl Deserialize for TransactionWitnessSet {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut vkeywitnesses_encoding = LenEncoding::default();
             let mut vkeywitnesses_key_encoding = None;
             let mut vkeywitnesses = None;
@@ -3491,258 +4041,227 @@ impl Deserialize for TransactionWitnessSet {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if vkeywitnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut vkeywitnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let vkeywitnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (vkeywitnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        vkeywitnesses_arr.push(Vkeywitness::deserialize(raw)?);
-                                    }
-                                    Ok((vkeywitnesses_arr, vkeywitnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("vkeywitnesses"))?;
-                            vkeywitnesses = Some(tmp_vkeywitnesses);
-                            vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
-                            vkeywitnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if native_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_native_scripts, tmp_native_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut native_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let native_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (native_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        native_scripts_arr.push(NativeScript::deserialize(raw)?);
-                                    }
-                                    Ok((native_scripts_arr, native_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("native_scripts"))?;
-                            native_scripts = Some(tmp_native_scripts);
-                            native_scripts_encoding = tmp_native_scripts_encoding;
-                            native_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if bootstrap_witnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut bootstrap_witnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let bootstrap_witnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (bootstrap_witnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        bootstrap_witnesses_arr
-                                            .push(BootstrapWitness::deserialize(raw)?);
-                                    }
-                                    Ok((bootstrap_witnesses_arr, bootstrap_witnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("bootstrap_witnesses"))?;
-                            bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
-                            bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
-                            bootstrap_witnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if plutus_v1_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_scripts_arr
-                                            .push(PlutusV1Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_scripts_arr, plutus_v1_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1_scripts"))?;
-                            plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
-                            plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
-                            plutus_v1_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (4, key_enc) => {
-                            if plutus_datums.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_plutus_datums, tmp_plutus_datums_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_datums_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_datums_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_datums_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_datums_arr.push(PlutusData::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_datums_arr, plutus_datums_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_datums"))?;
-                            plutus_datums = Some(tmp_plutus_datums);
-                            plutus_datums_encoding = tmp_plutus_datums_encoding;
-                            plutus_datums_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (5, key_enc) => {
-                            if redeemers.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_redeemers, tmp_redeemers_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut redeemers_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let redeemers_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (redeemers_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        redeemers_arr.push(Redeemer::deserialize(raw)?);
-                                    }
-                                    Ok((redeemers_arr, redeemers_encoding))
-                                })()
-                                .map_err(|e| e.annotate("redeemers"))?;
-                            redeemers = Some(tmp_redeemers);
-                            redeemers_encoding = tmp_redeemers_encoding;
-                            redeemers_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        }
-                        (6, key_enc) => {
-                            if plutus_v2_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v2_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_scripts_arr
-                                            .push(PlutusV2Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_scripts_arr, plutus_v2_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v2_scripts"))?;
-                            plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
-                            plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
-                            plutus_v2_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        }
-                        (7, key_enc) => {
-                            if plutus_v3_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_plutus_v3_scripts, tmp_plutus_v3_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v3_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v3_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v3_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v3_scripts_arr
-                                            .push(PlutusV3Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v3_scripts_arr, plutus_v3_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v3_scripts"))?;
-                            plutus_v3_scripts = Some(tmp_plutus_v3_scripts);
-                            plutus_v3_scripts_encoding = tmp_plutus_v3_scripts_encoding;
-                            plutus_v3_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if vkeywitnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Vkeywitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("vkeywitnesses"))?;
+                                vkeywitnesses = Some(tmp_vkeywitnesses);
+                                vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
+                                vkeywitnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if native_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_native_scripts, tmp_native_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, NativeScript::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("native_scripts"))?;
+                                native_scripts = Some(tmp_native_scripts);
+                                native_scripts_encoding = tmp_native_scripts_encoding;
+                                native_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if bootstrap_witnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, BootstrapWitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("bootstrap_witnesses"))?;
+                                bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
+                                bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
+                                bootstrap_witnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV1Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1_scripts"))?;
+                                plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
+                                plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
+                                plutus_v1_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_datums.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_plutus_datums, tmp_plutus_datums_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusData::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_datums"))?;
+                                plutus_datums = Some(tmp_plutus_datums);
+                                plutus_datums_encoding = tmp_plutus_datums_encoding;
+                                plutus_datums_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if redeemers.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (tmp_redeemers, tmp_redeemers_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Redeemer::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("redeemers"))?;
+                                redeemers = Some(tmp_redeemers);
+                                redeemers_encoding = tmp_redeemers_encoding;
+                                redeemers_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV2Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2_scripts"))?;
+                                plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
+                                plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
+                                plutus_v2_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v3_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v3_scripts, tmp_plutus_v3_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV3Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v3_scripts"))?;
+                                plutus_v3_scripts = Some(tmp_plutus_v3_scripts);
+                                plutus_v3_scripts_encoding = tmp_plutus_v3_scripts_encoding;
+                                plutus_v3_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -3788,6 +4307,7 @@ impl Deserialize for TransactionWitnessSet {
                     plutus_v2_scripts_encoding,
                     plutus_v3_scripts_key_encoding,
                     plutus_v3_scripts_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -2,22 +2,161 @@ use std::collections::BTreeSet;
 
 use crate::{
     address::Address,
-    plutus::Language,
+    assets::Mint,
+    auxdata::AuxiliaryData,
+    certs::Certificate,
+    crypto::{BootstrapWitness, Vkeywitness},
+    governance::{utils::voting_procedures_field_names, ProposalProcedure},
+    plutus::{ExUnits, Language, Redeemer},
     transaction::{DatumOption, ScriptRef, TransactionOutput},
-    Value,
+    utils::withdrawals_field_names,
+    Slot, Value,
 };
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_crypto::{DatumHash, Ed25519KeyHash, TransactionHash};
 
 use super::{
-    AlonzoFormatTxOut, ConwayFormatTxOut, NativeScript, TransactionBody, TransactionWitnessSet,
+    AlonzoFormatTxOut, ConwayFormatTxOut, NativeScript, ScriptAll, ScriptAny, ScriptInvalidBefore,
+    ScriptInvalidHereafter, ScriptNOfK, ScriptPubkey, Transaction, TransactionBody,
+    TransactionInput, TransactionWitnessSet,
 };
 
+cml_core::impl_unknown_fields!(ConwayFormatTxOut, TransactionBody, TransactionWitnessSet);
+
 impl TransactionBody {
     pub fn hash(&self) -> TransactionHash {
         crate::crypto::hash::hash_transaction(self)
     }
 }
 
+cml_core::impl_cbor_field_names!(Transaction [
+    body: TransactionBody::cbor_field_names(),
+    witness_set: TransactionWitnessSet::cbor_field_names(),
+    is_valid,
+    auxiliary_data: AuxiliaryData::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(TransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(TransactionOutput::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(Certificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    7 => auxiliary_data_hash,
+    8 => validity_interval_start,
+    9 => mint: Mint::cbor_field_names(),
+    11 => script_data_hash,
+    13 => collateral_inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    14 => required_signers,
+    15 => network_id,
+    16 => collateral_return: TransactionOutput::cbor_field_names(),
+    17 => total_collateral,
+    18 => reference_inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    19 => voting_procedures: voting_procedures_field_names(),
+    20 => proposal_procedures: FieldNames::elements(ProposalProcedure::cbor_field_names()),
+    21 => current_treasury_value,
+    22 => donation,
+});
+cml_core::impl_cbor_field_names!(TransactionInput [transaction_id, index]);
+
+impl CBORFieldNames for TransactionOutput {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            AlonzoFormatTxOut::cbor_field_names(),
+            ConwayFormatTxOut::cbor_field_names(),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(AlonzoFormatTxOut [address, amount: Value::cbor_field_names(), datum_hash]);
+cml_core::impl_cbor_field_names!(ConwayFormatTxOut {
+    0 => address,
+    1 => amount: Value::cbor_field_names(),
+    2 => datum_option: DatumOption::cbor_field_names(),
+    3 => script_reference,
+});
+
+impl CBORFieldNames for DatumOption {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (
+                0,
+                FieldNames::Array(vec![
+                    ("", FieldNames::None),
+                    ("datum_hash", FieldNames::None),
+                ]),
+            ),
+            (
+                1,
+                FieldNames::Array(vec![("", FieldNames::None), ("datum", FieldNames::None)]),
+            ),
+        ])
+    }
+}
+
+impl CBORFieldNames for NativeScript {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, ScriptPubkey::cbor_field_names()),
+            (1, ScriptAll::cbor_field_names()),
+            (2, ScriptAny::cbor_field_names()),
+            (3, ScriptNOfK::cbor_field_names()),
+            (4, ScriptInvalidBefore::cbor_field_names()),
+            (5, ScriptInvalidHereafter::cbor_field_names()),
+        ])
+    }
+}
+
+// native scripts nest so their names are only created for the scripts present
+cml_core::impl_cbor_field_names!(ScriptPubkey [0, ed25519_key_hash]);
+cml_core::impl_cbor_field_names!(ScriptAll [
+    1,
+    native_scripts: FieldNames::elements(FieldNames::Lazy(NativeScript::cbor_field_names)),
+]);
+cml_core::impl_cbor_field_names!(ScriptAny [
+    2,
+    native_scripts: FieldNames::elements(FieldNames::Lazy(NativeScript::cbor_field_names)),
+]);
+cml_core::impl_cbor_field_names!(ScriptNOfK [
+    3,
+    n,
+    native_scripts: FieldNames::elements(FieldNames::Lazy(NativeScript::cbor_field_names)),
+]);
+cml_core::impl_cbor_field_names!(ScriptInvalidBefore [4, before]);
+cml_core::impl_cbor_field_names!(ScriptInvalidHereafter [5, after]);
+
+cml_core::impl_cbor_field_names!(TransactionWitnessSet {
+    0 => vkeywitnesses: FieldNames::elements(Vkeywitness::cbor_field_names()),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => bootstrap_witnesses: FieldNames::elements(BootstrapWitness::cbor_field_names()),
+    3 => plutus_v1_scripts,
+    4 => plutus_datums,
+    5 => redeemers: redeemers_field_names(),
+    6 => plutus_v2_scripts,
+    7 => plutus_v3_scripts,
+});
+
+/// Redeemers are either the legacy `[* redeemer]` or Conway's `{ + [tag, index] => [data, ex_units] }`
+fn redeemers_field_names() -> FieldNames {
+    FieldNames::Choice(vec![
+        // before the legacy names, which as `Elements` would also name map values
+        FieldNames::entries(
+            (
+                "",
+                FieldNames::Array(vec![("tag", FieldNames::None), ("index", FieldNames::None)]),
+            ),
+            (
+                "",
+                FieldNames::Array(vec![
+                    ("data", FieldNames::None),
+                    ("ex_units", ExUnits::cbor_field_names()),
+                ]),
+            ),
+        ),
+        FieldNames::elements(Redeemer::cbor_field_names()),
+    ])
+}
+
 impl TransactionOutput {
     pub fn new(
         address: Address,
@@ -138,6 +277,63 @@ impl NativeScript {
     pub fn get_required_signers(&self) -> Vec<Ed25519KeyHash> {
         RequiredSignersSet::from(self).iter().cloned().collect()
     }
+
+    /// Evaluates the script the same way the ledger does.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower_bound` - start of the validity interval (inclusive). None if unbounded.
+    /// * `upper_bound` - end of the validity interval (exclusive). None if unbounded.
+    /// * `key_hashes` - hashes of the keys that signed
+    pub fn verify(
+        &self,
+        lower_bound: Option<Slot>,
+        upper_bound: Option<Slot>,
+        key_hashes: &RequiredSignersSet,
+    ) -> bool {
+        fn verify_helper(
+            script: &NativeScript,
+            lower_bound: Option<Slot>,
+            upper_bound: Option<Slot>,
+            key_hashes: &RequiredSignersSet,
+        ) -> bool {
+            match script {
+                NativeScript::ScriptPubkey(pub_key) => {
+                    key_hashes.contains(&pub_key.ed25519_key_hash)
+                }
+                NativeScript::ScriptAll(script_all) => {
+                    script_all.native_scripts.iter().all(|sub_script| {
+                        verify_helper(sub_script, lower_bound, upper_bound, key_hashes)
+                    })
+                }
+                NativeScript::ScriptAny(script_any) => {
+                    script_any.native_scripts.iter().any(|sub_script| {
+                        verify_helper(sub_script, lower_bound, upper_bound, key_hashes)
+                    })
+                }
+                NativeScript::ScriptNOfK(script_atleast) => {
+                    script_atleast
+                        .native_scripts
+                        .iter()
+                        .filter(|sub_script| {
+                            verify_helper(sub_script, lower_bound, upper_bound, key_hashes)
+                        })
+                        .count()
+                        >= script_atleast.n as usize
+                }
+                NativeScript::ScriptInvalidBefore(timelock_start) => match lower_bound {
+                    Some(tx_slot) => tx_slot >= timelock_start.before,
+                    _ => false,
+                },
+                NativeScript::ScriptInvalidHereafter(timelock_expiry) => match upper_bound {
+                    Some(tx_slot) => tx_slot <= timelock_expiry.after,
+                    _ => false,
+                },
+            }
+        }
+
+        verify_helper(self, lower_bound, upper_bound, key_hashes)
+    }
 }
 
 impl TransactionWitnessSet {
@@ -215,3 +411,126 @@ impl TransactionWitnessSet {
         used_langs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cml_core::{
+        error::{DeserializeFailure, Key},
+        serialization::{Deserialize, Serialize},
+        CborValue,
+    };
+    use cml_crypto::{blake2b256, RawBytesEncoding};
+
+    use super::*;
+    use crate::certs::Certificate;
+
+    #[test]
+    fn lenient_unknown_key() {
+        // {0: [], 1: [], 99: [1, 2], 2: 0}
+        let bytes = hex::decode("a40080018018638201020200").unwrap();
+        let strict = TransactionBody::from_cbor_bytes(&bytes).unwrap_err();
+        assert!(matches!(
+            strict.failure(),
+            DeserializeFailure::UnknownKey(Key::Uint(99))
+        ));
+        let body = TransactionBody::from_cbor_bytes_lenient(&bytes).unwrap();
+        assert_eq!(body.unknown_fields().len(), 1);
+        assert_eq!(
+            body.unknown_fields().get(&CborValue::from(99u64)),
+            Some(&CborValue::new_array(vec![1u64.into(), 2u64.into()]))
+        );
+        assert_eq!(body.to_cbor_bytes(), bytes);
+        assert_eq!(body.hash().to_raw_bytes(), blake2b256(&bytes));
+        assert_eq!(
+            hex::encode(body.to_canonical_cbor_bytes()),
+            "a40080018002001863820102"
+        );
+        // lenient mode doesn't leak outside of from_cbor_bytes_lenient
+        assert!(TransactionBody::from_cbor_bytes(&bytes).is_err());
+
+        // {6: 7, 0: [], 1: [], 2: 0, 3: 5} - canonical sorts them in among the known keys
+        let bytes = hex::decode("a506070080018002000305").unwrap();
+        let body = TransactionBody::from_cbor_bytes_lenient(&bytes).unwrap();
+        assert_eq!(body.to_cbor_bytes(), bytes);
+        assert_eq!(
+            hex::encode(body.to_canonical_cbor_bytes()),
+            "a500800180020003050607"
+        );
+    }
+
+    #[test]
+    fn lenient_unknown_variant() {
+        // {0: [], 4: [[99, 0], [0, [0, h'00..']]], 1: [], 2: 0}
+        // i.e. a cert type that doesn't exist (yet) before a stake registration
+        let bytes = hex::decode(format!(
+            "a4008004828218630082008200581c{}01800200",
+            "00".repeat(28)
+        ))
+        .unwrap();
+        assert!(TransactionBody::from_cbor_bytes(&bytes).is_err());
+        let body = TransactionBody::from_cbor_bytes_lenient(&bytes).unwrap();
+        assert!(matches!(
+            body.certs.as_deref(),
+            Some([Certificate::StakeRegistration(_)])
+        ));
+        assert!(body.unknown_fields().is_empty());
+        let [item] = body.unknown_fields().items() else {
+            panic!("{:?}", body.unknown_fields());
+        };
+        assert_eq!(item.key, CborValue::from(4u64));
+        assert_eq!(item.index, 0);
+        assert_eq!(
+            item.value.at_path(&[0.into()]).and_then(CborValue::as_uint),
+            Some(99)
+        );
+        assert_eq!(body.to_cbor_bytes(), bytes);
+        assert_eq!(body.hash().to_raw_bytes(), blake2b256(&bytes));
+        // also when nested in a transaction
+        let mut tx_bytes = vec![0x84];
+        tx_bytes.extend(&bytes);
+        tx_bytes.extend([0xa0, 0xf5, 0xf6]);
+        let tx = Transaction::from_cbor_bytes_lenient(&tx_bytes).unwrap();
+        assert_eq!(tx.to_cbor_bytes(), tx_bytes);
+        assert_eq!(tx.body.hash().to_raw_bytes(), blake2b256(&bytes));
+    }
+
+    #[test]
+    fn lenient_unknown_output() {
+        // {0: [], 1: [5, [h'61..', 1000000]], 2: 0} i.e. an output format that doesn't exist
+        let bytes = hex::decode(format!(
+            "a3008001820582581d61{}1a000f42400200",
+            "00".repeat(28)
+        ))
+        .unwrap();
+        let body = TransactionBody::from_cbor_bytes_lenient(&bytes).unwrap();
+        // the output at index 1 is now the first one
+        assert_eq!(body.outputs.len(), 1);
+        assert_eq!(body.outputs[0].amount().coin, 1000000);
+        let [item] = body.unknown_fields().items() else {
+            panic!("{:?}", body.unknown_fields());
+        };
+        assert_eq!(item.key, CborValue::from(1u64));
+        assert_eq!(item.index, 0);
+        assert_eq!(item.value, CborValue::from(5u64));
+        assert_eq!(body.to_cbor_bytes(), bytes);
+    }
+
+    #[test]
+    fn lenient_witness_set() {
+        // {1: [[9, 0], [1, []]], 99: 1} i.e. an unknown native script type and key
+        let bytes = hex::decode("a20182820900820180186301").unwrap();
+        assert!(TransactionWitnessSet::from_cbor_bytes(&bytes).is_err());
+        let witness_set = TransactionWitnessSet::from_cbor_bytes_lenient(&bytes).unwrap();
+        assert!(matches!(
+            witness_set.native_scripts.as_deref(),
+            Some([NativeScript::ScriptAll(_)])
+        ));
+        assert_eq!(witness_set.unknown_fields().items().len(), 1);
+        assert_eq!(
+            witness_set.unknown_fields().get(&CborValue::from(99u64)),
+            Some(&CborValue::from(1u64))
+        );
+        assert_eq!(witness_set.to_cbor_bytes(), bytes);
+        assert_eq!(witness_set.to_canonical_cbor_bytes(), bytes);
+    }
+}
@@ -1,5 +1,6 @@
 use cbor_event::{de::Deserializer, se::Serializer};
 use cml_core::{
+    diagnostic::FieldNames,
     error::{DeserializeError, DeserializeFailure},
     serialization::{fit_sz, sz_max, Deserialize, Serialize},
     Int,
@@ -10,10 +11,76 @@ use std::io::{BufRead, Seek, Write};
 
 use crate::{
     crypto::hash::{hash_script, ScriptHashNamespace},
-    plutus::{Language, PlutusScript, PlutusV1Script, PlutusV2Script},
-    NativeScript, Script,
+    plutus::{
+        uplc::{Program, UplcError},
+        CostModels, ExUnitPrices, ExUnits, Language, PlutusScript, PlutusV1Script, PlutusV2Script,
+    },
+    DRepVotingThresholds, NativeScript, PoolVotingThresholds, ProtocolParamUpdate, Rational,
+    Script, UnitInterval,
 };
 
+cml_core::impl_unknown_fields!(ProtocolParamUpdate);
+
+cml_core::impl_cbor_field_names!(Rational tag 30 [numerator, denominator]);
+cml_core::impl_cbor_field_names!(UnitInterval tag 30 [start, end]);
+cml_core::impl_cbor_field_names!(PoolVotingThresholds [
+    motion_no_confidence: UnitInterval::cbor_field_names(),
+    committee_normal: UnitInterval::cbor_field_names(),
+    committee_no_confidence: UnitInterval::cbor_field_names(),
+    hard_fork_initiation: UnitInterval::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(DRepVotingThresholds [
+    motion_no_confidence: UnitInterval::cbor_field_names(),
+    committee_normal: UnitInterval::cbor_field_names(),
+    committee_no_confidence: UnitInterval::cbor_field_names(),
+    update_constitution: UnitInterval::cbor_field_names(),
+    hard_fork_initiation: UnitInterval::cbor_field_names(),
+    pp_network_group: UnitInterval::cbor_field_names(),
+    pp_economic_group: UnitInterval::cbor_field_names(),
+    pp_technical_group: UnitInterval::cbor_field_names(),
+    pp_governance_group: UnitInterval::cbor_field_names(),
+    treasury_withdrawal: UnitInterval::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(ProtocolParamUpdate {
+    0 => minfee_a,
+    1 => minfee_b,
+    2 => max_block_body_size,
+    3 => max_transaction_size,
+    4 => max_block_header_size,
+    5 => key_deposit,
+    6 => pool_deposit,
+    7 => maximum_epoch,
+    8 => n_opt,
+    9 => pool_pledge_influence: Rational::cbor_field_names(),
+    10 => expansion_rate: UnitInterval::cbor_field_names(),
+    11 => treasury_growth_rate: UnitInterval::cbor_field_names(),
+    16 => min_pool_cost,
+    17 => ada_per_utxo_byte,
+    18 => cost_models_for_script_languages: CostModels::cbor_field_names(),
+    19 => execution_costs: ExUnitPrices::cbor_field_names(),
+    20 => max_tx_ex_units: ExUnits::cbor_field_names(),
+    21 => max_block_ex_units: ExUnits::cbor_field_names(),
+    22 => max_value_size,
+    23 => collateral_percentage,
+    24 => max_collateral_inputs,
+    25 => pool_voting_thresholds: PoolVotingThresholds::cbor_field_names(),
+    26 => d_rep_voting_thresholds: DRepVotingThresholds::cbor_field_names(),
+    27 => min_committee_size,
+    28 => committee_term_limit,
+    29 => governance_action_validity_period,
+    30 => governance_action_deposit,
+    31 => d_rep_deposit,
+    32 => d_rep_inactivity_period,
+});
+
+/// Names for `Withdrawals`, which (as a map type) can't implement `CBORFieldNames` itself
+pub fn withdrawals_field_names() -> FieldNames {
+    FieldNames::entries(
+        ("reward_account", FieldNames::None),
+        ("coin", FieldNames::None),
+    )
+}
+
 impl Script {
     pub fn hash(&self) -> ScriptHash {
         match self {
@@ -34,6 +101,17 @@ impl Script {
             Self::PlutusV3 { .. } => Some(Language::PlutusV3),
         }
     }
+
+    /// Decodes the UPLC program if it's a Plutus script e.g. to inspect a reference script
+    /// Returns None otherwise (i.e. NativeScript)
+    pub fn to_program(&self) -> Option<Result<Program, UplcError>> {
+        match self {
+            Self::Native { .. } => None,
+            Self::PlutusV1 { script, .. } => Some(script.to_program()),
+            Self::PlutusV2 { script, .. } => Some(script.to_program()),
+            Self::PlutusV3 { script, .. } => Some(script.to_program()),
+        }
+    }
 }
 
 impl NativeScript {
@@ -161,6 +239,19 @@ pub fn write_bounded_bytes<'se, W: Write>(
 pub fn read_bounded_bytes<R: BufRead + Seek>(
     raw: &mut Deserializer<R>,
 ) -> Result<(Vec<u8>, StringEncoding), DeserializeError> {
+    // checked before reading so that oversized definite lengths are never allocated
+    if raw.cbor_type()? == cbor_event::Type::Bytes {
+        if let cbor_event::LenSz::Len(len, _) = raw.cbor_len_sz()? {
+            if len > BOUNDED_BYTES_CHUNK_SIZE as u64 {
+                return Err(DeserializeFailure::OutOfRange {
+                    min: 0,
+                    max: BOUNDED_BYTES_CHUNK_SIZE,
+                    found: len as usize,
+                }
+                .into());
+            }
+        }
+    }
     let (bytes, bytes_enc) = raw.bytes_sz()?;
     match &bytes_enc {
         cbor_event::StringLenSz::Len(_sz) => {
@@ -306,6 +397,16 @@ impl BigInteger {
         }
     }
 
+    /// Converts to an i128
+    /// Returns None if the number was too big for an i128
+    pub fn as_i128(&self) -> Option<i128> {
+        num::ToPrimitive::to_i128(&self.num)
+    }
+
+    pub(crate) fn as_bigint(&self) -> &num_bigint::BigInt {
+        &self.num
+    }
+
     /// Converts to an Int
     /// Returns None when the number is too big for an Int (outside +/- 64-bit unsigned)
     /// Retains encoding info if the original was encoded as an Int
@@ -574,6 +675,313 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn strict_deserialization() {
+        use crate::plutus::PlutusData;
+        use cml_core::canonical::NonCanonicalKind;
+        use cml_core::DeserializeFailure;
+
+        let strict_failures = |bytes: &[u8]| -> Vec<NonCanonicalKind> {
+            match PlutusData::from_cbor_bytes_strict(bytes)
+                .unwrap_err()
+                .failure()
+            {
+                DeserializeFailure::NonCanonical(violations) => {
+                    violations.iter().map(|violation| violation.kind).collect()
+                }
+                failure => panic!("unexpected {:?}", failure),
+            }
+        };
+        assert_eq!(
+            strict_failures(&[0x9f, 0x01, 0xff]),
+            vec![NonCanonicalKind::IndefiniteLength]
+        );
+        assert_eq!(
+            strict_failures(&[0x18, 0x05]),
+            vec![NonCanonicalKind::NonMinimalLength]
+        );
+        assert_eq!(
+            strict_failures(&[0xa2, 0x02, 0x00, 0x01, 0x00]),
+            vec![NonCanonicalKind::UnsortedMapKeys]
+        );
+        assert_eq!(
+            strict_failures(&[0x01, 0x00]),
+            vec![NonCanonicalKind::TrailingBytes]
+        );
+        // Plutus data maps can have duplicate keys
+        PlutusData::from_cbor_bytes_strict(&[0xa2, 0x01, 0x00, 0x01, 0x01]).unwrap();
+        // bounded bytes over 64 bytes are chunked in canonical form
+        let mut chunked = vec![0x5f, 0x58, 0x40];
+        chunked.extend([0u8; 64]);
+        chunked.extend([0x41, 0x00, 0xff]);
+        PlutusData::from_cbor_bytes_strict(&chunked).unwrap();
+
+        let non_canonical = |err: cml_core::DeserializeError| match err.failure() {
+            DeserializeFailure::NonCanonical(violations) => violations
+                .iter()
+                .map(|violation| (violation.offset, violation.kind))
+                .collect::<Vec<_>>(),
+            failure => panic!("unexpected {:?}", failure),
+        };
+        // metadatum maps can have duplicate keys but the labels of metadata can't
+        crate::auxdata::Metadata::from_cbor_bytes_strict(&[
+            0xa1, 0x01, 0xa2, 0x61, 0x61, 0x00, 0x61, 0x61, 0x01,
+        ])
+        .unwrap();
+        assert_eq!(
+            non_canonical(
+                crate::auxdata::Metadata::from_cbor_bytes_strict(&[0xa2, 0x01, 0x00, 0x01, 0x01])
+                    .unwrap_err()
+            ),
+            vec![(3, NonCanonicalKind::DuplicateKey)]
+        );
+    }
+
+    #[test]
+    fn lenient_protocol_param_update() {
+        use crate::ProtocolParamUpdate;
+        use cml_core::CborValue;
+
+        // {99: "x", 0: 5}
+        let bytes = hex::decode("a2186361780005").unwrap();
+        assert!(ProtocolParamUpdate::from_cbor_bytes(&bytes).is_err());
+        let update = ProtocolParamUpdate::from_cbor_bytes_lenient(&bytes).unwrap();
+        assert_eq!(update.minfee_a, Some(5));
+        assert_eq!(
+            update.unknown_fields().get(&CborValue::from(99u64)),
+            Some(&CborValue::from("x"))
+        );
+        assert_eq!(update.to_cbor_bytes(), bytes);
+        assert_eq!(
+            hex::encode(update.to_canonical_cbor_bytes()),
+            "a2000518636178"
+        );
+        // fields added by hand go after the known ones
+        let mut update = ProtocolParamUpdate::new();
+        update.minfee_b = Some(1);
+        update
+            .unknown_fields_mut()
+            .push(CborValue::from(99u64), CborValue::from(2u64));
+        assert_eq!(hex::encode(update.to_cbor_bytes()), "a20101186302");
+    }
+
+    #[test]
+    fn diagnostic_notation() {
+        use crate::transaction::Transaction;
+        use cml_core::diagnostic::{from_diagnostic, CBORFieldNames};
+
+        let tx_id = "00".repeat(32);
+        let address = format!("61{}", "00".repeat(28));
+        let bytes = from_diagnostic(&format!(
+            r#"[
+                {{
+                    / inputs / 0: [[h'{tx_id}', 0]],
+                    / outputs / 1: [[h'{address}', 1000000_3]],
+                    / fee / 2: 200000
+                }},
+                {{}},
+                true,
+                null
+            ]"#
+        ))
+        .unwrap();
+        let tx = Transaction::from_cbor_bytes(&bytes).unwrap();
+        assert_eq!(tx.to_cbor_bytes(), bytes);
+        let annotated = tx.to_annotated_cbor_diagnostic();
+        assert!(annotated.contains("/ fee / 2: 200000"));
+        assert!(annotated.contains("/ amount / 1000000_3"));
+        assert!(annotated.contains(&format!("/ transaction_id / h'{tx_id}'")));
+        assert!(annotated.contains("/ is_valid / true"));
+        assert_eq!(from_diagnostic(&annotated).unwrap(), bytes);
+        assert_eq!(from_diagnostic(&tx.to_cbor_diagnostic()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn diagnostic_field_names() {
+        use crate::transaction::{TransactionBody, TransactionWitnessSet};
+        use cml_core::diagnostic::{from_diagnostic, to_diagnostic_annotated, CBORFieldNames};
+
+        let key_hash = "00".repeat(28);
+        let pool = "11".repeat(28);
+        let policy_id = "22".repeat(28);
+        let reward_account = format!("e1{key_hash}");
+        let body = from_diagnostic(&format!(
+            r#"{{
+                0: [],
+                1: [],
+                2: 0,
+                4: [[2, [0, h'{key_hash}'], h'{pool}'], [7, [1, h'{key_hash}'], 5]],
+                5: {{h'{reward_account}': 3}},
+                9: {{h'{policy_id}': {{h'01': -1}}}}
+            }}"#
+        ))
+        .unwrap();
+        let annotated =
+            to_diagnostic_annotated(&body, &TransactionBody::cbor_field_names()).unwrap();
+        assert!(annotated.contains(&format!("/ hash / h'{key_hash}'")));
+        assert!(annotated.contains(&format!("/ pool / h'{pool}'")));
+        assert!(annotated.contains("/ coin / 5"));
+        assert!(annotated.contains(&format!(
+            "/ reward_account / h'{reward_account}': / coin / 3"
+        )));
+        assert!(annotated.contains(&format!("/ policy_id / h'{policy_id}'")));
+        assert!(annotated.contains("/ asset_name / h'01': -1"));
+        assert_eq!(from_diagnostic(&annotated).unwrap(), body);
+
+        // native scripts nest, redeemers are either an array or (since Conway) a map
+        let names = TransactionWitnessSet::cbor_field_names();
+        for redeemers in ["[[0, 1, 42, [10, 20]]]", "{[0, 1]: [42, [10, 20]]}"] {
+            let witness_set = from_diagnostic(&format!(
+                "{{1: [[1, [[0, h'{key_hash}']]]], 5: {redeemers}}}"
+            ))
+            .unwrap();
+            let annotated = to_diagnostic_annotated(&witness_set, &names).unwrap();
+            assert!(annotated.contains(&format!("/ ed25519_key_hash / h'{key_hash}'")));
+            for field in ["/ tag / 0", "/ index / 1", "/ data / 42", "/ steps / 20"] {
+                assert!(
+                    annotated.contains(field),
+                    "{} missing from {}",
+                    field,
+                    annotated
+                );
+            }
+            assert_eq!(from_diagnostic(&annotated).unwrap(), witness_set);
+        }
+    }
+
+    #[test]
+    fn preserved_json() {
+        use crate::transaction::Transaction;
+        use cml_core::diagnostic::from_diagnostic;
+        use cml_core::preserved_json::{FromPreservedJson, PreservedJson, ToPreservedJson};
+
+        let tx_id = "00".repeat(32);
+        let address = format!("61{}", "00".repeat(28));
+        let bytes = from_diagnostic(&format!(
+            r#"[_
+                {{
+                    2: 200000_2,
+                    0: [_ [h'{tx_id}', 0_1]],
+                    1: [[h'{address}', 1000000_3]]
+                }},
+                {{_ }},
+                true,
+                null
+            ]"#
+        ))
+        .unwrap();
+        let tx = Transaction::from_cbor_bytes(&bytes).unwrap();
+        // plain JSON loses the encoding details
+        let plain: Transaction =
+            serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+        assert_ne!(plain.to_cbor_bytes(), bytes);
+
+        let json = tx.to_preserved_json_string().unwrap();
+        let preserved = PreservedJson::from_json(&json).unwrap();
+        assert!(preserved.cbor_hex.is_none());
+        assert!(!preserved.encodings.is_empty());
+        let read = Transaction::from_preserved_json_str(&json).unwrap();
+        assert_eq!(read.to_cbor_bytes(), bytes);
+        assert_eq!(read.body.hash(), tx.body.hash());
+
+        // edits keep the encoding of everything else
+        let mut edited = preserved.clone();
+        edited.value["body"]["fee"] = serde_json::json!(300000);
+        let edited_bytes = from_diagnostic(&format!(
+            r#"[_
+                {{
+                    2: 300000_2,
+                    0: [_ [h'{tx_id}', 0_1]],
+                    1: [[h'{address}', 1000000_3]]
+                }},
+                {{_ }},
+                true,
+                null
+            ]"#
+        ))
+        .unwrap();
+        assert_eq!(
+            Transaction::from_preserved_json(&edited)
+                .unwrap()
+                .to_cbor_bytes(),
+            edited_bytes
+        );
+        // but structural changes can't keep them
+        edited.value["body"]["inputs"] = serde_json::json!([]);
+        assert!(Transaction::from_preserved_json(&edited).is_err());
+    }
+
+    #[test]
+    fn limited_deserialization() {
+        use crate::plutus::PlutusData;
+        use cml_core::limits::{DecodeLimits, LimitExceeded};
+
+        // 2 nested lists: [_ [_ 1]]
+        let nested = [0x9f, 0x9f, 0x01, 0xff, 0xff];
+        let limits = DecodeLimits {
+            max_depth: 1,
+            ..Default::default()
+        };
+        let err = PlutusData::from_cbor_bytes_with_limits(&nested, &limits).unwrap_err();
+        assert!(matches!(
+            err.failure(),
+            DeserializeFailure::LimitExceeded(LimitExceeded::Depth { max: 1 })
+        ));
+        assert_eq!(err.offset(), Some(1));
+        PlutusData::from_cbor_bytes_with_limits(&nested, &DecodeLimits::default()).unwrap();
+        // limits apply when calling deserialize() directly on a limited reader
+        let mut raw = Deserializer::from(
+            cml_core::reader::DecodeReader::new(std::io::Cursor::new(nested)).with_limits(&limits),
+        );
+        let err = PlutusData::deserialize(&mut raw).unwrap_err();
+        assert!(matches!(
+            err.deepest().1.failure(),
+            DeserializeFailure::LimitExceeded(LimitExceeded::Depth { max: 1 })
+        ));
+        let err = raw.as_ref().limit_error().unwrap();
+        assert_eq!(err.offset(), Some(1));
+        assert_eq!(err.major_type(), Some(cbor_event::Type::Array));
+        // and to anything decoded within a with_limits() scope
+        let err = cml_core::limits::with_limits(&limits, || PlutusData::from_cbor_bytes(&nested))
+            .unwrap_err();
+        assert!(matches!(
+            err.failure(),
+            DeserializeFailure::LimitExceeded(LimitExceeded::Depth { max: 1 })
+        ));
+        PlutusData::from_cbor_bytes(&nested).unwrap();
+        // bounded bytes claiming 2^32 bytes fails without trying to allocate them
+        assert!(PlutusData::from_cbor_bytes(&[0x5a, 0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    fn deserialize_error_diagnostics() {
+        // [2, 1] - a PlutusV2 script with an integer instead of bytes
+        let bytes = [0x82, 0x02, 0x01];
+        let e = Script::from_cbor_bytes(&bytes).unwrap_err();
+        let (location, deepest) = e.deepest();
+        assert_eq!(location.as_deref(), Some("Script.Script.PlutusV2.script"));
+        assert_eq!(deepest.offset(), Some(2));
+        assert_eq!(
+            deepest.major_type(),
+            Some(cbor_event::Type::UnsignedInteger)
+        );
+        let rendered = e.render(&bytes);
+        assert!(rendered.contains("at byte offset 2"));
+        assert!(rendered.contains("within: Array@0 > UnsignedInteger@2"));
+        assert!(rendered.contains("00000000  82 02 01\n                ^^"));
+        let json = e.to_json_value();
+        assert_eq!(json["kind"], "NoVariantMatchedWithCauses");
+        assert_eq!(json["causes"].as_array().unwrap().len(), 4);
+        assert_eq!(json["causes"][2]["offset"], 2);
+        assert_eq!(json["causes"][2]["major_type"], "UnsignedInteger");
+        // offsets are also recorded when deserializing from a DecodeReader directly
+        let mut raw = Deserializer::from(cml_core::reader::DecodeReader::new(
+            std::io::Cursor::new(bytes),
+        ));
+        let e = Script::deserialize(&mut raw).unwrap_err();
+        assert_eq!(e.deepest().1.offset(), Some(2));
+    }
+
     #[test]
     fn bigint_uint_u64_min() {
         let bytes = [0x00];
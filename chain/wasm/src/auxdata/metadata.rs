@@ -11,7 +11,7 @@ use wasm_bindgen::{
 
 use cml_core::serialization::{Deserialize, Serialize};
 
-use cml_core_wasm::{impl_wasm_conversions, impl_wasm_list, Int};
+use cml_core_wasm::{impl_wasm_conversions, impl_wasm_list, CborValue, Int};
 
 pub use cml_chain::auxdata::TransactionMetadatumLabel;
 
@@ -303,6 +303,19 @@ impl TransactionMetadatum {
             _ => None,
         }
     }
+
+    /// Generic CBOR tree of this metadatum, keeping its encoding details
+    pub fn to_cbor_value(&self) -> CborValue {
+        cml_core::CborValue::from(&self.0).into()
+    }
+
+    /// Fails if the CBOR is not valid metadata e.g. contains tags, floats or strings over 64 bytes
+    pub fn from_cbor_value(value: &CborValue) -> Result<TransactionMetadatum, JsError> {
+        use std::convert::TryFrom;
+        cml_chain::auxdata::TransactionMetadatum::try_from(value.as_ref())
+            .map(Self)
+            .map_err(Into::into)
+    }
 }
 
 impl From<cml_chain::auxdata::TransactionMetadatum> for TransactionMetadatum {
@@ -2,6 +2,7 @@ pub mod certificate_builder;
 pub mod input_builder;
 pub mod mint_builder;
 pub mod output_builder;
+pub mod provider;
 pub mod redeemer_builder;
 pub mod tx_builder;
 pub mod withdrawal_builder;
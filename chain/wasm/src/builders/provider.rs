@@ -0,0 +1,269 @@
+use std::rc::Rc;
+
+use cml_chain::builders::provider::{Provider, ProviderError};
+use cml_chain::builders::redeemer_builder::RedeemerWitnessKey;
+use cml_chain::plutus::RedeemerTag;
+use cml_core::serialization::{Deserialize, Serialize, ToBytes};
+use cml_core_wasm::{impl_wasm_conversions, impl_wasm_json_api};
+use js_sys::{Array, Promise};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use crate::{
+    builders::tx_builder::{TransactionBuilderConfigBuilder, TransactionUnspentOutput},
+    plutus::ExUnits,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const PROVIDER_TS: &'static str = r#"
+/**
+ * Chain access used by TransactionBuilder.complete(). All CBOR is passed as hex strings.
+ * InMemoryProvider implements this for tests.
+ */
+export interface Provider {
+  /** CBOR of the TransactionUnspentOutputs at the bech32 address (same as CIP-30's getUtxos()) */
+  utxosAt(address: string): Promise<string[]>;
+  /** Current protocol parameters in the same format as ProtocolParameters.to_js_value() */
+  protocolParams(): Promise<object>;
+  /**
+   * ExUnits needed for each redeemer in the transaction's CBOR.
+   * additionalUtxos are the CBOR of the TransactionUnspentOutputs the tx spends/references.
+   * The tag is the name of the RedeemerTag variant e.g. "Spend".
+   */
+  evaluateTx(
+    tx: string,
+    additionalUtxos: string[]
+  ): Promise<{ tag: keyof typeof RedeemerTag; index: number; mem: number; steps: number }[]>;
+  /** Submits the transaction's CBOR returning the tx hash as hex */
+  submitTx(tx: string): Promise<string>;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Any JS object implementing the Provider interface
+    #[wasm_bindgen(typescript_type = "Provider")]
+    pub type JsProvider;
+
+    #[wasm_bindgen(method, catch, js_name = utxosAt)]
+    fn utxos_at(this: &JsProvider, address: String) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = protocolParams)]
+    fn protocol_params(this: &JsProvider) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = evaluateTx)]
+    fn evaluate_tx(
+        this: &JsProvider,
+        tx: String,
+        additional_utxos: Array,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = submitTx)]
+    fn submit_tx(this: &JsProvider, tx: String) -> Result<Promise, JsValue>;
+}
+
+fn js_provider_error(e: JsValue) -> ProviderError {
+    ProviderError::new(
+        e.as_string()
+            .or_else(|| e.dyn_ref::<js_sys::Error>().map(|e| e.message().into()))
+            .unwrap_or_else(|| format!("{e:?}")),
+    )
+}
+
+async fn await_js(promise: Result<Promise, JsValue>) -> Result<JsValue, ProviderError> {
+    JsFuture::from(promise.map_err(js_provider_error)?)
+        .await
+        .map_err(js_provider_error)
+}
+
+fn utxos_to_js(utxos: &[cml_chain::builders::tx_builder::TransactionUnspentOutput]) -> Array {
+    utxos
+        .iter()
+        .map(|utxo| JsValue::from_str(&hex::encode(utxo.to_bytes())))
+        .collect()
+}
+
+fn utxos_from_js(
+    utxos: &JsValue,
+) -> Result<Vec<cml_chain::builders::tx_builder::TransactionUnspentOutput>, ProviderError> {
+    Array::from(utxos)
+        .iter()
+        .map(|utxo| {
+            let bytes = utxo
+                .as_string()
+                .and_then(|utxo| hex::decode(utxo).ok())
+                .ok_or_else(|| ProviderError::new("UTxOs must be CBOR hex strings"))?;
+            cml_chain::builders::tx_builder::TransactionUnspentOutput::from_cbor_bytes(&bytes)
+                .map_err(|e| ProviderError::new(e.to_string()))
+        })
+        .collect()
+}
+
+fn tx_from_js(tx: &str) -> Result<cml_chain::transaction::Transaction, JsError> {
+    let bytes = hex::decode(tx).map_err(|e| JsError::new(&e.to_string()))?;
+    cml_chain::transaction::Transaction::from_cbor_bytes(&bytes).map_err(Into::into)
+}
+
+impl Provider for JsProvider {
+    async fn utxos_at_address(
+        &self,
+        address: &cml_chain::address::Address,
+    ) -> Result<Vec<cml_chain::builders::tx_builder::TransactionUnspentOutput>, ProviderError> {
+        let address = address
+            .to_bech32(None)
+            .map_err(|e| ProviderError::new(e.to_string()))?;
+        utxos_from_js(&await_js(self.utxos_at(address)).await?)
+    }
+
+    async fn protocol_params(
+        &self,
+    ) -> Result<cml_chain::builders::provider::ProtocolParameters, ProviderError> {
+        serde_wasm_bindgen::from_value(await_js(self.protocol_params()).await?)
+            .map_err(|e| ProviderError::new(e.to_string()))
+    }
+
+    async fn evaluate_tx(
+        &self,
+        tx: &cml_chain::transaction::Transaction,
+        additional_utxos: &[cml_chain::builders::tx_builder::TransactionUnspentOutput],
+    ) -> Result<Vec<(RedeemerWitnessKey, cml_chain::plutus::ExUnits)>, ProviderError> {
+        let evaluated = await_js(self.evaluate_tx(
+            hex::encode(tx.to_cbor_bytes()),
+            utxos_to_js(additional_utxos),
+        ))
+        .await?;
+        let evaluated: Vec<serde_json::Value> = serde_wasm_bindgen::from_value(evaluated)
+            .map_err(|e| ProviderError::new(e.to_string()))?;
+        evaluated
+            .into_iter()
+            .map(|redeemer| {
+                let tag: RedeemerTag = serde_json::from_value(redeemer["tag"].clone())
+                    .map_err(|e| ProviderError::new(format!("evaluateTx tag: {e}")))?;
+                let field = |name: &str| {
+                    redeemer[name]
+                        .as_u64()
+                        .ok_or_else(|| ProviderError::new(format!("evaluateTx missing {name}")))
+                };
+                Ok((
+                    RedeemerWitnessKey::new(tag, field("index")?),
+                    cml_chain::plutus::ExUnits::new(field("mem")?, field("steps")?),
+                ))
+            })
+            .collect()
+    }
+
+    async fn submit_tx(
+        &self,
+        tx: &cml_chain::transaction::Transaction,
+    ) -> Result<cml_crypto::TransactionHash, ProviderError> {
+        let hash = await_js(self.submit_tx(hex::encode(tx.to_cbor_bytes())))
+            .await?
+            .as_string()
+            .ok_or_else(|| ProviderError::new("submitTx must return the tx hash as hex"))?;
+        cml_crypto::TransactionHash::from_hex(&hash).map_err(|e| ProviderError::new(e.to_string()))
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ProtocolParameters(cml_chain::builders::provider::ProtocolParameters);
+
+impl_wasm_conversions!(
+    cml_chain::builders::provider::ProtocolParameters,
+    ProtocolParameters
+);
+
+impl_wasm_json_api!(ProtocolParameters);
+
+#[wasm_bindgen]
+impl ProtocolParameters {
+    /// Config builder with all protocol parameters set. Non-protocol options
+    /// (e.g. prefer_pure_change) can still be set on it before building.
+    pub fn to_config_builder(&self) -> TransactionBuilderConfigBuilder {
+        self.0.to_config_builder().into()
+    }
+}
+
+/// Deterministic Provider backed by an in-memory UTxO set, for tests.
+/// UTxOs are always returned sorted by input, every redeemer is evaluated to the same ExUnits
+/// and submitting only checks that the inputs exist (no signatures, scripts or balance) before
+/// spending them and adding the outputs to the UTxO set.
+/// This implements the Provider interface so it can be passed to TransactionBuilder.complete()
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct InMemoryProvider(Rc<cml_chain::builders::provider::InMemoryProvider>);
+
+#[wasm_bindgen]
+impl InMemoryProvider {
+    pub fn new(params: &ProtocolParameters, ex_units: &ExUnits) -> Self {
+        Self(Rc::new(
+            cml_chain::builders::provider::InMemoryProvider::new(
+                params.clone().into(),
+                ex_units.clone().into(),
+            ),
+        ))
+    }
+
+    pub fn add_utxo(&self, utxo: &TransactionUnspentOutput) {
+        self.0.add_utxo(utxo.clone().into());
+    }
+
+    #[wasm_bindgen(js_name = utxosAt)]
+    pub fn utxos_at(&self, address: String) -> Promise {
+        let provider = self.0.clone();
+        future_to_promise(async move {
+            let address =
+                cml_chain::address::Address::from_bech32(&address).map_err(JsError::from)?;
+            let utxos = provider
+                .utxos_at_address(&address)
+                .await
+                .map_err(JsError::from)?;
+            Ok(utxos_to_js(&utxos).into())
+        })
+    }
+
+    #[wasm_bindgen(js_name = protocolParams)]
+    pub fn protocol_params(&self) -> Promise {
+        let provider = self.0.clone();
+        future_to_promise(async move {
+            let params = provider.protocol_params().await.map_err(JsError::from)?;
+            serde_wasm_bindgen::to_value(&params).map_err(Into::into)
+        })
+    }
+
+    #[wasm_bindgen(js_name = evaluateTx)]
+    pub fn evaluate_tx(&self, tx: String, additional_utxos: Array) -> Promise {
+        let provider = self.0.clone();
+        future_to_promise(async move {
+            let tx = tx_from_js(&tx)?;
+            let additional_utxos =
+                utxos_from_js(&additional_utxos.into()).map_err(JsError::from)?;
+            let evaluated = provider
+                .evaluate_tx(&tx, &additional_utxos)
+                .await
+                .map_err(JsError::from)?
+                .into_iter()
+                .map(|(redeemer, ex_units)| {
+                    serde_json::json!({
+                        "tag": redeemer.tag(),
+                        "index": redeemer.index(),
+                        "mem": ex_units.mem,
+                        "steps": ex_units.steps,
+                    })
+                })
+                .collect::<Vec<_>>();
+            serde_wasm_bindgen::to_value(&evaluated).map_err(Into::into)
+        })
+    }
+
+    #[wasm_bindgen(js_name = submitTx)]
+    pub fn submit_tx(&self, tx: String) -> Promise {
+        let provider = self.0.clone();
+        future_to_promise(async move {
+            let tx = tx_from_js(&tx)?;
+            let hash = provider.submit_tx(&tx).await.map_err(JsError::from)?;
+            Ok(JsValue::from_str(&hash.to_hex()))
+        })
+    }
+}
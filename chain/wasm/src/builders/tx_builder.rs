@@ -1,7 +1,9 @@
 use cml_chain::builders::tx_builder::{ChangeSelectionAlgo, CoinSelectionStrategyCIP2};
 use cml_core_wasm::{impl_wasm_cbor_event_serialize_api, impl_wasm_conversions};
 use cml_crypto_wasm::Ed25519KeyHash;
+use js_sys::Promise;
 use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
     address::Address,
@@ -10,8 +12,8 @@ use crate::{
     builders::{
         certificate_builder::CertificateBuilderResult, input_builder::InputBuilderResult,
         mint_builder::MintBuilderResult, output_builder::SingleOutputBuilderResult,
-        redeemer_builder::RedeemerWitnessKey, withdrawal_builder::WithdrawalBuilderResult,
-        witness_builder::TransactionWitnessSetBuilder,
+        provider::JsProvider, redeemer_builder::RedeemerWitnessKey,
+        withdrawal_builder::WithdrawalBuilderResult, witness_builder::TransactionWitnessSetBuilder,
     },
     crypto::{BootstrapWitness, Vkeywitness},
     fees::LinearFee,
@@ -340,6 +342,42 @@ impl TransactionBuilder {
             .map_err(Into::into)
     }
 
+    /// Creates a builder using the current protocol parameters from {provider}.
+    /// Resolves to a TransactionBuilder.
+    pub fn from_provider(provider: JsProvider) -> Promise {
+        future_to_promise(async move {
+            cml_chain::builders::tx_builder::TransactionBuilder::from_provider(&provider)
+                .await
+                .map(|builder| TransactionBuilder::from(builder).into())
+                .map_err(|e| JsError::from(e).into())
+        })
+    }
+
+    /// Finishes the transaction using {provider}'s view of the chain:
+    /// 1) selects inputs from the key-locked UTxOs at {change_address} using {strategy}
+    /// 2) if there are any scripts, evaluates their ExUnits (adding more inputs for the script fees if needed)
+    /// 3) also for scripts, adds collateral from the ADA-only UTxOs at {change_address} unless some was already added
+    /// 4) adds change to {change_address} and moves to the signing step like build()
+    ///
+    /// Everything else (outputs, certs, script inputs, etc) must be added before calling this.
+    /// This builder is left unmodified. Resolves to a SignedTxBuilder.
+    pub fn complete(
+        &self,
+        provider: JsProvider,
+        change_address: &Address,
+        strategy: CoinSelectionStrategyCIP2,
+    ) -> Promise {
+        let mut builder = self.0.clone();
+        let change_address = change_address.as_ref().clone();
+        future_to_promise(async move {
+            builder
+                .complete(&provider, &change_address, strategy)
+                .await
+                .map(|signed| SignedTxBuilder::from(signed).into())
+                .map_err(|e| JsError::from(e).into())
+        })
+    }
+
     /// used to override the exunit values initially provided when adding inputs
     pub fn set_exunits(&mut self, redeemer: &RedeemerWitnessKey, ex_units: &ExUnits) {
         self.0
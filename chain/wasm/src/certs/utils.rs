@@ -1,5 +1,6 @@
-use wasm_bindgen::prelude::wasm_bindgen;
-use super::{Ipv4, Ipv6, StakeCredential};
+use cml_core_wasm::impl_wasm_text_envelope_api;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+use super::{Certificate, Ipv4, Ipv6, StakeCredential};
 
 #[wasm_bindgen]
 impl StakeCredential {
@@ -31,4 +32,6 @@ impl Ipv6 {
     pub fn from_str(s: &str) -> Result<Ipv6, JsError> {
         cml_chain::certs::Ipv6::from_str(s).map(Into::into).map_err(Into::into)
     }
-}
\ No newline at end of file
+}
+
+impl_wasm_text_envelope_api!(Certificate);
@@ -1,3 +1,4 @@
+use cml_core_wasm::impl_wasm_text_envelope_api;
 use wasm_bindgen::prelude::{wasm_bindgen, JsError};
 
 use crate::{byron::AddressContent, crypto::BootstrapWitness, Vkeywitness};
@@ -15,3 +16,6 @@ impl BootstrapWitness {
 pub fn make_vkey_witness(tx_body_hash: &TransactionHash, sk: &PrivateKey) -> Vkeywitness {
     cml_chain::crypto::utils::make_vkey_witness(tx_body_hash.as_ref(), sk.as_ref()).into()
 }
+
+impl_wasm_text_envelope_api!(BootstrapWitness);
+impl_wasm_text_envelope_api!(Vkeywitness);
@@ -22,3 +22,28 @@ pub fn decode_metadatum_to_json_str(
     cml_chain::json::metadatums::decode_metadatum_to_json_str(metadatum.as_ref(), schema)
         .map_err(Into::into)
 }
+
+/// Converts JSON to Metadata according to MetadataJsonSchema.
+/// Errors on any conversion that would not give back the same JSON when decoded again
+/// e.g. duplicate DetailedSchema map keys or BasicConversions keys like "007".
+#[wasm_bindgen]
+pub fn encode_json_str_to_metadatum_strict(
+    json: &str,
+    schema: MetadataJsonSchema,
+) -> Result<TransactionMetadatum, JsError> {
+    cml_chain::json::metadatums::encode_json_str_to_metadatum_strict(json, schema)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Converts Metadata to JSON according to MetadataJsonSchema.
+/// Errors on any conversion that would not give back the same metadatum when encoded again
+/// e.g. duplicate map keys, or under BasicConversions text that would be read back as bytes.
+#[wasm_bindgen]
+pub fn decode_metadatum_to_json_str_strict(
+    metadatum: &TransactionMetadatum,
+    schema: MetadataJsonSchema,
+) -> Result<String, JsError> {
+    cml_chain::json::metadatums::decode_metadatum_to_json_str_strict(metadatum.as_ref(), schema)
+        .map_err(Into::into)
+}
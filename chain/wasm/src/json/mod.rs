@@ -1,2 +1,3 @@
 pub mod metadatums;
 pub mod plutus_datums;
+pub mod views;
@@ -0,0 +1,85 @@
+pub use cml_chain::json::views::ChainJsonSchema;
+
+use crate::{
+    certs::Certificate,
+    transaction::{Transaction, TransactionOutput},
+    Value,
+};
+
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// Converts JSON to a Value according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn encode_json_str_to_value(json: &str, schema: ChainJsonSchema) -> Result<Value, JsError> {
+    cml_chain::json::views::encode_json_str_to_value(json, schema)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Converts a Value to JSON according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn decode_value_to_json_str(value: &Value, schema: ChainJsonSchema) -> Result<String, JsError> {
+    cml_chain::json::views::decode_value_to_json_str(value.as_ref(), schema).map_err(Into::into)
+}
+
+/// Converts JSON to a TransactionOutput according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn encode_json_str_to_transaction_output(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<TransactionOutput, JsError> {
+    cml_chain::json::views::encode_json_str_to_transaction_output(json, schema)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Converts a TransactionOutput to JSON according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn decode_transaction_output_to_json_str(
+    output: &TransactionOutput,
+    schema: ChainJsonSchema,
+) -> Result<String, JsError> {
+    cml_chain::json::views::decode_transaction_output_to_json_str(output.as_ref(), schema)
+        .map_err(Into::into)
+}
+
+/// Converts JSON to a Certificate according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn encode_json_str_to_certificate(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<Certificate, JsError> {
+    cml_chain::json::views::encode_json_str_to_certificate(json, schema)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Converts a Certificate to JSON according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn decode_certificate_to_json_str(
+    cert: &Certificate,
+    schema: ChainJsonSchema,
+) -> Result<String, JsError> {
+    cml_chain::json::views::decode_certificate_to_json_str(cert.as_ref(), schema)
+        .map_err(Into::into)
+}
+
+/// Converts JSON to a Transaction according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn encode_json_str_to_transaction(
+    json: &str,
+    schema: ChainJsonSchema,
+) -> Result<Transaction, JsError> {
+    cml_chain::json::views::encode_json_str_to_transaction(json, schema)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Converts a Transaction to JSON according to ChainJsonSchema
+#[wasm_bindgen]
+pub fn decode_transaction_to_json_str(
+    tx: &Transaction,
+    schema: ChainJsonSchema,
+) -> Result<String, JsError> {
+    cml_chain::json::views::decode_transaction_to_json_str(tx.as_ref(), schema).map_err(Into::into)
+}
@@ -1,3 +1,4 @@
+pub mod uplc;
 pub mod utils;
 
 // This file was code-generated using an experimental CDDL to rust tool:
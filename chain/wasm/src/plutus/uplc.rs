@@ -0,0 +1,64 @@
+use cml_core_wasm::impl_wasm_conversions;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use super::{utils::PlutusScript, PlutusDataList};
+
+/// Decoded UPLC program of a Plutus script
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct UplcProgram(cml_chain::plutus::uplc::Program);
+
+impl_wasm_conversions!(cml_chain::plutus::uplc::Program, UplcProgram);
+
+#[wasm_bindgen]
+impl UplcProgram {
+    /// Decodes the inner bytes of a Plutus script (CBOR-wrapped flat program)
+    pub fn from_script_bytes(bytes: &[u8]) -> Result<UplcProgram, JsError> {
+        cml_chain::plutus::uplc::Program::from_script_bytes(bytes)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn from_plutus_script(script: &PlutusScript) -> Result<UplcProgram, JsError> {
+        script
+            .as_ref()
+            .to_program()
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// Re-encodes as script bytes. Byte-exact with the decoded bytes if unmodified.
+    pub fn to_script_bytes(&self) -> Vec<u8> {
+        self.0.to_script_bytes()
+    }
+
+    pub fn to_flat_bytes(&self) -> Vec<u8> {
+        self.0.to_flat()
+    }
+
+    /// Plutus Core language version e.g. "1.1.0"
+    pub fn version(&self) -> String {
+        let (major, minor, patch) = self.0.version;
+        format!("{major}.{minor}.{patch}")
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        self.0.to_pretty_string()
+    }
+
+    pub fn flat_size(&self) -> usize {
+        self.0.stats().flat_size
+    }
+
+    pub fn script_size(&self) -> usize {
+        self.0.stats().script_size
+    }
+
+    pub fn term_count(&self) -> usize {
+        self.0.stats().terms
+    }
+
+    pub fn apply_data(&self, arguments: &PlutusDataList) -> UplcProgram {
+        self.0.clone().apply_data(arguments.as_ref()).into()
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{plutus::PlutusData, PlutusDataList, RedeemerList};
 use cml_chain::plutus::Language;
-use cml_core_wasm::{impl_wasm_cbor_api, impl_wasm_cbor_json_api, impl_wasm_conversions};
+use cml_core_wasm::impl_wasm_text_envelope_api;
+use cml_core_wasm::{
+    impl_wasm_cbor_api, impl_wasm_cbor_json_api, impl_wasm_conversions, CborValue,
+};
 use cml_crypto_wasm::ScriptHash;
 use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
 
@@ -32,6 +35,22 @@ impl ConstrPlutusData {
     }
 }
 
+#[wasm_bindgen]
+impl PlutusData {
+    /// Generic CBOR tree of this datum, keeping its encoding details
+    pub fn to_cbor_value(&self) -> CborValue {
+        cml_core::CborValue::from(self.as_ref()).into()
+    }
+
+    /// Fails if the CBOR is not a valid datum e.g. contains text or a float
+    pub fn from_cbor_value(value: &CborValue) -> Result<PlutusData, JsError> {
+        use std::convert::TryFrom;
+        cml_chain::plutus::PlutusData::try_from(value.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
+
 #[derive(Clone, Debug)]
 #[wasm_bindgen]
 pub struct PlutusMap(cml_chain::plutus::PlutusMap);
@@ -165,3 +184,7 @@ pub fn compute_total_ex_units(redeemers: &RedeemerList) -> Result<ExUnits, JsErr
         .map(Into::into)
         .map_err(Into::into)
 }
+
+impl_wasm_text_envelope_api!(PlutusV1Script);
+impl_wasm_text_envelope_api!(PlutusV2Script);
+impl_wasm_text_envelope_api!(PlutusV3Script);
@@ -2,12 +2,13 @@ use crate::{
     address::Address,
     transaction::{DatumOption, ScriptRef, TransactionOutput},
     utils::LanguageList,
-    Ed25519KeyHashList, NativeScript, Value,
+    Ed25519KeyHashList, NativeScript, Slot, Value,
 };
+use cml_core_wasm::impl_wasm_text_envelope_api;
 use cml_crypto_wasm::{DatumHash, ScriptHash};
-use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
 
-use super::TransactionWitnessSet;
+use super::{Transaction, TransactionWitnessSet};
 
 #[wasm_bindgen]
 impl TransactionOutput {
@@ -65,6 +66,21 @@ impl NativeScript {
         self.as_ref().get_required_signers().into()
     }
 
+    /// Evaluates the script the same way the ledger does.
+    /// lower_bound is inclusive, upper_bound exclusive and None means unbounded.
+    pub fn verify(
+        &self,
+        lower_bound: Option<Slot>,
+        upper_bound: Option<Slot>,
+        key_hashes: &Ed25519KeyHashList,
+    ) -> bool {
+        self.0.verify(
+            lower_bound,
+            upper_bound,
+            &key_hashes.as_ref().iter().copied().collect(),
+        )
+    }
+
     pub fn hash(&self) -> ScriptHash {
         self.0.hash().into()
     }
@@ -80,3 +96,6 @@ impl TransactionWitnessSet {
         self.0.languages().into()
     }
 }
+
+impl_wasm_text_envelope_api!(NativeScript);
+impl_wasm_text_envelope_api!(Transaction);
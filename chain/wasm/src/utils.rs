@@ -1,5 +1,6 @@
 use super::{Int, Script, ScriptHash};
 use cml_chain::plutus::Language;
+use cml_core_wasm::impl_wasm_text_envelope_api;
 use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
 
 use cml_core_wasm::{impl_wasm_cbor_json_api, impl_wasm_conversions, impl_wasm_list};
@@ -85,3 +86,5 @@ impl NetworkId {
         self.0.network
     }
 }
+
+impl_wasm_text_envelope_api!(Script);
@@ -0,0 +1,194 @@
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use base64::Engine;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::sha2::Sha256;
+use rand::RngCore;
+
+use crate::error::CIP20Error;
+use crate::{CIP20Message, CIP20String64};
+
+/// Passphrase used when none is agreed on between sender and receiver.
+/// This only hides the message from casual viewers of the chain.
+pub const CIP83_DEFAULT_PASSPHRASE: &str = "cardano";
+
+// The basic method is compatible with:
+// openssl enc -e -aes-256-cbc -pbkdf2 -iter 10000 -a -k <passphrase>
+const PBKDF2_ITER: u32 = 10_000;
+const SALT_HEADER: &[u8] = b"Salted__";
+const SALT_SIZE: usize = 8;
+const KEY_SIZE: usize = 32;
+const IV_SIZE: usize = 16;
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+
+#[wasm_bindgen::prelude::wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CIP83EncryptionMethod {
+    /// AES-256-CBC with PKCS7 padding. The key and IV are derived with PBKDF2-HMAC-SHA256
+    /// (10000 iterations) and the result is base64 encoded in the OpenSSL salted format.
+    Basic,
+}
+
+impl CIP83EncryptionMethod {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Basic => "basic",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(method: &str) -> Result<Self, CIP20Error> {
+        match method {
+            "basic" => Ok(Self::Basic),
+            _ => Err(CIP20Error::UnsupportedEncryption(method.to_owned())),
+        }
+    }
+}
+
+/// A CIP-83 encrypted CIP-20 message.
+/// The plaintext is the message's `{"msg":[...]}` JSON and the chunks are its base64 encoded ciphertext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP83EncryptedMessage {
+    pub method: CIP83EncryptionMethod,
+    pub msg: Vec<CIP20String64>,
+}
+
+impl CIP83EncryptedMessage {
+    pub fn new(method: CIP83EncryptionMethod, msg: Vec<CIP20String64>) -> Self {
+        Self { method, msg }
+    }
+
+    /// Encrypts with the basic method using a random salt.
+    /// Use `CIP83_DEFAULT_PASSPHRASE` if no passphrase was agreed upon.
+    pub fn encrypt(message: &CIP20Message, passphrase: &str) -> Self {
+        let mut salt = [0u8; SALT_SIZE];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::encrypt_with_salt(message, passphrase, salt)
+    }
+
+    fn encrypt_with_salt(message: &CIP20Message, passphrase: &str, salt: [u8; SALT_SIZE]) -> Self {
+        let lines = message
+            .msg
+            .iter()
+            .map(CIP20String64::to_str)
+            .collect::<Vec<_>>();
+        let plaintext = serde_json::json!({ "msg": lines }).to_string();
+        let (key, iv) = derive_key_iv(passphrase, &salt);
+        let ciphertext = Aes256CbcEnc::new(&key.into(), &iv.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(plaintext.as_bytes());
+        let mut bytes = Vec::with_capacity(SALT_HEADER.len() + SALT_SIZE + ciphertext.len());
+        bytes.extend_from_slice(SALT_HEADER);
+        bytes.extend_from_slice(&salt);
+        bytes.extend_from_slice(&ciphertext);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        Self::new(CIP83EncryptionMethod::Basic, CIP20String64::chunk(&encoded))
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<CIP20Message, CIP20Error> {
+        match self.method {
+            CIP83EncryptionMethod::Basic => {}
+        }
+        let encoded = self
+            .msg
+            .iter()
+            .map(CIP20String64::to_str)
+            .collect::<String>();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+        if bytes.len() < SALT_HEADER.len() + SALT_SIZE || !bytes.starts_with(SALT_HEADER) {
+            return Err(CIP20Error::MissingSalt);
+        }
+        let (salt, ciphertext) = bytes[SALT_HEADER.len()..].split_at(SALT_SIZE);
+        let (key, iv) = derive_key_iv(passphrase, salt);
+        let plaintext = Aes256CbcDec::new(&key.into(), &iv.into())
+            .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+            .map_err(|_| CIP20Error::DecryptionFailed)?;
+        let json: serde_json::Value =
+            serde_json::from_slice(&plaintext).map_err(|_| CIP20Error::DecryptionFailed)?;
+        let msg = json
+            .get("msg")
+            .and_then(serde_json::Value::as_array)
+            .ok_or(CIP20Error::InvalidPlaintext)?
+            .iter()
+            .map(|line| {
+                line.as_str()
+                    .ok_or(CIP20Error::InvalidPlaintext)
+                    .and_then(|line| CIP20String64::new_str(line).map_err(Into::into))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CIP20Message::new(msg))
+    }
+}
+
+fn derive_key_iv(passphrase: &str, salt: &[u8]) -> ([u8; KEY_SIZE], [u8; IV_SIZE]) {
+    let mut mac = Hmac::new(Sha256::new(), passphrase.as_bytes());
+    let mut derived = [0u8; KEY_SIZE + IV_SIZE];
+    pbkdf2(&mut mac, salt, PBKDF2_ITER, &mut derived);
+    let mut key = [0u8; KEY_SIZE];
+    let mut iv = [0u8; IV_SIZE];
+    key.copy_from_slice(&derived[..KEY_SIZE]);
+    iv.copy_from_slice(&derived[KEY_SIZE..]);
+    (key, iv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CIP20Metadata;
+
+    // generated with:
+    // echo -n '{"msg":["Invoice-No: 1234567890","Customer-No: 555-1234"]}' \
+    //   | openssl enc -e -aes-256-cbc -pbkdf2 -iter 10000 -a -k cardano
+    const OPENSSL_CHUNKS: [&str; 2] = [
+        "U2FsdGVkX1+5+hHykJ10atld2beXDmh/pvp+y8qxFFCZW++Ndl68l1sql94+nunx",
+        "hXn5YvUEdOdYxyewKoAyG+BN6+PLev3V+AeUhfzVr9s=",
+    ];
+
+    fn message() -> CIP20Message {
+        CIP20Message::new_lines(&["Invoice-No: 1234567890", "Customer-No: 555-1234"])
+    }
+
+    #[test]
+    fn openssl_compatible() {
+        let encrypted = CIP83EncryptedMessage::encrypt_with_salt(
+            &message(),
+            CIP83_DEFAULT_PASSPHRASE,
+            [0xb9, 0xfa, 0x11, 0xf2, 0x90, 0x9d, 0x74, 0x6a],
+        );
+        assert_eq!(
+            encrypted
+                .msg
+                .iter()
+                .map(CIP20String64::to_str)
+                .collect::<Vec<_>>(),
+            OPENSSL_CHUNKS
+        );
+        let from_openssl = CIP83EncryptedMessage::new(
+            CIP83EncryptionMethod::Basic,
+            OPENSSL_CHUNKS
+                .iter()
+                .map(|chunk| CIP20String64::new_str(chunk).unwrap())
+                .collect(),
+        );
+        assert_eq!(
+            from_openssl.decrypt(CIP83_DEFAULT_PASSPHRASE).unwrap(),
+            message()
+        );
+    }
+
+    #[test]
+    fn encrypted_metadata_round_trip() {
+        let encrypted = CIP83EncryptedMessage::encrypt(&message(), "secret");
+        let metadata = CIP20Metadata::new_encrypted(encrypted).to_metadata();
+        let parsed = match CIP20Metadata::from_metadata(&metadata).unwrap() {
+            CIP20Metadata::Encrypted(encrypted) => encrypted,
+            CIP20Metadata::Message(_) => panic!("expected an encrypted message"),
+        };
+        assert_eq!(parsed.decrypt("secret").unwrap(), message());
+        assert!(matches!(
+            parsed.decrypt(CIP83_DEFAULT_PASSPHRASE),
+            Err(CIP20Error::DecryptionFailed)
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+use cml_core::error::DeserializeError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CIP20Error {
+    #[error("Deserialize: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Invalid CIP-20 message: {0}")]
+    InvalidFormat(&'static str),
+    #[error("Unsupported CIP-83 encryption method: {0}")]
+    UnsupportedEncryption(String),
+    #[error("Encrypted message is not valid base64: {0}")]
+    InvalidBase64(#[from] base64::DecodeError),
+    #[error("Encrypted message is missing the salt header")]
+    MissingSalt,
+    #[error("Decryption failed (wrong passphrase?)")]
+    DecryptionFailed,
+    #[error("Decrypted message is not a CIP-20 msg JSON object")]
+    InvalidPlaintext,
+}
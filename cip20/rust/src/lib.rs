@@ -0,0 +1,292 @@
+//! CIP-20 transaction messages (metadata label 674) and CIP-83 encrypted messages.
+//!
+//! A message is stored as `{ "msg": [* text] }` where each text is at most 64 bytes.
+//! Encrypted messages (CIP-83) additionally have an `"enc"` key naming the encryption method
+//! and their `"msg"` chunks hold the base64 encoded ciphertext instead.
+
+pub use cml_chain::auxdata::{AuxiliaryData, Metadata, MetadatumMap, TransactionMetadatum};
+pub use cml_core::error::{DeserializeError, DeserializeFailure, Key};
+
+pub mod encryption;
+pub mod error;
+
+pub use encryption::{CIP83EncryptedMessage, CIP83EncryptionMethod, CIP83_DEFAULT_PASSPHRASE};
+pub use error::CIP20Error;
+
+use std::convert::TryFrom;
+
+pub static CIP20_METADATA_LABEL: u64 = 674;
+
+const MSG_KEY: &str = "msg";
+const ENC_KEY: &str = "enc";
+
+/// A String of at most 64 bytes.
+/// This is to conform with Cardano metadata restrictions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP20String64(String);
+
+impl CIP20String64 {
+    pub fn new_str(inner: &str) -> Result<Self, DeserializeError> {
+        if inner.len() > 64 {
+            return Err(DeserializeError::new(
+                "CIP20String64",
+                DeserializeFailure::RangeCheck {
+                    found: inner.len() as isize,
+                    min: Some(0),
+                    max: Some(64),
+                },
+            ));
+        }
+        Ok(Self(inner.to_owned()))
+    }
+
+    pub fn to_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Splits a string of any length into chunks of at most 64 bytes.
+    /// Chunks are only split on character boundaries.
+    pub fn chunk(s: &str) -> Vec<Self> {
+        let mut chunks = Vec::with_capacity(s.len() / 64 + 1);
+        let mut start = 0;
+        while start < s.len() {
+            let mut end = std::cmp::min(s.len(), start + 64);
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            chunks.push(Self(s[start..end].to_owned()));
+            start = end;
+        }
+        chunks
+    }
+}
+
+impl TryFrom<&str> for CIP20String64 {
+    type Error = DeserializeError;
+
+    fn try_from(inner: &str) -> Result<Self, Self::Error> {
+        CIP20String64::new_str(inner)
+    }
+}
+
+impl From<CIP20String64> for String {
+    fn from(wrapper: CIP20String64) -> Self {
+        wrapper.0
+    }
+}
+
+/// A plain CIP-20 message.
+/// Unless you have good reasons, you should be using the From<&str> trait or `new_lines`
+/// to construct this as they automatically chunk the message into 64-byte pieces:
+/// ```
+/// use cml_cip20::CIP20Message;
+/// let message = CIP20Message::from("this can be any length and will automatically be chunked into 64-byte pieces when/if needed");
+/// assert_eq!(message.msg.len(), 2);
+/// assert_eq!(message.msg[0].to_str(), "this can be any length and will automatically be chunked into 64");
+/// assert_eq!(message.text(), "this can be any length and will automatically be chunked into 64-byte pieces when/if needed");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP20Message {
+    pub msg: Vec<CIP20String64>,
+}
+
+impl CIP20Message {
+    /// Construct from an explicit list of chunks
+    /// If size is not known or for simplicity use From<&str> or `new_lines` instead
+    pub fn new(msg: Vec<CIP20String64>) -> Self {
+        Self { msg }
+    }
+
+    /// Each line is chunked separately so that no chunk spans two lines
+    pub fn new_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        Self {
+            msg: lines
+                .iter()
+                .flat_map(|line| CIP20String64::chunk(line.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// All chunks concatenated together
+    pub fn text(&self) -> String {
+        self.msg.iter().map(CIP20String64::to_str).collect()
+    }
+}
+
+impl From<&str> for CIP20Message {
+    fn from(s: &str) -> Self {
+        Self {
+            msg: CIP20String64::chunk(s),
+        }
+    }
+}
+
+/// The contents of metadata label 674: either a plain or an encrypted message
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CIP20Metadata {
+    Message(CIP20Message),
+    Encrypted(CIP83EncryptedMessage),
+}
+
+impl CIP20Metadata {
+    pub fn new_message(message: CIP20Message) -> Self {
+        Self::Message(message)
+    }
+
+    pub fn new_encrypted(encrypted: CIP83EncryptedMessage) -> Self {
+        Self::Encrypted(encrypted)
+    }
+
+    /// Create a Metadata containing only the CIP20 message
+    pub fn to_metadata(&self) -> Metadata {
+        let mut metadata = Metadata::new();
+        self.add_to_metadata(&mut metadata);
+        metadata
+    }
+
+    /// Read the CIP20 message from a Metadata. Ignores all other data besides CIP20
+    pub fn from_metadata(metadata: &Metadata) -> Result<CIP20Metadata, CIP20Error> {
+        Self::try_from(metadata)
+    }
+
+    /// Add to an existing metadata (could be empty) the CIP20 message
+    pub fn add_to_metadata(&self, metadata: &mut Metadata) {
+        metadata.set(CIP20_METADATA_LABEL, self.to_metadatum());
+    }
+
+    /// Reads the CIP20 message (if any) from a transaction's auxiliary data
+    pub fn from_auxiliary_data(
+        auxiliary_data: &AuxiliaryData,
+    ) -> Result<Option<CIP20Metadata>, CIP20Error> {
+        match auxiliary_data.metadata() {
+            Some(metadata) if metadata.get(CIP20_METADATA_LABEL).is_some() => {
+                Self::from_metadata(metadata).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn to_metadatum(&self) -> TransactionMetadatum {
+        let (enc, msg) = match self {
+            Self::Message(message) => (None, &message.msg),
+            Self::Encrypted(encrypted) => (Some(encrypted.method.to_str()), &encrypted.msg),
+        };
+        let mut map = MetadatumMap::new();
+        if let Some(enc) = enc {
+            map.set(text(ENC_KEY), text(enc));
+        }
+        map.set(
+            text(MSG_KEY),
+            TransactionMetadatum::new_list(msg.iter().map(|chunk| text(chunk.to_str())).collect()),
+        );
+        TransactionMetadatum::new_map(map)
+    }
+}
+
+impl TryFrom<&Metadata> for CIP20Metadata {
+    type Error = CIP20Error;
+
+    fn try_from(metadata: &Metadata) -> Result<Self, Self::Error> {
+        let map = metadata
+            .get(CIP20_METADATA_LABEL)
+            .ok_or_else(|| {
+                DeserializeError::new(
+                    "CIP20Metadata",
+                    DeserializeFailure::MandatoryFieldMissing(Key::Uint(CIP20_METADATA_LABEL)),
+                )
+            })?
+            .as_map()
+            .ok_or(CIP20Error::InvalidFormat("label 674 must be a map"))?;
+        let msg = map
+            .get_str(MSG_KEY)
+            .ok_or_else(|| {
+                DeserializeError::new(
+                    "CIP20Metadata",
+                    DeserializeFailure::MandatoryFieldMissing(Key::Str(MSG_KEY.to_owned())),
+                )
+            })?
+            .as_list()
+            .ok_or(CIP20Error::InvalidFormat("msg must be a list"))?
+            .iter()
+            .map(|chunk| {
+                chunk
+                    .as_text()
+                    .ok_or(CIP20Error::InvalidFormat("msg chunks must be text"))
+                    .and_then(|chunk| CIP20String64::new_str(chunk).map_err(Into::into))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match map.get_str(ENC_KEY) {
+            None => Ok(Self::Message(CIP20Message::new(msg))),
+            Some(enc) => {
+                let enc = enc
+                    .as_text()
+                    .ok_or(CIP20Error::InvalidFormat("enc must be text"))?;
+                Ok(Self::Encrypted(CIP83EncryptedMessage::new(
+                    CIP83EncryptionMethod::from_str(enc)?,
+                    msg,
+                )))
+            }
+        }
+    }
+}
+
+// all our keys/chunks are at most 64 bytes
+fn text(s: &str) -> TransactionMetadatum {
+    TransactionMetadatum::new_text(s.to_owned()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_core::serialization::{Deserialize, Serialize};
+
+    #[test]
+    fn chunking() {
+        let long = "é".repeat(40);
+        let message = CIP20Message::from(long.as_str());
+        assert_eq!(message.msg.len(), 2);
+        assert_eq!(message.msg[0].to_str().len(), 64);
+        assert_eq!(message.text(), long);
+        let lines = CIP20Message::new_lines(&["Invoice-No: 1234567890", "Customer-No: 555-1234"]);
+        assert_eq!(lines.msg.len(), 2);
+        assert!(CIP20Message::from("").msg.is_empty());
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let cip20 = CIP20Metadata::new_message(CIP20Message::new_lines(&[
+            "Invoice-No: 1234567890",
+            "Customer-No: 555-1234",
+        ]));
+        let metadata = cip20.to_metadata();
+        // {674: {"msg": ["Invoice-No: 1234567890", "Customer-No: 555-1234"]}}
+        assert_eq!(
+            hex::encode(metadata.to_cbor_bytes()),
+            "a11902a2a1636d73678276496e766f6963652d4e6f3a203132333435363738393075437573746f6d65722d4e6f3a203535352d31323334"
+        );
+        let parsed = Metadata::from_cbor_bytes(&metadata.to_cbor_bytes()).unwrap();
+        assert_eq!(CIP20Metadata::from_metadata(&parsed).unwrap(), cip20);
+        let mut auxiliary_data = AuxiliaryData::new();
+        auxiliary_data.add_metadata(parsed);
+        assert_eq!(
+            CIP20Metadata::from_auxiliary_data(&auxiliary_data).unwrap(),
+            Some(cip20)
+        );
+        assert_eq!(
+            CIP20Metadata::from_auxiliary_data(&AuxiliaryData::new()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_metadata() {
+        let mut metadata = Metadata::new();
+        metadata.set(CIP20_METADATA_LABEL, text("not a map"));
+        assert!(CIP20Metadata::from_metadata(&metadata).is_err());
+        let mut map = MetadatumMap::new();
+        map.set(text(ENC_KEY), text("basic"));
+        metadata.set(CIP20_METADATA_LABEL, TransactionMetadatum::new_map(map));
+        assert!(CIP20Metadata::from_metadata(&metadata).is_err());
+    }
+}
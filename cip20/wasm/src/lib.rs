@@ -0,0 +1,186 @@
+#![allow(
+    clippy::len_without_is_empty,
+    clippy::too_many_arguments,
+    clippy::new_without_default
+)]
+
+use cml_chain_wasm::auxdata::{AuxiliaryData, Metadata};
+use cml_core_wasm::{impl_wasm_conversions, impl_wasm_list};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+pub use cml_cip20::CIP83EncryptionMethod;
+
+/// A String of at most 64 bytes.
+/// This is to conform with Cardano metadata restrictions.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP20String64(cml_cip20::CIP20String64);
+
+impl_wasm_conversions!(cml_cip20::CIP20String64, CIP20String64);
+
+#[wasm_bindgen]
+impl CIP20String64 {
+    pub fn new(s: &str) -> Result<CIP20String64, JsError> {
+        cml_cip20::CIP20String64::new_str(s)
+            .map(Self)
+            .map_err(Into::into)
+    }
+
+    pub fn get(&self) -> String {
+        self.0.to_str().to_owned()
+    }
+}
+
+impl_wasm_list!(cml_cip20::CIP20String64, CIP20String64, CIP20String64List);
+
+/// A plain CIP-20 message
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP20Message(cml_cip20::CIP20Message);
+
+impl_wasm_conversions!(cml_cip20::CIP20Message, CIP20Message);
+
+#[wasm_bindgen]
+impl CIP20Message {
+    /// Construct from an explicit list of chunks
+    /// If size is not known or for simplicity use from_text() or new_lines() instead
+    pub fn new(msg: &CIP20String64List) -> Self {
+        cml_cip20::CIP20Message::new(msg.clone().into()).into()
+    }
+
+    /// Automatically chunks the text into 64-byte pieces
+    pub fn from_text(text: &str) -> Self {
+        cml_cip20::CIP20Message::from(text).into()
+    }
+
+    /// Each line is chunked separately so that no chunk spans two lines
+    pub fn new_lines(lines: Vec<String>) -> Self {
+        cml_cip20::CIP20Message::new_lines(&lines).into()
+    }
+
+    pub fn msg(&self) -> CIP20String64List {
+        self.0.msg.clone().into()
+    }
+
+    /// All chunks concatenated together
+    pub fn text(&self) -> String {
+        self.0.text()
+    }
+}
+
+/// A CIP-83 encrypted CIP-20 message
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP83EncryptedMessage(cml_cip20::CIP83EncryptedMessage);
+
+impl_wasm_conversions!(cml_cip20::CIP83EncryptedMessage, CIP83EncryptedMessage);
+
+#[wasm_bindgen]
+impl CIP83EncryptedMessage {
+    pub fn new(method: CIP83EncryptionMethod, msg: &CIP20String64List) -> Self {
+        cml_cip20::CIP83EncryptedMessage::new(method, msg.clone().into()).into()
+    }
+
+    /// Encrypts with the basic method using a random salt.
+    /// If no passphrase is given the CIP-83 default passphrase is used.
+    pub fn encrypt(message: &CIP20Message, passphrase: Option<String>) -> Self {
+        cml_cip20::CIP83EncryptedMessage::encrypt(
+            message.as_ref(),
+            passphrase
+                .as_deref()
+                .unwrap_or(cml_cip20::CIP83_DEFAULT_PASSPHRASE),
+        )
+        .into()
+    }
+
+    /// If no passphrase is given the CIP-83 default passphrase is used.
+    pub fn decrypt(&self, passphrase: Option<String>) -> Result<CIP20Message, JsError> {
+        self.0
+            .decrypt(
+                passphrase
+                    .as_deref()
+                    .unwrap_or(cml_cip20::CIP83_DEFAULT_PASSPHRASE),
+            )
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn method(&self) -> CIP83EncryptionMethod {
+        self.0.method
+    }
+
+    pub fn msg(&self) -> CIP20String64List {
+        self.0.msg.clone().into()
+    }
+}
+
+/// The contents of metadata label 674: either a plain or an encrypted message
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP20Metadata(cml_cip20::CIP20Metadata);
+
+impl_wasm_conversions!(cml_cip20::CIP20Metadata, CIP20Metadata);
+
+#[wasm_bindgen]
+impl CIP20Metadata {
+    pub fn new_message(message: &CIP20Message) -> Self {
+        cml_cip20::CIP20Metadata::new_message(message.clone().into()).into()
+    }
+
+    pub fn new_encrypted(encrypted: &CIP83EncryptedMessage) -> Self {
+        cml_cip20::CIP20Metadata::new_encrypted(encrypted.clone().into()).into()
+    }
+
+    pub fn kind(&self) -> CIP20MetadataKind {
+        match &self.0 {
+            cml_cip20::CIP20Metadata::Message(_) => CIP20MetadataKind::Message,
+            cml_cip20::CIP20Metadata::Encrypted(_) => CIP20MetadataKind::Encrypted,
+        }
+    }
+
+    pub fn as_message(&self) -> Option<CIP20Message> {
+        match &self.0 {
+            cml_cip20::CIP20Metadata::Message(message) => Some(message.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_encrypted(&self) -> Option<CIP83EncryptedMessage> {
+        match &self.0 {
+            cml_cip20::CIP20Metadata::Encrypted(encrypted) => Some(encrypted.clone().into()),
+            _ => None,
+        }
+    }
+
+    /// Create a Metadata containing only the CIP20 message
+    pub fn to_metadata(&self) -> Metadata {
+        self.0.to_metadata().into()
+    }
+
+    /// Read the CIP20 message from a Metadata. Ignores all other data besides CIP20
+    pub fn from_metadata(metadata: &Metadata) -> Result<CIP20Metadata, JsError> {
+        cml_cip20::CIP20Metadata::from_metadata(metadata.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// Add to an existing metadata (could be empty) the CIP20 message
+    pub fn add_to_metadata(&self, metadata: &mut Metadata) {
+        self.0.add_to_metadata(metadata.as_mut())
+    }
+
+    /// Reads the CIP20 message (if any) from a transaction's auxiliary data
+    pub fn from_auxiliary_data(
+        auxiliary_data: &AuxiliaryData,
+    ) -> Result<Option<CIP20Metadata>, JsError> {
+        cml_cip20::CIP20Metadata::from_auxiliary_data(auxiliary_data.as_ref())
+            .map(|cip20| cip20.map(Into::into))
+            .map_err(Into::into)
+    }
+}
+
+#[wasm_bindgen]
+pub enum CIP20MetadataKind {
+    Message,
+    Encrypted,
+}
@@ -0,0 +1,400 @@
+//! CIP-27 royalties (metadata label 777).
+//!
+//! Royalties are declared by minting a token with an empty asset name under the collection's
+//! policy with `{777: {"rate": text, "addr": text / [* text]}}` metadata and then burning it
+//! so that the royalty can never be changed. Marketplaces use the metadata of the first such mint.
+
+use std::convert::TryFrom;
+
+use cml_chain::address::{Address, AddressError};
+use cml_chain::assets::AssetName;
+use cml_chain::auxdata::{AuxiliaryData, Metadata, MetadatumMap, TransactionMetadatum};
+use cml_chain::builders::mint_builder::{MintBuilderResult, SingleMintBuilder};
+use cml_chain::builders::tx_builder::{TransactionBuilder, TxBuilderError};
+use cml_chain::builders::witness_builder::{NativeScriptWitnessInfo, PartialPlutusWitness};
+use cml_chain::transaction::{NativeScript, RequiredSigners};
+use cml_core::error::{DeserializeError, DeserializeFailure, Key};
+
+pub static CIP27_METADATA_LABEL: u64 = 777;
+
+const RATE_KEY: &str = "rate";
+// early versions of the CIP used "pct" which is still found on-chain
+const LEGACY_RATE_KEY: &str = "pct";
+const ADDR_KEY: &str = "addr";
+const MAX_RATE_DECIMALS: usize = 18;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CIP27Error {
+    #[error("Deserialization: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Invalid royalty rate {0}: must be a decimal between 0 and 1")]
+    InvalidRate(String),
+    #[error("Basis points {0} over 10000")]
+    InvalidBasisPoints(u16),
+    #[error("Invalid format: {0}")]
+    InvalidFormat(&'static str),
+    #[error("Address: {0}")]
+    Address(#[from] AddressError),
+}
+
+/// Royalty rate as the decimal text stored on-chain e.g. "0.025" for 2.5%
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CIP27RoyaltyRate(String);
+
+impl CIP27RoyaltyRate {
+    /// Must be a plain decimal in [0, 1] with at most 18 decimal places
+    pub fn new(rate: &str) -> Result<Self, CIP27Error> {
+        let invalid = || CIP27Error::InvalidRate(rate.to_owned());
+        let (integer, decimals) = match rate.split_once('.') {
+            Some((integer, decimals)) => (integer, decimals),
+            None => (rate, ""),
+        };
+        if integer.is_empty()
+            || !integer.chars().all(|c| c.is_ascii_digit())
+            || !decimals.chars().all(|c| c.is_ascii_digit())
+            || (rate.contains('.') && decimals.is_empty())
+            || decimals.len() > MAX_RATE_DECIMALS
+        {
+            return Err(invalid());
+        }
+        let integer = integer.trim_start_matches('0');
+        let in_range = integer.is_empty() || (integer == "1" && decimals.chars().all(|c| c == '0'));
+        if !in_range {
+            return Err(invalid());
+        }
+        Ok(Self(rate.to_owned()))
+    }
+
+    /// e.g. 250 basis points is a rate of "0.025"
+    pub fn from_basis_points(basis_points: u16) -> Result<Self, CIP27Error> {
+        if basis_points > 10_000 {
+            return Err(CIP27Error::InvalidBasisPoints(basis_points));
+        }
+        let rate = format!("{}.{:04}", basis_points / 10_000, basis_points % 10_000);
+        let rate = rate.trim_end_matches('0').trim_end_matches('.');
+        Ok(Self(rate.to_owned()))
+    }
+
+    pub fn to_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        // always a valid decimal
+        self.0.parse().unwrap()
+    }
+
+    /// Exact (numerator, denominator) with the denominator being a power of 10
+    pub fn as_fraction(&self) -> (u64, u64) {
+        let (integer, decimals) = self.0.split_once('.').unwrap_or((&self.0, ""));
+        let denominator = 10u64.pow(decimals.len() as u32);
+        let integer: u64 = integer.parse().unwrap();
+        let decimals: u64 = if decimals.is_empty() {
+            0
+        } else {
+            decimals.parse().unwrap()
+        };
+        (integer * denominator + decimals, denominator)
+    }
+}
+
+impl std::fmt::Display for CIP27RoyaltyRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The contents of metadata label 777
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP27RoyaltyMetadata {
+    pub rate: CIP27RoyaltyRate,
+    /// Where royalties should be paid to
+    pub addr: Address,
+}
+
+impl CIP27RoyaltyMetadata {
+    pub fn new(rate: CIP27RoyaltyRate, addr: Address) -> Self {
+        Self { rate, addr }
+    }
+
+    /// Create a Metadata containing only the CIP27 royalty
+    pub fn to_metadata(&self) -> Result<Metadata, CIP27Error> {
+        let mut metadata = Metadata::new();
+        self.add_to_metadata(&mut metadata)?;
+        Ok(metadata)
+    }
+
+    /// Read the CIP27 royalty from a Metadata. Ignores all other data besides CIP27
+    pub fn from_metadata(metadata: &Metadata) -> Result<Self, CIP27Error> {
+        Self::try_from(metadata)
+    }
+
+    /// Add to an existing metadata (could be empty) the CIP27 royalty
+    pub fn add_to_metadata(&self, metadata: &mut Metadata) -> Result<(), CIP27Error> {
+        let addr = self.addr.to_bech32(None)?;
+        let addr = if addr.len() > 64 {
+            // bech32 is ascii so any byte is a char boundary
+            TransactionMetadatum::new_list(
+                addr.as_bytes()
+                    .chunks(64)
+                    .map(|chunk| text(std::str::from_utf8(chunk).unwrap()))
+                    .collect(),
+            )
+        } else {
+            text(&addr)
+        };
+        let mut map = MetadatumMap::new();
+        map.set(text(RATE_KEY), text(self.rate.to_str()));
+        map.set(text(ADDR_KEY), addr);
+        metadata.set(CIP27_METADATA_LABEL, TransactionMetadatum::new_map(map));
+        Ok(())
+    }
+}
+
+impl TryFrom<&Metadata> for CIP27RoyaltyMetadata {
+    type Error = CIP27Error;
+
+    fn try_from(metadata: &Metadata) -> Result<Self, Self::Error> {
+        let missing = |key: Key| {
+            DeserializeError::new(
+                "CIP27RoyaltyMetadata",
+                DeserializeFailure::MandatoryFieldMissing(key),
+            )
+        };
+        let map = metadata
+            .get(CIP27_METADATA_LABEL)
+            .ok_or_else(|| missing(Key::Uint(CIP27_METADATA_LABEL)))?
+            .as_map()
+            .ok_or(CIP27Error::InvalidFormat("label 777 must be a map"))?;
+        let rate = map
+            .get_str(RATE_KEY)
+            .or_else(|| map.get_str(LEGACY_RATE_KEY))
+            .ok_or_else(|| missing(Key::Str(RATE_KEY.to_owned())))?
+            .as_text()
+            .ok_or(CIP27Error::InvalidFormat("rate must be text"))?;
+        let addr = match map
+            .get_str(ADDR_KEY)
+            .ok_or_else(|| missing(Key::Str(ADDR_KEY.to_owned())))?
+        {
+            TransactionMetadatum::Text { text, .. } => text.clone(),
+            TransactionMetadatum::List { elements, .. } => elements
+                .iter()
+                .map(|chunk| {
+                    chunk
+                        .as_text()
+                        .map(String::as_str)
+                        .ok_or(CIP27Error::InvalidFormat("addr chunks must be text"))
+                })
+                .collect::<Result<String, _>>()?,
+            _ => {
+                return Err(CIP27Error::InvalidFormat(
+                    "addr must be text or a list of text",
+                ))
+            }
+        };
+        Ok(Self::new(
+            CIP27RoyaltyRate::new(rate)?,
+            Address::from_bech32(&addr)?,
+        ))
+    }
+}
+
+/// The mint of the royalty token with its metadata and the burn that must follow in a later transaction
+#[derive(Clone)]
+pub struct CIP27RoyaltyTokenResult {
+    pub mint: MintBuilderResult,
+    pub burn: MintBuilderResult,
+    pub auxiliary_data: AuxiliaryData,
+}
+
+impl CIP27RoyaltyTokenResult {
+    /// Adds the royalty token mint along with its metadata
+    #[allow(clippy::result_large_err)]
+    pub fn add_mint(&self, builder: &mut TransactionBuilder) -> Result<(), TxBuilderError> {
+        builder.add_mint(self.mint.clone())?;
+        builder.add_auxiliary_data(self.auxiliary_data.clone());
+        Ok(())
+    }
+
+    /// Adds the royalty token burn. The mint must already be on-chain.
+    #[allow(clippy::result_large_err)]
+    pub fn add_burn(&self, builder: &mut TransactionBuilder) -> Result<(), TxBuilderError> {
+        builder.add_mint(self.burn.clone())
+    }
+}
+
+/// Creates the mint and burn of the (empty asset name) royalty token for a policy.
+/// The policy must still allow minting as the royalty token has to be the first one minted.
+#[derive(Clone, Debug)]
+pub struct CIP27RoyaltyTokenBuilder {
+    royalty: CIP27RoyaltyMetadata,
+}
+
+impl CIP27RoyaltyTokenBuilder {
+    pub fn new(royalty: CIP27RoyaltyMetadata) -> Self {
+        Self { royalty }
+    }
+
+    pub fn royalty(&self) -> &CIP27RoyaltyMetadata {
+        &self.royalty
+    }
+
+    /// The royalty token has an empty asset name
+    pub fn asset_name() -> AssetName {
+        AssetName::new(Vec::new()).unwrap()
+    }
+
+    pub fn native_script(
+        &self,
+        native_script: NativeScript,
+        witness_info: NativeScriptWitnessInfo,
+    ) -> Result<CIP27RoyaltyTokenResult, CIP27Error> {
+        let mint = SingleMintBuilder::new_single_asset(Self::asset_name(), 1)
+            .native_script(native_script.clone(), witness_info.clone());
+        let burn = SingleMintBuilder::new_single_asset(Self::asset_name(), -1)
+            .native_script(native_script, witness_info);
+        self.with_mint_and_burn(mint, burn)
+    }
+
+    pub fn plutus_script(
+        &self,
+        partial_witness: PartialPlutusWitness,
+        required_signers: RequiredSigners,
+    ) -> Result<CIP27RoyaltyTokenResult, CIP27Error> {
+        let mint = SingleMintBuilder::new_single_asset(Self::asset_name(), 1)
+            .plutus_script(partial_witness.clone(), required_signers.clone());
+        let burn = SingleMintBuilder::new_single_asset(Self::asset_name(), -1)
+            .plutus_script(partial_witness, required_signers);
+        self.with_mint_and_burn(mint, burn)
+    }
+
+    fn with_mint_and_burn(
+        &self,
+        mint: MintBuilderResult,
+        burn: MintBuilderResult,
+    ) -> Result<CIP27RoyaltyTokenResult, CIP27Error> {
+        let mut auxiliary_data = AuxiliaryData::new();
+        auxiliary_data.add_metadata(self.royalty.to_metadata()?);
+        Ok(CIP27RoyaltyTokenResult {
+            mint,
+            burn,
+            auxiliary_data,
+        })
+    }
+}
+
+// all our keys/chunks are at most 64 bytes
+fn text(s: &str) -> TransactionMetadatum {
+    TransactionMetadatum::new_text(s.to_owned()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_chain::address::BaseAddress;
+    use cml_chain::certs::StakeCredential;
+    use cml_core::serialization::{Deserialize, Serialize};
+    use cml_crypto::Ed25519KeyHash;
+
+    const ADDR: &str = "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8";
+
+    #[test]
+    fn royalty_rate() {
+        for valid in ["0", "0.025", "1", "1.000", "00.5"] {
+            assert!(CIP27RoyaltyRate::new(valid).is_ok(), "{}", valid);
+        }
+        for invalid in [
+            "",
+            ".5",
+            "0.",
+            "1.01",
+            "2",
+            "-0.1",
+            "0.1e1",
+            "0.0000000000000000001",
+        ] {
+            assert!(CIP27RoyaltyRate::new(invalid).is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            CIP27RoyaltyRate::from_basis_points(250).unwrap().to_str(),
+            "0.025"
+        );
+        assert_eq!(
+            CIP27RoyaltyRate::from_basis_points(10_000)
+                .unwrap()
+                .to_str(),
+            "1"
+        );
+        assert_eq!(
+            CIP27RoyaltyRate::from_basis_points(0).unwrap().to_str(),
+            "0"
+        );
+        assert!(CIP27RoyaltyRate::from_basis_points(10_001).is_err());
+        let rate = CIP27RoyaltyRate::new("0.025").unwrap();
+        assert_eq!(rate.as_fraction(), (25, 1000));
+        assert_eq!(rate.as_f64(), 0.025);
+        assert_eq!(CIP27RoyaltyRate::new("1").unwrap().as_fraction(), (1, 1));
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let base_addr = BaseAddress::new(
+            1,
+            StakeCredential::new_pub_key(Ed25519KeyHash::from([1; 28])),
+            StakeCredential::new_pub_key(Ed25519KeyHash::from([2; 28])),
+        )
+        .to_address();
+        for addr in [Address::from_bech32(ADDR).unwrap(), base_addr] {
+            let addr_len = addr.to_bech32(None).unwrap().len();
+            let royalty = CIP27RoyaltyMetadata::new(CIP27RoyaltyRate::new("0.05").unwrap(), addr);
+            let bytes = royalty.to_metadata().unwrap().to_cbor_bytes();
+            let metadata = Metadata::from_cbor_bytes(&bytes).unwrap();
+            let addr_metadatum = metadata
+                .get(CIP27_METADATA_LABEL)
+                .unwrap()
+                .as_map()
+                .unwrap()
+                .get_str(ADDR_KEY)
+                .unwrap();
+            assert_eq!(addr_metadatum.as_list().is_some(), addr_len > 64);
+            assert_eq!(
+                CIP27RoyaltyMetadata::from_metadata(&metadata).unwrap(),
+                royalty
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_pct_key() {
+        let mut map = MetadatumMap::new();
+        map.set(text(LEGACY_RATE_KEY), text("0.2"));
+        map.set(text(ADDR_KEY), text(ADDR));
+        let mut metadata = Metadata::new();
+        metadata.set(CIP27_METADATA_LABEL, TransactionMetadatum::new_map(map));
+        let royalty = CIP27RoyaltyMetadata::from_metadata(&metadata).unwrap();
+        assert_eq!(royalty.rate.to_str(), "0.2");
+    }
+
+    #[test]
+    fn royalty_token() {
+        let royalty = CIP27RoyaltyMetadata::new(
+            CIP27RoyaltyRate::from_basis_points(500).unwrap(),
+            Address::from_bech32(ADDR).unwrap(),
+        );
+        let native_script = NativeScript::new_script_pubkey(Ed25519KeyHash::from([0; 28]));
+        let result = CIP27RoyaltyTokenBuilder::new(royalty.clone())
+            .native_script(
+                native_script.clone(),
+                NativeScriptWitnessInfo::assume_signature_count(),
+            )
+            .unwrap();
+        let asset_name = CIP27RoyaltyTokenBuilder::asset_name();
+        assert_eq!(result.mint.policy_id, native_script.hash());
+        assert_eq!(result.mint.assets.get(&asset_name), Some(&1));
+        assert_eq!(result.burn.assets.get(&asset_name), Some(&-1));
+        assert_eq!(
+            CIP27RoyaltyMetadata::from_metadata(result.auxiliary_data.metadata().unwrap()).unwrap(),
+            royalty
+        );
+    }
+}
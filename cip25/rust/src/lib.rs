@@ -3,8 +3,10 @@
 // This library was code-generated using an experimental CDDL to rust tool:
 // https://github.com/dcSpark/cddl-codegen
 
+pub mod cip27;
 pub mod serialization;
 pub mod utils;
+pub mod validation;
 
 pub use utils::{CIP25LabelMetadata, CIP25Version};
 
@@ -0,0 +1,513 @@
+//! Non-failing CIP-25 validation.
+//!
+//! `CIP25Metadata::from_metadata` only tells you whether the whole label parsed and
+//! `CIP25MiniMetadataDetails::loose_parse` only extracts the name and image.
+//! This reports every violation found instead, grouped by (policy, asset) where possible.
+
+use std::collections::BTreeMap;
+
+use cml_chain::assets::AssetName;
+use cml_chain::auxdata::{Metadata, MetadatumMap, TransactionMetadatum};
+use cml_chain::PolicyId;
+use cml_core::Int;
+use cml_crypto::RawBytesEncoding;
+
+use crate::utils::CIP25_METADATA_LABEL;
+use crate::CIP25Version;
+
+/// URL schemes accepted for `image` and `files.src`
+pub const CIP25_URL_SCHEMES: [&str; 5] = ["ipfs://", "ar://", "https://", "http://", "data:"];
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CIP25Violation {
+    #[error("Metadata has no label 721")]
+    MissingLabel,
+    #[error("Invalid structure: {0}")]
+    InvalidStructure(String),
+    #[error("Unknown version {0}")]
+    UnknownVersion(String),
+    #[error("Invalid policy id {0}")]
+    InvalidPolicyId(String),
+    #[error("Invalid asset name {0}")]
+    InvalidAssetName(String),
+    #[error("Policy id must be {} for {:?}", expected_encoding(*.0), .0)]
+    PolicyIdEncoding(CIP25Version),
+    #[error("Asset name must be {} for {:?}", expected_encoding(*.0), .0)]
+    AssetNameEncoding(CIP25Version),
+    #[error("Missing required field {0}")]
+    MissingField(String),
+    #[error("Field {field} must be {expected}")]
+    InvalidFieldType {
+        field: String,
+        expected: &'static str,
+    },
+    #[error("Field {field} has invalid media type {media_type}")]
+    InvalidMediaType { field: String, media_type: String },
+    #[error("mediaType {0} is not an image/* media type")]
+    NonImageMediaType(String),
+    #[error("Field {field} has unsupported URL {url}")]
+    InvalidUrl { field: String, url: String },
+}
+
+fn expected_encoding(version: CIP25Version) -> &'static str {
+    match version {
+        CIP25Version::V1 => "text",
+        CIP25Version::V2 => "bytes",
+    }
+}
+
+/// Every CIP-25 violation found in a transaction's metadata
+#[derive(Clone, Debug)]
+pub struct CIP25ValidationReport {
+    /// Version as declared by the metadata (V1 if absent)
+    pub version: CIP25Version,
+    /// Violations not specific to a single asset
+    pub general: Vec<CIP25Violation>,
+    /// Violations for each asset found. Assets without violations are present with an empty list.
+    pub assets: BTreeMap<PolicyId, BTreeMap<AssetName, Vec<CIP25Violation>>>,
+}
+
+impl CIP25ValidationReport {
+    pub fn new(metadata: &Metadata) -> Self {
+        match metadata.get(CIP25_METADATA_LABEL) {
+            Some(label_metadatum) => Self::from_label_metadatum(label_metadatum),
+            None => Self {
+                version: CIP25Version::V1,
+                general: vec![CIP25Violation::MissingLabel],
+                assets: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Validates the metadatum under label 721
+    pub fn from_label_metadatum(label_metadatum: &TransactionMetadatum) -> Self {
+        let mut report = Self {
+            version: CIP25Version::V1,
+            general: Vec::new(),
+            assets: BTreeMap::new(),
+        };
+        let label_map = match label_metadatum.as_map() {
+            Some(map) => map,
+            None => {
+                report.general.push(CIP25Violation::InvalidStructure(
+                    "label 721 must be a map".to_owned(),
+                ));
+                return report;
+            }
+        };
+        if let Some(version) = label_map.get_str("version") {
+            match version {
+                TransactionMetadatum::Int(Int::Uint { value: 1, .. }) => (),
+                TransactionMetadatum::Int(Int::Uint { value: 2, .. }) => {
+                    report.version = CIP25Version::V2
+                }
+                TransactionMetadatum::Text { text, .. } if text == "1.0" => (),
+                TransactionMetadatum::Text { text, .. } if text == "2.0" => {
+                    report.version = CIP25Version::V2
+                }
+                other => report
+                    .general
+                    .push(CIP25Violation::UnknownVersion(metadatum_to_string(other))),
+            }
+        }
+        match report.version {
+            CIP25Version::V1 => report.validate_policies(label_map),
+            CIP25Version::V2 => match label_map.get_str("data").map(TransactionMetadatum::as_map) {
+                Some(Some(data)) => report.validate_policies(data),
+                Some(None) => report.general.push(CIP25Violation::InvalidFieldType {
+                    field: "data".to_owned(),
+                    expected: "a map",
+                }),
+                None => report
+                    .general
+                    .push(CIP25Violation::MissingField("data".to_owned())),
+            },
+        }
+        report
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.general.is_empty() && self.violation_count() == 0
+    }
+
+    /// Number of violations across all assets, not including general ones
+    pub fn violation_count(&self) -> usize {
+        self.assets
+            .values()
+            .flat_map(BTreeMap::values)
+            .map(Vec::len)
+            .sum()
+    }
+
+    pub fn get(
+        &self,
+        policy_id: &PolicyId,
+        asset_name: &AssetName,
+    ) -> Option<&Vec<CIP25Violation>> {
+        self.assets.get(policy_id)?.get(asset_name)
+    }
+
+    fn validate_policies(&mut self, policies: &MetadatumMap) {
+        for (policy_key, assets) in policies.entries.iter() {
+            if is_text(policy_key, "version") || is_text(policy_key, "data") {
+                continue;
+            }
+            let (policy_id, policy_encoding_ok) = match policy_key {
+                TransactionMetadatum::Text { text, .. } => (
+                    PolicyId::from_hex(text).ok(),
+                    self.version == CIP25Version::V1,
+                ),
+                TransactionMetadatum::Bytes { bytes, .. } => (
+                    PolicyId::from_raw_bytes(bytes).ok(),
+                    self.version == CIP25Version::V2,
+                ),
+                _ => (None, false),
+            };
+            let policy_id = match policy_id {
+                Some(policy_id) => policy_id,
+                None => {
+                    self.general
+                        .push(CIP25Violation::InvalidPolicyId(metadatum_to_string(
+                            policy_key,
+                        )));
+                    continue;
+                }
+            };
+            let assets = match assets.as_map() {
+                Some(assets) => assets,
+                None => {
+                    self.general.push(CIP25Violation::InvalidStructure(format!(
+                        "assets of policy {} must be a map",
+                        policy_id.to_hex()
+                    )));
+                    continue;
+                }
+            };
+            for (asset_key, details) in assets.entries.iter() {
+                let (asset_name, asset_encoding_ok) = match asset_key {
+                    TransactionMetadatum::Text { text, .. } => (
+                        AssetName::new(text.as_bytes().to_vec()).ok(),
+                        self.version == CIP25Version::V1,
+                    ),
+                    TransactionMetadatum::Bytes { bytes, .. } => (
+                        AssetName::new(bytes.clone()).ok(),
+                        self.version == CIP25Version::V2,
+                    ),
+                    _ => (None, false),
+                };
+                let asset_name = match asset_name {
+                    Some(asset_name) => asset_name,
+                    None => {
+                        self.general.push(CIP25Violation::InvalidAssetName(format!(
+                            "{} under policy {}",
+                            metadatum_to_string(asset_key),
+                            policy_id.to_hex()
+                        )));
+                        continue;
+                    }
+                };
+                let mut violations = Vec::new();
+                if !policy_encoding_ok {
+                    violations.push(CIP25Violation::PolicyIdEncoding(self.version));
+                }
+                if !asset_encoding_ok {
+                    violations.push(CIP25Violation::AssetNameEncoding(self.version));
+                }
+                validate_details(details, &mut violations);
+                self.assets
+                    .entry(policy_id)
+                    .or_default()
+                    .insert(asset_name, violations);
+            }
+        }
+    }
+}
+
+fn validate_details(details: &TransactionMetadatum, violations: &mut Vec<CIP25Violation>) {
+    let details = match details.as_map() {
+        Some(details) => details,
+        None => {
+            violations.push(CIP25Violation::InvalidStructure(
+                "metadata details must be a map".to_owned(),
+            ));
+            return;
+        }
+    };
+    match details.get_str("name") {
+        Some(TransactionMetadatum::Text { .. }) => (),
+        Some(_) => violations.push(CIP25Violation::InvalidFieldType {
+            field: "name".to_owned(),
+            expected: "text",
+        }),
+        None => violations.push(CIP25Violation::MissingField("name".to_owned())),
+    }
+    match details.get_str("image") {
+        Some(image) => {
+            if let Some(url) = chunkable_string(image, "image", violations) {
+                validate_url(&url, "image", violations);
+            }
+        }
+        None => violations.push(CIP25Violation::MissingField("image".to_owned())),
+    }
+    if let Some(media_type) = details.get_str("mediaType") {
+        if let Some(media_type) = validate_media_type(media_type, "mediaType", violations) {
+            if !media_type.starts_with("image/") {
+                violations.push(CIP25Violation::NonImageMediaType(media_type));
+            }
+        }
+    }
+    if let Some(description) = details.get_str("description") {
+        chunkable_string(description, "description", violations);
+    }
+    if let Some(files) = details.get_str("files") {
+        let files = match files.as_list() {
+            Some(files) => files,
+            None => {
+                violations.push(CIP25Violation::InvalidFieldType {
+                    field: "files".to_owned(),
+                    expected: "a list",
+                });
+                return;
+            }
+        };
+        for (i, file) in files.iter().enumerate() {
+            let file = match file.as_map() {
+                Some(file) => file,
+                None => {
+                    violations.push(CIP25Violation::InvalidFieldType {
+                        field: format!("files[{i}]"),
+                        expected: "a map",
+                    });
+                    continue;
+                }
+            };
+            let field = |name: &str| format!("files[{i}].{name}");
+            match file.get_str("name") {
+                Some(TransactionMetadatum::Text { .. }) => (),
+                Some(_) => violations.push(CIP25Violation::InvalidFieldType {
+                    field: field("name"),
+                    expected: "text",
+                }),
+                None => violations.push(CIP25Violation::MissingField(field("name"))),
+            }
+            match file.get_str("mediaType") {
+                Some(media_type) => {
+                    validate_media_type(media_type, &field("mediaType"), violations);
+                }
+                None => violations.push(CIP25Violation::MissingField(field("mediaType"))),
+            }
+            match file.get_str("src") {
+                Some(src) => {
+                    if let Some(url) = chunkable_string(src, &field("src"), violations) {
+                        validate_url(&url, &field("src"), violations);
+                    }
+                }
+                None => violations.push(CIP25Violation::MissingField(field("src"))),
+            }
+        }
+    }
+}
+
+/// Reads a string that is either text or a list of text chunks
+fn chunkable_string(
+    metadatum: &TransactionMetadatum,
+    field: &str,
+    violations: &mut Vec<CIP25Violation>,
+) -> Option<String> {
+    let invalid = || CIP25Violation::InvalidFieldType {
+        field: field.to_owned(),
+        expected: "text or a list of text",
+    };
+    match metadatum {
+        TransactionMetadatum::Text { text, .. } => Some(text.clone()),
+        TransactionMetadatum::List { elements, .. } => {
+            match elements
+                .iter()
+                .map(TransactionMetadatum::as_text)
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(chunks) => Some(chunks.into_iter().map(String::as_str).collect()),
+                None => {
+                    violations.push(invalid());
+                    None
+                }
+            }
+        }
+        _ => {
+            violations.push(invalid());
+            None
+        }
+    }
+}
+
+fn validate_url(url: &str, field: &str, violations: &mut Vec<CIP25Violation>) {
+    if !CIP25_URL_SCHEMES
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+    {
+        violations.push(CIP25Violation::InvalidUrl {
+            field: field.to_owned(),
+            url: url.to_owned(),
+        });
+    }
+}
+
+/// Checks for a `type/subtype` media type and returns it if it is text
+fn validate_media_type(
+    metadatum: &TransactionMetadatum,
+    field: &str,
+    violations: &mut Vec<CIP25Violation>,
+) -> Option<String> {
+    let media_type = match metadatum.as_text() {
+        Some(media_type) => media_type,
+        None => {
+            violations.push(CIP25Violation::InvalidFieldType {
+                field: field.to_owned(),
+                expected: "text",
+            });
+            return None;
+        }
+    };
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+*".contains(c))
+    };
+    // parameters (e.g. "; charset=utf-8") are allowed after the subtype
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    let valid = match essence.split_once('/') {
+        Some((type_, subtype)) => is_token(type_) && is_token(subtype),
+        None => false,
+    };
+    if !valid {
+        violations.push(CIP25Violation::InvalidMediaType {
+            field: field.to_owned(),
+            media_type: media_type.clone(),
+        });
+    }
+    Some(media_type.clone())
+}
+
+fn is_text(metadatum: &TransactionMetadatum, expected: &str) -> bool {
+    matches!(metadatum, TransactionMetadatum::Text { text, .. } if text == expected)
+}
+
+fn metadatum_to_string(metadatum: &TransactionMetadatum) -> String {
+    match metadatum {
+        TransactionMetadatum::Text { text, .. } => format!("\"{text}\""),
+        TransactionMetadatum::Bytes { bytes, .. } => format!("0x{}", hex::encode(bytes)),
+        TransactionMetadatum::Int(int) => int.to_string(),
+        TransactionMetadatum::Map(_) => "<map>".to_owned(),
+        TransactionMetadatum::List { .. } => "<list>".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_core::serialization::Deserialize;
+
+    fn text(s: &str) -> TransactionMetadatum {
+        TransactionMetadatum::new_text(s.to_owned()).unwrap()
+    }
+
+    fn map(entries: Vec<(TransactionMetadatum, TransactionMetadatum)>) -> TransactionMetadatum {
+        let mut map = MetadatumMap::new();
+        for (k, v) in entries {
+            map.set(k, v);
+        }
+        TransactionMetadatum::new_map(map)
+    }
+
+    const POLICY: &str = "baadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00d";
+
+    #[test]
+    fn valid_v1() {
+        // {721: {"baadf00d...": {"Bud": {"name": "Bud", "image": ["ipfs://", "Qm"], "mediaType": "image/png"}}}}
+        let details = map(vec![
+            (text("name"), text("Bud")),
+            (
+                text("image"),
+                TransactionMetadatum::new_list(vec![text("ipfs://"), text("Qm")]),
+            ),
+            (text("mediaType"), text("image/png")),
+        ]);
+        let label = map(vec![(text(POLICY), map(vec![(text("Bud"), details)]))]);
+        let report = CIP25ValidationReport::from_label_metadatum(&label);
+        assert!(report.is_valid(), "{:?}", report);
+        let policy_id = PolicyId::from_hex(POLICY).unwrap();
+        let asset_name = AssetName::new(b"Bud".to_vec()).unwrap();
+        assert_eq!(report.get(&policy_id, &asset_name), Some(&vec![]));
+    }
+
+    #[test]
+    fn every_violation_reported() {
+        let bad_details = map(vec![
+            (text("Name"), text("uppercase key")),
+            (text("image"), text("Qmnoscheme")),
+            (text("mediaType"), text("text/plain")),
+            (
+                text("files"),
+                TransactionMetadatum::new_list(vec![map(vec![
+                    (text("name"), text("file")),
+                    (text("mediaType"), text("not a media type")),
+                    (text("src"), text("https://example.com/file")),
+                ])]),
+            ),
+        ]);
+        let policy_bytes = PolicyId::from_hex(POLICY).unwrap().to_raw_bytes().to_vec();
+        // version 2 with a text asset name and an invalid policy
+        let label = map(vec![
+            (
+                text("data"),
+                map(vec![
+                    (
+                        TransactionMetadatum::new_bytes(policy_bytes).unwrap(),
+                        map(vec![(text("Bud"), bad_details)]),
+                    ),
+                    (text("nothex"), map(vec![])),
+                ]),
+            ),
+            (
+                text("version"),
+                TransactionMetadatum::new_int(Int::new_uint(2)),
+            ),
+        ]);
+        let report = CIP25ValidationReport::from_label_metadatum(&label);
+        assert_eq!(report.version, CIP25Version::V2);
+        assert_eq!(
+            report.general,
+            vec![CIP25Violation::InvalidPolicyId("\"nothex\"".to_owned())]
+        );
+        let violations = report
+            .get(
+                &PolicyId::from_hex(POLICY).unwrap(),
+                &AssetName::new(b"Bud".to_vec()).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            violations,
+            &vec![
+                CIP25Violation::AssetNameEncoding(CIP25Version::V2),
+                CIP25Violation::MissingField("name".to_owned()),
+                CIP25Violation::InvalidUrl {
+                    field: "image".to_owned(),
+                    url: "Qmnoscheme".to_owned()
+                },
+                CIP25Violation::NonImageMediaType("text/plain".to_owned()),
+                CIP25Violation::InvalidMediaType {
+                    field: "files[0].mediaType".to_owned(),
+                    media_type: "not a media type".to_owned()
+                },
+            ]
+        );
+        assert_eq!(report.violation_count(), 5);
+    }
+
+    #[test]
+    fn missing_label() {
+        let metadata = Metadata::from_cbor_bytes(&[0xa0]).unwrap();
+        let report = CIP25ValidationReport::new(&metadata);
+        assert_eq!(report.general, vec![CIP25Violation::MissingLabel]);
+    }
+}
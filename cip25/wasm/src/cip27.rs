@@ -0,0 +1,164 @@
+use cml_chain_wasm::{
+    address::Address,
+    assets::AssetName,
+    auxdata::{AuxiliaryData, Metadata},
+    builders::{
+        mint_builder::MintBuilderResult,
+        witness_builder::{NativeScriptWitnessInfo, PartialPlutusWitness},
+    },
+    transaction::{NativeScript, RequiredSigners},
+};
+use cml_core_wasm::impl_wasm_conversions;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// Royalty rate as the decimal text stored on-chain e.g. "0.025" for 2.5%
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP27RoyaltyRate(cml_cip25::cip27::CIP27RoyaltyRate);
+
+impl_wasm_conversions!(cml_cip25::cip27::CIP27RoyaltyRate, CIP27RoyaltyRate);
+
+#[wasm_bindgen]
+impl CIP27RoyaltyRate {
+    /// Must be a plain decimal in [0, 1] with at most 18 decimal places
+    pub fn new(rate: &str) -> Result<CIP27RoyaltyRate, JsError> {
+        cml_cip25::cip27::CIP27RoyaltyRate::new(rate)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// e.g. 250 basis points is a rate of "0.025"
+    pub fn from_basis_points(basis_points: u16) -> Result<CIP27RoyaltyRate, JsError> {
+        cml_cip25::cip27::CIP27RoyaltyRate::from_basis_points(basis_points)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn to_str(&self) -> String {
+        self.0.to_str().to_owned()
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0.as_f64()
+    }
+}
+
+/// The contents of metadata label 777
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP27RoyaltyMetadata(cml_cip25::cip27::CIP27RoyaltyMetadata);
+
+impl_wasm_conversions!(cml_cip25::cip27::CIP27RoyaltyMetadata, CIP27RoyaltyMetadata);
+
+#[wasm_bindgen]
+impl CIP27RoyaltyMetadata {
+    pub fn new(rate: &CIP27RoyaltyRate, addr: &Address) -> Self {
+        cml_cip25::cip27::CIP27RoyaltyMetadata::new(rate.clone().into(), addr.clone().into()).into()
+    }
+
+    pub fn rate(&self) -> CIP27RoyaltyRate {
+        self.0.rate.clone().into()
+    }
+
+    pub fn addr(&self) -> Address {
+        self.0.addr.clone().into()
+    }
+
+    /// Create a Metadata containing only the CIP27 royalty
+    pub fn to_metadata(&self) -> Result<Metadata, JsError> {
+        self.0.to_metadata().map(Into::into).map_err(Into::into)
+    }
+
+    /// Read the CIP27 royalty from a Metadata. Ignores all other data besides CIP27
+    pub fn from_metadata(metadata: &Metadata) -> Result<CIP27RoyaltyMetadata, JsError> {
+        cml_cip25::cip27::CIP27RoyaltyMetadata::from_metadata(metadata.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// Add to an existing metadata (could be empty) the CIP27 royalty
+    pub fn add_to_metadata(&self, metadata: &mut Metadata) -> Result<(), JsError> {
+        self.0
+            .add_to_metadata(metadata.as_mut())
+            .map_err(Into::into)
+    }
+}
+
+/// The mint of the royalty token with its metadata and the burn that must follow in a later transaction
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct CIP27RoyaltyTokenResult(cml_cip25::cip27::CIP27RoyaltyTokenResult);
+
+impl_wasm_conversions!(
+    cml_cip25::cip27::CIP27RoyaltyTokenResult,
+    CIP27RoyaltyTokenResult
+);
+
+#[wasm_bindgen]
+impl CIP27RoyaltyTokenResult {
+    /// Add to the minting transaction along with auxiliary_data()
+    pub fn mint(&self) -> MintBuilderResult {
+        self.0.mint.clone().into()
+    }
+
+    /// Add to a later transaction once the mint is on-chain
+    pub fn burn(&self) -> MintBuilderResult {
+        self.0.burn.clone().into()
+    }
+
+    pub fn auxiliary_data(&self) -> AuxiliaryData {
+        self.0.auxiliary_data.clone().into()
+    }
+}
+
+/// Creates the mint and burn of the (empty asset name) royalty token for a policy.
+/// The policy must still allow minting as the royalty token has to be the first one minted.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP27RoyaltyTokenBuilder(cml_cip25::cip27::CIP27RoyaltyTokenBuilder);
+
+impl_wasm_conversions!(
+    cml_cip25::cip27::CIP27RoyaltyTokenBuilder,
+    CIP27RoyaltyTokenBuilder
+);
+
+#[wasm_bindgen]
+impl CIP27RoyaltyTokenBuilder {
+    pub fn new(royalty: &CIP27RoyaltyMetadata) -> Self {
+        cml_cip25::cip27::CIP27RoyaltyTokenBuilder::new(royalty.clone().into()).into()
+    }
+
+    pub fn royalty(&self) -> CIP27RoyaltyMetadata {
+        self.0.royalty().clone().into()
+    }
+
+    /// The royalty token has an empty asset name
+    pub fn asset_name() -> AssetName {
+        cml_cip25::cip27::CIP27RoyaltyTokenBuilder::asset_name().into()
+    }
+
+    pub fn native_script(
+        &self,
+        native_script: &NativeScript,
+        witness_info: &NativeScriptWitnessInfo,
+    ) -> Result<CIP27RoyaltyTokenResult, JsError> {
+        self.0
+            .native_script(native_script.clone().into(), witness_info.clone().into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn plutus_script(
+        &self,
+        partial_witness: &PartialPlutusWitness,
+        required_signers: &RequiredSigners,
+    ) -> Result<CIP27RoyaltyTokenResult, JsError> {
+        self.0
+            .plutus_script(
+                partial_witness.clone().into(),
+                required_signers.clone().into(),
+            )
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
@@ -4,7 +4,9 @@
     clippy::new_without_default
 )]
 
+pub mod cip27;
 pub mod utils;
+pub mod validation;
 
 use cml_core_wasm::{
     impl_wasm_cbor_json_api_cbor_event_serialize, impl_wasm_conversions, impl_wasm_json_api,
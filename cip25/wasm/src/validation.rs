@@ -0,0 +1,59 @@
+use cml_chain_wasm::{
+    assets::AssetName,
+    auxdata::{Metadata, TransactionMetadatum},
+    PolicyId,
+};
+use cml_core_wasm::impl_wasm_conversions;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::CIP25Version;
+
+/// Every CIP-25 violation found in a transaction's metadata.
+/// Violations are returned as their human-readable descriptions.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP25ValidationReport(cml_cip25::validation::CIP25ValidationReport);
+
+impl_wasm_conversions!(
+    cml_cip25::validation::CIP25ValidationReport,
+    CIP25ValidationReport
+);
+
+#[wasm_bindgen]
+impl CIP25ValidationReport {
+    pub fn new(metadata: &Metadata) -> Self {
+        cml_cip25::validation::CIP25ValidationReport::new(metadata.as_ref()).into()
+    }
+
+    /// Validates the metadatum under label 721
+    pub fn from_label_metadatum(label_metadatum: &TransactionMetadatum) -> Self {
+        cml_cip25::validation::CIP25ValidationReport::from_label_metadatum(label_metadatum.as_ref())
+            .into()
+    }
+
+    /// Version as declared by the metadata (V1 if absent)
+    pub fn version(&self) -> CIP25Version {
+        self.0.version
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    /// Number of violations across all assets, not including general ones
+    pub fn violation_count(&self) -> usize {
+        self.0.violation_count()
+    }
+
+    /// Violations not specific to a single asset
+    pub fn general(&self) -> Vec<String> {
+        self.0.general.iter().map(ToString::to_string).collect()
+    }
+
+    /// Violations for the given asset or None if the asset was not found
+    pub fn get(&self, policy_id: &PolicyId, asset_name: &AssetName) -> Option<Vec<String>> {
+        self.0
+            .get(policy_id.as_ref(), asset_name.as_ref())
+            .map(|violations| violations.iter().map(ToString::to_string).collect())
+    }
+}
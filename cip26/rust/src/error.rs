@@ -0,0 +1,36 @@
+use cml_core::DeserializeError;
+use cml_crypto::{CryptoError, ScriptHash};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CIP26Error {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Missing field {0}")]
+    MissingField(String),
+    #[error("Invalid field {0}")]
+    InvalidField(String),
+    #[error("Invalid subject {0}: must be a hex policy id followed by the asset name")]
+    InvalidSubject(String),
+    #[error("Hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Crypto: {0}")]
+    Crypto(#[from] CryptoError),
+    #[error("Deserialize: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Entry has no policy to verify against")]
+    MissingPolicy,
+    #[error("Policy hash {found} does not match subject policy id {expected}")]
+    PolicyMismatch {
+        expected: ScriptHash,
+        found: ScriptHash,
+    },
+    #[error("Invalid signature on {property} by {public_key}")]
+    InvalidSignature {
+        property: String,
+        public_key: String,
+    },
+    #[error("Signatures on {0} do not satisfy the policy")]
+    PolicyNotSatisfied(String),
+}
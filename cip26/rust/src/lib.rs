@@ -0,0 +1,549 @@
+//! CIP-26 off-chain metadata as used by the Cardano token registry.
+//!
+//! Each registry entry describes one native asset (the subject) and every property carries
+//! a sequence number and signatures from the keys of the asset's minting policy.
+//! A signature is over the attestation digest of the property:
+//! `blake2b256(h(cbor(subject)) ++ h(cbor(property name)) ++ h(cbor(json(value))) ++ h(cbor(sequence number)))`
+//! where `h` is blake2b256, the subject/name/value are CBOR text and the sequence number a CBOR uint.
+
+pub mod error;
+
+pub use error::CIP26Error;
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use base64::Engine;
+use cbor_event::se::Serializer;
+use cml_chain::assets::AssetName;
+use cml_chain::transaction::utils::RequiredSignersSet;
+use cml_chain::transaction::NativeScript;
+use cml_chain::{PolicyId, Slot};
+use cml_core::serialization::{Deserialize, Serialize};
+use cml_crypto::{blake2b256, Ed25519Signature, PrivateKey, PublicKey, RawBytesEncoding};
+use serde_json::Value;
+
+pub const CIP26_NAME: &str = "name";
+pub const CIP26_DESCRIPTION: &str = "description";
+pub const CIP26_TICKER: &str = "ticker";
+pub const CIP26_DECIMALS: &str = "decimals";
+pub const CIP26_LOGO: &str = "logo";
+pub const CIP26_URL: &str = "url";
+
+/// The asset an entry describes. Encoded as the hex of the policy id followed by the asset name.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CIP26Subject {
+    pub policy_id: PolicyId,
+    pub asset_name: AssetName,
+}
+
+impl CIP26Subject {
+    pub fn new(policy_id: PolicyId, asset_name: AssetName) -> Self {
+        Self {
+            policy_id,
+            asset_name,
+        }
+    }
+
+    pub fn from_hex(subject: &str) -> Result<Self, CIP26Error> {
+        let bytes = hex::decode(subject)?;
+        if bytes.len() < PolicyId::BYTE_COUNT {
+            return Err(CIP26Error::InvalidSubject(subject.to_owned()));
+        }
+        let (policy_id, asset_name) = bytes.split_at(PolicyId::BYTE_COUNT);
+        Ok(Self {
+            policy_id: PolicyId::from_raw_bytes(policy_id)?,
+            asset_name: AssetName::new(asset_name.to_vec())
+                .map_err(|_| CIP26Error::InvalidSubject(subject.to_owned()))?,
+        })
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!(
+            "{}{}",
+            self.policy_id.to_hex(),
+            hex::encode(self.asset_name.get())
+        )
+    }
+}
+
+impl std::fmt::Display for CIP26Subject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+/// A signature along with the key that made it
+#[derive(Clone, Debug)]
+pub struct CIP26AnnotatedSignature {
+    pub signature: Ed25519Signature,
+    pub public_key: PublicKey,
+}
+
+impl CIP26AnnotatedSignature {
+    pub fn new(signature: Ed25519Signature, public_key: PublicKey) -> Self {
+        Self {
+            signature,
+            public_key,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        serde_json::json!({
+            "signature": self.signature.to_raw_hex(),
+            "publicKey": self.public_key.to_raw_hex(),
+        })
+    }
+
+    fn from_json(json: &Value) -> Result<Self, CIP26Error> {
+        let signature = json_str(json, "signature", "signatures.signature")?;
+        let public_key = json_str(json, "publicKey", "signatures.publicKey")?;
+        Ok(Self::new(
+            Ed25519Signature::from_raw_hex(signature)?,
+            PublicKey::from_raw_hex(public_key)?,
+        ))
+    }
+}
+
+/// Types that can be the value of a CIP-26 property
+pub trait CIP26PropertyValue: Sized {
+    fn to_json_value(&self) -> Value;
+
+    fn from_json_value(json: &Value) -> Option<Self>;
+}
+
+impl CIP26PropertyValue for String {
+    fn to_json_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn from_json_value(json: &Value) -> Option<Self> {
+        json.as_str().map(str::to_owned)
+    }
+}
+
+impl CIP26PropertyValue for u64 {
+    fn to_json_value(&self) -> Value {
+        Value::from(*self)
+    }
+
+    fn from_json_value(json: &Value) -> Option<Self> {
+        json.as_u64()
+    }
+}
+
+/// Binary values (i.e. the logo) are base64 encoded
+impl CIP26PropertyValue for Vec<u8> {
+    fn to_json_value(&self) -> Value {
+        Value::String(base64::engine::general_purpose::STANDARD.encode(self))
+    }
+
+    fn from_json_value(json: &Value) -> Option<Self> {
+        json.as_str()
+            .and_then(|s| base64::engine::general_purpose::STANDARD.decode(s).ok())
+    }
+}
+
+/// A single property of an entry
+#[derive(Clone, Debug)]
+pub struct CIP26Property<T> {
+    /// Must increase with every update of the property
+    pub sequence_number: u64,
+    pub value: T,
+    pub signatures: Vec<CIP26AnnotatedSignature>,
+}
+
+impl<T: CIP26PropertyValue> CIP26Property<T> {
+    /// Creates an unsigned property
+    pub fn new(value: T, sequence_number: u64) -> Self {
+        Self {
+            sequence_number,
+            value,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// The message that is signed by the policy's keys
+    pub fn attestation_digest(&self, subject: &CIP26Subject, property_name: &str) -> [u8; 32] {
+        let value = self.value.to_json_value().to_string();
+        let mut sequence_number = Serializer::new_vec();
+        sequence_number
+            .write_unsigned_integer(self.sequence_number)
+            .unwrap();
+        let mut preimage = Vec::with_capacity(4 * 32);
+        preimage.extend(blake2b256(&cbor_text(&subject.to_hex())));
+        preimage.extend(blake2b256(&cbor_text(property_name)));
+        preimage.extend(blake2b256(&cbor_text(&value)));
+        preimage.extend(blake2b256(&sequence_number.finalize()));
+        blake2b256(&preimage)
+    }
+
+    /// Signs the property and adds the signature
+    pub fn sign(&mut self, subject: &CIP26Subject, property_name: &str, key: &PrivateKey) {
+        let signature = key.sign(&self.attestation_digest(subject, property_name));
+        self.signatures
+            .push(CIP26AnnotatedSignature::new(signature, key.to_public()));
+    }
+
+    /// Hashes of the keys that signed this property.
+    /// Errors if any of the signatures are invalid.
+    pub fn verified_signers(
+        &self,
+        subject: &CIP26Subject,
+        property_name: &str,
+    ) -> Result<RequiredSignersSet, CIP26Error> {
+        let digest = self.attestation_digest(subject, property_name);
+        self.signatures
+            .iter()
+            .map(|signature| {
+                if signature.public_key.verify(&digest, &signature.signature) {
+                    Ok(signature.public_key.hash())
+                } else {
+                    Err(CIP26Error::InvalidSignature {
+                        property: property_name.to_owned(),
+                        public_key: signature.public_key.to_raw_hex(),
+                    })
+                }
+            })
+            .collect()
+    }
+
+    fn to_json(&self) -> Value {
+        serde_json::json!({
+            "sequenceNumber": self.sequence_number,
+            "value": self.value.to_json_value(),
+            "signatures": self
+                .signatures
+                .iter()
+                .map(CIP26AnnotatedSignature::to_json)
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn from_json(json: &Value, property_name: &str) -> Result<Self, CIP26Error> {
+        let field = |name: &str| format!("{property_name}.{name}");
+        let sequence_number = json
+            .get("sequenceNumber")
+            .ok_or_else(|| CIP26Error::MissingField(field("sequenceNumber")))?
+            .as_u64()
+            .ok_or_else(|| CIP26Error::InvalidField(field("sequenceNumber")))?;
+        let value = T::from_json_value(
+            json.get("value")
+                .ok_or_else(|| CIP26Error::MissingField(field("value")))?,
+        )
+        .ok_or_else(|| CIP26Error::InvalidField(field("value")))?;
+        let signatures = match json.get("signatures") {
+            Some(signatures) => signatures
+                .as_array()
+                .ok_or_else(|| CIP26Error::InvalidField(field("signatures")))?
+                .iter()
+                .map(CIP26AnnotatedSignature::from_json)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            sequence_number,
+            value,
+            signatures,
+        })
+    }
+}
+
+/// A token registry entry
+#[derive(Clone, Debug)]
+pub struct CIP26RegistryEntry {
+    pub subject: CIP26Subject,
+    /// The minting policy of the asset. Needed to verify the signatures.
+    pub policy: Option<NativeScript>,
+    pub name: CIP26Property<String>,
+    pub description: CIP26Property<String>,
+    pub ticker: Option<CIP26Property<String>>,
+    pub decimals: Option<CIP26Property<u64>>,
+    /// PNG image bytes
+    pub logo: Option<CIP26Property<Vec<u8>>>,
+    pub url: Option<CIP26Property<String>>,
+}
+
+impl CIP26RegistryEntry {
+    pub fn new(
+        subject: CIP26Subject,
+        policy: Option<NativeScript>,
+        name: CIP26Property<String>,
+        description: CIP26Property<String>,
+    ) -> Self {
+        Self {
+            subject,
+            policy,
+            name,
+            description,
+            ticker: None,
+            decimals: None,
+            logo: None,
+            url: None,
+        }
+    }
+
+    /// Parses an entry as found in the registry repository or returned by the metadata server
+    pub fn from_json(json: &str) -> Result<Self, CIP26Error> {
+        let json: Value = serde_json::from_str(json)?;
+        Self::try_from(&json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_value()).unwrap()
+    }
+
+    pub fn to_json_value(&self) -> Value {
+        let mut json = serde_json::Map::new();
+        json.insert("subject".to_owned(), Value::String(self.subject.to_hex()));
+        if let Some(policy) = &self.policy {
+            json.insert(
+                "policy".to_owned(),
+                Value::String(hex::encode(policy.to_cbor_bytes())),
+            );
+        }
+        json.insert(CIP26_NAME.to_owned(), self.name.to_json());
+        json.insert(CIP26_DESCRIPTION.to_owned(), self.description.to_json());
+        if let Some(ticker) = &self.ticker {
+            json.insert(CIP26_TICKER.to_owned(), ticker.to_json());
+        }
+        if let Some(decimals) = &self.decimals {
+            json.insert(CIP26_DECIMALS.to_owned(), decimals.to_json());
+        }
+        if let Some(logo) = &self.logo {
+            json.insert(CIP26_LOGO.to_owned(), logo.to_json());
+        }
+        if let Some(url) = &self.url {
+            json.insert(CIP26_URL.to_owned(), url.to_json());
+        }
+        Value::Object(json)
+    }
+
+    /// Hashes of the keys that signed each property present, keyed by property name.
+    /// Errors if any signature is invalid.
+    pub fn verified_signers(
+        &self,
+    ) -> Result<BTreeMap<&'static str, RequiredSignersSet>, CIP26Error> {
+        let subject = &self.subject;
+        let mut signers = BTreeMap::new();
+        signers.insert(CIP26_NAME, self.name.verified_signers(subject, CIP26_NAME)?);
+        signers.insert(
+            CIP26_DESCRIPTION,
+            self.description
+                .verified_signers(subject, CIP26_DESCRIPTION)?,
+        );
+        if let Some(ticker) = &self.ticker {
+            signers.insert(
+                CIP26_TICKER,
+                ticker.verified_signers(subject, CIP26_TICKER)?,
+            );
+        }
+        if let Some(decimals) = &self.decimals {
+            signers.insert(
+                CIP26_DECIMALS,
+                decimals.verified_signers(subject, CIP26_DECIMALS)?,
+            );
+        }
+        if let Some(logo) = &self.logo {
+            signers.insert(CIP26_LOGO, logo.verified_signers(subject, CIP26_LOGO)?);
+        }
+        if let Some(url) = &self.url {
+            signers.insert(CIP26_URL, url.verified_signers(subject, CIP26_URL)?);
+        }
+        Ok(signers)
+    }
+
+    /// Checks that the policy matches the subject and that every property is validly signed
+    /// by keys satisfying the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower_bound` - inclusive slot to evaluate the policy's time locks against. None if unbounded.
+    /// * `upper_bound` - exclusive slot to evaluate the policy's time locks against. None if unbounded.
+    pub fn verify(
+        &self,
+        lower_bound: Option<Slot>,
+        upper_bound: Option<Slot>,
+    ) -> Result<(), CIP26Error> {
+        let policy = self.policy.as_ref().ok_or(CIP26Error::MissingPolicy)?;
+        let policy_hash = policy.hash();
+        if policy_hash != self.subject.policy_id {
+            return Err(CIP26Error::PolicyMismatch {
+                expected: self.subject.policy_id,
+                found: policy_hash,
+            });
+        }
+        for (property, signers) in self.verified_signers()? {
+            if !policy.verify(lower_bound, upper_bound, &signers) {
+                return Err(CIP26Error::PolicyNotSatisfied(property.to_owned()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&Value> for CIP26RegistryEntry {
+    type Error = CIP26Error;
+
+    fn try_from(json: &Value) -> Result<Self, Self::Error> {
+        let subject = CIP26Subject::from_hex(json_str(json, "subject", "subject")?)?;
+        let policy = match json.get("policy") {
+            Some(policy) => {
+                let policy = policy
+                    .as_str()
+                    .ok_or_else(|| CIP26Error::InvalidField("policy".to_owned()))?;
+                Some(NativeScript::from_cbor_bytes(&hex::decode(policy)?)?)
+            }
+            None => None,
+        };
+        let required = |name: &str| {
+            json.get(name)
+                .ok_or_else(|| CIP26Error::MissingField(name.to_owned()))
+        };
+        let optional = |name: &str| json.get(name);
+        Ok(Self {
+            subject,
+            policy,
+            name: CIP26Property::from_json(required(CIP26_NAME)?, CIP26_NAME)?,
+            description: CIP26Property::from_json(required(CIP26_DESCRIPTION)?, CIP26_DESCRIPTION)?,
+            ticker: optional(CIP26_TICKER)
+                .map(|json| CIP26Property::from_json(json, CIP26_TICKER))
+                .transpose()?,
+            decimals: optional(CIP26_DECIMALS)
+                .map(|json| CIP26Property::from_json(json, CIP26_DECIMALS))
+                .transpose()?,
+            logo: optional(CIP26_LOGO)
+                .map(|json| CIP26Property::from_json(json, CIP26_LOGO))
+                .transpose()?,
+            url: optional(CIP26_URL)
+                .map(|json| CIP26Property::from_json(json, CIP26_URL))
+                .transpose()?,
+        })
+    }
+}
+
+fn json_str<'a>(json: &'a Value, key: &str, field: &str) -> Result<&'a str, CIP26Error> {
+    json.get(key)
+        .ok_or_else(|| CIP26Error::MissingField(field.to_owned()))?
+        .as_str()
+        .ok_or_else(|| CIP26Error::InvalidField(field.to_owned()))
+}
+
+fn cbor_text(text: &str) -> Vec<u8> {
+    let mut serializer = Serializer::new_vec();
+    serializer.write_text(text).unwrap();
+    serializer.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_entry(keys: &[&PrivateKey], policy: NativeScript) -> CIP26RegistryEntry {
+        let subject = CIP26Subject::new(policy.hash(), AssetName::new(b"TOKEN".to_vec()).unwrap());
+        let mut entry = CIP26RegistryEntry::new(
+            subject.clone(),
+            Some(policy),
+            CIP26Property::new("Token".to_owned(), 0),
+            CIP26Property::new("A test token".to_owned(), 0),
+        );
+        entry.decimals = Some(CIP26Property::new(6, 1));
+        entry.logo = Some(CIP26Property::new(vec![0x89, b'P', b'N', b'G'], 0));
+        for key in keys {
+            entry.name.sign(&subject, CIP26_NAME, key);
+            entry.description.sign(&subject, CIP26_DESCRIPTION, key);
+            entry
+                .decimals
+                .as_mut()
+                .unwrap()
+                .sign(&subject, CIP26_DECIMALS, key);
+            entry.logo.as_mut().unwrap().sign(&subject, CIP26_LOGO, key);
+        }
+        entry
+    }
+
+    #[test]
+    fn json_round_trip_and_verify() {
+        let key = PrivateKey::generate_ed25519();
+        let policy = NativeScript::new_script_pubkey(key.to_public().hash());
+        let entry = signed_entry(&[&key], policy);
+        let parsed = CIP26RegistryEntry::from_json(&entry.to_json()).unwrap();
+        assert_eq!(parsed.subject, entry.subject);
+        assert_eq!(parsed.name.value, "Token");
+        assert_eq!(parsed.decimals.as_ref().unwrap().value, 6);
+        assert_eq!(parsed.decimals.as_ref().unwrap().sequence_number, 1);
+        assert_eq!(parsed.logo.as_ref().unwrap().value, b"\x89PNG");
+        assert!(parsed.ticker.is_none());
+        parsed.verify(None, None).unwrap();
+        let signers = parsed.verified_signers().unwrap();
+        assert_eq!(signers.len(), 4);
+        assert!(signers[CIP26_NAME].contains(&key.to_public().hash()));
+    }
+
+    #[test]
+    fn tampered_value() {
+        let key = PrivateKey::generate_ed25519();
+        let policy = NativeScript::new_script_pubkey(key.to_public().hash());
+        let mut entry = signed_entry(&[&key], policy);
+        entry.name.value = "Other".to_owned();
+        assert!(matches!(
+            entry.verify(None, None),
+            Err(CIP26Error::InvalidSignature { property, .. }) if property == CIP26_NAME
+        ));
+    }
+
+    #[test]
+    fn policy_not_satisfied() {
+        let key1 = PrivateKey::generate_ed25519();
+        let key2 = PrivateKey::generate_ed25519();
+        let policy = NativeScript::new_script_all(vec![
+            NativeScript::new_script_pubkey(key1.to_public().hash()),
+            NativeScript::new_script_pubkey(key2.to_public().hash()),
+        ]);
+        signed_entry(&[&key1, &key2], policy.clone())
+            .verify(None, None)
+            .unwrap();
+        assert!(matches!(
+            signed_entry(&[&key1], policy.clone()).verify(None, None),
+            Err(CIP26Error::PolicyNotSatisfied(_))
+        ));
+        let mut wrong_policy = signed_entry(&[&key1, &key2], policy);
+        wrong_policy.policy = Some(NativeScript::new_script_pubkey(key1.to_public().hash()));
+        assert!(matches!(
+            wrong_policy.verify(None, None),
+            Err(CIP26Error::PolicyMismatch { .. })
+        ));
+        // time locks are evaluated against the given bounds
+        let timelocked = NativeScript::new_script_all(vec![
+            NativeScript::new_script_pubkey(key1.to_public().hash()),
+            NativeScript::new_script_invalid_hereafter(1000),
+        ]);
+        let entry = signed_entry(&[&key1], timelocked);
+        assert!(entry.verify(None, None).is_err());
+        entry.verify(None, Some(1000)).unwrap();
+        assert!(entry.verify(None, Some(1001)).is_err());
+    }
+
+    #[test]
+    fn parse_registry_json() {
+        // an unsigned entry in the registry repository's format
+        let json = r#"{
+            "subject": "baadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00d544f4b454e",
+            "policy": "8200581cbaadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00dbaadf00d",
+            "name": { "sequenceNumber": 0, "value": "Token", "signatures": [] },
+            "description": { "sequenceNumber": 2, "value": "A test token", "signatures": [] },
+            "ticker": { "sequenceNumber": 0, "value": "TKN", "signatures": [] },
+            "url": { "sequenceNumber": 0, "value": "https://example.com", "signatures": [] }
+        }"#;
+        let entry = CIP26RegistryEntry::from_json(json).unwrap();
+        assert_eq!(entry.subject.asset_name.get(), b"TOKEN");
+        assert_eq!(entry.description.sequence_number, 2);
+        assert_eq!(entry.ticker.unwrap().value, "TKN");
+        assert_eq!(entry.url.unwrap().value, "https://example.com");
+        assert!(entry.policy.is_some());
+        assert!(matches!(
+            CIP26RegistryEntry::from_json(r#"{"subject": "baadf00d"}"#),
+            Err(CIP26Error::InvalidSubject(_))
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+#![allow(
+    clippy::len_without_is_empty,
+    clippy::too_many_arguments,
+    clippy::new_without_default
+)]
+
+use cml_chain_wasm::{
+    assets::AssetName, transaction::NativeScript, Ed25519KeyHashList, PolicyId, Slot,
+};
+use cml_core_wasm::{impl_wasm_conversions, impl_wasm_list};
+use cml_crypto_wasm::{Ed25519Signature, PrivateKey, PublicKey};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// The asset an entry describes. Encoded as the hex of the policy id followed by the asset name.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP26Subject(cml_cip26::CIP26Subject);
+
+impl_wasm_conversions!(cml_cip26::CIP26Subject, CIP26Subject);
+
+#[wasm_bindgen]
+impl CIP26Subject {
+    pub fn new(policy_id: &PolicyId, asset_name: &AssetName) -> Self {
+        cml_cip26::CIP26Subject::new(policy_id.clone().into(), asset_name.clone().into()).into()
+    }
+
+    pub fn from_hex(subject: &str) -> Result<CIP26Subject, JsError> {
+        cml_cip26::CIP26Subject::from_hex(subject)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.to_hex()
+    }
+
+    pub fn policy_id(&self) -> PolicyId {
+        self.0.policy_id.into()
+    }
+
+    pub fn asset_name(&self) -> AssetName {
+        self.0.asset_name.clone().into()
+    }
+}
+
+/// A signature along with the key that made it
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP26AnnotatedSignature(cml_cip26::CIP26AnnotatedSignature);
+
+impl_wasm_conversions!(cml_cip26::CIP26AnnotatedSignature, CIP26AnnotatedSignature);
+
+#[wasm_bindgen]
+impl CIP26AnnotatedSignature {
+    pub fn new(signature: &Ed25519Signature, public_key: &PublicKey) -> Self {
+        cml_cip26::CIP26AnnotatedSignature::new(signature.clone().into(), public_key.clone().into())
+            .into()
+    }
+
+    pub fn signature(&self) -> Ed25519Signature {
+        self.0.signature.clone().into()
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.0.public_key.clone().into()
+    }
+}
+
+impl_wasm_list!(
+    cml_cip26::CIP26AnnotatedSignature,
+    CIP26AnnotatedSignature,
+    CIP26AnnotatedSignatureList
+);
+
+macro_rules! impl_wasm_cip26_property {
+    ($wasm_name:ident, $value_rust:ty, $value_wasm:ty) => {
+        #[derive(Clone, Debug)]
+        #[wasm_bindgen]
+        pub struct $wasm_name(cml_cip26::CIP26Property<$value_rust>);
+
+        impl_wasm_conversions!(cml_cip26::CIP26Property<$value_rust>, $wasm_name);
+
+        #[wasm_bindgen]
+        impl $wasm_name {
+            /// Creates an unsigned property
+            pub fn new(value: $value_wasm, sequence_number: u64) -> Self {
+                cml_cip26::CIP26Property::new(value.into(), sequence_number).into()
+            }
+
+            pub fn value(&self) -> $value_wasm {
+                self.0.value.clone().into()
+            }
+
+            pub fn sequence_number(&self) -> u64 {
+                self.0.sequence_number
+            }
+
+            pub fn signatures(&self) -> CIP26AnnotatedSignatureList {
+                self.0.signatures.clone().into()
+            }
+
+            /// The message that is signed by the policy's keys
+            pub fn attestation_digest(
+                &self,
+                subject: &CIP26Subject,
+                property_name: &str,
+            ) -> Vec<u8> {
+                self.0
+                    .attestation_digest(subject.as_ref(), property_name)
+                    .to_vec()
+            }
+
+            /// Signs the property and adds the signature
+            pub fn sign(&mut self, subject: &CIP26Subject, property_name: &str, key: &PrivateKey) {
+                self.0.sign(subject.as_ref(), property_name, key.as_ref())
+            }
+
+            /// Hashes of the keys that signed this property.
+            /// Errors if any of the signatures are invalid.
+            pub fn verified_signers(
+                &self,
+                subject: &CIP26Subject,
+                property_name: &str,
+            ) -> Result<Ed25519KeyHashList, JsError> {
+                self.0
+                    .verified_signers(subject.as_ref(), property_name)
+                    .map(|signers| signers.into_iter().collect::<Vec<_>>().into())
+                    .map_err(Into::into)
+            }
+        }
+    };
+}
+
+impl_wasm_cip26_property!(CIP26TextProperty, String, String);
+impl_wasm_cip26_property!(CIP26DecimalsProperty, u64, u64);
+impl_wasm_cip26_property!(CIP26LogoProperty, Vec<u8>, Vec<u8>);
+
+/// A token registry entry
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP26RegistryEntry(cml_cip26::CIP26RegistryEntry);
+
+impl_wasm_conversions!(cml_cip26::CIP26RegistryEntry, CIP26RegistryEntry);
+
+#[wasm_bindgen]
+impl CIP26RegistryEntry {
+    pub fn new(
+        subject: &CIP26Subject,
+        policy: Option<NativeScript>,
+        name: &CIP26TextProperty,
+        description: &CIP26TextProperty,
+    ) -> Self {
+        cml_cip26::CIP26RegistryEntry::new(
+            subject.clone().into(),
+            policy.map(Into::into),
+            name.clone().into(),
+            description.clone().into(),
+        )
+        .into()
+    }
+
+    /// Parses an entry as found in the registry repository or returned by the metadata server
+    pub fn from_json(json: &str) -> Result<CIP26RegistryEntry, JsError> {
+        cml_cip26::CIP26RegistryEntry::from_json(json)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    pub fn subject(&self) -> CIP26Subject {
+        self.0.subject.clone().into()
+    }
+
+    pub fn policy(&self) -> Option<NativeScript> {
+        self.0.policy.clone().map(Into::into)
+    }
+
+    pub fn name(&self) -> CIP26TextProperty {
+        self.0.name.clone().into()
+    }
+
+    pub fn description(&self) -> CIP26TextProperty {
+        self.0.description.clone().into()
+    }
+
+    pub fn set_ticker(&mut self, ticker: &CIP26TextProperty) {
+        self.0.ticker = Some(ticker.clone().into())
+    }
+
+    pub fn ticker(&self) -> Option<CIP26TextProperty> {
+        self.0.ticker.clone().map(Into::into)
+    }
+
+    pub fn set_decimals(&mut self, decimals: &CIP26DecimalsProperty) {
+        self.0.decimals = Some(decimals.clone().into())
+    }
+
+    pub fn decimals(&self) -> Option<CIP26DecimalsProperty> {
+        self.0.decimals.clone().map(Into::into)
+    }
+
+    pub fn set_logo(&mut self, logo: &CIP26LogoProperty) {
+        self.0.logo = Some(logo.clone().into())
+    }
+
+    pub fn logo(&self) -> Option<CIP26LogoProperty> {
+        self.0.logo.clone().map(Into::into)
+    }
+
+    pub fn set_url(&mut self, url: &CIP26TextProperty) {
+        self.0.url = Some(url.clone().into())
+    }
+
+    pub fn url(&self) -> Option<CIP26TextProperty> {
+        self.0.url.clone().map(Into::into)
+    }
+
+    /// Checks that the policy matches the subject and that every property is validly signed
+    /// by keys satisfying the policy.
+    /// The bounds are the (inclusive/exclusive) slots to evaluate time locks against.
+    pub fn verify(
+        &self,
+        lower_bound: Option<Slot>,
+        upper_bound: Option<Slot>,
+    ) -> Result<(), JsError> {
+        self.0.verify(lower_bound, upper_bound).map_err(Into::into)
+    }
+}
@@ -2,9 +2,14 @@
 pub enum CIP36Error {
     #[error("Empty delegation array")]
     EmptyDelegationArray,
-    // TODO: can we check this somehow against anything? I don't believe so, so maybe remove this
-    // #[error("Reward wrong network")]
-    // RewardWrongNetwork,
+    #[error("Payment address network {found} does not match expected network {expected}")]
+    PaymentAddressWrongNetwork { expected: u8, found: u8 },
+    #[error("Payment address has no network: {0}")]
+    PaymentAddressNoNetwork(#[from] cml_chain::address::AddressError),
     #[error("Invalid delegation weights")]
     DelegationWeightsZero,
+    #[error("Stake witness signature does not match the stake credential")]
+    InvalidSignature,
+    #[error("Could not create data to sign: {0}")]
+    SignData(#[from] cbor_event::Error),
 }
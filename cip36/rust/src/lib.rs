@@ -16,6 +16,7 @@ use std::convert::From;
 pub mod cbor_encodings;
 pub mod error;
 pub mod serialization;
+pub mod tally;
 pub mod utils;
 
 use cbor_encodings::*;
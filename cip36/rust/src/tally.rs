@@ -0,0 +1,321 @@
+//! Voting power snapshots from on-chain CIP-36 registrations.
+//!
+//! This follows the rules used by the Catalyst snapshot tooling:
+//! * only registrations that pass full verification (see `CIP36RegistrationCbor::verify_full`) count
+//! * per stake key the registration/deregistration with the highest nonce wins,
+//!   with ties broken by the later slot and then the later transaction index within that slot
+//! * a stake key's stake is split between its delegations proportionally to their weights,
+//!   rounding down with any leftover going to the last delegation
+
+use std::collections::BTreeMap;
+
+use cml_crypto::Ed25519KeyHash;
+use linked_hash_map::LinkedHashMap;
+
+use crate::error::CIP36Error;
+use crate::utils::{KEY_DEREGISTRATION_LABEL, KEY_REGISTRATION_LABEL, REGISTRATION_WITNESS_LABEL};
+use crate::{
+    CIP36DelegationDistribution, CIP36DeregistrationCbor, CIP36Nonce, CIP36RegistrationCbor,
+    CIP36StakeCredential, CIP36VotingPubKey, CIP36VotingPurpose, DeserializeError, Metadata,
+};
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum CIP36Event {
+    Registration(CIP36RegistrationCbor),
+    Deregistration(CIP36DeregistrationCbor),
+}
+
+impl CIP36Event {
+    /// Reads a registration or deregistration from a transaction's metadata.
+    /// Returns None if the metadata contains neither.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Result<Self, DeserializeError>> {
+        use std::convert::TryFrom;
+        if metadata.get(KEY_REGISTRATION_LABEL).is_some() {
+            Some(CIP36RegistrationCbor::try_from(metadata).map(Self::Registration))
+        } else if metadata.get(KEY_DEREGISTRATION_LABEL).is_some()
+            && metadata.get(REGISTRATION_WITNESS_LABEL).is_some()
+        {
+            Some(CIP36DeregistrationCbor::try_from(metadata).map(Self::Deregistration))
+        } else {
+            None
+        }
+    }
+
+    pub fn stake_credential(&self) -> &CIP36StakeCredential {
+        match self {
+            Self::Registration(reg) => &reg.key_registration.stake_credential,
+            Self::Deregistration(dereg) => &dereg.key_deregistration.stake_credential,
+        }
+    }
+
+    pub fn nonce(&self) -> CIP36Nonce {
+        match self {
+            Self::Registration(reg) => reg.key_registration.nonce,
+            Self::Deregistration(dereg) => dereg.key_deregistration.nonce,
+        }
+    }
+
+    pub fn voting_purpose(&self) -> CIP36VotingPurpose {
+        match self {
+            Self::Registration(reg) => reg.key_registration.voting_purpose,
+            Self::Deregistration(dereg) => dereg.key_deregistration.voting_purpose,
+        }
+    }
+
+    /// Full verification of a registration (see `CIP36RegistrationCbor::verify_full`)
+    /// or the signature of a deregistration.
+    pub fn verify_full(&self, network_id: u8) -> Result<(), CIP36Error> {
+        match self {
+            Self::Registration(reg) => reg.verify_full(network_id),
+            Self::Deregistration(dereg) => dereg.verify_signature(),
+        }
+    }
+}
+
+/// A registration or deregistration along with where it appeared on chain
+#[derive(Clone, Debug)]
+pub struct CIP36TxEvent {
+    pub slot: u64,
+    /// Index of the transaction within its block
+    pub tx_index: u64,
+    pub event: CIP36Event,
+}
+
+impl CIP36TxEvent {
+    pub fn new(slot: u64, tx_index: u64, event: CIP36Event) -> Self {
+        Self {
+            slot,
+            tx_index,
+            event,
+        }
+    }
+
+    fn precedence(&self) -> (CIP36Nonce, u64, u64) {
+        (self.event.nonce(), self.slot, self.tx_index)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CIP36VotingPowerTally {
+    /// Voting power in lovelace per voting key, ordered by the first stake key hash delegating to it
+    pub voting_power: LinkedHashMap<CIP36VotingPubKey, u64>,
+    /// The registration in effect for each stake key. Deregistered stake keys are not present.
+    pub registrations: BTreeMap<Ed25519KeyHash, CIP36TxEvent>,
+    /// Events which failed verification and were ignored
+    pub rejected: Vec<(CIP36TxEvent, CIP36Error)>,
+}
+
+impl CIP36VotingPowerTally {
+    /// Computes the voting power of every voting key.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - all registrations/deregistrations up to the snapshot, in any order
+    /// * `stake` - stake distribution in lovelace by stake key hash at the snapshot
+    /// * `network_id` - network the payment addresses of registrations must be for
+    /// * `voting_purpose` - only events with this voting purpose are considered (0 for Catalyst)
+    pub fn new<I: IntoIterator<Item = CIP36TxEvent>>(
+        events: I,
+        stake: &BTreeMap<Ed25519KeyHash, u64>,
+        network_id: u8,
+        voting_purpose: CIP36VotingPurpose,
+    ) -> Self {
+        let mut latest = BTreeMap::<Ed25519KeyHash, CIP36TxEvent>::new();
+        let mut rejected = Vec::new();
+        for tx_event in events {
+            if tx_event.event.voting_purpose() != voting_purpose {
+                continue;
+            }
+            if let Err(e) = tx_event.event.verify_full(network_id) {
+                rejected.push((tx_event, e));
+                continue;
+            }
+            let stake_key_hash = tx_event.event.stake_credential().hash();
+            let replaces = latest
+                .get(&stake_key_hash)
+                .map(|current| tx_event.precedence() > current.precedence())
+                .unwrap_or(true);
+            if replaces {
+                latest.insert(stake_key_hash, tx_event);
+            }
+        }
+        let registrations = latest
+            .into_iter()
+            .filter(|(_, tx_event)| matches!(tx_event.event, CIP36Event::Registration(_)))
+            .collect::<BTreeMap<_, _>>();
+        let mut voting_power = LinkedHashMap::<CIP36VotingPubKey, u64>::new();
+        for (stake_key_hash, tx_event) in registrations.iter() {
+            let registration = match &tx_event.event {
+                CIP36Event::Registration(registration) => registration,
+                CIP36Event::Deregistration(_) => continue,
+            };
+            let stake = stake.get(stake_key_hash).copied().unwrap_or(0);
+            if stake == 0 {
+                continue;
+            }
+            for (voting_key, power) in distribute(&registration.key_registration.delegation, stake)
+            {
+                *voting_power.entry(voting_key).or_insert(0) += power;
+            }
+        }
+        Self {
+            voting_power,
+            registrations,
+            rejected,
+        }
+    }
+
+    /// Sum of voting power over all voting keys
+    pub fn total_voting_power(&self) -> u64 {
+        self.voting_power.values().sum()
+    }
+}
+
+/// Splits `stake` between the delegations by weight, rounding down. Leftovers go to the last delegation.
+fn distribute(
+    delegation: &CIP36DelegationDistribution,
+    stake: u64,
+) -> Vec<(CIP36VotingPubKey, u64)> {
+    match delegation {
+        CIP36DelegationDistribution::Legacy { legacy, .. } => vec![(legacy.clone(), stake)],
+        CIP36DelegationDistribution::Weighted { delegations, .. } => {
+            let total_weight = delegations
+                .iter()
+                .map(|d| u128::from(d.weight))
+                .sum::<u128>();
+            let mut remaining = stake;
+            delegations
+                .iter()
+                .enumerate()
+                .map(|(i, d)| {
+                    let power = if i + 1 == delegations.len() {
+                        remaining
+                    } else {
+                        (u128::from(stake) * u128::from(d.weight) / total_weight) as u64
+                    };
+                    remaining -= power;
+                    (d.voting_pub_key.clone(), power)
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Address, CIP36Delegation, CIP36DeregistrationWitness, CIP36KeyDeregistration,
+        CIP36KeyRegistration, CIP36RegistrationWitness,
+    };
+    use cml_crypto::PrivateKey;
+
+    fn address() -> Address {
+        Address::from_bech32("addr1u8pcjgmx7962w6hey5hhsd502araxp26kdtgagakhaqtq8sxy9w7g").unwrap()
+    }
+
+    fn registration(
+        stake_key: &PrivateKey,
+        delegation: CIP36DelegationDistribution,
+        nonce: u64,
+    ) -> CIP36Event {
+        let key_registration =
+            CIP36KeyRegistration::new(delegation, stake_key.to_public(), address(), nonce);
+        let witness = CIP36RegistrationWitness::new(
+            stake_key.sign(&key_registration.hash_to_sign(false).unwrap()),
+        );
+        CIP36Event::Registration(CIP36RegistrationCbor::new(key_registration, witness))
+    }
+
+    fn deregistration(stake_key: &PrivateKey, nonce: u64) -> CIP36Event {
+        let key_deregistration = CIP36KeyDeregistration::new(stake_key.to_public(), nonce);
+        let witness = CIP36DeregistrationWitness::new(
+            stake_key.sign(&key_deregistration.hash_to_sign(false).unwrap()),
+        );
+        CIP36Event::Deregistration(CIP36DeregistrationCbor::new(key_deregistration, witness))
+    }
+
+    #[test]
+    fn verify_full() {
+        let stake_key = PrivateKey::generate_ed25519();
+        let voting_key = PrivateKey::generate_ed25519().to_public();
+        let event = registration(
+            &stake_key,
+            CIP36DelegationDistribution::new_legacy(voting_key),
+            5,
+        );
+        let mut reg = match event {
+            CIP36Event::Registration(reg) => reg,
+            CIP36Event::Deregistration(_) => unreachable!(),
+        };
+        assert!(reg.verify_full(1).is_ok());
+        assert!(matches!(
+            reg.verify_full(0),
+            Err(CIP36Error::PaymentAddressWrongNetwork {
+                expected: 0,
+                found: 1
+            })
+        ));
+        reg.key_registration.nonce = 6;
+        assert!(matches!(
+            reg.verify_full(1),
+            Err(CIP36Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn tally() {
+        let stake_a = PrivateKey::generate_ed25519();
+        let stake_b = PrivateKey::generate_ed25519();
+        let stake_c = PrivateKey::generate_ed25519();
+        let voting_x = PrivateKey::generate_ed25519().to_public();
+        let voting_y = PrivateKey::generate_ed25519().to_public();
+        let weighted = CIP36DelegationDistribution::new_weighted(vec![
+            CIP36Delegation::new(voting_x.clone(), 1),
+            CIP36Delegation::new(voting_y.clone(), 2),
+        ]);
+        let mut invalid = registration(
+            &stake_c,
+            CIP36DelegationDistribution::new_legacy(voting_x.clone()),
+            1,
+        );
+        if let CIP36Event::Registration(reg) = &mut invalid {
+            reg.key_registration.nonce = 2;
+        }
+        let events = vec![
+            // the later nonce wins even though it was in an earlier slot
+            CIP36TxEvent::new(
+                200,
+                0,
+                registration(
+                    &stake_a,
+                    CIP36DelegationDistribution::new_legacy(voting_y.clone()),
+                    10,
+                ),
+            ),
+            CIP36TxEvent::new(100, 0, registration(&stake_a, weighted, 20)),
+            CIP36TxEvent::new(
+                100,
+                1,
+                registration(
+                    &stake_b,
+                    CIP36DelegationDistribution::new_legacy(voting_y.clone()),
+                    30,
+                ),
+            ),
+            CIP36TxEvent::new(300, 0, deregistration(&stake_b, 31)),
+            CIP36TxEvent::new(300, 1, invalid),
+        ];
+        let mut stake = BTreeMap::new();
+        stake.insert(stake_a.to_public().hash(), 1000);
+        stake.insert(stake_b.to_public().hash(), 500);
+        stake.insert(stake_c.to_public().hash(), 50);
+        let tally = CIP36VotingPowerTally::new(events, &stake, 1, 0);
+        assert_eq!(tally.registrations.len(), 1);
+        assert_eq!(tally.rejected.len(), 1);
+        assert_eq!(tally.voting_power.get(&voting_x), Some(&333));
+        assert_eq!(tally.voting_power.get(&voting_y), Some(&667));
+        assert_eq!(tally.total_voting_power(), 1000);
+    }
+}
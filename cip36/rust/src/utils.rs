@@ -55,6 +55,21 @@ impl CIP36DeregistrationCbor {
         Ok(())
     }
 
+    /// Checks that `stake_witness` is a valid signature by `stake_credential` of the deregistration.
+    /// The deregistration's original encoding is used if it was deserialized.
+    pub fn verify_signature(&self) -> Result<(), CIP36Error> {
+        let hash = self.key_deregistration.hash_to_sign(false)?;
+        if self
+            .key_deregistration
+            .stake_credential
+            .verify(&hash, &self.deregistration_witness.stake_witness)
+        {
+            Ok(())
+        } else {
+            Err(CIP36Error::InvalidSignature)
+        }
+    }
+
     // these are not implementing Serialize/Deserialize as we do not keep track of the rest of the encoding metadata
     // so it would be disingenuous to implement them if users called to_cbor_bytes() and we skip the rest of
     // the metadata, as well as when creating from a Metadata object its outer encoding (e.g. map len, key encodings)
@@ -318,13 +333,43 @@ impl CIP36RegistrationCbor {
             if delegations.is_empty() {
                 return Err(CIP36Error::EmptyDelegationArray);
             }
-            if delegations.iter().any(|d| d.weight != 0) {
+            if delegations.iter().all(|d| d.weight == 0) {
                 return Err(CIP36Error::DelegationWeightsZero);
             }
         }
         Ok(())
     }
 
+    /// Checks that `stake_witness` is a valid signature by `stake_credential` of the registration.
+    /// The registration's original encoding is used if it was deserialized.
+    pub fn verify_signature(&self) -> Result<(), CIP36Error> {
+        let hash = self.key_registration.hash_to_sign(false)?;
+        if self
+            .key_registration
+            .stake_credential
+            .verify(&hash, &self.registration_witness.stake_witness)
+        {
+            Ok(())
+        } else {
+            Err(CIP36Error::InvalidSignature)
+        }
+    }
+
+    /// Performs all checks needed to consider this registration valid on a given network:
+    /// the invariants in verify(), that the payment address is for `network_id`
+    /// and the stake witness signature.
+    pub fn verify_full(&self, network_id: u8) -> Result<(), CIP36Error> {
+        self.verify()?;
+        let found = self.key_registration.payment_address.network_id()?;
+        if found != network_id {
+            return Err(CIP36Error::PaymentAddressWrongNetwork {
+                expected: network_id,
+                found,
+            });
+        }
+        self.verify_signature()
+    }
+
     // these are not implementing Serialize/Deserialize as we do not keep track of the rest of the encoding metadata
     // so it would be disingenuous to implement them if users called to_cbor_bytes() and we skip the rest of
     // the metadata, as well as when creating from a Metadata object its outer encoding (e.g. map len, key encodings)
@@ -24,6 +24,11 @@ impl CIP36DeregistrationCbor {
             .map_err(Into::into)
     }
 
+    /// Verifies the deregistration witness signature against the stake credential.
+    pub fn verify_signature(&self) -> Result<(), JsError> {
+        self.0.verify_signature().map_err(Into::into)
+    }
+
     // these are not implementing Serialize/Deserialize as we do not keep track of the rest of the encoding metadata
     // so it would be disingenuous to implement them if users called to_cbor_bytes() and we skip the rest of
     // the metadata, as well as when creating from a Metadata object its outer encoding (e.g. map len, key encodings)
@@ -127,6 +132,16 @@ impl CIP36RegistrationCbor {
         self.0.verify().map_err(Into::into)
     }
 
+    /// Verifies the registration witness signature against the stake credential.
+    pub fn verify_signature(&self) -> Result<(), JsError> {
+        self.0.verify_signature().map_err(Into::into)
+    }
+
+    /// Verifies CIP36 invariants, that the payment address is for network_id and the witness signature.
+    pub fn verify_full(&self, network_id: u8) -> Result<(), JsError> {
+        self.0.verify_full(network_id).map_err(Into::into)
+    }
+
     // these are not implementing Serialize/Deserialize as we do not keep track of the rest of the encoding metadata
     // so it would be disingenuous to implement them if users called to_cbor_bytes() and we skip the rest of
     // the metadata, as well as when creating from a Metadata object its outer encoding (e.g. map len, key encodings)
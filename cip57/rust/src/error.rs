@@ -0,0 +1,37 @@
+use cml_chain::plutus::uplc::UplcError;
+use cml_crypto::ScriptHash;
+
+/// Data not matching a schema. `path` locates the offending value e.g. `$.fields[1][0]`
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CIP57Error {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Unknown Plutus version {0}")]
+    UnknownPlutusVersion(String),
+    #[error("No validator titled {0}")]
+    UnknownValidator(String),
+    #[error("Validator {0} has no compiled code")]
+    MissingCompiledCode(String),
+    #[error("Validator {0} does not declare a {1}")]
+    MissingArgument(String, &'static str),
+    #[error("Script hash {found} does not match declared hash {declared}")]
+    HashMismatch { declared: String, found: ScriptHash },
+    #[error("Expected {expected} parameters but {found} were given")]
+    WrongParameterCount { expected: usize, found: usize },
+    #[error("Invalid {argument}: {violation}")]
+    SchemaMismatch {
+        argument: String,
+        violation: SchemaViolation,
+    },
+    #[error("UPLC: {0}")]
+    Uplc(#[from] UplcError),
+}
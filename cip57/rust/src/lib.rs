@@ -0,0 +1,598 @@
+//! CIP-57 Plutus contract blueprints (`plutus.json`) as emitted by Aiken and other toolchains.
+//!
+//! A blueprint lists the compiled validators of a contract together with JSON schemas
+//! describing how their datum, redeemer and parameters are represented as `PlutusData`.
+//! Parameterized validators are instantiated by applying the parameters to the compiled
+//! UPLC program, which changes the script and therefore its hash.
+
+pub mod error;
+
+pub use error::{CIP57Error, SchemaViolation};
+
+use std::collections::BTreeMap;
+
+use cml_chain::plutus::uplc::apply_params_to_script;
+use cml_chain::plutus::{PlutusData, PlutusScript, PlutusV1Script, PlutusV2Script, PlutusV3Script};
+use cml_crypto::ScriptHash;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Schemas nested deeper than this (including `$ref` indirections) are rejected
+/// to protect against cyclic definitions.
+const MAX_SCHEMA_DEPTH: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlutusVersion {
+    V1,
+    V2,
+    V3,
+}
+
+impl std::str::FromStr for PlutusVersion {
+    type Err = CIP57Error;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        match version {
+            "v1" => Ok(Self::V1),
+            "v2" => Ok(Self::V2),
+            "v3" => Ok(Self::V3),
+            _ => Err(CIP57Error::UnknownPlutusVersion(version.to_owned())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compiler {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preamble {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub version: String,
+    /// `v1`, `v2` or `v3`. Defaults to `v2` when absent as in older blueprints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plutus_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler: Option<Compiler>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+/// A datum, redeemer or parameter of a validator
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argument {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<Value>,
+    pub schema: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Validator {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datum: Option<Argument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redeemer: Option<Argument>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Argument>,
+    /// Hex of the script bytes (CBOR bytestring wrapping the flat-encoded program)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiled_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blueprint {
+    pub preamble: Preamble,
+    pub validators: Vec<Validator>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub definitions: BTreeMap<String, Value>,
+}
+
+impl Blueprint {
+    pub fn from_json(json: &str) -> Result<Self, CIP57Error> {
+        let blueprint: Self = serde_json::from_str(json)?;
+        // fail early on unknown versions rather than when building scripts
+        blueprint.plutus_version()?;
+        Ok(blueprint)
+    }
+
+    pub fn to_json(&self) -> Result<String, CIP57Error> {
+        serde_json::to_string_pretty(self).map_err(Into::into)
+    }
+
+    pub fn plutus_version(&self) -> Result<PlutusVersion, CIP57Error> {
+        self.preamble
+            .plutus_version
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(PlutusVersion::V2))
+    }
+
+    pub fn validator(&self, title: &str) -> Result<&Validator, CIP57Error> {
+        self.validators
+            .iter()
+            .find(|validator| validator.title == title)
+            .ok_or_else(|| CIP57Error::UnknownValidator(title.to_owned()))
+    }
+
+    /// The compiled (unapplied) script of a validator.
+    /// If the blueprint declares a hash it is checked against the script's hash.
+    pub fn script(&self, title: &str) -> Result<PlutusScript, CIP57Error> {
+        let validator = self.validator(title)?;
+        let script = self.make_script(compiled_code(validator)?)?;
+        if let Some(declared) = &validator.hash {
+            let found = script.hash();
+            if found.to_hex() != declared.to_lowercase() {
+                return Err(CIP57Error::HashMismatch {
+                    declared: declared.clone(),
+                    found,
+                });
+            }
+        }
+        Ok(script)
+    }
+
+    /// Hash of the compiled (unapplied) script of a validator
+    pub fn script_hash(&self, title: &str) -> Result<ScriptHash, CIP57Error> {
+        self.script(title).map(|script| script.hash())
+    }
+
+    pub fn validate_datum(&self, title: &str, datum: &PlutusData) -> Result<(), CIP57Error> {
+        let validator = self.validator(title)?;
+        let argument = validator
+            .datum
+            .as_ref()
+            .ok_or_else(|| CIP57Error::MissingArgument(title.to_owned(), "datum"))?;
+        self.validate_argument(argument, "datum", datum)
+    }
+
+    pub fn validate_redeemer(&self, title: &str, redeemer: &PlutusData) -> Result<(), CIP57Error> {
+        let validator = self.validator(title)?;
+        let argument = validator
+            .redeemer
+            .as_ref()
+            .ok_or_else(|| CIP57Error::MissingArgument(title.to_owned(), "redeemer"))?;
+        self.validate_argument(argument, "redeemer", redeemer)
+    }
+
+    /// Checks `data` against a schema, resolving references against this blueprint's definitions
+    pub fn validate_schema(
+        &self,
+        schema: &Value,
+        data: &PlutusData,
+    ) -> Result<(), SchemaViolation> {
+        SchemaChecker {
+            definitions: &self.definitions,
+        }
+        .check(schema, data, "$", 0)
+    }
+
+    /// Applies `params` (in declaration order) to a parameterized validator after checking them
+    /// against their schemas, returning the applied script. Its hash is the one to use on-chain.
+    pub fn apply_parameters(
+        &self,
+        title: &str,
+        params: &[PlutusData],
+    ) -> Result<PlutusScript, CIP57Error> {
+        let validator = self.validator(title)?;
+        if validator.parameters.len() != params.len() {
+            return Err(CIP57Error::WrongParameterCount {
+                expected: validator.parameters.len(),
+                found: params.len(),
+            });
+        }
+        for (i, (argument, param)) in validator.parameters.iter().zip(params).enumerate() {
+            let name = match &argument.title {
+                Some(param_title) => format!("parameter {param_title}"),
+                None => format!("parameter {i}"),
+            };
+            self.validate_argument(argument, &name, param)?;
+        }
+        let applied = apply_params_to_script(&compiled_code(validator)?, params)?;
+        self.make_script(applied)
+    }
+
+    fn validate_argument(
+        &self,
+        argument: &Argument,
+        name: &str,
+        data: &PlutusData,
+    ) -> Result<(), CIP57Error> {
+        self.validate_schema(&argument.schema, data)
+            .map_err(|violation| CIP57Error::SchemaMismatch {
+                argument: name.to_owned(),
+                violation,
+            })
+    }
+
+    fn make_script(&self, bytes: Vec<u8>) -> Result<PlutusScript, CIP57Error> {
+        Ok(match self.plutus_version()? {
+            PlutusVersion::V1 => PlutusV1Script::new(bytes).into(),
+            PlutusVersion::V2 => PlutusV2Script::new(bytes).into(),
+            PlutusVersion::V3 => PlutusV3Script::new(bytes).into(),
+        })
+    }
+}
+
+fn compiled_code(validator: &Validator) -> Result<Vec<u8>, CIP57Error> {
+    let code = validator
+        .compiled_code
+        .as_ref()
+        .ok_or_else(|| CIP57Error::MissingCompiledCode(validator.title.clone()))?;
+    hex::decode(code).map_err(Into::into)
+}
+
+struct SchemaChecker<'a> {
+    definitions: &'a BTreeMap<String, Value>,
+}
+
+impl SchemaChecker<'_> {
+    fn check(
+        &self,
+        schema: &Value,
+        data: &PlutusData,
+        path: &str,
+        depth: usize,
+    ) -> Result<(), SchemaViolation> {
+        let violation = |message: String| SchemaViolation {
+            path: path.to_owned(),
+            message,
+        };
+        if depth > MAX_SCHEMA_DEPTH {
+            return Err(violation("schema nested too deeply".to_owned()));
+        }
+        let schema = schema
+            .as_object()
+            .ok_or_else(|| violation(format!("schema must be an object, found {schema}")))?;
+        if let Some(reference) = schema.get("$ref") {
+            let target = reference
+                .as_str()
+                .and_then(|reference| reference.strip_prefix("#/definitions/"))
+                .map(|name| name.replace("~1", "/").replace("~0", "~"))
+                .and_then(|name| self.definitions.get(&name))
+                .ok_or_else(|| violation(format!("unresolved reference {reference}")))?;
+            return self.check(target, data, path, depth + 1);
+        }
+        if let Some(alternatives) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            let alternatives = alternatives
+                .as_array()
+                .ok_or_else(|| violation("anyOf/oneOf must be an array".to_owned()))?;
+            let results = alternatives
+                .iter()
+                .map(|alternative| self.check(alternative, data, path, depth + 1))
+                .collect::<Vec<_>>();
+            let matches = results.iter().filter(|result| result.is_ok()).count();
+            if matches == 1 || (matches > 1 && schema.contains_key("anyOf")) {
+                return Ok(());
+            }
+            if matches > 1 {
+                return Err(violation(format!("matched {matches} oneOf alternatives")));
+            }
+            // report why the constructor with the matching index failed as that's usually the intent
+            if let PlutusData::ConstrPlutusData(constr) = data {
+                let same_index = alternatives.iter().zip(&results).find(|(alternative, _)| {
+                    self.constructor_index(alternative, depth) == Some(constr.alternative)
+                });
+                if let Some((_, Err(e))) = same_index {
+                    return Err(e.clone());
+                }
+            }
+            return Err(violation(format!(
+                "matched none of {} alternatives",
+                alternatives.len()
+            )));
+        }
+        if let Some(all) = schema.get("allOf") {
+            let all = all
+                .as_array()
+                .ok_or_else(|| violation("allOf must be an array".to_owned()))?;
+            for sub_schema in all {
+                self.check(sub_schema, data, path, depth + 1)?;
+            }
+            return Ok(());
+        }
+        let data_type = match schema.get("dataType") {
+            Some(data_type) => data_type
+                .as_str()
+                .ok_or_else(|| violation(format!("invalid dataType {data_type}")))?,
+            // an empty schema (bar annotations) matches any data
+            None => return Ok(()),
+        };
+        match (data_type, data) {
+            ("integer", PlutusData::Integer(value)) => {
+                // numbers outside i128 are beyond any bound expressible in the JSON
+                let value = value.as_i128().unwrap_or_else(|| {
+                    if value.to_string().starts_with('-') {
+                        i128::MIN
+                    } else {
+                        i128::MAX
+                    }
+                });
+                let bound = |key: &str| schema.get(key).and_then(json_i128);
+                if let Some(minimum) = bound("minimum").filter(|minimum| value < *minimum) {
+                    return Err(violation(format!("{value} is less than {minimum}")));
+                }
+                if let Some(maximum) = bound("maximum").filter(|maximum| value > *maximum) {
+                    return Err(violation(format!("{value} is greater than {maximum}")));
+                }
+                if let Some(minimum) = bound("exclusiveMinimum").filter(|minimum| value <= *minimum)
+                {
+                    return Err(violation(format!("{value} is not greater than {minimum}")));
+                }
+                if let Some(maximum) = bound("exclusiveMaximum").filter(|maximum| value >= *maximum)
+                {
+                    return Err(violation(format!("{value} is not less than {maximum}")));
+                }
+                if let Some(multiple) = bound("multipleOf").filter(|m| *m != 0 && value % m != 0) {
+                    return Err(violation(format!(
+                        "{value} is not a multiple of {multiple}"
+                    )));
+                }
+                Ok(())
+            }
+            ("bytes", PlutusData::Bytes { bytes, .. }) => {
+                check_length(schema, "minLength", "maxLength", bytes.len())
+                    .map_err(|message| violation(format!("{message} bytes")))?;
+                if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+                    let hex = hex::encode(bytes);
+                    if !allowed.iter().any(|allowed| allowed.as_str() == Some(&hex)) {
+                        return Err(violation(format!("{hex} is not one of the allowed values")));
+                    }
+                }
+                Ok(())
+            }
+            ("list", PlutusData::List { list, .. }) => {
+                check_length(schema, "minItems", "maxItems", list.len())
+                    .map_err(|message| violation(format!("{message} items")))?;
+                match schema.get("items") {
+                    // tuple: a schema per position
+                    Some(Value::Array(items)) => {
+                        if items.len() != list.len() {
+                            return Err(violation(format!(
+                                "expected {} items, found {}",
+                                items.len(),
+                                list.len()
+                            )));
+                        }
+                        for (i, (item, elem)) in items.iter().zip(list).enumerate() {
+                            self.check(item, elem, &format!("{path}[{i}]"), depth + 1)?;
+                        }
+                    }
+                    Some(items) => {
+                        for (i, elem) in list.iter().enumerate() {
+                            self.check(items, elem, &format!("{path}[{i}]"), depth + 1)?;
+                        }
+                    }
+                    None => (),
+                }
+                Ok(())
+            }
+            ("map", PlutusData::Map(map)) => {
+                check_length(schema, "minItems", "maxItems", map.entries.len())
+                    .map_err(|message| violation(format!("{message} entries")))?;
+                for (i, (key, value)) in map.entries.iter().enumerate() {
+                    if let Some(keys) = schema.get("keys") {
+                        self.check(keys, key, &format!("{path}.keys[{i}]"), depth + 1)?;
+                    }
+                    if let Some(values) = schema.get("values") {
+                        self.check(values, value, &format!("{path}.values[{i}]"), depth + 1)?;
+                    }
+                }
+                Ok(())
+            }
+            ("constructor", PlutusData::ConstrPlutusData(constr)) => {
+                let index = schema
+                    .get("index")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| violation("constructor schema without index".to_owned()))?;
+                if constr.alternative != index {
+                    return Err(violation(format!(
+                        "expected constructor {index}, found {}",
+                        constr.alternative
+                    )));
+                }
+                let fields = schema
+                    .get("fields")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| violation("constructor schema without fields".to_owned()))?;
+                if fields.len() != constr.fields.len() {
+                    return Err(violation(format!(
+                        "constructor {index} expects {} fields, found {}",
+                        fields.len(),
+                        constr.fields.len()
+                    )));
+                }
+                for (i, (field, value)) in fields.iter().zip(&constr.fields).enumerate() {
+                    self.check(field, value, &format!("{path}.fields[{i}]"), depth + 1)?;
+                }
+                Ok(())
+            }
+            (builtin, _) if builtin.starts_with('#') => Err(violation(format!(
+                "builtin type {builtin} can't be represented as data"
+            ))),
+            ("integer" | "bytes" | "list" | "map" | "constructor", _) => Err(violation(format!(
+                "expected {data_type}, found {}",
+                data_kind(data)
+            ))),
+            (unknown, _) => Err(violation(format!("unknown dataType {unknown}"))),
+        }
+    }
+
+    /// Constructor index of a schema (following references) if it is a constructor schema
+    fn constructor_index(&self, schema: &Value, depth: usize) -> Option<u64> {
+        if depth > MAX_SCHEMA_DEPTH {
+            return None;
+        }
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let name = reference
+                    .strip_prefix("#/definitions/")?
+                    .replace("~1", "/")
+                    .replace("~0", "~");
+                self.constructor_index(self.definitions.get(&name)?, depth + 1)
+            }
+            None => schema.get("index").and_then(Value::as_u64),
+        }
+    }
+}
+
+fn json_i128(value: &Value) -> Option<i128> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+}
+
+fn check_length(
+    schema: &serde_json::Map<String, Value>,
+    min_key: &str,
+    max_key: &str,
+    len: usize,
+) -> Result<(), String> {
+    if let Some(min) = schema.get(min_key).and_then(Value::as_u64) {
+        if (len as u64) < min {
+            return Err(format!("expected at least {min}, found {len}"));
+        }
+    }
+    if let Some(max) = schema.get(max_key).and_then(Value::as_u64) {
+        if len as u64 > max {
+            return Err(format!("expected at most {max}, found {len}"));
+        }
+    }
+    Ok(())
+}
+
+fn data_kind(data: &PlutusData) -> &'static str {
+    match data {
+        PlutusData::ConstrPlutusData(_) => "constructor",
+        PlutusData::Map(_) => "map",
+        PlutusData::List { .. } => "list",
+        PlutusData::Integer(_) => "integer",
+        PlutusData::Bytes { .. } => "bytes",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_chain::plutus::uplc::{Program, Term};
+    use cml_chain::plutus::ConstrPlutusData;
+    use cml_chain::utils::BigInteger;
+
+    fn blueprint() -> Blueprint {
+        // \owner _ctx -> owner as a parameterized V3 validator, in the layout aiken writes
+        Blueprint::from_json(include_str!("test_data/plutus.json")).unwrap()
+    }
+
+    fn int(value: i64) -> PlutusData {
+        PlutusData::new_integer(BigInteger::from(value))
+    }
+
+    fn constr(alternative: u64, fields: Vec<PlutusData>) -> PlutusData {
+        PlutusData::new_constr_plutus_data(ConstrPlutusData::new(alternative, fields))
+    }
+
+    #[test]
+    fn script_and_schemas() {
+        let blueprint = blueprint();
+        assert!(matches!(
+            blueprint.script("vault.spend").unwrap(),
+            PlutusScript::PlutusV3(_)
+        ));
+        let datum = constr(
+            0,
+            vec![
+                PlutusData::new_bytes(vec![1; 28]),
+                PlutusData::new_list(vec![int(1), int(2)]),
+            ],
+        );
+        blueprint.validate_datum("vault.spend", &datum).unwrap();
+        let bad_datum = constr(
+            0,
+            vec![
+                PlutusData::new_bytes(vec![1; 28]),
+                PlutusData::new_list(vec![int(1), PlutusData::new_bytes(vec![])]),
+            ],
+        );
+        match blueprint.validate_datum("vault.spend", &bad_datum) {
+            Err(CIP57Error::SchemaMismatch { violation, .. }) => {
+                assert_eq!(violation.path, "$.fields[1][1]")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        blueprint
+            .validate_redeemer("vault.spend", &constr(1, vec![int(5)]))
+            .unwrap();
+        match blueprint.validate_redeemer("vault.spend", &constr(1, vec![])) {
+            Err(CIP57Error::SchemaMismatch { violation, .. }) => {
+                assert_eq!(violation.path, "$");
+                assert!(violation.message.contains("expects 1 fields"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(blueprint
+            .validate_redeemer("vault.spend", &constr(2, vec![]))
+            .is_err());
+    }
+
+    #[test]
+    fn apply_parameters() {
+        let blueprint = blueprint();
+        let owner = PlutusData::new_bytes(vec![7; 28]);
+        let applied = blueprint
+            .apply_parameters("vault.spend", std::slice::from_ref(&owner))
+            .unwrap();
+        let script = match &applied {
+            PlutusScript::PlutusV3(script) => script.get().clone(),
+            _ => panic!("wrong version"),
+        };
+        // the compiledCode and the applied script were assembled by hand from the flat encoding
+        // in the Plutus Core spec: version 1.1.0 | apply | lam | lam | var 2 | con [data] |
+        // filler | chunk of 30 bytes (CBOR of the bytes) | end of chunks | filler
+        assert_eq!(
+            hex::encode(&script),
+            "5829\
+             0101003220024c011e\
+             581c07070707070707070707070707070707070707070707070707070707\
+             0001"
+        );
+        assert_eq!(
+            applied.hash().to_hex(),
+            "fecbb26b193d744a9fe17fcab689fc8d9f46342258cf38b115f28dcb"
+        );
+        assert_eq!(
+            blueprint.script_hash("vault.spend").unwrap().to_hex(),
+            "63ea1682deaff958843e19c6f4e47f5cb7304919c619e361ce3a75f2"
+        );
+        let program = Program::from_script_bytes(&script).unwrap();
+        assert_eq!(
+            program.term,
+            Term::Lambda(Box::new(Term::Lambda(Box::new(Term::Var(2))))).apply(Term::Constant(
+                cml_chain::plutus::uplc::Constant::Data(owner)
+            ))
+        );
+        assert!(matches!(
+            blueprint.apply_parameters("vault.spend", &[int(1)]),
+            Err(CIP57Error::SchemaMismatch { .. })
+        ));
+        assert!(matches!(
+            blueprint.apply_parameters("vault.spend", &[]),
+            Err(CIP57Error::WrongParameterCount { .. })
+        ));
+    }
+}
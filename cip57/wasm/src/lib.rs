@@ -0,0 +1,83 @@
+#![allow(
+    clippy::len_without_is_empty,
+    clippy::too_many_arguments,
+    clippy::new_without_default
+)]
+
+use cml_chain_wasm::{
+    plutus::{utils::PlutusScript, PlutusData},
+    PlutusDataList,
+};
+use cml_core_wasm::impl_wasm_conversions;
+use cml_crypto_wasm::ScriptHash;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// A CIP-57 Plutus contract blueprint (`plutus.json`)
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP57Blueprint(cml_cip57::Blueprint);
+
+impl_wasm_conversions!(cml_cip57::Blueprint, CIP57Blueprint);
+
+#[wasm_bindgen]
+impl CIP57Blueprint {
+    pub fn from_json(json: &str) -> Result<CIP57Blueprint, JsError> {
+        cml_cip57::Blueprint::from_json(json)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn to_json(&self) -> Result<String, JsError> {
+        self.0.to_json().map_err(Into::into)
+    }
+
+    pub fn title(&self) -> String {
+        self.0.preamble.title.clone()
+    }
+
+    /// Titles of all validators in the blueprint
+    pub fn validator_titles(&self) -> Vec<String> {
+        self.0
+            .validators
+            .iter()
+            .map(|validator| validator.title.clone())
+            .collect()
+    }
+
+    /// The compiled (unapplied) script of a validator, checked against its declared hash
+    pub fn script(&self, title: &str) -> Result<PlutusScript, JsError> {
+        self.0.script(title).map(Into::into).map_err(Into::into)
+    }
+
+    pub fn script_hash(&self, title: &str) -> Result<ScriptHash, JsError> {
+        self.0
+            .script_hash(title)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn validate_datum(&self, title: &str, datum: &PlutusData) -> Result<(), JsError> {
+        self.0
+            .validate_datum(title, datum.as_ref())
+            .map_err(Into::into)
+    }
+
+    pub fn validate_redeemer(&self, title: &str, redeemer: &PlutusData) -> Result<(), JsError> {
+        self.0
+            .validate_redeemer(title, redeemer.as_ref())
+            .map_err(Into::into)
+    }
+
+    /// Applies the parameters (in declaration order) to a parameterized validator
+    /// after checking them against their schemas.
+    pub fn apply_parameters(
+        &self,
+        title: &str,
+        params: &PlutusDataList,
+    ) -> Result<PlutusScript, JsError> {
+        self.0
+            .apply_parameters(title, params.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
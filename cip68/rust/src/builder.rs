@@ -0,0 +1,199 @@
+use std::convert::TryFrom;
+
+use cml_chain::address::Address;
+use cml_chain::assets::{AssetName, Coin, MultiAsset};
+use cml_chain::builders::mint_builder::{MintBuilderResult, SingleMintBuilder};
+use cml_chain::builders::output_builder::{SingleOutputBuilderResult, TransactionOutputBuilder};
+use cml_chain::builders::witness_builder::{NativeScriptWitnessInfo, PartialPlutusWitness};
+use cml_chain::transaction::NativeScript;
+use cml_chain::transaction::{DatumOption, RequiredSigners};
+use cml_chain::PolicyId;
+use cml_core::ordered_hash_map::OrderedHashMap;
+
+use crate::datum::CIP68Datum;
+use crate::error::CIP68Error;
+use crate::label::CIP67Label;
+
+/// The mint of a CIP-68 reference/user token pair along with the output locking the reference token
+#[derive(Clone)]
+pub struct CIP68MintBuilderResult {
+    pub mint: MintBuilderResult,
+    pub reference_output: SingleOutputBuilderResult,
+}
+
+/// Mints a CIP-68 reference token (label 100) and its user token (label 222/333/444) under
+/// the same policy and name, and creates the output locking the reference token with the
+/// metadata as an inline datum.
+#[derive(Clone, Debug)]
+pub struct CIP68MintBuilder {
+    reference_asset_name: AssetName,
+    user_asset_name: AssetName,
+    user_amount: i64,
+    datum: CIP68Datum,
+}
+
+impl CIP68MintBuilder {
+    /// # Arguments
+    ///
+    /// * `user_label` - label of the user token. Can't be the reference NFT label.
+    /// * `name` - asset name without any label. At most 28 bytes.
+    /// * `user_amount` - how many user tokens to mint
+    /// * `datum` - the metadata datum to lock with the reference token
+    pub fn new(
+        user_label: CIP67Label,
+        name: &[u8],
+        user_amount: u64,
+        datum: CIP68Datum,
+    ) -> Result<Self, CIP68Error> {
+        if user_label == CIP67Label::REFERENCE_NFT {
+            return Err(CIP68Error::InvalidUserTokenLabel(user_label.get()));
+        }
+        let user_amount =
+            i64::try_from(user_amount).map_err(|_| CIP68Error::AmountTooLarge(user_amount))?;
+        Ok(Self {
+            reference_asset_name: CIP67Label::REFERENCE_NFT.asset_name(name)?,
+            user_asset_name: user_label.asset_name(name)?,
+            user_amount,
+            datum,
+        })
+    }
+
+    /// A single (label 222) NFT
+    pub fn new_nft(name: &[u8], datum: CIP68Datum) -> Result<Self, CIP68Error> {
+        Self::new(CIP67Label::NFT, name, 1, datum)
+    }
+
+    pub fn reference_asset_name(&self) -> &AssetName {
+        &self.reference_asset_name
+    }
+
+    pub fn user_asset_name(&self) -> &AssetName {
+        &self.user_asset_name
+    }
+
+    pub fn datum(&self) -> &CIP68Datum {
+        &self.datum
+    }
+
+    /// Mint of one reference token and `user_amount` user tokens.
+    /// Use this directly if the policy needs something other than `native_script`/`plutus_script`.
+    pub fn mint_builder(&self) -> SingleMintBuilder {
+        let mut assets = OrderedHashMap::new();
+        assets.insert(self.reference_asset_name.clone(), 1);
+        assets.insert(self.user_asset_name.clone(), self.user_amount);
+        SingleMintBuilder::new(assets)
+    }
+
+    /// Output to `address` holding the reference token with the datum inline and the minimum required ADA
+    pub fn reference_output(
+        &self,
+        policy_id: PolicyId,
+        address: Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<SingleOutputBuilderResult, CIP68Error> {
+        let mut multiasset = MultiAsset::new();
+        multiasset.set(policy_id, self.reference_asset_name.clone(), 1);
+        TransactionOutputBuilder::new()
+            .with_address(address)
+            .with_data(DatumOption::new_datum(self.datum.to_plutus_data()))
+            .next()?
+            .with_asset_and_min_required_coin(multiasset, coins_per_utxo_byte)?
+            .build()
+            .map_err(Into::into)
+    }
+
+    pub fn native_script(
+        self,
+        native_script: NativeScript,
+        witness_info: NativeScriptWitnessInfo,
+        reference_address: Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<CIP68MintBuilderResult, CIP68Error> {
+        let mint = self
+            .mint_builder()
+            .native_script(native_script, witness_info);
+        self.with_mint(mint, reference_address, coins_per_utxo_byte)
+    }
+
+    pub fn plutus_script(
+        self,
+        partial_witness: PartialPlutusWitness,
+        required_signers: RequiredSigners,
+        reference_address: Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<CIP68MintBuilderResult, CIP68Error> {
+        let mint = self
+            .mint_builder()
+            .plutus_script(partial_witness, required_signers);
+        self.with_mint(mint, reference_address, coins_per_utxo_byte)
+    }
+
+    fn with_mint(
+        self,
+        mint: MintBuilderResult,
+        reference_address: Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<CIP68MintBuilderResult, CIP68Error> {
+        let reference_output =
+            self.reference_output(mint.policy_id, reference_address, coins_per_utxo_byte)?;
+        Ok(CIP68MintBuilderResult {
+            mint,
+            reference_output,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datum::CIP68MetadataDetails;
+    use cml_chain::transaction::TransactionOutput;
+    use cml_crypto::Ed25519KeyHash;
+
+    #[test]
+    fn mint_nft_pair() {
+        let datum =
+            CIP68MetadataDetails::new("Bud".to_owned(), "ipfs://bud".to_owned()).to_datum(1);
+        let builder = CIP68MintBuilder::new_nft(b"Bud", datum.clone()).unwrap();
+        let native_script = NativeScript::new_script_pubkey(Ed25519KeyHash::from([0; 28]));
+        let witness_info = NativeScriptWitnessInfo::assume_signature_count();
+        let address =
+            Address::from_bech32("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8")
+                .unwrap();
+        let result = builder
+            .native_script(native_script.clone(), witness_info, address, 4310)
+            .unwrap();
+        assert_eq!(result.mint.policy_id, native_script.hash());
+        let reference_name = CIP67Label::REFERENCE_NFT.asset_name(b"Bud").unwrap();
+        let user_name = CIP67Label::NFT.asset_name(b"Bud").unwrap();
+        assert_eq!(result.mint.assets.get(&reference_name), Some(&1));
+        assert_eq!(result.mint.assets.get(&user_name), Some(&1));
+        let output = match &result.reference_output.output {
+            TransactionOutput::ConwayFormatTxOut(output) => output,
+            _ => panic!("expected a post-Alonzo output"),
+        };
+        match &output.datum_option {
+            Some(DatumOption::Datum { datum: inline, .. }) => {
+                assert_eq!(CIP68Datum::from_plutus_data(inline).unwrap(), datum)
+            }
+            _ => panic!("expected an inline datum"),
+        }
+        assert_eq!(
+            output
+                .amount
+                .multiasset
+                .get(&native_script.hash(), &reference_name),
+            Some(1)
+        );
+        assert!(output.amount.coin > 0);
+        assert!(matches!(
+            CIP68MintBuilder::new(
+                CIP67Label::REFERENCE_NFT,
+                b"Bud",
+                1,
+                CIP68Datum::new(Default::default(), 1)
+            ),
+            Err(CIP68Error::InvalidUserTokenLabel(100))
+        ));
+    }
+}
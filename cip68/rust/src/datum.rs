@@ -0,0 +1,379 @@
+use std::convert::TryFrom;
+
+use cml_chain::plutus::{ConstrPlutusData, PlutusData, PlutusMap};
+use cml_chain::utils::BigInteger;
+use cml_cip25::{CIP25ChunkableString, CIP25FilesDetails, CIP25MetadataDetails, CIP25String64};
+
+use crate::error::CIP68Error;
+
+/// The CIP-68 datum stored inline in the reference token's output:
+/// `#6.121([metadata, version, extra])`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP68Datum {
+    /// Metadata as a map from byte string keys to arbitrary plutus data
+    pub metadata: PlutusMap,
+    pub version: u64,
+    /// Custom user defined data. This is unit (`Constr 0 []`) when not used.
+    pub extra: PlutusData,
+}
+
+impl CIP68Datum {
+    /// Creates a datum with unit as the extra field
+    pub fn new(metadata: PlutusMap, version: u64) -> Self {
+        Self {
+            metadata,
+            version,
+            extra: PlutusData::new_constr_plutus_data(ConstrPlutusData::new(0, vec![])),
+        }
+    }
+
+    pub fn to_plutus_data(&self) -> PlutusData {
+        PlutusData::new_constr_plutus_data(ConstrPlutusData::from(self))
+    }
+
+    pub fn from_plutus_data(data: &PlutusData) -> Result<Self, CIP68Error> {
+        match data {
+            PlutusData::ConstrPlutusData(constr) => Self::try_from(constr),
+            _ => Err(CIP68Error::InvalidField("datum")),
+        }
+    }
+}
+
+impl From<&CIP68Datum> for ConstrPlutusData {
+    fn from(datum: &CIP68Datum) -> Self {
+        ConstrPlutusData::new(
+            0,
+            vec![
+                PlutusData::new_map(datum.metadata.clone()),
+                PlutusData::new_integer(BigInteger::from(datum.version)),
+                datum.extra.clone(),
+            ],
+        )
+    }
+}
+
+impl TryFrom<&ConstrPlutusData> for CIP68Datum {
+    type Error = CIP68Error;
+
+    fn try_from(constr: &ConstrPlutusData) -> Result<Self, Self::Error> {
+        if constr.alternative != 0 {
+            return Err(CIP68Error::WrongConstructor(constr.alternative));
+        }
+        if constr.fields.len() != 3 {
+            return Err(CIP68Error::WrongFieldCount(constr.fields.len()));
+        }
+        let metadata = match &constr.fields[0] {
+            PlutusData::Map(map) => map.clone(),
+            _ => return Err(CIP68Error::InvalidField("metadata")),
+        };
+        let version = match &constr.fields[1] {
+            PlutusData::Integer(version) => version
+                .as_u64()
+                .ok_or(CIP68Error::InvalidField("version"))?,
+            _ => return Err(CIP68Error::InvalidField("version")),
+        };
+        Ok(Self {
+            metadata,
+            version,
+            extra: constr.fields[2].clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP68FilesDetails {
+    pub name: String,
+    pub media_type: String,
+    pub src: String,
+}
+
+impl CIP68FilesDetails {
+    pub fn new(name: String, media_type: String, src: String) -> Self {
+        Self {
+            name,
+            media_type,
+            src,
+        }
+    }
+}
+
+/// The standard NFT (label 222) metadata fields of a CIP-68 datum.
+/// This mirrors `CIP25MetadataDetails` but strings are stored as UTF-8 byte strings
+/// and are not limited to 64 bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CIP68MetadataDetails {
+    pub name: String,
+    pub image: String,
+    pub media_type: Option<String>,
+    pub description: Option<String>,
+    pub files: Option<Vec<CIP68FilesDetails>>,
+    /// Any other metadata entries (e.g. project specific attributes) in their original order
+    pub other: PlutusMap,
+}
+
+const NAME_KEY: &str = "name";
+const IMAGE_KEY: &str = "image";
+const MEDIA_TYPE_KEY: &str = "mediaType";
+const DESCRIPTION_KEY: &str = "description";
+const FILES_KEY: &str = "files";
+const SRC_KEY: &str = "src";
+
+impl CIP68MetadataDetails {
+    pub fn new(name: String, image: String) -> Self {
+        Self {
+            name,
+            image,
+            media_type: None,
+            description: None,
+            files: None,
+            other: PlutusMap::new(),
+        }
+    }
+
+    pub fn to_plutus_map(&self) -> PlutusMap {
+        let mut map = PlutusMap::new();
+        set_string(&mut map, NAME_KEY, &self.name);
+        set_string(&mut map, IMAGE_KEY, &self.image);
+        if let Some(media_type) = &self.media_type {
+            set_string(&mut map, MEDIA_TYPE_KEY, media_type);
+        }
+        if let Some(description) = &self.description {
+            set_string(&mut map, DESCRIPTION_KEY, description);
+        }
+        if let Some(files) = &self.files {
+            let files = files
+                .iter()
+                .map(|file| {
+                    let mut file_map = PlutusMap::new();
+                    set_string(&mut file_map, NAME_KEY, &file.name);
+                    set_string(&mut file_map, MEDIA_TYPE_KEY, &file.media_type);
+                    set_string(&mut file_map, SRC_KEY, &file.src);
+                    PlutusData::new_map(file_map)
+                })
+                .collect();
+            map.set(key(FILES_KEY), PlutusData::new_list(files));
+        }
+        map.entries.extend(self.other.entries.iter().cloned());
+        map
+    }
+
+    pub fn from_plutus_map(map: &PlutusMap) -> Result<Self, CIP68Error> {
+        let name = get_string(map, NAME_KEY)?.ok_or(CIP68Error::MissingField(NAME_KEY))?;
+        let image = get_string(map, IMAGE_KEY)?.ok_or(CIP68Error::MissingField(IMAGE_KEY))?;
+        let media_type = get_string(map, MEDIA_TYPE_KEY)?;
+        let description = get_string(map, DESCRIPTION_KEY)?;
+        let files = match map.get(&key(FILES_KEY)) {
+            None => None,
+            Some(PlutusData::List { list, .. }) => Some(
+                list.iter()
+                    .map(|file| match file {
+                        PlutusData::Map(file_map) => Ok(CIP68FilesDetails {
+                            name: get_string(file_map, NAME_KEY)?
+                                .ok_or(CIP68Error::MissingField("files.name"))?,
+                            media_type: get_string(file_map, MEDIA_TYPE_KEY)?
+                                .ok_or(CIP68Error::MissingField("files.mediaType"))?,
+                            src: get_string(file_map, SRC_KEY)?
+                                .ok_or(CIP68Error::MissingField("files.src"))?,
+                        }),
+                        _ => Err(CIP68Error::InvalidField(FILES_KEY)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Some(_) => return Err(CIP68Error::InvalidField(FILES_KEY)),
+        };
+        let known_keys = [
+            NAME_KEY,
+            IMAGE_KEY,
+            MEDIA_TYPE_KEY,
+            DESCRIPTION_KEY,
+            FILES_KEY,
+        ]
+        .iter()
+        .map(|k| key(k))
+        .collect::<Vec<_>>();
+        let mut other = PlutusMap::new();
+        other.entries = map
+            .entries
+            .iter()
+            .filter(|(k, _)| !known_keys.contains(k))
+            .cloned()
+            .collect();
+        Ok(Self {
+            name,
+            image,
+            media_type,
+            description,
+            files,
+            other,
+        })
+    }
+
+    pub fn to_datum(&self, version: u64) -> CIP68Datum {
+        CIP68Datum::new(self.to_plutus_map(), version)
+    }
+
+    pub fn from_datum(datum: &CIP68Datum) -> Result<Self, CIP68Error> {
+        Self::from_plutus_map(&datum.metadata)
+    }
+}
+
+impl From<&CIP25MetadataDetails> for CIP68MetadataDetails {
+    fn from(details: &CIP25MetadataDetails) -> Self {
+        Self {
+            name: details.name.0.clone(),
+            image: String::from(&details.image),
+            media_type: details.media_type.as_ref().map(|m| m.0.clone()),
+            description: details.description.as_ref().map(String::from),
+            files: details.files.as_ref().map(|files| {
+                files
+                    .iter()
+                    .map(|file| CIP68FilesDetails {
+                        name: file.name.0.clone(),
+                        media_type: file.media_type.0.clone(),
+                        src: String::from(&file.src),
+                    })
+                    .collect()
+            }),
+            other: PlutusMap::new(),
+        }
+    }
+}
+
+/// Fails if the name or media types are over 64 bytes. Other entries are dropped.
+impl TryFrom<&CIP68MetadataDetails> for CIP25MetadataDetails {
+    type Error = CIP68Error;
+
+    fn try_from(details: &CIP68MetadataDetails) -> Result<Self, Self::Error> {
+        let mut cip25 = CIP25MetadataDetails::new(
+            CIP25String64::new_str(&details.name)?,
+            CIP25ChunkableString::from(details.image.as_str()),
+        );
+        cip25.media_type = details
+            .media_type
+            .as_deref()
+            .map(CIP25String64::new_str)
+            .transpose()?;
+        cip25.description = details
+            .description
+            .as_deref()
+            .map(CIP25ChunkableString::from);
+        cip25.files = details
+            .files
+            .as_ref()
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|file| {
+                        Ok(CIP25FilesDetails::new(
+                            CIP25String64::new_str(&file.name)?,
+                            CIP25String64::new_str(&file.media_type)?,
+                            CIP25ChunkableString::from(file.src.as_str()),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, CIP68Error>>()
+            })
+            .transpose()?;
+        Ok(cip25)
+    }
+}
+
+fn key(key: &str) -> PlutusData {
+    PlutusData::new_bytes(key.as_bytes().to_vec())
+}
+
+fn set_string(map: &mut PlutusMap, k: &str, value: &str) {
+    map.set(key(k), PlutusData::new_bytes(value.as_bytes().to_vec()));
+}
+
+fn get_string(map: &PlutusMap, k: &'static str) -> Result<Option<String>, CIP68Error> {
+    match map.get(&key(k)) {
+        None => Ok(None),
+        Some(PlutusData::Bytes { bytes, .. }) => String::from_utf8(bytes.clone())
+            .map(Some)
+            .map_err(|_| CIP68Error::InvalidUtf8(k)),
+        Some(_) => Err(CIP68Error::InvalidField(k)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_core::serialization::{Deserialize, Serialize};
+
+    #[test]
+    fn datum_round_trip() {
+        let mut details = CIP68MetadataDetails::new(
+            "SpaceBud #1".to_owned(),
+            "ipfs://QmRhTTbUrPYEw3mJGGhQqQST9k86v1DPBiTTWJGKDJsVFw".to_owned(),
+        );
+        details.media_type = Some("image/png".to_owned());
+        details.files = Some(vec![CIP68FilesDetails::new(
+            "SpaceBud #1".to_owned(),
+            "image/png".to_owned(),
+            "ipfs://QmRhTTbUrPYEw3mJGGhQqQST9k86v1DPBiTTWJGKDJsVFw".to_owned(),
+        )]);
+        details.other.set(
+            key("traits"),
+            PlutusData::new_list(vec![PlutusData::new_bytes(b"Star Suit".to_vec())]),
+        );
+        let datum = details.to_datum(1);
+        let bytes = datum.to_plutus_data().to_cbor_bytes();
+        // 121 tag for constructor 0
+        assert_eq!(&bytes[..2], &[0xd8, 0x79]);
+        let parsed =
+            CIP68Datum::from_plutus_data(&PlutusData::from_cbor_bytes(&bytes).unwrap()).unwrap();
+        assert_eq!(parsed, datum);
+        assert_eq!(CIP68MetadataDetails::from_datum(&parsed).unwrap(), details);
+    }
+
+    #[test]
+    fn invalid_datums() {
+        let metadata = PlutusData::new_map(PlutusMap::new());
+        let version = PlutusData::new_integer(BigInteger::from(1u64));
+        assert!(matches!(
+            CIP68Datum::try_from(&ConstrPlutusData::new(1, vec![])),
+            Err(CIP68Error::WrongConstructor(1))
+        ));
+        assert!(matches!(
+            CIP68Datum::try_from(&ConstrPlutusData::new(
+                0,
+                vec![metadata.clone(), version.clone()]
+            )),
+            Err(CIP68Error::WrongFieldCount(2))
+        ));
+        assert!(matches!(
+            CIP68Datum::try_from(&ConstrPlutusData::new(
+                0,
+                vec![version.clone(), version, metadata]
+            )),
+            Err(CIP68Error::InvalidField("metadata"))
+        ));
+        let datum = CIP68Datum::new(PlutusMap::new(), 1);
+        assert!(matches!(
+            CIP68MetadataDetails::from_datum(&datum),
+            Err(CIP68Error::MissingField("name"))
+        ));
+    }
+
+    #[test]
+    fn cip25_conversion() {
+        let mut cip25 = CIP25MetadataDetails::new(
+            CIP25String64::new_str("Test").unwrap(),
+            CIP25ChunkableString::from(
+                "https://this.is.a.very.long.url.so.it.gets.chunked/into/several/pieces.png",
+            ),
+        );
+        cip25.description = Some(CIP25ChunkableString::from("desc"));
+        let cip68 = CIP68MetadataDetails::from(&cip25);
+        assert_eq!(
+            cip68.image,
+            "https://this.is.a.very.long.url.so.it.gets.chunked/into/several/pieces.png"
+        );
+        let back = CIP25MetadataDetails::try_from(&cip68).unwrap();
+        assert_eq!(String::from(&back.image), cip68.image);
+        assert_eq!(back.name.0, "Test");
+        let mut long_name = cip68;
+        long_name.name = "a".repeat(65);
+        assert!(CIP25MetadataDetails::try_from(&long_name).is_err());
+    }
+}
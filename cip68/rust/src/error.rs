@@ -0,0 +1,32 @@
+use cml_chain::builders::output_builder::OutputBuilderError;
+use cml_core::error::DeserializeError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CIP68Error {
+    #[error("Asset name is too short to contain a CIP-67 label")]
+    AssetNameTooShort,
+    #[error("CIP-67 label prefix {0} must start and end with a zero nibble")]
+    InvalidLabelPadding(String),
+    #[error(
+        "CIP-67 label {label} checksum mismatch: expected {expected:#04x}, found {found:#04x}"
+    )]
+    LabelChecksumMismatch { label: u16, expected: u8, found: u8 },
+    #[error("CIP-67 label {0} can't be used for a user token")]
+    InvalidUserTokenLabel(u16),
+    #[error("CIP-68 datum must use constructor 0, found {0}")]
+    WrongConstructor(u64),
+    #[error("CIP-68 datum must have 3 fields (metadata, version, extra), found {0}")]
+    WrongFieldCount(usize),
+    #[error("CIP-68 field {0} has the wrong type")]
+    InvalidField(&'static str),
+    #[error("CIP-68 metadata is missing {0}")]
+    MissingField(&'static str),
+    #[error("CIP-68 metadata field {0} is not valid UTF-8")]
+    InvalidUtf8(&'static str),
+    #[error("Mint amount {0} does not fit in an i64")]
+    AmountTooLarge(u64),
+    #[error("Deserialize: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Output builder: {0}")]
+    OutputBuilder(#[from] OutputBuilderError),
+}
@@ -0,0 +1,171 @@
+use cml_chain::assets::AssetName;
+
+use crate::error::CIP68Error;
+
+/// Size in bytes of a CIP-67 label prefix on an asset name
+pub const CIP67_PREFIX_SIZE: usize = 4;
+
+/// A CIP-67 asset name label.
+///
+/// It is encoded as a 4 byte asset name prefix: a zero nibble, the 16-bit label,
+/// a CRC-8 checksum of the label and then another zero nibble.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CIP67Label(pub u16);
+
+impl CIP67Label {
+    /// CIP-68 reference NFT holding the metadata datum
+    pub const REFERENCE_NFT: Self = Self(100);
+    /// CIP-68 user NFT
+    pub const NFT: Self = Self(222);
+    /// CIP-68 user fungible token
+    pub const FT: Self = Self(333);
+    /// CIP-68 user rich fungible token
+    pub const RFT: Self = Self(444);
+
+    pub fn new(label: u16) -> Self {
+        Self(label)
+    }
+
+    pub fn get(&self) -> u16 {
+        self.0
+    }
+
+    pub fn checksum(&self) -> u8 {
+        crc8(&self.0.to_be_bytes())
+    }
+
+    pub fn to_prefix(&self) -> [u8; CIP67_PREFIX_SIZE] {
+        let [hi, lo] = self.0.to_be_bytes();
+        let checksum = self.checksum();
+        [
+            hi >> 4,
+            (hi << 4) | (lo >> 4),
+            (lo << 4) | (checksum >> 4),
+            checksum << 4,
+        ]
+    }
+
+    /// Parses the label from the first 4 bytes, checking the padding and checksum.
+    /// Any bytes after the prefix are ignored.
+    pub fn from_prefix(bytes: &[u8]) -> Result<Self, CIP68Error> {
+        if bytes.len() < CIP67_PREFIX_SIZE {
+            return Err(CIP68Error::AssetNameTooShort);
+        }
+        let prefix = &bytes[..CIP67_PREFIX_SIZE];
+        if prefix[0] & 0xF0 != 0 || prefix[3] & 0x0F != 0 {
+            return Err(CIP68Error::InvalidLabelPadding(hex::encode(prefix)));
+        }
+        let label = Self(u16::from_be_bytes([
+            (prefix[0] << 4) | (prefix[1] >> 4),
+            (prefix[1] << 4) | (prefix[2] >> 4),
+        ]));
+        let found = (prefix[2] << 4) | (prefix[3] >> 4);
+        let expected = label.checksum();
+        if found != expected {
+            return Err(CIP68Error::LabelChecksumMismatch {
+                label: label.0,
+                expected,
+                found,
+            });
+        }
+        Ok(label)
+    }
+
+    /// Creates an asset name of this label's prefix followed by `name`.
+    /// `name` can be at most 28 bytes.
+    pub fn asset_name(&self, name: &[u8]) -> Result<AssetName, CIP68Error> {
+        let mut bytes = self.to_prefix().to_vec();
+        bytes.extend_from_slice(name);
+        AssetName::new(bytes).map_err(Into::into)
+    }
+
+    /// Splits a labeled asset name into its label and the rest of the name
+    pub fn split_asset_name(asset_name: &AssetName) -> Result<(Self, &[u8]), CIP68Error> {
+        let label = Self::from_prefix(asset_name.get())?;
+        Ok((label, &asset_name.get()[CIP67_PREFIX_SIZE..]))
+    }
+}
+
+impl From<u16> for CIP67Label {
+    fn from(label: u16) -> Self {
+        Self(label)
+    }
+}
+
+impl std::fmt::Display for CIP67Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({})", self.0)
+    }
+}
+
+/// CRC-8 (polynomial 0x07, no reflection, zero init) as specified by CIP-67
+pub fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cip67_test_vectors() {
+        let vectors = [
+            (CIP67Label::new(0), "00000000"),
+            (CIP67Label::new(1), "00001070"),
+            (CIP67Label::new(23), "00017650"),
+            (CIP67Label::REFERENCE_NFT, "000643b0"),
+            (CIP67Label::NFT, "000de140"),
+            (CIP67Label::FT, "0014df10"),
+            (CIP67Label::RFT, "001bc280"),
+            (CIP67Label::new(65535), "0ffff240"),
+        ];
+        for (label, prefix) in vectors.iter() {
+            assert_eq!(hex::encode(label.to_prefix()), *prefix);
+            assert_eq!(
+                CIP67Label::from_prefix(&hex::decode(prefix).unwrap()).unwrap(),
+                *label
+            );
+        }
+    }
+
+    #[test]
+    fn asset_name_round_trip() {
+        let asset_name = CIP67Label::NFT.asset_name(b"SpaceBud").unwrap();
+        assert_eq!(hex::encode(asset_name.get()), "000de1405370616365427564");
+        let (label, name) = CIP67Label::split_asset_name(&asset_name).unwrap();
+        assert_eq!(label, CIP67Label::NFT);
+        assert_eq!(name, b"SpaceBud");
+        assert!(CIP67Label::NFT.asset_name(&[0; 29]).is_err());
+    }
+
+    #[test]
+    fn invalid_prefixes() {
+        assert!(matches!(
+            CIP67Label::from_prefix(&[0x00, 0x0d, 0xe1]),
+            Err(CIP68Error::AssetNameTooShort)
+        ));
+        assert!(matches!(
+            CIP67Label::from_prefix(&[0x10, 0x0d, 0xe1, 0x40]),
+            Err(CIP68Error::InvalidLabelPadding(_))
+        ));
+        assert!(matches!(
+            CIP67Label::from_prefix(&[0x00, 0x0d, 0xe1, 0x50]),
+            Err(CIP68Error::LabelChecksumMismatch {
+                label: 222,
+                expected: 0x14,
+                found: 0x15
+            })
+        ));
+    }
+}
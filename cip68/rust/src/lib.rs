@@ -0,0 +1,17 @@
+//! Support for CIP-67 asset name labels and CIP-68 datum metadata.
+//!
+//! CIP-68 tokens come in pairs sharing a policy and name: a reference NFT (label 100) which is locked
+//! in an output holding the metadata as an inline datum, and the user token (label 222/333/444) which
+//! is what is actually held and transferred. See <https://cips.cardano.org/cip/CIP-68>.
+
+pub use cml_core::error::{DeserializeError, DeserializeFailure};
+
+pub mod builder;
+pub mod datum;
+pub mod error;
+pub mod label;
+
+pub use builder::{CIP68MintBuilder, CIP68MintBuilderResult};
+pub use datum::{CIP68Datum, CIP68FilesDetails, CIP68MetadataDetails};
+pub use error::CIP68Error;
+pub use label::CIP67Label;
@@ -0,0 +1,334 @@
+#![allow(
+    clippy::len_without_is_empty,
+    clippy::too_many_arguments,
+    clippy::new_without_default
+)]
+
+use cml_chain_wasm::{
+    address::Address,
+    assets::AssetName,
+    builders::{
+        mint_builder::{MintBuilderResult, SingleMintBuilder},
+        output_builder::SingleOutputBuilderResult,
+        witness_builder::{NativeScriptWitnessInfo, PartialPlutusWitness},
+    },
+    plutus::{PlutusData, PlutusMap},
+    transaction::{NativeScript, RequiredSigners},
+    Coin, PolicyId,
+};
+use cml_core_wasm::{impl_wasm_conversions, impl_wasm_list};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// A CIP-67 asset name label
+#[derive(Clone, Copy, Debug)]
+#[wasm_bindgen]
+pub struct CIP67Label(cml_cip68::CIP67Label);
+
+impl_wasm_conversions!(cml_cip68::CIP67Label, CIP67Label);
+
+#[wasm_bindgen]
+impl CIP67Label {
+    pub fn new(label: u16) -> Self {
+        cml_cip68::CIP67Label::new(label).into()
+    }
+
+    pub fn reference_nft() -> Self {
+        cml_cip68::CIP67Label::REFERENCE_NFT.into()
+    }
+
+    pub fn nft() -> Self {
+        cml_cip68::CIP67Label::NFT.into()
+    }
+
+    pub fn ft() -> Self {
+        cml_cip68::CIP67Label::FT.into()
+    }
+
+    pub fn rft() -> Self {
+        cml_cip68::CIP67Label::RFT.into()
+    }
+
+    pub fn get(&self) -> u16 {
+        self.0.get()
+    }
+
+    /// The 4 byte asset name prefix encoding this label
+    pub fn to_prefix(&self) -> Vec<u8> {
+        self.0.to_prefix().to_vec()
+    }
+
+    /// Parses the label from the first 4 bytes, checking the padding and checksum.
+    pub fn from_prefix(bytes: &[u8]) -> Result<CIP67Label, JsError> {
+        cml_cip68::CIP67Label::from_prefix(bytes)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// Creates an asset name of this label's prefix followed by name (at most 28 bytes)
+    pub fn asset_name(&self, name: &[u8]) -> Result<AssetName, JsError> {
+        self.0.asset_name(name).map(Into::into).map_err(Into::into)
+    }
+
+    /// The label of a labeled asset name
+    pub fn from_asset_name(asset_name: &AssetName) -> Result<CIP67Label, JsError> {
+        cml_cip68::CIP67Label::split_asset_name(asset_name.as_ref())
+            .map(|(label, _name)| label.into())
+            .map_err(Into::into)
+    }
+
+    /// The rest of a labeled asset name after the label prefix
+    pub fn unlabeled_name(asset_name: &AssetName) -> Result<Vec<u8>, JsError> {
+        cml_cip68::CIP67Label::split_asset_name(asset_name.as_ref())
+            .map(|(_label, name)| name.to_vec())
+            .map_err(Into::into)
+    }
+}
+
+/// The CIP-68 datum stored inline in the reference token's output
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP68Datum(cml_cip68::CIP68Datum);
+
+impl_wasm_conversions!(cml_cip68::CIP68Datum, CIP68Datum);
+
+#[wasm_bindgen]
+impl CIP68Datum {
+    /// Creates a datum with unit as the extra field
+    pub fn new(metadata: &PlutusMap, version: u64) -> Self {
+        cml_cip68::CIP68Datum::new(metadata.clone().into(), version).into()
+    }
+
+    pub fn metadata(&self) -> PlutusMap {
+        self.0.metadata.clone().into()
+    }
+
+    pub fn version(&self) -> u64 {
+        self.0.version
+    }
+
+    pub fn set_extra(&mut self, extra: &PlutusData) {
+        self.0.extra = extra.clone().into()
+    }
+
+    pub fn extra(&self) -> PlutusData {
+        self.0.extra.clone().into()
+    }
+
+    pub fn to_plutus_data(&self) -> PlutusData {
+        self.0.to_plutus_data().into()
+    }
+
+    pub fn from_plutus_data(data: &PlutusData) -> Result<CIP68Datum, JsError> {
+        cml_cip68::CIP68Datum::from_plutus_data(data.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
+
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP68FilesDetails(cml_cip68::CIP68FilesDetails);
+
+impl_wasm_conversions!(cml_cip68::CIP68FilesDetails, CIP68FilesDetails);
+
+#[wasm_bindgen]
+impl CIP68FilesDetails {
+    pub fn name(&self) -> String {
+        self.0.name.clone()
+    }
+
+    pub fn media_type(&self) -> String {
+        self.0.media_type.clone()
+    }
+
+    pub fn src(&self) -> String {
+        self.0.src.clone()
+    }
+
+    pub fn new(name: String, media_type: String, src: String) -> Self {
+        cml_cip68::CIP68FilesDetails::new(name, media_type, src).into()
+    }
+}
+
+impl_wasm_list!(
+    cml_cip68::CIP68FilesDetails,
+    CIP68FilesDetails,
+    CIP68FilesDetailsList
+);
+
+/// The standard NFT (label 222) metadata fields of a CIP-68 datum
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP68MetadataDetails(cml_cip68::CIP68MetadataDetails);
+
+impl_wasm_conversions!(cml_cip68::CIP68MetadataDetails, CIP68MetadataDetails);
+
+#[wasm_bindgen]
+impl CIP68MetadataDetails {
+    pub fn name(&self) -> String {
+        self.0.name.clone()
+    }
+
+    pub fn image(&self) -> String {
+        self.0.image.clone()
+    }
+
+    pub fn set_media_type(&mut self, media_type: String) {
+        self.0.media_type = Some(media_type)
+    }
+
+    pub fn media_type(&self) -> Option<String> {
+        self.0.media_type.clone()
+    }
+
+    pub fn set_description(&mut self, description: String) {
+        self.0.description = Some(description)
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.0.description.clone()
+    }
+
+    pub fn set_files(&mut self, files: &CIP68FilesDetailsList) {
+        self.0.files = Some(files.clone().into())
+    }
+
+    pub fn files(&self) -> Option<CIP68FilesDetailsList> {
+        self.0.files.clone().map(std::convert::Into::into)
+    }
+
+    /// Any other metadata entries (e.g. project specific attributes)
+    pub fn set_other(&mut self, other: &PlutusMap) {
+        self.0.other = other.clone().into()
+    }
+
+    pub fn other(&self) -> PlutusMap {
+        self.0.other.clone().into()
+    }
+
+    pub fn new(name: String, image: String) -> Self {
+        cml_cip68::CIP68MetadataDetails::new(name, image).into()
+    }
+
+    pub fn to_datum(&self, version: u64) -> CIP68Datum {
+        self.0.to_datum(version).into()
+    }
+
+    pub fn from_datum(datum: &CIP68Datum) -> Result<CIP68MetadataDetails, JsError> {
+        cml_cip68::CIP68MetadataDetails::from_datum(datum.as_ref())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct CIP68MintBuilderResult(cml_cip68::CIP68MintBuilderResult);
+
+impl_wasm_conversions!(cml_cip68::CIP68MintBuilderResult, CIP68MintBuilderResult);
+
+#[wasm_bindgen]
+impl CIP68MintBuilderResult {
+    pub fn mint(&self) -> MintBuilderResult {
+        self.0.mint.clone().into()
+    }
+
+    pub fn reference_output(&self) -> SingleOutputBuilderResult {
+        self.0.reference_output.clone().into()
+    }
+}
+
+/// Mints a CIP-68 reference token and its user token under the same policy and name,
+/// and creates the output locking the reference token with the metadata as an inline datum.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct CIP68MintBuilder(cml_cip68::CIP68MintBuilder);
+
+impl_wasm_conversions!(cml_cip68::CIP68MintBuilder, CIP68MintBuilder);
+
+#[wasm_bindgen]
+impl CIP68MintBuilder {
+    pub fn new(
+        user_label: &CIP67Label,
+        name: &[u8],
+        user_amount: u64,
+        datum: &CIP68Datum,
+    ) -> Result<CIP68MintBuilder, JsError> {
+        cml_cip68::CIP68MintBuilder::new(user_label.0, name, user_amount, datum.clone().into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    /// A single (label 222) NFT
+    pub fn new_nft(name: &[u8], datum: &CIP68Datum) -> Result<CIP68MintBuilder, JsError> {
+        cml_cip68::CIP68MintBuilder::new_nft(name, datum.clone().into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn reference_asset_name(&self) -> AssetName {
+        self.0.reference_asset_name().clone().into()
+    }
+
+    pub fn user_asset_name(&self) -> AssetName {
+        self.0.user_asset_name().clone().into()
+    }
+
+    pub fn mint_builder(&self) -> SingleMintBuilder {
+        self.0.mint_builder().into()
+    }
+
+    pub fn reference_output(
+        &self,
+        policy_id: &PolicyId,
+        address: &Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<SingleOutputBuilderResult, JsError> {
+        self.0
+            .reference_output(
+                policy_id.clone().into(),
+                address.clone().into(),
+                coins_per_utxo_byte,
+            )
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn native_script(
+        &self,
+        native_script: &NativeScript,
+        witness_info: &NativeScriptWitnessInfo,
+        reference_address: &Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<CIP68MintBuilderResult, JsError> {
+        self.0
+            .clone()
+            .native_script(
+                native_script.clone().into(),
+                witness_info.clone().into(),
+                reference_address.clone().into(),
+                coins_per_utxo_byte,
+            )
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn plutus_script(
+        &self,
+        partial_witness: &PartialPlutusWitness,
+        required_signers: &RequiredSigners,
+        reference_address: &Address,
+        coins_per_utxo_byte: Coin,
+    ) -> Result<CIP68MintBuilderResult, JsError> {
+        self.0
+            .clone()
+            .plutus_script(
+                partial_witness.clone().into(),
+                required_signers.clone().into(),
+                reference_address.clone().into(),
+                coins_per_utxo_byte,
+            )
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
@@ -5,5 +5,9 @@
 // We don't need to worry about cml_core_wasm and cml_crypto_wasm since they
 // will be exported by the other crates here.
 pub use cml_chain_wasm::AssetNameList;
+pub use cml_cip20_wasm::CIP20Metadata;
 pub use cml_cip25_wasm::CIP25Metadata;
+pub use cml_cip26_wasm::CIP26RegistryEntry;
 pub use cml_cip36_wasm::CIP36DeregistrationCbor;
+pub use cml_cip57_wasm::CIP57Blueprint;
+pub use cml_cip68_wasm::CIP68Datum;
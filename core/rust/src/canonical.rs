@@ -0,0 +1,594 @@
+//! Detection of CBOR that isn't canonically encoded (RFC 7049 section 3.9):
+//! minimal length/argument encodings, definite lengths and map keys sorted
+//! shortest first then bytewise, without duplicates.
+//!
+//! `Deserialize::from_cbor_bytes_strict()` finds these from the encoding details the decoded
+//! value keeps in its `*Encoding` structs: the input is compared against the value's canonical
+//! re-encoding, so e.g. bounded bytes chunked as the ledger requires are accepted.
+//! `find_non_canonical()` checks raw CBOR without a type to go by.
+
+use crate::reader::current_position;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonCanonicalKind {
+    /// length, integer or tag argument not encoded in the fewest bytes
+    NonMinimalLength,
+    IndefiniteLength,
+    /// the map's keys are not in canonical order
+    UnsortedMapKeys,
+    DuplicateKey,
+    /// bytes remaining after the item
+    TrailingBytes,
+    /// re-encoding didn't reproduce the input e.g. an encoding detail that couldn't be preserved
+    NotRoundTrippable,
+}
+
+impl std::fmt::Display for NonCanonicalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonMinimalLength => write!(f, "non-minimal length"),
+            Self::IndefiniteLength => write!(f, "indefinite length"),
+            Self::UnsortedMapKeys => write!(f, "unsorted map keys"),
+            Self::DuplicateKey => write!(f, "duplicate key"),
+            Self::TrailingBytes => write!(f, "trailing bytes"),
+            Self::NotRoundTrippable => write!(f, "encoding not preserved"),
+        }
+    }
+}
+
+/// A non-canonical encoding found at `offset` (the start of the offending item)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonCanonical {
+    pub offset: u64,
+    pub kind: NonCanonicalKind,
+}
+
+impl std::fmt::Display for NonCanonical {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+thread_local! {
+    /// map offsets collected by allow_duplicate_keys() during strict deserialization
+    static DUPLICATES_ALLOWED: RefCell<Option<Vec<u64>>> = const { RefCell::new(None) };
+}
+
+/// Called by the `Deserialize` impls of maps that can have the same key more than once on chain
+/// (Plutus data and metadatum maps) right before reading the map header, so that strict
+/// deserialization accepts their duplicate keys. Any other map is treated the way the ledger
+/// (since Conway) treats it: duplicate keys are rejected.
+pub fn allow_duplicate_keys() {
+    DUPLICATES_ALLOWED.with(|allowed| {
+        if let Some(allowed) = allowed.borrow_mut().as_mut() {
+            if let Some((offset, _)) = current_position() {
+                allowed.push(offset);
+            }
+        }
+    });
+}
+
+/// Runs `f` collecting the offsets of maps that allow duplicate keys
+pub(crate) fn collect_duplicates_allowed<T>(f: impl FnOnce() -> T) -> (T, Vec<u64>) {
+    struct Restore(Option<Vec<u64>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DUPLICATES_ALLOWED.with(|allowed| *allowed.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(DUPLICATES_ALLOWED.with(|allowed| allowed.replace(Some(Vec::new()))));
+    let result = f();
+    let allowed = DUPLICATES_ALLOWED.with(|allowed| allowed.borrow_mut().take());
+    (result, allowed.unwrap_or_default())
+}
+
+/// Violations of `input` (one complete item) for a value whose `to_cbor_bytes()` is `preserved`
+/// and `to_canonical_cbor_bytes()` is `canonical`, in input order:
+/// * where the input differs from the canonical encoding of the value
+/// * duplicate keys in maps other than those at `duplicates_allowed`
+/// * the first difference if the value doesn't re-encode to the input
+pub(crate) fn strict_violations(
+    input: &[u8],
+    preserved: &[u8],
+    canonical: &[u8],
+    duplicates_allowed: &[u64],
+) -> Vec<NonCanonical> {
+    let mut violations = Vec::new();
+    match (Node::parse(input, 0, 0), Node::parse(canonical, 0, 0)) {
+        (Some(input_node), Some(canonical_node)) => {
+            input_node.duplicates(input, duplicates_allowed, &mut violations);
+            input_node.compare(input, &canonical_node, canonical, &mut violations);
+        }
+        // too deep to compare so only the encoding itself can be checked
+        _ => {
+            let (non_canonical, duplicates, _) = find_non_canonical(input);
+            violations.extend(non_canonical);
+            violations.extend(
+                duplicates
+                    .into_iter()
+                    .filter(|duplicate| !duplicates_allowed.contains(&duplicate.offset)),
+            );
+        }
+    }
+    if preserved != input {
+        let offset = preserved
+            .iter()
+            .zip(input)
+            .position(|(lhs, rhs)| lhs != rhs)
+            .unwrap_or_else(|| preserved.len().min(input.len()));
+        violations.push(NonCanonical {
+            offset: offset as u64,
+            kind: NonCanonicalKind::NotRoundTrippable,
+        });
+    }
+    violations.sort_by_key(|violation| violation.offset);
+    violations
+}
+
+/// A CBOR item parsed just enough to compare encodings
+struct Node {
+    offset: usize,
+    end: usize,
+    major: u8,
+    /// length, value or tag number. None for indefinite lengths
+    arg: Option<u64>,
+    header_len: usize,
+    /// array elements, map keys and values alternating, the tagged item or string chunks
+    children: Vec<Node>,
+    /// contents of a definite length string
+    contents: Range<usize>,
+}
+
+impl Node {
+    fn parse(bytes: &[u8], pos: usize, depth: usize) -> Option<Self> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let initial = *bytes.get(pos)?;
+        let major = initial >> 5;
+        let (arg, header_len) = match initial & 0x1f {
+            info @ 0..=23 => (Some(u64::from(info)), 1),
+            info @ 24..=27 => {
+                let len = 1usize << (info - 24);
+                let arg = bytes
+                    .get(pos + 1..pos + 1 + len)?
+                    .iter()
+                    .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+                (Some(arg), 1 + len)
+            }
+            31 if (2..=5).contains(&major) => (None, 1),
+            _ => return None,
+        };
+        let mut node = Self {
+            offset: pos,
+            end: pos + header_len,
+            major,
+            arg,
+            header_len,
+            children: Vec::new(),
+            contents: 0..0,
+        };
+        let children = match (major, arg) {
+            (2 | 3, Some(len)) => {
+                let end = node.end.checked_add(usize::try_from(len).ok()?)?;
+                if end > bytes.len() {
+                    return None;
+                }
+                node.contents = node.end..end;
+                node.end = end;
+                Some(0)
+            }
+            (2..=5, None) => None,
+            (4, Some(len)) => Some(len),
+            (5, Some(len)) => Some(len.checked_mul(2)?),
+            (6, _) => Some(1),
+            _ => Some(0),
+        };
+        let mut read = 0;
+        loop {
+            match children {
+                Some(count) if read == count => break,
+                None if *bytes.get(node.end)? == 0xff => {
+                    node.end += 1;
+                    break;
+                }
+                _ => (),
+            }
+            let child = Self::parse(bytes, node.end, depth + 1)?;
+            node.end = child.end;
+            node.children.push(child);
+            read += 1;
+        }
+        Some(node)
+    }
+
+    /// Minimal definite length encoding of the item, to compare map keys regardless of encoding
+    fn normalized(&self, bytes: &[u8], out: &mut Vec<u8>) {
+        let write_header = |out: &mut Vec<u8>, value: u64| {
+            let major = self.major << 5;
+            match value {
+                0..=23 => out.push(major | value as u8),
+                24..=0xff => out.extend([major | 24, value as u8]),
+                0x100..=0xffff => {
+                    out.push(major | 25);
+                    out.extend((value as u16).to_be_bytes());
+                }
+                0x1_0000..=0xffff_ffff => {
+                    out.push(major | 26);
+                    out.extend((value as u32).to_be_bytes());
+                }
+                _ => {
+                    out.push(major | 27);
+                    out.extend(value.to_be_bytes());
+                }
+            }
+        };
+        match self.major {
+            2 | 3 => {
+                let contents = if self.arg.is_some() {
+                    bytes[self.contents.clone()].to_vec()
+                } else {
+                    self.children
+                        .iter()
+                        .flat_map(|chunk| bytes[chunk.contents.clone()].iter().copied())
+                        .collect()
+                };
+                write_header(out, contents.len() as u64);
+                out.extend(contents);
+            }
+            4 | 5 => {
+                let len = self.children.len() as u64 / u64::from(self.major - 3);
+                write_header(out, len);
+                for child in &self.children {
+                    child.normalized(bytes, out);
+                }
+            }
+            // floats have no minimal form
+            7 => out.extend(&bytes[self.offset..self.end]),
+            _ => {
+                write_header(out, self.arg.unwrap_or_default());
+                for child in &self.children {
+                    child.normalized(bytes, out);
+                }
+            }
+        }
+    }
+
+    fn normalized_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.normalized(bytes, &mut out);
+        out
+    }
+
+    fn duplicates(&self, bytes: &[u8], allowed: &[u64], violations: &mut Vec<NonCanonical>) {
+        if self.major == 5 && !allowed.contains(&(self.offset as u64)) {
+            let mut seen = HashSet::new();
+            for key in self.children.iter().step_by(2) {
+                if !seen.insert(key.normalized_bytes(bytes)) {
+                    violations.push(NonCanonical {
+                        offset: key.offset as u64,
+                        kind: NonCanonicalKind::DuplicateKey,
+                    });
+                }
+            }
+        }
+        for child in &self.children {
+            child.duplicates(bytes, allowed, violations);
+        }
+    }
+
+    /// Reports where this input item differs from `canonical`, the same value encoded canonically
+    fn compare(
+        &self,
+        bytes: &[u8],
+        canonical: &Self,
+        canonical_bytes: &[u8],
+        violations: &mut Vec<NonCanonical>,
+    ) {
+        if self.major != canonical.major {
+            return;
+        }
+        let mut report = |kind| {
+            violations.push(NonCanonical {
+                offset: self.offset as u64,
+                kind,
+            })
+        };
+        match (self.arg, canonical.arg) {
+            (None, Some(_)) => report(NonCanonicalKind::IndefiniteLength),
+            (Some(_), Some(_)) if self.major != 7 && self.header_len > canonical.header_len => {
+                report(NonCanonicalKind::NonMinimalLength)
+            }
+            _ => (),
+        }
+        match self.major {
+            // chunks are only comparable if both are chunked
+            2 | 3 if self.arg.is_some() || canonical.arg.is_some() => (),
+            5 => self.compare_entries(bytes, canonical, canonical_bytes, violations),
+            _ => {
+                for (child, canonical_child) in self.children.iter().zip(&canonical.children) {
+                    child.compare(bytes, canonical_child, canonical_bytes, violations);
+                }
+            }
+        }
+    }
+
+    /// Pairs up map entries by key as they can be in a different order (or merged)
+    fn compare_entries(
+        &self,
+        bytes: &[u8],
+        canonical: &Self,
+        canonical_bytes: &[u8],
+        violations: &mut Vec<NonCanonical>,
+    ) {
+        let mut canonical_entries: HashMap<Vec<u8>, VecDeque<usize>> = HashMap::new();
+        for (index, key) in canonical.children.iter().step_by(2).enumerate() {
+            canonical_entries
+                .entry(key.normalized_bytes(canonical_bytes))
+                .or_default()
+                .push_back(index);
+        }
+        let mut previous = None;
+        let mut unsorted = false;
+        for entry in self.children.chunks(2) {
+            let index = match canonical_entries
+                .get_mut(&entry[0].normalized_bytes(bytes))
+                .and_then(|indices| indices.pop_front())
+            {
+                Some(index) => index,
+                // duplicate merged into an earlier entry
+                None => continue,
+            };
+            unsorted |= previous.is_some_and(|previous| previous > index);
+            previous = Some(index);
+            for (child, canonical_child) in entry.iter().zip(&canonical.children[2 * index..]) {
+                child.compare(bytes, canonical_child, canonical_bytes, violations);
+            }
+        }
+        if unsorted {
+            violations.push(NonCanonical {
+                offset: self.offset as u64,
+                kind: NonCanonicalKind::UnsortedMapKeys,
+            });
+        }
+    }
+}
+
+/// Nesting deeper than this is not checked
+const MAX_DEPTH: usize = 256;
+
+/// Non-canonical encodings in the CBOR item at the start of `bytes`, in input order.
+/// Returns the violations and where the item ends (None if the item is malformed).
+/// Duplicate keys are reported separately as some structures (e.g. Plutus data) allow them.
+pub fn find_non_canonical(bytes: &[u8]) -> (Vec<NonCanonical>, Vec<NonCanonical>, Option<usize>) {
+    let mut checker = Checker {
+        bytes,
+        violations: Vec::new(),
+        duplicates: Vec::new(),
+    };
+    let end = checker.item(0, 0);
+    (checker.violations, checker.duplicates, end)
+}
+
+struct Checker<'a> {
+    bytes: &'a [u8],
+    violations: Vec<NonCanonical>,
+    duplicates: Vec<NonCanonical>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, offset: usize, kind: NonCanonicalKind) {
+        self.violations.push(NonCanonical {
+            offset: offset as u64,
+            kind,
+        });
+    }
+
+    /// Whether another element follows after `read` of a definite (`Some`) or indefinite length
+    fn more(&self, len: Option<u64>, read: u64, pos: usize) -> Option<bool> {
+        match len {
+            Some(len) => Some(read < len),
+            None => Some(*self.bytes.get(pos)? != 0xff),
+        }
+    }
+
+    /// Checks the item at `pos`, returning where it ends
+    fn item(&mut self, pos: usize, depth: usize) -> Option<usize> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let initial = *self.bytes.get(pos)?;
+        let major = initial >> 5;
+        let (arg, mut end) = match initial & 0x1f {
+            info @ 0..=23 => (Some(u64::from(info)), pos + 1),
+            info @ 24..=27 => {
+                let len = 1usize << (info - 24);
+                let arg = self
+                    .bytes
+                    .get(pos + 1..pos + 1 + len)?
+                    .iter()
+                    .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+                // floats (major type 7 with 2/4/8 byte arguments) have no minimal form to check
+                let minimal = match len {
+                    1 => 24,
+                    2 => 0x100,
+                    4 => 0x1_0000,
+                    _ => 0x1_0000_0000,
+                };
+                if arg < minimal && (major != 7 || info == 24) {
+                    self.report(pos, NonCanonicalKind::NonMinimalLength);
+                }
+                (Some(arg), pos + 1 + len)
+            }
+            31 if (2..=5).contains(&major) => {
+                self.report(pos, NonCanonicalKind::IndefiniteLength);
+                (None, pos + 1)
+            }
+            _ => return None,
+        };
+        match major {
+            // bytes / text
+            2 | 3 => match arg {
+                Some(len) => end = end.checked_add(usize::try_from(len).ok()?)?,
+                None => {
+                    while *self.bytes.get(end)? != 0xff {
+                        end = self.item(end, depth + 1)?;
+                    }
+                    end += 1;
+                }
+            },
+            // array
+            4 => {
+                let mut read = 0;
+                while self.more(arg, read, end)? {
+                    end = self.item(end, depth + 1)?;
+                    read += 1;
+                }
+                if arg.is_none() {
+                    end += 1;
+                }
+            }
+            // map
+            5 => {
+                let mut keys: Vec<&[u8]> = Vec::new();
+                let mut seen = HashSet::new();
+                let mut unsorted = false;
+                let mut read = 0;
+                while self.more(arg, read, end)? {
+                    let key_start = end;
+                    end = self.item(end, depth + 1)?;
+                    let key = &self.bytes[key_start..end];
+                    if !seen.insert(key) {
+                        self.duplicates.push(NonCanonical {
+                            offset: key_start as u64,
+                            kind: NonCanonicalKind::DuplicateKey,
+                        });
+                    }
+                    if let Some(previous) = keys.last() {
+                        if (previous.len(), *previous) > (key.len(), key) {
+                            unsorted = true;
+                        }
+                    }
+                    keys.push(key);
+                    end = self.item(end, depth + 1)?;
+                    read += 1;
+                }
+                if arg.is_none() {
+                    end += 1;
+                }
+                if unsorted {
+                    self.report(pos, NonCanonicalKind::UnsortedMapKeys);
+                }
+            }
+            // tag
+            6 => end = self.item(end, depth + 1)?,
+            _ => (),
+        }
+        Some(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(hex: &str) -> Vec<(u64, NonCanonicalKind)> {
+        let (violations, duplicates, end) = find_non_canonical(&hex::decode(hex).unwrap());
+        assert_eq!(end, Some(hex.len() / 2));
+        violations
+            .into_iter()
+            .chain(duplicates)
+            .map(|violation| (violation.offset, violation.kind))
+            .collect()
+    }
+
+    #[test]
+    fn non_canonical() {
+        // [1, h'a0', {1: "a", 2: true}]
+        assert_eq!(kinds("830141a0a201616102f5"), vec![]);
+        assert_eq!(kinds("1817"), vec![(0, NonCanonicalKind::NonMinimalLength)]);
+        assert_eq!(
+            kinds("1a0000ffff"),
+            vec![(0, NonCanonicalKind::NonMinimalLength)]
+        );
+        assert_eq!(
+            kinds("9f015f4100ffff"),
+            vec![
+                (0, NonCanonicalKind::IndefiniteLength),
+                (2, NonCanonicalKind::IndefiniteLength)
+            ]
+        );
+        // {"aa": 0, "b": 0} - shorter keys sort first
+        assert_eq!(
+            kinds("a262616100616200"),
+            vec![(0, NonCanonicalKind::UnsortedMapKeys)]
+        );
+        assert_eq!(
+            kinds("a2010001f5"),
+            vec![(3, NonCanonicalKind::DuplicateKey)]
+        );
+        // floats aren't length-checked
+        assert_eq!(kinds("fb3ff0000000000000"), vec![]);
+    }
+
+    fn strict_kinds(
+        input: &str,
+        preserved: &str,
+        canonical: &str,
+        duplicates_allowed: &[u64],
+    ) -> Vec<(u64, NonCanonicalKind)> {
+        strict_violations(
+            &hex::decode(input).unwrap(),
+            &hex::decode(preserved).unwrap(),
+            &hex::decode(canonical).unwrap(),
+            duplicates_allowed,
+        )
+        .into_iter()
+        .map(|violation| (violation.offset, violation.kind))
+        .collect()
+    }
+
+    #[test]
+    fn strict() {
+        // [{2: 0, 1: h'00'}, 5] decoded keeping its encoding
+        assert_eq!(
+            strict_kinds(
+                "9fa202000141001805ff",
+                "9fa202000141001805ff",
+                "82a2014100020005",
+                &[]
+            ),
+            vec![
+                (0, NonCanonicalKind::IndefiniteLength),
+                (1, NonCanonicalKind::UnsortedMapKeys),
+                (7, NonCanonicalKind::NonMinimalLength)
+            ]
+        );
+        // chunked in canonical form too e.g. bounded bytes
+        assert_eq!(
+            strict_kinds("5f4100ff", "5f4100ff", "5f4100ff", &[]),
+            vec![]
+        );
+        // {1: 0, 1: 1} merged into {1: 1}
+        assert_eq!(
+            strict_kinds("a201000101", "a10101", "a10101", &[]),
+            vec![
+                (0, NonCanonicalKind::NotRoundTrippable),
+                (3, NonCanonicalKind::DuplicateKey)
+            ]
+        );
+        // kept by a type allowing duplicates
+        assert_eq!(
+            strict_kinds("a201000101", "a201000101", "a201000101", &[0]),
+            vec![]
+        );
+        assert_eq!(
+            strict_kinds("a201000101", "a201000101", "a201000101", &[]),
+            vec![(3, NonCanonicalKind::DuplicateKey)]
+        );
+    }
+}
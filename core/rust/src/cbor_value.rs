@@ -0,0 +1,735 @@
+//! Any CBOR item as a tree, for CDDL that CML has no types for (node queries, fields of
+//! future eras, etc) without having to use cbor_event by hand.
+//!
+//! Like the generated types, `CborValue` remembers how it was encoded so that
+//! `CborValue::from_cbor_bytes(bytes)?.to_cbor_bytes() == bytes`. Equality and hashing
+//! ignore these encoding details (except for floats, which are compared by their bits).
+//!
+//! Any CML type can be converted to and from a `CborValue` via its CBOR bytes using
+//! `CborValue::from_cbor_type()` / `CborValue::to_cbor_type()`, which is lossless as the
+//! encoding details carry over in both directions.
+
+use crate::error::{DeserializeError, DeserializeFailure};
+use crate::serialization::{fit_sz, Deserialize, LenEncoding, Serialize, StringEncoding};
+use crate::Int;
+
+use cbor_event::{de::Deserializer, se::Serializer, Sz};
+use derivative::Derivative;
+use std::io::{BufRead, Seek, Write};
+
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Eq, PartialEq, Hash)]
+pub enum CborValue {
+    Int(Int),
+    Bytes {
+        bytes: Vec<u8>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        bytes_encoding: StringEncoding,
+    },
+    Text {
+        text: String,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        text_encoding: StringEncoding,
+    },
+    Array {
+        elements: Vec<CborValue>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        elements_encoding: LenEncoding,
+    },
+    /// Entries are kept in their original order and may contain duplicate keys
+    Map {
+        entries: Vec<(CborValue, CborValue)>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        entries_encoding: LenEncoding,
+    },
+    Tag {
+        tag: u64,
+        value: Box<CborValue>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        tag_encoding: Option<Sz>,
+    },
+    Bool(bool),
+    Null,
+    Undefined,
+    /// Simple values other than false/true/null/undefined
+    Simple(u8),
+    Float {
+        #[derivative(
+            PartialEq(compare_with = "float_bits_eq"),
+            Hash(hash_with = "float_bits_hash")
+        )]
+        value: f64,
+        /// Sz::Two/Four/Eight for half/single/double precision
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        encoding: Option<Sz>,
+    },
+}
+
+fn float_bits_eq(lhs: &f64, rhs: &f64) -> bool {
+    lhs.to_bits() == rhs.to_bits()
+}
+
+fn float_bits_hash<H: std::hash::Hasher>(value: &f64, state: &mut H) {
+    std::hash::Hash::hash(&value.to_bits(), state)
+}
+
+/// One step into a CborValue, see `CborValue::at_path()`
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CborPathSegment {
+    /// Element of an array
+    Index(usize),
+    /// Value of the first map entry with this key (ignoring encoding details)
+    Key(CborValue),
+}
+
+impl CborPathSegment {
+    pub fn key(key: impl Into<CborValue>) -> Self {
+        Self::Key(key.into())
+    }
+}
+
+impl From<usize> for CborPathSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl CborValue {
+    pub fn new_int(int: Int) -> Self {
+        Self::Int(int)
+    }
+
+    pub fn new_bytes(bytes: Vec<u8>) -> Self {
+        Self::Bytes {
+            bytes,
+            bytes_encoding: StringEncoding::default(),
+        }
+    }
+
+    pub fn new_text(text: String) -> Self {
+        Self::Text {
+            text,
+            text_encoding: StringEncoding::default(),
+        }
+    }
+
+    pub fn new_array(elements: Vec<CborValue>) -> Self {
+        Self::Array {
+            elements,
+            elements_encoding: LenEncoding::default(),
+        }
+    }
+
+    pub fn new_map(entries: Vec<(CborValue, CborValue)>) -> Self {
+        Self::Map {
+            entries,
+            entries_encoding: LenEncoding::default(),
+        }
+    }
+
+    pub fn new_tag(tag: u64, value: CborValue) -> Self {
+        Self::Tag {
+            tag,
+            value: Box::new(value),
+            tag_encoding: None,
+        }
+    }
+
+    pub fn new_float(value: f64) -> Self {
+        Self::Float {
+            value,
+            encoding: None,
+        }
+    }
+
+    /// Converts any CML type via its CBOR bytes so its encoding details are kept
+    pub fn from_cbor_type<T: Serialize>(value: &T) -> Self {
+        Self::from_cbor_bytes(&value.to_cbor_bytes())
+            .expect("serialization always produces valid CBOR")
+    }
+
+    /// Converts to any CML type via its CBOR bytes so encoding details are kept.
+    /// Fails if the CBOR doesn't match what the type expects.
+    pub fn to_cbor_type<T: Deserialize>(&self) -> Result<T, DeserializeError> {
+        T::from_cbor_bytes(&self.to_cbor_bytes())
+    }
+
+    pub fn as_int(&self) -> Option<&Int> {
+        match self {
+            Self::Int(int) => Some(int),
+            _ => None,
+        }
+    }
+
+    pub fn as_uint(&self) -> Option<u64> {
+        match self {
+            Self::Int(Int::Uint { value, .. }) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes { bytes, .. } => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text { text, .. } => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<CborValue>> {
+        match self {
+            Self::Array { elements, .. } => Some(elements),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&Vec<(CborValue, CborValue)>> {
+        match self {
+            Self::Map { entries, .. } => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// The tag number if this is a tagged item
+    pub fn tag(&self) -> Option<u64> {
+        match self {
+            Self::Tag { tag, .. } => Some(*tag),
+            _ => None,
+        }
+    }
+
+    /// The item inside any (possibly nested) tags
+    pub fn untagged(&self) -> &CborValue {
+        match self {
+            Self::Tag { value, .. } => value.untagged(),
+            _ => self,
+        }
+    }
+
+    fn untagged_mut(&mut self) -> &mut CborValue {
+        match self {
+            Self::Tag { value, .. } => value.untagged_mut(),
+            _ => self,
+        }
+    }
+
+    /// Number of elements of an array or entries of a map
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Self::Array { elements, .. } => Some(elements.len()),
+            Self::Map { entries, .. } => Some(entries.len()),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Array element or map value for this step. Tags are stepped through first
+    /// so e.g. sets (tag 258) can be indexed directly.
+    pub fn get(&self, segment: &CborPathSegment) -> Option<&CborValue> {
+        match (self.untagged(), segment) {
+            (Self::Array { elements, .. }, CborPathSegment::Index(i)) => elements.get(*i),
+            (Self::Map { entries, .. }, CborPathSegment::Key(key)) => entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, segment: &CborPathSegment) -> Option<&mut CborValue> {
+        match (self.untagged_mut(), segment) {
+            (Self::Array { elements, .. }, CborPathSegment::Index(i)) => elements.get_mut(*i),
+            (Self::Map { entries, .. }, CborPathSegment::Key(key)) => entries
+                .iter_mut()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Follows each step in turn e.g. for a transaction `[Index(0), CborPathSegment::key(2u64)]`
+    /// is the fee. None if any step doesn't exist.
+    pub fn at_path(&self, path: &[CborPathSegment]) -> Option<&CborValue> {
+        path.iter()
+            .try_fold(self, |value, segment| value.get(segment))
+    }
+
+    pub fn at_path_mut(&mut self, path: &[CborPathSegment]) -> Option<&mut CborValue> {
+        path.iter()
+            .try_fold(self, |value, segment| value.get_mut(segment))
+    }
+}
+
+impl From<Int> for CborValue {
+    fn from(int: Int) -> Self {
+        Self::new_int(int)
+    }
+}
+
+impl From<u64> for CborValue {
+    fn from(x: u64) -> Self {
+        Self::new_int(x.into())
+    }
+}
+
+impl From<i64> for CborValue {
+    fn from(x: i64) -> Self {
+        Self::new_int(x.into())
+    }
+}
+
+impl From<bool> for CborValue {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<Vec<u8>> for CborValue {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new_bytes(bytes)
+    }
+}
+
+impl From<String> for CborValue {
+    fn from(text: String) -> Self {
+        Self::new_text(text)
+    }
+}
+
+impl From<&str> for CborValue {
+    fn from(text: &str) -> Self {
+        Self::new_text(text.to_owned())
+    }
+}
+
+impl From<Vec<CborValue>> for CborValue {
+    fn from(elements: Vec<CborValue>) -> Self {
+        Self::new_array(elements)
+    }
+}
+
+fn half_to_f64(half: u16) -> f64 {
+    let sign = if half & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = (half >> 10) & 0x1f;
+    let mant = (half & 0x3ff) as u64;
+    match exp {
+        0 => sign * (mant as f64) * 2f64.powi(-24),
+        0x1f if mant == 0 => sign * f64::INFINITY,
+        // keep the payload in the top bits of the f64's so it survives the round trip
+        0x1f => f64::from_bits(((half as u64 & 0x8000) << 48) | (0x7ff << 52) | (mant << 42)),
+        _ => sign * (1.0 + (mant as f64) / 1024.0) * 2f64.powi(exp as i32 - 15),
+    }
+}
+
+/// The half precision float with exactly this value, if there is one
+fn f64_to_half(value: f64) -> Option<u16> {
+    let bits = value.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    let half = if value.is_nan() {
+        if bits & ((1 << 42) - 1) != 0 {
+            return None;
+        }
+        sign | 0x7c00 | ((bits >> 42) & 0x3ff) as u16
+    } else if value.is_infinite() {
+        sign | 0x7c00
+    } else if value == 0.0 {
+        sign
+    } else {
+        let exp = ((bits >> 52) & 0x7ff) as i32 - 1023;
+        let abs = value.abs();
+        if exp > 15 {
+            return None;
+        } else if exp >= -14 {
+            let mant = (abs / 2f64.powi(exp) - 1.0) * 1024.0;
+            if mant.fract() != 0.0 {
+                return None;
+            }
+            sign | (((exp + 15) as u16) << 10) | mant as u16
+        } else {
+            let mant = abs * 2f64.powi(24);
+            if mant.fract() != 0.0 || mant >= 1024.0 {
+                return None;
+            }
+            sign | mant as u16
+        }
+    };
+    Some(half).filter(|half| half_to_f64(*half).to_bits() == bits)
+}
+
+fn f64_to_single(value: f64) -> Option<f32> {
+    Some(value as f32).filter(|single| (*single as f64).to_bits() == value.to_bits())
+}
+
+impl Serialize for CborValue {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        match self {
+            Self::Int(int) => int.serialize(serializer, force_canonical),
+            Self::Bytes {
+                bytes,
+                bytes_encoding,
+            } => serializer.write_bytes_sz(
+                bytes,
+                bytes_encoding.to_str_len_sz(bytes.len() as u64, force_canonical),
+            ),
+            Self::Text {
+                text,
+                text_encoding,
+            } => serializer.write_text_sz(
+                text,
+                text_encoding.to_str_len_sz(text.len() as u64, force_canonical),
+            ),
+            Self::Array {
+                elements,
+                elements_encoding,
+            } => {
+                serializer.write_array_sz(
+                    elements_encoding.to_len_sz(elements.len() as u64, force_canonical),
+                )?;
+                for element in elements {
+                    element.serialize(serializer, force_canonical)?;
+                }
+                elements_encoding.end(serializer, force_canonical)
+            }
+            Self::Map {
+                entries,
+                entries_encoding,
+            } => {
+                serializer.write_map_sz(
+                    entries_encoding.to_len_sz(entries.len() as u64, force_canonical),
+                )?;
+                let mut key_order = entries
+                    .iter()
+                    .map(|(k, v)| {
+                        let mut buf = cbor_event::se::Serializer::new_vec();
+                        k.serialize(&mut buf, force_canonical)?;
+                        Ok((buf.finalize(), k, v))
+                    })
+                    .collect::<Result<Vec<(Vec<u8>, &_, &_)>, cbor_event::Error>>()?;
+                if force_canonical {
+                    key_order.sort_by(|(lhs_bytes, _, _), (rhs_bytes, _, _)| {
+                        match lhs_bytes.len().cmp(&rhs_bytes.len()) {
+                            std::cmp::Ordering::Equal => lhs_bytes.cmp(rhs_bytes),
+                            diff_ord => diff_ord,
+                        }
+                    });
+                }
+                for (key_bytes, _key, value) in key_order {
+                    serializer.write_raw_bytes(&key_bytes)?;
+                    value.serialize(serializer, force_canonical)?;
+                }
+                entries_encoding.end(serializer, force_canonical)
+            }
+            Self::Tag {
+                tag,
+                value,
+                tag_encoding,
+            } => {
+                serializer.write_tag_sz(*tag, fit_sz(*tag, *tag_encoding, force_canonical))?;
+                value.serialize(serializer, force_canonical)
+            }
+            Self::Bool(b) => serializer.write_special(cbor_event::Special::Bool(*b)),
+            Self::Null => serializer.write_special(cbor_event::Special::Null),
+            Self::Undefined => serializer.write_special(cbor_event::Special::Undefined),
+            Self::Simple(simple) => {
+                serializer.write_special(cbor_event::Special::Unassigned(*simple))
+            }
+            Self::Float { value, encoding } => {
+                // cbor_event only writes doubles so we write the bytes ourselves
+                let half = f64_to_half(*value);
+                let single = f64_to_single(*value);
+                let sz = match (encoding, force_canonical) {
+                    (_, true) | (None, _) => match (half, single) {
+                        (Some(_), _) => Sz::Two,
+                        (None, Some(_)) => Sz::Four,
+                        (None, None) => Sz::Eight,
+                    },
+                    (Some(Sz::Two), false) if half.is_some() => Sz::Two,
+                    (Some(Sz::Two | Sz::Four), false) if single.is_some() => Sz::Four,
+                    _ => Sz::Eight,
+                };
+                match sz {
+                    Sz::Two => serializer
+                        .write_raw_bytes(&[0xf9])?
+                        .write_raw_bytes(&half.expect("checked above").to_be_bytes()),
+                    Sz::Four => serializer
+                        .write_raw_bytes(&[0xfa])?
+                        .write_raw_bytes(&single.expect("checked above").to_bits().to_be_bytes()),
+                    _ => serializer
+                        .write_raw_bytes(&[0xfb])?
+                        .write_raw_bytes(&value.to_bits().to_be_bytes()),
+                }
+            }
+        }
+    }
+}
+
+impl Deserialize for CborValue {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        (|| -> Result<_, DeserializeError> {
+            match raw.cbor_type()? {
+                cbor_event::Type::UnsignedInteger | cbor_event::Type::NegativeInteger => {
+                    Int::deserialize(raw).map(Self::Int)
+                }
+                cbor_event::Type::Bytes => {
+                    let (bytes, enc) = raw.bytes_sz()?;
+                    Ok(Self::Bytes {
+                        bytes,
+                        bytes_encoding: enc.into(),
+                    })
+                }
+                cbor_event::Type::Text => {
+                    let (text, enc) = raw.text_sz()?;
+                    Ok(Self::Text {
+                        text,
+                        text_encoding: enc.into(),
+                    })
+                }
+                cbor_event::Type::Array => {
+                    let mut elements = Vec::new();
+                    let len = raw.array_sz()?;
+                    while match len {
+                        cbor_event::LenSz::Len(n, _) => (elements.len() as u64) < n,
+                        cbor_event::LenSz::Indefinite => true,
+                    } {
+                        // unlike most types elements can be simple values so we can't just
+                        // check the type before reading a Break
+                        if raw.cbor_type()? == cbor_event::Type::Special && raw.special_break()? {
+                            break;
+                        }
+                        elements.push(CborValue::deserialize(raw)?);
+                    }
+                    Ok(Self::Array {
+                        elements,
+                        elements_encoding: len.into(),
+                    })
+                }
+                cbor_event::Type::Map => {
+                    let mut entries = Vec::new();
+                    let len = raw.map_sz()?;
+                    while match len {
+                        cbor_event::LenSz::Len(n, _) => (entries.len() as u64) < n,
+                        cbor_event::LenSz::Indefinite => true,
+                    } {
+                        if raw.cbor_type()? == cbor_event::Type::Special && raw.special_break()? {
+                            break;
+                        }
+                        let key = CborValue::deserialize(raw)?;
+                        let value = CborValue::deserialize(raw)?;
+                        entries.push((key, value));
+                    }
+                    Ok(Self::Map {
+                        entries,
+                        entries_encoding: len.into(),
+                    })
+                }
+                cbor_event::Type::Tag => {
+                    let (tag, tag_encoding) = raw.tag_sz()?;
+                    let value = CborValue::deserialize(raw)?;
+                    Ok(Self::Tag {
+                        tag,
+                        value: Box::new(value),
+                        tag_encoding: Some(tag_encoding),
+                    })
+                }
+                cbor_event::Type::Special => {
+                    // cbor_event reads half precision floats incorrectly and doesn't
+                    // tell us the size so we read floats ourselves
+                    let initial = raw
+                        .as_mut_ref()
+                        .fill_buf()
+                        .map_err(cbor_event::Error::IoError)?
+                        .first()
+                        .copied();
+                    let mut read_float = |buf: &mut [u8]| {
+                        raw.as_mut_ref()
+                            .read_exact(buf)
+                            .map_err(cbor_event::Error::IoError)
+                    };
+                    match initial {
+                        Some(0xf9) => {
+                            let mut buf = [0u8; 3];
+                            read_float(&mut buf)?;
+                            Ok(Self::Float {
+                                value: half_to_f64(u16::from_be_bytes([buf[1], buf[2]])),
+                                encoding: Some(Sz::Two),
+                            })
+                        }
+                        Some(0xfa) => {
+                            let mut buf = [0u8; 5];
+                            read_float(&mut buf)?;
+                            let bits = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]);
+                            Ok(Self::Float {
+                                value: f32::from_bits(bits) as f64,
+                                encoding: Some(Sz::Four),
+                            })
+                        }
+                        Some(0xfb) => {
+                            let mut buf = [0u8; 9];
+                            read_float(&mut buf)?;
+                            let mut bits = [0u8; 8];
+                            bits.copy_from_slice(&buf[1..]);
+                            Ok(Self::Float {
+                                value: f64::from_bits(u64::from_be_bytes(bits)),
+                                encoding: Some(Sz::Eight),
+                            })
+                        }
+                        _ => match raw.special()? {
+                            cbor_event::Special::Bool(b) => Ok(Self::Bool(b)),
+                            cbor_event::Special::Null => Ok(Self::Null),
+                            cbor_event::Special::Undefined => Ok(Self::Undefined),
+                            cbor_event::Special::Unassigned(simple) => Ok(Self::Simple(simple)),
+                            cbor_event::Special::Float(_) | cbor_event::Special::Break => {
+                                Err(DeserializeFailure::NoVariantMatched.into())
+                            }
+                        },
+                    }
+                }
+            }
+        })()
+        .map_err(|e| e.annotate("CborValue"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(hex: &str) -> CborValue {
+        let bytes = hex::decode(hex).unwrap();
+        let value = CborValue::from_cbor_bytes(&bytes).unwrap();
+        assert_eq!(value.to_cbor_bytes(), bytes, "{hex}");
+        value
+    }
+
+    #[test]
+    fn preserves_encodings() {
+        // non-minimal uint, nint and tag
+        round_trip("190005");
+        round_trip("1800");
+        round_trip("390000");
+        round_trip("d9010280");
+        // indefinite array containing simple values and a chunked string
+        round_trip("9ff4f5f6f7f05f42010241ffff7f6161ffff");
+        // indefinite map with unsorted and duplicate keys
+        round_trip("bf0201010201037f6161ffa0ff");
+        // floats of each size
+        round_trip("f93c00");
+        round_trip("f97e00");
+        round_trip("fa47c35000");
+        round_trip("fb3ff199999999999a");
+        // non-canonical float sizes
+        round_trip("fa3f800000");
+        round_trip("fb3ff0000000000000");
+    }
+
+    #[test]
+    fn canonical() {
+        let value = round_trip("bf02f9030001fb3ff8000000000000ff");
+        assert_eq!(
+            hex::encode(value.to_canonical_cbor_bytes()),
+            "a201f93e0002f90300"
+        );
+    }
+
+    #[test]
+    fn floats() {
+        for (hex, value) in [
+            ("f90000", 0.0),
+            ("f98000", -0.0),
+            ("f90001", 5.960464477539063e-8),
+            ("f903ff", 0.00006097555160522461),
+            ("f97bff", 65504.0),
+            ("f9c400", -4.0),
+            ("f97c00", f64::INFINITY),
+            ("f9fc00", f64::NEG_INFINITY),
+        ] {
+            let parsed = round_trip(hex);
+            assert_eq!(
+                parsed.as_float().unwrap().to_bits(),
+                value.to_bits(),
+                "{hex}"
+            );
+            assert_eq!(
+                hex::encode(CborValue::new_float(value).to_cbor_bytes()),
+                hex,
+                "{hex}"
+            );
+        }
+        assert!(round_trip("f97e00").as_float().unwrap().is_nan());
+        assert_eq!(
+            hex::encode(CborValue::new_float(100000.0).to_cbor_bytes()),
+            "fa47c35000"
+        );
+        assert_eq!(
+            hex::encode(CborValue::new_float(1.1).to_cbor_bytes()),
+            "fb3ff199999999999a"
+        );
+    }
+
+    #[test]
+    fn paths() {
+        // [{0: "a", "k": 258([1, 2])}]
+        let mut value = round_trip("81a2006161616bd90102820102");
+        let set = [CborPathSegment::Index(0), CborPathSegment::key("k")];
+        assert_eq!(value.at_path(&set).and_then(CborValue::tag), Some(258));
+        let second = [
+            CborPathSegment::Index(0),
+            CborPathSegment::key("k"),
+            CborPathSegment::Index(1),
+        ];
+        assert_eq!(value.at_path(&second).and_then(CborValue::as_uint), Some(2));
+        let zero = [CborPathSegment::Index(0), CborPathSegment::key(0u64)];
+        assert_eq!(value.at_path(&zero).and_then(CborValue::as_text), Some("a"));
+        assert!(value.at_path(&[CborPathSegment::Index(1)]).is_none());
+        *value.at_path_mut(&zero).unwrap() = CborValue::from(-5i64);
+        assert_eq!(
+            hex::encode(value.to_cbor_bytes()),
+            "81a20024616bd90102820102"
+        );
+    }
+
+    #[test]
+    fn cbor_type_conversion() {
+        let int = Int::Uint {
+            value: 5,
+            encoding: Some(Sz::Eight),
+        };
+        let value = CborValue::from_cbor_type(&int);
+        assert_eq!(value.as_uint(), Some(5));
+        assert_eq!(value.to_cbor_bytes(), int.to_cbor_bytes());
+        let back: Int = value.to_cbor_type().unwrap();
+        assert_eq!(back.to_cbor_bytes(), int.to_cbor_bytes());
+        assert!(CborValue::from("text").to_cbor_type::<Int>().is_err());
+    }
+}
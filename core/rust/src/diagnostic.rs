@@ -0,0 +1,1061 @@
+//! CBOR diagnostic notation (RFC 8949 section 8 and RFC 8610 appendix G).
+//!
+//! Printing keeps the encoding details needed to reproduce the exact bytes: indefinite lengths
+//! are shown with `_` and non-minimal lengths/arguments and non-preferred floats with an
+//! encoding indicator (`_0` = 1 byte, `_1` = 2 bytes, `_2` = 4 bytes, `_3` = 8 bytes).
+//! Parsing accepts the same notation (plus `/ comments /` and `<< embedded CBOR >>`) so
+//! `from_diagnostic(&to_diagnostic(bytes)?)?` gives back `bytes`.
+
+use crate::serialization::Serialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DiagnosticError {
+    #[error("Invalid CBOR at byte {offset}: {reason}")]
+    InvalidCbor { offset: usize, reason: &'static str },
+    #[error("Invalid diagnostic notation at byte {position}: {reason}")]
+    InvalidNotation { position: usize, reason: String },
+}
+
+/// Field names used to annotate diagnostic notation with `/ name /` comments
+#[derive(Clone, Debug)]
+pub enum FieldNames {
+    None,
+    /// array-encoded struct, in order. Fields with an empty name (e.g. the uint selecting a
+    /// type choice) aren't annotated.
+    Array(Vec<(&'static str, FieldNames)>),
+    /// map-encoded struct, by uint key
+    Map(Vec<(u64, &'static str, FieldNames)>),
+    /// names for every element of an array or value of a map
+    Elements(Box<FieldNames>),
+    /// names for every key and every value of a map whose keys aren't fields, each annotated
+    /// with the given name (if not empty) e.g. `{ + policy_id => { + asset_name => int } }`
+    Entries(
+        Box<(&'static str, FieldNames)>,
+        Box<(&'static str, FieldNames)>,
+    ),
+    /// the first of these matching the item (arrays or maps) is used e.g. for type choices
+    Choice(Vec<FieldNames>),
+    /// type choice between arrays selected by the uint they start with e.g. certificates
+    Variants(Vec<(u64, FieldNames)>),
+    /// names for the item inside a CBOR tag, only used for that tag
+    Tag(u64, Box<FieldNames>),
+    /// fields of a group embedded in an array-encoded struct, see `FieldNames::array()`
+    Group(Vec<(&'static str, FieldNames)>),
+    /// names created when needed, for recursive types e.g. native scripts
+    Lazy(fn() -> FieldNames),
+}
+
+impl FieldNames {
+    pub fn elements(names: FieldNames) -> Self {
+        Self::Elements(Box::new(names))
+    }
+
+    pub fn entries(key: (&'static str, FieldNames), value: (&'static str, FieldNames)) -> Self {
+        Self::Entries(Box::new(key), Box::new(value))
+    }
+
+    pub fn tag(tag: u64, names: FieldNames) -> Self {
+        Self::Tag(tag, Box::new(names))
+    }
+
+    /// Array-encoded struct with the fields of any `Group` spliced in
+    pub fn array(fields: Vec<(&'static str, FieldNames)>) -> Self {
+        let mut spliced = Vec::with_capacity(fields.len());
+        for (name, names) in fields {
+            match names {
+                Self::Group(group) => spliced.extend(group),
+                names => spliced.push((name, names)),
+            }
+        }
+        Self::Array(spliced)
+    }
+
+    /// These array field names as a group to embed in another array, see `FieldNames::array()`
+    pub fn embedded(self) -> Self {
+        match self {
+            Self::Array(fields) => Self::Group(fields),
+            names => names,
+        }
+    }
+
+    fn for_array(&self) -> &Self {
+        match self {
+            Self::Array(_) | Self::Elements(_) | Self::Variants(_) => self,
+            Self::Choice(choices) => choices
+                .iter()
+                .map(Self::for_array)
+                .find(|names| !matches!(names, Self::None))
+                .unwrap_or(&Self::None),
+            _ => &Self::None,
+        }
+    }
+
+    fn for_map(&self) -> &Self {
+        match self {
+            Self::Map(_) | Self::Elements(_) | Self::Entries(..) => self,
+            Self::Choice(choices) => choices
+                .iter()
+                .map(Self::for_map)
+                .find(|names| !matches!(names, Self::None))
+                .unwrap_or(&Self::None),
+            _ => &Self::None,
+        }
+    }
+
+    /// Names for the item inside `tag`. Tags other than those named (e.g. 258 for sets) wrap
+    /// the structure the names refer to so they are passed on.
+    fn for_tag(&self, tag: u64) -> &Self {
+        match self {
+            Self::Tag(named, names) if *named == tag => names,
+            Self::Choice(choices) => choices
+                .iter()
+                .find_map(|names| match names {
+                    Self::Tag(named, names) if *named == tag => Some(&**names),
+                    _ => None,
+                })
+                .unwrap_or(self),
+            _ => self,
+        }
+    }
+}
+
+/// Implements `CBORFieldNames` for a generated struct using the names of its fields, which
+/// must all be listed (checked at compile time) so the names follow the struct definition.
+/// Array-encoded structs list their fields in order, after the uint selecting them if they
+/// are part of a type choice, map-encoded structs by key. Either can be wrapped in a tag. A
+/// field's names default to `FieldNames::None`.
+/// ```ignore
+/// impl_cbor_field_names!(StakeDelegation [2, stake_credential: Credential::cbor_field_names(), pool]);
+/// impl_cbor_field_names!(Rational tag 30 [numerator, denominator]);
+/// impl_cbor_field_names!(CostModels { 0 => plutus_v1, 1 => plutus_v2, 2 => plutus_v3 });
+/// ```
+#[macro_export]
+macro_rules! impl_cbor_field_names {
+    (@all_fields $name:ident $($field:ident)*) => {
+        #[allow(dead_code)]
+        fn all_fields_named(value: &$name) {
+            let $name {
+                $($field: _,)*
+                encodings: _,
+            } = value;
+        }
+    };
+    // the uint selecting an array-encoded struct within a type choice isn't named
+    (@discriminant $tag:literal) => {
+        ("", $crate::diagnostic::FieldNames::None)
+    };
+    (@names) => {
+        $crate::diagnostic::FieldNames::None
+    };
+    (@names $names:expr) => {
+        $names
+    };
+    (@array $name:ident [$($tag:literal,)? $($field:ident $(: $names:expr)?),* $(,)?]) => {{
+        $crate::impl_cbor_field_names!(@all_fields $name $($field)*);
+        $crate::diagnostic::FieldNames::array(vec![
+            $($crate::impl_cbor_field_names!(@discriminant $tag),)?
+            $((stringify!($field), $crate::impl_cbor_field_names!(@names $($names)?)),)*
+        ])
+    }};
+    (@map $name:ident {$($key:literal => $field:ident $(: $names:expr)?),* $(,)?}) => {{
+        $crate::impl_cbor_field_names!(@all_fields $name $($field)*);
+        $crate::diagnostic::FieldNames::Map(vec![
+            $(($key, stringify!($field), $crate::impl_cbor_field_names!(@names $($names)?)),)*
+        ])
+    }};
+    (@struct $name:ident [$($fields:tt)*]) => {
+        $crate::impl_cbor_field_names!(@array $name [$($fields)*])
+    };
+    (@struct $name:ident {$($fields:tt)*}) => {
+        $crate::impl_cbor_field_names!(@map $name {$($fields)*})
+    };
+    ($name:ident tag $tag:literal $fields:tt) => {
+        impl $crate::diagnostic::CBORFieldNames for $name {
+            fn cbor_field_names() -> $crate::diagnostic::FieldNames {
+                $crate::diagnostic::FieldNames::tag(
+                    $tag,
+                    $crate::impl_cbor_field_names!(@struct $name $fields),
+                )
+            }
+        }
+    };
+    ($name:ident $fields:tt) => {
+        impl $crate::diagnostic::CBORFieldNames for $name {
+            fn cbor_field_names() -> $crate::diagnostic::FieldNames {
+                $crate::impl_cbor_field_names!(@struct $name $fields)
+            }
+        }
+    };
+}
+
+/// Types that can name the fields of their CBOR structure (from the CDDL) in diagnostic notation
+pub trait CBORFieldNames {
+    fn cbor_field_names() -> FieldNames;
+
+    /// Diagnostic notation of this type's CBOR annotated with CDDL field names
+    fn to_annotated_cbor_diagnostic(&self) -> String
+    where
+        Self: Serialize,
+    {
+        to_diagnostic_annotated(&self.to_cbor_bytes(), &Self::cbor_field_names())
+            .expect("serialization always produces valid CBOR")
+    }
+}
+
+/// Nesting deeper than this is rejected instead of risking the stack
+const MAX_DEPTH: usize = 256;
+
+/// Diagnostic notation for the CBOR items in `bytes` (a comma separated sequence if more than one)
+pub fn to_diagnostic(bytes: &[u8]) -> Result<String, DiagnosticError> {
+    to_diagnostic_annotated(bytes, &FieldNames::None)
+}
+
+/// Like to_diagnostic() but with `/ name /` comments from `names` before named fields
+pub fn to_diagnostic_annotated(
+    bytes: &[u8],
+    names: &FieldNames,
+) -> Result<String, DiagnosticError> {
+    let mut printer = Printer {
+        bytes,
+        pos: 0,
+        out: String::new(),
+    };
+    while printer.pos < bytes.len() {
+        if printer.pos > 0 {
+            printer.out.push_str(",\n");
+        }
+        printer.item(names, 0, 0)?;
+    }
+    Ok(printer.out)
+}
+
+struct Printer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    out: String,
+}
+
+/// Additional info value of the shortest encoding of `arg`
+fn minimal_info(arg: u64) -> u8 {
+    match arg {
+        0..=23 => arg as u8,
+        24..=0xff => 24,
+        0x100..=0xffff => 25,
+        0x1_0000..=0xffff_ffff => 26,
+        _ => 27,
+    }
+}
+
+/// Encoding indicator if `info` is not the shortest encoding of `arg`
+fn indicator(info: u8, arg: u64) -> String {
+    if info >= 24 && info != minimal_info(arg) {
+        format!("_{}", info - 24)
+    } else {
+        String::new()
+    }
+}
+
+impl Printer<'_> {
+    fn error<T>(&self, offset: usize, reason: &'static str) -> Result<T, DiagnosticError> {
+        Err(DiagnosticError::InvalidCbor { offset, reason })
+    }
+
+    fn take(&mut self, len: u64) -> Result<&[u8], DiagnosticError> {
+        if len > (self.bytes.len() - self.pos) as u64 {
+            return self.error(self.bytes.len(), "unexpected end of input");
+        }
+        let taken = &self.bytes[self.pos..self.pos + len as usize];
+        self.pos += len as usize;
+        Ok(taken)
+    }
+
+    /// Reads an item's major type, additional info and argument (None for indefinite lengths)
+    fn head(&mut self) -> Result<(u8, u8, Option<u64>), DiagnosticError> {
+        let start = self.pos;
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let arg = match info {
+            0..=23 => Some(u64::from(info)),
+            24..=27 => Some(
+                self.take(1 << (info - 24))?
+                    .iter()
+                    .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)),
+            ),
+            31 if (2..=5).contains(&major) || initial == 0xff => None,
+            _ => return self.error(start, "reserved additional info"),
+        };
+        Ok((major, info, arg))
+    }
+
+    /// `/ name /` before an item unless `name` is empty
+    fn comment(&mut self, name: &str) {
+        if !name.is_empty() {
+            self.out.push_str(&format!("/ {name} / "));
+        }
+    }
+
+    fn is_break(&self) -> bool {
+        self.bytes.get(self.pos) == Some(&0xff)
+    }
+
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        for _ in 0..indent {
+            self.out.push_str("  ");
+        }
+    }
+
+    fn item(
+        &mut self,
+        names: &FieldNames,
+        indent: usize,
+        depth: usize,
+    ) -> Result<(), DiagnosticError> {
+        if depth > MAX_DEPTH {
+            return self.error(self.pos, "nesting too deep");
+        }
+        if let FieldNames::Lazy(names) = names {
+            return self.item(&names(), indent, depth);
+        }
+        let start = self.pos;
+        let (major, info, arg) = self.head()?;
+        match (major, arg) {
+            (0, Some(n)) => self.out.push_str(&format!("{n}{}", indicator(info, n))),
+            (1, Some(n)) => {
+                let value = -1 - i128::from(n);
+                self.out.push_str(&format!("{value}{}", indicator(info, n)));
+            }
+            (2, Some(len)) => {
+                let bytes = hex::encode(self.take(len)?);
+                self.out
+                    .push_str(&format!("h'{bytes}'{}", indicator(info, len)));
+            }
+            (3, Some(len)) => {
+                let text = std::str::from_utf8(self.take(len)?)
+                    .map_err(|_| DiagnosticError::InvalidCbor {
+                        offset: start,
+                        reason: "invalid UTF-8 in text string",
+                    })?
+                    .to_owned();
+                let quoted = serde_json::to_string(&text).unwrap();
+                self.out
+                    .push_str(&format!("{quoted}{}", indicator(info, len)));
+            }
+            (2 | 3, None) => {
+                self.out.push_str("(_ ");
+                let mut first = true;
+                while !self.is_break() {
+                    if !first {
+                        self.out.push_str(", ");
+                    }
+                    first = false;
+                    let chunk_start = self.pos;
+                    let chunk_major = self.bytes.get(self.pos).map(|b| b >> 5);
+                    if chunk_major != Some(major) || self.bytes[self.pos] & 0x1f == 31 {
+                        return self.error(chunk_start, "invalid indefinite length string chunk");
+                    }
+                    self.item(&FieldNames::None, indent, depth + 1)?;
+                }
+                self.pos += 1;
+                self.out.push(')');
+            }
+            (4, len) => {
+                let names = match names.for_array() {
+                    FieldNames::Variants(variants) => {
+                        let selector = self.peek_uint().filter(|_| len != Some(0));
+                        variants
+                            .iter()
+                            .find(|(variant, _)| Some(*variant) == selector)
+                            .map_or(&FieldNames::None, |(_, names)| names.for_array())
+                    }
+                    names => names,
+                };
+                self.out.push('[');
+                self.collection_prefix(info, len);
+                let mut read = 0u64;
+                while len
+                    .map(|len| read < len)
+                    .unwrap_or_else(|| !self.is_break())
+                {
+                    if read > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(indent + 1);
+                    let element_names = match names {
+                        FieldNames::Array(fields) => match fields.get(read as usize) {
+                            Some((name, names)) => {
+                                self.comment(name);
+                                names
+                            }
+                            None => &FieldNames::None,
+                        },
+                        FieldNames::Elements(names) => names,
+                        _ => &FieldNames::None,
+                    };
+                    self.item(element_names, indent + 1, depth + 1)?;
+                    read += 1;
+                }
+                if len.is_none() {
+                    self.pos += 1;
+                }
+                self.collection_end(len, read, indent);
+                self.out.push(']');
+            }
+            (5, len) => {
+                let names = names.for_map();
+                self.out.push('{');
+                self.collection_prefix(info, len);
+                let mut read = 0u64;
+                while len
+                    .map(|len| read < len)
+                    .unwrap_or_else(|| !self.is_break())
+                {
+                    if read > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(indent + 1);
+                    let (key_names, value_names) = match names {
+                        FieldNames::Map(fields) => {
+                            let key = self.peek_uint();
+                            match fields.iter().find(|(k, _, _)| Some(*k) == key) {
+                                Some((_, name, names)) => {
+                                    self.comment(name);
+                                    (&FieldNames::None, names)
+                                }
+                                None => (&FieldNames::None, &FieldNames::None),
+                            }
+                        }
+                        FieldNames::Elements(names) => (&FieldNames::None, &**names),
+                        FieldNames::Entries(key, value) => {
+                            self.comment(key.0);
+                            (&key.1, &value.1)
+                        }
+                        _ => (&FieldNames::None, &FieldNames::None),
+                    };
+                    self.item(key_names, indent + 1, depth + 1)?;
+                    self.out.push_str(": ");
+                    if let FieldNames::Entries(_, value) = names {
+                        self.comment(value.0);
+                    }
+                    self.item(value_names, indent + 1, depth + 1)?;
+                    read += 1;
+                }
+                if len.is_none() {
+                    self.pos += 1;
+                }
+                self.collection_end(len, read, indent);
+                self.out.push('}');
+            }
+            (6, Some(tag)) => {
+                self.out
+                    .push_str(&format!("{tag}{}(", indicator(info, tag)));
+                self.item(names.for_tag(tag), indent, depth + 1)?;
+                self.out.push(')');
+            }
+            (7, None) => return self.error(start, "unexpected break"),
+            (7, Some(value)) => match info {
+                20 => self.out.push_str("false"),
+                21 => self.out.push_str("true"),
+                22 => self.out.push_str("null"),
+                23 => self.out.push_str("undefined"),
+                0..=19 | 24 => self.out.push_str(&format!("simple({value})")),
+                _ => {
+                    let float = match info {
+                        25 => f16_to_f64(value as u16),
+                        26 => f64::from(f32::from_bits(value as u32)),
+                        _ => f64::from_bits(value),
+                    };
+                    let width = preferred_float_width(float);
+                    let indicator = if info == 24 + width {
+                        String::new()
+                    } else {
+                        format!("_{}", info - 24)
+                    };
+                    self.out
+                        .push_str(&format!("{}{indicator}", format_float(float)));
+                }
+            },
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Indefinite length or encoding indicator after the opening bracket
+    fn collection_prefix(&mut self, info: u8, len: Option<u64>) {
+        match len {
+            None => self.out.push('_'),
+            Some(len) => self.out.push_str(&indicator(info, len)),
+        }
+    }
+
+    fn collection_end(&mut self, len: Option<u64>, read: u64, indent: usize) {
+        if read > 0 {
+            self.newline(indent);
+        } else if len.is_none() {
+            // keeps `[_ ]` distinct from `[_0]` etc
+            self.out.push(' ');
+        }
+    }
+
+    fn peek_uint(&self) -> Option<u64> {
+        let mut peek = Printer {
+            bytes: self.bytes,
+            pos: self.pos,
+            out: String::new(),
+        };
+        match peek.head() {
+            Ok((0, _, arg)) => arg,
+            _ => None,
+        }
+    }
+}
+
+fn format_float(float: f64) -> String {
+    if float.is_nan() {
+        "NaN".to_owned()
+    } else if float.is_infinite() {
+        if float > 0.0 { "Infinity" } else { "-Infinity" }.to_owned()
+    } else {
+        format!("{float:?}")
+    }
+}
+
+fn f16_to_f64(half: u16) -> f64 {
+    let sign = if half & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((half >> 10) & 0x1f);
+    let mantissa = f64::from(half & 0x3ff);
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1024.0 + mantissa) * 2f64.powi(exponent - 25),
+    }
+}
+
+/// The half precision bits of `float` if it can be represented exactly
+fn f64_to_f16(float: f64) -> Option<u16> {
+    if float.is_nan() {
+        return Some(0x7e00);
+    }
+    let sign = if float.is_sign_negative() { 0x8000 } else { 0 };
+    let magnitude = float.abs();
+    if magnitude.is_infinite() {
+        return Some(sign | 0x7c00);
+    }
+    if magnitude == 0.0 {
+        return Some(sign);
+    }
+    // try every exponent as there are few of them
+    (0u16..31).find_map(|exponent| {
+        let (base, scale) = match exponent {
+            0 => (0.0, 2f64.powi(-24)),
+            _ => (1024.0, 2f64.powi(i32::from(exponent) - 25)),
+        };
+        let mantissa = magnitude / scale - base;
+        (mantissa.fract() == 0.0 && (0.0..1024.0).contains(&mantissa))
+            .then_some(sign | (exponent << 10) | mantissa as u16)
+    })
+}
+
+/// Width (additional info - 24) of the shortest exact float encoding
+fn preferred_float_width(float: f64) -> u8 {
+    if f64_to_f16(float).is_some() {
+        1
+    } else if float.is_nan() || f64::from(float as f32) == float {
+        2
+    } else {
+        3
+    }
+}
+
+/// CBOR bytes for diagnostic notation as printed by to_diagnostic() (a comma separated sequence
+/// of items is concatenated). Lengths and arguments without an encoding indicator use the shortest
+/// encoding and floats the shortest exact precision.
+pub fn from_diagnostic(text: &str) -> Result<Vec<u8>, DiagnosticError> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+        out: Vec::new(),
+    };
+    parser.skip_whitespace()?;
+    while parser.pos < parser.text.len() {
+        if !parser.out.is_empty() {
+            parser.expect(b',')?;
+        }
+        parser.item(0)?;
+        parser.skip_whitespace()?;
+    }
+    Ok(parser.out)
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    out: Vec<u8>,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, reason: impl Into<String>) -> Result<T, DiagnosticError> {
+        Err(DiagnosticError::InvalidNotation {
+            position: self.pos,
+            reason: reason.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), DiagnosticError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(b'/') => {
+                    let start = self.pos;
+                    match self.text[start + 1..].iter().position(|c| *c == b'/') {
+                        Some(len) => self.pos = start + len + 2,
+                        None => return self.error("unterminated comment"),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> Result<bool, DiagnosticError> {
+        self.skip_whitespace()?;
+        if self.text[self.pos..].starts_with(token.as_bytes()) {
+            self.pos += token.len();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), DiagnosticError> {
+        if self.eat(std::str::from_utf8(&[c]).unwrap())? {
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", c as char))
+        }
+    }
+
+    /// An optional `_N` encoding indicator directly after an item
+    fn indicator(&mut self) -> Result<Option<u8>, DiagnosticError> {
+        match (self.peek(), self.text.get(self.pos + 1)) {
+            (Some(b'_'), Some(width @ b'0'..=b'3')) => {
+                self.pos += 2;
+                Ok(Some(width - b'0'))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn head(&mut self, major: u8, arg: u64, width: Option<u8>) -> Result<(), DiagnosticError> {
+        let info = match width {
+            None => minimal_info(arg),
+            Some(width) => 24 + width,
+        };
+        if info >= 24 && info != 27 && arg >> (8 << (info - 24)) != 0 {
+            return self.error(format!(
+                "{arg} does not fit in encoding indicator _{}",
+                info - 24
+            ));
+        }
+        self.out.push(major << 5 | info);
+        if info >= 24 {
+            let len = 1 << (info - 24);
+            self.out.extend(&arg.to_be_bytes()[8 - len..]);
+        }
+        Ok(())
+    }
+
+    fn number_token(&mut self) -> &str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let sign_after_exponent = (c == b'+' || c == b'-')
+                && self.pos > start
+                && matches!(self.text[self.pos - 1], b'e' | b'E');
+            let leading_minus = c == b'-' && self.pos == start;
+            if c.is_ascii_alphanumeric() || c == b'.' || sign_after_exponent || leading_minus {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        std::str::from_utf8(&self.text[start..self.pos]).unwrap()
+    }
+
+    fn item(&mut self, depth: usize) -> Result<(), DiagnosticError> {
+        if depth > MAX_DEPTH {
+            return self.error("nesting too deep");
+        }
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                self.collection(4, b']', depth)
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                self.collection(5, b'}', depth)
+            }
+            Some(b'(') => {
+                self.pos += 1;
+                if !self.eat("_")? {
+                    return self.error("expected '_' for indefinite length string");
+                }
+                let start = self.out.len();
+                let mut first = true;
+                let mut major = None;
+                while !self.eat(")")? {
+                    if !first {
+                        self.expect(b',')?;
+                    }
+                    first = false;
+                    let chunk_start = self.out.len();
+                    self.item(depth + 1)?;
+                    let chunk_major = self.out[chunk_start] >> 5;
+                    if !(chunk_major == 2 || chunk_major == 3)
+                        || self.out[chunk_start] & 0x1f == 31
+                        || *major.get_or_insert(chunk_major) != chunk_major
+                    {
+                        return self
+                            .error("indefinite length string chunks must be strings of one type");
+                    }
+                }
+                self.out.insert(start, major.unwrap_or(2) << 5 | 31);
+                self.out.push(0xff);
+                Ok(())
+            }
+            Some(b'h') if self.text.get(self.pos + 1) == Some(&b'\'') => {
+                self.pos += 2;
+                let end = match self.text[self.pos..].iter().position(|c| *c == b'\'') {
+                    Some(len) => self.pos + len,
+                    None => return self.error("unterminated byte string"),
+                };
+                let digits = self.text[self.pos..end]
+                    .iter()
+                    .filter(|c| !c.is_ascii_whitespace())
+                    .copied()
+                    .collect::<Vec<u8>>();
+                let bytes = match hex::decode(digits) {
+                    Ok(bytes) => bytes,
+                    Err(e) => return self.error(format!("invalid hex: {e}")),
+                };
+                self.pos = end + 1;
+                let width = self.indicator()?;
+                self.head(2, bytes.len() as u64, width)?;
+                self.out.extend(bytes);
+                Ok(())
+            }
+            Some(b'"') => {
+                let start = self.pos;
+                self.pos += 1;
+                loop {
+                    match self.peek() {
+                        Some(b'\\') => self.pos += 2,
+                        Some(b'"') => break,
+                        Some(_) => self.pos += 1,
+                        None => return self.error("unterminated text string"),
+                    }
+                }
+                self.pos += 1;
+                let text: String = match serde_json::from_slice(&self.text[start..self.pos]) {
+                    Ok(text) => text,
+                    Err(e) => return self.error(format!("invalid text string: {e}")),
+                };
+                let width = self.indicator()?;
+                self.head(3, text.len() as u64, width)?;
+                self.out.extend(text.as_bytes());
+                Ok(())
+            }
+            Some(b'<') if self.text.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                let start = self.out.len();
+                let mut first = true;
+                while !self.eat(">>")? {
+                    if !first {
+                        self.expect(b',')?;
+                    }
+                    first = false;
+                    self.item(depth + 1)?;
+                }
+                let embedded = self.out.split_off(start);
+                let width = self.indicator()?;
+                self.head(2, embedded.len() as u64, width)?;
+                self.out.extend(embedded);
+                Ok(())
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let word = self.number_token().to_owned();
+                match word.as_str() {
+                    "false" => self.out.push(0xf4),
+                    "true" => self.out.push(0xf5),
+                    "null" => self.out.push(0xf6),
+                    "undefined" => self.out.push(0xf7),
+                    "simple" => {
+                        self.expect(b'(')?;
+                        self.skip_whitespace()?;
+                        let value = self.number_token().to_owned();
+                        let value = match value.parse::<u8>() {
+                            Ok(value) => value,
+                            Err(_) => return self.error(format!("invalid simple value {value}")),
+                        };
+                        self.expect(b')')?;
+                        let width = self.indicator()?;
+                        match (value, width) {
+                            (0..=19, None) => self.out.push(0xe0 | value),
+                            (32..=255, None | Some(0)) => self.out.extend([0xf8, value]),
+                            _ => return self.error(format!("invalid simple value {value}")),
+                        }
+                    }
+                    "NaN" | "Infinity" => self.float(&word)?,
+                    _ => return self.error(format!("unknown word {word}")),
+                }
+                Ok(())
+            }
+            Some(b'-' | b'0'..=b'9') => {
+                let token = self.number_token().to_owned();
+                if token.contains(['.', 'e', 'E']) || token.ends_with("Infinity") {
+                    return self.float(&token);
+                }
+                let value = match token.parse::<i128>() {
+                    Ok(value) => value,
+                    Err(_) => return self.error(format!("invalid number {token}")),
+                };
+                let width = self.indicator()?;
+                if self.eat("(")? {
+                    if value < 0 || value > i128::from(u64::MAX) {
+                        return self.error(format!("invalid tag {value}"));
+                    }
+                    self.head(6, value as u64, width)?;
+                    self.item(depth + 1)?;
+                    return self.expect(b')');
+                }
+                if value >= 0 && value <= i128::from(u64::MAX) {
+                    self.head(0, value as u64, width)
+                } else if value < 0 && -1 - value <= i128::from(u64::MAX) {
+                    self.head(1, (-1 - value) as u64, width)
+                } else {
+                    self.error(format!("{token} out of range"))
+                }
+            }
+            Some(c) => self.error(format!("unexpected '{}'", c as char)),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn float(&mut self, token: &str) -> Result<(), DiagnosticError> {
+        let float = match token {
+            "NaN" => f64::NAN,
+            "Infinity" => f64::INFINITY,
+            "-Infinity" => f64::NEG_INFINITY,
+            _ => match token.parse::<f64>() {
+                Ok(float) => float,
+                Err(_) => return self.error(format!("invalid number {token}")),
+            },
+        };
+        let width = match self.indicator()? {
+            Some(0) => return self.error("floats can't use encoding indicator _0"),
+            Some(width) => width,
+            None => preferred_float_width(float),
+        };
+        match width {
+            1 => match f64_to_f16(float) {
+                Some(half) => {
+                    self.out.push(0xf9);
+                    self.out.extend(half.to_be_bytes());
+                }
+                None => return self.error(format!("{token} is not exact in half precision")),
+            },
+            2 => {
+                if !float.is_nan() && f64::from(float as f32) != float {
+                    return self.error(format!("{token} is not exact in single precision"));
+                }
+                self.out.push(0xfa);
+                self.out.extend((float as f32).to_bits().to_be_bytes());
+            }
+            _ => {
+                self.out.push(0xfb);
+                self.out.extend(float.to_bits().to_be_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    /// Arrays (major 4) and maps (major 5) after the opening bracket
+    fn collection(&mut self, major: u8, close: u8, depth: usize) -> Result<(), DiagnosticError> {
+        let indefinite = matches!((self.peek(), self.text.get(self.pos + 1)), (Some(b'_'), Some(c)) if !(b'0'..=b'3').contains(c));
+        let width = if indefinite {
+            self.pos += 1;
+            None
+        } else {
+            self.indicator()?
+        };
+        let start = self.out.len();
+        let mut len = 0u64;
+        let close_str = (close as char).to_string();
+        while !self.eat(&close_str)? {
+            if len > 0 {
+                self.expect(b',')?;
+            }
+            self.item(depth + 1)?;
+            if major == 5 {
+                self.expect(b':')?;
+                self.item(depth + 1)?;
+            }
+            len += 1;
+        }
+        let elements = self.out.split_off(start);
+        if indefinite {
+            self.out.push(major << 5 | 31);
+            self.out.extend(elements);
+            self.out.push(0xff);
+        } else {
+            self.head(major, len, width)?;
+            self.out.extend(elements);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(hex_bytes: &str, diagnostic: &str) {
+        let bytes = hex::decode(hex_bytes).unwrap();
+        assert_eq!(to_diagnostic(&bytes).unwrap(), diagnostic);
+        assert_eq!(hex::encode(from_diagnostic(diagnostic).unwrap()), hex_bytes);
+    }
+
+    #[test]
+    fn scalars() {
+        round_trip("00", "0");
+        round_trip("1818", "24");
+        round_trip("1801", "1_0");
+        round_trip("1b0000000000000001", "1_3");
+        round_trip("20", "-1");
+        round_trip("3bffffffffffffffff", "-18446744073709551616");
+        round_trip("43010203", "h'010203'");
+        round_trip("5801ff", "h'ff'_0");
+        round_trip("6568656c6c6f", "\"hello\"");
+        round_trip("62225c", "\"\\\"\\\\\"");
+        round_trip("f4", "false");
+        round_trip("f6", "null");
+        round_trip("f0", "simple(16)");
+        round_trip("f93e00", "1.5");
+        round_trip("fb3ff8000000000000", "1.5_3");
+        round_trip("fa47c35000", "100000.0");
+        round_trip("f97c00", "Infinity");
+        round_trip("c249010000000000000000", "2(h'010000000000000000')");
+    }
+
+    #[test]
+    fn collections() {
+        round_trip("80", "[]");
+        round_trip("820102", "[\n  1,\n  2\n]");
+        round_trip("9f01ff", "[_\n  1\n]");
+        round_trip("9fff", "[_ ]");
+        round_trip("980101", "[_0\n  1\n]");
+        round_trip("a1016161", "{\n  1: \"a\"\n}");
+        round_trip("bf0102ff", "{_\n  1: 2\n}");
+        round_trip("5f4101420203ff", "(_ h'01', h'0203')");
+        round_trip("0102", "1,\n2");
+    }
+
+    #[test]
+    fn parse_extensions() {
+        // comments, whitespace within bytes and embedded CBOR
+        let bytes = from_diagnostic("[/ first / 1, h'01 02', <<[1, 2]>>, 24(<<\"a\">>)]").unwrap();
+        assert_eq!(hex::encode(bytes), "840142010243820102d818426161");
+        assert!(from_diagnostic("[1, 2").is_err());
+        assert!(from_diagnostic("256_0").is_err());
+        assert!(from_diagnostic("1.1_1").is_err());
+    }
+
+    #[test]
+    fn annotated() {
+        let names = FieldNames::Array(vec![
+            ("inputs", FieldNames::elements(FieldNames::None)),
+            ("body", FieldNames::Map(vec![(0, "fee", FieldNames::None)])),
+        ]);
+        let bytes = hex::decode("8281d90102f6a2000101f5").unwrap();
+        assert_eq!(
+            to_diagnostic_annotated(&bytes, &names).unwrap(),
+            "[\n  / inputs / [\n    258(null)\n  ],\n  / body / {\n    / fee / 0: 1,\n    1: true\n  }\n]"
+        );
+    }
+
+    fn script() -> FieldNames {
+        // [0, key_hash] / [1, [* script]] with the scripts wrapped in tag 259 for the test
+        FieldNames::Variants(vec![
+            (
+                0,
+                FieldNames::array(vec![("", FieldNames::None), ("key_hash", FieldNames::None)]),
+            ),
+            (
+                1,
+                FieldNames::array(vec![
+                    ("", FieldNames::None),
+                    (
+                        "scripts",
+                        FieldNames::tag(259, FieldNames::elements(FieldNames::Lazy(script))),
+                    ),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn annotated_choices() {
+        let names = FieldNames::array(vec![
+            ("script", script()),
+            (
+                "version",
+                FieldNames::array(vec![
+                    ("major", FieldNames::None),
+                    ("minor", FieldNames::None),
+                ])
+                .embedded(),
+            ),
+            (
+                "assets",
+                FieldNames::entries(
+                    ("policy_id", FieldNames::None),
+                    (
+                        "",
+                        FieldNames::entries(
+                            ("asset_name", FieldNames::None),
+                            ("", FieldNames::None),
+                        ),
+                    ),
+                ),
+            ),
+        ]);
+        let bytes =
+            from_diagnostic("[[1, 259([[0, h'01'], [1, 258([])]])], 8, 0, {h'02': {h'03': 4}}]")
+                .unwrap();
+        assert_eq!(
+            to_diagnostic_annotated(&bytes, &names).unwrap(),
+            "[\n  / script / [\n    1,\n    / scripts / 259([\n      [\n        0,\n        / key_hash / h'01'\n      ],\n      [\n        1,\n        / scripts / 258([])\n      ]\n    ])\n  ],\n  / major / 8,\n  / minor / 0,\n  / assets / {\n    / policy_id / h'02': {\n      / asset_name / h'03': 4\n    }\n  }\n]"
+        );
+        // unknown variants and other tags aren't named
+        let bytes = from_diagnostic("[[5, 1], 259([])]").unwrap();
+        assert!(
+            !to_diagnostic_annotated(&bytes, &FieldNames::elements(script()))
+                .unwrap()
+                .contains('/')
+        );
+    }
+
+    #[test]
+    fn invalid_cbor() {
+        assert!(matches!(
+            to_diagnostic(&[0x82, 0x01]),
+            Err(DiagnosticError::InvalidCbor { offset: 2, .. })
+        ));
+        assert!(to_diagnostic(&[0xff]).is_err());
+        assert!(to_diagnostic(&[0x5f, 0x01, 0xff]).is_err());
+    }
+}
@@ -1,3 +1,8 @@
+use crate::canonical::NonCanonical;
+use crate::limits::{LimitError, LimitExceeded};
+use crate::reader::current_position;
+use std::convert::TryFrom;
+
 #[derive(Debug)]
 pub enum Key {
     Str(String),
@@ -28,9 +33,13 @@ pub enum DeserializeFailure {
     },
     /// Invalid internal structure imposed on top of the CBOR format
     InvalidStructure(Box<dyn std::error::Error>),
+    /// Input over one of the `DecodeLimits` from limited deserialization
+    LimitExceeded(LimitExceeded),
     MandatoryFieldMissing(Key),
     NoVariantMatched,
     NoVariantMatchedWithCauses(Vec<DeserializeError>),
+    /// Valid but not canonical CBOR, from strict deserialization
+    NonCanonical(Vec<NonCanonical>),
     OutOfRange {
         min: usize,
         max: usize,
@@ -55,6 +64,10 @@ pub enum DeserializeFailure {
 pub struct DeserializeError {
     location: Option<String>,
     failure: DeserializeFailure,
+    /// byte offset into the input where the failure was detected
+    offset: Option<u64>,
+    /// major type of the CBOR item at `offset` (None if past the end of the input)
+    major_type: Option<cbor_event::Type>,
 }
 
 impl DeserializeError {
@@ -62,18 +75,172 @@ impl DeserializeError {
         Self {
             location: Some(location.into()),
             failure,
+            offset: None,
+            major_type: None,
         }
+        .at_current_position()
     }
 
     pub fn annotate<T: Into<String>>(self, location: T) -> Self {
-        match self.location {
-            Some(loc) => Self::new(format!("{}.{}", location.into(), loc), self.failure),
-            None => Self::new(location, self.failure),
+        let location = match self.location {
+            Some(loc) => format!("{}.{}", location.into(), loc),
+            None => location.into(),
+        };
+        Self {
+            location: Some(location),
+            ..self
+        }
+        .at_current_position()
+    }
+
+    /// Records the position of the active `DecodeReader` (see `crate::reader`) as where
+    /// the failure happened unless a (more precise) offset was already recorded.
+    fn at_current_position(mut self) -> Self {
+        if self.offset.is_none() {
+            if let Some((offset, major_type)) = current_position() {
+                self.offset = Some(offset);
+                self.major_type = major_type;
+            }
+        }
+        self
+    }
+
+    pub(crate) fn from_limit_error(limit_error: LimitError) -> Self {
+        Self {
+            location: None,
+            failure: DeserializeFailure::LimitExceeded(limit_error.limit),
+            offset: Some(limit_error.offset),
+            major_type: limit_error.major_type,
+        }
+    }
+
+    /// Records `offset` into `input` as where the failure happened
+    pub(crate) fn at_offset(mut self, input: &[u8], offset: u64) -> Self {
+        self.offset = Some(offset);
+        self.major_type = input
+            .get(offset as usize)
+            .map(|byte| cbor_event::Type::from(*byte));
+        self
+    }
+
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    pub fn failure(&self) -> &DeserializeFailure {
+        &self.failure
+    }
+
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    pub fn major_type(&self) -> Option<cbor_event::Type> {
+        self.major_type
+    }
+
+    /// Follows `NoVariantMatchedWithCauses` into the variant that got furthest into the input.
+    /// Returns the full location path and the error it ends at.
+    pub fn deepest(&self) -> (Option<String>, &DeserializeError) {
+        let mut location = self.location.clone();
+        let mut error = self;
+        while let DeserializeFailure::NoVariantMatchedWithCauses(causes) = &error.failure {
+            // on ties prefer variants that got past their tag/discriminant
+            let progress = |e: &Self| {
+                let discriminant_mismatch = matches!(
+                    e.failure,
+                    DeserializeFailure::FixedValueMismatch { .. }
+                        | DeserializeFailure::TagMismatch { .. }
+                );
+                (e.offset, !discriminant_mismatch)
+            };
+            let furthest =
+                causes
+                    .iter()
+                    .fold(None, |furthest: Option<&Self>, cause| match furthest {
+                        Some(furthest) if progress(furthest) >= progress(cause) => Some(furthest),
+                        _ => Some(cause),
+                    });
+            match furthest {
+                Some(cause) => {
+                    location = match (location, &cause.location) {
+                        (Some(outer), Some(inner)) => Some(format!("{outer}.{inner}")),
+                        (outer, inner) => outer.or_else(|| inner.clone()),
+                    };
+                    error = cause;
+                }
+                None => break,
+            }
+        }
+        (location, error)
+    }
+
+    /// Human readable report for the failure of deserializing `input`:
+    /// the deepest location, the CBOR items enclosing the failure point
+    /// and a hex dump of the bytes around it.
+    pub fn render(&self, input: &[u8]) -> String {
+        use std::fmt::Write;
+        let (location, deepest) = self.deepest();
+        let mut out = String::new();
+        match &location {
+            Some(loc) => write!(out, "Deserialization failed in {loc} because: "),
+            None => write!(out, "Deserialization: "),
+        }
+        .unwrap();
+        deepest.fmt_failure(&mut out, 0).unwrap();
+        let offset = match deepest.offset.or(self.offset) {
+            Some(offset) => offset as usize,
+            None => return out,
+        };
+        write!(out, "\nat byte offset {offset} ({offset:#x})").unwrap();
+        if let Some(major_type) = deepest.major_type {
+            write!(out, ", CBOR item of type {major_type:?}").unwrap();
+        }
+        let enclosing = enclosing_items(input, offset);
+        if !enclosing.is_empty() {
+            let items = enclosing
+                .iter()
+                .map(|(start, major_type)| format!("{major_type:?}@{start}"))
+                .collect::<Vec<_>>();
+            write!(out, "\nwithin: {}", items.join(" > ")).unwrap();
+        }
+        // 16 bytes per line with the line containing the failure in the middle
+        let line_start = offset.min(input.len()) / 16 * 16;
+        for start in (line_start.saturating_sub(32)..(line_start + 48).min(input.len())).step_by(16)
+        {
+            let line = &input[start..(start + 16).min(input.len())];
+            let bytes = line.iter().map(|b| format!("{b:02x}")).collect::<Vec<_>>();
+            write!(out, "\n{start:08x}  {}", bytes.join(" ")).unwrap();
+            if (start..start + 16).contains(&offset) {
+                write!(out, "\n{}^^", " ".repeat(10 + 3 * (offset - start))).unwrap();
+            }
+        }
+        if offset >= input.len() {
+            write!(out, "\n(failure is at the end of the input)").unwrap();
         }
+        out
+    }
+
+    /// Structured form of the error (including all variant failures) e.g. for logging
+    pub fn to_json_value(&self) -> serde_json::Value {
+        let mut failure = String::new();
+        self.fmt_failure(&mut failure, 0).unwrap();
+        let mut json = serde_json::json!({
+            "location": self.location,
+            "kind": self.failure.kind(),
+            "offset": self.offset,
+            "major_type": self.major_type.map(|major_type| format!("{major_type:?}")),
+        });
+        match &self.failure {
+            DeserializeFailure::NoVariantMatchedWithCauses(causes) => {
+                json["causes"] = causes.iter().map(Self::to_json_value).collect();
+            }
+            _ => json["message"] = failure.into(),
+        }
+        json
     }
 
     fn fmt_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: u32) -> std::fmt::Result {
-        use std::fmt::Display;
         for _ in 0..indent {
             write!(f, "\t")?;
         }
@@ -81,6 +248,10 @@ impl DeserializeError {
             Some(loc) => write!(f, "Deserialization failed in {loc} because: "),
             None => write!(f, "Deserialization: "),
         }?;
+        self.fmt_failure(f, indent)
+    }
+
+    fn fmt_failure(&self, f: &mut impl std::fmt::Write, indent: u32) -> std::fmt::Result {
         match &self.failure {
             DeserializeFailure::BadAddressType(header) => {
                 write!(f, "Encountered unknown address header {header:#08b}")
@@ -89,7 +260,7 @@ impl DeserializeError {
                 f,
                 "Encountered CBOR Break while reading definite length sequence"
             ),
-            DeserializeFailure::CBOR(e) => e.fmt(f),
+            DeserializeFailure::CBOR(e) => write!(f, "{e}"),
             DeserializeFailure::DefiniteLenMismatch(found, expected) => {
                 write!(f, "Definite length mismatch: found {found}")?;
                 if let Some(expected_elems) = expected {
@@ -106,6 +277,7 @@ impl DeserializeError {
             DeserializeFailure::InvalidStructure(e) => {
                 write!(f, "Invalid internal structure: {e}")
             }
+            DeserializeFailure::LimitExceeded(limit) => write!(f, "Decode limit exceeded: {limit}"),
             DeserializeFailure::MandatoryFieldMissing(key) => {
                 write!(f, "Mandatory field {key} not found")
             }
@@ -113,11 +285,19 @@ impl DeserializeError {
             DeserializeFailure::NoVariantMatchedWithCauses(errs) => {
                 writeln!(f, "No variant matched. Failures:")?;
                 for e in errs {
-                    e.fmt_indent(f, indent + 1)?;
+                    write!(f, "{}", Indented(e, indent + 1))?;
                     writeln!(f)?;
                 }
                 Ok(())
             }
+            DeserializeFailure::NonCanonical(violations) => {
+                write!(f, "Non-canonical CBOR:")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{separator}{violation}")?;
+                }
+                Ok(())
+            }
             DeserializeFailure::OutOfRange { min, max, found } => {
                 write!(f, "Out of range: {found} - must be in range {min} - {max}")
             }
@@ -141,6 +321,113 @@ impl DeserializeError {
     }
 }
 
+impl DeserializeFailure {
+    /// Name of the variant e.g. for structured logging
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BadAddressType(_) => "BadAddressType",
+            Self::BreakInDefiniteLen => "BreakInDefiniteLen",
+            Self::CBOR(_) => "CBOR",
+            Self::DefiniteLenMismatch(_, _) => "DefiniteLenMismatch",
+            Self::DuplicateKey(_) => "DuplicateKey",
+            Self::EndingBreakMissing => "EndingBreakMissing",
+            Self::ExpectedNull => "ExpectedNull",
+            Self::FixedValueMismatch { .. } => "FixedValueMismatch",
+            Self::InvalidStructure(_) => "InvalidStructure",
+            Self::LimitExceeded(_) => "LimitExceeded",
+            Self::MandatoryFieldMissing(_) => "MandatoryFieldMissing",
+            Self::NoVariantMatched => "NoVariantMatched",
+            Self::NoVariantMatchedWithCauses(_) => "NoVariantMatchedWithCauses",
+            Self::NonCanonical(_) => "NonCanonical",
+            Self::OutOfRange { .. } => "OutOfRange",
+            Self::RangeCheck { .. } => "RangeCheck",
+            Self::TagMismatch { .. } => "TagMismatch",
+            Self::UnknownKey(_) => "UnknownKey",
+            Self::UnexpectedKeyType(_) => "UnexpectedKeyType",
+            Self::VariableLenNatDecodeFailed => "VariableLenNatDecodeFailed",
+        }
+    }
+}
+
+/// Displays an error at the given nesting level of `NoVariantMatchedWithCauses`
+struct Indented<'a>(&'a DeserializeError, u32);
+
+impl std::fmt::Display for Indented<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_indent(f, self.1)
+    }
+}
+
+/// Start offsets and major types of the CBOR items containing `offset`, outermost first.
+/// Stops early on malformed input.
+fn enclosing_items(input: &[u8], offset: usize) -> Vec<(usize, cbor_event::Type)> {
+    let mut chain = Vec::new();
+    walk_cbor(input, 0, offset, &mut chain);
+    chain
+}
+
+/// Nesting deeper than this isn't walked
+const MAX_WALK_DEPTH: usize = 256;
+
+/// Walks the CBOR item at `pos`, returning its end if it doesn't contain `target`.
+/// If it does, the path to `target` is left in `chain` and None returned.
+fn walk_cbor(
+    input: &[u8],
+    pos: usize,
+    target: usize,
+    chain: &mut Vec<(usize, cbor_event::Type)>,
+) -> Option<usize> {
+    let initial = *input.get(pos)?;
+    if chain.len() >= MAX_WALK_DEPTH {
+        return None;
+    }
+    let major_type = cbor_event::Type::from(initial);
+    chain.push((pos, major_type));
+    // argument of the header, None for indefinite length
+    let (arg, mut end) = match initial & 0x1f {
+        info @ 0..=23 => (Some(u64::from(info)), pos + 1),
+        info @ 24..=27 => {
+            let len = 1 << (info - 24);
+            let bytes = input.get(pos + 1..pos + 1 + len)?;
+            let arg = bytes.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+            (Some(arg), pos + 1 + len)
+        }
+        31 => (None, pos + 1),
+        _ => return None,
+    };
+    let mut children = |count: Option<u64>, end: &mut usize| -> Option<()> {
+        let mut read = 0;
+        loop {
+            match count {
+                Some(count) if read == count => return Some(()),
+                None if *input.get(*end)? == 0xff => {
+                    *end += 1;
+                    return Some(());
+                }
+                _ => (),
+            }
+            *end = walk_cbor(input, *end, target, chain)?;
+            read += 1;
+        }
+    };
+    match major_type {
+        cbor_event::Type::Bytes | cbor_event::Type::Text => match arg {
+            Some(len) => end = end.checked_add(usize::try_from(len).ok()?)?,
+            // chunks are strings themselves
+            None => children(None, &mut end)?,
+        },
+        cbor_event::Type::Array => children(arg, &mut end)?,
+        cbor_event::Type::Map => children(arg.map(|len| len * 2), &mut end)?,
+        cbor_event::Type::Tag => children(Some(1), &mut end)?,
+        _ => (),
+    }
+    if end > target {
+        return None;
+    }
+    chain.pop();
+    Some(end)
+}
+
 impl std::error::Error for DeserializeError {}
 
 impl std::fmt::Display for DeserializeError {
@@ -154,16 +441,31 @@ impl From<DeserializeFailure> for DeserializeError {
         DeserializeError {
             location: None,
             failure,
+            offset: None,
+            major_type: None,
         }
+        .at_current_position()
     }
 }
 
 impl From<cbor_event::Error> for DeserializeError {
     fn from(err: cbor_event::Error) -> DeserializeError {
+        // limits exceeded while reading from a DecodeReader
+        if let cbor_event::Error::IoError(io_error) = &err {
+            if let Some(limit_error) = io_error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<LimitError>())
+            {
+                return Self::from_limit_error(*limit_error);
+            }
+        }
         DeserializeError {
             location: None,
             failure: DeserializeFailure::CBOR(err),
+            offset: None,
+            major_type: None,
         }
+        .at_current_position()
     }
 }
 
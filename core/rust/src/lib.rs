@@ -10,12 +10,22 @@
 // from within their proc macros itself. Issue: https://github.com/mcarton/rust-derivative/issues/115
 #![allow(clippy::non_canonical_partial_ord_impl)]
 
+pub use cbor_value::{CborPathSegment, CborValue};
 pub use error::*;
+pub use unknown_fields::UnknownFields;
 
+pub mod canonical;
+pub mod cbor_value;
+pub mod diagnostic;
 pub mod error;
+pub mod limits;
 pub mod network;
 pub mod ordered_hash_map;
+pub mod preserved_json;
+pub mod reader;
 pub mod serialization;
+pub mod text_envelope;
+pub mod unknown_fields;
 
 use crate::serialization::{fit_sz, Deserialize, Serialize};
 
@@ -0,0 +1,659 @@
+//! Resource limits for deserializing untrusted CBOR.
+//!
+//! The generated deserialization code trusts the lengths it reads and recurses into nested
+//! structures (e.g. PlutusData, NativeScript, metadata) so hostile input can exhaust the stack
+//! or request huge allocations. A `DecodeReader` (see `crate::reader`) with limits walks the
+//! CBOR as the deserializer looks at it, without recursion, and fails reading anything over the
+//! limits before the deserializer acts on it. This covers every `Deserialize` impl however it
+//! is called, e.g. from `Deserialize::from_cbor_bytes_with_limits()`, directly on a
+//! `Deserializer` over a limited `DecodeReader` or anywhere inside a `with_limits()` scope.
+//! `check_limits` does the same walk over a complete input, which is what `from_cbor_bytes()`
+//! and friends do instead as they have all of it at hand. Without limits nothing is walked and
+//! only the bounded reads of the deserializer (e.g. `read_bounded_bytes`) apply.
+
+use crate::error::DeserializeFailure;
+use std::cell::Cell;
+
+/// Limits checked by `Deserialize::from_cbor_bytes_with_limits()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// deepest nesting of arrays, maps and tags
+    pub max_depth: usize,
+    /// most elements of an array or entries of a map
+    pub max_collection_len: u64,
+    /// largest input accepted
+    pub max_bytes: usize,
+    /// largest big integer (tag 2/3 byte string) in bytes
+    pub max_bigint_bytes: u64,
+}
+
+impl Default for DecodeLimits {
+    /// Generous enough for any block or transaction found on chain
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_collection_len: 65_536,
+            max_bytes: 16 * 1024 * 1024,
+            max_bigint_bytes: 64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Depth { max: usize },
+    CollectionLen { max: u64, found: u64 },
+    Bytes { max: usize, found: usize },
+    BigIntBytes { max: u64, found: u64 },
+}
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Depth { max } => write!(f, "nesting deeper than {max}"),
+            Self::CollectionLen { max, found } => {
+                write!(f, "collection of {found} elements exceeds {max}")
+            }
+            Self::Bytes { max, found } => write!(f, "input of {found} bytes exceeds {max}"),
+            Self::BigIntBytes { max, found } => {
+                write!(f, "big integer of {found} bytes exceeds {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+thread_local! {
+    static SCOPE_LIMITS: Cell<Option<DecodeLimits>> = const { Cell::new(None) };
+}
+
+/// Limits applied to `DecodeReader`s created on this thread, see `with_limits()`
+pub fn current_limits() -> Option<DecodeLimits> {
+    SCOPE_LIMITS.with(|limits| limits.get())
+}
+
+/// Runs `f` with `limits` applied to all deserialization on the current thread that goes through
+/// a `DecodeReader`, e.g. `from_cbor_bytes()` or `MultiEraBlock::from_explicit_network_cbor_bytes()`.
+pub fn with_limits<T>(limits: &DecodeLimits, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<DecodeLimits>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPE_LIMITS.with(|limits| limits.set(self.0));
+        }
+    }
+    let _restore = Restore(SCOPE_LIMITS.with(|scope| scope.replace(Some(*limits))));
+    f()
+}
+
+/// A limit hit while reading from a `DecodeReader`. Returned as the payload of the io::Error
+/// the deserializer sees and turned back into `DeserializeFailure::LimitExceeded`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LimitError {
+    pub offset: u64,
+    pub major_type: Option<cbor_event::Type>,
+    pub limit: LimitExceeded,
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte offset {}", self.limit, self.offset)
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    Array,
+    Map,
+    Tag,
+    /// chunks of an indefinite length string
+    String,
+    /// chunks of an indefinite length big integer byte string
+    BigInt,
+}
+
+struct Frame {
+    kind: FrameKind,
+    /// items left for definite lengths, None for indefinite ones
+    remaining: Option<u64>,
+    /// items (or for big integers, bytes) read so far
+    read: u64,
+}
+
+/// Incremental form of `check_limits` that is fed the input a piece at a time
+pub(crate) struct LimitChecker {
+    limits: DecodeLimits,
+    /// total size of the input if known, to reject strings longer than it
+    input_len: Option<u64>,
+    stack: Vec<Frame>,
+    after_bigint_tag: bool,
+    /// header of the current item, which can be split between pieces
+    header: Vec<u8>,
+    /// offset of the current item
+    item_start: u64,
+    /// string contents still to be skipped
+    payload: u64,
+    /// bytes fed so far
+    fed: u64,
+}
+
+impl LimitChecker {
+    pub fn new(limits: DecodeLimits, input_len: Option<u64>) -> Self {
+        Self {
+            limits,
+            input_len,
+            stack: Vec::new(),
+            after_bigint_tag: false,
+            header: Vec::with_capacity(9),
+            item_start: 0,
+            payload: 0,
+            fed: 0,
+        }
+    }
+
+    /// Offset and initial byte of the item currently being checked
+    pub fn item(&self) -> (u64, Option<u8>) {
+        (self.item_start, self.header.first().copied())
+    }
+
+    /// Checks the next piece of the input. Returns how much of `bytes` was used if a top-level
+    /// item ended in it, in which case the rest belongs to the next item.
+    /// Failures are at the offset (relative to the first byte fed) of the offending item.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Option<usize>, (u64, DeserializeFailure)> {
+        let mut pos = 0;
+        while pos < bytes.len() {
+            if self.payload > 0 {
+                let skipped = self.payload.min((bytes.len() - pos) as u64);
+                self.payload -= skipped;
+                pos += skipped as usize;
+                self.fed += skipped;
+                if self.payload == 0 && self.item_done().map_err(|e| (self.item_start, e))? {
+                    return Ok(Some(pos));
+                }
+                continue;
+            }
+            if self.header.is_empty() {
+                self.item_start = self.fed;
+            }
+            self.header.push(bytes[pos]);
+            pos += 1;
+            self.fed += 1;
+            if self.fed > self.limits.max_bytes as u64 {
+                return Err((
+                    self.limits.max_bytes as u64,
+                    DeserializeFailure::LimitExceeded(LimitExceeded::Bytes {
+                        max: self.limits.max_bytes,
+                        found: self.fed as usize,
+                    }),
+                ));
+            }
+            let info = self.header[0] & 0x1f;
+            let header_len = match info {
+                24..=27 => 1 + (1 << (info - 24)),
+                _ => 1,
+            };
+            if self.header.len() < header_len {
+                continue;
+            }
+            let done = self.check_item().map_err(|e| (self.item_start, e))?;
+            self.header.clear();
+            if done {
+                return Ok(Some(pos));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks the item whose complete header is in `self.header`.
+    /// Returns true once the outermost item is complete.
+    fn check_item(&mut self) -> Result<bool, DeserializeFailure> {
+        let initial = self.header[0];
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+        let bigint = std::mem::take(&mut self.after_bigint_tag);
+        if initial == 0xff {
+            return match self.stack.last() {
+                Some(Frame {
+                    remaining: None, ..
+                }) => {
+                    self.stack.pop();
+                    self.item_done()
+                }
+                _ => Err(DeserializeFailure::BreakInDefiniteLen),
+            };
+        }
+        let arg = match info {
+            0..=27 => Some(
+                self.header[1..]
+                    .iter()
+                    .fold(u64::from(if info < 24 { info } else { 0 }), |acc, byte| {
+                        (acc << 8) | u64::from(*byte)
+                    }),
+            ),
+            31 if (2..=5).contains(&major) => None,
+            _ => {
+                return Err(DeserializeFailure::CBOR(cbor_event::Error::UnknownLenType(
+                    info,
+                )))
+            }
+        };
+        let kind = match (major, arg) {
+            // uint / nint / simple values and floats
+            (0 | 1 | 7, _) => None,
+            (2 | 3, Some(len)) => {
+                if let Some(input_len) = self.input_len {
+                    let available = input_len.saturating_sub(self.fed);
+                    if len > available {
+                        return Err(DeserializeFailure::CBOR(cbor_event::Error::NotEnough(
+                            available as usize,
+                            len.min(usize::MAX as u64) as usize,
+                        )));
+                    }
+                }
+                let end = self.fed.saturating_add(len);
+                if end > self.limits.max_bytes as u64 {
+                    return Err(DeserializeFailure::LimitExceeded(LimitExceeded::Bytes {
+                        max: self.limits.max_bytes,
+                        found: end.min(usize::MAX as u64) as usize,
+                    }));
+                }
+                let bigint_len = match self.stack.last() {
+                    _ if bigint && major == 2 => Some(len),
+                    Some(frame) if frame.kind == FrameKind::BigInt => Some(frame.read + len),
+                    _ => None,
+                };
+                if let Some(found) = bigint_len.filter(|len| *len > self.limits.max_bigint_bytes) {
+                    return Err(DeserializeFailure::LimitExceeded(
+                        LimitExceeded::BigIntBytes {
+                            max: self.limits.max_bigint_bytes,
+                            found,
+                        },
+                    ));
+                }
+                if let Some(frame) = self
+                    .stack
+                    .last_mut()
+                    .filter(|f| f.kind == FrameKind::BigInt)
+                {
+                    frame.read += len;
+                }
+                if len > 0 {
+                    // done once the contents have been skipped
+                    self.payload = len;
+                    return Ok(false);
+                }
+                None
+            }
+            (2, None) if bigint => Some((FrameKind::BigInt, None)),
+            (2 | 3, None) => Some((FrameKind::String, None)),
+            (4 | 5, len) => {
+                if let Some(found) = len.filter(|len| *len > self.limits.max_collection_len) {
+                    return Err(DeserializeFailure::LimitExceeded(
+                        LimitExceeded::CollectionLen {
+                            max: self.limits.max_collection_len,
+                            found,
+                        },
+                    ));
+                }
+                let kind = if major == 4 {
+                    FrameKind::Array
+                } else {
+                    FrameKind::Map
+                };
+                match len {
+                    Some(0) => None,
+                    Some(len) if major == 5 => Some((kind, Some(len.saturating_mul(2)))),
+                    len => Some((kind, len)),
+                }
+            }
+            // tag
+            (_, tag) => {
+                self.after_bigint_tag = matches!(tag, Some(2 | 3));
+                Some((FrameKind::Tag, Some(1)))
+            }
+        };
+        match kind {
+            Some((kind, remaining)) => {
+                if self.stack.len() >= self.limits.max_depth {
+                    return Err(DeserializeFailure::LimitExceeded(LimitExceeded::Depth {
+                        max: self.limits.max_depth,
+                    }));
+                }
+                self.stack.push(Frame {
+                    kind,
+                    remaining,
+                    read: 0,
+                });
+                Ok(false)
+            }
+            None => self.item_done(),
+        }
+    }
+
+    /// Marks one item of the innermost container as read, closing any containers this completes.
+    /// Returns true once the outermost item is complete.
+    fn item_done(&mut self) -> Result<bool, DeserializeFailure> {
+        loop {
+            let frame = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None => return Ok(true),
+            };
+            if frame.kind != FrameKind::BigInt {
+                frame.read += 1;
+            }
+            match &mut frame.remaining {
+                Some(remaining) => {
+                    *remaining -= 1;
+                    if *remaining > 0 {
+                        return Ok(false);
+                    }
+                    self.stack.pop();
+                }
+                None => {
+                    let found = match frame.kind {
+                        FrameKind::Array => frame.read,
+                        FrameKind::Map => frame.read / 2,
+                        _ => return Ok(false),
+                    };
+                    if found > self.limits.max_collection_len {
+                        return Err(DeserializeFailure::LimitExceeded(
+                            LimitExceeded::CollectionLen {
+                                max: self.limits.max_collection_len,
+                                found,
+                            },
+                        ));
+                    }
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
+/// Limit checking state of a `DecodeReader`
+pub(crate) struct ReadLimits {
+    /// None once the input can't be checked any further
+    checker: Option<LimitChecker>,
+    /// stream offset the checker started at
+    start: u64,
+    /// stream offset up to which everything has been checked
+    checked: u64,
+    failed: Option<LimitError>,
+}
+
+impl ReadLimits {
+    pub fn new(limits: DecodeLimits, start: u64) -> Self {
+        Self {
+            checker: Some(LimitChecker::new(limits, None)),
+            start,
+            checked: start,
+            failed: None,
+        }
+    }
+
+    pub fn failed(&self) -> Option<LimitError> {
+        self.failed
+    }
+
+    /// Checks `bytes` found at stream offset `offset` unless already checked. Fails (now and on
+    /// every later call) once a limit is exceeded.
+    pub fn check(&mut self, offset: u64, bytes: &[u8]) -> std::io::Result<()> {
+        if let Some(failed) = self.failed {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, failed));
+        }
+        let checker = match &mut self.checker {
+            Some(checker) => checker,
+            None => return Ok(()),
+        };
+        let end = offset + bytes.len() as u64;
+        if end <= self.checked {
+            return Ok(());
+        }
+        if offset > self.checked {
+            // bytes were skipped without being looked at so where items start is unknown
+            self.checker = None;
+            return Ok(());
+        }
+        let mut unchecked = &bytes[(self.checked - offset) as usize..];
+        while !unchecked.is_empty() {
+            match checker.feed(unchecked) {
+                // the rest is the next item
+                Ok(Some(used)) => unchecked = &unchecked[used..],
+                Ok(None) => break,
+                Err((item_offset, DeserializeFailure::LimitExceeded(limit))) => {
+                    let (start, initial) = checker.item();
+                    let failed = LimitError {
+                        offset: self.start + item_offset,
+                        major_type: initial
+                            .filter(|_| start == item_offset)
+                            .map(cbor_event::Type::from),
+                        limit,
+                    };
+                    self.failed = Some(failed);
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, failed));
+                }
+                // malformed CBOR is reported by the deserializer when it gets there
+                Err(_) => {
+                    self.checker = None;
+                    return Ok(());
+                }
+            }
+        }
+        self.checked = end;
+        Ok(())
+    }
+}
+
+/// Checks the CBOR item at the start of `bytes` against `limits`.
+/// Also fails on lengths running past the end of the input so nothing is allocated for them.
+/// Returns where the item ends, or the offset of the offending item and why it was rejected.
+/// Other malformed CBOR is left for the deserializer to report.
+pub fn check_limits(
+    bytes: &[u8],
+    limits: &DecodeLimits,
+) -> Result<usize, (usize, DeserializeFailure)> {
+    if bytes.len() > limits.max_bytes {
+        return Err((
+            limits.max_bytes,
+            DeserializeFailure::LimitExceeded(LimitExceeded::Bytes {
+                max: limits.max_bytes,
+                found: bytes.len(),
+            }),
+        ));
+    }
+    let mut checker = LimitChecker::new(*limits, Some(bytes.len() as u64));
+    match checker.feed(bytes) {
+        Ok(Some(end)) => Ok(end),
+        Ok(None) => {
+            let (start, initial) = checker.item();
+            let needed = match initial.map(|initial| initial & 0x1f) {
+                Some(info @ 24..=27) => 1 << (info - 24),
+                _ => 1,
+            };
+            let available = bytes.len() - start as usize;
+            Err((
+                start as usize,
+                DeserializeFailure::CBOR(cbor_event::Error::NotEnough(
+                    available.saturating_sub(1),
+                    needed,
+                )),
+            ))
+        }
+        Err((offset, failure)) => Err((offset as usize, failure)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exceeded(bytes: &[u8], limits: &DecodeLimits) -> (usize, LimitExceeded) {
+        match check_limits(bytes, limits) {
+            Err((offset, DeserializeFailure::LimitExceeded(limit))) => (offset, limit),
+            result => panic!("unexpected {:?}", result.map_err(|(_, e)| e.kind())),
+        }
+    }
+
+    /// Reads everything from a DecodeReader over `bytes` that only sees `capacity` bytes at a time
+    fn read_limited(bytes: &[u8], capacity: usize, limits: &DecodeLimits) -> Option<LimitError> {
+        use std::io::BufRead;
+        let mut reader = crate::reader::DecodeReader::new(std::io::BufReader::with_capacity(
+            capacity,
+            std::io::Cursor::new(bytes),
+        ))
+        .with_limits(limits);
+        loop {
+            match reader.fill_buf() {
+                Ok([]) => return None,
+                Ok(buf) => {
+                    let len = buf.len();
+                    reader.consume(len);
+                }
+                Err(e) => {
+                    let limit_error = *e.get_ref().unwrap().downcast_ref::<LimitError>().unwrap();
+                    // later reads fail the same way
+                    assert!(reader.fill_buf().is_err());
+                    return Some(limit_error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn streamed() {
+        let limits = DecodeLimits {
+            max_depth: 2,
+            max_collection_len: 300,
+            ..Default::default()
+        };
+        // [[1]] then [[[1]]] after a byte string whose header and contents are split up
+        let mut bytes = vec![0x81, 0x81, 0x01, 0x58, 0x03, 0x01, 0x02, 0x03];
+        bytes.extend([0x81, 0x81, 0x81, 0x01]);
+        for capacity in [1, 2, 3, bytes.len()] {
+            let limit_error = read_limited(&bytes, capacity, &limits).unwrap();
+            assert_eq!(limit_error.offset, 10);
+            assert_eq!(limit_error.major_type, Some(cbor_event::Type::Array));
+            assert_eq!(limit_error.limit, LimitExceeded::Depth { max: 2 });
+            assert!(read_limited(&bytes[..8], capacity, &limits).is_none());
+        }
+        // array of 301 elements with its 2-byte length split
+        let mut bytes = vec![0x99, 0x01, 0x2d];
+        bytes.extend([0x00; 301]);
+        let limit_error = read_limited(&bytes, 2, &limits).unwrap();
+        assert_eq!(limit_error.offset, 0);
+        assert_eq!(
+            limit_error.limit,
+            LimitExceeded::CollectionLen {
+                max: 300,
+                found: 301
+            }
+        );
+        // a byte string longer than max_bytes is rejected before its contents
+        let limits = DecodeLimits {
+            max_bytes: 100,
+            ..Default::default()
+        };
+        let limit_error = read_limited(&[0x5a, 0xff, 0xff, 0xff, 0xff], 16, &limits).unwrap();
+        assert_eq!(limit_error.offset, 0);
+        assert!(matches!(
+            limit_error.limit,
+            LimitExceeded::Bytes { max: 100, .. }
+        ));
+    }
+
+    #[test]
+    fn within_limits() {
+        // [_ h'01', (_ h'02' h'03'), {_ 1: 2}, 2(h'0102'), 1.5] followed by 0
+        let bytes = [
+            0x9f, 0x41, 0x01, 0x5f, 0x41, 0x02, 0x41, 0x03, 0xff, 0xbf, 0x01, 0x02, 0xff, 0xc2,
+            0x42, 0x01, 0x02, 0xf9, 0x3e, 0x00, 0xff, 0x00,
+        ];
+        assert_eq!(
+            check_limits(&bytes, &DecodeLimits::default()).ok(),
+            Some(bytes.len() - 1)
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let limits = DecodeLimits {
+            max_depth: 3,
+            ..Default::default()
+        };
+        // [[[1]]] and [[24(1)]]
+        assert!(check_limits(&[0x81, 0x81, 0x81, 0x01], &limits).is_ok());
+        assert!(check_limits(&[0x81, 0x81, 0xd8, 0x18, 0x01], &limits).is_ok());
+        assert_eq!(
+            exceeded(&[0x81, 0x81, 0x81, 0x81, 0x01], &limits),
+            (3, LimitExceeded::Depth { max: 3 })
+        );
+        // hostile nesting fails without overflowing the stack
+        let deep = vec![0x81; 1_000_000];
+        assert_eq!(
+            exceeded(&deep, &DecodeLimits::default()),
+            (128, LimitExceeded::Depth { max: 128 })
+        );
+    }
+
+    #[test]
+    fn collection_len() {
+        let limits = DecodeLimits {
+            max_collection_len: 2,
+            ..Default::default()
+        };
+        assert!(check_limits(&[0x82, 0x01, 0x02], &limits).is_ok());
+        assert_eq!(
+            exceeded(&[0x83, 0x01, 0x02, 0x03], &limits),
+            (0, LimitExceeded::CollectionLen { max: 2, found: 3 })
+        );
+        assert!(check_limits(&[0xbf, 0x01, 0x02, 0x03, 0x04, 0xff], &limits).is_ok());
+        assert_eq!(
+            exceeded(&[0x9f, 0x01, 0x02, 0x03, 0xff], &limits),
+            (3, LimitExceeded::CollectionLen { max: 2, found: 3 })
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        let limits = DecodeLimits {
+            max_bytes: 2,
+            ..Default::default()
+        };
+        assert!(check_limits(&[0x41, 0x00], &limits).is_ok());
+        assert_eq!(
+            exceeded(&[0x42, 0x00, 0x00], &limits),
+            (2, LimitExceeded::Bytes { max: 2, found: 3 })
+        );
+        // a byte string claiming 2^32 bytes is rejected before anything tries to allocate it
+        assert!(matches!(
+            check_limits(
+                &[0x5a, 0xff, 0xff, 0xff, 0xff, 0x00],
+                &DecodeLimits::default()
+            ),
+            Err((0, DeserializeFailure::CBOR(_)))
+        ));
+    }
+
+    #[test]
+    fn bigint_bytes() {
+        let limits = DecodeLimits {
+            max_bigint_bytes: 2,
+            ..Default::default()
+        };
+        // 2(h'0102') and 3(h'010203')
+        assert!(check_limits(&[0xc2, 0x42, 0x01, 0x02], &limits).is_ok());
+        assert_eq!(
+            exceeded(&[0xc3, 0x43, 0x01, 0x02, 0x03], &limits),
+            (1, LimitExceeded::BigIntBytes { max: 2, found: 3 })
+        );
+        // 2((_ h'01', h'0203'))
+        assert_eq!(
+            exceeded(&[0xc2, 0x5f, 0x41, 0x01, 0x42, 0x02, 0x03, 0xff], &limits),
+            (4, LimitExceeded::BigIntBytes { max: 2, found: 3 })
+        );
+        // other tagged byte strings aren't big integers
+        assert!(check_limits(&[0xd8, 0x18, 0x43, 0x01, 0x02, 0x03], &limits).is_ok());
+    }
+}
@@ -0,0 +1,774 @@
+//! JSON that keeps the CBOR encoding details of a value.
+//!
+//! The regular serde JSON of CML types skips `encodings`, so `T -> JSON -> T` loses
+//! length encodings, int sizes, map key orders etc and re-serializing changes hashes.
+//! `PreservedJson` stores the regular JSON `value` alongside the differences between
+//! the original CBOR and the canonical CBOR of that value, allowing the original bytes
+//! to be reproduced exactly when reading it back.
+//!
+//! The differences are expressed per CBOR item, numbered in a pre-order walk of the
+//! original CBOR (map keys before their values). In the rare case that the original
+//! can't be described this way (e.g. an encoding choice that changes the CBOR structure)
+//! the original CBOR is kept as hex in `cborHex` instead.
+
+use std::collections::BTreeMap;
+
+use crate::error::DeserializeError;
+use crate::serialization::{Deserialize, Serialize};
+
+/// Regular serde JSON of a value plus what is needed to reproduce its original CBOR.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PreservedJson {
+    pub value: serde_json::Value,
+    /// Non-canonical encoding details of the original CBOR
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encodings: Vec<EncodingDetail>,
+    /// The original CBOR when it could not be described by `encodings`
+    #[serde(default, rename = "cborHex", skip_serializing_if = "Option::is_none")]
+    pub cbor_hex: Option<String>,
+}
+
+/// How a single CBOR item was encoded when it differs from its canonical encoding.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EncodingDetail {
+    /// Index of the item in a pre-order walk of the original CBOR
+    pub item: usize,
+    /// Byte size (1, 2, 4 or 8) of the item's int/length/tag argument when not the shortest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u8>,
+    /// Whether the array/map/string used an indefinite length
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indefinite: bool,
+    /// Chunk sizes of an indefinite length string
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chunks: Vec<u64>,
+    /// For maps, the original position of each entry as an index into the canonical order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<usize>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PreservedJsonError {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Deserialization: {0}")]
+    Deserialization(#[from] DeserializeError),
+    #[error("Encoding details do not match the structure of the value")]
+    EncodingMismatch,
+}
+
+impl PreservedJson {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, PreservedJsonError> {
+        serde_json::from_str(json).map_err(Into::into)
+    }
+}
+
+pub trait ToPreservedJson {
+    fn to_preserved_json(&self) -> Result<PreservedJson, PreservedJsonError>;
+
+    fn to_preserved_json_string(&self) -> Result<String, PreservedJsonError> {
+        self.to_preserved_json().map(|json| json.to_json())
+    }
+}
+
+pub trait FromPreservedJson {
+    /// Reads the value back such that `to_cbor_bytes()` gives the original CBOR.
+    /// The value may have been edited as long as its CBOR structure is unchanged,
+    /// in which case the encoding details are kept for the rest of it.
+    fn from_preserved_json(json: &PreservedJson) -> Result<Self, PreservedJsonError>
+    where
+        Self: Sized;
+
+    fn from_preserved_json_str(json: &str) -> Result<Self, PreservedJsonError>
+    where
+        Self: Sized,
+    {
+        Self::from_preserved_json(&PreservedJson::from_json(json)?)
+    }
+}
+
+impl<T> ToPreservedJson for T
+where
+    T: Serialize + serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn to_preserved_json(&self) -> Result<PreservedJson, PreservedJsonError> {
+        let value = serde_json::to_value(self)?;
+        let original = self.to_cbor_bytes();
+        // the baseline must be what reading the JSON back gives, not self's canonical bytes
+        let canonical = serde_json::from_value::<T>(value.clone())?.to_canonical_cbor_bytes();
+        let encodings = match describe(&original, &canonical) {
+            Some(encodings) if apply(&canonical, &encodings).as_ref() == Some(&original) => {
+                encodings
+            }
+            _ => {
+                return Ok(PreservedJson {
+                    value,
+                    encodings: Vec::new(),
+                    cbor_hex: Some(hex::encode(original)),
+                })
+            }
+        };
+        Ok(PreservedJson {
+            value,
+            encodings,
+            cbor_hex: None,
+        })
+    }
+}
+
+impl<T> FromPreservedJson for T
+where
+    T: Deserialize + Serialize + serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn from_preserved_json(json: &PreservedJson) -> Result<Self, PreservedJsonError> {
+        if let Some(cbor_hex) = &json.cbor_hex {
+            let value = T::from_cbor_bytes(&hex::decode(cbor_hex)?)?;
+            return if serde_json::to_value(&value)? == json.value {
+                Ok(value)
+            } else {
+                Err(PreservedJsonError::EncodingMismatch)
+            };
+        }
+        let canonical = serde_json::from_value::<T>(json.value.clone())?.to_canonical_cbor_bytes();
+        let bytes =
+            apply(&canonical, &json.encodings).ok_or(PreservedJsonError::EncodingMismatch)?;
+        T::from_cbor_bytes(&bytes).map_err(Into::into)
+    }
+}
+
+/// Encoding details turning `canonical` into `original`, if they only differ in encoding
+fn describe(original: &[u8], canonical: &[u8]) -> Option<Vec<EncodingDetail>> {
+    let original = Node::parse(original)?;
+    let canonical = Node::parse(canonical)?;
+    let mut encodings = Vec::new();
+    diff(&original, &canonical, &mut 0, &mut encodings)?;
+    Some(encodings)
+}
+
+/// Re-encodes `canonical` using `encodings`
+fn apply(canonical: &[u8], encodings: &[EncodingDetail]) -> Option<Vec<u8>> {
+    let details = encodings
+        .iter()
+        .map(|detail| (detail.item, detail))
+        .collect::<BTreeMap<_, _>>();
+    if details.len() != encodings.len() {
+        return None;
+    }
+    let mut applied = 0;
+    let node = reencode(Node::parse(canonical)?, &details, &mut 0, &mut applied)?;
+    if applied != details.len() {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(canonical.len());
+    node.write(&mut bytes);
+    Some(bytes)
+}
+
+/// A decoded CBOR item keeping its encoding. `info` is the head's additional info (31 = indefinite).
+#[derive(Clone, Debug)]
+enum Node {
+    /// Unsigned/negative ints and simple values
+    Int {
+        major: u8,
+        info: u8,
+        arg: u64,
+    },
+    /// Floats, kept verbatim including the head
+    Float(Vec<u8>),
+    /// Byte/text strings. Definite length strings have exactly one chunk.
+    Str {
+        major: u8,
+        indefinite: bool,
+        chunks: Vec<(u8, Vec<u8>)>,
+    },
+    Array {
+        info: u8,
+        items: Vec<Node>,
+    },
+    Map {
+        info: u8,
+        entries: Vec<(Node, Node)>,
+    },
+    Tag {
+        info: u8,
+        tag: u64,
+        item: Box<Node>,
+    },
+}
+
+const INDEFINITE: u8 = 31;
+
+fn minimal_info(arg: u64) -> u8 {
+    match arg {
+        0..=23 => arg as u8,
+        24..=0xff => 24,
+        0x100..=0xffff => 25,
+        0x1_0000..=0xffff_ffff => 26,
+        _ => 27,
+    }
+}
+
+/// Additional info for an argument of `width` bytes, if `arg` fits
+fn width_info(width: Option<u8>, arg: u64) -> Option<u8> {
+    let (info, max) = match width {
+        None => return Some(minimal_info(arg)),
+        Some(1) => (24, 0xff),
+        Some(2) => (25, 0xffff),
+        Some(4) => (26, 0xffff_ffff),
+        Some(8) => (27, u64::MAX),
+        Some(_) => return None,
+    };
+    (arg <= max).then_some(info)
+}
+
+/// Argument width recorded for `info` if it is not the shortest encoding of `arg`
+fn info_width(info: u8, arg: u64) -> Option<u8> {
+    (info != minimal_info(arg) && (24..=27).contains(&info)).then(|| 1 << (info - 24))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: u64) -> Option<&[u8]> {
+        if len > (self.bytes.len() - self.pos) as u64 {
+            return None;
+        }
+        let taken = &self.bytes[self.pos..self.pos + len as usize];
+        self.pos += len as usize;
+        Some(taken)
+    }
+
+    /// (major, info, argument) where the argument is 0 for indefinite lengths
+    fn head(&mut self) -> Option<(u8, u8, u64)> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let arg = match info {
+            0..=23 => u64::from(info),
+            24..=27 => self
+                .take(1 << (info - 24))?
+                .iter()
+                .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)),
+            INDEFINITE if (2..=5).contains(&major) => 0,
+            _ => return None,
+        };
+        Some((major, info, arg))
+    }
+
+    fn at_break(&mut self) -> bool {
+        if self.bytes.get(self.pos) == Some(&0xff) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether another element follows in a collection of `len` (None = indefinite) with `read` elements read
+    fn more(&mut self, info: u8, len: u64, read: u64) -> bool {
+        if info == INDEFINITE {
+            !self.at_break()
+        } else {
+            read < len
+        }
+    }
+
+    fn item(&mut self, depth: usize) -> Option<Node> {
+        // well beyond any real chain data, just to keep recursion bounded
+        if depth > 1024 {
+            return None;
+        }
+        let start = self.pos;
+        let (major, info, arg) = self.head()?;
+        Some(match major {
+            0 | 1 => Node::Int { major, info, arg },
+            2 | 3 => {
+                let mut chunks = Vec::new();
+                if info == INDEFINITE {
+                    while !self.at_break() {
+                        let (chunk_major, chunk_info, len) = self.head()?;
+                        if chunk_major != major || chunk_info == INDEFINITE {
+                            return None;
+                        }
+                        chunks.push((chunk_info, self.take(len)?.to_vec()));
+                    }
+                } else {
+                    chunks.push((info, self.take(arg)?.to_vec()));
+                }
+                Node::Str {
+                    major,
+                    indefinite: info == INDEFINITE,
+                    chunks,
+                }
+            }
+            4 => {
+                let mut items = Vec::new();
+                while self.more(info, arg, items.len() as u64) {
+                    items.push(self.item(depth + 1)?);
+                }
+                Node::Array { info, items }
+            }
+            5 => {
+                let mut entries = Vec::new();
+                while self.more(info, arg, entries.len() as u64) {
+                    entries.push((self.item(depth + 1)?, self.item(depth + 1)?));
+                }
+                Node::Map { info, entries }
+            }
+            6 => Node::Tag {
+                info,
+                tag: arg,
+                item: Box::new(self.item(depth + 1)?),
+            },
+            _ if (25..=27).contains(&info) => Node::Float(self.bytes[start..self.pos].to_vec()),
+            _ => Node::Int { major, info, arg },
+        })
+    }
+}
+
+fn write_head(major: u8, info: u8, arg: u64, out: &mut Vec<u8>) {
+    out.push((major << 5) | info);
+    if (24..=27).contains(&info) {
+        let width = 1usize << (info - 24);
+        out.extend_from_slice(&arg.to_be_bytes()[8 - width..]);
+    }
+}
+
+impl Node {
+    /// Parses exactly one item spanning all of `bytes`
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let node = reader.item(0)?;
+        (reader.pos == bytes.len()).then_some(node)
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Int { major, info, arg } => write_head(*major, *info, *arg, out),
+            Self::Float(bytes) => out.extend_from_slice(bytes),
+            Self::Str {
+                major,
+                indefinite,
+                chunks,
+            } => {
+                if *indefinite {
+                    out.push((major << 5) | INDEFINITE);
+                }
+                for (info, chunk) in chunks {
+                    write_head(*major, *info, chunk.len() as u64, out);
+                    out.extend_from_slice(chunk);
+                }
+                if *indefinite {
+                    out.push(0xff);
+                }
+            }
+            Self::Array { info, items } => {
+                write_head(4, *info, items.len() as u64, out);
+                items.iter().for_each(|item| item.write(out));
+                if *info == INDEFINITE {
+                    out.push(0xff);
+                }
+            }
+            Self::Map { info, entries } => {
+                write_head(5, *info, entries.len() as u64, out);
+                for (key, value) in entries {
+                    key.write(out);
+                    value.write(out);
+                }
+                if *info == INDEFINITE {
+                    out.push(0xff);
+                }
+            }
+            Self::Tag { info, tag, item } => {
+                write_head(6, *info, *tag, out);
+                item.write(out);
+            }
+        }
+    }
+
+    fn string_data(chunks: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        chunks.iter().flat_map(|(_, chunk)| chunk.clone()).collect()
+    }
+
+    /// Equality ignoring how the items are encoded
+    fn same_value(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Int { major, arg, .. },
+                Self::Int {
+                    major: other_major,
+                    arg: other_arg,
+                    ..
+                },
+            ) => major == other_major && arg == other_arg,
+            (Self::Float(bytes), Self::Float(other_bytes)) => bytes == other_bytes,
+            (
+                Self::Str { major, chunks, .. },
+                Self::Str {
+                    major: other_major,
+                    chunks: other_chunks,
+                    ..
+                },
+            ) => {
+                major == other_major && Self::string_data(chunks) == Self::string_data(other_chunks)
+            }
+            (
+                Self::Array { items, .. },
+                Self::Array {
+                    items: other_items, ..
+                },
+            ) => {
+                items.len() == other_items.len()
+                    && items.iter().zip(other_items).all(|(a, b)| a.same_value(b))
+            }
+            (
+                Self::Map { entries, .. },
+                Self::Map {
+                    entries: other_entries,
+                    ..
+                },
+            ) => {
+                entries.len() == other_entries.len()
+                    && entries
+                        .iter()
+                        .zip(other_entries)
+                        .all(|((k1, v1), (k2, v2))| k1.same_value(k2) && v1.same_value(v2))
+            }
+            (
+                Self::Tag { tag, item, .. },
+                Self::Tag {
+                    tag: other_tag,
+                    item: other_item,
+                    ..
+                },
+            ) => tag == other_tag && item.same_value(other_item),
+            _ => false,
+        }
+    }
+}
+
+/// Records how `original` differs from `canonical`. None if they differ in more than encoding.
+fn diff(
+    original: &Node,
+    canonical: &Node,
+    index: &mut usize,
+    out: &mut Vec<EncodingDetail>,
+) -> Option<()> {
+    let mut detail = EncodingDetail {
+        item: *index,
+        ..EncodingDetail::default()
+    };
+    *index += 1;
+    let position = out.len();
+    match (original, canonical) {
+        (Node::Int { info, arg, .. }, Node::Int { .. }) => {
+            if !original.same_value(canonical) {
+                return None;
+            }
+            detail.width = info_width(*info, *arg);
+        }
+        (Node::Float(_), Node::Float(_)) => {
+            if !original.same_value(canonical) {
+                return None;
+            }
+        }
+        (
+            Node::Str {
+                indefinite, chunks, ..
+            },
+            Node::Str { .. },
+        ) => {
+            if !original.same_value(canonical) {
+                return None;
+            }
+            if *indefinite {
+                detail.indefinite = true;
+                for (info, chunk) in chunks {
+                    // non-minimal chunk sizes can't be described
+                    if info_width(*info, chunk.len() as u64).is_some() {
+                        return None;
+                    }
+                    detail.chunks.push(chunk.len() as u64);
+                }
+            } else {
+                detail.width = info_width(chunks[0].0, chunks[0].1.len() as u64);
+            }
+        }
+        (
+            Node::Array { info, items },
+            Node::Array {
+                items: canonical_items,
+                ..
+            },
+        ) => {
+            if items.len() != canonical_items.len() {
+                return None;
+            }
+            detail.indefinite = *info == INDEFINITE;
+            detail.width = info_width(*info, items.len() as u64);
+            out.push(detail.clone());
+            for (item, canonical_item) in items.iter().zip(canonical_items) {
+                diff(item, canonical_item, index, out)?;
+            }
+        }
+        (
+            Node::Map { info, entries },
+            Node::Map {
+                entries: canonical_entries,
+                ..
+            },
+        ) => {
+            if entries.len() != canonical_entries.len() {
+                return None;
+            }
+            detail.indefinite = *info == INDEFINITE;
+            detail.width = info_width(*info, entries.len() as u64);
+            let mut used = vec![false; canonical_entries.len()];
+            for (key, _) in entries {
+                let found =
+                    canonical_entries
+                        .iter()
+                        .enumerate()
+                        .position(|(i, (canonical_key, _))| {
+                            !used[i] && key.same_value(canonical_key)
+                        })?;
+                used[found] = true;
+                detail.order.push(found);
+            }
+            if detail
+                .order
+                .iter()
+                .enumerate()
+                .all(|(i, found)| i == *found)
+            {
+                detail.order.clear();
+            }
+            let order = detail.order.clone();
+            out.push(detail.clone());
+            for (i, (key, value)) in entries.iter().enumerate() {
+                let (canonical_key, canonical_value) =
+                    &canonical_entries[order.get(i).copied().unwrap_or(i)];
+                diff(key, canonical_key, index, out)?;
+                diff(value, canonical_value, index, out)?;
+            }
+        }
+        (
+            Node::Tag { info, tag, item },
+            Node::Tag {
+                tag: canonical_tag,
+                item: canonical_item,
+                ..
+            },
+        ) => {
+            if tag != canonical_tag {
+                return None;
+            }
+            detail.width = info_width(*info, *tag);
+            out.push(detail.clone());
+            diff(item, canonical_item, index, out)?;
+        }
+        _ => return None,
+    }
+    // collections were pushed before their children so details stay in item order
+    let needed = detail != EncodingDetail::default_for(detail.item);
+    if out.len() > position {
+        if !needed {
+            out.remove(position);
+        }
+    } else if needed {
+        out.push(detail);
+    }
+    Some(())
+}
+
+impl EncodingDetail {
+    fn default_for(item: usize) -> Self {
+        Self {
+            item,
+            ..Self::default()
+        }
+    }
+}
+
+/// Applies `details` to the canonically encoded `node`, counting in `applied` how many were used
+fn reencode(
+    node: Node,
+    details: &BTreeMap<usize, &EncodingDetail>,
+    index: &mut usize,
+    applied: &mut usize,
+) -> Option<Node> {
+    let detail = details.get(index).copied();
+    *index += 1;
+    if detail.is_some() {
+        *applied += 1;
+    }
+    let default = EncodingDetail::default();
+    let detail = detail.unwrap_or(&default);
+    let collection_info = |len: usize| {
+        if detail.indefinite {
+            (detail.width.is_none()).then_some(INDEFINITE)
+        } else {
+            width_info(detail.width, len as u64)
+        }
+    };
+    let is_collection = matches!(node, Node::Array { .. } | Node::Map { .. });
+    let is_string = matches!(node, Node::Str { .. });
+    if (!is_collection && !is_string && detail.indefinite)
+        || (!is_string && !detail.chunks.is_empty())
+        || (!matches!(node, Node::Map { .. }) && !detail.order.is_empty())
+    {
+        return None;
+    }
+    Some(match node {
+        Node::Int { major, arg, .. } => Node::Int {
+            major,
+            info: width_info(detail.width, arg)?,
+            arg,
+        },
+        Node::Float(bytes) => {
+            if detail.width.is_some() {
+                return None;
+            }
+            Node::Float(bytes)
+        }
+        Node::Str { major, chunks, .. } => {
+            let data = Node::string_data(&chunks);
+            if detail.indefinite {
+                if detail.width.is_some() || detail.chunks.iter().sum::<u64>() != data.len() as u64
+                {
+                    return None;
+                }
+                let mut rest = data.as_slice();
+                let chunks = detail
+                    .chunks
+                    .iter()
+                    .map(|len| {
+                        let (chunk, remaining) = rest.split_at(*len as usize);
+                        rest = remaining;
+                        (minimal_info(*len), chunk.to_vec())
+                    })
+                    .collect();
+                Node::Str {
+                    major,
+                    indefinite: true,
+                    chunks,
+                }
+            } else {
+                Node::Str {
+                    major,
+                    indefinite: false,
+                    chunks: vec![(width_info(detail.width, data.len() as u64)?, data)],
+                }
+            }
+        }
+        Node::Array { items, .. } => Node::Array {
+            info: collection_info(items.len())?,
+            items: items
+                .into_iter()
+                .map(|item| reencode(item, details, index, applied))
+                .collect::<Option<_>>()?,
+        },
+        Node::Map { entries, .. } => {
+            let info = collection_info(entries.len())?;
+            let entries = if detail.order.is_empty() {
+                entries
+            } else {
+                if detail.order.len() != entries.len() {
+                    return None;
+                }
+                let mut slots = entries.into_iter().map(Some).collect::<Vec<_>>();
+                detail
+                    .order
+                    .iter()
+                    .map(|i| slots.get_mut(*i).and_then(Option::take))
+                    .collect::<Option<Vec<_>>>()?
+            };
+            Node::Map {
+                info,
+                entries: entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Some((
+                            reencode(key, details, index, applied)?,
+                            reencode(value, details, index, applied)?,
+                        ))
+                    })
+                    .collect::<Option<_>>()?,
+            }
+        }
+        Node::Tag { tag, item, .. } => Node::Tag {
+            info: width_info(detail.width, tag)?,
+            tag,
+            item: Box::new(reencode(*item, details, index, applied)?),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(original: &str, canonical: &str) -> Vec<EncodingDetail> {
+        let original = hex::decode(original).unwrap();
+        let canonical = hex::decode(canonical).unwrap();
+        let encodings = describe(&original, &canonical).unwrap();
+        assert_eq!(apply(&canonical, &encodings).unwrap(), original);
+        encodings
+    }
+
+    #[test]
+    fn canonical_has_no_details() {
+        assert!(round_trip("a201820102024101", "a201820102024101").is_empty());
+    }
+
+    #[test]
+    fn encoding_details() {
+        // indefinite array holding a 2-byte wide 1 and a chunked byte string
+        let encodings = round_trip("9f1900015f41014102ffff", "8201420102");
+        assert_eq!(
+            encodings,
+            vec![
+                EncodingDetail {
+                    item: 0,
+                    indefinite: true,
+                    ..Default::default()
+                },
+                EncodingDetail {
+                    item: 1,
+                    width: Some(2),
+                    ..Default::default()
+                },
+                EncodingDetail {
+                    item: 2,
+                    indefinite: true,
+                    chunks: vec![1, 1],
+                    ..Default::default()
+                },
+            ]
+        );
+        // map keys out of order with a wide tag in the first entry
+        let encodings = round_trip("a202d80181000180", "a2018002c18100");
+        assert_eq!(encodings.len(), 2);
+        assert_eq!(encodings[0].order, vec![1, 0]);
+        assert_eq!(encodings[1].item, 2);
+        assert_eq!(encodings[1].width, Some(1));
+    }
+
+    #[test]
+    fn structural_differences() {
+        // different values or tags can't be described
+        assert!(describe(&hex::decode("01").unwrap(), &hex::decode("02").unwrap()).is_none());
+        assert!(describe(&hex::decode("c101").unwrap(), &hex::decode("01").unwrap()).is_none());
+        // details for a different structure don't apply
+        let encodings = round_trip("9f01ff", "8101");
+        assert!(apply(&hex::decode("01").unwrap(), &encodings).is_none());
+    }
+}
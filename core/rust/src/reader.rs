@@ -0,0 +1,236 @@
+//! Position tracking for deserialization errors.
+//!
+//! `DeserializeError`s are created deep inside the generated `Deserialize` impls which only see
+//! a `Deserializer<R>`. Wrapping the input in a `DecodeReader` makes its position (and the major
+//! type of the CBOR item there) available to `DeserializeError::new()`, `annotate()` and the
+//! `From` conversions, which record it. `Deserialize::from_cbor_bytes()` and friends do this
+//! already. Readers can be nested, in which case the innermost one is used.
+//!
+//! A `DecodeReader` also enforces `DecodeLimits` on what is read through it (see `crate::limits`).
+
+use crate::error::DeserializeError;
+use crate::limits::{current_limits, DecodeLimits, ReadLimits};
+use std::cell::{Cell, RefCell};
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::rc::Rc;
+
+#[derive(Clone, Copy)]
+struct Position {
+    offset: u64,
+    /// first byte at `offset` if the deserializer has looked at it
+    next_byte: Option<u8>,
+}
+
+thread_local! {
+    /// positions of the live DecodeReaders on this thread, innermost last. Readers only write to
+    /// their own entry so nothing here is touched while reading, only when errors are created.
+    static POSITIONS: RefCell<Vec<Rc<Cell<Position>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Offset of the innermost `DecodeReader` on this thread and the major type of the CBOR item
+/// there (if it has been read yet).
+pub fn current_position() -> Option<(u64, Option<cbor_event::Type>)> {
+    POSITIONS.with(|positions| {
+        positions.borrow().last().map(|position| {
+            let position = position.get();
+            (
+                position.offset,
+                position.next_byte.map(cbor_event::Type::from),
+            )
+        })
+    })
+}
+
+/// Reader that makes its position known to `DeserializeError`s (see module docs).
+/// Use as `Deserializer::from(DecodeReader::new(reader))`.
+///
+/// It is registered with the thread it was created on so it can't be sent to another one:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<cml_core::reader::DecodeReader<std::io::Cursor<Vec<u8>>>>();
+/// ```
+pub struct DecodeReader<R> {
+    inner: R,
+    /// shared with POSITIONS
+    position: Rc<Cell<Position>>,
+    limits: Option<ReadLimits>,
+}
+
+impl<R: Seek> DecodeReader<R> {
+    /// Applies the limits of the enclosing `crate::limits::with_limits()` scope if there is one
+    pub fn new(inner: R) -> Self {
+        Self::with_optional_limits(inner, current_limits())
+    }
+
+    /// Ignores any enclosing `crate::limits::with_limits()` scope e.g. for input that was
+    /// already checked as a whole with `crate::limits::check_limits()`
+    pub(crate) fn unlimited(inner: R) -> Self {
+        Self::with_optional_limits(inner, None)
+    }
+
+    fn with_optional_limits(mut inner: R, limits: Option<DecodeLimits>) -> Self {
+        let offset = inner.stream_position().unwrap_or(0);
+        let position = Rc::new(Cell::new(Position {
+            offset,
+            next_byte: None,
+        }));
+        POSITIONS.with(|positions| positions.borrow_mut().push(position.clone()));
+        Self {
+            inner,
+            position,
+            limits: limits.map(|limits| ReadLimits::new(limits, offset)),
+        }
+    }
+}
+
+impl<R> DecodeReader<R> {
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Current offset into the input
+    pub fn position(&self) -> u64 {
+        self.position.get().offset
+    }
+
+    /// Fails reading anything from here on that exceeds `limits`
+    pub fn with_limits(mut self, limits: &DecodeLimits) -> Self {
+        self.limits = Some(ReadLimits::new(*limits, self.position()));
+        self
+    }
+
+    /// The limit that was exceeded if reading failed because of one. As the deserializer can
+    /// turn it into other failures (e.g. NoVariantMatched) this is the more precise error.
+    pub fn limit_error(&self) -> Option<DeserializeError> {
+        self.limits
+            .as_ref()
+            .and_then(ReadLimits::failed)
+            .map(DeserializeError::from_limit_error)
+    }
+
+    fn set_offset(&self, offset: u64) {
+        self.position.set(Position {
+            offset,
+            next_byte: None,
+        });
+    }
+}
+
+impl<R> Drop for DecodeReader<R> {
+    fn drop(&mut self) {
+        // readers aren't necessarily dropped in the order they were created
+        POSITIONS.with(|positions| {
+            let mut positions = positions.borrow_mut();
+            if let Some(index) = positions
+                .iter()
+                .rposition(|position| Rc::ptr_eq(position, &self.position))
+            {
+                positions.remove(index);
+            }
+        });
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let offset = self.position();
+        if let Some(limits) = &mut self.limits {
+            limits.check(offset, &buf[..read])?;
+        }
+        self.set_offset(offset + read as u64);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodeReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        let offset = self.position();
+        let buf = self.inner.fill_buf()?;
+        if let Some(limits) = &mut self.limits {
+            limits.check(offset, buf)?;
+        }
+        self.position.set(Position {
+            offset,
+            next_byte: buf.first().copied(),
+        });
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.set_offset(self.position() + amt as u64);
+    }
+}
+
+impl<R: Seek> Seek for DecodeReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let offset = self.inner.seek(pos)?;
+        self.set_offset(offset);
+        Ok(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_readers() {
+        assert_eq!(current_position(), None);
+        let mut outer = DecodeReader::new(std::io::Cursor::new([0x82u8, 0x01, 0x02]));
+        outer.consume(1);
+        assert_eq!(current_position(), Some((1, None)));
+        {
+            let mut inner = DecodeReader::new(std::io::Cursor::new([0x41u8, 0x00]));
+            inner.fill_buf().unwrap();
+            assert_eq!(current_position(), Some((0, Some(cbor_event::Type::Bytes))));
+        }
+        outer.fill_buf().unwrap();
+        assert_eq!(
+            current_position(),
+            Some((1, Some(cbor_event::Type::UnsignedInteger)))
+        );
+        drop(outer);
+        assert_eq!(current_position(), None);
+    }
+
+    #[test]
+    fn unlimited_without_scope() {
+        let mut reader = DecodeReader::new(std::io::Cursor::new(vec![0x81; 1000]));
+        assert!(reader.limits.is_none());
+        assert_eq!(reader.fill_buf().unwrap().len(), 1000);
+        let limited = crate::limits::with_limits(&DecodeLimits::default(), || {
+            DecodeReader::new(std::io::Cursor::new([0x00]))
+        });
+        assert!(limited.limits.is_some());
+    }
+
+    #[test]
+    fn readers_dropped_out_of_order() {
+        let reader = |bytes: &'static [u8], consumed| {
+            let mut reader = DecodeReader::new(std::io::Cursor::new(bytes));
+            reader.consume(consumed);
+            reader
+        };
+        let first = reader(&[0x01, 0x02, 0x03], 1);
+        let second = reader(&[0x01, 0x02, 0x03], 2);
+        let third = reader(&[0x01, 0x02, 0x03], 3);
+        drop(second);
+        assert_eq!(current_position(), Some((3, None)));
+        drop(third);
+        assert_eq!(current_position(), Some((1, None)));
+        // a new reader doesn't take over the position of the one that is still alive
+        let mut fourth = reader(&[0x01, 0x02], 0);
+        drop(first);
+        fourth.consume(2);
+        assert_eq!(current_position(), Some((2, None)));
+        drop(fourth);
+        assert_eq!(current_position(), None);
+    }
+}
@@ -1,10 +1,16 @@
+use crate::canonical::{
+    collect_duplicates_allowed, strict_violations, NonCanonical, NonCanonicalKind,
+};
+use crate::diagnostic::to_diagnostic;
 use crate::error::{DeserializeError, DeserializeFailure};
+use crate::limits::{check_limits, current_limits, with_limits, DecodeLimits};
+use crate::reader::DecodeReader;
 use cbor_event::{de::Deserializer, se::Serializer, Sz};
 use std::io::{BufRead, Seek, Write};
 
 pub struct CBORReadLen {
     deser_len: cbor_event::LenSz,
-    read: u64,
+    pub(crate) read: u64,
 }
 
 impl CBORReadLen {
@@ -226,6 +232,12 @@ pub trait Serialize {
         self.serialize(&mut buf, true).unwrap();
         buf.finalize()
     }
+
+    /// CBOR diagnostic notation (RFC 8949 section 8) of to_cbor_bytes(). See `crate::diagnostic`
+    /// for how encoding details are shown and `CBORFieldNames` for annotating field names.
+    fn to_cbor_diagnostic(&self) -> String {
+        to_diagnostic(&self.to_cbor_bytes()).expect("serialization always produces valid CBOR")
+    }
 }
 
 pub trait SerializeEmbeddedGroup {
@@ -247,9 +259,79 @@ pub trait Deserialize {
     where
         Self: Sized,
     {
-        let mut raw = Deserializer::from(std::io::Cursor::new(data));
+        let mut raw = Deserializer::from(checked_reader(data)?);
         Self::deserialize(&mut raw)
     }
+
+    /// Like from_cbor_bytes but fails on input over `limits` (see `crate::limits`) so that
+    /// untrusted input can't exhaust the stack or memory while being decoded.
+    fn from_cbor_bytes_with_limits(
+        data: &[u8],
+        limits: &DecodeLimits,
+    ) -> Result<Self, DeserializeError>
+    where
+        Self: Sized,
+    {
+        with_limits(limits, || Self::from_cbor_bytes(data))
+    }
+
+    /// Like from_cbor_bytes but map-encoded types keep any fields, or array elements of
+    /// known fields, that they don't understand instead of failing e.g. for CBOR from a
+    /// future era. They are available via `unknown_fields()` (see `crate::unknown_fields`).
+    /// Array elements kept this way are missing from their field so the indices of the
+    /// elements after them shift e.g. a transaction's outputs.
+    fn from_cbor_bytes_lenient(data: &[u8]) -> Result<Self, DeserializeError>
+    where
+        Self: Sized,
+    {
+        crate::unknown_fields::with_lenient(|| Self::from_cbor_bytes(data))
+    }
+
+    /// Like from_cbor_bytes but rejects anything that isn't canonical CBOR (see `crate::canonical`),
+    /// has bytes after the item or doesn't re-encode to the exact input. Duplicate map keys are
+    /// rejected except in maps the ledger allows them in (Plutus data and metadatum maps).
+    fn from_cbor_bytes_strict(data: &[u8]) -> Result<Self, DeserializeError>
+    where
+        Self: Sized + Serialize,
+    {
+        let mut raw = Deserializer::from(checked_reader(data)?);
+        let (value, duplicates_allowed) =
+            collect_duplicates_allowed(|| Self::deserialize(&mut raw));
+        let value = value?;
+        let read = raw.as_ref().position() as usize;
+        let mut violations = strict_violations(
+            &data[..read],
+            &value.to_cbor_bytes(),
+            &value.to_canonical_cbor_bytes(),
+            &duplicates_allowed,
+        );
+        if read < data.len() {
+            violations.push(NonCanonical {
+                offset: read as u64,
+                kind: NonCanonicalKind::TrailingBytes,
+            });
+        }
+        if violations.is_empty() {
+            return Ok(value);
+        }
+        let offset = violations[0].offset;
+        Err(
+            DeserializeError::from(DeserializeFailure::NonCanonical(violations))
+                .at_offset(data, offset),
+        )
+    }
+}
+
+/// Reader for deserializing `data` once it passed the limits of the enclosing
+/// `crate::limits::with_limits()` scope if there is one. As all of the input is at hand it is
+/// checked up front instead of while it is being read.
+fn checked_reader(data: &[u8]) -> Result<DecodeReader<std::io::Cursor<&[u8]>>, DeserializeError> {
+    if let Some(limits) = current_limits() {
+        if let Err((offset, failure)) = check_limits(data, &limits) {
+            return Err(DeserializeError::from(failure).at_offset(data, offset as u64));
+        }
+    }
+    Ok(DecodeReader::unlimited(std::io::Cursor::new(data)))
 }
 
 // TODO: remove ToBytes / FromBytes after we regenerate the WASM wrappers.
@@ -284,7 +366,7 @@ impl<T: Deserialize> FromBytes for T {
     where
         Self: Sized,
     {
-        let mut raw = Deserializer::from(std::io::Cursor::new(data));
+        let mut raw = Deserializer::from(DecodeReader::new(std::io::Cursor::new(data)));
         Self::deserialize(&mut raw).map_err(Into::into)
     }
 }
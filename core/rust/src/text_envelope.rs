@@ -0,0 +1,169 @@
+use crate::error::DeserializeError;
+use crate::serialization::{Deserialize, Serialize};
+
+/// The `{"type", "description", "cborHex"}` JSON format used by cardano-cli
+/// for `.skey`/`.vkey`/`.tx`/etc files.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextEnvelope {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub description: String,
+    #[serde(rename = "cborHex")]
+    pub cbor_hex: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TextEnvelopeError {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Unexpected TextEnvelope type {found}, expected one of {expected:?}")]
+    UnexpectedType {
+        found: String,
+        expected: Vec<String>,
+    },
+    #[error("Deserialization: {0}")]
+    Deserialization(#[from] DeserializeError),
+}
+
+impl TextEnvelope {
+    pub fn new(type_name: String, description: String, cbor_bytes: &[u8]) -> Self {
+        Self {
+            type_name,
+            description,
+            cbor_hex: hex::encode(cbor_bytes),
+        }
+    }
+
+    /// The raw CBOR bytes contained in this envelope.
+    pub fn cbor_bytes(&self) -> Result<Vec<u8>, TextEnvelopeError> {
+        hex::decode(&self.cbor_hex).map_err(Into::into)
+    }
+
+    /// Pretty-printed JSON in the same layout that cardano-cli writes out.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, TextEnvelopeError> {
+        serde_json::from_str(json).map_err(Into::into)
+    }
+
+    /// Errors with TextEnvelopeError::UnexpectedType if this envelope's type is not in `expected`
+    pub fn check_type(&self, expected: Vec<String>) -> Result<(), TextEnvelopeError> {
+        if expected.contains(&self.type_name) {
+            Ok(())
+        } else {
+            Err(TextEnvelopeError::UnexpectedType {
+                found: self.type_name.clone(),
+                expected,
+            })
+        }
+    }
+}
+
+/// cardano-cli TextEnvelope type information for a type whose `cborHex` is exactly
+/// its CBOR encoding as given by `Serialize`/`Deserialize`.
+/// Implementing this provides `ToTextEnvelope` and `FromTextEnvelope`.
+pub trait TextEnvelopeType {
+    /// The type written out for this value
+    fn text_envelope_type(&self) -> String;
+
+    /// The description written out for this value
+    fn text_envelope_description(&self) -> String {
+        String::new()
+    }
+
+    /// All envelope types that can be read as Self.
+    /// Often several types are accepted e.g. older eras or names used by older cardano-cli versions.
+    fn accepted_text_envelope_types() -> Vec<String>;
+
+    /// Whether an envelope of this type can be read as Self.
+    fn accepts_text_envelope_type(type_name: &str) -> bool {
+        Self::accepted_text_envelope_types()
+            .iter()
+            .any(|accepted| accepted == type_name)
+    }
+}
+
+pub trait ToTextEnvelope {
+    fn to_text_envelope(&self) -> TextEnvelope;
+
+    fn to_text_envelope_json(&self) -> String {
+        self.to_text_envelope().to_json()
+    }
+}
+
+pub trait FromTextEnvelope {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError>
+    where
+        Self: Sized;
+
+    fn from_text_envelope_json(json: &str) -> Result<Self, TextEnvelopeError>
+    where
+        Self: Sized,
+    {
+        Self::from_text_envelope(&TextEnvelope::from_json(json)?)
+    }
+}
+
+impl<T: TextEnvelopeType + Serialize> ToTextEnvelope for T {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        TextEnvelope::new(
+            self.text_envelope_type(),
+            self.text_envelope_description(),
+            &self.to_cbor_bytes(),
+        )
+    }
+}
+
+impl<T: TextEnvelopeType + Deserialize> FromTextEnvelope for T {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(T::accepted_text_envelope_types())?;
+        T::from_cbor_bytes(&envelope.cbor_bytes()?).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Int;
+
+    #[test]
+    fn cardano_cli_skey_json() {
+        let json = r#"{
+            "type": "PaymentSigningKeyShelley_ed25519",
+            "description": "Payment Signing Key",
+            "cborHex": "5820d4ffbd2fdb9a4bd7b3da0d6d24a7c2ae14ac06b9d1ee1bf5d7d0aa6af6b0b0d1"
+        }"#;
+        let envelope = TextEnvelope::from_json(json).unwrap();
+        assert_eq!(envelope.type_name, "PaymentSigningKeyShelley_ed25519");
+        assert_eq!(envelope.cbor_bytes().unwrap().len(), 34);
+        assert_eq!(
+            TextEnvelope::from_json(&envelope.to_json()).unwrap(),
+            envelope
+        );
+    }
+
+    impl TextEnvelopeType for Int {
+        fn text_envelope_type(&self) -> String {
+            "TestInt".to_owned()
+        }
+
+        fn accepted_text_envelope_types() -> Vec<String> {
+            vec!["TestInt".to_owned()]
+        }
+    }
+
+    #[test]
+    fn serializable_round_trip() {
+        let x = Int::new_nint(1000);
+        let envelope = x.to_text_envelope();
+        assert_eq!(envelope.cbor_hex, "3903e8");
+        assert_eq!(Int::from_text_envelope(&envelope).unwrap(), x);
+        let mut wrong_type = envelope;
+        wrong_type.type_name = "Tx ConwayEra".to_owned();
+        assert!(Int::from_text_envelope(&wrong_type).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Opt-in tolerance for map fields CML doesn't know about yet, so that CBOR from a newer era
+//! can still be inspected (and re-signed / re-submitted) with an older version of CML.
+//!
+//! By default map-encoded types (transaction bodies, witness sets, protocol param updates, etc)
+//! reject unknown keys. When decoding via `Deserialize::from_cbor_bytes_lenient()` they instead
+//! keep them as `CborValue`s in the `UnknownFields` of their encoding details, accessible via
+//! `unknown_fields()`. Array elements of a known field that didn't match any of the variants we
+//! know of (e.g. a new certificate type in the certs) are kept there as unknown items, while the
+//! other elements are deserialized as usual. Any other value of a known field that can't be
+//! represented by the field's type is kept as an unknown field. These are all written back in
+//! their original position on serialization so hashes (e.g. the tx id) are unchanged.
+//!
+//! As unknown items are left out of the field they were in, the elements after them move down
+//! e.g. with an unknown output before it, the output at index 1 of the transaction (the one
+//! `TransactionInput { index: 1, .. }` spends) is `outputs[0]` of the deserialized body. Use
+//! `UnknownItem::index` to map between the two.
+
+use crate::cbor_value::CborValue;
+use crate::error::{DeserializeError, DeserializeFailure, Key};
+use crate::serialization::{CBORReadLen, Deserialize, LenEncoding, Serialize};
+use crate::Int;
+
+use cbor_event::{de::Deserializer, se::Serializer, Sz};
+use std::cell::{Cell, RefCell};
+use std::io::{BufRead, Seek, SeekFrom, Write};
+
+thread_local! {
+    static LENIENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether unknown fields are currently being kept instead of rejected during deserialization.
+pub fn is_lenient() -> bool {
+    LENIENT.with(|lenient| lenient.get())
+}
+
+/// Runs `f` with lenient deserialization enabled (see module docs) for the current thread.
+pub fn with_lenient<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LENIENT.with(|lenient| lenient.set(self.0));
+        }
+    }
+    let _restore = Restore(LENIENT.with(|lenient| lenient.replace(true)));
+    f()
+}
+
+/// where the value of the array field being deserialized starts and its unknown items
+type PendingItems = Option<(u64, Vec<(usize, CborValue)>)>;
+
+thread_local! {
+    /// unknown items of the array field being deserialized
+    static ITEMS: RefCell<PendingItems> = const { RefCell::new(None) };
+}
+
+/// Implements `unknown_fields()` and `unknown_fields_mut()` for map-encoded structs keeping
+/// their `UnknownFields` in their `*Encoding` struct, boxed as they're almost always empty
+#[macro_export]
+macro_rules! impl_unknown_fields {
+    ($($name:ty),+ $(,)?) => {$(
+        impl $name {
+            /// Fields and items kept by lenient deserialization (see `cml_core::unknown_fields`)
+            pub fn unknown_fields(&self) -> &$crate::UnknownFields {
+                self.encodings
+                    .as_ref()
+                    .and_then(|encs| encs.unknown_fields.as_deref())
+                    .unwrap_or($crate::UnknownFields::none())
+            }
+
+            pub fn unknown_fields_mut(&mut self) -> &mut $crate::UnknownFields {
+                self.encodings
+                    .get_or_insert_with(Default::default)
+                    .unknown_fields
+                    .get_or_insert_with(Default::default)
+            }
+        }
+    )+};
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownField {
+    pub key: CborValue,
+    pub value: CborValue,
+    /// How many of the struct's known fields came before this one in the original map
+    pub position: usize,
+}
+
+/// An array element of a known field that isn't of any variant we know of (e.g. a new certificate
+/// type). The other elements are kept in the field as usual.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownItem {
+    /// key of the field the array is in
+    pub key: CborValue,
+    /// index in the original array
+    pub index: usize,
+    pub value: CborValue,
+}
+
+/// Map entries and array elements that a struct did not know how to represent. Only ever
+/// non-empty when deserialized using lenient mode (see module docs) or when added by hand.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UnknownFields {
+    fields: Vec<UnknownField>,
+    items: Vec<UnknownItem>,
+}
+
+static NONE: UnknownFields = UnknownFields {
+    fields: Vec::new(),
+    items: Vec::new(),
+};
+
+impl UnknownFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty bag for types without encoding details to keep one in
+    pub fn none() -> &'static Self {
+        &NONE
+    }
+
+    /// Number of unknown fields (not counting items)
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, UnknownField> {
+        self.fields.iter()
+    }
+
+    pub fn get(&self, key: &CborValue) -> Option<&CborValue> {
+        self.fields
+            .iter()
+            .find(|field| field.key == *key)
+            .map(|field| &field.value)
+    }
+
+    /// Adds a field to be serialized after all the known ones
+    pub fn push(&mut self, key: CborValue, value: CborValue) {
+        self.fields.push(UnknownField {
+            key,
+            value,
+            position: usize::MAX,
+        });
+    }
+
+    pub fn items(&self) -> &[UnknownItem] {
+        &self.items
+    }
+
+    /// Adds an element to be written at `index` of the array in the field `key`
+    pub fn push_item(&mut self, key: CborValue, index: usize, value: CborValue) {
+        self.items.push(UnknownItem { key, index, value });
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+        self.items.clear();
+    }
+
+    /// For keeping in encoding details, None when there are no fields or items
+    pub fn into_boxed(self) -> Option<Box<Self>> {
+        if self.fields.is_empty() && self.items.is_empty() {
+            None
+        } else {
+            Some(Box::new(self))
+        }
+    }
+
+    /// Handles a uint or text key the struct doesn't know. This is an UnknownKey error
+    /// unless in lenient mode in which case the value is read and kept.
+    pub fn deserialize_unknown<R: BufRead + Seek>(
+        &mut self,
+        raw: &mut Deserializer<R>,
+        read_len: &mut CBORReadLen,
+        position: usize,
+        key: CborValue,
+    ) -> Result<(), DeserializeError> {
+        if !is_lenient() {
+            let key = match key {
+                CborValue::Int(Int::Uint { value, .. }) => Key::Uint(value),
+                CborValue::Text { text, .. } => Key::Str(text),
+                // only uint/text keys are passed in here
+                other => Key::Str(format!("{other:?}")),
+            };
+            return Err(DeserializeFailure::UnknownKey(key).into());
+        }
+        read_len.read_elems(1)?;
+        let value = CborValue::deserialize(raw)?;
+        self.fields.push(UnknownField {
+            key,
+            value,
+            position,
+        });
+        Ok(())
+    }
+
+    /// Deserializes the value of the known key `key` using `field`. In lenient mode elements of
+    /// an array value (read with `deserialize_array()`) of a variant we don't know about are kept
+    /// as unknown items. If the value itself is of such a variant it's kept as an unknown field
+    /// instead and `field` is treated as if it was never present.
+    pub fn deserialize_known<R: BufRead + Seek, F>(
+        &mut self,
+        raw: &mut Deserializer<R>,
+        read_len: &mut CBORReadLen,
+        position: usize,
+        key: u64,
+        key_encoding: Sz,
+        field: F,
+    ) -> Result<(), DeserializeError>
+    where
+        F: FnOnce(&mut Deserializer<R>, &mut CBORReadLen) -> Result<(), DeserializeError>,
+    {
+        if !is_lenient() {
+            return field(raw, read_len);
+        }
+        let start = raw
+            .as_mut_ref()
+            .stream_position()
+            .map_err(cbor_event::Error::IoError)?;
+        let read_before = read_len.read;
+        let key = CborValue::Int(Int::Uint {
+            value: key,
+            encoding: Some(key_encoding),
+        });
+        let (result, items) = collect_items(start, || field(raw, read_len));
+        match result {
+            Ok(()) => {
+                self.items
+                    .extend(items.into_iter().map(|(index, value)| UnknownItem {
+                        key: key.clone(),
+                        index,
+                        value,
+                    }));
+                Ok(())
+            }
+            Err(e) if is_unknown_variant(e.failure()) => {
+                raw.as_mut_ref()
+                    .seek(SeekFrom::Start(start))
+                    .map_err(cbor_event::Error::IoError)?;
+                read_len.read = read_before;
+                read_len.read_elems(1)?;
+                let value = CborValue::deserialize(raw)?;
+                self.fields.push(UnknownField {
+                    key,
+                    value,
+                    position,
+                });
+                Ok(())
+            }
+            res => res,
+        }
+    }
+
+    /// Writes the unknown fields that originally came right before the known field at
+    /// `position` i.e. call this before each known field written.
+    /// Nothing is written here when `force_canonical` as those all go at the end.
+    pub fn serialize_before<'se, W: Write>(
+        &self,
+        position: usize,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        if !force_canonical {
+            for field in self
+                .fields
+                .iter()
+                .filter(|field| field.position == position)
+            {
+                field.key.serialize(serializer, force_canonical)?;
+                field.value.serialize(serializer, force_canonical)?;
+            }
+        }
+        Ok(serializer)
+    }
+
+    /// Writes all unknown fields not yet written by `serialize_before()` after the `known_len`
+    /// known fields of the struct. When `force_canonical` this is all of them, which
+    /// `patched_cbor()` then sorts in among the known ones.
+    pub fn serialize_after<'se, W: Write>(
+        &self,
+        known_len: usize,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let mut fields = self
+            .fields
+            .iter()
+            .filter(|field| force_canonical || field.position >= known_len)
+            .collect::<Vec<_>>();
+        // stable so fields added by hand stay in insertion order
+        fields.sort_by_key(|field| field.position);
+        for field in fields {
+            field.key.serialize(serializer, force_canonical)?;
+            field.value.serialize(serializer, force_canonical)?;
+        }
+        Ok(serializer)
+    }
+
+    /// The CBOR of the map-encoded struct these belong to, when the regular serialization
+    /// (written by `unpatched`, which must not call this again) isn't enough: with the unknown
+    /// items put back into their arrays and in canonical mode with the unknown fields sorted in
+    /// among the known ones by key. None if there is nothing to do, in which case the struct
+    /// should serialize as usual.
+    pub fn patched_cbor(
+        &self,
+        force_canonical: bool,
+        unpatched: impl FnOnce(&mut Serializer<Vec<u8>>) -> cbor_event::Result<()>,
+    ) -> Option<Vec<u8>> {
+        if self.items.is_empty() && (!force_canonical || self.fields.is_empty()) {
+            return None;
+        }
+        let mut serializer = Serializer::new_vec();
+        unpatched(&mut serializer).expect("serializing to a Vec can't fail");
+        let bytes = serializer.finalize();
+        let mut cbor =
+            CborValue::from_cbor_bytes(&bytes).expect("serialization always produces valid CBOR");
+        if let CborValue::Map { entries, .. } = &mut cbor {
+            let mut items = self.items.iter().collect::<Vec<_>>();
+            items.sort_by_key(|item| item.index);
+            for item in items {
+                match entries.iter_mut().find(|(key, _)| *key == item.key) {
+                    Some((_, CborValue::Array { elements, .. })) => {
+                        elements.insert(item.index.min(elements.len()), item.value.clone());
+                    }
+                    Some(_) => (),
+                    // the field was removed so the item gets a new one
+                    None => entries.push((
+                        item.key.clone(),
+                        CborValue::new_array(vec![item.value.clone()]),
+                    )),
+                }
+            }
+        }
+        Some(if force_canonical {
+            cbor.to_canonical_cbor_bytes()
+        } else {
+            cbor.to_cbor_bytes()
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a UnknownFields {
+    type Item = &'a UnknownField;
+    type IntoIter = std::slice::Iter<'a, UnknownField>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+/// Deserializes an array using `element` for each element. In lenient mode when this is the
+/// value of a field read by `UnknownFields::deserialize_known()`, elements of a variant we don't
+/// know about are kept as unknown items of that field instead of failing.
+pub fn deserialize_array<R: BufRead + Seek, T>(
+    raw: &mut Deserializer<R>,
+    mut element: impl FnMut(&mut Deserializer<R>) -> Result<T, DeserializeError>,
+) -> Result<(Vec<T>, LenEncoding), DeserializeError> {
+    let start = raw
+        .as_mut_ref()
+        .stream_position()
+        .map_err(cbor_event::Error::IoError)?;
+    // only the field's value itself and not any arrays nested in its elements
+    let mut items = ITEMS.with(|items| {
+        let mut items = items.borrow_mut();
+        match &*items {
+            Some((field_start, _)) if *field_start == start => items.take(),
+            _ => None,
+        }
+    });
+    let result = (|| {
+        let mut elements = Vec::new();
+        let len = raw.array_sz()?;
+        let mut read = 0;
+        while match len {
+            cbor_event::LenSz::Len(n, _) => read < n,
+            cbor_event::LenSz::Indefinite => true,
+        } {
+            if raw.cbor_type()? == cbor_event::Type::Special {
+                assert_eq!(raw.special()?, cbor_event::Special::Break);
+                break;
+            }
+            match &mut items {
+                Some((_, items)) => {
+                    let element_start = raw
+                        .as_mut_ref()
+                        .stream_position()
+                        .map_err(cbor_event::Error::IoError)?;
+                    match element(raw) {
+                        Ok(element) => elements.push(element),
+                        Err(e) if is_unknown_variant(e.failure()) => {
+                            raw.as_mut_ref()
+                                .seek(SeekFrom::Start(element_start))
+                                .map_err(cbor_event::Error::IoError)?;
+                            items.push((read as usize, CborValue::deserialize(raw)?));
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => elements.push(element(raw)?),
+            }
+            read += 1;
+        }
+        Ok((elements, len.into()))
+    })();
+    if items.is_some() {
+        ITEMS.with(|outer| *outer.borrow_mut() = items);
+    }
+    result
+}
+
+/// Runs `f` keeping the unknown items of an array starting at `start`
+fn collect_items<T>(start: u64, f: impl FnOnce() -> T) -> (T, Vec<(usize, CborValue)>) {
+    struct Restore(Option<(u64, Vec<(usize, CborValue)>)>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ITEMS.with(|items| *items.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(ITEMS.with(|items| items.replace(Some((start, Vec::new())))));
+    let result = f();
+    let items = ITEMS.with(|items| items.borrow_mut().take());
+    (result, items.map(|(_, items)| items).unwrap_or_default())
+}
+
+fn is_unknown_variant(failure: &DeserializeFailure) -> bool {
+    matches!(
+        failure,
+        DeserializeFailure::NoVariantMatched
+            | DeserializeFailure::NoVariantMatchedWithCauses(_)
+            | DeserializeFailure::TagMismatch { .. }
+            | DeserializeFailure::FixedValueMismatch { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lenient_reset() {
+        assert!(!is_lenient());
+        assert!(CborValue::from_cbor_bytes_lenient(&[0x82, 0x01]).is_err());
+        assert!(!is_lenient());
+        let panicked = std::panic::catch_unwind(|| {
+            with_lenient(|| {
+                assert!(is_lenient());
+                panic!("deserialization bug");
+            })
+        });
+        assert!(panicked.is_err());
+        assert!(!is_lenient());
+        // nested scopes restore the outer one
+        with_lenient(|| {
+            with_lenient(|| ());
+            assert!(is_lenient());
+        });
+        assert!(!is_lenient());
+    }
+}
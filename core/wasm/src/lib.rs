@@ -1,4 +1,4 @@
-use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
 
 use cml_core::serialization::{Deserialize, Serialize};
 
@@ -76,3 +76,211 @@ impl AsRef<cml_core::Int> for Int {
         &self.0
     }
 }
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CborValueKind {
+    Int,
+    Bytes,
+    Text,
+    Array,
+    Map,
+    Tag,
+    Bool,
+    Null,
+    Undefined,
+    Simple,
+    Float,
+}
+
+/// Any CBOR item, remembering how it was encoded. See cml_core::cbor_value
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct CborValue(cml_core::CborValue);
+
+impl_wasm_conversions!(cml_core::CborValue, CborValue);
+
+impl_wasm_cbor_api!(CborValue);
+
+#[wasm_bindgen]
+impl CborValue {
+    pub fn new_int(int: &Int) -> Self {
+        Self(cml_core::CborValue::new_int(int.clone().into()))
+    }
+
+    pub fn new_bytes(bytes: Vec<u8>) -> Self {
+        Self(cml_core::CborValue::new_bytes(bytes))
+    }
+
+    pub fn new_text(text: String) -> Self {
+        Self(cml_core::CborValue::new_text(text))
+    }
+
+    pub fn new_tag(tag: u64, value: &CborValue) -> Self {
+        Self(cml_core::CborValue::new_tag(tag, value.0.clone()))
+    }
+
+    pub fn new_bool(b: bool) -> Self {
+        Self(cml_core::CborValue::Bool(b))
+    }
+
+    pub fn new_null() -> Self {
+        Self(cml_core::CborValue::Null)
+    }
+
+    pub fn new_float(value: f64) -> Self {
+        Self(cml_core::CborValue::new_float(value))
+    }
+
+    pub fn kind(&self) -> CborValueKind {
+        match &self.0 {
+            cml_core::CborValue::Int(_) => CborValueKind::Int,
+            cml_core::CborValue::Bytes { .. } => CborValueKind::Bytes,
+            cml_core::CborValue::Text { .. } => CborValueKind::Text,
+            cml_core::CborValue::Array { .. } => CborValueKind::Array,
+            cml_core::CborValue::Map { .. } => CborValueKind::Map,
+            cml_core::CborValue::Tag { .. } => CborValueKind::Tag,
+            cml_core::CborValue::Bool(_) => CborValueKind::Bool,
+            cml_core::CborValue::Null => CborValueKind::Null,
+            cml_core::CborValue::Undefined => CborValueKind::Undefined,
+            cml_core::CborValue::Simple(_) => CborValueKind::Simple,
+            cml_core::CborValue::Float { .. } => CborValueKind::Float,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<Int> {
+        self.0.as_int().cloned().map(Into::into)
+    }
+
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        self.0.as_bytes().map(ToOwned::to_owned)
+    }
+
+    pub fn as_text(&self) -> Option<String> {
+        self.0.as_text().map(ToOwned::to_owned)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        self.0.as_bool()
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        self.0.as_float()
+    }
+
+    /// The tag number if this is a tagged item
+    pub fn tag(&self) -> Option<u64> {
+        self.0.tag()
+    }
+
+    /// The item inside any (possibly nested) tags
+    pub fn untagged(&self) -> CborValue {
+        self.0.untagged().clone().into()
+    }
+
+    /// Number of elements of an array or entries of a map
+    pub fn len(&self) -> Option<usize> {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> Option<bool> {
+        self.0.is_empty()
+    }
+
+    /// Element of an array (stepping through any tags)
+    pub fn get_index(&self, index: usize) -> Option<CborValue> {
+        self.0
+            .get(&cml_core::CborPathSegment::Index(index))
+            .cloned()
+            .map(Into::into)
+    }
+
+    /// Value of the first entry with this key of a map (stepping through any tags)
+    pub fn get_key(&self, key: &CborValue) -> Option<CborValue> {
+        self.0
+            .get(&cml_core::CborPathSegment::Key(key.0.clone()))
+            .cloned()
+            .map(Into::into)
+    }
+
+    /// Key of the map entry at this position
+    pub fn map_key_at(&self, index: usize) -> Option<CborValue> {
+        self.0
+            .untagged()
+            .as_map()
+            .and_then(|entries| entries.get(index))
+            .map(|(key, _value)| key.clone().into())
+    }
+
+    /// Value of the map entry at this position
+    pub fn map_value_at(&self, index: usize) -> Option<CborValue> {
+        self.0
+            .untagged()
+            .as_map()
+            .and_then(|entries| entries.get(index))
+            .map(|(_key, value)| value.clone().into())
+    }
+}
+
+/// Limits on untrusted CBOR input, see from_cbor_bytes_with_limits()
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DecodeLimits(cml_core::limits::DecodeLimits);
+
+#[wasm_bindgen]
+impl DecodeLimits {
+    /// Defaults generous enough for any block or transaction found on chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.0.max_depth = max_depth;
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.0.max_depth
+    }
+
+    pub fn set_max_collection_len(&mut self, max_collection_len: u64) {
+        self.0.max_collection_len = max_collection_len;
+    }
+
+    pub fn max_collection_len(&self) -> u64 {
+        self.0.max_collection_len
+    }
+
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.0.max_bytes = max_bytes;
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.0.max_bytes
+    }
+
+    pub fn set_max_bigint_bytes(&mut self, max_bigint_bytes: u64) {
+        self.0.max_bigint_bytes = max_bigint_bytes;
+    }
+
+    pub fn max_bigint_bytes(&self) -> u64 {
+        self.0.max_bigint_bytes
+    }
+}
+
+impl From<cml_core::limits::DecodeLimits> for DecodeLimits {
+    fn from(native: cml_core::limits::DecodeLimits) -> Self {
+        Self(native)
+    }
+}
+
+impl From<DecodeLimits> for cml_core::limits::DecodeLimits {
+    fn from(wasm: DecodeLimits) -> Self {
+        wasm.0
+    }
+}
+
+impl AsRef<cml_core::limits::DecodeLimits> for DecodeLimits {
+    fn as_ref(&self) -> &cml_core::limits::DecodeLimits {
+        &self.0
+    }
+}
@@ -387,6 +387,7 @@ macro_rules! impl_wasm_cbor_json_api {
     ($wasm_name:ident) => {
         $crate::impl_wasm_cbor_api!($wasm_name);
         $crate::impl_wasm_json_api!($wasm_name);
+        $crate::impl_wasm_preserved_json_api!($wasm_name);
     };
 }
 
@@ -417,6 +418,14 @@ macro_rules! impl_wasm_cbor_api {
                 cml_core::serialization::Serialize::to_cbor_bytes(&self.0)
             }
 
+            /**
+             * CBOR diagnostic notation (RFC 8949 section 8) of to_cbor_bytes() showing encoding
+             * details such as indefinite lengths and non-minimal integers.
+             */
+            pub fn to_cbor_diagnostic(&self) -> String {
+                cml_core::serialization::Serialize::to_cbor_diagnostic(&self.0)
+            }
+
             /**
              * Create this type from CBOR bytes
              */
@@ -431,6 +440,59 @@ macro_rules! impl_wasm_cbor_api {
                     })
             }
 
+            /**
+             * Create this type from untrusted CBOR bytes, failing as soon as the input is found to nest
+             * too deeply or to have collections, big integers or a total size over the given limits.
+             */
+            pub fn from_cbor_bytes_with_limits(
+                cbor_bytes: &[u8],
+                limits: &$crate::DecodeLimits,
+            ) -> Result<$wasm_name, JsError> {
+                cml_core::serialization::Deserialize::from_cbor_bytes_with_limits(
+                    cbor_bytes,
+                    limits.as_ref(),
+                )
+                .map(Self)
+                .map_err(|e| {
+                    JsError::new(&format!(
+                        concat!(stringify!($wasm_name), "::from_bytes_with_limits: {}"),
+                        e
+                    ))
+                })
+            }
+
+            /**
+             * Create this type from CBOR bytes, rejecting non-canonical CBOR (non-minimal lengths,
+             * indefinite lengths, unsorted map keys), duplicate map keys where the ledger forbids
+             * them, CBOR that doesn't re-encode to the same bytes and trailing bytes.
+             */
+            pub fn from_cbor_bytes_strict(cbor_bytes: &[u8]) -> Result<$wasm_name, JsError> {
+                cml_core::serialization::Deserialize::from_cbor_bytes_strict(cbor_bytes)
+                    .map(Self)
+                    .map_err(|e| {
+                        JsError::new(&format!(
+                            concat!(stringify!($wasm_name), "::from_bytes_strict: {}"),
+                            e
+                        ))
+                    })
+            }
+
+            /**
+             * Create this type from CBOR bytes, keeping any map fields or array elements not known
+             * to this version (e.g. from a future era) so they are written back unchanged instead
+             * of failing.
+             */
+            pub fn from_cbor_bytes_lenient(cbor_bytes: &[u8]) -> Result<$wasm_name, JsError> {
+                cml_core::serialization::Deserialize::from_cbor_bytes_lenient(cbor_bytes)
+                    .map(Self)
+                    .map_err(|e| {
+                        JsError::new(&format!(
+                            concat!(stringify!($wasm_name), "::from_bytes_lenient: {}"),
+                            e
+                        ))
+                    })
+            }
+
             /**
              * Serialize this type to CBOR bytes encoded as a hex string (useful for working with CIP30).
              * This type type supports encoding preservation so this will preserve round-trip CBOR formats.
@@ -550,3 +612,69 @@ macro_rules! impl_wasm_json_api {
         }
     };
 }
+
+/// Implements the encoding-preserving JSON API (see cml_core::preserved_json) for WASM wrappers
+/// whose rust type implements both cml's Serialize/Deserialize and serde's
+#[macro_export]
+macro_rules! impl_wasm_preserved_json_api {
+    ($wasm_name:ident) => {
+        #[wasm_bindgen::prelude::wasm_bindgen]
+        impl $wasm_name {
+            /**
+             * JSON that also records the original CBOR encoding details so that
+             * from_preserved_json(...).to_cbor_bytes() gives back the exact same bytes
+             */
+            pub fn to_preserved_json(&self) -> Result<String, JsError> {
+                cml_core::preserved_json::ToPreservedJson::to_preserved_json_string(&self.0)
+                    .map_err(|e| {
+                        JsError::new(&format!(
+                            concat!(stringify!($wasm_name), "::to_preserved_json: {}"),
+                            e
+                        ))
+                    })
+            }
+
+            pub fn from_preserved_json(json: &str) -> Result<$wasm_name, JsError> {
+                cml_core::preserved_json::FromPreservedJson::from_preserved_json_str(json)
+                    .map(Self)
+                    .map_err(|e| {
+                        JsError::new(&format!(
+                            concat!(stringify!($wasm_name), "::from_preserved_json: {}"),
+                            e
+                        ))
+                    })
+            }
+        }
+    };
+}
+
+/// Implements the to/from cardano-cli TextEnvelope JSON API for WASM wrappers
+/// whose rust type implements cml_core::text_envelope::{ToTextEnvelope, FromTextEnvelope}
+#[macro_export]
+macro_rules! impl_wasm_text_envelope_api {
+    ($wasm_name:ident) => {
+        #[wasm_bindgen::prelude::wasm_bindgen]
+        impl $wasm_name {
+            /**
+             * Serialize this type as a cardano-cli TextEnvelope JSON e.g. to pass to cardano-cli
+             */
+            pub fn to_text_envelope_json(&self) -> String {
+                cml_core::text_envelope::ToTextEnvelope::to_text_envelope_json(&self.0)
+            }
+
+            /**
+             * Create this type from a cardano-cli TextEnvelope JSON e.g. a file written by cardano-cli
+             */
+            pub fn from_text_envelope_json(json: &str) -> Result<$wasm_name, JsError> {
+                cml_core::text_envelope::FromTextEnvelope::from_text_envelope_json(json)
+                    .map(Self)
+                    .map_err(|e| {
+                        JsError::new(&format!(
+                            concat!(stringify!($wasm_name), "::from_text_envelope_json: {}"),
+                            e
+                        ))
+                    })
+            }
+        }
+    };
+}
@@ -4,11 +4,9 @@ use cryptoxide::pbkdf2::pbkdf2;
 use cryptoxide::sha2::Sha512;
 use hex::ToHex;
 
-use std::iter::repeat;
-
 // taken from js-cardano-wasm
 
-mod password_encryption_parameter {
+pub(crate) mod password_encryption_parameter {
     pub const ITER: u32 = 19_162;
     pub const SALT_SIZE: usize = 32;
     pub const NONCE_SIZE: usize = 12;
@@ -49,13 +47,32 @@ pub fn emip3_encrypt_with_password(
     nonce: &str,
     data: &str,
 ) -> Result<String, EmIP3Error> {
-    use password_encryption_parameter::*;
-
     let password = hex::decode(password)?;
     let salt = hex::decode(salt)?;
     let nonce = hex::decode(nonce)?;
     let data = hex::decode(data)?;
 
+    emip3_encrypt(&password, &salt, &nonce, &data).map(|output| output.encode_hex::<String>())
+}
+
+/// Decrypt using Emip3: https://github.com/Emurgo/EmIPs/blob/master/specs/emip-003.md
+pub fn emip3_decrypt_with_password(password: &str, data: &str) -> Result<String, EmIP3Error> {
+    let password = hex::decode(password)?;
+    let data = hex::decode(data)?;
+
+    emip3_decrypt(&password, &data).map(|decrypted| decrypted.encode_hex::<String>())
+}
+
+/// Same as emip3_encrypt_with_password but operating on raw bytes instead of hex strings.
+/// The output is laid out as salt | nonce | tag | encrypted data
+pub fn emip3_encrypt(
+    password: &[u8],
+    salt: &[u8],
+    nonce: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, EmIP3Error> {
+    use password_encryption_parameter::*;
+
     if salt.len() != SALT_SIZE {
         return Err(EmIP3Error::SaltLen(SALT_SIZE, salt.len()));
     }
@@ -66,57 +83,77 @@ pub fn emip3_encrypt_with_password(
         return Err(EmIP3Error::EmptyPassword);
     }
 
-    let key = {
-        let mut mac = Hmac::new(Sha512::new(), &password);
-        let mut key: Vec<u8> = repeat(0).take(KEY_SIZE).collect();
-        pbkdf2(&mut mac, &salt[..], ITER, &mut key);
-        key
-    };
+    seal(&pbkdf2_key(password, salt), salt, nonce, data)
+}
+
+/// Same as emip3_decrypt_with_password but operating on raw bytes instead of hex strings.
+pub fn emip3_decrypt(password: &[u8], data: &[u8]) -> Result<Vec<u8>, EmIP3Error> {
+    use password_encryption_parameter::*;
+
+    if data.len() <= METADATA_SIZE {
+        // not enough input to decrypt.
+        return Err(EmIP3Error::MissingInputData(METADATA_SIZE, data.len()));
+    }
+
+    open(&pbkdf2_key(password, &data[SALT_START..SALT_END]), data)
+}
+
+fn pbkdf2_key(password: &[u8], salt: &[u8]) -> [u8; password_encryption_parameter::KEY_SIZE] {
+    use password_encryption_parameter::*;
+
+    let mut mac = Hmac::new(Sha512::new(), password);
+    let mut key = [0u8; KEY_SIZE];
+    pbkdf2(&mut mac, salt, ITER, &mut key);
+    key
+}
+
+/// ChaCha20Poly1305 encryption with an already-derived key, using the EmIP3 output layout.
+/// This is shared with other key derivation functions (e.g. scrypt in the keystore).
+pub(crate) fn seal(
+    key: &[u8],
+    salt: &[u8],
+    nonce: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, EmIP3Error> {
+    use password_encryption_parameter::*;
+
+    if nonce.len() != NONCE_SIZE {
+        return Err(EmIP3Error::NonceLen(NONCE_SIZE, nonce.len()));
+    }
 
     let mut tag = [0; TAG_SIZE];
-    let mut encrypted: Vec<u8> = repeat(0).take(data.len()).collect();
+    let mut encrypted = vec![0u8; data.len()];
     {
-        ChaCha20Poly1305::new(&key, &nonce, &[]).encrypt(&data, &mut encrypted, &mut tag);
+        ChaCha20Poly1305::new(key, nonce, &[]).encrypt(data, &mut encrypted, &mut tag);
     }
 
     let mut output = Vec::with_capacity(data.len() + METADATA_SIZE);
-    output.extend_from_slice(&salt);
-    output.extend_from_slice(&nonce);
+    output.extend_from_slice(salt);
+    output.extend_from_slice(nonce);
     output.extend_from_slice(&tag);
     output.extend_from_slice(&encrypted);
 
-    Ok(output.encode_hex::<String>())
+    Ok(output)
 }
 
-/// Decrypt using Emip3: https://github.com/Emurgo/EmIPs/blob/master/specs/emip-003.md
-pub fn emip3_decrypt_with_password(password: &str, data: &str) -> Result<String, EmIP3Error> {
+/// Inverse of seal(). The salt portion of data is ignored as the key is already derived.
+pub(crate) fn open(key: &[u8], data: &[u8]) -> Result<Vec<u8>, EmIP3Error> {
     use password_encryption_parameter::*;
-    let password = hex::decode(password)?;
-    let data = hex::decode(data)?;
 
     if data.len() <= METADATA_SIZE {
-        // not enough input to decrypt.
         return Err(EmIP3Error::MissingInputData(METADATA_SIZE, data.len()));
     }
 
-    let salt = &data[SALT_START..SALT_END];
     let nonce = &data[NONCE_START..NONCE_END];
     let tag = &data[TAG_START..TAG_END];
     let encrypted = &data[ENCRYPTED_START..];
 
-    let key = {
-        let mut mac = Hmac::new(Sha512::new(), &password);
-        let mut key: Vec<u8> = repeat(0).take(KEY_SIZE).collect();
-        pbkdf2(&mut mac, salt, ITER, &mut key);
-        key
-    };
-
-    let mut decrypted: Vec<u8> = repeat(0).take(encrypted.len()).collect();
+    let mut decrypted = vec![0u8; encrypted.len()];
     let decryption_succeed =
-        { ChaCha20Poly1305::new(&key, nonce, &[]).decrypt(encrypted, &mut decrypted, tag) };
+        { ChaCha20Poly1305::new(key, nonce, &[]).decrypt(encrypted, &mut decrypted, tag) };
 
     if decryption_succeed {
-        Ok(decrypted.encode_hex::<String>())
+        Ok(decrypted)
     } else {
         Err(EmIP3Error::DecryptionFailed)
     }
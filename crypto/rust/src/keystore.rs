@@ -0,0 +1,785 @@
+//! Password-encrypted storage of signing keys.
+//!
+//! Keys are encrypted either with EmIP3 (PBKDF2 + ChaCha20Poly1305) or with scrypt + ChaCha20Poly1305
+//! and stored alongside unencrypted metadata (key type, public key, derivation path, creation time)
+//! so that they can be found without needing the password. Encrypted keys are read/written as
+//! cardano-cli TextEnvelope JSON, and plain cardano-cli `.skey` files can be imported directly.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::io::{BufRead, Cursor, Write};
+use std::path::Path;
+
+use cbor_event::{de::Deserializer, se::Serializer};
+use cml_core::text_envelope::{FromTextEnvelope, TextEnvelope, TextEnvelopeError};
+use cryptoxide::scrypt::scrypt;
+use rand::{rngs::OsRng, RngCore};
+
+use crate::emip3::{self, password_encryption_parameter, EmIP3Error};
+pub use crate::text_envelope::KeyRole;
+use crate::{
+    Bip32PrivateKey, Bip32PublicKey, CryptoError, Ed25519KeyHash, Ed25519Signature, PrivateKey,
+    PublicKey, RawBytesEncoding,
+};
+
+/// TextEnvelope type used for keys encrypted by this module.
+/// This is not understood by cardano-cli as it has no encrypted key format.
+pub const ENCRYPTED_KEY_ENVELOPE_TYPE: &str = "EncryptedSigningKeyCML_ed25519";
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("Encryption: {0}")]
+    Encryption(#[from] EmIP3Error),
+    #[error("Crypto: {0}")]
+    Crypto(#[from] CryptoError),
+    #[error("TextEnvelope: {0}")]
+    TextEnvelope(#[from] TextEnvelopeError),
+    #[error("CBOR: {0}")]
+    CBOR(#[from] cbor_event::Error),
+    #[error("IO: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Invalid encrypted key: {0}")]
+    InvalidEncryptedKey(&'static str),
+    #[error("Invalid scrypt parameters: {0}")]
+    InvalidScryptParams(&'static str),
+    #[error("Decrypted key does not match the stored public key")]
+    PublicKeyMismatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// 32-byte ed25519 key (PrivateKey::from_normal_bytes)
+    Ed25519,
+    /// 64-byte extended ed25519 key without a chaincode (PrivateKey::from_extended_bytes)
+    Ed25519Extended,
+    /// 96-byte BIP32-Ed25519 key (Bip32PrivateKey)
+    Bip32,
+}
+
+impl KeyType {
+    fn to_tag(self) -> u64 {
+        match self {
+            Self::Ed25519 => 0,
+            Self::Ed25519Extended => 1,
+            Self::Bip32 => 2,
+        }
+    }
+
+    fn from_tag(tag: u64) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Ed25519),
+            1 => Some(Self::Ed25519Extended),
+            2 => Some(Self::Bip32),
+            _ => None,
+        }
+    }
+}
+
+/// Either kind of private key that can be stored in the keystore
+pub enum SigningKey {
+    Raw(PrivateKey),
+    Bip32(Bip32PrivateKey),
+}
+
+impl SigningKey {
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            Self::Raw(key) => match key.to_raw_bytes().len() {
+                32 => KeyType::Ed25519,
+                _ => KeyType::Ed25519Extended,
+            },
+            Self::Bip32(_) => KeyType::Bip32,
+        }
+    }
+
+    pub fn to_public(&self) -> PublicKey {
+        match self {
+            Self::Raw(key) => key.to_public(),
+            Self::Bip32(key) => key.to_public().to_raw_key(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Ed25519Signature {
+        match self {
+            Self::Raw(key) => key.sign(message),
+            Self::Bip32(key) => key.to_raw_key().sign(message),
+        }
+    }
+
+    /// Exports as a cardano-cli `.skey` TextEnvelope.
+    /// BIP32 keys are exported using the 128-byte xprv format that cardano-cli uses.
+    /// Extended non-BIP32 keys use the same format with a zeroed chaincode.
+    pub fn to_text_envelope(&self, role: KeyRole) -> TextEnvelope {
+        match self {
+            Self::Raw(key) => key.to_text_envelope_with_role(role),
+            Self::Bip32(key) => key.to_text_envelope_with_role(role),
+        }
+    }
+
+    /// Imports a cardano-cli `.skey` TextEnvelope, also returning the role of the key.
+    pub fn from_text_envelope(envelope: &TextEnvelope) -> Result<(KeyRole, Self), KeystoreError> {
+        let (role, extended) = KeyRole::from_signing_key_type_name(&envelope.type_name)
+            .ok_or_else(|| TextEnvelopeError::UnexpectedType {
+                found: envelope.type_name.clone(),
+                expected: KeyRole::signing_key_type_names(false),
+            })?;
+        let key = if extended {
+            Self::Bip32(Bip32PrivateKey::from_text_envelope(envelope)?)
+        } else {
+            Self::Raw(PrivateKey::from_text_envelope(envelope)?)
+        };
+        Ok((role, key))
+    }
+
+    /// Reads a cardano-cli `.skey` file
+    pub fn from_skey_file<P: AsRef<Path>>(path: P) -> Result<(KeyRole, Self), KeystoreError> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_text_envelope(&TextEnvelope::from_json(&json)?)
+    }
+
+    fn to_plain_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Raw(key) => key.to_raw_bytes().to_vec(),
+            Self::Bip32(key) => key.to_raw_bytes().to_vec(),
+        }
+    }
+
+    fn from_plain_bytes(key_type: KeyType, bytes: &[u8]) -> Result<Self, CryptoError> {
+        match key_type {
+            KeyType::Ed25519 => PrivateKey::from_normal_bytes(bytes).map(Self::Raw),
+            KeyType::Ed25519Extended => PrivateKey::from_extended_bytes(bytes).map(Self::Raw),
+            KeyType::Bip32 => Bip32PrivateKey::from_raw_bytes(bytes).map(Self::Bip32),
+        }
+    }
+}
+
+impl From<PrivateKey> for SigningKey {
+    fn from(key: PrivateKey) -> Self {
+        Self::Raw(key)
+    }
+}
+
+impl From<Bip32PrivateKey> for SigningKey {
+    fn from(key: Bip32PrivateKey) -> Self {
+        Self::Bip32(key)
+    }
+}
+
+/// Parameters for scrypt key derivation. N = 2^log_n
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEncryption {
+    /// EmIP3: PBKDF2-HMAC-SHA512 + ChaCha20Poly1305. Compatible with emip3_decrypt()
+    Emip3,
+    /// scrypt + ChaCha20Poly1305, using the same salt | nonce | tag | data layout as EmIP3
+    Scrypt(ScryptParams),
+}
+
+impl KeyEncryption {
+    fn encrypt(&self, password: &[u8], data: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+        use password_encryption_parameter::*;
+
+        let mut salt = [0u8; SALT_SIZE];
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        match self {
+            Self::Emip3 => emip3::emip3_encrypt(password, &salt, &nonce, data).map_err(Into::into),
+            Self::Scrypt(params) => {
+                if password.is_empty() {
+                    return Err(EmIP3Error::EmptyPassword.into());
+                }
+                let key = params.derive_key(password, &salt)?;
+                emip3::seal(&key, &salt, &nonce, data).map_err(Into::into)
+            }
+        }
+    }
+
+    fn decrypt(&self, password: &[u8], data: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+        use password_encryption_parameter::*;
+
+        match self {
+            Self::Emip3 => emip3::emip3_decrypt(password, data).map_err(Into::into),
+            Self::Scrypt(params) => {
+                if data.len() <= METADATA_SIZE {
+                    return Err(EmIP3Error::MissingInputData(METADATA_SIZE, data.len()).into());
+                }
+                let key = params.derive_key(password, &data[SALT_START..SALT_END])?;
+                emip3::open(&key, data).map_err(Into::into)
+            }
+        }
+    }
+}
+
+impl ScryptParams {
+    /// Largest accepted log_n. N = 2^20 with r = 8 already needs 1GiB of memory
+    pub const MAX_LOG_N: u8 = 20;
+    /// Largest accepted r * p
+    pub const MAX_R_P: u64 = 1 << 10;
+    /// Largest accepted scrypt memory usage (128 * r * N bytes)
+    pub const MAX_MEMORY: u64 = 1 << 30;
+
+    /// Checks the parameters are valid for scrypt and bounded so that deriving a key
+    /// from untrusted parameters (e.g. from a keystore file) can't exhaust memory/CPU.
+    pub fn validate(&self) -> Result<(), KeystoreError> {
+        if self.log_n == 0 || self.r == 0 || self.p == 0 {
+            return Err(KeystoreError::InvalidScryptParams(
+                "log_n, r and p must be non-zero",
+            ));
+        }
+        if self.log_n > Self::MAX_LOG_N {
+            return Err(KeystoreError::InvalidScryptParams("log_n too large"));
+        }
+        if self.r as u64 * self.p as u64 > Self::MAX_R_P {
+            return Err(KeystoreError::InvalidScryptParams("r * p too large"));
+        }
+        if 128 * self.r as u64 * (1u64 << self.log_n) > Self::MAX_MEMORY {
+            return Err(KeystoreError::InvalidScryptParams(
+                "memory usage (128 * r * 2^log_n) too large",
+            ));
+        }
+        Ok(())
+    }
+
+    fn derive_key(&self, password: &[u8], salt: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+        self.validate()?;
+        let params = cryptoxide::scrypt::ScryptParams::new(self.log_n, self.r, self.p);
+        let mut key = vec![0u8; password_encryption_parameter::KEY_SIZE];
+        scrypt(password, salt, &params, &mut key);
+        Ok(key)
+    }
+}
+
+/// Unencrypted information stored alongside an encrypted key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMetadata {
+    pub key_type: KeyType,
+    pub public_key: PublicKey,
+    /// Only present for BIP32 keys
+    pub chaincode: Option<Vec<u8>>,
+    /// BIP32 derivation path from the root key e.g. [1852H, 1815H, 0H, 0, 0]
+    pub derivation_path: Option<Vec<u32>>,
+    /// Seconds since the UNIX epoch
+    pub created_at: u64,
+}
+
+impl KeyMetadata {
+    pub fn key_hash(&self) -> Ed25519KeyHash {
+        self.public_key.hash()
+    }
+
+    /// Extended public key for BIP32 keys, which can be used to derive soft-derived children
+    pub fn bip32_public_key(&self) -> Result<Option<Bip32PublicKey>, CryptoError> {
+        self.chaincode
+            .as_ref()
+            .map(|chaincode| {
+                let mut bytes = self.public_key.to_raw_bytes().to_vec();
+                bytes.extend_from_slice(chaincode);
+                Bip32PublicKey::from_raw_bytes(&bytes)
+            })
+            .transpose()
+    }
+}
+
+/// A password-encrypted signing key along with its unencrypted metadata.
+///
+/// Stored as the cbor of a TextEnvelope of type ENCRYPTED_KEY_ENVELOPE_TYPE:
+/// ```text
+/// encrypted_key = [
+///   key_type: 0 / 1 / 2,            ; ed25519 / ed25519 extended / bip32
+///   public_key: bytes .size 32,
+///   chaincode: bytes .size 32 / null,
+///   derivation_path: [* uint] / null,
+///   created_at: uint,
+///   encryption: [0] / [1, log_n: uint, r: uint, p: uint], ; emip3 / scrypt
+///   ciphertext: bytes,              ; salt | nonce | tag | encrypted key
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedKey {
+    pub metadata: KeyMetadata,
+    pub encryption: KeyEncryption,
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedKey {
+    /// * `created_at` - seconds since the UNIX epoch. This is passed in as the system time is not available on all targets (e.g. WASM).
+    pub fn encrypt(
+        key: &SigningKey,
+        password: &[u8],
+        encryption: KeyEncryption,
+        derivation_path: Option<Vec<u32>>,
+        created_at: u64,
+    ) -> Result<Self, KeystoreError> {
+        let metadata = KeyMetadata {
+            key_type: key.key_type(),
+            public_key: key.to_public(),
+            chaincode: match key {
+                SigningKey::Raw(_) => None,
+                SigningKey::Bip32(bip32) => Some(bip32.chaincode()),
+            },
+            derivation_path,
+            created_at,
+        };
+        let ciphertext = encryption.encrypt(password, &key.to_plain_bytes())?;
+        Ok(Self {
+            metadata,
+            encryption,
+            ciphertext,
+        })
+    }
+
+    pub fn decrypt(&self, password: &[u8]) -> Result<SigningKey, KeystoreError> {
+        let plain = self.encryption.decrypt(password, &self.ciphertext)?;
+        let key = SigningKey::from_plain_bytes(self.metadata.key_type, &plain)?;
+        if key.to_public() != self.metadata.public_key {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+        Ok(key)
+    }
+
+    pub fn key_hash(&self) -> Ed25519KeyHash {
+        self.metadata.key_hash()
+    }
+
+    pub fn to_cbor_bytes(&self) -> Vec<u8> {
+        let mut serializer = Serializer::new_vec();
+        self.serialize(&mut serializer).unwrap();
+        serializer.finalize()
+    }
+
+    pub fn from_cbor_bytes(bytes: &[u8]) -> Result<Self, KeystoreError> {
+        let mut raw = Deserializer::from(Cursor::new(bytes));
+        Self::deserialize(&mut raw)
+    }
+
+    pub fn to_text_envelope(&self) -> TextEnvelope {
+        TextEnvelope::new(
+            ENCRYPTED_KEY_ENVELOPE_TYPE.to_owned(),
+            "Encrypted Signing Key".to_owned(),
+            &self.to_cbor_bytes(),
+        )
+    }
+
+    pub fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, KeystoreError> {
+        if envelope.type_name != ENCRYPTED_KEY_ENVELOPE_TYPE {
+            return Err(TextEnvelopeError::UnexpectedType {
+                found: envelope.type_name.clone(),
+                expected: vec![ENCRYPTED_KEY_ENVELOPE_TYPE.to_owned()],
+            }
+            .into());
+        }
+        Self::from_cbor_bytes(&envelope.cbor_bytes()?)
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_text_envelope().to_json()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        Self::from_text_envelope(&TextEnvelope::from_json(json)?)
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), KeystoreError> {
+        std::fs::write(path, self.to_json()).map_err(Into::into)
+    }
+
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, KeystoreError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    fn serialize<'a, W: Write>(
+        &self,
+        serializer: &'a mut Serializer<W>,
+    ) -> cbor_event::Result<&'a mut Serializer<W>> {
+        serializer.write_array(cbor_event::Len::Len(7))?;
+        serializer.write_unsigned_integer(self.metadata.key_type.to_tag())?;
+        serializer.write_bytes(self.metadata.public_key.to_raw_bytes())?;
+        match &self.metadata.chaincode {
+            Some(chaincode) => serializer.write_bytes(chaincode)?,
+            None => serializer.write_special(cbor_event::Special::Null)?,
+        };
+        match &self.metadata.derivation_path {
+            Some(path) => {
+                serializer.write_array(cbor_event::Len::Len(path.len() as u64))?;
+                for index in path {
+                    serializer.write_unsigned_integer(*index as u64)?;
+                }
+            }
+            None => {
+                serializer.write_special(cbor_event::Special::Null)?;
+            }
+        }
+        serializer.write_unsigned_integer(self.metadata.created_at)?;
+        match &self.encryption {
+            KeyEncryption::Emip3 => {
+                serializer.write_array(cbor_event::Len::Len(1))?;
+                serializer.write_unsigned_integer(0)?;
+            }
+            KeyEncryption::Scrypt(params) => {
+                serializer.write_array(cbor_event::Len::Len(4))?;
+                serializer.write_unsigned_integer(1)?;
+                serializer.write_unsigned_integer(params.log_n as u64)?;
+                serializer.write_unsigned_integer(params.r as u64)?;
+                serializer.write_unsigned_integer(params.p as u64)?;
+            }
+        }
+        serializer.write_bytes(&self.ciphertext)
+    }
+
+    fn deserialize<R: BufRead>(raw: &mut Deserializer<R>) -> Result<Self, KeystoreError> {
+        raw.tuple(7, "EncryptedKey")?;
+        let key_type = KeyType::from_tag(raw.unsigned_integer()?)
+            .ok_or(KeystoreError::InvalidEncryptedKey("unknown key type"))?;
+        let public_key = PublicKey::from_raw_bytes(&raw.bytes()?)?;
+        let chaincode = match raw.cbor_type()? {
+            cbor_event::Type::Special => {
+                read_null(raw)?;
+                None
+            }
+            _ => {
+                let chaincode = raw.bytes()?;
+                if chaincode.len() != CHAINCODE_SIZE {
+                    return Err(KeystoreError::InvalidEncryptedKey(
+                        "chaincode must be 32 bytes",
+                    ));
+                }
+                Some(chaincode)
+            }
+        };
+        if chaincode.is_some() != (key_type == KeyType::Bip32) {
+            return Err(KeystoreError::InvalidEncryptedKey(
+                "chaincode must be present for (only) BIP32 keys",
+            ));
+        }
+        let derivation_path = match raw.cbor_type()? {
+            cbor_event::Type::Special => {
+                read_null(raw)?;
+                None
+            }
+            _ => {
+                let len = match raw.array()? {
+                    cbor_event::Len::Len(len) => len,
+                    cbor_event::Len::Indefinite => {
+                        return Err(KeystoreError::InvalidEncryptedKey(
+                            "derivation path must be definite length",
+                        ))
+                    }
+                };
+                let mut path = Vec::new();
+                for _ in 0..len {
+                    let index = u32::try_from(raw.unsigned_integer()?).map_err(|_| {
+                        KeystoreError::InvalidEncryptedKey("derivation index out of range")
+                    })?;
+                    path.push(index);
+                }
+                Some(path)
+            }
+        };
+        let created_at = raw.unsigned_integer()?;
+        let encryption = match raw.array()? {
+            cbor_event::Len::Len(1) if raw.unsigned_integer()? == 0 => KeyEncryption::Emip3,
+            cbor_event::Len::Len(4) if raw.unsigned_integer()? == 1 => {
+                let log_n = raw.unsigned_integer()?;
+                let r = raw.unsigned_integer()?;
+                let p = raw.unsigned_integer()?;
+                match (u8::try_from(log_n), u32::try_from(r), u32::try_from(p)) {
+                    (Ok(log_n), Ok(r), Ok(p)) => {
+                        let params = ScryptParams { log_n, r, p };
+                        params.validate()?;
+                        KeyEncryption::Scrypt(params)
+                    }
+                    _ => {
+                        return Err(KeystoreError::InvalidEncryptedKey(
+                            "scrypt parameters out of range",
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(KeystoreError::InvalidEncryptedKey(
+                    "unknown encryption scheme",
+                ))
+            }
+        };
+        let ciphertext = raw.bytes()?;
+        Ok(Self {
+            metadata: KeyMetadata {
+                key_type,
+                public_key,
+                chaincode,
+                derivation_path,
+                created_at,
+            },
+            encryption,
+            ciphertext,
+        })
+    }
+}
+
+const CHAINCODE_SIZE: usize = 32;
+
+/// Reads a special that must be null (used for absent optional fields)
+fn read_null<R: BufRead>(raw: &mut Deserializer<R>) -> Result<(), KeystoreError> {
+    match raw.special()? {
+        cbor_event::Special::Null => Ok(()),
+        _ => Err(KeystoreError::InvalidEncryptedKey(
+            "expected null for absent field",
+        )),
+    }
+}
+
+/// A collection of encrypted keys indexed by their key hash
+#[derive(Debug, Clone, Default)]
+pub struct Keystore {
+    keys: BTreeMap<Ed25519KeyHash, EncryptedKey>,
+}
+
+impl Keystore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key, returning any previous key with the same key hash
+    pub fn insert(&mut self, key: EncryptedKey) -> Option<EncryptedKey> {
+        self.keys.insert(key.key_hash(), key)
+    }
+
+    pub fn get(&self, key_hash: &Ed25519KeyHash) -> Option<&EncryptedKey> {
+        self.keys.get(key_hash)
+    }
+
+    pub fn get_by_public_key(&self, public_key: &PublicKey) -> Option<&EncryptedKey> {
+        self.get(&public_key.hash())
+    }
+
+    pub fn remove(&mut self, key_hash: &Ed25519KeyHash) -> Option<EncryptedKey> {
+        self.keys.remove(key_hash)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EncryptedKey> {
+        self.keys.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Decrypts the key with the given hash, if it is in the keystore
+    pub fn decrypt(
+        &self,
+        key_hash: &Ed25519KeyHash,
+        password: &[u8],
+    ) -> Option<Result<SigningKey, KeystoreError>> {
+        self.get(key_hash).map(|key| key.decrypt(password))
+    }
+
+    /// Encrypts and adds an unencrypted cardano-cli `.skey` file to the keystore
+    pub fn import_skey_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        password: &[u8],
+        encryption: KeyEncryption,
+        created_at: u64,
+    ) -> Result<Ed25519KeyHash, KeystoreError> {
+        let (_role, key) = SigningKey::from_skey_file(path)?;
+        let encrypted = EncryptedKey::encrypt(&key, password, encryption, None, created_at)?;
+        let key_hash = encrypted.key_hash();
+        self.insert(encrypted);
+        Ok(key_hash)
+    }
+
+    /// Loads all encrypted keys (TextEnvelope JSON files) in a directory.
+    /// Files of other TextEnvelope types (e.g. unencrypted `.skey`/`.vkey`) are skipped.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Self, KeystoreError> {
+        let mut keystore = Self::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let envelope = match TextEnvelope::from_json(&std::fs::read_to_string(&path)?) {
+                Ok(envelope) => envelope,
+                Err(_) => continue,
+            };
+            if envelope.type_name == ENCRYPTED_KEY_ENVELOPE_TYPE {
+                keystore.insert(EncryptedKey::from_text_envelope(&envelope)?);
+            }
+        }
+        Ok(keystore)
+    }
+
+    /// Writes every key as `<key hash>.json` in the given directory
+    pub fn save_dir<P: AsRef<Path>>(&self, dir: P) -> Result<(), KeystoreError> {
+        for (key_hash, key) in self.keys.iter() {
+            key.write_to_file(dir.as_ref().join(format!("{}.json", key_hash.to_hex())))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HARDENED: u32 = 0x80000000;
+
+    fn test_scrypt() -> KeyEncryption {
+        // keep tests fast
+        KeyEncryption::Scrypt(ScryptParams {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        })
+    }
+
+    #[test]
+    fn emip3_round_trip() {
+        let key = SigningKey::from(PrivateKey::generate_ed25519());
+        let encrypted =
+            EncryptedKey::encrypt(&key, b"password", KeyEncryption::Emip3, None, 1700000000)
+                .unwrap();
+        let decoded = EncryptedKey::from_json(&encrypted.to_json()).unwrap();
+        assert_eq!(decoded, encrypted);
+        let decrypted = decoded.decrypt(b"password").unwrap();
+        assert_eq!(decrypted.to_public(), key.to_public());
+        assert!(decoded.decrypt(b"wrong password").is_err());
+    }
+
+    #[test]
+    fn scrypt_bip32_round_trip() {
+        let root = Bip32PrivateKey::generate_ed25519_bip32();
+        let path = vec![1852 + HARDENED, 1815 + HARDENED, HARDENED, 0, 0];
+        let child = path.iter().fold(root, |key, index| key.derive(*index));
+        let expected_pub = child.to_public();
+        let key = SigningKey::from(child);
+        let encrypted =
+            EncryptedKey::encrypt(&key, b"password", test_scrypt(), Some(path.clone()), 42)
+                .unwrap();
+        let decoded = EncryptedKey::from_cbor_bytes(&encrypted.to_cbor_bytes()).unwrap();
+        assert_eq!(decoded.metadata.derivation_path, Some(path));
+        assert_eq!(decoded.metadata.created_at, 42);
+        assert_eq!(
+            decoded.metadata.bip32_public_key().unwrap(),
+            Some(expected_pub)
+        );
+        match decoded.decrypt(b"password").unwrap() {
+            SigningKey::Bip32(_) => (),
+            SigningKey::Raw(_) => panic!("expected bip32 key"),
+        }
+        // emip3 and scrypt are not interchangeable
+        let mut wrong_scheme = decoded;
+        wrong_scheme.encryption = KeyEncryption::Emip3;
+        assert!(wrong_scheme.decrypt(b"password").is_err());
+    }
+
+    #[test]
+    fn reject_invalid_metadata() {
+        let key = SigningKey::from(Bip32PrivateKey::generate_ed25519_bip32());
+        let encrypted = EncryptedKey::encrypt(&key, b"password", test_scrypt(), None, 0).unwrap();
+        for (log_n, r, p) in [
+            (0, 8, 1),
+            (10, 0, 1),
+            (10, 8, 0),
+            (64, 8, 1),
+            (21, 8, 1),
+            (10, 8, 1 << 20),
+            (20, 16, 1),
+        ] {
+            let mut invalid = encrypted.clone();
+            invalid.encryption = KeyEncryption::Scrypt(ScryptParams { log_n, r, p });
+            assert!(matches!(
+                EncryptedKey::from_cbor_bytes(&invalid.to_cbor_bytes()),
+                Err(KeystoreError::InvalidScryptParams(_))
+            ));
+            assert!(invalid.decrypt(b"password").is_err());
+        }
+        let mut short_chaincode = encrypted.clone();
+        short_chaincode.metadata.chaincode = Some(vec![0; 31]);
+        assert!(short_chaincode.metadata.bip32_public_key().is_err());
+        assert!(EncryptedKey::from_cbor_bytes(&short_chaincode.to_cbor_bytes()).is_err());
+        let mut missing_chaincode = encrypted.clone();
+        missing_chaincode.metadata.chaincode = None;
+        assert!(EncryptedKey::from_cbor_bytes(&missing_chaincode.to_cbor_bytes()).is_err());
+        // chaincode = true instead of null
+        let mut bytes = missing_chaincode.to_cbor_bytes();
+        let null_pos = 2 + 2 + 32;
+        assert_eq!(bytes[null_pos], 0xf6);
+        bytes[null_pos] = 0xf5;
+        assert!(EncryptedKey::from_cbor_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn cardano_cli_skey() {
+        let json = r#"{
+            "type": "PaymentSigningKeyShelley_ed25519",
+            "description": "Payment Signing Key",
+            "cborHex": "5820d4ffbd2fdb9a4bd7b3da0d6d24a7c2ae14ac06b9d1ee1bf5d7d0aa6af6b0b0d1"
+        }"#;
+        let envelope = TextEnvelope::from_json(json).unwrap();
+        let (role, key) = SigningKey::from_text_envelope(&envelope).unwrap();
+        assert_eq!(role, KeyRole::Payment);
+        assert_eq!(key.key_type(), KeyType::Ed25519);
+        assert_eq!(key.to_text_envelope(role), envelope);
+    }
+
+    #[test]
+    fn cardano_cli_extended_skey() {
+        let key = SigningKey::from(Bip32PrivateKey::generate_ed25519_bip32());
+        let envelope = key.to_text_envelope(KeyRole::Stake);
+        assert_eq!(
+            envelope.type_name,
+            "StakeExtendedSigningKeyShelley_ed25519_bip32"
+        );
+        // 128-byte xprv with a 2-byte bytes header
+        assert_eq!(envelope.cbor_bytes().unwrap().len(), 130);
+        let (role, decoded) = SigningKey::from_text_envelope(&envelope).unwrap();
+        assert_eq!(role, KeyRole::Stake);
+        assert_eq!(decoded.to_public(), key.to_public());
+        // extended keys without a chaincode are exported with a zeroed one
+        let key = SigningKey::from(PrivateKey::generate_ed25519extended());
+        let envelope = key.to_text_envelope(KeyRole::Payment);
+        assert_eq!(envelope.cbor_bytes().unwrap().len(), 130);
+        let (_role, decoded) = SigningKey::from_text_envelope(&envelope).unwrap();
+        assert_eq!(decoded.to_public(), key.to_public());
+    }
+
+    #[test]
+    fn keystore_lookup() {
+        let mut keystore = Keystore::new();
+        let key = SigningKey::from(PrivateKey::generate_ed25519extended());
+        let public_key = key.to_public();
+        keystore.insert(
+            EncryptedKey::encrypt(&key, b"password", KeyEncryption::Emip3, None, 0).unwrap(),
+        );
+        assert_eq!(keystore.len(), 1);
+        assert!(keystore.get_by_public_key(&public_key).is_some());
+        let decrypted = keystore
+            .decrypt(&public_key.hash(), b"password")
+            .unwrap()
+            .unwrap();
+        assert_eq!(decrypted.key_type(), KeyType::Ed25519Extended);
+        let message = b"message";
+        assert!(public_key.verify(message, &decrypted.sign(message)));
+    }
+}
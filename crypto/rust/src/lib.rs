@@ -11,6 +11,8 @@ use rand::rngs::OsRng;
 use std::convert::From;
 
 pub mod emip3;
+pub mod keystore;
+pub mod text_envelope;
 
 // brought over from old IOHK code
 pub mod chain_core;
@@ -0,0 +1,404 @@
+//! cardano-cli TextEnvelope (`.skey`/`.vkey`) support for keys.
+//!
+//! The key types do not know what they are used for, so the `ToTextEnvelope` impls here
+//! use the payment key types. Use `KeyRole` for the other kinds of keys.
+
+use std::io::Cursor;
+
+use cbor_event::{de::Deserializer, se::Serializer};
+use cml_core::error::{DeserializeError, DeserializeFailure};
+pub use cml_core::text_envelope::{
+    FromTextEnvelope, TextEnvelope, TextEnvelopeError, ToTextEnvelope,
+};
+
+use crate::{
+    Bip32PrivateKey, Bip32PublicKey, CryptoError, PrivateKey, PublicKey, RawBytesEncoding,
+};
+
+/// What a key is used for. This determines the cardano-cli TextEnvelope type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyRole {
+    Payment,
+    Stake,
+    StakePool,
+    DRep,
+    CommitteeCold,
+    CommitteeHot,
+}
+
+impl KeyRole {
+    const ALL: [KeyRole; 6] = [
+        Self::Payment,
+        Self::Stake,
+        Self::StakePool,
+        Self::DRep,
+        Self::CommitteeCold,
+        Self::CommitteeHot,
+    ];
+
+    fn type_name_prefix(&self) -> &'static str {
+        match self {
+            Self::Payment => "Payment",
+            Self::Stake => "Stake",
+            Self::StakePool => "StakePool",
+            Self::DRep => "DRep",
+            Self::CommitteeCold => "ConstitutionalCommitteeCold",
+            Self::CommitteeHot => "ConstitutionalCommitteeHot",
+        }
+    }
+
+    fn type_name(&self, key_kind: &str, extended: bool) -> String {
+        // only the original shelley-era key types have this suffix
+        let era_suffix = match self {
+            Self::Payment | Self::Stake => "Shelley",
+            _ => "",
+        };
+        if extended {
+            format!(
+                "{}Extended{}{}_ed25519_bip32",
+                self.type_name_prefix(),
+                key_kind,
+                era_suffix
+            )
+        } else {
+            format!(
+                "{}{}{}_ed25519",
+                self.type_name_prefix(),
+                key_kind,
+                era_suffix
+            )
+        }
+    }
+
+    fn description(&self, key_kind: &str) -> String {
+        let prefix = match self {
+            Self::Payment => "Payment",
+            Self::Stake => "Stake",
+            Self::StakePool => "Stake Pool Operator",
+            Self::DRep => "Delegate Representative",
+            Self::CommitteeCold => "Constitutional Committee Cold",
+            Self::CommitteeHot => "Constitutional Committee Hot",
+        };
+        format!("{prefix} {key_kind}")
+    }
+
+    /// cardano-cli TextEnvelope type for a signing key of this role.
+    /// `extended` refers to BIP32 keys (`*ExtendedSigningKey*_ed25519_bip32`)
+    pub fn signing_key_type_name(&self, extended: bool) -> String {
+        self.type_name("SigningKey", extended)
+    }
+
+    /// cardano-cli TextEnvelope type for a verification key of this role.
+    /// `extended` refers to BIP32 keys (`*ExtendedVerificationKey*_ed25519_bip32`)
+    pub fn verification_key_type_name(&self, extended: bool) -> String {
+        self.type_name("VerificationKey", extended)
+    }
+
+    /// Description cardano-cli writes out for signing keys of this role
+    pub fn signing_key_description(&self) -> String {
+        self.description("Signing Key")
+    }
+
+    /// Description cardano-cli writes out for verification keys of this role
+    pub fn verification_key_description(&self) -> String {
+        self.description("Verification Key")
+    }
+
+    /// Parses a cardano-cli signing key type, returning the role and whether it is a BIP32 key
+    pub fn from_signing_key_type_name(type_name: &str) -> Option<(Self, bool)> {
+        Self::find(type_name, Self::signing_key_type_name)
+    }
+
+    /// Parses a cardano-cli verification key type, returning the role and whether it is a BIP32 key
+    pub fn from_verification_key_type_name(type_name: &str) -> Option<(Self, bool)> {
+        Self::find(type_name, Self::verification_key_type_name)
+    }
+
+    /// All cardano-cli signing key types, optionally only BIP32 ones
+    pub(crate) fn signing_key_type_names(only_extended: bool) -> Vec<String> {
+        Self::type_names(Self::signing_key_type_name, only_extended)
+    }
+
+    /// All cardano-cli verification key types, optionally only BIP32 ones
+    pub(crate) fn verification_key_type_names(only_extended: bool) -> Vec<String> {
+        Self::type_names(Self::verification_key_type_name, only_extended)
+    }
+
+    fn type_names<F: Fn(&Self, bool) -> String>(name: F, only_extended: bool) -> Vec<String> {
+        let mut names = Vec::new();
+        for role in Self::ALL.iter() {
+            if !only_extended {
+                names.push(name(role, false));
+            }
+            names.push(name(role, true));
+        }
+        names
+    }
+
+    fn find<F: Fn(&Self, bool) -> String>(type_name: &str, name: F) -> Option<(Self, bool)> {
+        Self::ALL.iter().find_map(|role| {
+            [false, true]
+                .iter()
+                .find(|extended| name(role, **extended) == type_name)
+                .map(|extended| (*role, *extended))
+        })
+    }
+}
+
+/// Envelope with the key bytes as a CBOR bytestring, as cardano-cli does
+pub fn key_text_envelope(type_name: String, description: String, key_bytes: &[u8]) -> TextEnvelope {
+    let mut serializer = Serializer::new_vec();
+    serializer.write_bytes(key_bytes).unwrap();
+    TextEnvelope::new(type_name, description, &serializer.finalize())
+}
+
+/// Reads the key bytes out of an envelope created by key_text_envelope()
+pub fn key_text_envelope_bytes(envelope: &TextEnvelope) -> Result<Vec<u8>, TextEnvelopeError> {
+    let cbor_bytes = envelope.cbor_bytes()?;
+    Deserializer::from(Cursor::new(cbor_bytes))
+        .bytes()
+        .map_err(|e| DeserializeError::from(e).into())
+}
+
+fn key_error(location: &'static str, e: CryptoError) -> TextEnvelopeError {
+    DeserializeError::new(location, DeserializeFailure::InvalidStructure(Box::new(e))).into()
+}
+
+fn check_extended_len(
+    location: &'static str,
+    bytes: &[u8],
+    len: usize,
+) -> Result<(), TextEnvelopeError> {
+    if bytes.len() != len {
+        return Err(DeserializeError::new(
+            location,
+            DeserializeFailure::CBOR(cbor_event::Error::WrongLen(
+                len as u64,
+                cbor_event::Len::Len(bytes.len() as u64),
+                "extended key length",
+            )),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+impl PublicKey {
+    pub fn to_text_envelope_with_role(&self, role: KeyRole) -> TextEnvelope {
+        key_text_envelope(
+            role.verification_key_type_name(false),
+            role.verification_key_description(),
+            self.to_raw_bytes(),
+        )
+    }
+}
+
+impl ToTextEnvelope for PublicKey {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        self.to_text_envelope_with_role(KeyRole::Payment)
+    }
+}
+
+impl FromTextEnvelope for PublicKey {
+    /// Accepts verification keys of any role. Extended (BIP32) keys are converted to their raw key.
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(KeyRole::verification_key_type_names(false))?;
+        let (_role, extended) =
+            KeyRole::from_verification_key_type_name(&envelope.type_name).unwrap();
+        if extended {
+            Bip32PublicKey::from_text_envelope(envelope).map(|key| key.to_raw_key())
+        } else {
+            let bytes = key_text_envelope_bytes(envelope)?;
+            PublicKey::from_raw_bytes(&bytes).map_err(|e| key_error("PublicKey", e))
+        }
+    }
+}
+
+impl Bip32PublicKey {
+    pub fn to_text_envelope_with_role(&self, role: KeyRole) -> TextEnvelope {
+        key_text_envelope(
+            role.verification_key_type_name(true),
+            role.verification_key_description(),
+            self.to_raw_bytes(),
+        )
+    }
+}
+
+impl ToTextEnvelope for Bip32PublicKey {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        self.to_text_envelope_with_role(KeyRole::Payment)
+    }
+}
+
+impl FromTextEnvelope for Bip32PublicKey {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(KeyRole::verification_key_type_names(true))?;
+        let bytes = key_text_envelope_bytes(envelope)?;
+        Bip32PublicKey::from_raw_bytes(&bytes).map_err(|e| key_error("Bip32PublicKey", e))
+    }
+}
+
+impl Bip32PrivateKey {
+    /// Uses the 128-byte xprv format (see to_128_xprv) that cardano-cli uses
+    pub fn to_text_envelope_with_role(&self, role: KeyRole) -> TextEnvelope {
+        key_text_envelope(
+            role.signing_key_type_name(true),
+            role.signing_key_description(),
+            &self.to_128_xprv(),
+        )
+    }
+}
+
+impl ToTextEnvelope for Bip32PrivateKey {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        self.to_text_envelope_with_role(KeyRole::Payment)
+    }
+}
+
+impl FromTextEnvelope for Bip32PrivateKey {
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(KeyRole::signing_key_type_names(true))?;
+        let bytes = key_text_envelope_bytes(envelope)?;
+        check_extended_len("Bip32PrivateKey", &bytes, 128)?;
+        Bip32PrivateKey::from_128_xprv(&bytes).map_err(|e| key_error("Bip32PrivateKey", e))
+    }
+}
+
+impl PrivateKey {
+    /// Extended keys have no chaincode so they use the 128-byte xprv format (see
+    /// Bip32PrivateKey::to_128_xprv) with a zeroed chaincode. Signing only uses the key itself.
+    pub fn to_text_envelope_with_role(&self, role: KeyRole) -> TextEnvelope {
+        let key_bytes = self.to_raw_bytes();
+        if key_bytes.len() == 32 {
+            key_text_envelope(
+                role.signing_key_type_name(false),
+                role.signing_key_description(),
+                key_bytes,
+            )
+        } else {
+            let mut xprv = [0; 128];
+            xprv[0..64].clone_from_slice(key_bytes);
+            xprv[64..96].clone_from_slice(self.to_public().to_raw_bytes());
+            key_text_envelope(
+                role.signing_key_type_name(true),
+                role.signing_key_description(),
+                &xprv,
+            )
+        }
+    }
+}
+
+impl ToTextEnvelope for PrivateKey {
+    fn to_text_envelope(&self) -> TextEnvelope {
+        self.to_text_envelope_with_role(KeyRole::Payment)
+    }
+}
+
+impl FromTextEnvelope for PrivateKey {
+    /// Accepts signing keys of any role. Extended (BIP32) keys are converted to their raw key.
+    fn from_text_envelope(envelope: &TextEnvelope) -> Result<Self, TextEnvelopeError> {
+        envelope.check_type(KeyRole::signing_key_type_names(false))?;
+        let (_role, extended) = KeyRole::from_signing_key_type_name(&envelope.type_name).unwrap();
+        let bytes = key_text_envelope_bytes(envelope)?;
+        if extended {
+            // the chaincode is not needed (and may be zeroed, see to_text_envelope_with_role())
+            check_extended_len("PrivateKey", &bytes, 128)?;
+            PrivateKey::from_extended_bytes(&bytes[0..64]).map_err(|e| key_error("PrivateKey", e))
+        } else {
+            PrivateKey::from_normal_bytes(&bytes).map_err(|e| key_error("PrivateKey", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_names() {
+        assert_eq!(
+            KeyRole::Payment.signing_key_type_name(false),
+            "PaymentSigningKeyShelley_ed25519"
+        );
+        assert_eq!(
+            KeyRole::Stake.verification_key_type_name(true),
+            "StakeExtendedVerificationKeyShelley_ed25519_bip32"
+        );
+        assert_eq!(
+            KeyRole::DRep.signing_key_type_name(false),
+            "DRepSigningKey_ed25519"
+        );
+        assert_eq!(
+            KeyRole::CommitteeHot.verification_key_type_name(false),
+            "ConstitutionalCommitteeHotVerificationKey_ed25519"
+        );
+        assert_eq!(
+            KeyRole::StakePool.verification_key_description(),
+            "Stake Pool Operator Verification Key"
+        );
+        assert_eq!(
+            KeyRole::from_signing_key_type_name("StakePoolExtendedSigningKey_ed25519_bip32"),
+            Some((KeyRole::StakePool, true))
+        );
+        assert_eq!(KeyRole::from_signing_key_type_name("Tx ConwayEra"), None);
+    }
+
+    #[test]
+    fn cardano_cli_vkey() {
+        let json = r#"{
+            "type": "PaymentVerificationKeyShelley_ed25519",
+            "description": "Payment Verification Key",
+            "cborHex": "58208be8339e9f3addfa6810d59e2f072f85e64d4c024c087e0d24f8317c6544f62f"
+        }"#;
+        let key = PublicKey::from_text_envelope_json(json).unwrap();
+        assert_eq!(
+            key.to_text_envelope(),
+            TextEnvelope::from_json(json).unwrap()
+        );
+        assert!(Bip32PublicKey::from_text_envelope_json(json).is_err());
+    }
+
+    #[test]
+    fn bip32_keys() {
+        let prv = Bip32PrivateKey::generate_ed25519_bip32();
+        let pubkey = prv.to_public();
+        let prv_envelope = prv.to_text_envelope_with_role(KeyRole::Stake);
+        assert_eq!(
+            prv_envelope.type_name,
+            "StakeExtendedSigningKeyShelley_ed25519_bip32"
+        );
+        let decoded = Bip32PrivateKey::from_text_envelope(&prv_envelope).unwrap();
+        assert_eq!(decoded.to_public(), pubkey);
+        let raw = PrivateKey::from_text_envelope(&prv_envelope).unwrap();
+        assert_eq!(raw.to_public(), pubkey.to_raw_key());
+
+        let pub_envelope = pubkey.to_text_envelope();
+        assert_eq!(
+            Bip32PublicKey::from_text_envelope(&pub_envelope).unwrap(),
+            pubkey
+        );
+        assert_eq!(
+            PublicKey::from_text_envelope(&pub_envelope).unwrap(),
+            pubkey.to_raw_key()
+        );
+    }
+
+    #[test]
+    fn raw_private_keys() {
+        let normal = PrivateKey::generate_ed25519();
+        let envelope = normal.to_text_envelope_with_role(KeyRole::DRep);
+        assert_eq!(envelope.type_name, "DRepSigningKey_ed25519");
+        let decoded = PrivateKey::from_text_envelope(&envelope).unwrap();
+        assert_eq!(decoded.to_raw_bytes(), normal.to_raw_bytes());
+
+        let extended = PrivateKey::generate_ed25519extended();
+        let envelope = extended.to_text_envelope();
+        assert_eq!(
+            envelope.type_name,
+            "PaymentExtendedSigningKeyShelley_ed25519_bip32"
+        );
+        let decoded = PrivateKey::from_text_envelope_json(&envelope.to_json()).unwrap();
+        assert_eq!(decoded.to_raw_bytes(), extended.to_raw_bytes());
+        assert_eq!(decoded.to_public(), extended.to_public());
+    }
+}
@@ -0,0 +1,136 @@
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::{Bip32PrivateKey, Bip32PublicKey, Ed25519KeyHash, PrivateKey, PublicKey};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct KeyEncryption(cml_crypto::keystore::KeyEncryption);
+
+#[wasm_bindgen]
+impl KeyEncryption {
+    /// EmIP3: PBKDF2-HMAC-SHA512 + ChaCha20Poly1305
+    pub fn new_emip3() -> Self {
+        Self(cml_crypto::keystore::KeyEncryption::Emip3)
+    }
+
+    /// scrypt + ChaCha20Poly1305 with N = 2^log_n
+    /// Fails if the parameters are invalid or exceed ScryptParams' limits
+    pub fn new_scrypt(log_n: u8, r: u32, p: u32) -> Result<KeyEncryption, JsError> {
+        let params = cml_crypto::keystore::ScryptParams { log_n, r, p };
+        params.validate()?;
+        Ok(Self(cml_crypto::keystore::KeyEncryption::Scrypt(params)))
+    }
+}
+
+/// A password-encrypted signing key along with its unencrypted metadata
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct EncryptedKey(cml_crypto::keystore::EncryptedKey);
+
+#[wasm_bindgen]
+impl EncryptedKey {
+    /// * `created_at` - seconds since the UNIX epoch
+    pub fn encrypt_bip32_key(
+        key: &Bip32PrivateKey,
+        password: &[u8],
+        encryption: &KeyEncryption,
+        derivation_path: Option<Vec<u32>>,
+        created_at: u64,
+    ) -> Result<EncryptedKey, JsError> {
+        use cml_crypto::RawBytesEncoding;
+        let key = cml_crypto::Bip32PrivateKey::from_raw_bytes(key.as_ref().to_raw_bytes())
+            .map(cml_crypto::keystore::SigningKey::from)?;
+        cml_crypto::keystore::EncryptedKey::encrypt(
+            &key,
+            password,
+            encryption.0,
+            derivation_path,
+            created_at,
+        )
+        .map(Self)
+        .map_err(Into::into)
+    }
+
+    /// * `created_at` - seconds since the UNIX epoch
+    pub fn encrypt_private_key(
+        key: &PrivateKey,
+        password: &[u8],
+        encryption: &KeyEncryption,
+        created_at: u64,
+    ) -> Result<EncryptedKey, JsError> {
+        use cml_crypto::RawBytesEncoding;
+        let key = cml_crypto::PrivateKey::from_raw_bytes(key.as_ref().to_raw_bytes())
+            .map(cml_crypto::keystore::SigningKey::from)?;
+        cml_crypto::keystore::EncryptedKey::encrypt(&key, password, encryption.0, None, created_at)
+            .map(Self)
+            .map_err(Into::into)
+    }
+
+    /// Decrypts a BIP32 key. Fails if the stored key is not a BIP32 key.
+    pub fn decrypt_bip32_key(&self, password: &[u8]) -> Result<Bip32PrivateKey, JsError> {
+        match self.0.decrypt(password)? {
+            cml_crypto::keystore::SigningKey::Bip32(key) => Ok(key.into()),
+            cml_crypto::keystore::SigningKey::Raw(_) => {
+                Err(JsError::new("Encrypted key is not a BIP32 key"))
+            }
+        }
+    }
+
+    /// Decrypts the key. BIP32 keys are returned as their raw (non-BIP32) key.
+    pub fn decrypt_private_key(&self, password: &[u8]) -> Result<PrivateKey, JsError> {
+        match self.0.decrypt(password)? {
+            cml_crypto::keystore::SigningKey::Bip32(key) => Ok(key.to_raw_key().into()),
+            cml_crypto::keystore::SigningKey::Raw(key) => Ok(key.into()),
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.0.metadata.public_key.clone().into()
+    }
+
+    pub fn bip32_public_key(&self) -> Result<Option<Bip32PublicKey>, JsError> {
+        Ok(self.0.metadata.bip32_public_key()?.map(Into::into))
+    }
+
+    pub fn key_hash(&self) -> Ed25519KeyHash {
+        self.0.key_hash().into()
+    }
+
+    pub fn derivation_path(&self) -> Option<Vec<u32>> {
+        self.0.metadata.derivation_path.clone()
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.0.metadata.created_at
+    }
+
+    /// cardano-cli TextEnvelope JSON
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    /// cardano-cli TextEnvelope JSON
+    pub fn from_json(json: &str) -> Result<EncryptedKey, JsError> {
+        cml_crypto::keystore::EncryptedKey::from_json(json)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+impl From<cml_crypto::keystore::EncryptedKey> for EncryptedKey {
+    fn from(native: cml_crypto::keystore::EncryptedKey) -> Self {
+        Self(native)
+    }
+}
+
+impl From<EncryptedKey> for cml_crypto::keystore::EncryptedKey {
+    fn from(wasm: EncryptedKey) -> Self {
+        wasm.0
+    }
+}
+
+impl AsRef<cml_crypto::keystore::EncryptedKey> for EncryptedKey {
+    fn as_ref(&self) -> &cml_crypto::keystore::EncryptedKey {
+        &self.0
+    }
+}
@@ -3,6 +3,8 @@ use wasm_bindgen::prelude::{wasm_bindgen, JsError};
 use cml_crypto::RawBytesEncoding;
 
 pub mod emip3;
+pub mod keystore;
+pub mod text_envelope;
 
 #[wasm_bindgen]
 pub struct Bip32PrivateKey(cml_crypto::Bip32PrivateKey);
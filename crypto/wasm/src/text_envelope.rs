@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use cml_crypto::text_envelope::{FromTextEnvelope, ToTextEnvelope};
+
+use crate::{Bip32PrivateKey, Bip32PublicKey, PrivateKey, PublicKey};
+
+#[wasm_bindgen]
+impl PublicKey {
+    /// cardano-cli `.vkey` TextEnvelope JSON (as a payment verification key)
+    pub fn to_text_envelope_json(&self) -> String {
+        self.0.to_text_envelope_json()
+    }
+
+    /// cardano-cli `.vkey` TextEnvelope JSON of any key role
+    pub fn from_text_envelope_json(json: &str) -> Result<PublicKey, JsError> {
+        cml_crypto::PublicKey::from_text_envelope_json(json)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+#[wasm_bindgen]
+impl Bip32PublicKey {
+    /// cardano-cli `.vkey` TextEnvelope JSON (as a payment extended verification key)
+    pub fn to_text_envelope_json(&self) -> String {
+        self.0.to_text_envelope_json()
+    }
+
+    /// cardano-cli `.vkey` TextEnvelope JSON of any key role
+    pub fn from_text_envelope_json(json: &str) -> Result<Bip32PublicKey, JsError> {
+        cml_crypto::Bip32PublicKey::from_text_envelope_json(json)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+#[wasm_bindgen]
+impl Bip32PrivateKey {
+    /// cardano-cli `.skey` TextEnvelope JSON (as a payment extended signing key)
+    pub fn to_text_envelope_json(&self) -> String {
+        self.0.to_text_envelope_json()
+    }
+
+    /// cardano-cli `.skey` TextEnvelope JSON of any key role
+    pub fn from_text_envelope_json(json: &str) -> Result<Bip32PrivateKey, JsError> {
+        cml_crypto::Bip32PrivateKey::from_text_envelope_json(json)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+#[wasm_bindgen]
+impl PrivateKey {
+    /// cardano-cli `.skey` TextEnvelope JSON (as a payment signing key).
+    /// Extended keys use the 128-byte xprv format with a zeroed chaincode.
+    pub fn to_text_envelope_json(&self) -> String {
+        self.0.to_text_envelope_json()
+    }
+
+    /// cardano-cli `.skey` TextEnvelope JSON of any key role.
+    /// Extended (BIP32) keys are converted to their raw key.
+    pub fn from_text_envelope_json(json: &str) -> Result<PrivateKey, JsError> {
+        cml_crypto::PrivateKey::from_text_envelope_json(json)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
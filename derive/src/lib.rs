@@ -0,0 +1,352 @@
+//! Derive macros for `cml_chain::plutus::conversion::{ToPlutusData, FromPlutusData}`.
+//!
+//! ```ignore
+//! #[derive(ToPlutusData, FromPlutusData)]
+//! struct Order {
+//!     owner: Ed25519KeyHash,
+//!     #[plutus(bytes)]
+//!     asset_name: Vec<u8>,
+//!     amounts: BTreeMap<ScriptHash, u64>,
+//! }
+//!
+//! #[derive(ToPlutusData, FromPlutusData)]
+//! enum Action {
+//!     Cancel,
+//!     #[plutus(constr = 2)]
+//!     Fill { amount: u64 },
+//! }
+//! ```
+//!
+//! * Structs and enum variants become constructors with their fields in declaration order.
+//!   The constructor index is 0 for structs and the variant's position for enums,
+//!   overridable with `#[plutus(constr = N)]`.
+//! * Tuple structs with a single field (newtypes) are represented as that field directly
+//!   unless `#[plutus(constr = N)]` is given.
+//! * `#[plutus(bytes)]` on a `Vec<u8>` field represents it as bytes instead of a list of integers.
+//!
+//! Generated code refers to the `cml_chain` crate so it must be a dependency.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Fields, Ident, LitInt};
+
+#[proc_macro_derive(ToPlutusData, attributes(plutus))]
+pub fn derive_to_plutus_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_plutus_data(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FromPlutusData, attributes(plutus))]
+pub fn derive_from_plutus_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_plutus_data(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn conversion() -> TokenStream2 {
+    quote!(::cml_chain::plutus::conversion)
+}
+
+/// `#[plutus(constr = N)]` on a struct or enum variant
+fn constr_attr(attrs: &[Attribute]) -> syn::Result<Option<u64>> {
+    let mut constr = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("plutus")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("constr") {
+                let index: LitInt = meta.value()?.parse()?;
+                constr = Some(index.base10_parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `constr = N`"))
+            }
+        })?;
+    }
+    Ok(constr)
+}
+
+/// `#[plutus(bytes)]` on a field
+fn bytes_attr(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut bytes = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("plutus")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bytes") {
+                bytes = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `bytes`"))
+            }
+        })?;
+    }
+    Ok(bytes)
+}
+
+struct Field {
+    /// variable the field is bound to when destructuring
+    binding: Ident,
+    /// for named fields
+    name: Option<Ident>,
+    ty: syn::Type,
+    bytes: bool,
+}
+
+impl Field {
+    fn all(fields: &Fields) -> syn::Result<Vec<Field>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                Ok(Field {
+                    binding: field
+                        .ident
+                        .clone()
+                        .unwrap_or_else(|| format_ident!("__field{}", i)),
+                    name: field.ident.clone(),
+                    ty: field.ty.clone(),
+                    bytes: bytes_attr(&field.attrs)?,
+                })
+            })
+            .collect()
+    }
+
+    fn encode(&self) -> TokenStream2 {
+        let conversion = conversion();
+        let binding = &self.binding;
+        if self.bytes {
+            quote!(#conversion::bytes_to_plutus_data(#binding))
+        } else {
+            quote!(#conversion::ToPlutusData::to_plutus_data(#binding))
+        }
+    }
+
+    /// Converts `data` without annotating the error's path
+    fn decode(&self, data: TokenStream2) -> TokenStream2 {
+        let conversion = conversion();
+        let ty = &self.ty;
+        if self.bytes {
+            quote!(#conversion::bytes_from_plutus_data(#data))
+        } else {
+            quote!(<#ty as #conversion::FromPlutusData>::from_plutus_data(#data))
+        }
+    }
+
+    fn at_path(&self, index: usize) -> TokenStream2 {
+        match &self.name {
+            Some(name) => {
+                let name = name.to_string();
+                quote!(.at_field(#name))
+            }
+            None => quote!(.at_index(#index)),
+        }
+    }
+}
+
+/// Pattern destructuring `path` into the fields' bindings
+fn pattern(path: TokenStream2, fields: &Fields, bound: &[Field]) -> TokenStream2 {
+    let bindings = bound.iter().map(|field| &field.binding);
+    match fields {
+        Fields::Named(_) => quote!(#path { #(#bindings),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+/// Expression constructing `path` from the constructor's `fields` slice
+fn construct(
+    path: TokenStream2,
+    fields: &Fields,
+    bound: &[Field],
+    variant: Option<&Ident>,
+) -> TokenStream2 {
+    let in_variant = variant.map(|variant| {
+        let variant = variant.to_string();
+        quote!(.in_variant(#variant))
+    });
+    let values = bound.iter().enumerate().map(|(i, field)| {
+        let from = field.decode(quote!(&fields[#i]));
+        let at_path = field.at_path(i);
+        let value = quote!(#from.map_err(|e| e #at_path #in_variant)?);
+        match &field.name {
+            Some(name) => quote!(#name: #value),
+            None => value,
+        }
+    });
+    match fields {
+        Fields::Named(_) => quote!(#path { #(#values),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#values),* )),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+fn is_newtype(fields: &Fields, constr: Option<u64>) -> bool {
+    matches!(fields, Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1) && constr.is_none()
+}
+
+fn generics_with_bound(input: &DeriveInput, bound: syn::Path) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+/// Constructor index of each variant, checking for duplicates
+fn variant_indices(data: &syn::DataEnum) -> syn::Result<Vec<u64>> {
+    let mut indices = Vec::new();
+    for (i, variant) in data.variants.iter().enumerate() {
+        let index = constr_attr(&variant.attrs)?.unwrap_or(i as u64);
+        if indices.contains(&index) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("duplicate constructor index {index}"),
+            ));
+        }
+        indices.push(index);
+    }
+    Ok(indices)
+}
+
+fn to_plutus_data(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let conversion = conversion();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let constr = constr_attr(&input.attrs)?;
+            let fields = Field::all(&data.fields)?;
+            let pattern = pattern(quote!(Self), &data.fields, &fields);
+            if is_newtype(&data.fields, constr) {
+                let value = fields[0].encode();
+                quote! {
+                    let #pattern = self;
+                    #value
+                }
+            } else {
+                let index = constr.unwrap_or(0);
+                let values = fields.iter().map(Field::encode);
+                quote! {
+                    let #pattern = self;
+                    #conversion::constr_to_plutus_data(#index, vec![#(#values),*])
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let indices = variant_indices(data)?;
+            let arms = data
+                .variants
+                .iter()
+                .zip(indices)
+                .map(|(variant, index)| {
+                    let ident = &variant.ident;
+                    let fields = Field::all(&variant.fields)?;
+                    let pattern = pattern(quote!(Self::#ident), &variant.fields, &fields);
+                    let values = fields.iter().map(Field::encode);
+                    Ok(quote! {
+                        #pattern => #conversion::constr_to_plutus_data(#index, vec![#(#values),*]),
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "ToPlutusData can't be derived for unions",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let generics = generics_with_bound(input, parse_quote!(#conversion::ToPlutusData));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #conversion::ToPlutusData for #ident #ty_generics #where_clause {
+            fn to_plutus_data(&self) -> ::cml_chain::plutus::PlutusData {
+                #body
+            }
+        }
+    })
+}
+
+fn from_plutus_data(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let conversion = conversion();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let constr = constr_attr(&input.attrs)?;
+            let fields = Field::all(&data.fields)?;
+            if is_newtype(&data.fields, constr) {
+                let value = fields[0].decode(quote!(data));
+                quote!(#value.map(Self))
+            } else {
+                let index = constr.unwrap_or(0);
+                let count = fields.len();
+                let value = construct(quote!(Self), &data.fields, &fields, None);
+                let fields_binding = if count == 0 {
+                    quote!(_)
+                } else {
+                    quote!(fields)
+                };
+                quote! {
+                    let #fields_binding = #conversion::constr_fields_from_plutus_data(data, #index, #count)?;
+                    Ok(#value)
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let indices = variant_indices(data)?;
+            let arms = data
+                .variants
+                .iter()
+                .zip(indices.iter())
+                .map(|(variant, index)| {
+                    let ident = &variant.ident;
+                    let name = ident.to_string();
+                    let fields = Field::all(&variant.fields)?;
+                    let count = fields.len();
+                    let value =
+                        construct(quote!(Self::#ident), &variant.fields, &fields, Some(ident));
+                    Ok(quote! {
+                        #index => {
+                            #conversion::check_field_count(fields, #count)
+                                .map_err(|e| e.in_variant(#name))?;
+                            Ok(#value)
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                let (alternative, fields) = #conversion::constr_from_plutus_data(data)?;
+                match alternative {
+                    #(#arms)*
+                    found => Err(#conversion::PlutusDataErrorKind::UnknownConstructor {
+                        expected: vec![#(#indices),*],
+                        found,
+                    }
+                    .into()),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "FromPlutusData can't be derived for unions",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let generics = generics_with_bound(input, parse_quote!(#conversion::FromPlutusData));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #conversion::FromPlutusData for #ident #ty_generics #where_clause {
+            fn from_plutus_data(
+                data: &::cml_chain::plutus::PlutusData,
+            ) -> ::std::result::Result<Self, #conversion::PlutusDataError> {
+                #body
+            }
+        }
+    })
+}
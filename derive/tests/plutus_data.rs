@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use cml_chain::plutus::conversion::{FromPlutusData, PlutusDataErrorKind, ToPlutusData};
+use cml_chain::plutus::{ConstrPlutusData, PlutusData};
+use cml_chain::utils::BigInteger;
+use cml_crypto::{Ed25519KeyHash, ScriptHash};
+use cml_derive::{FromPlutusData, ToPlutusData};
+
+#[derive(Clone, Debug, PartialEq, ToPlutusData, FromPlutusData)]
+struct Lovelace(u64);
+
+#[derive(Clone, Debug, PartialEq, ToPlutusData, FromPlutusData)]
+struct Order {
+    owner: Ed25519KeyHash,
+    #[plutus(bytes)]
+    asset_name: Vec<u8>,
+    amounts: BTreeMap<ScriptHash, Lovelace>,
+    deadline: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, ToPlutusData, FromPlutusData)]
+enum Action<T> {
+    Cancel,
+    Fill(Lovelace, T),
+    #[plutus(constr = 5)]
+    Update {
+        orders: Vec<Order>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, ToPlutusData, FromPlutusData)]
+#[plutus(constr = 1)]
+struct Wrapped(BigInteger);
+
+#[derive(Clone, Debug, PartialEq, ToPlutusData, FromPlutusData)]
+struct Marker;
+
+fn constr(alternative: u64, fields: Vec<PlutusData>) -> PlutusData {
+    PlutusData::new_constr_plutus_data(ConstrPlutusData::new(alternative, fields))
+}
+
+fn order() -> Order {
+    let mut amounts = BTreeMap::new();
+    amounts.insert(ScriptHash::from([1; 28]), Lovelace(5_000_000));
+    Order {
+        owner: Ed25519KeyHash::from([2; 28]),
+        asset_name: b"token".to_vec(),
+        amounts,
+        deadline: None,
+    }
+}
+
+#[test]
+fn representation() {
+    let order_data = order().to_plutus_data();
+    let (owner, asset_name, amounts, deadline) = match &order_data {
+        PlutusData::ConstrPlutusData(constr) if constr.alternative == 0 => (
+            &constr.fields[0],
+            &constr.fields[1],
+            &constr.fields[2],
+            &constr.fields[3],
+        ),
+        _ => panic!("not constructor 0"),
+    };
+    assert_eq!(*owner, PlutusData::new_bytes(vec![2; 28]));
+    assert_eq!(*asset_name, PlutusData::new_bytes(b"token".to_vec()));
+    // newtypes are transparent
+    let amount = match amounts {
+        PlutusData::Map(map) => map.get(&PlutusData::new_bytes(vec![1; 28])).unwrap(),
+        _ => panic!("not a map"),
+    };
+    assert_eq!(
+        *amount,
+        PlutusData::new_integer(BigInteger::from(5_000_000u64))
+    );
+    assert_eq!(*deadline, constr(1, vec![]));
+    assert_eq!(Action::<u8>::Cancel.to_plutus_data(), constr(0, vec![]));
+    assert_eq!(
+        Action::<u8>::Update { orders: vec![] }.to_plutus_data(),
+        constr(5, vec![PlutusData::new_list(vec![])])
+    );
+    assert_eq!(
+        Wrapped(BigInteger::from(3u64)).to_plutus_data(),
+        constr(1, vec![PlutusData::new_integer(BigInteger::from(3u64))])
+    );
+    assert_eq!(Marker.to_plutus_data(), constr(0, vec![]));
+}
+
+#[test]
+fn round_trip() {
+    let actions = vec![
+        Action::Cancel,
+        Action::Fill(Lovelace(10), true),
+        Action::Update {
+            orders: vec![order(), order()],
+        },
+    ];
+    let data = actions.to_plutus_data();
+    assert_eq!(
+        Vec::<Action<bool>>::from_plutus_data(&data).unwrap(),
+        actions
+    );
+    assert_eq!(
+        Marker::from_plutus_data(&Marker.to_plutus_data()).unwrap(),
+        Marker
+    );
+}
+
+#[test]
+fn error_paths() {
+    let mut bad_order = order().to_plutus_data();
+    if let PlutusData::ConstrPlutusData(constr) = &mut bad_order {
+        constr.fields[0] = PlutusData::new_bytes(vec![0; 3]);
+    }
+    let data = PlutusData::new_list(vec![
+        Action::<bool>::Cancel.to_plutus_data(),
+        constr(
+            5,
+            vec![PlutusData::new_list(vec![
+                order().to_plutus_data(),
+                bad_order,
+            ])],
+        ),
+    ]);
+    let err = Vec::<Action<bool>>::from_plutus_data(&data).unwrap_err();
+    assert_eq!(err.path_string(), "$[1]::Update.orders[1].owner");
+    assert_eq!(
+        err.kind,
+        PlutusDataErrorKind::WrongByteLength {
+            expected: 28,
+            found: 3
+        }
+    );
+
+    let err = Action::<bool>::from_plutus_data(&constr(3, vec![])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "$: unknown constructor 3, expected one of [0, 1, 5]"
+    );
+
+    let err = Action::<bool>::from_plutus_data(&constr(1, vec![])).unwrap_err();
+    assert_eq!(err.to_string(), "$::Fill: expected 2 fields, found 0");
+}
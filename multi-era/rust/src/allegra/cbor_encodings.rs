@@ -3,6 +3,7 @@
 
 use cml_chain::address::RewardAccount;
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default)]
@@ -36,6 +37,7 @@ pub struct AllegraTransactionBodyEncoding {
     pub auxiliary_data_hash_key_encoding: Option<cbor_event::Sz>,
     pub validity_interval_start_encoding: Option<cbor_event::Sz>,
     pub validity_interval_start_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -53,6 +55,7 @@ pub struct AllegraTransactionWitnessSetEncoding {
     pub native_scripts_key_encoding: Option<cbor_event::Sz>,
     pub bootstrap_witnesses_encoding: LenEncoding,
     pub bootstrap_witnesses_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
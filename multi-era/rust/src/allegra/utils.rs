@@ -1,10 +1,96 @@
-use cml_chain::{auxdata::AuxiliaryData, transaction::TransactionWitnessSet};
+use cml_chain::{
+    auxdata::{AuxiliaryData, Metadata, ShelleyMaFormatAuxData},
+    certs::{
+        Credential, PoolRegistration, PoolRetirement, StakeDelegation, StakeDeregistration,
+        StakeRegistration,
+    },
+    crypto::{BootstrapWitness, Vkeywitness},
+    transaction::{NativeScript, TransactionInput, TransactionWitnessSet},
+    utils::withdrawals_field_names,
+};
 
-use super::{AllegraAuxiliaryData, AllegraTransactionBody, AllegraTransactionWitnessSet};
+use super::{
+    AllegraAuxiliaryData, AllegraBlock, AllegraCertificate, AllegraTransaction,
+    AllegraTransactionBody, AllegraTransactionWitnessSet, MoveInstantaneousReward,
+    MoveInstantaneousRewardsCert,
+};
+use crate::shelley::{
+    GenesisKeyDelegation, ShelleyHeader, ShelleyTransactionOutput, ShelleyUpdate,
+};
 
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_core::serialization::Serialize;
 use cml_crypto::{blake2b256, TransactionHash};
 
+cml_core::impl_unknown_fields!(AllegraTransactionBody, AllegraTransactionWitnessSet);
+
+cml_core::impl_cbor_field_names!(AllegraBlock [
+    header: ShelleyHeader::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(AllegraTransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(AllegraTransactionWitnessSet::cbor_field_names()),
+    auxiliary_data_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", AllegraAuxiliaryData::cbor_field_names()),
+    ),
+]);
+cml_core::impl_cbor_field_names!(AllegraTransaction [
+    body: AllegraTransactionBody::cbor_field_names(),
+    witness_set: AllegraTransactionWitnessSet::cbor_field_names(),
+    auxiliary_data: AllegraAuxiliaryData::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(AllegraTransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(ShelleyTransactionOutput::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(AllegraCertificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    6 => update: ShelleyUpdate::cbor_field_names(),
+    7 => auxiliary_data_hash,
+    8 => validity_interval_start,
+});
+cml_core::impl_cbor_field_names!(AllegraTransactionWitnessSet {
+    0 => vkeywitnesses: FieldNames::elements(Vkeywitness::cbor_field_names()),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => bootstrap_witnesses: FieldNames::elements(BootstrapWitness::cbor_field_names()),
+});
+
+impl CBORFieldNames for AllegraAuxiliaryData {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            Metadata::cbor_field_names(),
+            ShelleyMaFormatAuxData::cbor_field_names(),
+        ])
+    }
+}
+
+impl CBORFieldNames for AllegraCertificate {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, StakeRegistration::cbor_field_names()),
+            (1, StakeDeregistration::cbor_field_names()),
+            (2, StakeDelegation::cbor_field_names()),
+            (3, PoolRegistration::cbor_field_names()),
+            (4, PoolRetirement::cbor_field_names()),
+            (5, GenesisKeyDelegation::cbor_field_names()),
+            (6, MoveInstantaneousRewardsCert::cbor_field_names()),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(MoveInstantaneousRewardsCert [
+    6,
+    move_instantaneous_reward: MoveInstantaneousReward::cbor_field_names(),
+]);
+// the action is either the rewards per credential or a coin to move to the other pot
+cml_core::impl_cbor_field_names!(MoveInstantaneousReward [
+    pot,
+    action: FieldNames::entries(
+        ("stake_credential", Credential::cbor_field_names()),
+        ("delta_coin", FieldNames::None),
+    ),
+]);
+
 impl AllegraTransactionBody {
     pub fn hash(&self) -> TransactionHash {
         blake2b256(&self.to_cbor_bytes()).into()
@@ -3,6 +3,7 @@
 
 use cml_chain::{address::RewardAccount, assets::AssetName, crypto::GenesisHash, PolicyId};
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default)]
@@ -22,6 +23,7 @@ pub struct AlonzoCostmdlsEncoding {
     pub orig_deser_order: Vec<usize>,
     pub plutus_v1_encoding: LenEncoding,
     pub plutus_v1_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -34,6 +36,7 @@ pub struct AlonzoFormatAuxDataEncoding {
     pub native_scripts_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v1_scripts_encoding: LenEncoding,
     pub plutus_v1_scripts_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -78,6 +81,7 @@ pub struct AlonzoProtocolParamUpdateEncoding {
     pub collateral_percentage_key_encoding: Option<cbor_event::Sz>,
     pub max_collateral_inputs_encoding: Option<cbor_event::Sz>,
     pub max_collateral_inputs_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -115,6 +119,7 @@ pub struct AlonzoTransactionBodyEncoding {
     pub required_signers_elem_encodings: Vec<StringEncoding>,
     pub required_signers_key_encoding: Option<cbor_event::Sz>,
     pub network_id_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -138,6 +143,7 @@ pub struct AlonzoTransactionWitnessSetEncoding {
     pub plutus_datums_key_encoding: Option<cbor_event::Sz>,
     pub redeemers_encoding: LenEncoding,
     pub redeemers_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -1,13 +1,125 @@
 use cml_chain::{
-    auxdata::{AuxiliaryData, ConwayFormatAuxData},
-    transaction::TransactionWitnessSet,
+    assets::Mint,
+    auxdata::{AuxiliaryData, ConwayFormatAuxData, Metadata, ShelleyMaFormatAuxData},
+    crypto::{BootstrapWitness, Nonce, Vkeywitness},
+    plutus::{ExUnitPrices, ExUnits, Redeemer},
+    transaction::{AlonzoFormatTxOut, NativeScript, TransactionInput, TransactionWitnessSet},
+    utils::withdrawals_field_names,
+    Rational, UnitInterval,
 };
 
-use super::{AlonzoAuxiliaryData, AlonzoTransactionBody, AlonzoTransactionWitnessSet};
+use super::{
+    AlonzoAuxiliaryData, AlonzoBlock, AlonzoCostmdls, AlonzoFormatAuxData,
+    AlonzoProtocolParamUpdate, AlonzoTransaction, AlonzoTransactionBody,
+    AlonzoTransactionWitnessSet, AlonzoUpdate,
+};
+use crate::allegra::AllegraCertificate;
+use crate::shelley::{ProtocolVersionStruct, ShelleyHeader};
 
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_core::serialization::Serialize;
 use cml_crypto::{blake2b256, TransactionHash};
 
+cml_core::impl_unknown_fields!(
+    AlonzoCostmdls,
+    AlonzoFormatAuxData,
+    AlonzoProtocolParamUpdate,
+    AlonzoTransactionBody,
+    AlonzoTransactionWitnessSet
+);
+
+cml_core::impl_cbor_field_names!(AlonzoBlock [
+    header: ShelleyHeader::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(AlonzoTransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(AlonzoTransactionWitnessSet::cbor_field_names()),
+    auxiliary_data_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", AlonzoAuxiliaryData::cbor_field_names()),
+    ),
+    invalid_transactions,
+]);
+cml_core::impl_cbor_field_names!(AlonzoTransaction [
+    body: AlonzoTransactionBody::cbor_field_names(),
+    witness_set: AlonzoTransactionWitnessSet::cbor_field_names(),
+    is_valid,
+    auxiliary_data: AlonzoAuxiliaryData::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(AlonzoTransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(AlonzoFormatTxOut::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(AllegraCertificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    6 => update: AlonzoUpdate::cbor_field_names(),
+    7 => auxiliary_data_hash,
+    8 => validity_interval_start,
+    9 => mint: Mint::cbor_field_names(),
+    11 => script_data_hash,
+    13 => collateral_inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    14 => required_signers,
+    15 => network_id,
+});
+cml_core::impl_cbor_field_names!(AlonzoTransactionWitnessSet {
+    0 => vkeywitnesses: FieldNames::elements(Vkeywitness::cbor_field_names()),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => bootstrap_witnesses: FieldNames::elements(BootstrapWitness::cbor_field_names()),
+    3 => plutus_v1_scripts,
+    4 => plutus_datums,
+    5 => redeemers: FieldNames::elements(Redeemer::cbor_field_names()),
+});
+
+impl CBORFieldNames for AlonzoAuxiliaryData {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            Metadata::cbor_field_names(),
+            ShelleyMaFormatAuxData::cbor_field_names(),
+            AlonzoFormatAuxData::cbor_field_names(),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(AlonzoFormatAuxData tag 259 {
+    0 => metadata: Metadata::cbor_field_names(),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => plutus_v1_scripts,
+});
+
+cml_core::impl_cbor_field_names!(AlonzoUpdate [
+    proposed_protocol_parameter_updates: FieldNames::entries(
+        ("genesis_hash", FieldNames::None),
+        ("", AlonzoProtocolParamUpdate::cbor_field_names()),
+    ),
+    epoch,
+]);
+cml_core::impl_cbor_field_names!(AlonzoProtocolParamUpdate {
+    0 => minfee_a,
+    1 => minfee_b,
+    2 => max_block_body_size,
+    3 => max_transaction_size,
+    4 => max_block_header_size,
+    5 => key_deposit,
+    6 => pool_deposit,
+    7 => maximum_epoch,
+    8 => n_opt,
+    9 => pool_pledge_influence: Rational::cbor_field_names(),
+    10 => expansion_rate: UnitInterval::cbor_field_names(),
+    11 => treasury_growth_rate: UnitInterval::cbor_field_names(),
+    12 => decentralization_constant: UnitInterval::cbor_field_names(),
+    13 => extra_entropy: Nonce::cbor_field_names(),
+    14 => protocol_version: ProtocolVersionStruct::cbor_field_names(),
+    16 => min_pool_cost,
+    17 => ada_per_utxo_byte,
+    18 => cost_models_for_script_languages: AlonzoCostmdls::cbor_field_names(),
+    19 => execution_costs: ExUnitPrices::cbor_field_names(),
+    20 => max_tx_ex_units: ExUnits::cbor_field_names(),
+    21 => max_block_ex_units: ExUnits::cbor_field_names(),
+    22 => max_value_size,
+    23 => collateral_percentage,
+    24 => max_collateral_inputs,
+});
+cml_core::impl_cbor_field_names!(AlonzoCostmdls { 0 => plutus_v1 });
+
 impl AlonzoTransactionBody {
     pub fn hash(&self) -> TransactionHash {
         blake2b256(&self.to_cbor_bytes()).into()
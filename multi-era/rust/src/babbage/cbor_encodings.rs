@@ -3,6 +3,7 @@
 
 use cml_chain::{address::RewardAccount, assets::AssetName, PolicyId};
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use cml_crypto::GenesisHash;
 use std::collections::BTreeMap;
 
@@ -25,6 +26,7 @@ pub struct BabbageCostModelsEncoding {
     pub plutus_v1_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v2_encoding: LenEncoding,
     pub plutus_v2_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -39,6 +41,7 @@ pub struct BabbageFormatAuxDataEncoding {
     pub plutus_v1_scripts_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v2_scripts_encoding: LenEncoding,
     pub plutus_v2_scripts_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -51,6 +54,7 @@ pub struct BabbageFormatTxOutEncoding {
     pub script_reference_tag_encoding: Option<cbor_event::Sz>,
     pub script_reference_bytes_encoding: StringEncoding,
     pub script_reference_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -93,6 +97,7 @@ pub struct BabbageProtocolParamUpdateEncoding {
     pub collateral_percentage_key_encoding: Option<cbor_event::Sz>,
     pub max_collateral_inputs_encoding: Option<cbor_event::Sz>,
     pub max_collateral_inputs_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -135,6 +140,7 @@ pub struct BabbageTransactionBodyEncoding {
     pub total_collateral_key_encoding: Option<cbor_event::Sz>,
     pub reference_inputs_encoding: LenEncoding,
     pub reference_inputs_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -160,6 +166,7 @@ pub struct BabbageTransactionWitnessSetEncoding {
     pub redeemers_key_encoding: Option<cbor_event::Sz>,
     pub plutus_v2_scripts_encoding: LenEncoding,
     pub plutus_v2_scripts_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
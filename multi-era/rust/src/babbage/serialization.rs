@@ -10,6 +10,8 @@ use cml_chain::assets::AssetName;
 use cml_chain::PolicyId;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::CborValue;
 use cml_crypto::Ed25519KeyHash;
 use cml_crypto::RawBytesEncoding;
 use std::io::{BufRead, Seek, SeekFrom, Write};
@@ -303,6 +305,26 @@ impl Serialize for BabbageCostModels {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageCostModels {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -316,7 +338,7 @@ impl Serialize for BabbageCostModels {
                     } + match &self.plutus_v2 {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -336,7 +358,9 @@ impl Serialize for BabbageCostModels {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.plutus_v1 {
@@ -401,6 +425,8 @@ impl Serialize for BabbageCostModels {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -416,6 +442,7 @@ impl Deserialize for BabbageCostModels {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut plutus_v1_encoding = LenEncoding::default();
             let mut plutus_v1_key_encoding = None;
             let mut plutus_v1 = None;
@@ -429,74 +456,77 @@ impl Deserialize for BabbageCostModels {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if plutus_v1.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_plutus_v1, tmp_plutus_v1_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_arr.push(Int::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_arr, plutus_v1_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1"))?;
-                            plutus_v1 = Some(tmp_plutus_v1);
-                            plutus_v1_encoding = tmp_plutus_v1_encoding;
-                            plutus_v1_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if plutus_v2.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_plutus_v2, tmp_plutus_v2_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v2_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_arr.push(Int::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_arr, plutus_v2_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v2"))?;
-                            plutus_v2 = Some(tmp_plutus_v2);
-                            plutus_v2_encoding = tmp_plutus_v2_encoding;
-                            plutus_v2_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1, tmp_plutus_v1_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Int::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1"))?;
+                                plutus_v1 = Some(tmp_plutus_v1);
+                                plutus_v1_encoding = tmp_plutus_v1_encoding;
+                                plutus_v1_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2, tmp_plutus_v2_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Int::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2"))?;
+                                plutus_v2 = Some(tmp_plutus_v2);
+                                plutus_v2_encoding = tmp_plutus_v2_encoding;
+                                plutus_v2_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -524,6 +554,7 @@ impl Deserialize for BabbageCostModels {
                     plutus_v1_encoding,
                     plutus_v2_key_encoding,
                     plutus_v2_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -536,6 +567,26 @@ impl Serialize for BabbageFormatAuxData {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageFormatAuxData {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_tag_sz(
             259u64,
@@ -566,7 +617,7 @@ impl Serialize for BabbageFormatAuxData {
                     } + match &self.plutus_v2_scripts {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -592,7 +643,9 @@ impl Serialize for BabbageFormatAuxData {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.metadata {
@@ -703,6 +756,8 @@ impl Serialize for BabbageFormatAuxData {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -728,6 +783,7 @@ impl Deserialize for BabbageFormatAuxData {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut metadata_key_encoding = None;
             let mut metadata = None;
             let mut native_scripts_encoding = LenEncoding::default();
@@ -746,119 +802,125 @@ impl Deserialize for BabbageFormatAuxData {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if metadata.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let tmp_metadata = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Metadata::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("metadata"))?;
-                            metadata = Some(tmp_metadata);
-                            metadata_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if native_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_native_scripts, tmp_native_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut native_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let native_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (native_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        native_scripts_arr.push(NativeScript::deserialize(raw)?);
-                                    }
-                                    Ok((native_scripts_arr, native_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("native_scripts"))?;
-                            native_scripts = Some(tmp_native_scripts);
-                            native_scripts_encoding = tmp_native_scripts_encoding;
-                            native_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if plutus_v1_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_scripts_arr
-                                            .push(PlutusV1Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_scripts_arr, plutus_v1_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1_scripts"))?;
-                            plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
-                            plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
-                            plutus_v1_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if plutus_v2_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if metadata.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let tmp_metadata = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    let mut plutus_v2_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_scripts_arr
-                                            .push(PlutusV2Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_scripts_arr, plutus_v2_scripts_encoding))
+                                    Metadata::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("plutus_v2_scripts"))?;
-                            plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
-                            plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
-                            plutus_v2_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                .map_err(|e| e.annotate("metadata"))?;
+                                metadata = Some(tmp_metadata);
+                                metadata_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if native_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_native_scripts, tmp_native_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, NativeScript::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("native_scripts"))?;
+                                native_scripts = Some(tmp_native_scripts);
+                                native_scripts_encoding = tmp_native_scripts_encoding;
+                                native_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV1Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1_scripts"))?;
+                                plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
+                                plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
+                                plutus_v1_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV2Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2_scripts"))?;
+                                plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
+                                plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
+                                plutus_v2_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -892,6 +954,7 @@ impl Deserialize for BabbageFormatAuxData {
                     plutus_v1_scripts_encoding,
                     plutus_v2_scripts_key_encoding,
                     plutus_v2_scripts_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -904,6 +967,26 @@ impl Serialize for BabbageFormatTxOut {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageFormatTxOut {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -917,7 +1000,7 @@ impl Serialize for BabbageFormatTxOut {
                     } + match &self.script_reference {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -937,7 +1020,9 @@ impl Serialize for BabbageFormatTxOut {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -1026,6 +1111,8 @@ impl Serialize for BabbageFormatTxOut {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -1042,6 +1129,7 @@ impl Deserialize for BabbageFormatTxOut {
         read_len.read_elems(2)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut address_key_encoding = None;
             let mut address = None;
             let mut amount_key_encoding = None;
@@ -1059,86 +1147,142 @@ impl Deserialize for BabbageFormatTxOut {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if address.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let tmp_address = Address::deserialize(raw)
-                                .map_err(|e: DeserializeError| e.annotate("address"))?;
-                            address = Some(tmp_address);
-                            address_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if amount.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let tmp_amount = Value::deserialize(raw)
-                                .map_err(|e: DeserializeError| e.annotate("amount"))?;
-                            amount = Some(tmp_amount);
-                            amount_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if datum_option.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let tmp_datum_option = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                DatumOption::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("datum_option"))?;
-                            datum_option = Some(tmp_datum_option);
-                            datum_option_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if script_reference.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (
-                                tmp_script_reference,
-                                tmp_script_reference_tag_encoding,
-                                tmp_script_reference_bytes_encoding,
-                            ) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                match raw.tag_sz()? {
-                                    (24, tag_enc) => {
-                                        let (
-                                            script_reference_bytes,
-                                            script_reference_bytes_encoding,
-                                        ) = raw.bytes_sz()?;
-                                        let inner_de = &mut Deserializer::from(
-                                            std::io::Cursor::new(script_reference_bytes),
-                                        );
-                                        Ok((
-                                            BabbageScript::deserialize(inner_de)?,
-                                            Some(tag_enc),
-                                            StringEncoding::from(script_reference_bytes_encoding),
-                                        ))
-                                    }
-                                    (tag, _enc) => Err(DeserializeFailure::TagMismatch {
-                                        found: tag,
-                                        expected: 24,
-                                    }
-                                    .into()),
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if address.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
                                 }
-                            })()
-                            .map_err(|e| e.annotate("script_reference"))?;
-                            script_reference = Some(tmp_script_reference);
-                            script_reference_tag_encoding = tmp_script_reference_tag_encoding;
-                            script_reference_bytes_encoding = tmp_script_reference_bytes_encoding;
-                            script_reference_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                let tmp_address = Address::deserialize(raw)
+                                    .map_err(|e: DeserializeError| e.annotate("address"))?;
+                                address = Some(tmp_address);
+                                address_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if amount.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let tmp_amount = Value::deserialize(raw)
+                                    .map_err(|e: DeserializeError| e.annotate("amount"))?;
+                                amount = Some(tmp_amount);
+                                amount_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if datum_option.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let tmp_datum_option = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    DatumOption::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("datum_option"))?;
+                                datum_option = Some(tmp_datum_option);
+                                datum_option_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if script_reference.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_script_reference,
+                                    tmp_script_reference_tag_encoding,
+                                    tmp_script_reference_bytes_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    match raw.tag_sz()? {
+                                        (24, tag_enc) => {
+                                            let (
+                                                script_reference_bytes,
+                                                script_reference_bytes_encoding,
+                                            ) = raw.bytes_sz()?;
+                                            let inner_de = &mut Deserializer::from(
+                                                std::io::Cursor::new(script_reference_bytes),
+                                            );
+                                            Ok((
+                                                BabbageScript::deserialize(inner_de)?,
+                                                Some(tag_enc),
+                                                StringEncoding::from(
+                                                    script_reference_bytes_encoding,
+                                                ),
+                                            ))
+                                        }
+                                        (tag, _enc) => Err(DeserializeFailure::TagMismatch {
+                                            found: tag,
+                                            expected: 24,
+                                        }
+                                        .into()),
+                                    }
+                                })()
+                                .map_err(|e| e.annotate("script_reference"))?;
+                                script_reference = Some(tmp_script_reference);
+                                script_reference_tag_encoding = tmp_script_reference_tag_encoding;
+                                script_reference_bytes_encoding =
+                                    tmp_script_reference_bytes_encoding;
+                                script_reference_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -1178,6 +1322,7 @@ impl Deserialize for BabbageFormatTxOut {
                     script_reference_key_encoding,
                     script_reference_tag_encoding,
                     script_reference_bytes_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -1190,6 +1335,26 @@ impl Serialize for BabbageProtocolParamUpdate {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageProtocolParamUpdate {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -1263,7 +1428,7 @@ impl Serialize for BabbageProtocolParamUpdate {
                     } + match &self.max_collateral_inputs {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -1347,7 +1512,9 @@ impl Serialize for BabbageProtocolParamUpdate {
                     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
                 ]
             });
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.minfee_a {
@@ -1844,6 +2011,8 @@ impl Serialize for BabbageProtocolParamUpdate {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -1859,6 +2028,7 @@ impl Deserialize for BabbageProtocolParamUpdate {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut minfee_a_encoding = None;
             let mut minfee_a_key_encoding = None;
             let mut minfee_a = None;
@@ -1924,358 +2094,610 @@ impl Deserialize for BabbageProtocolParamUpdate {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if minfee_a.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_minfee_a, tmp_minfee_a_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("minfee_a"))?;
-                            minfee_a = Some(tmp_minfee_a);
-                            minfee_a_encoding = tmp_minfee_a_encoding;
-                            minfee_a_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if minfee_b.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_minfee_b, tmp_minfee_b_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("minfee_b"))?;
-                            minfee_b = Some(tmp_minfee_b);
-                            minfee_b_encoding = tmp_minfee_b_encoding;
-                            minfee_b_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if max_block_body_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_block_body_size"))?;
-                            max_block_body_size = Some(tmp_max_block_body_size);
-                            max_block_body_size_encoding = tmp_max_block_body_size_encoding;
-                            max_block_body_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if max_transaction_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_transaction_size"))?;
-                            max_transaction_size = Some(tmp_max_transaction_size);
-                            max_transaction_size_encoding = tmp_max_transaction_size_encoding;
-                            max_transaction_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_a.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_minfee_a, tmp_minfee_a_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_a"))?;
+                                minfee_a = Some(tmp_minfee_a);
+                                minfee_a_encoding = tmp_minfee_a_encoding;
+                                minfee_a_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_b.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_minfee_b, tmp_minfee_b_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_b"))?;
+                                minfee_b = Some(tmp_minfee_b);
+                                minfee_b_encoding = tmp_minfee_b_encoding;
+                                minfee_b_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_block_body_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_body_size"))?;
+                                max_block_body_size = Some(tmp_max_block_body_size);
+                                max_block_body_size_encoding = tmp_max_block_body_size_encoding;
+                                max_block_body_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_transaction_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_transaction_size"))?;
+                                max_transaction_size = Some(tmp_max_transaction_size);
+                                max_transaction_size_encoding = tmp_max_transaction_size_encoding;
+                                max_transaction_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
                         (4, key_enc) => {
-                            if max_block_header_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_max_block_header_size, tmp_max_block_header_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_block_header_size"))?;
-                            max_block_header_size = Some(tmp_max_block_header_size);
-                            max_block_header_size_encoding = tmp_max_block_header_size_encoding;
-                            max_block_header_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (5, key_enc) => {
-                            if key_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_key_deposit, tmp_key_deposit_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("key_deposit"))?;
-                            key_deposit = Some(tmp_key_deposit);
-                            key_deposit_encoding = tmp_key_deposit_encoding;
-                            key_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        }
-                        (6, key_enc) => {
-                            if pool_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let (tmp_pool_deposit, tmp_pool_deposit_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("pool_deposit"))?;
-                            pool_deposit = Some(tmp_pool_deposit);
-                            pool_deposit_encoding = tmp_pool_deposit_encoding;
-                            pool_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        }
-                        (7, key_enc) => {
-                            if maximum_epoch.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("maximum_epoch"))?;
-                            maximum_epoch = Some(tmp_maximum_epoch);
-                            maximum_epoch_encoding = tmp_maximum_epoch_encoding;
-                            maximum_epoch_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                4,
+                                key_enc,
+                                |raw, read_len| {
+                                    if max_block_header_size.is_some() {
+                                        return Err(
+                                            DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                        );
+                                    }
+                                    let (
+                                        tmp_max_block_header_size,
+                                        tmp_max_block_header_size_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_header_size"))?;
+                                    max_block_header_size = Some(tmp_max_block_header_size);
+                                    max_block_header_size_encoding =
+                                        tmp_max_block_header_size_encoding;
+                                    max_block_header_size_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(4);
+                                    Ok(())
+                                },
+                            )?
                         }
-                        (8, key_enc) => {
-                            if n_opt.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_n_opt, tmp_n_opt_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if key_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (tmp_key_deposit, tmp_key_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("key_deposit"))?;
+                                key_deposit = Some(tmp_key_deposit);
+                                key_deposit_encoding = tmp_key_deposit_encoding;
+                                key_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
+                                }
+                                let (tmp_pool_deposit, tmp_pool_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("pool_deposit"))?;
+                                pool_deposit = Some(tmp_pool_deposit);
+                                pool_deposit_encoding = tmp_pool_deposit_encoding;
+                                pool_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if maximum_epoch.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("maximum_epoch"))?;
+                                maximum_epoch = Some(tmp_maximum_epoch);
+                                maximum_epoch_encoding = tmp_maximum_epoch_encoding;
+                                maximum_epoch_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if n_opt.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
+                                }
+                                let (tmp_n_opt, tmp_n_opt_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("n_opt"))?;
+                                n_opt = Some(tmp_n_opt);
+                                n_opt_encoding = tmp_n_opt_encoding;
+                                n_opt_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_pledge_influence.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
+                                }
+                                let tmp_pool_pledge_influence =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        Rational::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("pool_pledge_influence"))?;
+                                pool_pledge_influence = Some(tmp_pool_pledge_influence);
+                                pool_pledge_influence_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (10, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            10,
+                            key_enc,
+                            |raw, read_len| {
+                                if expansion_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(10)).into()
+                                    );
+                                }
+                                let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    UnitInterval::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("n_opt"))?;
-                            n_opt = Some(tmp_n_opt);
-                            n_opt_encoding = tmp_n_opt_encoding;
-                            n_opt_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        }
-                        (9, key_enc) => {
-                            if pool_pledge_influence.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let tmp_pool_pledge_influence = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Rational::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("pool_pledge_influence"))?;
-                            pool_pledge_influence = Some(tmp_pool_pledge_influence);
-                            pool_pledge_influence_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        }
-                        (10, key_enc) => {
-                            if expansion_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(10)).into());
-                            }
-                            let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("expansion_rate"))?;
-                            expansion_rate = Some(tmp_expansion_rate);
-                            expansion_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(10);
-                        }
-                        (11, key_enc) => {
-                            if treasury_growth_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(11)).into());
-                            }
-                            let tmp_treasury_growth_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("treasury_growth_rate"))?;
-                            treasury_growth_rate = Some(tmp_treasury_growth_rate);
-                            treasury_growth_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(11);
-                        }
-                        (14, key_enc) => {
-                            if protocol_version.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(14)).into());
-                            }
-                            let tmp_protocol_version = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ProtocolVersionStruct::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("protocol_version"))?;
-                            protocol_version = Some(tmp_protocol_version);
-                            protocol_version_key_encoding = Some(key_enc);
-                            orig_deser_order.push(12);
-                        }
-                        (16, key_enc) => {
-                            if min_pool_cost.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(16)).into());
-                            }
-                            let (tmp_min_pool_cost, tmp_min_pool_cost_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("expansion_rate"))?;
+                                expansion_rate = Some(tmp_expansion_rate);
+                                expansion_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(10);
+                                Ok(())
+                            },
+                        )?,
+                        (11, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            11,
+                            key_enc,
+                            |raw, read_len| {
+                                if treasury_growth_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(11)).into()
+                                    );
+                                }
+                                let tmp_treasury_growth_rate =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        UnitInterval::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("treasury_growth_rate"))?;
+                                treasury_growth_rate = Some(tmp_treasury_growth_rate);
+                                treasury_growth_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(11);
+                                Ok(())
+                            },
+                        )?,
+                        (14, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            14,
+                            key_enc,
+                            |raw, read_len| {
+                                if protocol_version.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(14)).into()
+                                    );
+                                }
+                                let tmp_protocol_version = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    ProtocolVersionStruct::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("min_pool_cost"))?;
-                            min_pool_cost = Some(tmp_min_pool_cost);
-                            min_pool_cost_encoding = tmp_min_pool_cost_encoding;
-                            min_pool_cost_key_encoding = Some(key_enc);
-                            orig_deser_order.push(13);
-                        }
-                        (17, key_enc) => {
-                            if ada_per_utxo_byte.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(17)).into());
-                            }
-                            let (tmp_ada_per_utxo_byte, tmp_ada_per_utxo_byte_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("protocol_version"))?;
+                                protocol_version = Some(tmp_protocol_version);
+                                protocol_version_key_encoding = Some(key_enc);
+                                orig_deser_order.push(12);
+                                Ok(())
+                            },
+                        )?,
+                        (16, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            16,
+                            key_enc,
+                            |raw, read_len| {
+                                if min_pool_cost.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(16)).into()
+                                    );
+                                }
+                                let (tmp_min_pool_cost, tmp_min_pool_cost_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("min_pool_cost"))?;
+                                min_pool_cost = Some(tmp_min_pool_cost);
+                                min_pool_cost_encoding = tmp_min_pool_cost_encoding;
+                                min_pool_cost_key_encoding = Some(key_enc);
+                                orig_deser_order.push(13);
+                                Ok(())
+                            },
+                        )?,
+                        (17, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            17,
+                            key_enc,
+                            |raw, read_len| {
+                                if ada_per_utxo_byte.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(17)).into()
+                                    );
+                                }
+                                let (tmp_ada_per_utxo_byte, tmp_ada_per_utxo_byte_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("ada_per_utxo_byte"))?;
+                                ada_per_utxo_byte = Some(tmp_ada_per_utxo_byte);
+                                ada_per_utxo_byte_encoding = tmp_ada_per_utxo_byte_encoding;
+                                ada_per_utxo_byte_key_encoding = Some(key_enc);
+                                orig_deser_order.push(14);
+                                Ok(())
+                            },
+                        )?,
+                        (18, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            18,
+                            key_enc,
+                            |raw, read_len| {
+                                if cost_models_for_script_languages.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(18)).into()
+                                    );
+                                }
+                                let tmp_cost_models_for_script_languages =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        BabbageCostModels::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("cost_models_for_script_languages"))?;
+                                cost_models_for_script_languages =
+                                    Some(tmp_cost_models_for_script_languages);
+                                cost_models_for_script_languages_key_encoding = Some(key_enc);
+                                orig_deser_order.push(15);
+                                Ok(())
+                            },
+                        )?,
+                        (19, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            19,
+                            key_enc,
+                            |raw, read_len| {
+                                if execution_costs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(19)).into()
+                                    );
+                                }
+                                let tmp_execution_costs = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    ExUnitPrices::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("ada_per_utxo_byte"))?;
-                            ada_per_utxo_byte = Some(tmp_ada_per_utxo_byte);
-                            ada_per_utxo_byte_encoding = tmp_ada_per_utxo_byte_encoding;
-                            ada_per_utxo_byte_key_encoding = Some(key_enc);
-                            orig_deser_order.push(14);
-                        }
-                        (18, key_enc) => {
-                            if cost_models_for_script_languages.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(18)).into());
-                            }
-                            let tmp_cost_models_for_script_languages =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("execution_costs"))?;
+                                execution_costs = Some(tmp_execution_costs);
+                                execution_costs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(16);
+                                Ok(())
+                            },
+                        )?,
+                        (20, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            20,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_tx_ex_units.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(20)).into()
+                                    );
+                                }
+                                let tmp_max_tx_ex_units = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    BabbageCostModels::deserialize(raw)
+                                    ExUnits::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("cost_models_for_script_languages"))?;
-                            cost_models_for_script_languages =
-                                Some(tmp_cost_models_for_script_languages);
-                            cost_models_for_script_languages_key_encoding = Some(key_enc);
-                            orig_deser_order.push(15);
-                        }
-                        (19, key_enc) => {
-                            if execution_costs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(19)).into());
-                            }
-                            let tmp_execution_costs = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnitPrices::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("execution_costs"))?;
-                            execution_costs = Some(tmp_execution_costs);
-                            execution_costs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(16);
-                        }
-                        (20, key_enc) => {
-                            if max_tx_ex_units.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(20)).into());
-                            }
-                            let tmp_max_tx_ex_units = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnits::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("max_tx_ex_units"))?;
-                            max_tx_ex_units = Some(tmp_max_tx_ex_units);
-                            max_tx_ex_units_key_encoding = Some(key_enc);
-                            orig_deser_order.push(17);
-                        }
-                        (21, key_enc) => {
-                            if max_block_ex_units.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(21)).into());
-                            }
-                            let tmp_max_block_ex_units = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ExUnits::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("max_block_ex_units"))?;
-                            max_block_ex_units = Some(tmp_max_block_ex_units);
-                            max_block_ex_units_key_encoding = Some(key_enc);
-                            orig_deser_order.push(18);
-                        }
-                        (22, key_enc) => {
-                            if max_value_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(22)).into());
-                            }
-                            let (tmp_max_value_size, tmp_max_value_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("max_tx_ex_units"))?;
+                                max_tx_ex_units = Some(tmp_max_tx_ex_units);
+                                max_tx_ex_units_key_encoding = Some(key_enc);
+                                orig_deser_order.push(17);
+                                Ok(())
+                            },
+                        )?,
+                        (21, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            21,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_block_ex_units.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(21)).into()
+                                    );
+                                }
+                                let tmp_max_block_ex_units = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    ExUnits::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("max_value_size"))?;
-                            max_value_size = Some(tmp_max_value_size);
-                            max_value_size_encoding = tmp_max_value_size_encoding;
-                            max_value_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(19);
-                        }
+                                .map_err(|e| e.annotate("max_block_ex_units"))?;
+                                max_block_ex_units = Some(tmp_max_block_ex_units);
+                                max_block_ex_units_key_encoding = Some(key_enc);
+                                orig_deser_order.push(18);
+                                Ok(())
+                            },
+                        )?,
+                        (22, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            22,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_value_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(22)).into()
+                                    );
+                                }
+                                let (tmp_max_value_size, tmp_max_value_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_value_size"))?;
+                                max_value_size = Some(tmp_max_value_size);
+                                max_value_size_encoding = tmp_max_value_size_encoding;
+                                max_value_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(19);
+                                Ok(())
+                            },
+                        )?,
                         (23, key_enc) => {
-                            if collateral_percentage.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(23)).into());
-                            }
-                            let (tmp_collateral_percentage, tmp_collateral_percentage_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("collateral_percentage"))?;
-                            collateral_percentage = Some(tmp_collateral_percentage);
-                            collateral_percentage_encoding = tmp_collateral_percentage_encoding;
-                            collateral_percentage_key_encoding = Some(key_enc);
-                            orig_deser_order.push(20);
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                23,
+                                key_enc,
+                                |raw, read_len| {
+                                    if collateral_percentage.is_some() {
+                                        return Err(DeserializeFailure::DuplicateKey(Key::Uint(
+                                            23,
+                                        ))
+                                        .into());
+                                    }
+                                    let (
+                                        tmp_collateral_percentage,
+                                        tmp_collateral_percentage_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("collateral_percentage"))?;
+                                    collateral_percentage = Some(tmp_collateral_percentage);
+                                    collateral_percentage_encoding =
+                                        tmp_collateral_percentage_encoding;
+                                    collateral_percentage_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(20);
+                                    Ok(())
+                                },
+                            )?
                         }
                         (24, key_enc) => {
-                            if max_collateral_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(24)).into());
-                            }
-                            let (tmp_max_collateral_inputs, tmp_max_collateral_inputs_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_collateral_inputs"))?;
-                            max_collateral_inputs = Some(tmp_max_collateral_inputs);
-                            max_collateral_inputs_encoding = tmp_max_collateral_inputs_encoding;
-                            max_collateral_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(21);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                24,
+                                key_enc,
+                                |raw, read_len| {
+                                    if max_collateral_inputs.is_some() {
+                                        return Err(DeserializeFailure::DuplicateKey(Key::Uint(
+                                            24,
+                                        ))
+                                        .into());
+                                    }
+                                    let (
+                                        tmp_max_collateral_inputs,
+                                        tmp_max_collateral_inputs_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_collateral_inputs"))?;
+                                    max_collateral_inputs = Some(tmp_max_collateral_inputs);
+                                    max_collateral_inputs_encoding =
+                                        tmp_max_collateral_inputs_encoding;
+                                    max_collateral_inputs_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(21);
+                                    Ok(())
+                                },
+                            )?
                         }
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -2355,6 +2777,7 @@ impl Deserialize for BabbageProtocolParamUpdate {
                     collateral_percentage_encoding,
                     max_collateral_inputs_key_encoding,
                     max_collateral_inputs_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -2631,6 +3054,26 @@ impl Serialize for BabbageTransactionBody {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageTransactionBody {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -2680,7 +3123,7 @@ impl Serialize for BabbageTransactionBody {
                     } + match &self.reference_inputs {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -2736,7 +3179,9 @@ impl Serialize for BabbageTransactionBody {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -3297,6 +3742,8 @@ impl Serialize for BabbageTransactionBody {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -3313,6 +3760,7 @@ impl Deserialize for BabbageTransactionBody {
         read_len.read_elems(3)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut inputs_encoding = LenEncoding::default();
             let mut inputs_key_encoding = None;
             let mut inputs = None;
@@ -3366,359 +3814,683 @@ impl Deserialize for BabbageTransactionBody {
             let mut reference_inputs_key_encoding = None;
             let mut reference_inputs = None;
             let mut read = 0;
-            while match len { cbor_event::LenSz::Len(n, _) => read < n, cbor_event::LenSz::Indefinite => true, } {
+            while match len {
+                cbor_event::LenSz::Len(n, _) => read < n,
+                cbor_event::LenSz::Indefinite => true,
+            } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) =>  {
-                            if inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_inputs, tmp_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    inputs_arr.push(TransactionInput::deserialize(raw)?);
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
                                 }
-                                Ok((inputs_arr, inputs_encoding))
-                            })().map_err(|e| e.annotate("inputs"))?;
-                            inputs = Some(tmp_inputs);
-                            inputs_encoding = tmp_inputs_encoding;
-                            inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        },
-                        (1, key_enc) =>  {
-                            if outputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_outputs, tmp_outputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut outputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let outputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (outputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    outputs_arr.push(BabbageTransactionOutput::deserialize(raw)?);
+                                let (tmp_inputs, tmp_inputs_encoding) =
+                                    deserialize_array(raw, TransactionInput::deserialize)
+                                        .map_err(|e| e.annotate("inputs"))?;
+                                inputs = Some(tmp_inputs);
+                                inputs_encoding = tmp_inputs_encoding;
+                                inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if outputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
                                 }
-                                Ok((outputs_arr, outputs_encoding))
-                            })().map_err(|e| e.annotate("outputs"))?;
-                            outputs = Some(tmp_outputs);
-                            outputs_encoding = tmp_outputs_encoding;
-                            outputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        },
-                        (2, key_enc) =>  {
-                            if fee.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_fee, tmp_fee_encoding) = raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc))).map_err(|e: DeserializeError| e.annotate("fee"))?;
-                            fee = Some(tmp_fee);
-                            fee_encoding = tmp_fee_encoding;
-                            fee_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        },
-                        (3, key_enc) =>  {
-                            if ttl.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_ttl, tmp_ttl_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc)))
-                            })().map_err(|e| e.annotate("ttl"))?;
-                            ttl = Some(tmp_ttl);
-                            ttl_encoding = tmp_ttl_encoding;
-                            ttl_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        },
-                        (4, key_enc) =>  {
-                            if certs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_certs, tmp_certs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut certs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let certs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (certs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    certs_arr.push(AllegraCertificate::deserialize(raw)?);
+                                let (tmp_outputs, tmp_outputs_encoding) =
+                                    deserialize_array(raw, BabbageTransactionOutput::deserialize)
+                                        .map_err(|e| e.annotate("outputs"))?;
+                                outputs = Some(tmp_outputs);
+                                outputs_encoding = tmp_outputs_encoding;
+                                outputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, _read_len| {
+                                if fee.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
                                 }
-                                Ok((certs_arr, certs_encoding))
-                            })().map_err(|e| e.annotate("certs"))?;
-                            certs = Some(tmp_certs);
-                            certs_encoding = tmp_certs_encoding;
-                            certs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        },
-                        (5, key_enc) =>  {
-                            if withdrawals.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_withdrawals, tmp_withdrawals_encoding, tmp_withdrawals_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut withdrawals_table = OrderedHashMap::new();
-                                let withdrawals_len = raw.map_sz()?;
-                                let withdrawals_encoding = withdrawals_len.into();
-                                let mut withdrawals_value_encodings = BTreeMap::new();
-                                while match withdrawals_len { cbor_event::LenSz::Len(n, _) => (withdrawals_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let withdrawals_key = RewardAccount::deserialize(raw)?;
-                                    let (withdrawals_value, withdrawals_value_encoding) = raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc)))?;
-                                    if withdrawals_table.insert(withdrawals_key.clone(), withdrawals_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
-                                    }
-                                    withdrawals_value_encodings.insert(withdrawals_key, withdrawals_value_encoding);
+                                let (tmp_fee, tmp_fee_encoding) = raw
+                                    .unsigned_integer_sz()
+                                    .map_err(Into::<DeserializeError>::into)
+                                    .map(|(x, enc)| (x, Some(enc)))
+                                    .map_err(|e: DeserializeError| e.annotate("fee"))?;
+                                fee = Some(tmp_fee);
+                                fee_encoding = tmp_fee_encoding;
+                                fee_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if ttl.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
                                 }
-                                Ok((withdrawals_table, withdrawals_encoding, withdrawals_value_encodings))
-                            })().map_err(|e| e.annotate("withdrawals"))?;
-                            withdrawals = Some(tmp_withdrawals);
-                            withdrawals_encoding = tmp_withdrawals_encoding;
-                            withdrawals_value_encodings = tmp_withdrawals_value_encodings;
-                            withdrawals_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        },
-                        (6, key_enc) =>  {
-                            if update.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let tmp_update = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                BabbageUpdate::deserialize(raw)
-                            })().map_err(|e| e.annotate("update"))?;
-                            update = Some(tmp_update);
-                            update_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        },
-                        (7, key_enc) =>  {
-                            if auxiliary_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| AuxiliaryDataHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))
-                            })().map_err(|e| e.annotate("auxiliary_data_hash"))?;
-                            auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
-                            auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
-                            auxiliary_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        },
-                        (8, key_enc) =>  {
-                            if validity_interval_start.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_validity_interval_start, tmp_validity_interval_start_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc)))
-                            })().map_err(|e| e.annotate("validity_interval_start"))?;
-                            validity_interval_start = Some(tmp_validity_interval_start);
-                            validity_interval_start_encoding = tmp_validity_interval_start_encoding;
-                            validity_interval_start_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        },
-                        (9, key_enc) =>  {
-                            if mint.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let (tmp_mint, tmp_mint_encoding, tmp_mint_key_encodings, tmp_mint_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut mint_table = OrderedHashMap::new();
-                                let mint_len = raw.map_sz()?;
-                                let mint_encoding = mint_len.into();
-                                let mut mint_key_encodings = BTreeMap::new();
-                                let mut mint_value_encodings = BTreeMap::new();
-                                while match mint_len { cbor_event::LenSz::Len(n, _) => (mint_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let (mint_key, mint_key_encoding) = raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| PolicyId::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))?;
-                                    let mut mint_value_table = OrderedHashMap::new();
-                                    let mint_value_len = raw.map_sz()?;
-                                    let mint_value_encoding = mint_value_len.into();
-                                    let mut mint_value_value_encodings = BTreeMap::new();
-                                    while match mint_value_len { cbor_event::LenSz::Len(n, _) => (mint_value_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
+                                let (tmp_ttl, tmp_ttl_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("ttl"))?;
+                                ttl = Some(tmp_ttl);
+                                ttl_encoding = tmp_ttl_encoding;
+                                ttl_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if certs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_certs, tmp_certs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, AllegraCertificate::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("certs"))?;
+                                certs = Some(tmp_certs);
+                                certs_encoding = tmp_certs_encoding;
+                                certs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if withdrawals.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_withdrawals,
+                                    tmp_withdrawals_encoding,
+                                    tmp_withdrawals_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut withdrawals_table = OrderedHashMap::new();
+                                    let withdrawals_len = raw.map_sz()?;
+                                    let withdrawals_encoding = withdrawals_len.into();
+                                    let mut withdrawals_value_encodings = BTreeMap::new();
+                                    while match withdrawals_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (withdrawals_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
                                         if raw.cbor_type()? == cbor_event::Type::Special {
                                             assert_eq!(raw.special()?, cbor_event::Special::Break);
                                             break;
                                         }
-                                        let mint_value_key = AssetName::deserialize(raw)?;
-                                        let (mint_value_value, mint_value_value_encoding) = match raw.cbor_type()? {
-                                            cbor_event::Type::UnsignedInteger => {
-                                                let (x, enc) = raw.unsigned_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                            _ => {
-                                                let (x, enc) = raw.negative_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                        };
-                                        if mint_value_table.insert(mint_value_key.clone(), mint_value_value).is_some() {
-                                            return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        let withdrawals_key = RewardAccount::deserialize(raw)?;
+                                        let (withdrawals_value, withdrawals_value_encoding) = raw
+                                            .unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))?;
+                                        if withdrawals_table
+                                            .insert(withdrawals_key.clone(), withdrawals_value)
+                                            .is_some()
+                                        {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
                                         }
-                                        mint_value_value_encodings.insert(mint_value_key, mint_value_value_encoding);
-                                    }
-                                    let (mint_value, mint_value_encoding, mint_value_value_encodings) = (mint_value_table, mint_value_encoding, mint_value_value_encodings);
-                                    if mint_table.insert(mint_key, mint_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        withdrawals_value_encodings
+                                            .insert(withdrawals_key, withdrawals_value_encoding);
                                     }
-                                    mint_key_encodings.insert(mint_key, mint_key_encoding);
-                                    mint_value_encodings.insert(mint_key, (mint_value_encoding, mint_value_value_encodings));
+                                    Ok((
+                                        withdrawals_table,
+                                        withdrawals_encoding,
+                                        withdrawals_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("withdrawals"))?;
+                                withdrawals = Some(tmp_withdrawals);
+                                withdrawals_encoding = tmp_withdrawals_encoding;
+                                withdrawals_value_encodings = tmp_withdrawals_value_encodings;
+                                withdrawals_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if update.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
                                 }
-                                Ok((mint_table, mint_encoding, mint_key_encodings, mint_value_encodings))
-                            })().map_err(|e| e.annotate("mint"))?;
-                            mint = Some(tmp_mint);
-                            mint_encoding = tmp_mint_encoding;
-                            mint_key_encodings = tmp_mint_key_encodings;
-                            mint_value_encodings = tmp_mint_value_encodings;
-                            mint_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        },
-                        (11, key_enc) =>  {
-                            if script_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(11)).into());
-                            }
-                            let (tmp_script_data_hash, tmp_script_data_hash_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| ScriptDataHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))
-                            })().map_err(|e| e.annotate("script_data_hash"))?;
-                            script_data_hash = Some(tmp_script_data_hash);
-                            script_data_hash_encoding = tmp_script_data_hash_encoding;
-                            script_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(10);
-                        },
-                        (13, key_enc) =>  {
-                            if collateral_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(13)).into());
-                            }
-                            let (tmp_collateral_inputs, tmp_collateral_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut collateral_inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let collateral_inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (collateral_inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    collateral_inputs_arr.push(TransactionInput::deserialize(raw)?);
+                                let tmp_update = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    BabbageUpdate::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("update"))?;
+                                update = Some(tmp_update);
+                                update_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if auxiliary_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
                                 }
-                                Ok((collateral_inputs_arr, collateral_inputs_encoding))
-                            })().map_err(|e| e.annotate("collateral_inputs"))?;
-                            collateral_inputs = Some(tmp_collateral_inputs);
-                            collateral_inputs_encoding = tmp_collateral_inputs_encoding;
-                            collateral_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(11);
-                        },
-                        (14, key_enc) =>  {
-                            if required_signers.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(14)).into());
-                            }
-                            let (tmp_required_signers, tmp_required_signers_encoding, tmp_required_signers_elem_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut required_signers_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let required_signers_encoding = len.into();
-                                let mut required_signers_elem_encodings = Vec::new();
-                                while match len { cbor_event::LenSz::Len(n, _) => (required_signers_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let (required_signers_elem, required_signers_elem_encoding) = raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| Ed25519KeyHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))?;
-                                    required_signers_arr.push(required_signers_elem);
-                                    required_signers_elem_encodings.push(required_signers_elem_encoding);
+                                let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                AuxiliaryDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("auxiliary_data_hash"))?;
+                                auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
+                                auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
+                                auxiliary_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if validity_interval_start.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
                                 }
-                                Ok((required_signers_arr, required_signers_encoding, required_signers_elem_encodings))
-                            })().map_err(|e| e.annotate("required_signers"))?;
-                            required_signers = Some(tmp_required_signers);
-                            required_signers_encoding = tmp_required_signers_encoding;
-                            required_signers_elem_encodings = tmp_required_signers_elem_encodings;
-                            required_signers_key_encoding = Some(key_enc);
-                            orig_deser_order.push(12);
-                        },
-                        (15, key_enc) =>  {
-                            if network_id.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(15)).into());
-                            }
-                            let tmp_network_id = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                NetworkId::deserialize(raw)
-                            })().map_err(|e| e.annotate("network_id"))?;
-                            network_id = Some(tmp_network_id);
-                            network_id_key_encoding = Some(key_enc);
-                            orig_deser_order.push(13);
-                        },
-                        (16, key_enc) =>  {
-                            if collateral_return.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(16)).into());
-                            }
-                            let tmp_collateral_return = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                BabbageTransactionOutput::deserialize(raw)
-                            })().map_err(|e| e.annotate("collateral_return"))?;
-                            collateral_return = Some(tmp_collateral_return);
-                            collateral_return_key_encoding = Some(key_enc);
-                            orig_deser_order.push(14);
-                        },
-                        (17, key_enc) =>  {
-                            if total_collateral.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(17)).into());
-                            }
-                            let (tmp_total_collateral, tmp_total_collateral_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc)))
-                            })().map_err(|e| e.annotate("total_collateral"))?;
-                            total_collateral = Some(tmp_total_collateral);
-                            total_collateral_encoding = tmp_total_collateral_encoding;
-                            total_collateral_key_encoding = Some(key_enc);
-                            orig_deser_order.push(15);
-                        },
-                        (18, key_enc) =>  {
-                            if reference_inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(18)).into());
-                            }
-                            let (tmp_reference_inputs, tmp_reference_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut reference_inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let reference_inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (reference_inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
+                                let (
+                                    tmp_validity_interval_start,
+                                    tmp_validity_interval_start_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map_err(Into::<DeserializeError>::into)
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                })()
+                                .map_err(|e| e.annotate("validity_interval_start"))?;
+                                validity_interval_start = Some(tmp_validity_interval_start);
+                                validity_interval_start_encoding =
+                                    tmp_validity_interval_start_encoding;
+                                validity_interval_start_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if mint.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_mint,
+                                    tmp_mint_encoding,
+                                    tmp_mint_key_encodings,
+                                    tmp_mint_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut mint_table = OrderedHashMap::new();
+                                    let mint_len = raw.map_sz()?;
+                                    let mint_encoding = mint_len.into();
+                                    let mut mint_key_encodings = BTreeMap::new();
+                                    let mut mint_value_encodings = BTreeMap::new();
+                                    while match mint_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (mint_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
+                                        if raw.cbor_type()? == cbor_event::Type::Special {
+                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
+                                            break;
+                                        }
+                                        let (mint_key, mint_key_encoding) = raw
+                                            .bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                PolicyId::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })?;
+                                        let mut mint_value_table = OrderedHashMap::new();
+                                        let mint_value_len = raw.map_sz()?;
+                                        let mint_value_encoding = mint_value_len.into();
+                                        let mut mint_value_value_encodings = BTreeMap::new();
+                                        while match mint_value_len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (mint_value_table.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let mint_value_key = AssetName::deserialize(raw)?;
+                                            let (mint_value_value, mint_value_value_encoding) =
+                                                match raw.cbor_type()? {
+                                                    cbor_event::Type::UnsignedInteger => {
+                                                        let (x, enc) = raw.unsigned_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                    _ => {
+                                                        let (x, enc) = raw.negative_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                };
+                                            if mint_value_table
+                                                .insert(mint_value_key.clone(), mint_value_value)
+                                                .is_some()
+                                            {
+                                                return Err(DeserializeFailure::DuplicateKey(
+                                                    Key::Str(String::from(
+                                                        "some complicated/unsupported type",
+                                                    )),
+                                                )
+                                                .into());
+                                            }
+                                            mint_value_value_encodings
+                                                .insert(mint_value_key, mint_value_value_encoding);
+                                        }
+                                        let (
+                                            mint_value,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        ) = (
+                                            mint_value_table,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        );
+                                        if mint_table.insert(mint_key, mint_value).is_some() {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
+                                        }
+                                        mint_key_encodings.insert(mint_key, mint_key_encoding);
+                                        mint_value_encodings.insert(
+                                            mint_key,
+                                            (mint_value_encoding, mint_value_value_encodings),
+                                        );
                                     }
-                                    reference_inputs_arr.push(TransactionInput::deserialize(raw)?);
+                                    Ok((
+                                        mint_table,
+                                        mint_encoding,
+                                        mint_key_encodings,
+                                        mint_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("mint"))?;
+                                mint = Some(tmp_mint);
+                                mint_encoding = tmp_mint_encoding;
+                                mint_key_encodings = tmp_mint_key_encodings;
+                                mint_value_encodings = tmp_mint_value_encodings;
+                                mint_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (11, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            11,
+                            key_enc,
+                            |raw, read_len| {
+                                if script_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(11)).into()
+                                    );
                                 }
-                                Ok((reference_inputs_arr, reference_inputs_encoding))
-                            })().map_err(|e| e.annotate("reference_inputs"))?;
-                            reference_inputs = Some(tmp_reference_inputs);
-                            reference_inputs_encoding = tmp_reference_inputs_encoding;
-                            reference_inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(16);
-                        },
-                        (unknown_key, _enc) => return Err(DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()),
+                                let (tmp_script_data_hash, tmp_script_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                ScriptDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("script_data_hash"))?;
+                                script_data_hash = Some(tmp_script_data_hash);
+                                script_data_hash_encoding = tmp_script_data_hash_encoding;
+                                script_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(10);
+                                Ok(())
+                            },
+                        )?,
+                        (13, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            13,
+                            key_enc,
+                            |raw, read_len| {
+                                if collateral_inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(13)).into()
+                                    );
+                                }
+                                let (tmp_collateral_inputs, tmp_collateral_inputs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, TransactionInput::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("collateral_inputs"))?;
+                                collateral_inputs = Some(tmp_collateral_inputs);
+                                collateral_inputs_encoding = tmp_collateral_inputs_encoding;
+                                collateral_inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(11);
+                                Ok(())
+                            },
+                        )?,
+                        (14, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            14,
+                            key_enc,
+                            |raw, read_len| {
+                                if required_signers.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(14)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_required_signers,
+                                    tmp_required_signers_encoding,
+                                    tmp_required_signers_elem_encodings,
+                                ) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        let mut required_signers_arr = Vec::new();
+                                        let len = raw.array_sz()?;
+                                        let required_signers_encoding = len.into();
+                                        let mut required_signers_elem_encodings = Vec::new();
+                                        while match len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (required_signers_arr.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let (
+                                                required_signers_elem,
+                                                required_signers_elem_encoding,
+                                            ) = raw
+                                                .bytes_sz()
+                                                .map_err(Into::<DeserializeError>::into)
+                                                .and_then(|(bytes, enc)| {
+                                                    Ed25519KeyHash::from_raw_bytes(&bytes)
+                                                        .map(|bytes| {
+                                                            (bytes, StringEncoding::from(enc))
+                                                        })
+                                                        .map_err(|e| {
+                                                            DeserializeFailure::InvalidStructure(
+                                                                Box::new(e),
+                                                            )
+                                                            .into()
+                                                        })
+                                                })?;
+                                            required_signers_arr.push(required_signers_elem);
+                                            required_signers_elem_encodings
+                                                .push(required_signers_elem_encoding);
+                                        }
+                                        Ok((
+                                            required_signers_arr,
+                                            required_signers_encoding,
+                                            required_signers_elem_encodings,
+                                        ))
+                                    })()
+                                    .map_err(|e| e.annotate("required_signers"))?;
+                                required_signers = Some(tmp_required_signers);
+                                required_signers_encoding = tmp_required_signers_encoding;
+                                required_signers_elem_encodings =
+                                    tmp_required_signers_elem_encodings;
+                                required_signers_key_encoding = Some(key_enc);
+                                orig_deser_order.push(12);
+                                Ok(())
+                            },
+                        )?,
+                        (15, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            15,
+                            key_enc,
+                            |raw, read_len| {
+                                if network_id.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(15)).into()
+                                    );
+                                }
+                                let tmp_network_id = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    NetworkId::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("network_id"))?;
+                                network_id = Some(tmp_network_id);
+                                network_id_key_encoding = Some(key_enc);
+                                orig_deser_order.push(13);
+                                Ok(())
+                            },
+                        )?,
+                        (16, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            16,
+                            key_enc,
+                            |raw, read_len| {
+                                if collateral_return.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(16)).into()
+                                    );
+                                }
+                                let tmp_collateral_return = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    BabbageTransactionOutput::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("collateral_return"))?;
+                                collateral_return = Some(tmp_collateral_return);
+                                collateral_return_key_encoding = Some(key_enc);
+                                orig_deser_order.push(14);
+                                Ok(())
+                            },
+                        )?,
+                        (17, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            17,
+                            key_enc,
+                            |raw, read_len| {
+                                if total_collateral.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(17)).into()
+                                    );
+                                }
+                                let (tmp_total_collateral, tmp_total_collateral_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("total_collateral"))?;
+                                total_collateral = Some(tmp_total_collateral);
+                                total_collateral_encoding = tmp_total_collateral_encoding;
+                                total_collateral_key_encoding = Some(key_enc);
+                                orig_deser_order.push(15);
+                                Ok(())
+                            },
+                        )?,
+                        (18, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            18,
+                            key_enc,
+                            |raw, read_len| {
+                                if reference_inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(18)).into()
+                                    );
+                                }
+                                let (tmp_reference_inputs, tmp_reference_inputs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, TransactionInput::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("reference_inputs"))?;
+                                reference_inputs = Some(tmp_reference_inputs);
+                                reference_inputs_encoding = tmp_reference_inputs_encoding;
+                                reference_inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(16);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
-                    cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),
+                    cbor_event::Type::Text => {
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
+                    }
                     cbor_event::Type::Special => match len {
-                        cbor_event::LenSz::Len(_, _) => return Err(DeserializeFailure::BreakInDefiniteLen.into()),
+                        cbor_event::LenSz::Len(_, _) => {
+                            return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                        }
                         cbor_event::LenSz::Indefinite => match raw.special()? {
                             cbor_event::Special::Break => break,
                             _ => return Err(DeserializeFailure::EndingBreakMissing.into()),
                         },
                     },
-                    other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),
+                    other_type => {
+                        return Err(DeserializeFailure::UnexpectedKeyType(other_type).into())
+                    }
                 }
                 read += 1;
             }
@@ -3791,9 +4563,11 @@ impl Deserialize for BabbageTransactionBody {
                     total_collateral_encoding,
                     reference_inputs_key_encoding,
                     reference_inputs_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
-        })().map_err(|e| e.annotate("BabbageTransactionBody"))
+        })()
+        .map_err(|e| e.annotate("BabbageTransactionBody"))
     }
 }
 
@@ -3857,6 +4631,26 @@ impl Serialize for BabbageTransactionWitnessSet {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl BabbageTransactionWitnessSet {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -3885,7 +4679,7 @@ impl Serialize for BabbageTransactionWitnessSet {
                     } + match &self.plutus_v2_scripts {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -3920,7 +4714,9 @@ impl Serialize for BabbageTransactionWitnessSet {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.vkeywitnesses {
@@ -4135,6 +4931,8 @@ impl Serialize for BabbageTransactionWitnessSet {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -4150,6 +4948,7 @@ impl Deserialize for BabbageTransactionWitnessSet {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut vkeywitnesses_encoding = LenEncoding::default();
             let mut vkeywitnesses_key_encoding = None;
             let mut vkeywitnesses = None;
@@ -4178,227 +4977,202 @@ impl Deserialize for BabbageTransactionWitnessSet {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if vkeywitnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut vkeywitnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let vkeywitnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (vkeywitnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        vkeywitnesses_arr.push(Vkeywitness::deserialize(raw)?);
-                                    }
-                                    Ok((vkeywitnesses_arr, vkeywitnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("vkeywitnesses"))?;
-                            vkeywitnesses = Some(tmp_vkeywitnesses);
-                            vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
-                            vkeywitnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if native_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_native_scripts, tmp_native_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut native_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let native_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (native_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        native_scripts_arr.push(NativeScript::deserialize(raw)?);
-                                    }
-                                    Ok((native_scripts_arr, native_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("native_scripts"))?;
-                            native_scripts = Some(tmp_native_scripts);
-                            native_scripts_encoding = tmp_native_scripts_encoding;
-                            native_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if bootstrap_witnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut bootstrap_witnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let bootstrap_witnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (bootstrap_witnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        bootstrap_witnesses_arr
-                                            .push(BootstrapWitness::deserialize(raw)?);
-                                    }
-                                    Ok((bootstrap_witnesses_arr, bootstrap_witnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("bootstrap_witnesses"))?;
-                            bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
-                            bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
-                            bootstrap_witnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if plutus_v1_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v1_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v1_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v1_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v1_scripts_arr
-                                            .push(PlutusV1Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v1_scripts_arr, plutus_v1_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v1_scripts"))?;
-                            plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
-                            plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
-                            plutus_v1_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (4, key_enc) => {
-                            if plutus_datums.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_plutus_datums, tmp_plutus_datums_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_datums_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_datums_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_datums_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_datums_arr.push(PlutusData::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_datums_arr, plutus_datums_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_datums"))?;
-                            plutus_datums = Some(tmp_plutus_datums);
-                            plutus_datums_encoding = tmp_plutus_datums_encoding;
-                            plutus_datums_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (5, key_enc) => {
-                            if redeemers.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_redeemers, tmp_redeemers_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut redeemers_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let redeemers_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (redeemers_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        redeemers_arr.push(Redeemer::deserialize(raw)?);
-                                    }
-                                    Ok((redeemers_arr, redeemers_encoding))
-                                })()
-                                .map_err(|e| e.annotate("redeemers"))?;
-                            redeemers = Some(tmp_redeemers);
-                            redeemers_encoding = tmp_redeemers_encoding;
-                            redeemers_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        }
-                        (6, key_enc) => {
-                            if plutus_v2_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut plutus_v2_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let plutus_v2_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (plutus_v2_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        plutus_v2_scripts_arr
-                                            .push(PlutusV2Script::deserialize(raw)?);
-                                    }
-                                    Ok((plutus_v2_scripts_arr, plutus_v2_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("plutus_v2_scripts"))?;
-                            plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
-                            plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
-                            plutus_v2_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if vkeywitnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Vkeywitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("vkeywitnesses"))?;
+                                vkeywitnesses = Some(tmp_vkeywitnesses);
+                                vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
+                                vkeywitnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if native_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_native_scripts, tmp_native_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, NativeScript::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("native_scripts"))?;
+                                native_scripts = Some(tmp_native_scripts);
+                                native_scripts_encoding = tmp_native_scripts_encoding;
+                                native_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if bootstrap_witnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, BootstrapWitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("bootstrap_witnesses"))?;
+                                bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
+                                bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
+                                bootstrap_witnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v1_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v1_scripts, tmp_plutus_v1_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV1Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v1_scripts"))?;
+                                plutus_v1_scripts = Some(tmp_plutus_v1_scripts);
+                                plutus_v1_scripts_encoding = tmp_plutus_v1_scripts_encoding;
+                                plutus_v1_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_datums.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_plutus_datums, tmp_plutus_datums_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusData::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_datums"))?;
+                                plutus_datums = Some(tmp_plutus_datums);
+                                plutus_datums_encoding = tmp_plutus_datums_encoding;
+                                plutus_datums_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if redeemers.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (tmp_redeemers, tmp_redeemers_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Redeemer::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("redeemers"))?;
+                                redeemers = Some(tmp_redeemers);
+                                redeemers_encoding = tmp_redeemers_encoding;
+                                redeemers_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if plutus_v2_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
+                                }
+                                let (tmp_plutus_v2_scripts, tmp_plutus_v2_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, PlutusV2Script::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("plutus_v2_scripts"))?;
+                                plutus_v2_scripts = Some(tmp_plutus_v2_scripts);
+                                plutus_v2_scripts_encoding = tmp_plutus_v2_scripts_encoding;
+                                plutus_v2_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -4441,6 +5215,7 @@ impl Deserialize for BabbageTransactionWitnessSet {
                     redeemers_encoding,
                     plutus_v2_scripts_key_encoding,
                     plutus_v2_scripts_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
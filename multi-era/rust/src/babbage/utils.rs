@@ -1,16 +1,148 @@
 use cml_chain::{
-    auxdata::{AuxiliaryData, ConwayFormatAuxData},
-    transaction::TransactionWitnessSet,
-    Script,
+    assets::{Mint, Value},
+    auxdata::{AuxiliaryData, ConwayFormatAuxData, Metadata, ShelleyMaFormatAuxData},
+    block::Header,
+    crypto::{BootstrapWitness, Vkeywitness},
+    plutus::{ExUnitPrices, ExUnits, Redeemer},
+    transaction::{
+        AlonzoFormatTxOut, DatumOption, NativeScript, TransactionInput, TransactionWitnessSet,
+    },
+    utils::withdrawals_field_names,
+    Rational, Script, UnitInterval,
 };
 
 use super::{
-    BabbageAuxiliaryData, BabbageScript, BabbageTransactionBody, BabbageTransactionWitnessSet,
+    BabbageAuxiliaryData, BabbageBlock, BabbageCostModels, BabbageFormatAuxData,
+    BabbageFormatTxOut, BabbageProtocolParamUpdate, BabbageScript, BabbageTransaction,
+    BabbageTransactionBody, BabbageTransactionOutput, BabbageTransactionWitnessSet, BabbageUpdate,
 };
+use crate::allegra::AllegraCertificate;
+use crate::shelley::ProtocolVersionStruct;
 
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_core::serialization::Serialize;
 use cml_crypto::{blake2b256, TransactionHash};
 
+cml_core::impl_unknown_fields!(
+    BabbageCostModels,
+    BabbageFormatAuxData,
+    BabbageFormatTxOut,
+    BabbageProtocolParamUpdate,
+    BabbageTransactionBody,
+    BabbageTransactionWitnessSet
+);
+
+cml_core::impl_cbor_field_names!(BabbageBlock [
+    header: Header::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(BabbageTransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(BabbageTransactionWitnessSet::cbor_field_names()),
+    auxiliary_data_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", BabbageAuxiliaryData::cbor_field_names()),
+    ),
+    invalid_transactions,
+]);
+cml_core::impl_cbor_field_names!(BabbageTransaction [
+    body: BabbageTransactionBody::cbor_field_names(),
+    witness_set: BabbageTransactionWitnessSet::cbor_field_names(),
+    is_valid,
+    auxiliary_data: BabbageAuxiliaryData::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(BabbageTransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(BabbageTransactionOutput::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(AllegraCertificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    6 => update: BabbageUpdate::cbor_field_names(),
+    7 => auxiliary_data_hash,
+    8 => validity_interval_start,
+    9 => mint: Mint::cbor_field_names(),
+    11 => script_data_hash,
+    13 => collateral_inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    14 => required_signers,
+    15 => network_id,
+    16 => collateral_return: BabbageTransactionOutput::cbor_field_names(),
+    17 => total_collateral,
+    18 => reference_inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+});
+cml_core::impl_cbor_field_names!(BabbageTransactionWitnessSet {
+    0 => vkeywitnesses: FieldNames::elements(Vkeywitness::cbor_field_names()),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => bootstrap_witnesses: FieldNames::elements(BootstrapWitness::cbor_field_names()),
+    3 => plutus_v1_scripts,
+    4 => plutus_datums,
+    5 => redeemers: FieldNames::elements(Redeemer::cbor_field_names()),
+    6 => plutus_v2_scripts,
+});
+
+impl CBORFieldNames for BabbageTransactionOutput {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            AlonzoFormatTxOut::cbor_field_names(),
+            BabbageFormatTxOut::cbor_field_names(),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(BabbageFormatTxOut {
+    0 => address,
+    1 => amount: Value::cbor_field_names(),
+    2 => datum_option: DatumOption::cbor_field_names(),
+    3 => script_reference,
+});
+
+impl CBORFieldNames for BabbageAuxiliaryData {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Choice(vec![
+            Metadata::cbor_field_names(),
+            ShelleyMaFormatAuxData::cbor_field_names(),
+            BabbageFormatAuxData::cbor_field_names(),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(BabbageFormatAuxData tag 259 {
+    0 => metadata: Metadata::cbor_field_names(),
+    1 => native_scripts: FieldNames::elements(NativeScript::cbor_field_names()),
+    2 => plutus_v1_scripts,
+    3 => plutus_v2_scripts,
+});
+
+cml_core::impl_cbor_field_names!(BabbageUpdate [
+    updates: FieldNames::entries(
+        ("genesis_hash", FieldNames::None),
+        ("", BabbageProtocolParamUpdate::cbor_field_names()),
+    ),
+    epoch,
+]);
+cml_core::impl_cbor_field_names!(BabbageProtocolParamUpdate {
+    0 => minfee_a,
+    1 => minfee_b,
+    2 => max_block_body_size,
+    3 => max_transaction_size,
+    4 => max_block_header_size,
+    5 => key_deposit,
+    6 => pool_deposit,
+    7 => maximum_epoch,
+    8 => n_opt,
+    9 => pool_pledge_influence: Rational::cbor_field_names(),
+    10 => expansion_rate: UnitInterval::cbor_field_names(),
+    11 => treasury_growth_rate: UnitInterval::cbor_field_names(),
+    14 => protocol_version: ProtocolVersionStruct::cbor_field_names(),
+    16 => min_pool_cost,
+    17 => ada_per_utxo_byte,
+    18 => cost_models_for_script_languages: BabbageCostModels::cbor_field_names(),
+    19 => execution_costs: ExUnitPrices::cbor_field_names(),
+    20 => max_tx_ex_units: ExUnits::cbor_field_names(),
+    21 => max_block_ex_units: ExUnits::cbor_field_names(),
+    22 => max_value_size,
+    23 => collateral_percentage,
+    24 => max_collateral_inputs,
+});
+cml_core::impl_cbor_field_names!(BabbageCostModels { 0 => plutus_v1, 1 => plutus_v2 });
+
 impl BabbageTransactionBody {
     pub fn hash(&self) -> TransactionHash {
         blake2b256(&self.to_cbor_bytes()).into()
@@ -0,0 +1,383 @@
+//! Reading blocks directly from a cardano-node ImmutableDB (`db/immutable`).
+//!
+//! Each chunk `NNNNN` has three files:
+//! * `NNNNN.chunk` - the blocks themselves as concatenated `[era, block]` CBOR items
+//! * `NNNNN.secondary` - one fixed-size entry per block with its offset, hash and CRC32 checksum
+//! * `NNNNN.primary` - maps relative slots to secondary index entries (not needed here)
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use cml_crypto::{BlockHeaderHash, RawBytesEncoding};
+
+use cml_core::limits::DecodeLimits;
+
+use super::{decode_block, BlockReadError, StreamedBlock, DEFAULT_MAX_BLOCK_SIZE};
+
+/// Size in bytes of one entry in a `.secondary` index file
+pub const SECONDARY_INDEX_ENTRY_SIZE: usize = 56;
+
+/// One entry of a chunk's secondary index. All fields are stored big-endian.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecondaryIndexEntry {
+    /// Byte offset of the block within the chunk file
+    pub block_offset: u64,
+    /// Offset of the header within the block
+    pub header_offset: u16,
+    /// Size of the header in bytes
+    pub header_size: u16,
+    /// CRC32 of the block's bytes
+    pub checksum: u32,
+    pub header_hash: BlockHeaderHash,
+    /// Slot number for regular blocks, epoch number for Byron epoch boundary blocks
+    pub slot_or_epoch: u64,
+}
+
+impl SecondaryIndexEntry {
+    pub fn from_bytes(bytes: &[u8; SECONDARY_INDEX_ENTRY_SIZE]) -> Self {
+        let mut u64_bytes = [0u8; 8];
+        u64_bytes.copy_from_slice(&bytes[0..8]);
+        let block_offset = u64::from_be_bytes(u64_bytes);
+        let header_offset = u16::from_be_bytes([bytes[8], bytes[9]]);
+        let header_size = u16::from_be_bytes([bytes[10], bytes[11]]);
+        let checksum = u32::from_be_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+        let header_hash = BlockHeaderHash::from_raw_bytes(&bytes[16..48]).unwrap();
+        u64_bytes.copy_from_slice(&bytes[48..56]);
+        let slot_or_epoch = u64::from_be_bytes(u64_bytes);
+        Self {
+            block_offset,
+            header_offset,
+            header_size,
+            checksum,
+            header_hash,
+            slot_or_epoch,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; SECONDARY_INDEX_ENTRY_SIZE] {
+        let mut bytes = [0u8; SECONDARY_INDEX_ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.block_offset.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.header_offset.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.header_size.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.checksum.to_be_bytes());
+        bytes[16..48].copy_from_slice(self.header_hash.to_raw_bytes());
+        bytes[48..56].copy_from_slice(&self.slot_or_epoch.to_be_bytes());
+        bytes
+    }
+
+    /// Reads all entries of a `.secondary` file
+    pub fn read_all<R: Read>(mut reader: R) -> std::io::Result<Vec<Self>> {
+        let mut entries = Vec::new();
+        let mut bytes = [0u8; SECONDARY_INDEX_ENTRY_SIZE];
+        loop {
+            let mut read = 0;
+            while read < bytes.len() {
+                match reader.read(&mut bytes[read..])? {
+                    0 => break,
+                    n => read += n,
+                }
+            }
+            match read {
+                0 => return Ok(entries),
+                SECONDARY_INDEX_ENTRY_SIZE => entries.push(Self::from_bytes(&bytes)),
+                _ => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "truncated secondary index entry",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// A block read from a chunk file along with its secondary index entry
+#[derive(Clone, Debug)]
+pub struct ChunkBlock {
+    pub entry: SecondaryIndexEntry,
+    pub block: StreamedBlock,
+}
+
+/**
+ * Reads the blocks of one ImmutableDB chunk using its secondary index to find block boundaries.
+ *
+ * Each block's CRC32 is checked against the index. Checksum and decoding failures are recoverable
+ * and reading continues with the next block. Only the current block is held in memory.
+ */
+pub struct ChunkReader<R: Read> {
+    chunk: R,
+    entries: std::vec::IntoIter<SecondaryIndexEntry>,
+    next_entry: Option<SecondaryIndexEntry>,
+    position: u64,
+    max_block_size: usize,
+    decode_limits: Option<DecodeLimits>,
+    finished: bool,
+}
+
+impl<R: Read> ChunkReader<R> {
+    pub fn new(chunk: R, secondary_index: Vec<SecondaryIndexEntry>) -> Self {
+        let mut entries = secondary_index.into_iter();
+        let next_entry = entries.next();
+        Self {
+            chunk,
+            entries,
+            next_entry,
+            position: 0,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            decode_limits: None,
+            finished: false,
+        }
+    }
+
+    /// Sets the largest block (in bytes) that will be read before failing with BlockTooLarge
+    pub fn with_max_block_size(mut self, max_block_size: usize) -> Self {
+        self.max_block_size = max_block_size;
+        self
+    }
+
+    /// Decodes blocks within `limits` (see `cml_core::limits`), failing with a recoverable
+    /// `BlockReadError::Deserialize` for blocks over them
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.decode_limits = Some(limits);
+        self
+    }
+
+    fn read_next(&mut self, entry: SecondaryIndexEntry) -> Result<ChunkBlock, BlockReadError> {
+        let offset = entry.block_offset;
+        let io_err = |source| BlockReadError::Io { offset, source };
+        if offset < self.position {
+            return Err(BlockReadError::InvalidIndex {
+                offset,
+                reason: "secondary index offsets are not increasing",
+            });
+        }
+        // skip anything between blocks
+        let gap = offset - self.position;
+        let skipped = std::io::copy(&mut (&mut self.chunk).take(gap), &mut std::io::sink())
+            .map_err(io_err)?;
+        self.position += skipped;
+        if skipped < gap {
+            return Err(io_err(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        let max_size = self.max_block_size as u64;
+        // the last block in a chunk has no following entry and runs until the end of the file
+        let size_limit = match &self.next_entry {
+            Some(next) => {
+                let size = next.block_offset.saturating_sub(offset);
+                if size > max_size {
+                    return Err(BlockReadError::BlockTooLarge {
+                        offset,
+                        max_size: self.max_block_size,
+                    });
+                }
+                size
+            }
+            None => max_size + 1,
+        };
+        let mut bytes = Vec::new();
+        (&mut self.chunk)
+            .take(size_limit)
+            .read_to_end(&mut bytes)
+            .map_err(io_err)?;
+        self.position += bytes.len() as u64;
+        if self.next_entry.is_some() && (bytes.len() as u64) < size_limit {
+            return Err(io_err(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        if bytes.len() as u64 > max_size {
+            return Err(BlockReadError::BlockTooLarge {
+                offset,
+                max_size: self.max_block_size,
+            });
+        }
+        let found = crc32(&bytes);
+        if found != entry.checksum {
+            return Err(BlockReadError::ChecksumMismatch {
+                offset,
+                expected: entry.checksum,
+                found,
+            });
+        }
+        let block = decode_block(&bytes, self.decode_limits.as_ref())
+            .map_err(|source| BlockReadError::Deserialize { offset, source })?;
+        Ok(ChunkBlock {
+            entry,
+            block: StreamedBlock {
+                offset,
+                size: bytes.len() as u64,
+                block,
+            },
+        })
+    }
+}
+
+impl<R: Read> Iterator for ChunkReader<R> {
+    type Item = Result<ChunkBlock, BlockReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let entry = self.next_entry.take()?;
+        self.next_entry = self.entries.next();
+        let result = self.read_next(entry);
+        if let Err(e) = &result {
+            self.finished = !e.is_recoverable();
+        }
+        Some(result)
+    }
+}
+
+impl ChunkReader<std::io::BufReader<std::fs::File>> {
+    /// Opens chunk `chunk_number` in an ImmutableDB directory (e.g. `db/immutable`)
+    pub fn open<P: AsRef<Path>>(immutable_dir: P, chunk_number: u64) -> std::io::Result<Self> {
+        let dir = immutable_dir.as_ref();
+        let secondary = std::fs::File::open(chunk_file_path(dir, chunk_number, "secondary"))?;
+        let entries = SecondaryIndexEntry::read_all(std::io::BufReader::new(secondary))?;
+        let chunk = std::fs::File::open(chunk_file_path(dir, chunk_number, "chunk"))?;
+        Ok(Self::new(std::io::BufReader::new(chunk), entries))
+    }
+}
+
+fn chunk_file_path(immutable_dir: &Path, chunk_number: u64, extension: &str) -> PathBuf {
+    immutable_dir.join(format!("{chunk_number:05}.{extension}"))
+}
+
+/// The chunk numbers present in an ImmutableDB directory in ascending order
+pub fn chunk_numbers<P: AsRef<Path>>(immutable_dir: P) -> std::io::Result<Vec<u64>> {
+    let mut chunks = Vec::new();
+    for entry in std::fs::read_dir(immutable_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("chunk") {
+            if let Some(chunk_number) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                chunks.push(chunk_number);
+            }
+        }
+    }
+    chunks.sort_unstable();
+    Ok(chunks)
+}
+
+/// CRC32 (IEEE) as used by cardano-node for ImmutableDB checksums
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::byron_block_bytes;
+    use super::*;
+    use crate::byron::block::ByronBlock;
+    use crate::MultiEraBlock;
+
+    fn entry_for(block: &[u8], block_offset: u64) -> SecondaryIndexEntry {
+        SecondaryIndexEntry {
+            block_offset,
+            header_offset: 3,
+            header_size: 0,
+            checksum: crc32(block),
+            header_hash: BlockHeaderHash::from([0; 32]),
+            slot_or_epoch: 4_000_000,
+        }
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn secondary_index_round_trip() {
+        let entries = vec![
+            entry_for(&[1, 2, 3], 0),
+            entry_for(&[4, 5], 3),
+            entry_for(&[6], 5),
+        ];
+        let bytes = entries
+            .iter()
+            .flat_map(|entry| entry.to_bytes().to_vec())
+            .collect::<Vec<u8>>();
+        assert_eq!(bytes.len(), 3 * SECONDARY_INDEX_ENTRY_SIZE);
+        assert_eq!(
+            SecondaryIndexEntry::read_all(bytes.as_slice()).unwrap(),
+            entries
+        );
+        assert!(SecondaryIndexEntry::read_all(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn read_chunk() {
+        let block = byron_block_bytes();
+        let mut chunk = block.clone();
+        chunk.extend(&block);
+        chunk.extend(&block);
+        let len = block.len() as u64;
+        let mut bad_entry = entry_for(&block, len);
+        bad_entry.checksum ^= 1;
+        let entries = vec![entry_for(&block, 0), bad_entry, entry_for(&block, 2 * len)];
+        let results = ChunkReader::new(chunk.as_slice(), entries).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().block.offset, 0);
+        assert!(matches!(
+            results[1],
+            Err(BlockReadError::ChecksumMismatch { offset, .. }) if offset == len
+        ));
+        let last = results[2].as_ref().unwrap();
+        assert_eq!(last.block.offset, 2 * len);
+        assert_eq!(last.block.size, len);
+        assert_eq!(last.entry.slot_or_epoch, 4_000_000);
+    }
+
+    /// `test_data/00000.{chunk,secondary}` follow the node's on-disk layout: an EBB (`[0, ebb]`),
+    /// a mainnet Byron main block (`[1, block]`) and a mainnet Shelley block (`[2, block]`).
+    #[test]
+    fn read_node_layout_chunk() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/block_reader/test_data");
+        assert_eq!(chunk_numbers(&dir).unwrap(), vec![0]);
+        let chunk = std::fs::read(chunk_file_path(&dir, 0, "chunk")).unwrap();
+        let blocks = ChunkReader::open(&dir, 0)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(blocks.len(), 3);
+        for (i, ChunkBlock { entry, block }) in blocks.iter().enumerate() {
+            assert_eq!(entry.block_offset, block.offset);
+            let raw = &chunk[block.offset as usize..(block.offset + block.size) as usize];
+            assert_eq!(entry.checksum, crc32(raw));
+            assert_eq!(entry.header_hash, BlockHeaderHash::from(block.block.hash()));
+            // headers are hashed as-is from Shelley on, Byron headers are hashed as [0|1, header]
+            let start = entry.header_offset as usize;
+            let header = &raw[start..start + entry.header_size as usize];
+            let header_hash = match raw[1] {
+                0 | 1 => cml_crypto::blake2b256(&[&raw[..2], header].concat()),
+                _ => cml_crypto::blake2b256(header),
+            };
+            assert_eq!(entry.header_hash, BlockHeaderHash::from(header_hash));
+            match (i, &block.block) {
+                (0, MultiEraBlock::Byron(ByronBlock::EpochBoundary(ebb))) => {
+                    assert_eq!(entry.slot_or_epoch, ebb.header.consensus_data.epoch_id)
+                }
+                (1, MultiEraBlock::Byron(ByronBlock::Main(_))) | (2, MultiEraBlock::Shelley(_)) => {
+                    assert_eq!(entry.slot_or_epoch, block.block.header().slot())
+                }
+                _ => panic!("unexpected block {i}: {:?}", block.block),
+            }
+        }
+        let byron = &blocks[1].block;
+        assert_eq!(
+            chunk[byron.offset as usize..(byron.offset + byron.size) as usize],
+            byron_block_bytes()
+        );
+    }
+}
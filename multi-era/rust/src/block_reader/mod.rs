@@ -0,0 +1,439 @@
+//! Streaming decoding of multi-era blocks from files or network dumps.
+//!
+//! Blocks are read one CBOR item at a time so memory use is bounded by the size of a single
+//! block regardless of how large the input is.
+
+pub mod immutable_db;
+
+use std::io::Read;
+
+use cml_core::error::DeserializeError;
+use cml_core::limits::{with_limits, DecodeLimits};
+
+use crate::MultiEraBlock;
+
+/// Default upper bound on the size of a single block. Well above the protocol's block size limits.
+pub const DEFAULT_MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BlockReadError {
+    #[error("IO error at byte offset {offset}: {source}")]
+    Io {
+        offset: u64,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid CBOR at byte offset {offset}: {reason}")]
+    InvalidCbor { offset: u64, reason: &'static str },
+    #[error("Invalid index for block at byte offset {offset}: {reason}")]
+    InvalidIndex { offset: u64, reason: &'static str },
+    #[error("Block at byte offset {offset} exceeds the maximum size of {max_size} bytes")]
+    BlockTooLarge { offset: u64, max_size: usize },
+    #[error("Invalid block at byte offset {offset}: {source}")]
+    Deserialize {
+        offset: u64,
+        #[source]
+        source: DeserializeError,
+    },
+    #[error("Checksum mismatch for block at byte offset {offset}: expected {expected:08x}, found {found:08x}")]
+    ChecksumMismatch {
+        offset: u64,
+        expected: u32,
+        found: u32,
+    },
+}
+
+impl BlockReadError {
+    /// Byte offset in the input of the block this error occurred in
+    pub fn offset(&self) -> u64 {
+        match self {
+            Self::Io { offset, .. } => *offset,
+            Self::InvalidCbor { offset, .. } => *offset,
+            Self::InvalidIndex { offset, .. } => *offset,
+            Self::BlockTooLarge { offset, .. } => *offset,
+            Self::Deserialize { offset, .. } => *offset,
+            Self::ChecksumMismatch { offset, .. } => *offset,
+        }
+    }
+
+    /// Whether reading can continue with the next block after this error.
+    /// Errors where the bounds of the block could not be determined end the stream.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Io { .. }
+            | Self::InvalidCbor { .. }
+            | Self::InvalidIndex { .. }
+            | Self::BlockTooLarge { .. } => false,
+            Self::Deserialize { .. } | Self::ChecksumMismatch { .. } => true,
+        }
+    }
+}
+
+/// A block along with where it was found in the input
+#[derive(Clone, Debug)]
+pub struct StreamedBlock {
+    /// Byte offset of the start of the block's CBOR in the input
+    pub offset: u64,
+    /// Size in bytes of the block's CBOR in the input
+    pub size: u64,
+    pub block: MultiEraBlock,
+}
+
+/**
+ * Reads concatenated `[era, block]` CBOR items (the format used by the network and by
+ * ImmutableDB chunk files) from any `std::io::Read`.
+ *
+ * Iterating yields one block at a time. Blocks that are well-formed CBOR but fail to decode
+ * produce a recoverable `BlockReadError` and reading continues with the next block.
+ * Unrecoverable errors (IO, malformed CBOR, oversized blocks) end the iteration.
+ *
+ * The reader does no buffering of its own so wrapping files in a `std::io::BufReader` is recommended.
+ */
+pub struct BlockReader<R: Read> {
+    reader: R,
+    position: u64,
+    max_block_size: usize,
+    decode_limits: Option<DecodeLimits>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl<R: Read> BlockReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            decode_limits: None,
+            buffer: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Sets the largest block (in bytes) that will be read before failing with BlockTooLarge
+    pub fn with_max_block_size(mut self, max_block_size: usize) -> Self {
+        self.max_block_size = max_block_size;
+        self
+    }
+
+    /// Decodes blocks within `limits` (see `cml_core::limits`), failing with a recoverable
+    /// `BlockReadError::Deserialize` for blocks over them
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.decode_limits = Some(limits);
+        self
+    }
+
+    /// Byte offset in the input of the next block to be read
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next block. Returns Ok(None) at a clean end of input.
+    pub fn read_block(&mut self) -> Result<Option<StreamedBlock>, BlockReadError> {
+        let decode_limits = self.decode_limits;
+        let (offset, bytes) = match self.read_block_bytes()? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        decode_block(bytes, decode_limits.as_ref())
+            .map(|block| {
+                Some(StreamedBlock {
+                    offset,
+                    size: bytes.len() as u64,
+                    block,
+                })
+            })
+            .map_err(|source| BlockReadError::Deserialize { offset, source })
+    }
+
+    /// Reads the next block's `[era, block]` CBOR without decoding it, along with its offset
+    /// in the input. Useful with `crate::lazy::LazyBlock` to only decode what is needed.
+    /// Returns Ok(None) at a clean end of input.
+    pub fn read_block_bytes(&mut self) -> Result<Option<(u64, &[u8])>, BlockReadError> {
+        let offset = self.position;
+        self.buffer.clear();
+        let found = read_cbor_item(&mut self.reader, &mut self.buffer, self.max_block_size)
+            .map_err(|e| e.at(offset));
+        self.position += self.buffer.len() as u64;
+        if !found? {
+            return Ok(None);
+        }
+        Ok(Some((offset, &self.buffer)))
+    }
+}
+
+impl<R: Read> Iterator for BlockReader<R> {
+    type Item = Result<StreamedBlock, BlockReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_block() {
+            Ok(Some(block)) => Some(Ok(block)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = !e.is_recoverable();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Decodes a `[era, block]` item, within `limits` if given
+fn decode_block(
+    bytes: &[u8],
+    limits: Option<&DecodeLimits>,
+) -> Result<MultiEraBlock, DeserializeError> {
+    match limits {
+        Some(limits) => with_limits(limits, || {
+            MultiEraBlock::from_explicit_network_cbor_bytes(bytes)
+        }),
+        None => MultiEraBlock::from_explicit_network_cbor_bytes(bytes),
+    }
+}
+
+/// Errors when finding the bounds of a CBOR item, before the offset is known
+enum FramingError {
+    Io(std::io::Error),
+    InvalidCbor(&'static str),
+    TooLarge(usize),
+}
+
+impl FramingError {
+    fn at(self, offset: u64) -> BlockReadError {
+        match self {
+            Self::Io(source) => BlockReadError::Io { offset, source },
+            Self::InvalidCbor(reason) => BlockReadError::InvalidCbor { offset, reason },
+            Self::TooLarge(max_size) => BlockReadError::BlockTooLarge { offset, max_size },
+        }
+    }
+}
+
+impl From<std::io::Error> for FramingError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+fn read_exact_into<R: Read>(
+    reader: &mut R,
+    buffer: &mut Vec<u8>,
+    len: u64,
+    max_size: usize,
+) -> Result<(), FramingError> {
+    if buffer.len() as u64 + len > max_size as u64 {
+        return Err(FramingError::TooLarge(max_size));
+    }
+    let read = reader.take(len).read_to_end(buffer)?;
+    if (read as u64) < len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(())
+}
+
+/// Copies exactly one complete CBOR data item from `reader` into `buffer` without decoding it.
+/// Returns false if the reader was already at the end of its input.
+fn read_cbor_item<R: Read>(
+    reader: &mut R,
+    buffer: &mut Vec<u8>,
+    max_size: usize,
+) -> Result<bool, FramingError> {
+    // items remaining in each containing array/map/tag/string, None for indefinite ones
+    let mut remaining: Vec<Option<u64>> = vec![Some(1)];
+    while !remaining.is_empty() {
+        let mut initial = [0u8; 1];
+        if reader.read(&mut initial)? == 0 {
+            if buffer.is_empty() {
+                return Ok(false);
+            }
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        if buffer.len() >= max_size {
+            return Err(FramingError::TooLarge(max_size));
+        }
+        buffer.push(initial[0]);
+        let major = initial[0] >> 5;
+        let info = initial[0] & 0x1f;
+        if initial[0] == 0xff {
+            match remaining.last() {
+                Some(None) => {
+                    remaining.pop();
+                    item_done(&mut remaining);
+                    continue;
+                }
+                _ => return Err(FramingError::InvalidCbor("unexpected break")),
+            }
+        }
+        let arg_len = match info {
+            0..=23 => 0,
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            31 => match major {
+                2..=5 => 0,
+                _ => return Err(FramingError::InvalidCbor("invalid indefinite length")),
+            },
+            _ => return Err(FramingError::InvalidCbor("reserved additional info")),
+        };
+        let arg_start = buffer.len();
+        read_exact_into(reader, buffer, arg_len, max_size)?;
+        let arg = match info {
+            0..=23 => u64::from(info),
+            _ => buffer[arg_start..]
+                .iter()
+                .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)),
+        };
+        let indefinite = info == 31;
+        match major {
+            // uint / nint / simple values and floats
+            0 | 1 | 7 => item_done(&mut remaining),
+            // bytes / text
+            2 | 3 => {
+                if indefinite {
+                    remaining.push(None);
+                } else {
+                    read_exact_into(reader, buffer, arg, max_size)?;
+                    item_done(&mut remaining);
+                }
+            }
+            // array / map
+            4 | 5 => {
+                let len = if major == 5 {
+                    arg.saturating_mul(2)
+                } else {
+                    arg
+                };
+                if indefinite {
+                    remaining.push(None);
+                } else if len == 0 {
+                    item_done(&mut remaining);
+                } else {
+                    remaining.push(Some(len));
+                }
+            }
+            // tag
+            _ => remaining.push(Some(1)),
+        }
+    }
+    Ok(true)
+}
+
+/// Marks one item of the innermost container as read, closing any containers this completes
+pub(crate) fn item_done(remaining: &mut Vec<Option<u64>>) {
+    while let Some(Some(count)) = remaining.last_mut() {
+        *count -= 1;
+        if *count > 0 {
+            break;
+        }
+        remaining.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [1, byron main block] from the byron_block_hash test in utils.rs
+    pub(super) fn byron_block_bytes() -> Vec<u8> {
+        hex::decode("820183851a2d964a095820d667b66b3234630d7c43a29d8ac1431cc74929218456688c4833664563d7f3af84830058200e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a85820afc0da64183bf2664f3d4eec7238d524ba607faeeab24fc100eb861dba69971b83025820d36a2619a672494604e11bb447cbcf5231e9f2ba25c2169177edc941bd50ad6c5820d36a2619a672494604e11bb447cbcf5231e9f2ba25c2169177edc941bd50ad6c5820afc0da64183bf2664f3d4eec7238d524ba607faeeab24fc100eb861dba69971b58204e66280cd94d591072349bec0a3090a53aa945562efb6d08d56e53654b0e40988482071945b358400bdb1f5ef3d994037593f2266255f134a564658bb2df814b3b9cefb96da34fa9c888591c85b770fd36726d5f3d991c668828affc7bbe0872fd699136e664d9d8811a00029434820282840058400bdb1f5ef3d994037593f2266255f134a564658bb2df814b3b9cefb96da34fa9c888591c85b770fd36726d5f3d991c668828affc7bbe0872fd699136e664d9d858405fddeedade2714d6db2f9e1104743d2d8d818ecddc306e176108db14caadd441b457d5840c60f8840b99c8f78c290ae229d4f8431e678ba7a545c35607b94ddb5840552741f728196e62f218047b944b24ce4d374300d04b9b281426f55aa000d53ded66989ad5ea0908e6ff6492001ff18ece6c7040a934060759e9ae09863bf20358407ac79f732d0a4956c5214c59d36ce7cd3a7887784c825caea7f424fd0b0984b5a0d5edee4257a8de1c56591b623ff266a64bf3d64d5c63f46120abb1f3ef24038483000000826a63617264616e6f2d736c02a058204ba92aa320c60acc9ad7b9a64f2eda55c4d2ec28e604faf186708b4f0c4e8edf849fff8302a0d90102809fff82809fff81a0").unwrap()
+    }
+
+    #[test]
+    fn read_concatenated_blocks() {
+        let block = byron_block_bytes();
+        let mut invalid = block.clone();
+        // era tag 9 does not exist
+        invalid[1] = 0x09;
+        let mut input = block.clone();
+        input.extend(&invalid);
+        input.extend(&block);
+        let results = BlockReader::new(input.as_slice()).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.offset, 0);
+        assert_eq!(first.size, block.len() as u64);
+        let err = results[1].as_ref().unwrap_err();
+        assert!(err.is_recoverable());
+        assert_eq!(err.offset(), block.len() as u64);
+        let third = results[2].as_ref().unwrap();
+        assert_eq!(third.offset, 2 * block.len() as u64);
+        assert_eq!(third.block.hash(), first.block.hash());
+    }
+
+    #[test]
+    fn truncated_input_ends_stream() {
+        let block = byron_block_bytes();
+        let mut input = block.clone();
+        input.extend(&block[..block.len() / 2]);
+        let mut reader = BlockReader::new(input.as_slice());
+        assert!(reader.next().unwrap().is_ok());
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(!err.is_recoverable());
+        assert_eq!(err.offset(), block.len() as u64);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn max_block_size() {
+        let block = byron_block_bytes();
+        let mut reader = BlockReader::new(block.as_slice()).with_max_block_size(block.len() - 1);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(BlockReadError::BlockTooLarge { offset: 0, .. }))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn decode_limits() {
+        let block = byron_block_bytes();
+        let limits = DecodeLimits {
+            max_depth: 4,
+            ..Default::default()
+        };
+        let mut input = block.clone();
+        input.extend(&block);
+        let mut reader = BlockReader::new(input.as_slice()).with_decode_limits(limits);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.is_recoverable());
+        let BlockReadError::Deserialize { source, .. } = err else {
+            panic!("unexpected {:?}", err);
+        };
+        assert!(matches!(
+            source.deepest().1.failure(),
+            cml_core::DeserializeFailure::LimitExceeded(cml_core::limits::LimitExceeded::Depth {
+                max: 4
+            })
+        ));
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        // the same limits apply to from_explicit_network_cbor_bytes() inside a with_limits() scope
+        assert!(with_limits(&limits, || {
+            MultiEraBlock::from_explicit_network_cbor_bytes(&block)
+        })
+        .is_err());
+        assert!(BlockReader::new(block.as_slice())
+            .with_decode_limits(DecodeLimits::default())
+            .all(|block| block.is_ok()));
+    }
+
+    #[test]
+    fn indefinite_length_items() {
+        // [_ h'01', (_ h'02' h'03'), {_ 1: 2}] followed by 0
+        let bytes = hex::decode("9f41015f41024103ff bf0102ff ff 00".replace(' ', "")).unwrap();
+        let mut reader = bytes.as_slice();
+        let mut buffer = Vec::new();
+        assert!(read_cbor_item(&mut reader, &mut buffer, 1024).is_ok());
+        assert_eq!(buffer, bytes[..bytes.len() - 1]);
+        assert_eq!(reader, [0x00]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Ogmios v6 JSON for blocks of any era, built on top of cml_chain::json::views.
+//!
+//! This is encoding only: Ogmios leaves out the header's KES signature and the block body
+//! hash so a block can't be rebuilt from its JSON. Transactions from before Conway are
+//! upgraded with to_conway_lossy() to produce their JSON, keeping the original body hash
+//! as the id. Genesis key delegation and MIR certificates are put back in, while protocol
+//! parameter update proposals are not shown. The "cbor" field is only given for Conway
+//! transactions as re-encoding the upgraded ones would not give back the original bytes.
+
+use crate::{
+    allegra::{MIRAction, MIRPot, MoveInstantaneousRewardsCert},
+    byron::{
+        block::ByronBlock,
+        transaction::{ByronTx, ByronTxIn},
+    },
+    shelley::GenesisKeyDelegation,
+    utils::{MultiEraBlockHeader, MultiEraCertificate},
+    MultiEraBlock, MultiEraTransactionBody,
+};
+use cml_chain::{
+    certs::Credential,
+    json::views::{decode_transaction_to_json_value, ChainJsonError, ChainJsonSchema},
+    transaction::Transaction,
+};
+use cml_core::serialization::Serialize;
+use cml_crypto::RawBytesEncoding;
+use serde_json::{json, Map, Value as JsonValue};
+
+/// Converts a MultiEraBlock to JSON according to ChainJsonSchema.
+/// Only ChainJsonSchema::OgmiosV6 is supported as cardano-cli has no view for blocks.
+pub fn decode_multi_era_block_to_json_value(
+    block: &MultiEraBlock,
+    schema: ChainJsonSchema,
+) -> Result<JsonValue, ChainJsonError> {
+    match schema {
+        ChainJsonSchema::CardanoCliView => Err(ChainJsonError::Unsupported(
+            "cardano-cli has no JSON view for blocks".to_owned(),
+        )),
+        ChainJsonSchema::OgmiosV6 => ogmios_block_to_json(block),
+    }
+}
+
+pub fn decode_multi_era_block_to_json_str(
+    block: &MultiEraBlock,
+    schema: ChainJsonSchema,
+) -> Result<String, ChainJsonError> {
+    decode_multi_era_block_to_json_value(block, schema).map(|json| json.to_string())
+}
+
+fn era_name(block: &MultiEraBlock) -> &'static str {
+    match block {
+        MultiEraBlock::Byron(_) => "byron",
+        MultiEraBlock::Shelley(_) => "shelley",
+        MultiEraBlock::Allegra(_) => "allegra",
+        MultiEraBlock::Mary(_) => "mary",
+        MultiEraBlock::Alonzo(_) => "alonzo",
+        MultiEraBlock::Babbage(_) => "babbage",
+        MultiEraBlock::Conway(_) => "conway",
+    }
+}
+
+fn ancestor_to_json(header: &MultiEraBlockHeader) -> JsonValue {
+    match header.prev_hash() {
+        Some(prev_hash) => prev_hash.to_hex().into(),
+        None => "genesis".into(),
+    }
+}
+
+fn ogmios_block_to_json(block: &MultiEraBlock) -> Result<JsonValue, ChainJsonError> {
+    let header = block.header();
+    let mut json = Map::new();
+    json.insert("era".to_owned(), era_name(block).into());
+    json.insert("id".to_owned(), hex::encode(block.hash()).into());
+    json.insert("ancestor".to_owned(), ancestor_to_json(&header));
+    json.insert("height".to_owned(), header.block_number().into());
+    json.insert("slot".to_owned(), header.slot().into());
+    match (block, &header) {
+        (MultiEraBlock::Byron(ByronBlock::EpochBoundary(_)), _) => {
+            json.insert("type".to_owned(), "ebb".into());
+        }
+        (MultiEraBlock::Byron(ByronBlock::Main(main)), MultiEraBlockHeader::Byron(header)) => {
+            let version = &header.extra_data.block_version;
+            let software = &header.extra_data.software_version;
+            json.insert("type".to_owned(), "bft".into());
+            json.insert(
+                "size".to_owned(),
+                json!({ "bytes": block.to_cbor_bytes().len() }),
+            );
+            json.insert(
+                "protocol".to_owned(),
+                json!({
+                    "id": header.protocol_magic,
+                    "version": {
+                        "major": version.u16,
+                        "minor": version.u162,
+                        "patch": version.u8,
+                    },
+                    "software": {
+                        "appName": software.application_name,
+                        "number": software.u32,
+                    },
+                }),
+            );
+            json.insert(
+                "issuer".to_owned(),
+                json!({ "verificationKey": hex::encode(&header.consensus_data.byron_pub_key) }),
+            );
+            json.insert(
+                "transactions".to_owned(),
+                main.body
+                    .tx_payload
+                    .iter()
+                    .map(|tx| byron_tx_to_json(&tx.byron_tx))
+                    .collect(),
+            );
+        }
+        (_, MultiEraBlockHeader::Shelley(header)) => {
+            let body = &header.body;
+            json.insert("type".to_owned(), "praos".into());
+            json.insert("size".to_owned(), json!({ "bytes": body.block_body_size }));
+            json.insert(
+                "nonce".to_owned(),
+                json!({
+                    "output": hex::encode(&body.nonce_vrf.output),
+                    "proof": hex::encode(&body.nonce_vrf.proof),
+                }),
+            );
+            json.insert(
+                "protocol".to_owned(),
+                json!({ "version": {
+                    "major": body.protocol_version.major,
+                    "minor": body.protocol_version.minor,
+                }}),
+            );
+            json.insert(
+                "issuer".to_owned(),
+                json!({
+                    "verificationKey": body.issuer_vkey.to_raw_hex(),
+                    "vrfVerificationKey": body.v_r_f_vkey.to_raw_hex(),
+                    "operationalCertificate": {
+                        "count": body.operational_cert.sequence_number,
+                        "kes": {
+                            "period": body.operational_cert.kes_period,
+                            "verificationKey": body.operational_cert.hot_vkey.to_raw_hex(),
+                        },
+                    },
+                    "leaderValue": {
+                        "output": hex::encode(&body.leader_vrf.output),
+                        "proof": hex::encode(&body.leader_vrf.proof),
+                    },
+                }),
+            );
+            json.insert("transactions".to_owned(), transactions_to_json(block)?);
+        }
+        (_, MultiEraBlockHeader::Babbage(header)) => {
+            let body = &header.header_body;
+            json.insert("type".to_owned(), "praos".into());
+            json.insert("size".to_owned(), json!({ "bytes": body.block_body_size }));
+            json.insert(
+                "protocol".to_owned(),
+                json!({ "version": {
+                    "major": body.protocol_version.major,
+                    "minor": body.protocol_version.minor,
+                }}),
+            );
+            json.insert(
+                "issuer".to_owned(),
+                json!({
+                    "verificationKey": body.issuer_vkey.to_raw_hex(),
+                    "vrfVerificationKey": body.vrf_vkey.to_raw_hex(),
+                    "operationalCertificate": {
+                        "count": body.operational_cert.sequence_number,
+                        "kes": {
+                            "period": body.operational_cert.kes_period,
+                            "verificationKey": body.operational_cert.hot_vkey.to_raw_hex(),
+                        },
+                    },
+                    "leaderValue": {
+                        "output": hex::encode(&body.vrf_result.output),
+                        "proof": hex::encode(&body.vrf_result.proof),
+                    },
+                }),
+            );
+            json.insert("transactions".to_owned(), transactions_to_json(block)?);
+        }
+        // header() always matches the block's era
+        _ => unreachable!(),
+    }
+    Ok(json.into())
+}
+
+fn byron_tx_to_json(tx: &ByronTx) -> JsonValue {
+    let inputs = tx
+        .inputs
+        .iter()
+        .map(|input| match input {
+            ByronTxIn::ByronTxInRegular(regular) => json!({
+                "transaction": { "id": regular.index_1.byron_tx_id.to_hex() },
+                "index": regular.index_1.u32,
+            }),
+            // never used on-chain but representable in the CDDL
+            ByronTxIn::ByronTxInGenesis(genesis) => json!({
+                "transaction": { "id": hex::encode(&genesis.index_1) },
+                "index": genesis.u8,
+            }),
+        })
+        .collect::<Vec<_>>();
+    let outputs = tx
+        .outputs
+        .iter()
+        .map(|output| {
+            json!({
+                "address": output.address.to_base58(),
+                "value": { "ada": { "lovelace": output.amount } },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "id": MultiEraTransactionBody::Byron(tx.clone()).hash().to_hex(),
+        "spends": "inputs",
+        "inputs": inputs,
+        "outputs": outputs,
+    })
+}
+
+fn transactions_to_json(block: &MultiEraBlock) -> Result<JsonValue, ChainJsonError> {
+    let is_conway = matches!(block, MultiEraBlock::Conway(_));
+    let witness_sets = block.transaction_witness_sets();
+    let auxiliary_data_set = block.auxiliary_data_set();
+    let invalid_transactions = block.invalid_transactions();
+    block
+        .transaction_bodies()
+        .into_iter()
+        .zip(witness_sets)
+        .enumerate()
+        .map(|(i, (body, witness_set))| {
+            let index = i as u16;
+            let (conway_body, _dropped) = body
+                .to_conway_lossy()
+                .map_err(|e| ChainJsonError::Unsupported(e.to_string()))?;
+            let tx = Transaction::new(
+                conway_body,
+                witness_set,
+                !invalid_transactions.contains(&index),
+                auxiliary_data_set.get(&index).cloned(),
+            );
+            let mut json = decode_transaction_to_json_value(&tx, ChainJsonSchema::OgmiosV6)?;
+            let obj = json
+                .as_object_mut()
+                .expect("Ogmios transactions are always JSON objects");
+            obj.insert("id".to_owned(), body.hash().to_hex().into());
+            if is_conway {
+                // the body was never converted so this is the original encoding
+                obj.insert("cbor".to_owned(), hex::encode(tx.to_cbor_bytes()).into());
+            } else {
+                obj.remove("cbor");
+                restore_pre_conway_certs(obj, &body);
+            }
+            Ok(json)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(JsonValue::Array)
+}
+
+/// Puts back the certificates that to_conway_lossy() dropped, at their original positions
+fn restore_pre_conway_certs(obj: &mut Map<String, JsonValue>, body: &MultiEraTransactionBody) {
+    let certs = match body.certs() {
+        Some(certs) => certs,
+        None => return,
+    };
+    for (i, cert) in certs.into_iter().enumerate() {
+        let cert_json = match cert {
+            MultiEraCertificate::GenesisKeyDelegation(cert) => genesis_delegation_to_json(&cert),
+            MultiEraCertificate::MoveInstantaneousRewardsCert(cert) => mir_to_json(&cert),
+            _ => continue,
+        };
+        let json_certs = obj
+            .entry("certificates")
+            .or_insert_with(|| JsonValue::Array(vec![]))
+            .as_array_mut()
+            .expect("Ogmios certificates are always a JSON array");
+        json_certs.insert(i.min(json_certs.len()), cert_json);
+    }
+}
+
+fn genesis_delegation_to_json(cert: &GenesisKeyDelegation) -> JsonValue {
+    json!({
+        "type": "genesisDelegation",
+        "delegate": {
+            "id": cert.genesis_delegate_hash.to_hex(),
+            "vrfVerificationKeyHash": cert.v_r_f_key_hash.to_hex(),
+        },
+        "issuer": { "id": cert.genesis_hash.to_hex() },
+    })
+}
+
+fn mir_to_json(cert: &MoveInstantaneousRewardsCert) -> JsonValue {
+    let mir = &cert.move_instantaneous_reward;
+    let source = match mir.pot {
+        MIRPot::Reserve => "reserves",
+        MIRPot::Treasury => "treasury",
+    };
+    match &mir.action {
+        MIRAction::ToStakeCredentials {
+            to_stake_credentials,
+            ..
+        } => {
+            let rewards = to_stake_credentials
+                .iter()
+                .map(|(credential, delta)| {
+                    let hash = match credential {
+                        Credential::PubKey { hash, .. } => hash.to_hex(),
+                        Credential::Script { hash, .. } => hash.to_hex(),
+                    };
+                    // DeltaCoin fits within i64 for any realistic amount
+                    let lovelace = JsonValue::from(i128::from(delta) as i64);
+                    (hash, json!({ "ada": { "lovelace": lovelace } }))
+                })
+                .collect::<Map<_, _>>();
+            json!({
+                "type": "treasuryTransfer",
+                "source": source,
+                "target": "rewardAccounts",
+                "rewards": rewards,
+            })
+        }
+        MIRAction::ToOtherPot { to_other_pot, .. } => json!({
+            "type": "treasuryTransfer",
+            "source": source,
+            "target": match mir.pot {
+                MIRPot::Reserve => "treasury",
+                MIRPot::Treasury => "reserves",
+            },
+            "value": { "ada": { "lovelace": to_other_pot } },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ogmios_babbage_block() {
+        let bytes = hex::decode(BABBAGE_BLOCK).unwrap();
+        let block = MultiEraBlock::from_explicit_network_cbor_bytes(&bytes).unwrap();
+        let json = decode_multi_era_block_to_json_value(&block, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(json["type"], "praos");
+        assert_eq!(json["era"], "babbage");
+        assert_eq!(json["id"], hex::encode(block.hash()));
+        assert_eq!(json["height"], block.header().block_number());
+        assert_eq!(json["slot"], block.header().slot());
+        let txs = json["transactions"].as_array().unwrap();
+        let bodies = block.transaction_bodies();
+        assert_eq!(txs.len(), bodies.len());
+        for (tx, body) in txs.iter().zip(bodies) {
+            assert_eq!(tx["id"], body.hash().to_hex());
+            assert!(tx.get("cbor").is_none());
+        }
+    }
+
+    #[test]
+    fn ogmios_byron_block() {
+        let bytes = hex::decode(BYRON_BLOCK).unwrap();
+        let block = MultiEraBlock::from_explicit_network_cbor_bytes(&bytes).unwrap();
+        let json = decode_multi_era_block_to_json_value(&block, ChainJsonSchema::OgmiosV6).unwrap();
+        assert_eq!(json["type"], "bft");
+        assert_eq!(json["era"], "byron");
+        assert_eq!(json["id"], hex::encode(block.hash()));
+        assert!(
+            decode_multi_era_block_to_json_value(&block, ChainJsonSchema::CardanoCliView).is_err()
+        );
+    }
+
+    const BYRON_BLOCK: &str = "82018385015820f3e188a0adb6e8bc840286be2241bdf8d72efa8078d84534f4ac5e51189f537284830058200e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a85820afc0da64183bf2664f3d4eec7238d524ba607faeeab24fc100eb861dba69971b82035820d36a2619a672494604e11bb447cbcf5231e9f2ba25c2169177edc941bd50ad6c5820afc0da64183bf2664f3d4eec7238d524ba607faeeab24fc100eb861dba69971b58204e66280cd94d591072349bec0a3090a53aa945562efb6d08d56e53654b0e409884820119087158405eba3a05b57a84c877453667b2de00061b50dafafcdd83d7a0b7d0f0959eba7bef72eb9d18142f2deab055f197ac15a830e38aae8155e3cca07d212adb185110810c820282840058405eba3a05b57a84c877453667b2de00061b50dafafcdd83d7a0b7d0f0959eba7bef72eb9d18142f2deab055f197ac15a830e38aae8155e3cca07d212adb18511058409aae625d4d15bcb3733d420e064f1cd338f386e0af049fcd42b455a69d28ad366483d177ba2b801b4136e0d6662e5e9e0a24f2c80a0e78d4c235b4c08f201f4c5840939dcfe5555ee661b9db5d817a70d5c3fa9d1d97c2ae5849696d915606b530f7e9edda5d02a01e61524a766f9c356084616ba058a3de70ea51bf29cd187a5f0758402deb50bb6bb566cc688abe0548612b72e92e16a4b20542d2488eb479b31f6646457bdf8575e3bd9f168d278bb4cc7f91a27efaa7ec4e6e7ab24afafef84f7f0b8483010000826a63617264616e6f2d736c01a058204ba92aa320c60acc9ad7b9a64f2eda55c4d2ec28e604faf186708b4f0c4e8edf849fff8203d90102809fff82809fff81a0";
+
+    const BABBAGE_BLOCK: &str = "820685828a1a0002a1d21a00360d985820f93e682d5b91a94d8660e748aef229c19cb285bfb9830db48941d6a78183d81f58209691ed9d98a5b79d5bc46c4496a6dba7e103f668f525c8349b6b92676cb3eae45820ea49e4652c460b9ee6daafefc999ca667fbe5eb5d7a7aeabbdff6fe19c1a3c9f825840c448c14556645bfb1c2de174b4f375bd8e5c27a41f26661b17d05ab8d30e9f2527681555316ce65a7e4dfb3cf83f57144a95df44d1fa67293c31f0ab15c4f4b55850c15e1be17e969159dd7ea5df78af4154767e6c13e4f41cf67d9bc305f6f4d0e1ec56df71df7e00ab51d9f7de9bdefc88e60a155f4af7225007146c38cff4d5ca7a8067214baba73aa5a2f726da72360904582029571d16f081709b3c48651860077bebf9340abb3fc7133443c54f1f5a5edcf18458200481896957dda8d02d825448eb22aad5e106114d924f49923b2fd68e6b3254d3000058403b0db934233f93c43de54a63f8be99bd21da431dcea996a01213ada37e66c6a3930a95399ce083875ba95ffa58cb8ca9b83c92f7d440adb968d8569d74fe90018207005901c0c8e3bb3da1d72fa8a736a6a66bec894083a7f74b388181c54b8faaaf7ccd020acd7a7fbde4a2505f6fbfa907a8bca491219713841720e122a94a057ad065850e88894f7b7985335bd2e3c6bbed5a3fa7fd526a8afafd78fd04dfaef371ad55d0d3d0f1707db5e6773f3ccb896d9f81b1428c3dbcf693c54580f91bb93364272349c08980f71e74e74f8d7863a29cee3fbd821642c14285d8198c68fdbc0a193bf63d97c7a5481ce7da17c97f43a7b98d0cad8c3af26ae4f9ac695fab06ca34685ec57339673d084e0417f844747025f4f5b42cbd883159a3d52dab9dafdfb37b0136757b67ec4e1a6768c43cee5937b81e03224aa6909a9a3254cdf33887914d67723f9a5a4505ac41b658cec10d6c2949e3d3c58bc7a27b64eb8407cbdad7806db704e7ee172405ddaa634119f878541d2f19674dcd0aebf4e6e1091635ce177489742c28d61a4698edaea3364b92b03a73838ca24ce83913727154dae3c2ee4a331997dacd155753f014bc98cfa25b8d84c808654852b5e1e74e251651e30063a6451a676795aa421ea59daa711a7ff0d4140de86bc89969f717d461690c341da4cd440818205961ed9cc9970f52b10ac1b1c3ed3e0d0170582daec84ef2068080a080";
+}
@@ -0,0 +1,542 @@
+//! Lazy views of blocks for indexers that only need some parts of each transaction.
+//!
+//! Indexing a block only walks its CBOR structure to find where the header and each
+//! transaction's body, witness set and auxiliary data are. Nothing is decoded or copied
+//! until asked for and hashes are taken over the original bytes.
+
+use std::convert::TryFrom;
+use std::io::{Cursor, Seek, SeekFrom};
+use std::ops::Range;
+
+use cbor_event::de::Deserializer;
+use cbor_event::{Len, Special, Type};
+use cml_chain::auxdata::{AuxiliaryData, Metadata};
+use cml_chain::block::Header;
+use cml_chain::transaction::{TransactionBody, TransactionWitnessSet};
+use cml_core::error::{DeserializeError, DeserializeFailure, Key};
+use cml_core::reader::DecodeReader;
+use cml_core::serialization::Deserialize;
+use cml_core::TransactionIndex;
+use cml_crypto::{blake2b256, TransactionHash};
+
+use crate::allegra::{AllegraAuxiliaryData, AllegraTransactionBody, AllegraTransactionWitnessSet};
+use crate::alonzo::{AlonzoAuxiliaryData, AlonzoTransactionBody, AlonzoTransactionWitnessSet};
+use crate::babbage::{BabbageAuxiliaryData, BabbageTransactionBody, BabbageTransactionWitnessSet};
+use crate::block_reader::item_done;
+use crate::byron::block::{ByronBlockHeader, EbbHead};
+use crate::byron::transaction::ByronTx;
+use crate::mary::MaryTransactionBody;
+use crate::shelley::{ShelleyHeader, ShelleyTransactionBody, ShelleyTransactionWitnessSet};
+use crate::utils::MultiEraBlockHeader;
+use crate::{MultiEraBlock, MultiEraTransactionBody};
+
+type Raw<'a> = Deserializer<DecodeReader<Cursor<&'a [u8]>>>;
+
+/// Header span and transactions found when indexing a block
+type Indexed<'a> = (Range<usize>, Vec<LazyTransaction<'a>>);
+
+/**
+ * A block indexed by where its components are in the original CBOR bytes.
+ *
+ * Construction only checks the block's outer structure (down to each transaction's components)
+ * and is much cheaper than decoding a MultiEraBlock. Components are decoded on demand and
+ * decoding errors report offsets relative to the start of the network block bytes.
+ */
+#[derive(Clone, Debug)]
+pub struct LazyBlock<'a> {
+    bytes: &'a [u8],
+    era: u64,
+    header: Range<usize>,
+    transactions: Vec<LazyTransaction<'a>>,
+}
+
+impl<'a> LazyBlock<'a> {
+    /// Indexes a block in the `[era, block]` network format. See MultiEraBlock::from_explicit_network_cbor_bytes()
+    pub fn from_explicit_network_cbor_bytes(bytes: &'a [u8]) -> Result<Self, DeserializeError> {
+        let mut raw = Deserializer::from(DecodeReader::new(Cursor::new(bytes)));
+        Self::index(bytes, &mut raw)
+    }
+
+    fn index(bytes: &'a [u8], raw: &mut Raw<'a>) -> Result<Self, DeserializeError> {
+        let len = fixed_array(raw, 2)?;
+        let era = raw
+            .unsigned_integer()
+            .map_err(|e| DeserializeError::from(e).annotate("block_era_tag"))?;
+        let (header, transactions) = match era {
+            0 => index_byron_ebb(raw).map_err(|e| e.annotate("Byron EBB"))?,
+            1 => index_byron_main(raw, bytes).map_err(|e| e.annotate("Byron"))?,
+            2..=7 => index_shelley_based(raw, bytes, era).map_err(|e| e.annotate(era_name(era)))?,
+            _ => return Err(DeserializeFailure::NoVariantMatched.into()),
+        };
+        finish(raw, len)?;
+        Ok(Self {
+            bytes,
+            era,
+            header,
+            transactions,
+        })
+    }
+
+    /// Era tag from the network format (0 = Byron EBB, 1 = Byron, 2 = Shelley, ..., 7 = Conway)
+    pub fn era(&self) -> u64 {
+        self.era
+    }
+
+    /// The complete `[era, block]` bytes this was indexed from
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn header_span(&self) -> Range<usize> {
+        self.header.clone()
+    }
+
+    pub fn header_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.header.clone()]
+    }
+
+    pub fn header(&self) -> Result<MultiEraBlockHeader, DeserializeError> {
+        match self.era {
+            0 => decode_at::<EbbHead>(self.bytes, &self.header).map(MultiEraBlockHeader::ByronEB),
+            1 => decode_at::<ByronBlockHeader>(self.bytes, &self.header)
+                .map(MultiEraBlockHeader::Byron),
+            2..=5 => decode_at::<ShelleyHeader>(self.bytes, &self.header)
+                .map(MultiEraBlockHeader::Shelley),
+            _ => decode_at::<Header>(self.bytes, &self.header).map(MultiEraBlockHeader::Babbage),
+        }
+    }
+
+    /// Same as MultiEraBlock::hash() but taken over the original header bytes
+    pub fn hash(&self) -> [u8; 32] {
+        match self.era {
+            // see MultiEraBlock::hash() for why Byron headers are wrapped
+            0 | 1 => {
+                let mut tagged_bytes = vec![0x82, self.era as u8];
+                tagged_bytes.extend(self.header_bytes());
+                blake2b256(&tagged_bytes)
+            }
+            _ => blake2b256(self.header_bytes()),
+        }
+    }
+
+    pub fn transactions(&self) -> &[LazyTransaction<'a>] {
+        &self.transactions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Decodes the entire block
+    pub fn to_block(&self) -> Result<MultiEraBlock, DeserializeError> {
+        MultiEraBlock::from_explicit_network_cbor_bytes(self.bytes)
+    }
+}
+
+/// A transaction within a LazyBlock. Spans are byte ranges within LazyBlock::bytes()
+#[derive(Clone, Debug)]
+pub struct LazyTransaction<'a> {
+    bytes: &'a [u8],
+    era: u64,
+    index: TransactionIndex,
+    body: Range<usize>,
+    witness_set: Range<usize>,
+    auxiliary_data: Option<Range<usize>>,
+    is_valid: bool,
+}
+
+impl<'a> LazyTransaction<'a> {
+    pub fn index(&self) -> TransactionIndex {
+        self.index
+    }
+
+    /// Whether phase-2 validation is expected to succeed. Always true before Alonzo.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    pub fn body_span(&self) -> Range<usize> {
+        self.body.clone()
+    }
+
+    pub fn body_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.body.clone()]
+    }
+
+    pub fn witness_set_span(&self) -> Range<usize> {
+        self.witness_set.clone()
+    }
+
+    /// For Byron this is the array of the transaction's witnesses
+    pub fn witness_set_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.witness_set.clone()]
+    }
+
+    pub fn auxiliary_data_span(&self) -> Option<Range<usize>> {
+        self.auxiliary_data.clone()
+    }
+
+    pub fn auxiliary_data_bytes(&self) -> Option<&'a [u8]> {
+        self.auxiliary_data
+            .as_ref()
+            .map(|span| &self.bytes[span.clone()])
+    }
+
+    /// Hash of the original body bytes. Does not decode the body.
+    pub fn hash(&self) -> TransactionHash {
+        blake2b256(self.body_bytes()).into()
+    }
+
+    pub fn body(&self) -> Result<MultiEraTransactionBody, DeserializeError> {
+        let body = &self.body;
+        match self.era {
+            1 => decode_at::<ByronTx>(self.bytes, body).map(MultiEraTransactionBody::Byron),
+            2 => decode_at::<ShelleyTransactionBody>(self.bytes, body)
+                .map(MultiEraTransactionBody::Shelley),
+            3 => decode_at::<AllegraTransactionBody>(self.bytes, body)
+                .map(MultiEraTransactionBody::Allegra),
+            4 => decode_at::<MaryTransactionBody>(self.bytes, body)
+                .map(MultiEraTransactionBody::Mary),
+            5 => decode_at::<AlonzoTransactionBody>(self.bytes, body)
+                .map(MultiEraTransactionBody::Alonzo),
+            6 => decode_at::<BabbageTransactionBody>(self.bytes, body)
+                .map(MultiEraTransactionBody::Babbage),
+            _ => {
+                decode_at::<TransactionBody>(self.bytes, body).map(MultiEraTransactionBody::Conway)
+            }
+        }
+    }
+
+    /// Witnesses converted to the current era's format.
+    /// Byron witnesses have no equivalent and will return None.
+    pub fn witness_set(&self) -> Result<Option<TransactionWitnessSet>, DeserializeError> {
+        let wits = &self.witness_set;
+        match self.era {
+            1 => Ok(None),
+            2 => {
+                decode_at::<ShelleyTransactionWitnessSet>(self.bytes, wits).map(|w| Some(w.into()))
+            }
+            3 | 4 => {
+                decode_at::<AllegraTransactionWitnessSet>(self.bytes, wits).map(|w| Some(w.into()))
+            }
+            5 => decode_at::<AlonzoTransactionWitnessSet>(self.bytes, wits).map(|w| Some(w.into())),
+            6 => {
+                decode_at::<BabbageTransactionWitnessSet>(self.bytes, wits).map(|w| Some(w.into()))
+            }
+            _ => decode_at::<TransactionWitnessSet>(self.bytes, wits).map(Some),
+        }
+    }
+
+    pub fn auxiliary_data(&self) -> Result<Option<AuxiliaryData>, DeserializeError> {
+        let aux = match &self.auxiliary_data {
+            Some(aux) => aux,
+            None => return Ok(None),
+        };
+        match self.era {
+            2 => decode_at::<Metadata>(self.bytes, aux).map(AuxiliaryData::new_shelley),
+            3 | 4 => decode_at::<AllegraAuxiliaryData>(self.bytes, aux).map(Into::into),
+            5 => decode_at::<AlonzoAuxiliaryData>(self.bytes, aux).map(Into::into),
+            6 => decode_at::<BabbageAuxiliaryData>(self.bytes, aux).map(Into::into),
+            _ => decode_at::<AuxiliaryData>(self.bytes, aux),
+        }
+        .map(Some)
+    }
+}
+
+fn era_name(era: u64) -> &'static str {
+    match era {
+        2 => "Shelley",
+        3 => "Allegra",
+        4 => "Mary",
+        5 => "Alonzo",
+        6 => "Babbage",
+        _ => "Conway",
+    }
+}
+
+/// Decodes the item at `span` reading from the whole block so errors have block offsets
+fn decode_at<T: Deserialize>(bytes: &[u8], span: &Range<usize>) -> Result<T, DeserializeError> {
+    let mut cursor = Cursor::new(bytes);
+    cursor.set_position(span.start as u64);
+    let mut raw = Deserializer::from(DecodeReader::new(cursor));
+    T::deserialize(&mut raw)
+}
+
+impl<'a> LazyTransaction<'a> {
+    fn new(
+        bytes: &'a [u8],
+        era: u64,
+        index: usize,
+        body: Range<usize>,
+        witness_set: Range<usize>,
+    ) -> Self {
+        Self {
+            bytes,
+            era,
+            index: index as TransactionIndex,
+            body,
+            witness_set,
+            auxiliary_data: None,
+            is_valid: true,
+        }
+    }
+}
+
+/// `[header, body, extra]` where the body is a list of stakeholder ids
+fn index_byron_ebb<'a>(raw: &mut Raw<'a>) -> Result<Indexed<'a>, DeserializeError> {
+    let len = fixed_array(raw, 3)?;
+    let header = skip_item(raw).map_err(|e| e.annotate("header"))?;
+    skip_item(raw).map_err(|e| e.annotate("body"))?;
+    skip_item(raw).map_err(|e| e.annotate("extra"))?;
+    finish(raw, len)?;
+    Ok((header, Vec::new()))
+}
+
+/// `[header, [tx_payload, ssc, dlg, upd], extra]` where the tx payload is a list of `[tx, [* twit]]`
+fn index_byron_main<'a>(
+    raw: &mut Raw<'a>,
+    bytes: &'a [u8],
+) -> Result<Indexed<'a>, DeserializeError> {
+    let len = fixed_array(raw, 3)?;
+    let header = skip_item(raw).map_err(|e| e.annotate("header"))?;
+    let mut transactions = Vec::new();
+    (|| -> Result<_, DeserializeError> {
+        let body_len = fixed_array(raw, 4)?;
+        let payload_len = raw.array()?;
+        while has_next(raw, payload_len, transactions.len())? {
+            let tx_len = fixed_array(raw, 2)?;
+            let body = skip_item(raw)?;
+            let witness_set = skip_item(raw)?;
+            finish(raw, tx_len)?;
+            transactions.push(LazyTransaction::new(
+                bytes,
+                1,
+                transactions.len(),
+                body,
+                witness_set,
+            ));
+        }
+        skip_item(raw)?;
+        skip_item(raw)?;
+        skip_item(raw)?;
+        finish(raw, body_len)
+    })()
+    .map_err(|e| e.annotate("body"))?;
+    skip_item(raw).map_err(|e| e.annotate("extra"))?;
+    finish(raw, len)?;
+    Ok((header, transactions))
+}
+
+/// `[header, [* body], [* witness_set], { * index => aux }]` followed by `[* invalid_index]` from Alonzo on
+fn index_shelley_based<'a>(
+    raw: &mut Raw<'a>,
+    bytes: &'a [u8],
+    era: u64,
+) -> Result<Indexed<'a>, DeserializeError> {
+    let has_invalid_transactions = era >= 5;
+    let len = fixed_array(raw, if has_invalid_transactions { 5 } else { 4 })?;
+    let header = skip_item(raw).map_err(|e| e.annotate("header"))?;
+    let bodies = item_spans(raw).map_err(|e| e.annotate("transaction_bodies"))?;
+    let witness_sets = item_spans(raw).map_err(|e| e.annotate("transaction_witness_sets"))?;
+    if witness_sets.len() != bodies.len() {
+        return Err(DeserializeError::new(
+            "transaction_witness_sets",
+            DeserializeFailure::DefiniteLenMismatch(
+                witness_sets.len() as u64,
+                Some(bodies.len() as u64),
+            ),
+        ));
+    }
+    let mut transactions = bodies
+        .into_iter()
+        .zip(witness_sets)
+        .enumerate()
+        .map(|(index, (body, witness_set))| {
+            LazyTransaction::new(bytes, era, index, body, witness_set)
+        })
+        .collect::<Vec<_>>();
+    let tx_count = transactions.len();
+    (|| -> Result<_, DeserializeError> {
+        let aux_len = raw.map()?;
+        let mut read = 0;
+        while has_next(raw, aux_len, read)? {
+            let index = raw.unsigned_integer()?;
+            let aux = skip_item(raw)?;
+            let tx = usize::try_from(index)
+                .ok()
+                .and_then(|index| transactions.get_mut(index))
+                .ok_or(DeserializeFailure::OutOfRange {
+                    min: 0,
+                    max: tx_count.saturating_sub(1),
+                    found: index as usize,
+                })?;
+            if tx.auxiliary_data.replace(aux).is_some() {
+                return Err(DeserializeFailure::DuplicateKey(Key::Uint(index)).into());
+            }
+            read += 1;
+        }
+        Ok(())
+    })()
+    .map_err(|e| e.annotate("auxiliary_data_set"))?;
+    if has_invalid_transactions {
+        (|| -> Result<_, DeserializeError> {
+            let invalid_len = raw.array()?;
+            let mut read = 0;
+            while has_next(raw, invalid_len, read)? {
+                let index = raw.unsigned_integer()?;
+                let tx = TransactionIndex::try_from(index)
+                    .ok()
+                    .and_then(|index| transactions.get_mut(index as usize))
+                    .ok_or(DeserializeFailure::OutOfRange {
+                        min: 0,
+                        max: tx_count.saturating_sub(1),
+                        found: index as usize,
+                    })?;
+                tx.is_valid = false;
+                read += 1;
+            }
+            Ok(())
+        })()
+        .map_err(|e| e.annotate("invalid_transactions"))?;
+    }
+    finish(raw, len)?;
+    Ok((header, transactions))
+}
+
+/// Reads the header of an array that must have `expected` elements
+fn fixed_array(raw: &mut Raw, expected: u64) -> Result<Len, DeserializeError> {
+    let len = raw.array()?;
+    match len {
+        Len::Len(found) if found != expected => {
+            Err(DeserializeFailure::DefiniteLenMismatch(found, Some(expected)).into())
+        }
+        _ => Ok(len),
+    }
+}
+
+/// Whether another element follows after `read` elements of an array/map of length `len`.
+/// Consumes the break of indefinite length ones.
+fn has_next(raw: &mut Raw, len: Len, read: usize) -> Result<bool, DeserializeError> {
+    match len {
+        Len::Len(n) => Ok((read as u64) < n),
+        Len::Indefinite => Ok(!(raw.cbor_type()? == Type::Special && raw.special_break()?)),
+    }
+}
+
+/// Consumes the break of an indefinite length array after all its expected elements were read
+fn finish(raw: &mut Raw, len: Len) -> Result<(), DeserializeError> {
+    match len {
+        Len::Len(_) => Ok(()),
+        Len::Indefinite => match raw.special()? {
+            Special::Break => Ok(()),
+            _ => Err(DeserializeFailure::EndingBreakMissing.into()),
+        },
+    }
+}
+
+/// Spans of every element of an array
+fn item_spans(raw: &mut Raw) -> Result<Vec<Range<usize>>, DeserializeError> {
+    let len = raw.array()?;
+    let mut spans = Vec::new();
+    while has_next(raw, len, spans.len())? {
+        spans.push(skip_item(raw)?);
+    }
+    Ok(spans)
+}
+
+/// Moves past `len` bytes, failing if there aren't that many left
+fn advance(raw: &mut Raw, len: u64) -> Result<(), DeserializeError> {
+    let reader = raw.as_mut_ref();
+    let available = reader.get_ref().get_ref().len() as u64 - reader.position();
+    if len > available {
+        return Err(cbor_event::Error::NotEnough(available as usize, len as usize).into());
+    }
+    reader
+        .seek(SeekFrom::Start(reader.position() + len))
+        .map_err(cbor_event::Error::IoError)?;
+    Ok(())
+}
+
+/// Moves past one complete CBOR item without decoding or copying it. Returns the item's span.
+fn skip_item(raw: &mut Raw) -> Result<Range<usize>, DeserializeError> {
+    let start = raw.as_mut_ref().position() as usize;
+    // items remaining in each containing array/map/tag/string, None for indefinite ones
+    let mut remaining: Vec<Option<u64>> = vec![Some(1)];
+    while !remaining.is_empty() {
+        let cbor_type = raw.cbor_type()?;
+        if cbor_type == Type::Special {
+            if let Special::Break = raw.special()? {
+                if remaining.pop() != Some(None) {
+                    return Err(DeserializeFailure::BreakInDefiniteLen.into());
+                }
+            }
+            item_done(&mut remaining);
+            continue;
+        }
+        let (len, len_sz) = raw.cbor_len()?;
+        advance(raw, 1 + len_sz as u64)?;
+        match (cbor_type, len) {
+            (Type::UnsignedInteger | Type::NegativeInteger | Type::Tag, Len::Indefinite) => {
+                return Err(cbor_event::Error::IndefiniteLenNotSupported(cbor_type).into());
+            }
+            (Type::UnsignedInteger | Type::NegativeInteger, _) => item_done(&mut remaining),
+            (Type::Tag, _) => remaining.push(Some(1)),
+            (_, Len::Indefinite) => remaining.push(None),
+            (Type::Bytes | Type::Text, Len::Len(n)) => {
+                advance(raw, n)?;
+                item_done(&mut remaining);
+            }
+            (_, Len::Len(0)) => item_done(&mut remaining),
+            (Type::Map, Len::Len(n)) => remaining.push(Some(n.saturating_mul(2))),
+            (_, Len::Len(n)) => remaining.push(Some(n)),
+        }
+    }
+    Ok(start..raw.as_mut_ref().position() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_indefinite_items() {
+        // [_ h'01', (_ h'02' h'03'), {_ 1: 2}, 24(-1), 1.5] followed by 0
+        let bytes = hex::decode("9f41015f41024103ffbf0102ffd81820f93e00ff00").unwrap();
+        let mut raw = Deserializer::from(DecodeReader::new(Cursor::new(bytes.as_slice())));
+        assert_eq!(skip_item(&mut raw).unwrap(), 0..bytes.len() - 1);
+        assert_eq!(raw.unsigned_integer().unwrap(), 0);
+    }
+
+    #[test]
+    fn skip_truncated_item() {
+        // [h'0102', 3] missing its last byte
+        let bytes = hex::decode("82420102").unwrap();
+        let mut raw = Deserializer::from(DecodeReader::new(Cursor::new(bytes.as_slice())));
+        assert!(skip_item(&mut raw).is_err());
+        // bytes string claims more bytes than there are
+        let bytes = hex::decode("4401").unwrap();
+        let mut raw = Deserializer::from(DecodeReader::new(Cursor::new(bytes.as_slice())));
+        assert!(skip_item(&mut raw).is_err());
+    }
+
+    #[test]
+    fn unexpected_break() {
+        let bytes = hex::decode("8101ff").unwrap();
+        let mut raw = Deserializer::from(DecodeReader::new(Cursor::new(bytes.as_slice())));
+        assert_eq!(skip_item(&mut raw).unwrap(), 0..2);
+        assert!(skip_item(&mut raw).is_err());
+    }
+    #[test]
+    fn invalid_transaction_index_out_of_range() {
+        // [6, [0, [0], [0], {}, [0]]] i.e. a Babbage block with one (skipped) invalid transaction
+        let bytes = hex::decode("8206850081008100a08100").unwrap();
+        let block = LazyBlock::from_explicit_network_cbor_bytes(&bytes).unwrap();
+        assert!(!block.transactions()[0].is_valid());
+        // an index past the transactions
+        let bytes = hex::decode("8206850081008100a08101").unwrap();
+        assert!(LazyBlock::from_explicit_network_cbor_bytes(&bytes).is_err());
+        // 65536 would truncate to index 0
+        let bytes = hex::decode("8206850081008100a0811a00010000").unwrap();
+        assert!(LazyBlock::from_explicit_network_cbor_bytes(&bytes).is_err());
+    }
+}
@@ -4,7 +4,10 @@ extern crate derivative;
 pub mod allegra;
 pub mod alonzo;
 pub mod babbage;
+pub mod block_reader;
 pub mod byron;
+pub mod json;
+pub mod lazy;
 pub mod mary;
 pub mod serialization;
 pub mod shelley;
@@ -13,16 +16,22 @@ pub mod utils;
 // https://github.com/dcSpark/cddl-codegen
 
 use crate::{
-    allegra::{AllegraBlock, AllegraTransactionBody},
-    alonzo::{AlonzoBlock, AlonzoTransactionBody},
-    babbage::{BabbageBlock, BabbageTransactionBody},
-    byron::{block::ByronBlock, transaction::ByronTx},
-    mary::{MaryBlock, MaryTransactionBody},
-    shelley::{ShelleyBlock, ShelleyTransactionBody},
+    allegra::{AllegraBlock, AllegraTransaction, AllegraTransactionBody},
+    alonzo::{AlonzoBlock, AlonzoTransaction, AlonzoTransactionBody},
+    babbage::{BabbageBlock, BabbageTransaction, BabbageTransactionBody},
+    byron::{
+        block::{ByronBlock, TxAux},
+        transaction::ByronTx,
+    },
+    mary::{MaryBlock, MaryTransaction, MaryTransactionBody},
+    shelley::{ShelleyBlock, ShelleyTransaction, ShelleyTransactionBody},
 };
 use cml_chain::block::Block;
 use cml_chain::crypto::GenesisHash;
-use cml_chain::{address::RewardAccount, transaction::TransactionBody};
+use cml_chain::{
+    address::RewardAccount,
+    transaction::{Transaction, TransactionBody},
+};
 
 pub type GenesisHashList = Vec<GenesisHash>;
 
@@ -69,6 +78,47 @@ impl MultiEraBlock {
     }
 }
 
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub enum MultiEraTransaction {
+    Byron(TxAux),
+    Shelley(ShelleyTransaction),
+    Allegra(AllegraTransaction),
+    Mary(MaryTransaction),
+    Alonzo(AlonzoTransaction),
+    Babbage(BabbageTransaction),
+    Conway(Transaction),
+}
+
+impl MultiEraTransaction {
+    pub fn new_byron(byron: TxAux) -> Self {
+        Self::Byron(byron)
+    }
+
+    pub fn new_shelley(shelley: ShelleyTransaction) -> Self {
+        Self::Shelley(shelley)
+    }
+
+    pub fn new_allegra(allegra: AllegraTransaction) -> Self {
+        Self::Allegra(allegra)
+    }
+
+    pub fn new_mary(mary: MaryTransaction) -> Self {
+        Self::Mary(mary)
+    }
+
+    pub fn new_alonzo(alonzo: AlonzoTransaction) -> Self {
+        Self::Alonzo(alonzo)
+    }
+
+    pub fn new_babbage(babbage: BabbageTransaction) -> Self {
+        Self::Babbage(babbage)
+    }
+
+    pub fn new_conway(conway: Transaction) -> Self {
+        Self::Conway(conway)
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
 pub enum MultiEraTransactionBody {
     Byron(ByronTx),
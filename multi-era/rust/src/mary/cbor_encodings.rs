@@ -3,6 +3,7 @@
 
 use cml_chain::{address::RewardAccount, assets::AssetName, PolicyId};
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default)]
@@ -41,6 +42,7 @@ pub struct MaryTransactionBodyEncoding {
     pub mint_value_encodings:
         BTreeMap<PolicyId, (LenEncoding, BTreeMap<AssetName, Option<cbor_event::Sz>>)>,
     pub mint_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
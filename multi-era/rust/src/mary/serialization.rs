@@ -11,6 +11,8 @@ use cml_chain::assets::AssetName;
 use cml_chain::PolicyId;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::{CborValue, Int};
 use cml_crypto::RawBytesEncoding;
 use std::io::{BufRead, Seek, Write};
 
@@ -260,6 +262,26 @@ impl Serialize for MaryTransactionBody {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl MaryTransactionBody {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -288,7 +310,7 @@ impl Serialize for MaryTransactionBody {
                     } + match &self.mint {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -323,7 +345,9 @@ impl Serialize for MaryTransactionBody {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -701,6 +725,8 @@ impl Serialize for MaryTransactionBody {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -717,6 +743,7 @@ impl Deserialize for MaryTransactionBody {
         read_len.read_elems(3)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut inputs_encoding = LenEncoding::default();
             let mut inputs_key_encoding = None;
             let mut inputs = None;
@@ -750,236 +777,450 @@ impl Deserialize for MaryTransactionBody {
             let mut mint_key_encoding = None;
             let mut mint = None;
             let mut read = 0;
-            while match len { cbor_event::LenSz::Len(n, _) => read < n, cbor_event::LenSz::Indefinite => true, } {
+            while match len {
+                cbor_event::LenSz::Len(n, _) => read < n,
+                cbor_event::LenSz::Indefinite => true,
+            } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) =>  {
-                            if inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_inputs, tmp_inputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut inputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let inputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (inputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    inputs_arr.push(TransactionInput::deserialize(raw)?);
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
                                 }
-                                Ok((inputs_arr, inputs_encoding))
-                            })().map_err(|e| e.annotate("inputs"))?;
-                            inputs = Some(tmp_inputs);
-                            inputs_encoding = tmp_inputs_encoding;
-                            inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        },
-                        (1, key_enc) =>  {
-                            if outputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_outputs, tmp_outputs_encoding) = (|| -> Result<_, DeserializeError> {
-                                let mut outputs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let outputs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (outputs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    outputs_arr.push(MaryTransactionOutput::deserialize(raw)?);
+                                let (tmp_inputs, tmp_inputs_encoding) =
+                                    deserialize_array(raw, TransactionInput::deserialize)
+                                        .map_err(|e| e.annotate("inputs"))?;
+                                inputs = Some(tmp_inputs);
+                                inputs_encoding = tmp_inputs_encoding;
+                                inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if outputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
                                 }
-                                Ok((outputs_arr, outputs_encoding))
-                            })().map_err(|e| e.annotate("outputs"))?;
-                            outputs = Some(tmp_outputs);
-                            outputs_encoding = tmp_outputs_encoding;
-                            outputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        },
-                        (2, key_enc) =>  {
-                            if fee.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_fee, tmp_fee_encoding) = raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc))).map_err(|e: DeserializeError| e.annotate("fee"))?;
-                            fee = Some(tmp_fee);
-                            fee_encoding = tmp_fee_encoding;
-                            fee_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        },
-                        (3, key_enc) =>  {
-                            if ttl.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_ttl, tmp_ttl_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc)))
-                            })().map_err(|e| e.annotate("ttl"))?;
-                            ttl = Some(tmp_ttl);
-                            ttl_encoding = tmp_ttl_encoding;
-                            ttl_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        },
-                        (4, key_enc) =>  {
-                            if certs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_certs, tmp_certs_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut certs_arr = Vec::new();
-                                let len = raw.array_sz()?;
-                                let certs_encoding = len.into();
-                                while match len { cbor_event::LenSz::Len(n, _) => (certs_arr.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    certs_arr.push(AllegraCertificate::deserialize(raw)?);
+                                let (tmp_outputs, tmp_outputs_encoding) =
+                                    deserialize_array(raw, MaryTransactionOutput::deserialize)
+                                        .map_err(|e| e.annotate("outputs"))?;
+                                outputs = Some(tmp_outputs);
+                                outputs_encoding = tmp_outputs_encoding;
+                                outputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, _read_len| {
+                                if fee.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
                                 }
-                                Ok((certs_arr, certs_encoding))
-                            })().map_err(|e| e.annotate("certs"))?;
-                            certs = Some(tmp_certs);
-                            certs_encoding = tmp_certs_encoding;
-                            certs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        },
-                        (5, key_enc) =>  {
-                            if withdrawals.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_withdrawals, tmp_withdrawals_encoding, tmp_withdrawals_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut withdrawals_table = OrderedHashMap::new();
-                                let withdrawals_len = raw.map_sz()?;
-                                let withdrawals_encoding = withdrawals_len.into();
-                                let mut withdrawals_value_encodings = BTreeMap::new();
-                                while match withdrawals_len { cbor_event::LenSz::Len(n, _) => (withdrawals_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let withdrawals_key = RewardAccount::deserialize(raw)?;
-                                    let (withdrawals_value, withdrawals_value_encoding) = raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc)))?;
-                                    if withdrawals_table.insert(withdrawals_key.clone(), withdrawals_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
-                                    }
-                                    withdrawals_value_encodings.insert(withdrawals_key, withdrawals_value_encoding);
+                                let (tmp_fee, tmp_fee_encoding) = raw
+                                    .unsigned_integer_sz()
+                                    .map_err(Into::<DeserializeError>::into)
+                                    .map(|(x, enc)| (x, Some(enc)))
+                                    .map_err(|e: DeserializeError| e.annotate("fee"))?;
+                                fee = Some(tmp_fee);
+                                fee_encoding = tmp_fee_encoding;
+                                fee_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if ttl.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
                                 }
-                                Ok((withdrawals_table, withdrawals_encoding, withdrawals_value_encodings))
-                            })().map_err(|e| e.annotate("withdrawals"))?;
-                            withdrawals = Some(tmp_withdrawals);
-                            withdrawals_encoding = tmp_withdrawals_encoding;
-                            withdrawals_value_encodings = tmp_withdrawals_value_encodings;
-                            withdrawals_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        },
-                        (6, key_enc) =>  {
-                            if update.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let tmp_update = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ShelleyUpdate::deserialize(raw)
-                            })().map_err(|e| e.annotate("update"))?;
-                            update = Some(tmp_update);
-                            update_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        },
-                        (7, key_enc) =>  {
-                            if auxiliary_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| AuxiliaryDataHash::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))
-                            })().map_err(|e| e.annotate("auxiliary_data_hash"))?;
-                            auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
-                            auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
-                            auxiliary_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        },
-                        (8, key_enc) =>  {
-                            if validity_interval_start.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_validity_interval_start, tmp_validity_interval_start_encoding) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                raw.unsigned_integer_sz().map_err(Into::<DeserializeError>::into).map(|(x, enc)| (x, Some(enc)))
-                            })().map_err(|e| e.annotate("validity_interval_start"))?;
-                            validity_interval_start = Some(tmp_validity_interval_start);
-                            validity_interval_start_encoding = tmp_validity_interval_start_encoding;
-                            validity_interval_start_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        },
-                        (9, key_enc) =>  {
-                            if mint.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let (tmp_mint, tmp_mint_encoding, tmp_mint_key_encodings, tmp_mint_value_encodings) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut mint_table = OrderedHashMap::new();
-                                let mint_len = raw.map_sz()?;
-                                let mint_encoding = mint_len.into();
-                                let mut mint_key_encodings = BTreeMap::new();
-                                let mut mint_value_encodings = BTreeMap::new();
-                                while match mint_len { cbor_event::LenSz::Len(n, _) => (mint_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let (mint_key, mint_key_encoding) = raw.bytes_sz().map_err(Into::<DeserializeError>::into).and_then(|(bytes, enc)| PolicyId::from_raw_bytes(&bytes).map(|bytes| (bytes, StringEncoding::from(enc))).map_err(|e| DeserializeFailure::InvalidStructure(Box::new(e)).into()))?;
-                                    let mut mint_value_table = OrderedHashMap::new();
-                                    let mint_value_len = raw.map_sz()?;
-                                    let mint_value_encoding = mint_value_len.into();
-                                    let mut mint_value_value_encodings = BTreeMap::new();
-                                    while match mint_value_len { cbor_event::LenSz::Len(n, _) => (mint_value_table.len() as u64) < n, cbor_event::LenSz::Indefinite => true, } {
+                                let (tmp_ttl, tmp_ttl_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("ttl"))?;
+                                ttl = Some(tmp_ttl);
+                                ttl_encoding = tmp_ttl_encoding;
+                                ttl_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if certs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_certs, tmp_certs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, AllegraCertificate::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("certs"))?;
+                                certs = Some(tmp_certs);
+                                certs_encoding = tmp_certs_encoding;
+                                certs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if withdrawals.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_withdrawals,
+                                    tmp_withdrawals_encoding,
+                                    tmp_withdrawals_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut withdrawals_table = OrderedHashMap::new();
+                                    let withdrawals_len = raw.map_sz()?;
+                                    let withdrawals_encoding = withdrawals_len.into();
+                                    let mut withdrawals_value_encodings = BTreeMap::new();
+                                    while match withdrawals_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (withdrawals_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
                                         if raw.cbor_type()? == cbor_event::Type::Special {
                                             assert_eq!(raw.special()?, cbor_event::Special::Break);
                                             break;
                                         }
-                                        let mint_value_key = AssetName::deserialize(raw)?;
-                                        let (mint_value_value, mint_value_value_encoding) = match raw.cbor_type()? {
-                                            cbor_event::Type::UnsignedInteger => {
-                                                let (x, enc) = raw.unsigned_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                            _ => {
-                                                let (x, enc) = raw.negative_integer_sz()?;
-                                                (x as i64, Some(enc))
-                                            },
-                                        };
-                                        if mint_value_table.insert(mint_value_key.clone(), mint_value_value).is_some() {
-                                            return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        let withdrawals_key = RewardAccount::deserialize(raw)?;
+                                        let (withdrawals_value, withdrawals_value_encoding) = raw
+                                            .unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))?;
+                                        if withdrawals_table
+                                            .insert(withdrawals_key.clone(), withdrawals_value)
+                                            .is_some()
+                                        {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
                                         }
-                                        mint_value_value_encodings.insert(mint_value_key, mint_value_value_encoding);
-                                    }
-                                    let (mint_value, mint_value_encoding, mint_value_value_encodings) = (mint_value_table, mint_value_encoding, mint_value_value_encodings);
-                                    if mint_table.insert(mint_key, mint_value).is_some() {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(String::from("some complicated/unsupported type"))).into());
+                                        withdrawals_value_encodings
+                                            .insert(withdrawals_key, withdrawals_value_encoding);
                                     }
-                                    mint_key_encodings.insert(mint_key, mint_key_encoding);
-                                    mint_value_encodings.insert(mint_key, (mint_value_encoding, mint_value_value_encodings));
+                                    Ok((
+                                        withdrawals_table,
+                                        withdrawals_encoding,
+                                        withdrawals_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("withdrawals"))?;
+                                withdrawals = Some(tmp_withdrawals);
+                                withdrawals_encoding = tmp_withdrawals_encoding;
+                                withdrawals_value_encodings = tmp_withdrawals_value_encodings;
+                                withdrawals_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if update.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
                                 }
-                                Ok((mint_table, mint_encoding, mint_key_encodings, mint_value_encodings))
-                            })().map_err(|e| e.annotate("mint"))?;
-                            mint = Some(tmp_mint);
-                            mint_encoding = tmp_mint_encoding;
-                            mint_key_encodings = tmp_mint_key_encodings;
-                            mint_value_encodings = tmp_mint_value_encodings;
-                            mint_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        },
-                        (unknown_key, _enc) => return Err(DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()),
+                                let tmp_update = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    ShelleyUpdate::deserialize(raw)
+                                })()
+                                .map_err(|e| e.annotate("update"))?;
+                                update = Some(tmp_update);
+                                update_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if auxiliary_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                AuxiliaryDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("auxiliary_data_hash"))?;
+                                auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
+                                auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
+                                auxiliary_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if validity_interval_start.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_validity_interval_start,
+                                    tmp_validity_interval_start_encoding,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    raw.unsigned_integer_sz()
+                                        .map_err(Into::<DeserializeError>::into)
+                                        .map(|(x, enc)| (x, Some(enc)))
+                                })()
+                                .map_err(|e| e.annotate("validity_interval_start"))?;
+                                validity_interval_start = Some(tmp_validity_interval_start);
+                                validity_interval_start_encoding =
+                                    tmp_validity_interval_start_encoding;
+                                validity_interval_start_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if mint.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_mint,
+                                    tmp_mint_encoding,
+                                    tmp_mint_key_encodings,
+                                    tmp_mint_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
+                                    read_len.read_elems(1)?;
+                                    let mut mint_table = OrderedHashMap::new();
+                                    let mint_len = raw.map_sz()?;
+                                    let mint_encoding = mint_len.into();
+                                    let mut mint_key_encodings = BTreeMap::new();
+                                    let mut mint_value_encodings = BTreeMap::new();
+                                    while match mint_len {
+                                        cbor_event::LenSz::Len(n, _) => {
+                                            (mint_table.len() as u64) < n
+                                        }
+                                        cbor_event::LenSz::Indefinite => true,
+                                    } {
+                                        if raw.cbor_type()? == cbor_event::Type::Special {
+                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
+                                            break;
+                                        }
+                                        let (mint_key, mint_key_encoding) = raw
+                                            .bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                PolicyId::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })?;
+                                        let mut mint_value_table = OrderedHashMap::new();
+                                        let mint_value_len = raw.map_sz()?;
+                                        let mint_value_encoding = mint_value_len.into();
+                                        let mut mint_value_value_encodings = BTreeMap::new();
+                                        while match mint_value_len {
+                                            cbor_event::LenSz::Len(n, _) => {
+                                                (mint_value_table.len() as u64) < n
+                                            }
+                                            cbor_event::LenSz::Indefinite => true,
+                                        } {
+                                            if raw.cbor_type()? == cbor_event::Type::Special {
+                                                assert_eq!(
+                                                    raw.special()?,
+                                                    cbor_event::Special::Break
+                                                );
+                                                break;
+                                            }
+                                            let mint_value_key = AssetName::deserialize(raw)?;
+                                            let (mint_value_value, mint_value_value_encoding) =
+                                                match raw.cbor_type()? {
+                                                    cbor_event::Type::UnsignedInteger => {
+                                                        let (x, enc) = raw.unsigned_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                    _ => {
+                                                        let (x, enc) = raw.negative_integer_sz()?;
+                                                        (x as i64, Some(enc))
+                                                    }
+                                                };
+                                            if mint_value_table
+                                                .insert(mint_value_key.clone(), mint_value_value)
+                                                .is_some()
+                                            {
+                                                return Err(DeserializeFailure::DuplicateKey(
+                                                    Key::Str(String::from(
+                                                        "some complicated/unsupported type",
+                                                    )),
+                                                )
+                                                .into());
+                                            }
+                                            mint_value_value_encodings
+                                                .insert(mint_value_key, mint_value_value_encoding);
+                                        }
+                                        let (
+                                            mint_value,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        ) = (
+                                            mint_value_table,
+                                            mint_value_encoding,
+                                            mint_value_value_encodings,
+                                        );
+                                        if mint_table.insert(mint_key, mint_value).is_some() {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
+                                        }
+                                        mint_key_encodings.insert(mint_key, mint_key_encoding);
+                                        mint_value_encodings.insert(
+                                            mint_key,
+                                            (mint_value_encoding, mint_value_value_encodings),
+                                        );
+                                    }
+                                    Ok((
+                                        mint_table,
+                                        mint_encoding,
+                                        mint_key_encodings,
+                                        mint_value_encodings,
+                                    ))
+                                })()
+                                .map_err(|e| e.annotate("mint"))?;
+                                mint = Some(tmp_mint);
+                                mint_encoding = tmp_mint_encoding;
+                                mint_key_encodings = tmp_mint_key_encodings;
+                                mint_value_encodings = tmp_mint_value_encodings;
+                                mint_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
-                    cbor_event::Type::Text => return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into()),
+                    cbor_event::Type::Text => {
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
+                    }
                     cbor_event::Type::Special => match len {
-                        cbor_event::LenSz::Len(_, _) => return Err(DeserializeFailure::BreakInDefiniteLen.into()),
+                        cbor_event::LenSz::Len(_, _) => {
+                            return Err(DeserializeFailure::BreakInDefiniteLen.into())
+                        }
                         cbor_event::LenSz::Indefinite => match raw.special()? {
                             cbor_event::Special::Break => break,
                             _ => return Err(DeserializeFailure::EndingBreakMissing.into()),
                         },
                     },
-                    other_type => return Err(DeserializeFailure::UnexpectedKeyType(other_type).into()),
+                    other_type => {
+                        return Err(DeserializeFailure::UnexpectedKeyType(other_type).into())
+                    }
                 }
                 read += 1;
             }
@@ -1032,9 +1273,11 @@ impl Deserialize for MaryTransactionBody {
                     mint_encoding,
                     mint_key_encodings,
                     mint_value_encodings,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
-        })().map_err(|e| e.annotate("MaryTransactionBody"))
+        })()
+        .map_err(|e| e.annotate("MaryTransactionBody"))
     }
 }
 
@@ -1,7 +1,43 @@
-use super::MaryTransactionBody;
+use super::{MaryBlock, MaryTransaction, MaryTransactionBody, MaryTransactionOutput};
+use crate::allegra::{AllegraAuxiliaryData, AllegraCertificate, AllegraTransactionWitnessSet};
+use crate::shelley::{ShelleyHeader, ShelleyUpdate};
+use cml_chain::assets::{Mint, Value};
+use cml_chain::transaction::TransactionInput;
+use cml_chain::utils::withdrawals_field_names;
+use cml_core::diagnostic::FieldNames;
 use cml_core::serialization::Serialize;
 use cml_crypto::{blake2b256, TransactionHash};
 
+cml_core::impl_unknown_fields!(MaryTransactionBody);
+
+cml_core::impl_cbor_field_names!(MaryBlock [
+    header: ShelleyHeader::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(MaryTransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(AllegraTransactionWitnessSet::cbor_field_names()),
+    auxiliary_data_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", AllegraAuxiliaryData::cbor_field_names()),
+    ),
+]);
+cml_core::impl_cbor_field_names!(MaryTransaction [
+    body: MaryTransactionBody::cbor_field_names(),
+    witness_set: AllegraTransactionWitnessSet::cbor_field_names(),
+    auxiliary_data: AllegraAuxiliaryData::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(MaryTransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(MaryTransactionOutput::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(AllegraCertificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    6 => update: ShelleyUpdate::cbor_field_names(),
+    7 => auxiliary_data_hash,
+    8 => validity_interval_start,
+    9 => mint: Mint::cbor_field_names(),
+});
+cml_core::impl_cbor_field_names!(MaryTransactionOutput [address, amount: Value::cbor_field_names()]);
+
 impl MaryTransactionBody {
     pub fn hash(&self) -> TransactionHash {
         blake2b256(&self.to_cbor_bytes()).into()
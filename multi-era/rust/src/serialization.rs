@@ -107,6 +107,110 @@ impl Deserialize for MultiEraBlock {
     }
 }
 
+impl Serialize for MultiEraTransaction {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        match self {
+            MultiEraTransaction::Byron(byron) => {
+                cbor_event::se::Serialize::serialize(byron, serializer)
+            }
+            MultiEraTransaction::Shelley(shelley) => shelley.serialize(serializer, force_canonical),
+            MultiEraTransaction::Allegra(allegra) => allegra.serialize(serializer, force_canonical),
+            MultiEraTransaction::Mary(mary) => mary.serialize(serializer, force_canonical),
+            MultiEraTransaction::Alonzo(alonzo) => alonzo.serialize(serializer, force_canonical),
+            MultiEraTransaction::Babbage(babbage) => babbage.serialize(serializer, force_canonical),
+            MultiEraTransaction::Conway(conway) => conway.serialize(serializer, force_canonical),
+        }
+    }
+}
+
+impl Deserialize for MultiEraTransaction {
+    fn deserialize<R: BufRead + Seek>(raw: &mut Deserializer<R>) -> Result<Self, DeserializeError> {
+        (|| -> Result<_, DeserializeError> {
+            let initial_position = raw.as_mut_ref().stream_position().unwrap();
+            let mut errs = Vec::new();
+            let deser_variant: Result<_, DeserializeError> = TxAux::deserialize(raw);
+            match deser_variant {
+                Ok(byron) => return Ok(Self::Byron(byron)),
+                Err(e) => {
+                    errs.push(e.annotate("Byron"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = ShelleyTransaction::deserialize(raw);
+            match deser_variant {
+                Ok(shelley) => return Ok(Self::Shelley(shelley)),
+                Err(e) => {
+                    errs.push(e.annotate("Shelley"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = AllegraTransaction::deserialize(raw);
+            match deser_variant {
+                Ok(allegra) => return Ok(Self::Allegra(allegra)),
+                Err(e) => {
+                    errs.push(e.annotate("Allegra"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = MaryTransaction::deserialize(raw);
+            match deser_variant {
+                Ok(mary) => return Ok(Self::Mary(mary)),
+                Err(e) => {
+                    errs.push(e.annotate("Mary"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = AlonzoTransaction::deserialize(raw);
+            match deser_variant {
+                Ok(alonzo) => return Ok(Self::Alonzo(alonzo)),
+                Err(e) => {
+                    errs.push(e.annotate("Alonzo"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = BabbageTransaction::deserialize(raw);
+            match deser_variant {
+                Ok(babbage) => return Ok(Self::Babbage(babbage)),
+                Err(e) => {
+                    errs.push(e.annotate("Babbage"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            let deser_variant: Result<_, DeserializeError> = Transaction::deserialize(raw);
+            match deser_variant {
+                Ok(conway) => return Ok(Self::Conway(conway)),
+                Err(e) => {
+                    errs.push(e.annotate("Conway"));
+                    raw.as_mut_ref()
+                        .seek(SeekFrom::Start(initial_position))
+                        .unwrap();
+                }
+            };
+            Err(DeserializeError::new(
+                "MultiEraTransaction",
+                DeserializeFailure::NoVariantMatchedWithCauses(errs),
+            ))
+        })()
+        .map_err(|e| e.annotate("MultiEraTransaction"))
+    }
+}
+
 impl Serialize for MultiEraTransactionBody {
     fn serialize<'se, W: Write>(
         &self,
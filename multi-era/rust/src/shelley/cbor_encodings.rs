@@ -3,6 +3,7 @@
 
 use cml_chain::{address::RewardAccount, certs::StakeCredential, crypto::GenesisHash};
 use cml_core::serialization::{LenEncoding, StringEncoding};
+use cml_core::UnknownFields;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default)]
@@ -118,6 +119,7 @@ pub struct ShelleyProtocolParamUpdateEncoding {
     pub protocol_version_key_encoding: Option<cbor_event::Sz>,
     pub min_utxo_value_encoding: Option<cbor_event::Sz>,
     pub min_utxo_value_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -140,6 +142,7 @@ pub struct ShelleyTransactionBodyEncoding {
     pub update_key_encoding: Option<cbor_event::Sz>,
     pub auxiliary_data_hash_encoding: StringEncoding,
     pub auxiliary_data_hash_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -163,6 +166,7 @@ pub struct ShelleyTransactionWitnessSetEncoding {
     pub native_scripts_key_encoding: Option<cbor_event::Sz>,
     pub bootstrap_witnesses_encoding: LenEncoding,
     pub bootstrap_witnesses_key_encoding: Option<cbor_event::Sz>,
+    pub unknown_fields: Option<Box<UnknownFields>>,
 }
 
 #[derive(Clone, Debug, Default)]
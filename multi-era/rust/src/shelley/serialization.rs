@@ -9,6 +9,8 @@ use cbor_event::se::Serializer;
 use cml_chain::address::RewardAccount;
 use cml_core::error::*;
 use cml_core::serialization::*;
+use cml_core::unknown_fields::{deserialize_array, UnknownFields};
+use cml_core::{CborValue, Int};
 use cml_crypto::GenesisDelegateHash;
 use cml_crypto::RawBytesEncoding;
 use cml_crypto::VRFKeyHash;
@@ -1860,6 +1862,26 @@ impl Serialize for ShelleyProtocolParamUpdate {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ShelleyProtocolParamUpdate {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -1915,7 +1937,7 @@ impl Serialize for ShelleyProtocolParamUpdate {
                     } + match &self.min_utxo_value {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -1977,7 +1999,9 @@ impl Serialize for ShelleyProtocolParamUpdate {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.minfee_a {
@@ -2338,6 +2362,8 @@ impl Serialize for ShelleyProtocolParamUpdate {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -2353,6 +2379,7 @@ impl Deserialize for ShelleyProtocolParamUpdate {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut minfee_a_encoding = None;
             let mut minfee_a_key_encoding = None;
             let mut minfee_a = None;
@@ -2402,263 +2429,443 @@ impl Deserialize for ShelleyProtocolParamUpdate {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if minfee_a.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_minfee_a, tmp_minfee_a_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("minfee_a"))?;
-                            minfee_a = Some(tmp_minfee_a);
-                            minfee_a_encoding = tmp_minfee_a_encoding;
-                            minfee_a_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if minfee_b.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_minfee_b, tmp_minfee_b_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("minfee_b"))?;
-                            minfee_b = Some(tmp_minfee_b);
-                            minfee_b_encoding = tmp_minfee_b_encoding;
-                            minfee_b_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if max_block_body_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_block_body_size"))?;
-                            max_block_body_size = Some(tmp_max_block_body_size);
-                            max_block_body_size_encoding = tmp_max_block_body_size_encoding;
-                            max_block_body_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if max_transaction_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_transaction_size"))?;
-                            max_transaction_size = Some(tmp_max_transaction_size);
-                            max_transaction_size_encoding = tmp_max_transaction_size_encoding;
-                            max_transaction_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_a.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_minfee_a, tmp_minfee_a_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_a"))?;
+                                minfee_a = Some(tmp_minfee_a);
+                                minfee_a_encoding = tmp_minfee_a_encoding;
+                                minfee_a_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if minfee_b.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_minfee_b, tmp_minfee_b_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("minfee_b"))?;
+                                minfee_b = Some(tmp_minfee_b);
+                                minfee_b_encoding = tmp_minfee_b_encoding;
+                                minfee_b_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_block_body_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_max_block_body_size, tmp_max_block_body_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_body_size"))?;
+                                max_block_body_size = Some(tmp_max_block_body_size);
+                                max_block_body_size_encoding = tmp_max_block_body_size_encoding;
+                                max_block_body_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, read_len| {
+                                if max_transaction_size.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_max_transaction_size, tmp_max_transaction_size_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_transaction_size"))?;
+                                max_transaction_size = Some(tmp_max_transaction_size);
+                                max_transaction_size_encoding = tmp_max_transaction_size_encoding;
+                                max_transaction_size_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
                         (4, key_enc) => {
-                            if max_block_header_size.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_max_block_header_size, tmp_max_block_header_size_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("max_block_header_size"))?;
-                            max_block_header_size = Some(tmp_max_block_header_size);
-                            max_block_header_size_encoding = tmp_max_block_header_size_encoding;
-                            max_block_header_size_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (5, key_enc) => {
-                            if key_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (tmp_key_deposit, tmp_key_deposit_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("key_deposit"))?;
-                            key_deposit = Some(tmp_key_deposit);
-                            key_deposit_encoding = tmp_key_deposit_encoding;
-                            key_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        }
-                        (6, key_enc) => {
-                            if pool_deposit.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let (tmp_pool_deposit, tmp_pool_deposit_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("pool_deposit"))?;
-                            pool_deposit = Some(tmp_pool_deposit);
-                            pool_deposit_encoding = tmp_pool_deposit_encoding;
-                            pool_deposit_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        }
-                        (7, key_enc) => {
-                            if maximum_epoch.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
-                                })()
-                                .map_err(|e| e.annotate("maximum_epoch"))?;
-                            maximum_epoch = Some(tmp_maximum_epoch);
-                            maximum_epoch_encoding = tmp_maximum_epoch_encoding;
-                            maximum_epoch_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
+                            unknown_fields.deserialize_known(
+                                raw,
+                                &mut read_len,
+                                orig_deser_order.len(),
+                                4,
+                                key_enc,
+                                |raw, read_len| {
+                                    if max_block_header_size.is_some() {
+                                        return Err(
+                                            DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                        );
+                                    }
+                                    let (
+                                        tmp_max_block_header_size,
+                                        tmp_max_block_header_size_encoding,
+                                    ) = (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("max_block_header_size"))?;
+                                    max_block_header_size = Some(tmp_max_block_header_size);
+                                    max_block_header_size_encoding =
+                                        tmp_max_block_header_size_encoding;
+                                    max_block_header_size_key_encoding = Some(key_enc);
+                                    orig_deser_order.push(4);
+                                    Ok(())
+                                },
+                            )?
                         }
-                        (8, key_enc) => {
-                            if n_opt.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(8)).into());
-                            }
-                            let (tmp_n_opt, tmp_n_opt_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if key_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (tmp_key_deposit, tmp_key_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("key_deposit"))?;
+                                key_deposit = Some(tmp_key_deposit);
+                                key_deposit_encoding = tmp_key_deposit_encoding;
+                                key_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_deposit.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
+                                }
+                                let (tmp_pool_deposit, tmp_pool_deposit_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("pool_deposit"))?;
+                                pool_deposit = Some(tmp_pool_deposit);
+                                pool_deposit_encoding = tmp_pool_deposit_encoding;
+                                pool_deposit_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if maximum_epoch.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_maximum_epoch, tmp_maximum_epoch_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("maximum_epoch"))?;
+                                maximum_epoch = Some(tmp_maximum_epoch);
+                                maximum_epoch_encoding = tmp_maximum_epoch_encoding;
+                                maximum_epoch_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (8, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            8,
+                            key_enc,
+                            |raw, read_len| {
+                                if n_opt.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(8)).into()
+                                    );
+                                }
+                                let (tmp_n_opt, tmp_n_opt_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("n_opt"))?;
+                                n_opt = Some(tmp_n_opt);
+                                n_opt_encoding = tmp_n_opt_encoding;
+                                n_opt_key_encoding = Some(key_enc);
+                                orig_deser_order.push(8);
+                                Ok(())
+                            },
+                        )?,
+                        (9, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            9,
+                            key_enc,
+                            |raw, read_len| {
+                                if pool_pledge_influence.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(9)).into()
+                                    );
+                                }
+                                let tmp_pool_pledge_influence =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        Rational::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("pool_pledge_influence"))?;
+                                pool_pledge_influence = Some(tmp_pool_pledge_influence);
+                                pool_pledge_influence_key_encoding = Some(key_enc);
+                                orig_deser_order.push(9);
+                                Ok(())
+                            },
+                        )?,
+                        (10, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            10,
+                            key_enc,
+                            |raw, read_len| {
+                                if expansion_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(10)).into()
+                                    );
+                                }
+                                let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    UnitInterval::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("n_opt"))?;
-                            n_opt = Some(tmp_n_opt);
-                            n_opt_encoding = tmp_n_opt_encoding;
-                            n_opt_key_encoding = Some(key_enc);
-                            orig_deser_order.push(8);
-                        }
-                        (9, key_enc) => {
-                            if pool_pledge_influence.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(9)).into());
-                            }
-                            let tmp_pool_pledge_influence = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Rational::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("pool_pledge_influence"))?;
-                            pool_pledge_influence = Some(tmp_pool_pledge_influence);
-                            pool_pledge_influence_key_encoding = Some(key_enc);
-                            orig_deser_order.push(9);
-                        }
-                        (10, key_enc) => {
-                            if expansion_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(10)).into());
-                            }
-                            let tmp_expansion_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("expansion_rate"))?;
-                            expansion_rate = Some(tmp_expansion_rate);
-                            expansion_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(10);
-                        }
-                        (11, key_enc) => {
-                            if treasury_growth_rate.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(11)).into());
-                            }
-                            let tmp_treasury_growth_rate = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                UnitInterval::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("treasury_growth_rate"))?;
-                            treasury_growth_rate = Some(tmp_treasury_growth_rate);
-                            treasury_growth_rate_key_encoding = Some(key_enc);
-                            orig_deser_order.push(11);
-                        }
-                        (12, key_enc) => {
-                            if decentralization_constant.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(12)).into());
-                            }
-                            let tmp_decentralization_constant =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("expansion_rate"))?;
+                                expansion_rate = Some(tmp_expansion_rate);
+                                expansion_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(10);
+                                Ok(())
+                            },
+                        )?,
+                        (11, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            11,
+                            key_enc,
+                            |raw, read_len| {
+                                if treasury_growth_rate.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(11)).into()
+                                    );
+                                }
+                                let tmp_treasury_growth_rate =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        UnitInterval::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("treasury_growth_rate"))?;
+                                treasury_growth_rate = Some(tmp_treasury_growth_rate);
+                                treasury_growth_rate_key_encoding = Some(key_enc);
+                                orig_deser_order.push(11);
+                                Ok(())
+                            },
+                        )?,
+                        (12, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            12,
+                            key_enc,
+                            |raw, read_len| {
+                                if decentralization_constant.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(12)).into()
+                                    );
+                                }
+                                let tmp_decentralization_constant =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        UnitInterval::deserialize(raw)
+                                    })()
+                                    .map_err(|e| e.annotate("decentralization_constant"))?;
+                                decentralization_constant = Some(tmp_decentralization_constant);
+                                decentralization_constant_key_encoding = Some(key_enc);
+                                orig_deser_order.push(12);
+                                Ok(())
+                            },
+                        )?,
+                        (13, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            13,
+                            key_enc,
+                            |raw, read_len| {
+                                if extra_entropy.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(13)).into()
+                                    );
+                                }
+                                let tmp_extra_entropy = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    UnitInterval::deserialize(raw)
+                                    Nonce::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("decentralization_constant"))?;
-                            decentralization_constant = Some(tmp_decentralization_constant);
-                            decentralization_constant_key_encoding = Some(key_enc);
-                            orig_deser_order.push(12);
-                        }
-                        (13, key_enc) => {
-                            if extra_entropy.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(13)).into());
-                            }
-                            let tmp_extra_entropy = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                Nonce::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("extra_entropy"))?;
-                            extra_entropy = Some(tmp_extra_entropy);
-                            extra_entropy_key_encoding = Some(key_enc);
-                            orig_deser_order.push(13);
-                        }
-                        (14, key_enc) => {
-                            if protocol_version.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(14)).into());
-                            }
-                            let tmp_protocol_version = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ProtocolVersionStruct::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("protocol_version"))?;
-                            protocol_version = Some(tmp_protocol_version);
-                            protocol_version_key_encoding = Some(key_enc);
-                            orig_deser_order.push(14);
-                        }
-                        (15, key_enc) => {
-                            if min_utxo_value.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(15)).into());
-                            }
-                            let (tmp_min_utxo_value, tmp_min_utxo_value_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                                .map_err(|e| e.annotate("extra_entropy"))?;
+                                extra_entropy = Some(tmp_extra_entropy);
+                                extra_entropy_key_encoding = Some(key_enc);
+                                orig_deser_order.push(13);
+                                Ok(())
+                            },
+                        )?,
+                        (14, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            14,
+                            key_enc,
+                            |raw, read_len| {
+                                if protocol_version.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(14)).into()
+                                    );
+                                }
+                                let tmp_protocol_version = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.unsigned_integer_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .map(|(x, enc)| (x, Some(enc)))
+                                    ProtocolVersionStruct::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("min_utxo_value"))?;
-                            min_utxo_value = Some(tmp_min_utxo_value);
-                            min_utxo_value_encoding = tmp_min_utxo_value_encoding;
-                            min_utxo_value_key_encoding = Some(key_enc);
-                            orig_deser_order.push(15);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                .map_err(|e| e.annotate("protocol_version"))?;
+                                protocol_version = Some(tmp_protocol_version);
+                                protocol_version_key_encoding = Some(key_enc);
+                                orig_deser_order.push(14);
+                                Ok(())
+                            },
+                        )?,
+                        (15, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            15,
+                            key_enc,
+                            |raw, read_len| {
+                                if min_utxo_value.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(15)).into()
+                                    );
+                                }
+                                let (tmp_min_utxo_value, tmp_min_utxo_value_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.unsigned_integer_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .map(|(x, enc)| (x, Some(enc)))
+                                    })()
+                                    .map_err(|e| e.annotate("min_utxo_value"))?;
+                                min_utxo_value = Some(tmp_min_utxo_value);
+                                min_utxo_value_encoding = tmp_min_utxo_value_encoding;
+                                min_utxo_value_key_encoding = Some(key_enc);
+                                orig_deser_order.push(15);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -2722,6 +2929,7 @@ impl Deserialize for ShelleyProtocolParamUpdate {
                     protocol_version_key_encoding,
                     min_utxo_value_key_encoding,
                     min_utxo_value_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -2803,6 +3011,26 @@ impl Serialize for ShelleyTransactionBody {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ShelleyTransactionBody {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -2822,7 +3050,7 @@ impl Serialize for ShelleyTransactionBody {
                     } + match &self.auxiliary_data_hash {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -2848,7 +3076,9 @@ impl Serialize for ShelleyTransactionBody {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6, 7]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     serializer.write_unsigned_integer_sz(
@@ -3085,6 +3315,8 @@ impl Serialize for ShelleyTransactionBody {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -3101,6 +3333,7 @@ impl Deserialize for ShelleyTransactionBody {
         read_len.read_elems(4)?;
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut inputs_encoding = LenEncoding::default();
             let mut inputs_key_encoding = None;
             let mut inputs = None;
@@ -3132,106 +3365,148 @@ impl Deserialize for ShelleyTransactionBody {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if inputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_inputs, tmp_inputs_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    let mut inputs_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let inputs_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (inputs_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        inputs_arr.push(TransactionInput::deserialize(raw)?);
-                                    }
-                                    Ok((inputs_arr, inputs_encoding))
-                                })()
-                                .map_err(|e| e.annotate("inputs"))?;
-                            inputs = Some(tmp_inputs);
-                            inputs_encoding = tmp_inputs_encoding;
-                            inputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if outputs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_outputs, tmp_outputs_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    let mut outputs_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let outputs_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (outputs_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        outputs_arr
-                                            .push(ShelleyTransactionOutput::deserialize(raw)?);
-                                    }
-                                    Ok((outputs_arr, outputs_encoding))
-                                })()
-                                .map_err(|e| e.annotate("outputs"))?;
-                            outputs = Some(tmp_outputs);
-                            outputs_encoding = tmp_outputs_encoding;
-                            outputs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if fee.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_fee, tmp_fee_encoding) = raw
-                                .unsigned_integer_sz()
-                                .map_err(Into::<DeserializeError>::into)
-                                .map(|(x, enc)| (x, Some(enc)))
-                                .map_err(|e: DeserializeError| e.annotate("fee"))?;
-                            fee = Some(tmp_fee);
-                            fee_encoding = tmp_fee_encoding;
-                            fee_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (3, key_enc) => {
-                            if ttl.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(3)).into());
-                            }
-                            let (tmp_ttl, tmp_ttl_encoding) = raw
-                                .unsigned_integer_sz()
-                                .map_err(Into::<DeserializeError>::into)
-                                .map(|(x, enc)| (x, Some(enc)))
-                                .map_err(|e: DeserializeError| e.annotate("ttl"))?;
-                            ttl = Some(tmp_ttl);
-                            ttl_encoding = tmp_ttl_encoding;
-                            ttl_key_encoding = Some(key_enc);
-                            orig_deser_order.push(3);
-                        }
-                        (4, key_enc) => {
-                            if certs.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(4)).into());
-                            }
-                            let (tmp_certs, tmp_certs_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, _read_len| {
+                                if inputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_inputs, tmp_inputs_encoding) =
+                                    deserialize_array(raw, TransactionInput::deserialize)
+                                        .map_err(|e| e.annotate("inputs"))?;
+                                inputs = Some(tmp_inputs);
+                                inputs_encoding = tmp_inputs_encoding;
+                                inputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, _read_len| {
+                                if outputs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_outputs, tmp_outputs_encoding) =
+                                    deserialize_array(raw, ShelleyTransactionOutput::deserialize)
+                                        .map_err(|e| e.annotate("outputs"))?;
+                                outputs = Some(tmp_outputs);
+                                outputs_encoding = tmp_outputs_encoding;
+                                outputs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, _read_len| {
+                                if fee.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_fee, tmp_fee_encoding) = raw
+                                    .unsigned_integer_sz()
+                                    .map_err(Into::<DeserializeError>::into)
+                                    .map(|(x, enc)| (x, Some(enc)))
+                                    .map_err(|e: DeserializeError| e.annotate("fee"))?;
+                                fee = Some(tmp_fee);
+                                fee_encoding = tmp_fee_encoding;
+                                fee_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (3, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            3,
+                            key_enc,
+                            |raw, _read_len| {
+                                if ttl.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(3)).into()
+                                    );
+                                }
+                                let (tmp_ttl, tmp_ttl_encoding) = raw
+                                    .unsigned_integer_sz()
+                                    .map_err(Into::<DeserializeError>::into)
+                                    .map(|(x, enc)| (x, Some(enc)))
+                                    .map_err(|e: DeserializeError| e.annotate("ttl"))?;
+                                ttl = Some(tmp_ttl);
+                                ttl_encoding = tmp_ttl_encoding;
+                                ttl_key_encoding = Some(key_enc);
+                                orig_deser_order.push(3);
+                                Ok(())
+                            },
+                        )?,
+                        (4, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            4,
+                            key_enc,
+                            |raw, read_len| {
+                                if certs.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(4)).into()
+                                    );
+                                }
+                                let (tmp_certs, tmp_certs_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, ShelleyCertificate::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("certs"))?;
+                                certs = Some(tmp_certs);
+                                certs_encoding = tmp_certs_encoding;
+                                certs_key_encoding = Some(key_enc);
+                                orig_deser_order.push(4);
+                                Ok(())
+                            },
+                        )?,
+                        (5, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            5,
+                            key_enc,
+                            |raw, read_len| {
+                                if withdrawals.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(5)).into()
+                                    );
+                                }
+                                let (
+                                    tmp_withdrawals,
+                                    tmp_withdrawals_encoding,
+                                    tmp_withdrawals_value_encodings,
+                                ) = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    let mut certs_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let certs_encoding = len.into();
-                                    while match len {
+                                    let mut withdrawals_table = OrderedHashMap::new();
+                                    let withdrawals_len = raw.map_sz()?;
+                                    let withdrawals_encoding = withdrawals_len.into();
+                                    let mut withdrawals_value_encodings = BTreeMap::new();
+                                    while match withdrawals_len {
                                         cbor_event::LenSz::Len(n, _) => {
-                                            (certs_arr.len() as u64) < n
+                                            (withdrawals_table.len() as u64) < n
                                         }
                                         cbor_event::LenSz::Indefinite => true,
                                     } {
@@ -3239,115 +3514,119 @@ impl Deserialize for ShelleyTransactionBody {
                                             assert_eq!(raw.special()?, cbor_event::Special::Break);
                                             break;
                                         }
-                                        certs_arr.push(ShelleyCertificate::deserialize(raw)?);
+                                        let withdrawals_key = RewardAccount::deserialize(raw)?;
+                                        let (withdrawals_value, withdrawals_value_encoding) = raw
+                                            .unsigned_integer_sz()
+                                            .map(|(x, enc)| (x, Some(enc)))?;
+                                        if withdrawals_table
+                                            .insert(withdrawals_key.clone(), withdrawals_value)
+                                            .is_some()
+                                        {
+                                            return Err(DeserializeFailure::DuplicateKey(
+                                                Key::Str(String::from(
+                                                    "some complicated/unsupported type",
+                                                )),
+                                            )
+                                            .into());
+                                        }
+                                        withdrawals_value_encodings
+                                            .insert(withdrawals_key, withdrawals_value_encoding);
                                     }
-                                    Ok((certs_arr, certs_encoding))
+                                    Ok((
+                                        withdrawals_table,
+                                        withdrawals_encoding,
+                                        withdrawals_value_encodings,
+                                    ))
                                 })()
-                                .map_err(|e| e.annotate("certs"))?;
-                            certs = Some(tmp_certs);
-                            certs_encoding = tmp_certs_encoding;
-                            certs_key_encoding = Some(key_enc);
-                            orig_deser_order.push(4);
-                        }
-                        (5, key_enc) => {
-                            if withdrawals.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(5)).into());
-                            }
-                            let (
-                                tmp_withdrawals,
-                                tmp_withdrawals_encoding,
-                                tmp_withdrawals_value_encodings,
-                            ) = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                let mut withdrawals_table = OrderedHashMap::new();
-                                let withdrawals_len = raw.map_sz()?;
-                                let withdrawals_encoding = withdrawals_len.into();
-                                let mut withdrawals_value_encodings = BTreeMap::new();
-                                while match withdrawals_len {
-                                    cbor_event::LenSz::Len(n, _) => {
-                                        (withdrawals_table.len() as u64) < n
-                                    }
-                                    cbor_event::LenSz::Indefinite => true,
-                                } {
-                                    if raw.cbor_type()? == cbor_event::Type::Special {
-                                        assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                        break;
-                                    }
-                                    let withdrawals_key = RewardAccount::deserialize(raw)?;
-                                    let (withdrawals_value, withdrawals_value_encoding) =
-                                        raw.unsigned_integer_sz().map(|(x, enc)| (x, Some(enc)))?;
-                                    if withdrawals_table
-                                        .insert(withdrawals_key.clone(), withdrawals_value)
-                                        .is_some()
-                                    {
-                                        return Err(DeserializeFailure::DuplicateKey(Key::Str(
-                                            String::from("some complicated/unsupported type"),
-                                        ))
-                                        .into());
-                                    }
-                                    withdrawals_value_encodings
-                                        .insert(withdrawals_key, withdrawals_value_encoding);
+                                .map_err(|e| e.annotate("withdrawals"))?;
+                                withdrawals = Some(tmp_withdrawals);
+                                withdrawals_encoding = tmp_withdrawals_encoding;
+                                withdrawals_value_encodings = tmp_withdrawals_value_encodings;
+                                withdrawals_key_encoding = Some(key_enc);
+                                orig_deser_order.push(5);
+                                Ok(())
+                            },
+                        )?,
+                        (6, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            6,
+                            key_enc,
+                            |raw, read_len| {
+                                if update.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(6)).into()
+                                    );
                                 }
-                                Ok((
-                                    withdrawals_table,
-                                    withdrawals_encoding,
-                                    withdrawals_value_encodings,
-                                ))
-                            })()
-                            .map_err(|e| e.annotate("withdrawals"))?;
-                            withdrawals = Some(tmp_withdrawals);
-                            withdrawals_encoding = tmp_withdrawals_encoding;
-                            withdrawals_value_encodings = tmp_withdrawals_value_encodings;
-                            withdrawals_key_encoding = Some(key_enc);
-                            orig_deser_order.push(5);
-                        }
-                        (6, key_enc) => {
-                            if update.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(6)).into());
-                            }
-                            let tmp_update = (|| -> Result<_, DeserializeError> {
-                                read_len.read_elems(1)?;
-                                ShelleyUpdate::deserialize(raw)
-                            })()
-                            .map_err(|e| e.annotate("update"))?;
-                            update = Some(tmp_update);
-                            update_key_encoding = Some(key_enc);
-                            orig_deser_order.push(6);
-                        }
-                        (7, key_enc) => {
-                            if auxiliary_data_hash.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(7)).into());
-                            }
-                            let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) =
-                                (|| -> Result<_, DeserializeError> {
+                                let tmp_update = (|| -> Result<_, DeserializeError> {
                                     read_len.read_elems(1)?;
-                                    raw.bytes_sz()
-                                        .map_err(Into::<DeserializeError>::into)
-                                        .and_then(|(bytes, enc)| {
-                                            AuxiliaryDataHash::from_raw_bytes(&bytes)
-                                                .map(|bytes| (bytes, StringEncoding::from(enc)))
-                                                .map_err(|e| {
-                                                    DeserializeFailure::InvalidStructure(Box::new(
-                                                        e,
-                                                    ))
-                                                    .into()
-                                                })
-                                        })
+                                    ShelleyUpdate::deserialize(raw)
                                 })()
-                                .map_err(|e| e.annotate("auxiliary_data_hash"))?;
-                            auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
-                            auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
-                            auxiliary_data_hash_key_encoding = Some(key_enc);
-                            orig_deser_order.push(7);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                                .map_err(|e| e.annotate("update"))?;
+                                update = Some(tmp_update);
+                                update_key_encoding = Some(key_enc);
+                                orig_deser_order.push(6);
+                                Ok(())
+                            },
+                        )?,
+                        (7, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            7,
+                            key_enc,
+                            |raw, read_len| {
+                                if auxiliary_data_hash.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(7)).into()
+                                    );
+                                }
+                                let (tmp_auxiliary_data_hash, tmp_auxiliary_data_hash_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        raw.bytes_sz()
+                                            .map_err(Into::<DeserializeError>::into)
+                                            .and_then(|(bytes, enc)| {
+                                                AuxiliaryDataHash::from_raw_bytes(&bytes)
+                                                    .map(|bytes| (bytes, StringEncoding::from(enc)))
+                                                    .map_err(|e| {
+                                                        DeserializeFailure::InvalidStructure(
+                                                            Box::new(e),
+                                                        )
+                                                        .into()
+                                                    })
+                                            })
+                                    })()
+                                    .map_err(|e| e.annotate("auxiliary_data_hash"))?;
+                                auxiliary_data_hash = Some(tmp_auxiliary_data_hash);
+                                auxiliary_data_hash_encoding = tmp_auxiliary_data_hash_encoding;
+                                auxiliary_data_hash_key_encoding = Some(key_enc);
+                                orig_deser_order.push(7);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -3409,6 +3688,7 @@ impl Deserialize for ShelleyTransactionBody {
                     update_key_encoding,
                     auxiliary_data_hash_key_encoding,
                     auxiliary_data_hash_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -3489,6 +3769,26 @@ impl Serialize for ShelleyTransactionWitnessSet {
         &self,
         serializer: &'se mut Serializer<W>,
         force_canonical: bool,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        let patched = self
+            .unknown_fields()
+            .patched_cbor(force_canonical, |serializer| {
+                self.serialize_unpatched(serializer, force_canonical)
+                    .map(|_| ())
+            });
+        match patched {
+            Some(bytes) => serializer.write_raw_bytes(&bytes),
+            None => self.serialize_unpatched(serializer, force_canonical),
+        }
+    }
+}
+
+impl ShelleyTransactionWitnessSet {
+    /// Serialization without the unknown items (see `UnknownFields::patched_cbor()`)
+    fn serialize_unpatched<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+        force_canonical: bool,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
         serializer.write_map_sz(
             self.encodings
@@ -3505,7 +3805,7 @@ impl Serialize for ShelleyTransactionWitnessSet {
                     } + match &self.bootstrap_witnesses {
                         Some(_) => 1,
                         None => 0,
-                    },
+                    } + self.unknown_fields().len() as u64,
                     force_canonical,
                 ),
         )?;
@@ -3528,7 +3828,9 @@ impl Serialize for ShelleyTransactionWitnessSet {
             })
             .map(|encs| encs.orig_deser_order.clone())
             .unwrap_or_else(|| vec![0, 1, 2]);
-        for field_index in deser_order {
+        for (i, field_index) in deser_order.iter().enumerate() {
+            self.unknown_fields()
+                .serialize_before(i, serializer, force_canonical)?;
             match field_index {
                 0 => {
                     if let Some(field) = &self.vkeywitnesses {
@@ -3623,6 +3925,8 @@ impl Serialize for ShelleyTransactionWitnessSet {
                 _ => unreachable!(),
             };
         }
+        self.unknown_fields()
+            .serialize_after(deser_order.len(), serializer, force_canonical)?;
         self.encodings
             .as_ref()
             .map(|encs| encs.len_encoding)
@@ -3638,6 +3942,7 @@ impl Deserialize for ShelleyTransactionWitnessSet {
         let mut read_len = CBORReadLen::new(len);
         (|| -> Result<_, DeserializeError> {
             let mut orig_deser_order = Vec::new();
+            let mut unknown_fields = UnknownFields::default();
             let mut vkeywitnesses_encoding = LenEncoding::default();
             let mut vkeywitnesses_key_encoding = None;
             let mut vkeywitnesses = None;
@@ -3654,105 +3959,102 @@ impl Deserialize for ShelleyTransactionWitnessSet {
             } {
                 match raw.cbor_type()? {
                     cbor_event::Type::UnsignedInteger => match raw.unsigned_integer_sz()? {
-                        (0, key_enc) => {
-                            if vkeywitnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(0)).into());
-                            }
-                            let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut vkeywitnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let vkeywitnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (vkeywitnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        vkeywitnesses_arr.push(Vkeywitness::deserialize(raw)?);
-                                    }
-                                    Ok((vkeywitnesses_arr, vkeywitnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("vkeywitnesses"))?;
-                            vkeywitnesses = Some(tmp_vkeywitnesses);
-                            vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
-                            vkeywitnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(0);
-                        }
-                        (1, key_enc) => {
-                            if native_scripts.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(1)).into());
-                            }
-                            let (tmp_native_scripts, tmp_native_scripts_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut native_scripts_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let native_scripts_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (native_scripts_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        native_scripts_arr.push(MultisigScript::deserialize(raw)?);
-                                    }
-                                    Ok((native_scripts_arr, native_scripts_encoding))
-                                })()
-                                .map_err(|e| e.annotate("native_scripts"))?;
-                            native_scripts = Some(tmp_native_scripts);
-                            native_scripts_encoding = tmp_native_scripts_encoding;
-                            native_scripts_key_encoding = Some(key_enc);
-                            orig_deser_order.push(1);
-                        }
-                        (2, key_enc) => {
-                            if bootstrap_witnesses.is_some() {
-                                return Err(DeserializeFailure::DuplicateKey(Key::Uint(2)).into());
-                            }
-                            let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
-                                (|| -> Result<_, DeserializeError> {
-                                    read_len.read_elems(1)?;
-                                    let mut bootstrap_witnesses_arr = Vec::new();
-                                    let len = raw.array_sz()?;
-                                    let bootstrap_witnesses_encoding = len.into();
-                                    while match len {
-                                        cbor_event::LenSz::Len(n, _) => {
-                                            (bootstrap_witnesses_arr.len() as u64) < n
-                                        }
-                                        cbor_event::LenSz::Indefinite => true,
-                                    } {
-                                        if raw.cbor_type()? == cbor_event::Type::Special {
-                                            assert_eq!(raw.special()?, cbor_event::Special::Break);
-                                            break;
-                                        }
-                                        bootstrap_witnesses_arr
-                                            .push(BootstrapWitness::deserialize(raw)?);
-                                    }
-                                    Ok((bootstrap_witnesses_arr, bootstrap_witnesses_encoding))
-                                })()
-                                .map_err(|e| e.annotate("bootstrap_witnesses"))?;
-                            bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
-                            bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
-                            bootstrap_witnesses_key_encoding = Some(key_enc);
-                            orig_deser_order.push(2);
-                        }
-                        (unknown_key, _enc) => {
-                            return Err(
-                                DeserializeFailure::UnknownKey(Key::Uint(unknown_key)).into()
-                            )
-                        }
+                        (0, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            0,
+                            key_enc,
+                            |raw, read_len| {
+                                if vkeywitnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(0)).into()
+                                    );
+                                }
+                                let (tmp_vkeywitnesses, tmp_vkeywitnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, Vkeywitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("vkeywitnesses"))?;
+                                vkeywitnesses = Some(tmp_vkeywitnesses);
+                                vkeywitnesses_encoding = tmp_vkeywitnesses_encoding;
+                                vkeywitnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(0);
+                                Ok(())
+                            },
+                        )?,
+                        (1, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            1,
+                            key_enc,
+                            |raw, read_len| {
+                                if native_scripts.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(1)).into()
+                                    );
+                                }
+                                let (tmp_native_scripts, tmp_native_scripts_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, MultisigScript::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("native_scripts"))?;
+                                native_scripts = Some(tmp_native_scripts);
+                                native_scripts_encoding = tmp_native_scripts_encoding;
+                                native_scripts_key_encoding = Some(key_enc);
+                                orig_deser_order.push(1);
+                                Ok(())
+                            },
+                        )?,
+                        (2, key_enc) => unknown_fields.deserialize_known(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            2,
+                            key_enc,
+                            |raw, read_len| {
+                                if bootstrap_witnesses.is_some() {
+                                    return Err(
+                                        DeserializeFailure::DuplicateKey(Key::Uint(2)).into()
+                                    );
+                                }
+                                let (tmp_bootstrap_witnesses, tmp_bootstrap_witnesses_encoding) =
+                                    (|| -> Result<_, DeserializeError> {
+                                        read_len.read_elems(1)?;
+                                        deserialize_array(raw, BootstrapWitness::deserialize)
+                                    })()
+                                    .map_err(|e| e.annotate("bootstrap_witnesses"))?;
+                                bootstrap_witnesses = Some(tmp_bootstrap_witnesses);
+                                bootstrap_witnesses_encoding = tmp_bootstrap_witnesses_encoding;
+                                bootstrap_witnesses_key_encoding = Some(key_enc);
+                                orig_deser_order.push(2);
+                                Ok(())
+                            },
+                        )?,
+                        (unknown_key, key_enc) => unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Int(Int::Uint {
+                                value: unknown_key,
+                                encoding: Some(key_enc),
+                            }),
+                        )?,
                     },
                     cbor_event::Type::Text => {
-                        return Err(DeserializeFailure::UnknownKey(Key::Str(raw.text()?)).into())
+                        let (text, text_encoding) = raw.text_sz()?;
+                        unknown_fields.deserialize_unknown(
+                            raw,
+                            &mut read_len,
+                            orig_deser_order.len(),
+                            CborValue::Text {
+                                text,
+                                text_encoding: text_encoding.into(),
+                            },
+                        )?
                     }
                     cbor_event::Type::Special => match len {
                         cbor_event::LenSz::Len(_, _) => {
@@ -3783,6 +4085,7 @@ impl Deserialize for ShelleyTransactionWitnessSet {
                     native_scripts_encoding,
                     bootstrap_witnesses_key_encoding,
                     bootstrap_witnesses_encoding,
+                    unknown_fields: unknown_fields.into_boxed(),
                 }),
             })
         })()
@@ -1,10 +1,166 @@
-use cml_chain::transaction::{NativeScript, TransactionWitnessSet};
+use cml_chain::auxdata::Metadata;
+use cml_chain::block::{OperationalCert, ProtocolVersion};
+use cml_chain::certs::{
+    Credential, PoolRegistration, PoolRetirement, StakeDelegation, StakeDeregistration,
+    StakeRegistration,
+};
+use cml_chain::crypto::{BootstrapWitness, Nonce, VRFCert, Vkeywitness};
+use cml_chain::transaction::{NativeScript, TransactionInput, TransactionWitnessSet};
+use cml_chain::utils::withdrawals_field_names;
+use cml_chain::{Rational, UnitInterval};
 
-use super::{MultisigScript, ShelleyTransactionBody, ShelleyTransactionWitnessSet};
+use super::{
+    GenesisKeyDelegation, MultisigAll, MultisigAny, MultisigNOfK, MultisigPubkey, MultisigScript,
+    ProtocolVersionStruct, ShelleyBlock, ShelleyCertificate, ShelleyHeader, ShelleyHeaderBody,
+    ShelleyMoveInstantaneousReward, ShelleyMoveInstantaneousRewardsCert,
+    ShelleyProtocolParamUpdate, ShelleyTransaction, ShelleyTransactionBody,
+    ShelleyTransactionOutput, ShelleyTransactionWitnessSet, ShelleyUpdate,
+};
 
+use cml_core::diagnostic::{CBORFieldNames, FieldNames};
 use cml_core::serialization::Serialize;
 use cml_crypto::{blake2b256, TransactionHash};
 
+cml_core::impl_unknown_fields!(
+    ShelleyProtocolParamUpdate,
+    ShelleyTransactionBody,
+    ShelleyTransactionWitnessSet
+);
+
+cml_core::impl_cbor_field_names!(ShelleyBlock [
+    header: ShelleyHeader::cbor_field_names(),
+    transaction_bodies: FieldNames::elements(ShelleyTransactionBody::cbor_field_names()),
+    transaction_witness_sets: FieldNames::elements(ShelleyTransactionWitnessSet::cbor_field_names()),
+    transaction_metadata_set: FieldNames::entries(
+        ("transaction_index", FieldNames::None),
+        ("", Metadata::cbor_field_names()),
+    ),
+]);
+cml_core::impl_cbor_field_names!(ShelleyHeader [body: ShelleyHeaderBody::cbor_field_names(), signature]);
+cml_core::impl_cbor_field_names!(ShelleyHeaderBody [
+    block_number,
+    slot,
+    prev_hash,
+    issuer_vkey,
+    v_r_f_vkey,
+    nonce_vrf: VRFCert::cbor_field_names(),
+    leader_vrf: VRFCert::cbor_field_names(),
+    block_body_size,
+    block_body_hash,
+    operational_cert: OperationalCert::cbor_field_names().embedded(),
+    protocol_version: ProtocolVersion::cbor_field_names().embedded(),
+]);
+
+cml_core::impl_cbor_field_names!(ShelleyTransaction [
+    body: ShelleyTransactionBody::cbor_field_names(),
+    witness_set: ShelleyTransactionWitnessSet::cbor_field_names(),
+    metadata: Metadata::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(ShelleyTransactionBody {
+    0 => inputs: FieldNames::elements(TransactionInput::cbor_field_names()),
+    1 => outputs: FieldNames::elements(ShelleyTransactionOutput::cbor_field_names()),
+    2 => fee,
+    3 => ttl,
+    4 => certs: FieldNames::elements(ShelleyCertificate::cbor_field_names()),
+    5 => withdrawals: withdrawals_field_names(),
+    6 => update: ShelleyUpdate::cbor_field_names(),
+    7 => auxiliary_data_hash,
+});
+cml_core::impl_cbor_field_names!(ShelleyTransactionOutput [address, amount]);
+cml_core::impl_cbor_field_names!(ShelleyTransactionWitnessSet {
+    0 => vkeywitnesses: FieldNames::elements(Vkeywitness::cbor_field_names()),
+    1 => native_scripts: FieldNames::elements(MultisigScript::cbor_field_names()),
+    2 => bootstrap_witnesses: FieldNames::elements(BootstrapWitness::cbor_field_names()),
+});
+
+impl CBORFieldNames for ShelleyCertificate {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, StakeRegistration::cbor_field_names()),
+            (1, StakeDeregistration::cbor_field_names()),
+            (2, StakeDelegation::cbor_field_names()),
+            (3, PoolRegistration::cbor_field_names()),
+            (4, PoolRetirement::cbor_field_names()),
+            (5, GenesisKeyDelegation::cbor_field_names()),
+            (6, ShelleyMoveInstantaneousRewardsCert::cbor_field_names()),
+        ])
+    }
+}
+
+cml_core::impl_cbor_field_names!(GenesisKeyDelegation [
+    5,
+    genesis_hash,
+    genesis_delegate_hash,
+    v_r_f_key_hash,
+]);
+cml_core::impl_cbor_field_names!(ShelleyMoveInstantaneousRewardsCert [
+    6,
+    shelley_move_instantaneous_reward: ShelleyMoveInstantaneousReward::cbor_field_names(),
+]);
+cml_core::impl_cbor_field_names!(ShelleyMoveInstantaneousReward [
+    pot,
+    to_stake_credentials: FieldNames::entries(
+        ("stake_credential", Credential::cbor_field_names()),
+        ("coin", FieldNames::None),
+    ),
+]);
+
+impl CBORFieldNames for MultisigScript {
+    fn cbor_field_names() -> FieldNames {
+        FieldNames::Variants(vec![
+            (0, MultisigPubkey::cbor_field_names()),
+            (1, MultisigAll::cbor_field_names()),
+            (2, MultisigAny::cbor_field_names()),
+            (3, MultisigNOfK::cbor_field_names()),
+        ])
+    }
+}
+
+// multisig scripts nest so their names are only created for the scripts present
+cml_core::impl_cbor_field_names!(MultisigPubkey [0, ed25519_key_hash]);
+cml_core::impl_cbor_field_names!(MultisigAll [
+    1,
+    multisig_scripts: FieldNames::elements(FieldNames::Lazy(MultisigScript::cbor_field_names)),
+]);
+cml_core::impl_cbor_field_names!(MultisigAny [
+    2,
+    multisig_scripts: FieldNames::elements(FieldNames::Lazy(MultisigScript::cbor_field_names)),
+]);
+cml_core::impl_cbor_field_names!(MultisigNOfK [
+    3,
+    n,
+    multisig_scripts: FieldNames::elements(FieldNames::Lazy(MultisigScript::cbor_field_names)),
+]);
+
+cml_core::impl_cbor_field_names!(ShelleyUpdate [
+    shelley_proposed_protocol_parameter_updates: FieldNames::entries(
+        ("genesis_hash", FieldNames::None),
+        ("", ShelleyProtocolParamUpdate::cbor_field_names()),
+    ),
+    epoch,
+]);
+cml_core::impl_cbor_field_names!(ShelleyProtocolParamUpdate {
+    0 => minfee_a,
+    1 => minfee_b,
+    2 => max_block_body_size,
+    3 => max_transaction_size,
+    4 => max_block_header_size,
+    5 => key_deposit,
+    6 => pool_deposit,
+    7 => maximum_epoch,
+    8 => n_opt,
+    9 => pool_pledge_influence: Rational::cbor_field_names(),
+    10 => expansion_rate: UnitInterval::cbor_field_names(),
+    11 => treasury_growth_rate: UnitInterval::cbor_field_names(),
+    12 => decentralization_constant: UnitInterval::cbor_field_names(),
+    13 => extra_entropy: Nonce::cbor_field_names(),
+    14 => protocol_version: ProtocolVersionStruct::cbor_field_names(),
+    15 => min_utxo_value,
+});
+cml_core::impl_cbor_field_names!(ProtocolVersionStruct [
+    protocol_version: ProtocolVersion::cbor_field_names().embedded(),
+]);
+
 impl ShelleyTransactionBody {
     pub fn hash(&self) -> TransactionHash {
         blake2b256(&self.to_cbor_bytes()).into()
@@ -0,0 +1,14 @@
+use crate::MultiEraBlock;
+use cml_chain_wasm::json::views::ChainJsonSchema;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// Converts a MultiEraBlock to JSON according to ChainJsonSchema.
+/// Only ChainJsonSchema.OgmiosV6 is supported as cardano-cli has no view for blocks.
+#[wasm_bindgen]
+pub fn decode_multi_era_block_to_json_str(
+    block: &MultiEraBlock,
+    schema: ChainJsonSchema,
+) -> Result<String, JsError> {
+    cml_multi_era::json::decode_multi_era_block_to_json_str(block.as_ref(), schema)
+        .map_err(Into::into)
+}
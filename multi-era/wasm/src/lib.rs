@@ -7,6 +7,7 @@ pub mod allegra;
 pub mod alonzo;
 pub mod babbage;
 pub mod byron;
+pub mod json;
 pub mod mary;
 pub mod shelley;
 pub mod utils;
@@ -22,27 +23,31 @@ pub use cml_cip36_wasm::CIP36DeregistrationCbor;
 
 use crate::{
     allegra::{
-        AllegraAuxiliaryData, AllegraBlock, AllegraCertificate, AllegraTransactionBody,
-        AllegraTransactionWitnessSet,
+        AllegraAuxiliaryData, AllegraBlock, AllegraCertificate, AllegraTransaction,
+        AllegraTransactionBody, AllegraTransactionWitnessSet,
     },
     alonzo::{
-        AlonzoAuxiliaryData, AlonzoBlock, AlonzoTransactionBody, AlonzoTransactionWitnessSet,
+        AlonzoAuxiliaryData, AlonzoBlock, AlonzoTransaction, AlonzoTransactionBody,
+        AlonzoTransactionWitnessSet,
     },
     babbage::{
-        BabbageAuxiliaryData, BabbageBlock, BabbageTransactionBody, BabbageTransactionOutput,
-        BabbageTransactionWitnessSet,
+        BabbageAuxiliaryData, BabbageBlock, BabbageTransaction, BabbageTransactionBody,
+        BabbageTransactionOutput, BabbageTransactionWitnessSet,
     },
-    byron::{block::ByronBlock, transaction::ByronTx},
-    mary::{MaryBlock, MaryTransactionBody, MaryTransactionOutput},
+    byron::{
+        block::{ByronBlock, TxAux},
+        transaction::ByronTx,
+    },
+    mary::{MaryBlock, MaryTransaction, MaryTransactionBody, MaryTransactionOutput},
     shelley::{
-        MultisigScript, ShelleyBlock, ShelleyCertificate, ShelleyTransactionBody,
-        ShelleyTransactionOutput, ShelleyTransactionWitnessSet,
+        MultisigScript, ShelleyBlock, ShelleyCertificate, ShelleyTransaction,
+        ShelleyTransactionBody, ShelleyTransactionOutput, ShelleyTransactionWitnessSet,
     },
 };
 use cml_chain_wasm::{
     block::Block,
     certs::StakeCredential,
-    transaction::{AlonzoFormatTxOut, TransactionBody},
+    transaction::{AlonzoFormatTxOut, Transaction, TransactionBody},
     Coin, StakeCredentialList, TransactionIndex,
 };
 use cml_core_wasm::{
@@ -313,6 +318,131 @@ pub enum MultiEraBlockKind {
     Conway,
 }
 
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct MultiEraTransaction(cml_multi_era::MultiEraTransaction);
+
+impl_wasm_cbor_json_api!(MultiEraTransaction);
+
+impl_wasm_conversions!(cml_multi_era::MultiEraTransaction, MultiEraTransaction);
+
+#[wasm_bindgen]
+impl MultiEraTransaction {
+    pub fn new_byron(byron: &TxAux) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_byron(
+            byron.clone().into(),
+        ))
+    }
+
+    pub fn new_shelley(shelley: &ShelleyTransaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_shelley(
+            shelley.clone().into(),
+        ))
+    }
+
+    pub fn new_allegra(allegra: &AllegraTransaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_allegra(
+            allegra.clone().into(),
+        ))
+    }
+
+    pub fn new_mary(mary: &MaryTransaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_mary(
+            mary.clone().into(),
+        ))
+    }
+
+    pub fn new_alonzo(alonzo: &AlonzoTransaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_alonzo(
+            alonzo.clone().into(),
+        ))
+    }
+
+    pub fn new_babbage(babbage: &BabbageTransaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_babbage(
+            babbage.clone().into(),
+        ))
+    }
+
+    pub fn new_conway(conway: &Transaction) -> Self {
+        Self(cml_multi_era::MultiEraTransaction::new_conway(
+            conway.clone().into(),
+        ))
+    }
+
+    pub fn kind(&self) -> MultiEraTransactionKind {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Byron(_) => MultiEraTransactionKind::Byron,
+            cml_multi_era::MultiEraTransaction::Shelley(_) => MultiEraTransactionKind::Shelley,
+            cml_multi_era::MultiEraTransaction::Allegra(_) => MultiEraTransactionKind::Allegra,
+            cml_multi_era::MultiEraTransaction::Mary(_) => MultiEraTransactionKind::Mary,
+            cml_multi_era::MultiEraTransaction::Alonzo(_) => MultiEraTransactionKind::Alonzo,
+            cml_multi_era::MultiEraTransaction::Babbage(_) => MultiEraTransactionKind::Babbage,
+            cml_multi_era::MultiEraTransaction::Conway(_) => MultiEraTransactionKind::Conway,
+        }
+    }
+
+    pub fn as_byron(&self) -> Option<TxAux> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Byron(byron) => Some(byron.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_shelley(&self) -> Option<ShelleyTransaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Shelley(shelley) => Some(shelley.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_allegra(&self) -> Option<AllegraTransaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Allegra(allegra) => Some(allegra.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_mary(&self) -> Option<MaryTransaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Mary(mary) => Some(mary.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_alonzo(&self) -> Option<AlonzoTransaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Alonzo(alonzo) => Some(alonzo.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_babbage(&self) -> Option<BabbageTransaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Babbage(babbage) => Some(babbage.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn as_conway(&self) -> Option<Transaction> {
+        match &self.0 {
+            cml_multi_era::MultiEraTransaction::Conway(conway) => Some(conway.clone().into()),
+            _ => None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub enum MultiEraTransactionKind {
+    Byron,
+    Shelley,
+    Allegra,
+    Mary,
+    Alonzo,
+    Babbage,
+    Conway,
+}
+
 #[derive(Clone, Debug)]
 #[wasm_bindgen]
 pub struct MultiEraTransactionBody(cml_multi_era::MultiEraTransactionBody);
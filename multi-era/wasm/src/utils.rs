@@ -2,6 +2,7 @@ use cml_chain::{assets::PositiveCoin, Coin};
 use cml_chain_wasm::{
     address::Address,
     assets::{Mint, Value},
+    auxdata::AuxiliaryData,
     block::{OperationalCert, ProtocolVersion},
     certs::{
         AuthCommitteeHotCert, PoolRegistration, PoolRetirement, RegCert, RegDrepCert,
@@ -12,7 +13,7 @@ use cml_chain_wasm::{
     crypto::{GenesisHash, Nonce, VRFCert, Vkey},
     governance::VotingProcedures,
     plutus::{CostModels, ExUnitPrices, ExUnits},
-    transaction::RequiredSigners,
+    transaction::{RequiredSigners, TransactionBody, TransactionWitnessSet},
     DRepVotingThresholds, MapTransactionIndexToAuxiliaryData, NetworkId, PoolVotingThresholds,
     ProposalProcedureList, Rational, TransactionInputList, TransactionWitnessSetList, UnitInterval,
     Withdrawals,
@@ -26,7 +27,8 @@ use wasm_bindgen::{prelude::wasm_bindgen, JsError, JsValue};
 
 use crate::{
     allegra::MoveInstantaneousRewardsCert, shelley::GenesisKeyDelegation,
-    shelley::ProtocolVersionStruct, GenesisHashList, MultiEraBlock, MultiEraTransactionBody,
+    shelley::ProtocolVersionStruct, GenesisHashList, MultiEraBlock, MultiEraTransaction,
+    MultiEraTransactionBody,
 };
 
 #[wasm_bindgen]
@@ -571,8 +573,59 @@ impl MultiEraProtocolParamUpdate {
     }
 }
 
+#[wasm_bindgen]
+impl MultiEraTransaction {
+    /**
+     * Parses a transaction given the network format with explicit era tag
+     *
+     * The node-to-client mini-protocols (e.g. local tx monitor/submission) send transactions
+     * as `[uint, <transaction>]` where the tag is the hard fork era index
+     * (0 = Byron, 1 = Shelley, ..., 6 = Conway) and the transaction is usually wrapped
+     * in CBOR-in-CBOR (tag 24) bytes. Both wrapped and unwrapped transactions are accepted.
+     */
+    pub fn from_explicit_network_cbor_bytes(bytes: &[u8]) -> Result<MultiEraTransaction, JsError> {
+        cml_multi_era::MultiEraTransaction::from_explicit_network_cbor_bytes(bytes)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    pub fn body(&self) -> MultiEraTransactionBody {
+        self.0.body().into()
+    }
+
+    /// Witnesses converted to the current era's format.
+    /// Byron witnesses have no equivalent and will return None.
+    pub fn witness_set(&self) -> Option<TransactionWitnessSet> {
+        self.0.witness_set().map(Into::into)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    pub fn auxiliary_data(&self) -> Option<AuxiliaryData> {
+        self.0.auxiliary_data().map(Into::into)
+    }
+
+    pub fn hash(&self) -> TransactionHash {
+        self.0.hash().into()
+    }
+}
+
 #[wasm_bindgen]
 impl MultiEraTransactionBody {
+    /**
+     * Converts this body into the Conway TransactionBody.
+     *
+     * Fails if there are any fields without a Conway equivalent
+     * (protocol param updates, genesis key delegation and MIR certs) or if this is a Byron body.
+     * CBOR encoding details are not carried over from older eras so hashes must be
+     * taken from the original body, not the converted one.
+     */
+    pub fn to_conway(&self) -> Result<TransactionBody, JsError> {
+        self.0.to_conway().map(Into::into).map_err(Into::into)
+    }
+
     pub fn inputs(&self) -> MultiEraTransactionInputList {
         self.0.inputs().into()
     }
@@ -14,16 +14,25 @@ echo "Preparing ${RELEASE_TYPE} release"
 
 # publish on crates.io
 cargo publish -p cml-core
+cargo publish -p cml-derive
 cargo publish -p cml-crypto
 cargo publish -p cml-chain
+cargo publish -p cml-cip20
 cargo publish -p cml-cip25
+cargo publish -p cml-cip26
 cargo publish -p cml-cip36
+cargo publish -p cml-cip57
+cargo publish -p cml-cip68
 cargo publish -p cml-multi-era
 cargo publish -p cml-core-wasm
 cargo publish -p cml-crypto-wasm
 cargo publish -p cml-chain-wasm
+cargo publish -p cml-cip20-wasm
 cargo publish -p cml-cip25-wasm
+cargo publish -p cml-cip26-wasm
 cargo publish -p cml-cip36-wasm
+cargo publish -p cml-cip57-wasm
+cargo publish -p cml-cip68-wasm
 cargo publish -p cml-multi-era-wasm
 cargo publish -p cardano-multiplatform-lib
 
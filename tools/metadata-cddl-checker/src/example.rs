@@ -0,0 +1,256 @@
+//! Generation of example values for CDDL rules.
+//!
+//! The first type/group choice that can be generated is used. Optional and repeated entries are
+//! included once so that the example shows every field. The generated value always validates
+//! against its rule.
+
+use std::convert::TryFrom;
+
+use cddl::ast::*;
+use cml_chain::auxdata::{MetadatumMap, TransactionMetadatum};
+use cml_core::Int;
+
+use crate::schema::{
+    bytes_literal, int_literal, size_bounds, text_metadatum, EntryKey, EntryValue, FlatEntry,
+    MetadataSchema, SchemaError, MAX_RULE_DEPTH,
+};
+
+impl MetadataSchema {
+    /// An example value for the named rule
+    pub fn example(&self, rule: &str) -> Result<TransactionMetadatum, SchemaError> {
+        let types = self
+            .type_rules(rule)
+            .ok_or_else(|| SchemaError::UnknownRule(rule.to_owned()))?;
+        first_ok(
+            types
+                .iter()
+                .flat_map(|ty| ty.type_choices.iter())
+                .map(|type1| self.example_type1(type1, 0)),
+        )
+    }
+
+    fn example_type(&self, ty: &Type, depth: usize) -> Result<TransactionMetadatum, SchemaError> {
+        first_ok(
+            ty.type_choices
+                .iter()
+                .map(|type1| self.example_type1(type1, depth)),
+        )
+    }
+
+    fn example_type1(
+        &self,
+        type1: &Type1,
+        depth: usize,
+    ) -> Result<TransactionMetadatum, SchemaError> {
+        match &type1.operator {
+            None => self.example_type2(&type1.type2, depth),
+            Some((RangeCtlOp::RangeOp { .. }, _upper)) => int_literal(&type1.type2)
+                .ok_or_else(|| SchemaError::Unsupported(format!("non-int range {type1}")))
+                .and_then(int_metadatum),
+            Some((RangeCtlOp::CtlOp { ctrl, .. }, controller)) => match *ctrl {
+                ".size" => {
+                    let (min, _max) = size_bounds(controller)
+                        .ok_or_else(|| SchemaError::Unsupported(format!(".size {controller}")))?;
+                    let min = usize::try_from(min)
+                        .map_err(|_| SchemaError::Unsupported(format!(".size {controller}")))?;
+                    match self.example_type2(&type1.type2, depth)? {
+                        TransactionMetadatum::Text { .. } => text_metadatum(&"a".repeat(min)),
+                        TransactionMetadatum::Bytes { .. } => {
+                            TransactionMetadatum::new_bytes(vec![0; min]).map_err(|_| {
+                                SchemaError::NotMetadata(format!(".size {controller} too big"))
+                            })
+                        }
+                        // smallest uint needing `min` bytes
+                        TransactionMetadatum::Int(Int::Uint { .. }) if min <= 8 => {
+                            Ok(TransactionMetadatum::new_int(Int::new_uint(if min == 0 {
+                                0
+                            } else {
+                                1u64 << (8 * (min - 1))
+                            })))
+                        }
+                        _ => Err(SchemaError::Unsupported(format!("{type1}"))),
+                    }
+                }
+                ".default" => self.example_type2(&type1.type2, depth),
+                other => Err(SchemaError::Unsupported(format!(
+                    "control operator {other}"
+                ))),
+            },
+        }
+    }
+
+    fn example_type2(
+        &self,
+        type2: &Type2,
+        depth: usize,
+    ) -> Result<TransactionMetadatum, SchemaError> {
+        match type2 {
+            Type2::IntValue { .. } | Type2::UintValue { .. } => {
+                int_metadatum(int_literal(type2).unwrap())
+            }
+            Type2::TextValue { value, .. } => text_metadatum(value),
+            Type2::UTF8ByteString { .. }
+            | Type2::B16ByteString { .. }
+            | Type2::B64ByteString { .. } => TransactionMetadatum::new_bytes(bytes_literal(type2)?)
+                .map_err(|_| SchemaError::NotMetadata(format!("bytes literal too long: {type2}"))),
+            Type2::Typename {
+                ident,
+                generic_arg: None,
+                ..
+            } => self.example_typename(&ident.ident, depth),
+            Type2::ParenthesizedType { pt, .. } => self.example_type(pt, depth),
+            Type2::Map { group, .. } => first_ok(group.group_choices.iter().map(|choice| {
+                let entries = self.flatten_choice(choice, false, depth)?;
+                let mut map = MetadatumMap::new();
+                for entry in entries.iter() {
+                    if entry.max == Some(0) {
+                        continue;
+                    }
+                    let key = match &entry.key {
+                        Some(EntryKey::Literal(literal)) => literal.clone(),
+                        Some(EntryKey::Type(key_type)) => self.example_type1(key_type, depth)?,
+                        None => {
+                            return Err(SchemaError::Unsupported(format!(
+                                "map entry without key: {}",
+                                entry.value
+                            )))
+                        }
+                    };
+                    map.set(key, self.example_entry_value(&entry.value, depth)?);
+                }
+                Ok(TransactionMetadatum::new_map(map))
+            })),
+            Type2::Array { group, .. } => first_ok(group.group_choices.iter().map(|choice| {
+                let entries = self.flatten_choice(choice, false, depth)?;
+                let mut elements = Vec::new();
+                self.example_sequence(&entries, depth, &mut elements)?;
+                Ok(TransactionMetadatum::new_list(elements))
+            })),
+            Type2::Any(_) => int_metadatum(0),
+            unsupported => Err(SchemaError::Unsupported(unsupported.to_string())),
+        }
+    }
+
+    fn example_typename(
+        &self,
+        name: &str,
+        depth: usize,
+    ) -> Result<TransactionMetadatum, SchemaError> {
+        match name {
+            "any" | "int" | "uint" | "u32" | "u64" | "i32" | "i64" => int_metadatum(0),
+            "nint" => int_metadatum(-1),
+            "text" | "tstr" => text_metadatum("text"),
+            "bytes" | "bstr" => Ok(TransactionMetadatum::new_bytes(b"bytes".to_vec()).unwrap()),
+            user_defined => {
+                if depth >= MAX_RULE_DEPTH {
+                    return Err(SchemaError::Unsupported(format!(
+                        "rule {user_defined} is recursive without a base case"
+                    )));
+                }
+                let types = self
+                    .type_rules(user_defined)
+                    .ok_or_else(|| SchemaError::UnknownRule(user_defined.to_owned()))?;
+                first_ok(
+                    types
+                        .iter()
+                        .flat_map(|ty| ty.type_choices.iter())
+                        .map(|type1| self.example_type1(type1, depth + 1)),
+                )
+            }
+        }
+    }
+
+    fn example_entry_value(
+        &self,
+        value: &EntryValue,
+        depth: usize,
+    ) -> Result<TransactionMetadatum, SchemaError> {
+        match value {
+            EntryValue::Type(ty) => self.example_type(ty, depth),
+            EntryValue::Typename(ident) => self.example_typename(&ident.ident, depth),
+            EntryValue::Group(_) => Err(SchemaError::Unsupported(
+                "repeated group as a map value".to_owned(),
+            )),
+        }
+    }
+
+    fn example_sequence(
+        &self,
+        entries: &[FlatEntry],
+        depth: usize,
+        elements: &mut Vec<TransactionMetadatum>,
+    ) -> Result<(), SchemaError> {
+        for entry in entries {
+            let count = std::cmp::max(entry.min, 1);
+            let count = entry.max.map_or(count, |max| std::cmp::min(count, max));
+            for _ in 0..count {
+                match &entry.value {
+                    EntryValue::Group(group) => self.example_sequence(group, depth, elements)?,
+                    value => elements.push(self.example_entry_value(value, depth)?),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn int_metadatum(i: i128) -> Result<TransactionMetadatum, SchemaError> {
+    Int::try_from(i)
+        .map(TransactionMetadatum::new_int)
+        .map_err(|_| SchemaError::NotMetadata(format!("int out of range: {i}")))
+}
+
+/// The first success, or the first error if none succeeded
+fn first_ok<I>(results: I) -> Result<TransactionMetadatum, SchemaError>
+where
+    I: Iterator<Item = Result<TransactionMetadatum, SchemaError>>,
+{
+    let mut first_err = None;
+    for result in results {
+        match result {
+            Ok(metadatum) => return Ok(metadatum),
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_err.unwrap_or_else(|| SchemaError::Unsupported("empty choice".to_owned())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_validate() {
+        let schema = MetadataSchema::from_cddl_str(
+            r#"
+            nft = {
+                name: text,
+                ? image: [+ text .size (1..64)],
+                ? decimals: 0..18,
+                kind: "art" / "music",
+                * tstr => any,
+            }
+            hash = bytes .size 32
+            pairs = [* (uint, text)]
+            tree = uint / [* tree]
+            "#,
+        )
+        .unwrap();
+        for rule in schema.rule_names() {
+            let example = schema.example(&rule).unwrap();
+            schema
+                .validate(&rule, &example)
+                .unwrap_or_else(|e| panic!("{}: {:?}", rule, e));
+        }
+        let hash = schema.example("hash").unwrap();
+        assert_eq!(hash.as_bytes().unwrap().len(), 32);
+        let pairs = schema.example("pairs").unwrap();
+        assert_eq!(pairs.as_list().unwrap().len(), 2);
+        assert!(matches!(
+            schema.example("missing"),
+            Err(SchemaError::UnknownRule(_))
+        ));
+    }
+}
@@ -0,0 +1,12 @@
+//! Tools for defining transaction metadata with CDDL.
+//!
+//! * `verify` checks that a CDDL definition only uses metadata-compatible types
+//! * `MetadataSchema::validate` checks a `TransactionMetadatum` against a rule, reporting
+//!   every violation along with its path inside the metadatum
+//! * `MetadataSchema::example` generates an example value for a rule
+
+pub mod example;
+pub mod schema;
+pub mod verify;
+
+pub use schema::{MetadataSchema, SchemaError, ValidationError};
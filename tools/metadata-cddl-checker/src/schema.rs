@@ -0,0 +1,955 @@
+//! Validation of transaction metadata against rules of a CDDL definition.
+//!
+//! Only the subset of CDDL that maps onto metadata is supported: int/uint/nint/text/bytes,
+//! literals, ranges, `.size`/`.default` controls, maps, arrays, occurrences, type choices,
+//! group choices and references to other rules. Maps are closed i.e. any key not described by
+//! the rule is an error unless the rule contains a catch-all like `* any => any`.
+
+use std::convert::TryFrom;
+
+use cddl::ast::*;
+use cddl::token::{ByteValue, Value};
+use cml_chain::auxdata::TransactionMetadatum;
+use cml_core::Int;
+
+use crate::verify::verify_cddl;
+
+// guards against rules that only refer to each other e.g. `a = b  b = a`
+pub(crate) const MAX_RULE_DEPTH: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SchemaError {
+    #[error("CDDL parsing: {0}")]
+    Parse(String),
+    #[error("{0}")]
+    NotMetadata(String),
+    #[error("No rule named {0}")]
+    UnknownRule(String),
+    #[error("Unsupported CDDL: {0}")]
+    Unsupported(String),
+}
+
+/// A single way in which a metadatum did not match a rule
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct ValidationError {
+    /// Location in the metadatum e.g. `$.files[2].src`.
+    /// Text keys are `.key`, other keys and list indices are `[key]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+/// A parsed CDDL definition that metadata can be validated against
+#[derive(Debug)]
+pub struct MetadataSchema {
+    pub(crate) cddl: CDDL,
+}
+
+impl MetadataSchema {
+    /// Parses the CDDL and checks that every rule is representable as metadata
+    pub fn from_cddl_str(cddl: &str) -> Result<Self, SchemaError> {
+        let cddl = cddl::parser::cddl_from_str(cddl, false).map_err(SchemaError::Parse)?;
+        verify_cddl(&cddl).map_err(SchemaError::NotMetadata)?;
+        Ok(Self { cddl })
+    }
+
+    pub fn rule_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for rule in self.cddl.rules.iter() {
+            let name = rule.name();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Validates the metadatum against the named rule.
+    /// Every violation found is returned, not just the first one.
+    pub fn validate(
+        &self,
+        rule: &str,
+        metadatum: &TransactionMetadatum,
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        match self.type_rules(rule) {
+            Some(types) => self.validate_choices(&types, metadatum, "$", 0, &mut errors),
+            None => errors.push(ValidationError::new(
+                "$",
+                SchemaError::UnknownRule(rule.to_owned()).to_string(),
+            )),
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// All the types of a type rule including any `/=` alternatives
+    pub(crate) fn type_rules(&self, name: &str) -> Option<Vec<&Type>> {
+        let types = self
+            .cddl
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::Type { rule, .. } if rule.name.ident == name => Some(&rule.value),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if types.is_empty() {
+            None
+        } else {
+            Some(types)
+        }
+    }
+
+    /// The group of a group rule. `/=` alternatives are not supported for groups.
+    pub(crate) fn group_rule(&self, name: &str) -> Option<&Group> {
+        self.cddl.rules.iter().find_map(|rule| match rule {
+            Rule::Group { rule, .. } if rule.name.ident == name => match &rule.entry {
+                GroupEntry::InlineGroup { group, .. } => Some(group),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    fn validate_choices(
+        &self,
+        types: &[&Type],
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let type1s = types
+            .iter()
+            .flat_map(|ty| ty.type_choices.iter())
+            .collect::<Vec<_>>();
+        self.validate_type1_choices(&type1s, metadatum, path, depth, errors)
+    }
+
+    fn validate_type(
+        &self,
+        ty: &Type,
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let type1s = ty.type_choices.iter().collect::<Vec<_>>();
+        self.validate_type1_choices(&type1s, metadatum, path, depth, errors)
+    }
+
+    fn validate_type1_choices(
+        &self,
+        type1s: &[&Type1],
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        // report the errors of the choice that got the furthest (fewest errors)
+        let mut best: Option<Vec<ValidationError>> = None;
+        for type1 in type1s {
+            let mut choice_errors = Vec::new();
+            self.validate_type1(type1, metadatum, path, depth, &mut choice_errors);
+            if choice_errors.is_empty() {
+                return;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|best| choice_errors.len() < best.len())
+            {
+                best = Some(choice_errors);
+            }
+        }
+        if type1s.len() > 1 {
+            if let Some(best) = &best {
+                // a choice of only literals/scalars is better reported as a single error
+                if best.len() == 1 && best[0].path == path {
+                    errors.push(ValidationError::new(
+                        path,
+                        format!(
+                            "expected one of {}",
+                            type1s
+                                .iter()
+                                .map(|t| t.to_string())
+                                .collect::<Vec<_>>()
+                                .join(" / ")
+                        ),
+                    ));
+                    return;
+                }
+            }
+        }
+        errors.extend(best.unwrap_or_default());
+    }
+
+    fn validate_type1(
+        &self,
+        type1: &Type1,
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        match &type1.operator {
+            None => self.validate_type2(&type1.type2, metadatum, path, depth, errors),
+            Some((RangeCtlOp::RangeOp { is_inclusive, .. }, upper)) => {
+                let (lower, upper) = match (int_literal(&type1.type2), int_literal(upper)) {
+                    (Some(lower), Some(upper)) => (lower, upper),
+                    _ => {
+                        errors.push(ValidationError::new(
+                            path,
+                            SchemaError::Unsupported(format!("non-int range {type1}")).to_string(),
+                        ));
+                        return;
+                    }
+                };
+                match metadatum_int(metadatum) {
+                    Some(value)
+                        if value >= lower
+                            && (value < upper || (*is_inclusive && value == upper)) => {}
+                    Some(value) => errors.push(ValidationError::new(
+                        path,
+                        format!("{value} is not in range {type1}"),
+                    )),
+                    None => errors.push(ValidationError::new(
+                        path,
+                        format!("expected int in range {type1}, found {}", kind(metadatum)),
+                    )),
+                }
+            }
+            Some((RangeCtlOp::CtlOp { ctrl, .. }, controller)) => match *ctrl {
+                ".size" => {
+                    let before = errors.len();
+                    self.validate_type2(&type1.type2, metadatum, path, depth, errors);
+                    if errors.len() == before {
+                        validate_size(controller, metadatum, path, errors);
+                    }
+                }
+                ".default" => self.validate_type2(&type1.type2, metadatum, path, depth, errors),
+                other => errors.push(ValidationError::new(
+                    path,
+                    SchemaError::Unsupported(format!("control operator {other}")).to_string(),
+                )),
+            },
+        }
+    }
+
+    fn validate_type2(
+        &self,
+        type2: &Type2,
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mismatch = |expected: &dyn std::fmt::Display| {
+            ValidationError::new(
+                path,
+                format!("expected {expected}, found {}", describe(metadatum)),
+            )
+        };
+        match type2 {
+            Type2::IntValue { .. } | Type2::UintValue { .. } => {
+                if metadatum_int(metadatum) != int_literal(type2) {
+                    errors.push(mismatch(type2));
+                }
+            }
+            Type2::TextValue { value, .. } => match metadatum {
+                TransactionMetadatum::Text { text, .. } if text == value => {}
+                _ => errors.push(mismatch(type2)),
+            },
+            Type2::UTF8ByteString { .. }
+            | Type2::B16ByteString { .. }
+            | Type2::B64ByteString { .. } => match (bytes_literal(type2), metadatum) {
+                (Ok(expected), TransactionMetadatum::Bytes { bytes, .. }) if *bytes == expected => {
+                }
+                (Err(e), _) => errors.push(ValidationError::new(path, e.to_string())),
+                _ => errors.push(mismatch(type2)),
+            },
+            Type2::Typename {
+                ident, generic_arg, ..
+            } => {
+                if generic_arg.is_some() {
+                    errors.push(ValidationError::new(
+                        path,
+                        SchemaError::Unsupported(format!("generic {type2}")).to_string(),
+                    ));
+                    return;
+                }
+                self.validate_typename(&ident.ident, metadatum, path, depth, errors)
+            }
+            Type2::ParenthesizedType { pt, .. } => {
+                self.validate_type(pt, metadatum, path, depth, errors)
+            }
+            Type2::Map { group, .. } => match metadatum {
+                TransactionMetadatum::Map(map) => {
+                    self.validate_group_choices(group, path, depth, errors, |entries, errors| {
+                        self.validate_map(entries, &map.entries, path, depth, errors)
+                    })
+                }
+                _ => errors.push(mismatch(&"map")),
+            },
+            Type2::Array { group, .. } => match metadatum {
+                TransactionMetadatum::List { elements, .. } => {
+                    self.validate_group_choices(group, path, depth, errors, |entries, errors| {
+                        self.validate_array(entries, elements, path, depth, errors)
+                    })
+                }
+                _ => errors.push(mismatch(&"array")),
+            },
+            Type2::Any(_) => {}
+            unsupported => errors.push(ValidationError::new(
+                path,
+                SchemaError::Unsupported(unsupported.to_string()).to_string(),
+            )),
+        }
+    }
+
+    fn validate_typename(
+        &self,
+        name: &str,
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let int = metadatum_int(metadatum);
+        let in_range = |min: i128, max: i128| int.is_some_and(|i| i >= min && i <= max);
+        let valid = match name {
+            "any" => true,
+            "int" => int.is_some(),
+            "uint" => in_range(0, u64::MAX as i128),
+            "nint" => in_range(-(u64::MAX as i128) - 1, -1),
+            "u32" => in_range(0, u32::MAX as i128),
+            "u64" => in_range(0, u64::MAX as i128),
+            "i32" => in_range(i32::MIN as i128, i32::MAX as i128),
+            "i64" => in_range(i64::MIN as i128, i64::MAX as i128),
+            "text" | "tstr" => matches!(metadatum, TransactionMetadatum::Text { .. }),
+            "bytes" | "bstr" => matches!(metadatum, TransactionMetadatum::Bytes { .. }),
+            user_defined => {
+                if depth >= MAX_RULE_DEPTH {
+                    errors.push(ValidationError::new(
+                        path,
+                        format!("rule {user_defined} is too deeply nested"),
+                    ));
+                    return;
+                }
+                match self.type_rules(user_defined) {
+                    Some(types) => {
+                        self.validate_choices(&types, metadatum, path, depth + 1, errors)
+                    }
+                    None => errors.push(ValidationError::new(
+                        path,
+                        SchemaError::UnknownRule(user_defined.to_owned()).to_string(),
+                    )),
+                }
+                return;
+            }
+        };
+        if !valid {
+            errors.push(ValidationError::new(
+                path,
+                format!("expected {name}, found {}", describe(metadatum)),
+            ));
+        }
+    }
+
+    /// Runs `validate` on each group choice, passing if any does
+    fn validate_group_choices<F>(
+        &self,
+        group: &Group,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+        validate: F,
+    ) where
+        F: Fn(&[FlatEntry], &mut Vec<ValidationError>),
+    {
+        let mut best: Option<Vec<ValidationError>> = None;
+        for choice in group.group_choices.iter() {
+            let mut choice_errors = Vec::new();
+            match self.flatten_choice(choice, false, depth) {
+                Ok(entries) => validate(&entries, &mut choice_errors),
+                Err(e) => choice_errors.push(ValidationError::new(path, e.to_string())),
+            }
+            if choice_errors.is_empty() {
+                return;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|best| choice_errors.len() < best.len())
+            {
+                best = Some(choice_errors);
+            }
+        }
+        errors.extend(best.unwrap_or_default());
+    }
+
+    fn validate_map(
+        &self,
+        entries: &[FlatEntry],
+        map: &[(TransactionMetadatum, TransactionMetadatum)],
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mut consumed = vec![false; map.len()];
+        // literal keys first so that type keys (e.g. `* tstr => any`) only get the rest
+        let literal_entries = entries
+            .iter()
+            .filter(|entry| matches!(entry.key, Some(EntryKey::Literal(_))));
+        let type_entries = entries
+            .iter()
+            .filter(|entry| !matches!(entry.key, Some(EntryKey::Literal(_))));
+        for entry in literal_entries.chain(type_entries) {
+            let mut count = 0;
+            for (i, (key, value)) in map.iter().enumerate() {
+                if consumed[i] {
+                    continue;
+                }
+                let key_matches = match &entry.key {
+                    Some(EntryKey::Literal(literal)) => key == literal,
+                    Some(EntryKey::Type(key_type)) => {
+                        let mut key_errors = Vec::new();
+                        self.validate_type1(key_type, key, path, depth, &mut key_errors);
+                        key_errors.is_empty()
+                    }
+                    None => false,
+                };
+                if !key_matches {
+                    continue;
+                }
+                consumed[i] = true;
+                count += 1;
+                self.validate_entry_value(&entry.value, value, &key_path(path, key), depth, errors);
+            }
+            match &entry.key {
+                None => errors.push(ValidationError::new(
+                    path,
+                    SchemaError::Unsupported(format!("map entry without key: {}", entry.value))
+                        .to_string(),
+                )),
+                Some(key) => {
+                    if count < entry.min {
+                        errors.push(match key {
+                            EntryKey::Literal(literal) => ValidationError::new(
+                                path,
+                                format!("missing required key {}", describe(literal)),
+                            ),
+                            EntryKey::Type(key_type) => ValidationError::new(
+                                path,
+                                format!(
+                                    "expected at least {} keys of type {key_type}, found {count}",
+                                    entry.min
+                                ),
+                            ),
+                        });
+                    }
+                    if let Some(max) = entry.max {
+                        if count > max {
+                            errors.push(ValidationError::new(
+                                path,
+                                format!("expected at most {max} entries for {key}, found {count}"),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        for (i, (key, _value)) in map.iter().enumerate() {
+            if !consumed[i] {
+                errors.push(ValidationError::new(&key_path(path, key), "unexpected key"));
+            }
+        }
+    }
+
+    fn validate_array(
+        &self,
+        entries: &[FlatEntry],
+        elements: &[TransactionMetadatum],
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let end = self.match_sequence(entries, elements, 0, path, depth, errors);
+        if end < elements.len() {
+            errors.push(ValidationError::new(
+                &index_path(path, end),
+                format!("unexpected element(s): expected at most {end} elements"),
+            ));
+        }
+    }
+
+    /// Greedily matches the entries in order starting at `start`, returning where matching ended
+    fn match_sequence(
+        &self,
+        entries: &[FlatEntry],
+        elements: &[TransactionMetadatum],
+        start: usize,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) -> usize {
+        let mut index = start;
+        for entry in entries {
+            let mut count = 0;
+            while entry.max.is_none_or(|max| count < max) && index < elements.len() {
+                let mut element_errors = Vec::new();
+                let next = match &entry.value {
+                    EntryValue::Group(group) => self.match_sequence(
+                        group,
+                        elements,
+                        index,
+                        path,
+                        depth,
+                        &mut element_errors,
+                    ),
+                    value => {
+                        self.validate_entry_value(
+                            value,
+                            &elements[index],
+                            &index_path(path, index),
+                            depth,
+                            &mut element_errors,
+                        );
+                        index + 1
+                    }
+                };
+                if !element_errors.is_empty() || next == index {
+                    break;
+                }
+                index = next;
+                count += 1;
+            }
+            if count < entry.min {
+                match &entry.value {
+                    EntryValue::Group(_) => errors.push(ValidationError::new(
+                        &index_path(path, index),
+                        format!("expected at least {} of group, found {count}", entry.min),
+                    )),
+                    value if index < elements.len() => {
+                        // report why the element didn't match
+                        self.validate_entry_value(
+                            value,
+                            &elements[index],
+                            &index_path(path, index),
+                            depth,
+                            errors,
+                        );
+                        index += 1;
+                    }
+                    _ => errors.push(ValidationError::new(
+                        &index_path(path, index),
+                        format!(
+                            "expected at least {} of {}, found {count}",
+                            entry.min, entry.value
+                        ),
+                    )),
+                }
+            }
+        }
+        index
+    }
+
+    fn validate_entry_value(
+        &self,
+        value: &EntryValue,
+        metadatum: &TransactionMetadatum,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        match value {
+            EntryValue::Type(ty) => self.validate_type(ty, metadatum, path, depth, errors),
+            EntryValue::Typename(ident) => {
+                self.validate_typename(&ident.ident, metadatum, path, depth, errors)
+            }
+            EntryValue::Group(_) => errors.push(ValidationError::new(
+                path,
+                SchemaError::Unsupported("repeated group as a map value".to_owned()).to_string(),
+            )),
+        }
+    }
+
+    /// Flattens a group choice into a list of entries, inlining referenced groups.
+    /// `optional` makes every entry optional (used for optional groups inside maps).
+    pub(crate) fn flatten_choice<'a>(
+        &'a self,
+        choice: &'a GroupChoice,
+        optional: bool,
+        depth: usize,
+    ) -> Result<Vec<FlatEntry<'a>>, SchemaError> {
+        if depth >= MAX_RULE_DEPTH {
+            return Err(SchemaError::Unsupported(
+                "group rules too deeply nested".to_owned(),
+            ));
+        }
+        let mut entries = Vec::new();
+        for (entry, _comma) in choice.group_entries.iter() {
+            let (group, occur) = match entry {
+                GroupEntry::ValueMemberKey { ge, .. } => {
+                    let (min, max) = occurrence(&ge.occur);
+                    let key = match &ge.member_key {
+                        None => None,
+                        Some(MemberKey::Bareword { ident, .. }) => {
+                            Some(EntryKey::Literal(text_metadatum(&ident.ident)?))
+                        }
+                        Some(MemberKey::Value { value, .. }) => {
+                            Some(EntryKey::Literal(value_metadatum(value)?))
+                        }
+                        Some(MemberKey::Type1 { t1, .. }) => Some(EntryKey::Type(t1)),
+                        Some(MemberKey::NonMemberKey(_)) => {
+                            return Err(SchemaError::Unsupported(format!(
+                                "non-member key in {entry}"
+                            )))
+                        }
+                    };
+                    entries.push(FlatEntry {
+                        key,
+                        value: EntryValue::Type(&ge.entry_type),
+                        min: if optional { 0 } else { min },
+                        max,
+                    });
+                    continue;
+                }
+                GroupEntry::TypeGroupname { ge, .. } => match self.group_rule(&ge.name.ident) {
+                    Some(group) => (group, &ge.occur),
+                    None => {
+                        // a type used as an (array) entry e.g. `[* my_type]`
+                        let (min, max) = occurrence(&ge.occur);
+                        entries.push(FlatEntry {
+                            key: None,
+                            value: EntryValue::Typename(&ge.name),
+                            min: if optional { 0 } else { min },
+                            max,
+                        });
+                        continue;
+                    }
+                },
+                GroupEntry::InlineGroup { group, occur, .. } => (group, occur),
+            };
+            if group.group_choices.len() != 1 {
+                return Err(SchemaError::Unsupported(format!(
+                    "group choices in nested group {group}"
+                )));
+            }
+            let (min, max) = occurrence(occur);
+            let inner =
+                self.flatten_choice(&group.group_choices[0], optional || min == 0, depth + 1)?;
+            if (min, max) == (1, Some(1)) || (min, max) == (0, Some(1)) {
+                entries.extend(inner);
+            } else {
+                entries.push(FlatEntry {
+                    key: None,
+                    value: EntryValue::Group(inner),
+                    min: if optional { 0 } else { min },
+                    max,
+                });
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// A group entry after inlining any referenced groups
+#[derive(Debug)]
+pub(crate) struct FlatEntry<'a> {
+    pub key: Option<EntryKey<'a>>,
+    pub value: EntryValue<'a>,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+#[derive(Debug)]
+pub(crate) enum EntryKey<'a> {
+    Literal(TransactionMetadatum),
+    Type(&'a Type1),
+}
+
+impl std::fmt::Display for EntryKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Literal(literal) => write!(f, "{}", describe(literal)),
+            Self::Type(key_type) => write!(f, "{key_type}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum EntryValue<'a> {
+    Type(&'a Type),
+    Typename(&'a Identifier),
+    /// A repeated group inside an array e.g. `[* (uint, text)]`
+    Group(Vec<FlatEntry<'a>>),
+}
+
+impl std::fmt::Display for EntryValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Type(ty) => write!(f, "{ty}"),
+            Self::Typename(ident) => write!(f, "{ident}"),
+            Self::Group(_) => write!(f, "group"),
+        }
+    }
+}
+
+pub(crate) fn occurrence(occur: &Option<Occur>) -> (usize, Option<usize>) {
+    match occur {
+        None => (1, Some(1)),
+        Some(Occur::Optional(_)) => (0, Some(1)),
+        Some(Occur::ZeroOrMore(_)) => (0, None),
+        Some(Occur::OneOrMore(_)) => (1, None),
+        Some(Occur::Exact { lower, upper, .. }) => (lower.unwrap_or(0), *upper),
+    }
+}
+
+pub(crate) fn int_literal(type2: &Type2) -> Option<i128> {
+    match type2 {
+        Type2::IntValue { value, .. } => Some(*value as i128),
+        Type2::UintValue { value, .. } => Some(*value as i128),
+        _ => None,
+    }
+}
+
+pub(crate) fn bytes_literal(type2: &Type2) -> Result<Vec<u8>, SchemaError> {
+    match type2 {
+        Type2::UTF8ByteString { value, .. } => Ok(value.clone()),
+        // the parser keeps the hex text
+        Type2::B16ByteString { value, .. } => hex::decode(value)
+            .map_err(|e| SchemaError::Unsupported(format!("invalid hex literal: {e}"))),
+        _ => Err(SchemaError::Unsupported(format!("byte literal {type2}"))),
+    }
+}
+
+pub(crate) fn text_metadatum(text: &str) -> Result<TransactionMetadatum, SchemaError> {
+    TransactionMetadatum::new_text(text.to_owned())
+        .map_err(|_| SchemaError::NotMetadata(format!("text literal too long: {text}")))
+}
+
+pub(crate) fn value_metadatum(value: &Value) -> Result<TransactionMetadatum, SchemaError> {
+    let int = |i: i128| {
+        Int::try_from(i)
+            .map(TransactionMetadatum::new_int)
+            .map_err(|_| SchemaError::NotMetadata(format!("int literal out of range: {i}")))
+    };
+    match value {
+        Value::INT(i) => int(*i as i128),
+        Value::UINT(u) => int(*u as i128),
+        Value::TEXT(text) => text_metadatum(text),
+        Value::BYTE(ByteValue::UTF8(bytes)) => TransactionMetadatum::new_bytes(bytes.clone())
+            .map_err(|_| SchemaError::NotMetadata("bytes literal too long".to_owned())),
+        Value::BYTE(ByteValue::B16(hex_text)) => hex::decode(hex_text)
+            .ok()
+            .and_then(|bytes| TransactionMetadatum::new_bytes(bytes).ok())
+            .ok_or_else(|| SchemaError::NotMetadata(format!("invalid bytes literal {value}"))),
+        other => Err(SchemaError::Unsupported(format!("key {other}"))),
+    }
+}
+
+fn validate_size(
+    controller: &Type2,
+    metadatum: &TransactionMetadatum,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let (min, max) = match size_bounds(controller) {
+        Some(bounds) => bounds,
+        None => {
+            errors.push(ValidationError::new(
+                path,
+                SchemaError::Unsupported(format!(".size {controller}")).to_string(),
+            ));
+            return;
+        }
+    };
+    let size = match metadatum {
+        TransactionMetadatum::Text { text, .. } => text.len() as i128,
+        TransactionMetadatum::Bytes { bytes, .. } => bytes.len() as i128,
+        // for uints .size is the number of bytes needed to represent it
+        TransactionMetadatum::Int(Int::Uint { value, .. }) => {
+            ((128 - (*value as u128).leading_zeros() as i128) + 7) / 8
+        }
+        _ => {
+            errors.push(ValidationError::new(
+                path,
+                format!(".size not applicable to {}", kind(metadatum)),
+            ));
+            return;
+        }
+    };
+    if size < min || size > max {
+        errors.push(ValidationError::new(
+            path,
+            format!("size {size} not within .size {controller}"),
+        ));
+    }
+}
+
+/// Inclusive (min, max) of a `.size` controller: either `n` or a range `(a..b)`
+pub(crate) fn size_bounds(controller: &Type2) -> Option<(i128, i128)> {
+    match controller {
+        Type2::UintValue { value, .. } => Some((*value as i128, *value as i128)),
+        Type2::ParenthesizedType { pt, .. } if pt.type_choices.len() == 1 => {
+            let range = &pt.type_choices[0];
+            match &range.operator {
+                Some((RangeCtlOp::RangeOp { is_inclusive, .. }, upper)) => {
+                    let lower = int_literal(&range.type2)?;
+                    let upper = int_literal(upper)?;
+                    Some((lower, if *is_inclusive { upper } else { upper - 1 }))
+                }
+                None => int_literal(&range.type2).map(|size| (size, size)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn metadatum_int(metadatum: &TransactionMetadatum) -> Option<i128> {
+    match metadatum {
+        TransactionMetadatum::Int(Int::Uint { value, .. }) => Some(*value as i128),
+        TransactionMetadatum::Int(Int::Nint { value, .. }) => Some(-(*value as i128) - 1),
+        _ => None,
+    }
+}
+
+fn kind(metadatum: &TransactionMetadatum) -> &'static str {
+    match metadatum {
+        TransactionMetadatum::Map(_) => "map",
+        TransactionMetadatum::List { .. } => "array",
+        TransactionMetadatum::Int(_) => "int",
+        TransactionMetadatum::Bytes { .. } => "bytes",
+        TransactionMetadatum::Text { .. } => "text",
+    }
+}
+
+/// Scalars are shown with their value, containers just by their kind
+fn describe(metadatum: &TransactionMetadatum) -> String {
+    match metadatum {
+        TransactionMetadatum::Int(int) => int.to_string(),
+        TransactionMetadatum::Bytes { bytes, .. } => format!("h'{}'", hex::encode(bytes)),
+        TransactionMetadatum::Text { text, .. } => format!("\"{text}\""),
+        other => kind(other).to_owned(),
+    }
+}
+
+fn key_path(path: &str, key: &TransactionMetadatum) -> String {
+    match key {
+        TransactionMetadatum::Text { text, .. } => format!("{path}.{text}"),
+        other => format!("{path}[{}]", describe(other)),
+    }
+}
+
+fn index_path(path: &str, index: usize) -> String {
+    format!("{path}[{index}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cml_chain::auxdata::MetadatumMap;
+
+    const CDDL: &str = r#"
+        message = {
+            msg: [+ text .size (0..64)],
+            ? enc: "basic" / "none",
+            ? priority: 0..10,
+        }
+        point = [x: int, y: int, ? label: text]
+        points = { * uint => point }
+    "#;
+
+    fn text(s: &str) -> TransactionMetadatum {
+        TransactionMetadatum::new_text(s.to_owned()).unwrap()
+    }
+
+    fn uint(u: u64) -> TransactionMetadatum {
+        TransactionMetadatum::new_int(Int::new_uint(u))
+    }
+
+    fn map(entries: Vec<(TransactionMetadatum, TransactionMetadatum)>) -> TransactionMetadatum {
+        let mut map = MetadatumMap::new();
+        for (k, v) in entries {
+            map.set(k, v);
+        }
+        TransactionMetadatum::new_map(map)
+    }
+
+    fn paths(errors: Vec<ValidationError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn valid_metadata() {
+        let schema = MetadataSchema::from_cddl_str(CDDL).unwrap();
+        let message = map(vec![
+            (
+                text("msg"),
+                TransactionMetadatum::new_list(vec![text("hello")]),
+            ),
+            (text("enc"), text("basic")),
+        ]);
+        schema.validate("message", &message).unwrap();
+        let points = map(vec![
+            (
+                uint(0),
+                TransactionMetadatum::new_list(vec![uint(1), uint(2)]),
+            ),
+            (
+                uint(1),
+                TransactionMetadatum::new_list(vec![uint(3), uint(4), text("four")]),
+            ),
+        ]);
+        schema.validate("points", &points).unwrap();
+    }
+
+    #[test]
+    fn path_aware_errors() {
+        let schema = MetadataSchema::from_cddl_str(CDDL).unwrap();
+        let message = map(vec![
+            (
+                text("msg"),
+                TransactionMetadatum::new_list(vec![text("ok"), uint(1)]),
+            ),
+            (text("enc"), text("aes")),
+            (text("priority"), uint(11)),
+            (text("extra"), uint(0)),
+        ]);
+        let errors = schema.validate("message", &message).unwrap_err();
+        assert_eq!(
+            paths(errors),
+            vec!["$.msg[1]", "$.enc", "$.priority", "$.extra"]
+        );
+        let errors = schema.validate("message", &map(vec![])).unwrap_err();
+        assert_eq!(errors[0].message, "missing required key \"msg\"");
+        let points = map(vec![(
+            uint(7),
+            TransactionMetadatum::new_list(vec![uint(1), text("two")]),
+        )]);
+        let errors = schema.validate("points", &points).unwrap_err();
+        assert_eq!(paths(errors), vec!["$[7][1]"]);
+        assert!(schema.validate("nonexistent", &uint(0)).is_err());
+    }
+
+    #[test]
+    fn not_metadata() {
+        assert!(matches!(
+            MetadataSchema::from_cddl_str("foo = { a: float }"),
+            Err(SchemaError::NotMetadata(_))
+        ));
+    }
+}